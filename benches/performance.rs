@@ -209,6 +209,57 @@ fn benchmark_scan(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_hybrid_chunk_size(c: &mut Criterion) {
+    use orlando_transducers::simd::{run_f64, Hybrid, NumericOp};
+
+    let mut group = c.benchmark_group("hybrid_chunk_size");
+    let data: Vec<f64> = (0..1_000_000).map(|i| (i % 1000) as f64).collect();
+    let plan = vec![NumericOp::ScaleOffset(0.5, 1.0), NumericOp::FilterGt(100.0)];
+    let suffix = || Map::new(|x: f64| x.sqrt()).compose(Filter::new(|x: &f64| x.fract() < 0.5));
+
+    // The whole plan first, then the suffix over its full output
+    group.bench_function("unchunked", |b| {
+        b.iter(|| black_box(to_vec(&suffix(), run_f64(&plan, &data))))
+    });
+
+    for chunk in [256, 1024, 2048, 8192, 65_536] {
+        let hybrid = Hybrid::new(plan.clone()).chunk_size(chunk);
+        group.bench_with_input(BenchmarkId::new("chunked", chunk), &chunk, |b, _| {
+            b.iter(|| black_box(hybrid.to_vec(&data, &suffix())))
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_all_pass(c: &mut Criterion) {
+    use orlando_transducers::logic::{all_pass, all_pass_tuple, PredicateVec};
+
+    let mut group = c.benchmark_group("all_pass");
+    let data: Vec<i32> = (0..100_000).collect();
+
+    group.bench_function("boxed_vec", |b| {
+        let predicates: PredicateVec<i32> = vec![
+            Box::new(|x: &i32| *x > 10),
+            Box::new(|x: &i32| x % 2 == 0),
+            Box::new(|x: &i32| *x < 90_000),
+        ];
+        let pipeline = Filter::new(all_pass(predicates));
+        b.iter(|| black_box(to_vec(&pipeline, data.iter().copied())))
+    });
+
+    group.bench_function("tuple", |b| {
+        let pipeline = Filter::new(all_pass_tuple((
+            |x: &i32| *x > 10,
+            |x: &i32| x % 2 == 0,
+            |x: &i32| *x < 90_000,
+        )));
+        b.iter(|| black_box(to_vec(&pipeline, data.iter().copied())))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_map_filter_take,
@@ -217,6 +268,8 @@ criterion_group!(
     benchmark_sum,
     benchmark_unique,
     benchmark_scan,
+    benchmark_hybrid_chunk_size,
+    benchmark_all_pass,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,141 @@
+/**
+ * Orlando Transducers - Operation Fusion Benchmark
+ *
+ * Runs each pipeline as built and after `freeze()`, which applies operation
+ * fusion once up front. The "Ops/element" column is the number of pipeline
+ * operations each element is dispatched through before and after fusion;
+ * the timings show what that reduction buys.
+ */
+
+const { Bench } = require('tinybench');
+const chalk = require('chalk');
+const Table = require('cli-table3');
+
+// Import Orlando (built with wasm-pack --target nodejs)
+const { Pipeline } = require('../pkg/orlando_transducers.js');
+
+// Benchmark configuration
+const QUICK_MODE = process.argv.includes('--quick');
+const ITERATIONS = QUICK_MODE ? 100 : 1000;
+const WARMUP_ITERATIONS = QUICK_MODE ? 10 : 100;
+
+function generateNumbers(size) {
+    return Array.from({ length: size }, (_, i) => i + 1);
+}
+
+function generateObjects(size) {
+    return Array.from({ length: size }, (_, i) => ({
+        id: i,
+        user: { name: `User ${i}`, active: i % 3 !== 0 },
+        score: i % 100
+    }));
+}
+
+// Each scenario lists the operation count before and after fusion
+const scenarios = [
+    {
+        name: 'Map → Map → Map (100K items)',
+        setup: () => generateNumbers(100000),
+        build: () => new Pipeline()
+            .map(x => x + 1)
+            .map(x => x * 2)
+            .map(x => x - 3),
+        ops: [3, 1]
+    },
+    {
+        name: 'Filter → Filter → Map → Filter (100K items)',
+        setup: () => generateNumbers(100000),
+        build: () => new Pipeline()
+            .filter(x => x % 2 === 0)
+            .filter(x => x % 3 === 0)
+            .map(x => x / 6)
+            .reject(x => x % 5 === 0),
+        // `filter` already fuses with the operation before it when building
+        ops: [3, 1]
+    },
+    {
+        name: 'Records: pluck → where → pluck (100K objects)',
+        setup: () => generateObjects(100000),
+        build: () => new Pipeline()
+            .pluck('user')
+            .where({ active: true })
+            .pluck('name'),
+        ops: [3, 1]
+    },
+    {
+        name: 'Drop → Drop → Map → Take → Take (100K items)',
+        setup: () => generateNumbers(100000),
+        build: () => new Pipeline()
+            .drop(10)
+            .drop(20)
+            .map(x => x * 2)
+            .take(5000)
+            .take(1000),
+        ops: [5, 3]
+    }
+];
+
+function formatNumber(num) {
+    return Math.round(num).toLocaleString();
+}
+
+async function runScenario(scenario) {
+    console.log(chalk.bold.cyan(`\n${'='.repeat(80)}`));
+    console.log(chalk.bold.cyan(`  ${scenario.name}`));
+    console.log(chalk.bold.cyan(`${'='.repeat(80)}\n`));
+
+    const bench = new Bench({
+        iterations: ITERATIONS,
+        warmupIterations: WARMUP_ITERATIONS
+    });
+
+    const data = scenario.setup();
+    const pipeline = scenario.build();
+    const frozen = pipeline.freeze();
+
+    bench.add('As built', () => pipeline.toArray(data));
+    bench.add('Frozen', () => frozen.toArray(data));
+    await bench.run();
+
+    const [built, fused] = bench.tasks;
+    const table = new Table({
+        head: [
+            chalk.bold('Pipeline'),
+            chalk.bold('Ops/element'),
+            chalk.bold('Ops/sec'),
+            chalk.bold('Avg Time')
+        ],
+        colWidths: [16, 15, 15, 15]
+    });
+
+    [built, fused].forEach((task, i) => {
+        table.push([
+            task.name,
+            scenario.ops[i],
+            formatNumber(task.result.hz),
+            `${(task.result.mean * 1000).toFixed(2)}ms`
+        ]);
+    });
+
+    console.log(table.toString());
+
+    const speedup = built.result.mean / fused.result.mean;
+    const style = speedup >= 1 ? chalk.green : chalk.yellow;
+    console.log(style(`\n  Frozen pipeline: ${speedup.toFixed(2)}x`));
+}
+
+async function main() {
+    console.log(chalk.bold.blue('\n  Orlando Transducers - Operation Fusion Benchmark\n'));
+    console.log(chalk.gray(`  Mode: ${QUICK_MODE ? 'Quick' : 'Full'}`));
+    console.log(chalk.gray(`  Iterations: ${ITERATIONS}`));
+
+    for (const scenario of scenarios) {
+        await runScenario(scenario);
+    }
+    console.log();
+}
+
+main().catch(err => {
+    console.error(chalk.red('Error running benchmarks:'), err);
+    process.exit(1);
+});
@@ -0,0 +1,38 @@
+// Worker shim for `Pipeline.runInWorker`.
+//
+// Receives `{ id, plan, source }`, rebuilds the pipeline with
+// `Pipeline.fromPlan`, runs it and posts back `{ id, result }` or
+// `{ id, error }`. Typed array sources produce a `Float64Array` whose buffer
+// is transferred back to the caller.
+//
+// The publish workflow copies this file next to the generated bindings in
+// `pkg/`, so it works with both the `web` and `bundler` wasm-pack targets.
+//
+//   const worker = new Worker(
+//     new URL('orlando-transducers/orlando-worker.js', import.meta.url),
+//     { type: 'module' },
+//   );
+//   const result = await pipeline.runInWorker(new Float64Array(data), worker);
+
+import * as orlando from './orlando_transducers.js';
+
+// The `web` target needs explicit initialization; `bundler` does not
+const ready = typeof orlando.default === 'function' ? orlando.default() : Promise.resolve();
+const { Pipeline } = orlando;
+
+self.addEventListener('message', async ({ data }) => {
+  const { id, plan, source } = data;
+  try {
+    await ready;
+    const pipeline = Pipeline.fromPlan(plan);
+
+    if (ArrayBuffer.isView(source) && !(source instanceof DataView)) {
+      const result = pipeline.toFloat64Array(source);
+      self.postMessage({ id, result }, [result.buffer]);
+    } else {
+      self.postMessage({ id, result: pipeline.toArray(source) });
+    }
+  } catch (error) {
+    self.postMessage({ id, error: error instanceof Error ? error : String(error) });
+  }
+});
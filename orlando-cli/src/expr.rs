@@ -0,0 +1,661 @@
+//! The expression language of `map` and `filter` steps.
+//!
+//! A small, jq-flavoured language over JSON values:
+//!
+//! - `.` is the input, `.a.b` and `.items[0]` read from it (a missing key or
+//!   index reads as `null`, and negative indexes count from the end)
+//! - literals: numbers, `"strings"`, `true`, `false`, `null`, arrays
+//!   `[.a, 1]` and objects `{name: .user.name, "total": .a + .b}`, where
+//!   `{name}` is short for `{name: .name}`
+//! - arithmetic `+ - * / %` on numbers; `+` also joins strings, arrays and
+//!   objects, and `null + x` is `x`
+//! - comparisons `== != < <= > >=`, and `and`, `or`, `not`; like jq, only
+//!   `false` and `null` are falsy
+//!
+//! Arithmetic that doesn't apply to its operands (dividing a string, or by
+//! zero) gives `null` rather than failing the run.
+
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A parsed expression, evaluated against each input value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Input,
+    Literal(Value),
+    Field(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Array(Vec<Expr>),
+    Object(Vec<(String, Expr)>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Binary(Box<Expr>, BinOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A syntax error, at a byte offset into the text being parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.offset + 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a complete expression.
+pub fn parse(text: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser::new(text)?;
+    let expr = parser.expr()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(parser.error_at(token, "expected the end of the expression")),
+    }
+}
+
+impl Expr {
+    /// Evaluate against `input`.
+    pub fn eval(&self, input: &Value) -> Value {
+        match self {
+            Expr::Input => input.clone(),
+            Expr::Literal(value) => value.clone(),
+            Expr::Field(target, key) => match target.eval(input) {
+                Value::Object(mut map) => map.remove(key).unwrap_or(Value::Null),
+                _ => Value::Null,
+            },
+            Expr::Index(target, index) => index_value(target.eval(input), &index.eval(input)),
+            Expr::Array(items) => Value::Array(items.iter().map(|e| e.eval(input)).collect()),
+            Expr::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, e)| (key.clone(), e.eval(input)))
+                    .collect(),
+            ),
+            Expr::Not(e) => Value::Bool(!truthy(&e.eval(input))),
+            Expr::Neg(e) => arithmetic(BinOp::Sub, &Value::from(0), &e.eval(input)),
+            Expr::And(a, b) => Value::Bool(truthy(&a.eval(input)) && truthy(&b.eval(input))),
+            Expr::Or(a, b) => Value::Bool(truthy(&a.eval(input)) || truthy(&b.eval(input))),
+            Expr::Binary(a, op, b) => {
+                let (a, b) = (a.eval(input), b.eval(input));
+                match op {
+                    BinOp::Eq => Value::Bool(equal(&a, &b)),
+                    BinOp::Ne => Value::Bool(!equal(&a, &b)),
+                    BinOp::Lt => Value::Bool(compare(&a, &b) == Some(Ordering::Less)),
+                    BinOp::Le => Value::Bool(matches!(
+                        compare(&a, &b),
+                        Some(Ordering::Less | Ordering::Equal)
+                    )),
+                    BinOp::Gt => Value::Bool(compare(&a, &b) == Some(Ordering::Greater)),
+                    BinOp::Ge => Value::Bool(matches!(
+                        compare(&a, &b),
+                        Some(Ordering::Greater | Ordering::Equal)
+                    )),
+                    _ => arithmetic(*op, &a, &b),
+                }
+            }
+        }
+    }
+
+    /// Evaluate as a filter predicate.
+    pub fn test(&self, input: &Value) -> bool {
+        truthy(&self.eval(input))
+    }
+}
+
+/// Only `false` and `null` are falsy, as in jq.
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn index_value(target: Value, index: &Value) -> Value {
+    match (target, index) {
+        (Value::Object(mut map), Value::String(key)) => map.remove(key).unwrap_or(Value::Null),
+        (Value::Array(mut items), Value::Number(n)) => {
+            let Some(i) = n.as_i64() else {
+                return Value::Null;
+            };
+            let i = if i < 0 { items.len() as i64 + i } else { i };
+            if (0..items.len() as i64).contains(&i) {
+                items.swap_remove(i as usize)
+            } else {
+                Value::Null
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Equality with numbers compared by value, so `1 == 1.0`.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+
+/// Ordering of two numbers or two strings; anything else is unordered.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64()?.partial_cmp(&y.as_f64()?),
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+fn arithmetic(op: BinOp, a: &Value, b: &Value) -> Value {
+    match (op, a, b) {
+        (BinOp::Add, Value::Null, other) | (BinOp::Add, other, Value::Null) => other.clone(),
+        (BinOp::Add, Value::String(x), Value::String(y)) => Value::String(format!("{x}{y}")),
+        (BinOp::Add, Value::Array(x), Value::Array(y)) => {
+            Value::Array(x.iter().chain(y).cloned().collect())
+        }
+        (BinOp::Add, Value::Object(x), Value::Object(y)) => {
+            let mut merged: Map<String, Value> = x.clone();
+            merged.extend(y.iter().map(|(k, v)| (k.clone(), v.clone())));
+            Value::Object(merged)
+        }
+        (_, Value::Number(x), Value::Number(y)) => number_op(op, x, y),
+        _ => Value::Null,
+    }
+}
+
+/// Integer arithmetic while both sides are integers and it doesn't
+/// overflow, so `.count + 1` stays an integer; floating point otherwise.
+fn number_op(op: BinOp, x: &Number, y: &Number) -> Value {
+    if let (Some(a), Some(b)) = (x.as_i64(), y.as_i64()) {
+        let exact = match op {
+            BinOp::Add => a.checked_add(b),
+            BinOp::Sub => a.checked_sub(b),
+            BinOp::Mul => a.checked_mul(b),
+            BinOp::Div if b != 0 && a % b == 0 => a.checked_div(b),
+            BinOp::Rem => a.checked_rem(b),
+            _ => None,
+        };
+        if let Some(n) = exact {
+            return Value::from(n);
+        }
+    }
+    let (Some(a), Some(b)) = (x.as_f64(), y.as_f64()) else {
+        return Value::Null;
+    };
+    let result = match op {
+        BinOp::Add => a + b,
+        BinOp::Sub => a - b,
+        BinOp::Mul => a * b,
+        BinOp::Div => a / b,
+        BinOp::Rem => a % b,
+        _ => unreachable!("comparisons are evaluated in Expr::eval"),
+    };
+    Number::from_f64(result).map_or(Value::Null, Value::Number)
+}
+
+// ============================================================================
+// Tokens
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Kind {
+    Ident(String),
+    Number(Number),
+    Str(String),
+    /// Punctuation and operators
+    Sym(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub kind: Kind,
+    pub start: usize,
+    pub end: usize,
+}
+
+const SYMBOLS: [&str; 20] = [
+    "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "(", ")", "[", "]", "{", "}", ",",
+    ":", "|",
+];
+
+/// Split `text` into tokens. `.` is returned as a symbol of its own.
+pub(crate) fn tokenize(text: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let kind = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if c == b'"' {
+            let (value, end) = string(text, start)?;
+            i = end;
+            Kind::Str(value)
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                // An exponent may be signed
+                if matches!(bytes[i], b'e' | b'E') && matches!(bytes.get(i + 1), Some(b'+' | b'-'))
+                {
+                    i += 1;
+                }
+                i += 1;
+            }
+            let number = serde_json::from_str(&text[start..i]).map_err(|_| ParseError {
+                offset: start,
+                message: format!("invalid number `{}`", &text[start..i]),
+            })?;
+            Kind::Number(number)
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            Kind::Ident(text[start..i].to_string())
+        } else if c == b'.' {
+            i += 1;
+            Kind::Sym(".")
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| text[i..].starts_with(**s)) {
+            i += symbol.len();
+            Kind::Sym(symbol)
+        } else {
+            let found = text[i..].chars().next().unwrap_or_default();
+            return Err(ParseError {
+                offset: start,
+                message: format!("unexpected character `{found}`"),
+            });
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    Ok(tokens)
+}
+
+/// A double-quoted string starting at `start`, with JSON escapes, and the
+/// offset just past its closing quote.
+fn string(text: &str, start: usize) -> Result<(String, usize), ParseError> {
+    let bytes = text.as_bytes();
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => {
+                let value = serde_json::from_str(&text[start..=i]).map_err(|e| ParseError {
+                    offset: start,
+                    message: format!("invalid string: {e}"),
+                })?;
+                return Ok((value, i + 1));
+            }
+            _ => i += 1,
+        }
+    }
+    Err(ParseError {
+        offset: start,
+        message: "unterminated string".to_string(),
+    })
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// A recursive-descent parser over tokens, shared with the pipeline syntax,
+/// which parses the expressions of its `map` and `filter` steps in place.
+pub(crate) struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(text: &'a str) -> Result<Self, ParseError> {
+        Ok(Parser {
+            text,
+            tokens: tokenize(text)?,
+            position: 0,
+        })
+    }
+
+    pub fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    pub fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += token.is_some() as usize;
+        token
+    }
+
+    /// Consume the next token if it is the symbol `symbol`.
+    pub fn eat(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token { kind: Kind::Sym(s), .. }) if *s == symbol);
+        self.position += found as usize;
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token { kind: Kind::Ident(s), .. }) if s == keyword);
+        self.position += found as usize;
+        found
+    }
+
+    pub fn error_at(&self, token: &Token, message: &str) -> ParseError {
+        ParseError {
+            offset: token.start,
+            message: format!("{message}, found `{}`", &self.text[token.start..token.end]),
+        }
+    }
+
+    /// An error at the next token, or at the end of the text.
+    pub fn error(&self, message: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => self.error_at(token, message),
+            None => ParseError {
+                offset: self.text.len(),
+                message: format!("{message}, found the end of the input"),
+            },
+        }
+    }
+
+    /// Byte offset where the next token starts, or the end of the text.
+    pub fn offset(&self) -> usize {
+        self.peek().map_or(self.text.len(), |t| t.start)
+    }
+
+    /// Byte offset just past the last consumed token.
+    pub fn consumed_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .map_or(0, |last| self.tokens[last].end)
+    }
+
+    pub fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.and()?;
+        while self.eat_keyword("or") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.not()?;
+        while self.eat_keyword("and") {
+            left = Expr::And(Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, ParseError> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ParseError> {
+        let left = self.sum()?;
+        let ops = [
+            ("==", BinOp::Eq),
+            ("!=", BinOp::Ne),
+            ("<=", BinOp::Le),
+            (">=", BinOp::Ge),
+            ("<", BinOp::Lt),
+            (">", BinOp::Gt),
+        ];
+        for (symbol, op) in ops {
+            if self.eat(symbol) {
+                return Ok(Expr::Binary(Box::new(left), op, Box::new(self.sum()?)));
+            }
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat("+") {
+                BinOp::Add
+            } else if self.eat("-") {
+                BinOp::Sub
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(Box::new(left), op, Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat("*") {
+                BinOp::Mul
+            } else if self.eat("/") {
+                BinOp::Div
+            } else if self.eat("%") {
+                BinOp::Rem
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(Box::new(left), op, Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let primary = self.primary()?;
+        self.postfix(primary)
+    }
+
+    /// `.key`, `."key"` and `[index]` after a value.
+    fn postfix(&mut self, mut target: Expr) -> Result<Expr, ParseError> {
+        loop {
+            if self.eat(".") {
+                target = Expr::Field(Box::new(target), self.key()?);
+            } else if self.eat("[") {
+                let index = self.expr()?;
+                self.expect("]")?;
+                target = Expr::Index(Box::new(target), Box::new(index));
+            } else {
+                return Ok(target);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let Some(token) = self.next() else {
+            return Err(self.error("expected an expression"));
+        };
+        Ok(match token.kind {
+            Kind::Sym(".") => match self.peek().map(|t| &t.kind) {
+                Some(Kind::Ident(_) | Kind::Str(_)) => {
+                    Expr::Field(Box::new(Expr::Input), self.key()?)
+                }
+                _ => Expr::Input,
+            },
+            Kind::Number(n) => Expr::Literal(Value::Number(n)),
+            Kind::Str(s) => Expr::Literal(Value::String(s)),
+            Kind::Ident(ref word) => match word.as_str() {
+                "true" => Expr::Literal(Value::Bool(true)),
+                "false" => Expr::Literal(Value::Bool(false)),
+                "null" => Expr::Literal(Value::Null),
+                _ => return Err(self.error_at(&token, "expected an expression")),
+            },
+            Kind::Sym("(") => {
+                let inner = self.expr()?;
+                self.expect(")")?;
+                inner
+            }
+            Kind::Sym("[") => Expr::Array(self.list("]", Self::expr)?),
+            Kind::Sym("{") => Expr::Object(self.list("}", Self::field)?),
+            _ => return Err(self.error_at(&token, "expected an expression")),
+        })
+    }
+
+    /// One `key: expr` of an object, or `key` for `key: .key`.
+    fn field(&mut self) -> Result<(String, Expr), ParseError> {
+        let key = self.key()?;
+        if self.eat(":") {
+            // Without `or`, so a field's value reads naturally up to the comma
+            Ok((key, self.and()?))
+        } else {
+            let value = Expr::Field(Box::new(Expr::Input), key.clone());
+            Ok((key, value))
+        }
+    }
+
+    /// Comma-separated items up to the closing symbol `close`.
+    fn list<T>(
+        &mut self,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::new();
+        if self.eat(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    /// An object key: a name or a string.
+    fn key(&mut self) -> Result<String, ParseError> {
+        match self.peek().map(|t| t.kind.clone()) {
+            Some(Kind::Ident(name)) | Some(Kind::Str(name)) => {
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected a key")),
+        }
+    }
+
+    pub fn expect(&mut self, symbol: &str) -> Result<(), ParseError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{symbol}`")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval(text: &str, input: Value) -> Value {
+        parse(text).unwrap().eval(&input)
+    }
+
+    #[test]
+    fn test_paths() {
+        let input = json!({"user": {"name": "ada", "tags": ["a", "b", "c"]}, "odd key": 1});
+        assert_eq!(eval(".", json!(3)), json!(3));
+        assert_eq!(eval(".user.name", input.clone()), json!("ada"));
+        assert_eq!(eval(".user.tags[1]", input.clone()), json!("b"));
+        assert_eq!(eval(".user.tags[-1]", input.clone()), json!("c"));
+        assert_eq!(eval(".\"odd key\"", input.clone()), json!(1));
+        assert_eq!(eval(".[\"odd key\"]", input.clone()), json!(1));
+        assert_eq!(eval(".user.missing.deeper", input.clone()), Value::Null);
+        assert_eq!(eval(".user.tags[7]", input), Value::Null);
+    }
+
+    #[test]
+    fn test_arithmetic_keeps_integers() {
+        assert_eq!(eval(".a + 1", json!({"a": 2})), json!(3));
+        assert_eq!(eval(".a * 1.5", json!({"a": 2})), json!(3.0));
+        assert_eq!(eval("7 / 2", Value::Null), json!(3.5));
+        assert_eq!(eval("8 / 2", Value::Null), json!(4));
+        assert_eq!(eval("-.a % 3", json!({"a": 7})), json!(-1));
+        assert_eq!(eval("1 / 0", Value::Null), Value::Null);
+        assert_eq!(eval("\"a\" * 2", Value::Null), Value::Null);
+        assert_eq!(
+            eval(
+                ".first + \" \" + .last",
+                json!({"first": "Ada", "last": "L"})
+            ),
+            json!("Ada L")
+        );
+        assert_eq!(eval(".missing + 1", json!({})), json!(1));
+    }
+
+    #[test]
+    fn test_comparisons_and_logic() {
+        let input = json!({"age": 30, "active": true, "name": "bo"});
+        assert!(parse(".age >= 18 and .active").unwrap().test(&input));
+        assert!(parse(".age == 30.0").unwrap().test(&input));
+        assert!(parse("not .deleted").unwrap().test(&input));
+        assert!(parse(".name < \"zed\" or false").unwrap().test(&input));
+        // Mixed types are unordered
+        assert!(!parse(".name < 5").unwrap().test(&input));
+        assert!(!parse(".age > 40 or .missing").unwrap().test(&input));
+        // Precedence: arithmetic, then comparison, then not/and/or
+        assert_eq!(
+            eval("1 + 2 * 3 == 7 and not 1 > 2", Value::Null),
+            json!(true)
+        );
+    }
+
+    #[test]
+    fn test_constructors() {
+        let input = json!({"id": 7, "user": {"name": "ada"}, "a": 1, "b": 2});
+        assert_eq!(
+            eval("{id, name: .user.name, \"sum\": .a + .b}", input.clone()),
+            json!({"id": 7, "name": "ada", "sum": 3})
+        );
+        assert_eq!(
+            eval("[.a, .b, [null]]", input.clone()),
+            json!([1, 2, [null]])
+        );
+        assert_eq!(eval("{} + {a: 1} + {a: 2}", input), json!({"a": 2}));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let error = parse(".a +").unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected an expression, found the end of the input"
+        );
+        assert_eq!(error.offset, 4);
+
+        let error = parse(".a 1").unwrap_err();
+        assert_eq!(error.offset, 3);
+        assert_eq!(
+            error.to_string(),
+            "expected the end of the expression, found `1` (at column 4)"
+        );
+
+        assert!(parse("{a: 1").is_err());
+        assert!(parse("\"open").is_err());
+        assert!(parse(".a & .b").is_err());
+        assert!(parse("foo").is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! `orlando`: run a pipeline over JSON Lines or CSV from the command line.
+//!
+//! ```text
+//! orlando 'filter .age >= 18 | map {name, age} | take 10' people.jsonl
+//! orlando -i csv -o csv --plan clean.yaml < readings.csv
+//! ```
+//!
+//! The pipeline is given inline in the [pipeline syntax](script) or as a
+//! plan file, JSON or YAML, in the format of
+//! [`orlando_transducers::plan`]. Either way, `map` and `filter` steps hold
+//! an [expression](expr) rather than the name of a registered function.
+//!
+//! Input is read and output written a record at a time, so the pipeline
+//! stops reading as soon as it has what it needs.
+
+mod expr;
+mod script;
+mod yaml;
+
+use clap::{Arg, ArgMatches, Command};
+use orlando_transducers::io::json_lines;
+use orlando_transducers::plan::{CompiledPlan, Plan, PlanStep, Registry};
+use serde_json::{Number, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+const EXAMPLES: &str = "\
+Examples:
+  orlando 'filter .active | map {id, total: .price * .qty}' orders.jsonl
+  orlando -i csv -o csv 'filter .temp > 30 | take 5' readings.csv
+  orlando --plan pipeline.yaml -o json < events.jsonl";
+
+/// Why a run failed.
+enum Error {
+    /// The pipeline or plan is invalid; exit code 2, like usage errors
+    Pipeline(String),
+    /// Reading input or writing output failed; exit code 1
+    Run(String),
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+    match run(&matches) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Pipeline(message)) => {
+            eprintln!("orlando: {message}");
+            ExitCode::from(2)
+        }
+        Err(Error::Run(message)) => {
+            eprintln!("orlando: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn command() -> Command {
+    Command::new("orlando")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Run a transducer pipeline over JSON Lines or CSV")
+        .override_usage(
+            "orlando [OPTIONS] <PIPELINE> [FILE]...\n       \
+             orlando [OPTIONS] --plan <PLAN> [FILE]...",
+        )
+        .arg(
+            Arg::new("plan")
+                .short('f')
+                .long("plan")
+                .value_name("PLAN")
+                .help("Read the pipeline from a plan file (.json, or .yaml/.yml)"),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FORMAT")
+                .value_parser(["ndjson", "csv"])
+                .default_value("ndjson")
+                .help("Input format; CSV rows become objects keyed by the header"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FORMAT")
+                .value_parser(["ndjson", "json", "csv"])
+                .default_value("ndjson")
+                .help("Output format; `json` writes one array at the end"),
+        )
+        .arg(Arg::new("args").value_name("ARGS").num_args(0..).help(
+            "The pipeline (unless --plan is given), then the files to read; stdin if none, or `-`",
+        ))
+        .after_help(EXAMPLES)
+}
+
+fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let mut args: Vec<&String> = matches
+        .get_many::<String>("args")
+        .map(Iterator::collect)
+        .unwrap_or_default();
+
+    let plan = match matches.get_one::<String>("plan") {
+        Some(path) => read_plan(Path::new(path))?,
+        None if args.is_empty() => {
+            return Err(Error::Pipeline(
+                "no pipeline given (see `orlando --help`)".to_string(),
+            ))
+        }
+        None => {
+            let text = args.remove(0);
+            script::parse(text).map_err(|e| Error::Pipeline(format!("pipeline: {e}")))?
+        }
+    };
+    let pipeline = compile(&plan)?;
+
+    let stdin = "-".to_string();
+    if args.is_empty() {
+        args.push(&stdin);
+    }
+    let input = matches.get_one::<String>("input").map(String::as_str);
+    let output = matches.get_one::<String>("output").map(String::as_str);
+
+    let mut sink = Sink::new(output.unwrap_or("ndjson"));
+    let result = feed(&pipeline, &args, input == Some("csv"), &mut sink);
+    // Whatever was produced before an input error is still written
+    let finished = sink.finish().or_else(write_error);
+    result.and(finished)
+}
+
+fn write_error(e: io::Error) -> Result<(), Error> {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        // The reader went away (`orlando ... | head`): not a failure
+        Ok(())
+    } else {
+        Err(Error::Run(format!("write error: {e}")))
+    }
+}
+
+/// Read a plan file, as YAML if its extension says so and JSON otherwise.
+fn read_plan(path: &Path) -> Result<Plan, Error> {
+    let name = path.display();
+    let text =
+        std::fs::read_to_string(path).map_err(|e| Error::Pipeline(format!("{name}: {e}")))?;
+    let yaml = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml")
+    );
+    let plan = if yaml {
+        let value = yaml::parse(&text).map_err(|e| Error::Pipeline(format!("{name}: {e}")))?;
+        serde_json::from_value(value).map_err(|e| format!("invalid plan: {e}"))
+    } else {
+        Plan::from_json(&text).map_err(|e| e.to_string())
+    };
+    plan.map_err(|message| Error::Pipeline(format!("{name}: {message}")))
+}
+
+/// Compile a plan, registering each `map` and `filter` expression under its
+/// own text.
+fn compile(plan: &Plan) -> Result<CompiledPlan, Error> {
+    let mut registry = Registry::new();
+    for (position, step) in plan.steps.iter().enumerate() {
+        let (function, is_map) = match step {
+            PlanStep::Map { function } => (function, true),
+            PlanStep::Filter { function } => (function, false),
+            _ => continue,
+        };
+        let expr = expr::parse(function)
+            .map_err(|e| Error::Pipeline(format!("step {position}: `{function}`: {e}")))?;
+        registry = if is_map {
+            registry.map(function.clone(), move |v| expr.eval(&v))
+        } else {
+            registry.filter(function.clone(), move |v| expr.test(v))
+        };
+    }
+    plan.compile(&registry)
+        .map_err(|e| Error::Pipeline(e.to_string()))
+}
+
+/// Push every record of every source through the pipeline, until the
+/// sources run out or the pipeline stops, then write the partial chunks it
+/// still holds.
+fn feed(
+    pipeline: &CompiledPlan,
+    sources: &[&String],
+    csv: bool,
+    sink: &mut Sink,
+) -> Result<(), Error> {
+    let mut run = pipeline.start();
+    'sources: for &name in sources {
+        let reader: Box<dyn BufRead> = if name == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            let file = File::open(name).map_err(|e| Error::Run(format!("{name}: {e}")))?;
+            Box::new(BufReader::new(file))
+        };
+        let records: Records = if csv {
+            csv_records(reader).map_err(|e| Error::Run(format!("{name}: {e}")))?
+        } else {
+            Box::new(json_lines::read::<Value, _>(reader).map(|r| r.map_err(|e| e.to_string())))
+        };
+        for record in records {
+            let record = record.map_err(|e| Error::Run(format!("{name}: {e}")))?;
+            for value in run.push(record) {
+                if let Err(e) = sink.write(value) {
+                    return write_error(e);
+                }
+            }
+            if run.is_done() {
+                break 'sources;
+            }
+        }
+    }
+    for value in run.finish() {
+        if let Err(e) = sink.write(value) {
+            return write_error(e);
+        }
+    }
+    Ok(())
+}
+
+type Records = Box<dyn Iterator<Item = Result<Value, String>>>;
+
+/// The rows of a CSV document as objects keyed by its header.
+fn csv_records(reader: Box<dyn BufRead>) -> Result<Records, csv::Error> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let header = reader.headers()?.clone();
+    Ok(Box::new(reader.into_records().map(move |row| {
+        let row = row.map_err(|e| e.to_string())?;
+        Ok(Value::Object(
+            header
+                .iter()
+                .zip(row.iter())
+                .map(|(key, field)| (key.to_string(), infer(field)))
+                .collect(),
+        ))
+    })))
+}
+
+/// A CSV field as JSON: numbers as numbers, empty fields as null, anything
+/// else as a string.
+fn infer(field: &str) -> Value {
+    if field.is_empty() {
+        return Value::Null;
+    }
+    match serde_json::from_str::<Number>(field) {
+        Ok(n) if field.trim() == field => Value::Number(n),
+        _ => Value::String(field.to_string()),
+    }
+}
+
+/// Where output goes.
+enum Sink {
+    Lines(io::StdoutLock<'static>),
+    /// Collected for one JSON array at the end
+    Array(Vec<Value>),
+    Csv {
+        writer: Box<csv::Writer<io::StdoutLock<'static>>>,
+        /// Column names, from the first output if it is an object
+        header: Option<Vec<String>>,
+        started: bool,
+    },
+}
+
+impl Sink {
+    fn new(format: &str) -> Self {
+        let stdout = io::stdout().lock();
+        match format {
+            "json" => Sink::Array(Vec::new()),
+            "csv" => Sink::Csv {
+                writer: Box::new(csv::WriterBuilder::new().flexible(true).from_writer(stdout)),
+                header: None,
+                started: false,
+            },
+            _ => Sink::Lines(stdout),
+        }
+    }
+
+    fn write(&mut self, value: Value) -> io::Result<()> {
+        match self {
+            Sink::Lines(out) => {
+                serde_json::to_writer(&mut *out, &value)?;
+                out.write_all(b"\n")
+            }
+            Sink::Array(values) => {
+                values.push(value);
+                Ok(())
+            }
+            Sink::Csv {
+                writer,
+                header,
+                started,
+            } => {
+                if !*started {
+                    *started = true;
+                    if let Value::Object(map) = &value {
+                        let keys: Vec<String> = map.keys().cloned().collect();
+                        writer.write_record(&keys)?;
+                        *header = Some(keys);
+                    }
+                }
+                let fields: Vec<String> = match (value, &*header) {
+                    (Value::Object(mut map), Some(keys)) => keys
+                        .iter()
+                        .map(|key| field(map.remove(key).unwrap_or(Value::Null)))
+                        .collect(),
+                    (Value::Object(map), None) => map.into_iter().map(|(_, v)| field(v)).collect(),
+                    (Value::Array(items), _) => items.into_iter().map(field).collect(),
+                    (value, _) => vec![field(value)],
+                };
+                Ok(writer.write_record(&fields)?)
+            }
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Sink::Lines(mut out) => out.flush(),
+            Sink::Array(values) => {
+                let mut out = io::stdout().lock();
+                serde_json::to_writer_pretty(&mut out, &values)?;
+                out.write_all(b"\n")?;
+                out.flush()
+            }
+            Sink::Csv { mut writer, .. } => writer.flush(),
+        }
+    }
+}
+
+/// A CSV field: strings as they are, null as empty, anything else as JSON.
+fn field(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,184 @@
+//! The pipeline syntax given on the command line.
+//!
+//! Stages are separated by `|` and named after the plan ops, with their
+//! constants as arguments:
+//!
+//! ```text
+//! filter .active | map {id, total: .price * .qty} | take 10
+//! map .score | filterRange 0 100 | scaleOffset 0.5 -1 | chunk 3
+//! ```
+//!
+//! `map` and `filter` take an [expression](crate::expr); the other stages
+//! take numbers, or a JSON-like value for `interpose` and `zip`.
+
+use crate::expr::{Kind, ParseError, Parser};
+use orlando_transducers::plan::{Plan, PlanStep};
+use serde_json::Value;
+
+/// Parse pipeline text into a plan. `map` and `filter` steps keep the text of
+/// their expression as the function name.
+pub fn parse(text: &str) -> Result<Plan, ParseError> {
+    let mut parser = Parser::new(text)?;
+    let mut steps = Vec::new();
+    // An empty pipeline passes its input through
+    if parser.peek().is_none() {
+        return Ok(Plan::new(steps));
+    }
+    loop {
+        steps.push(step(text, &mut parser)?);
+        if parser.peek().is_none() {
+            return Ok(Plan::new(steps));
+        }
+        parser.expect("|")?;
+    }
+}
+
+fn step(text: &str, parser: &mut Parser) -> Result<PlanStep, ParseError> {
+    let name = match parser.peek().map(|t| t.kind.clone()) {
+        Some(Kind::Ident(name)) => name,
+        _ => return Err(parser.error("expected a step name")),
+    };
+    let name_token = parser.next().expect("peeked a name");
+    Ok(match name.as_str() {
+        "take" => PlanStep::Take { n: count(parser)? },
+        "drop" => PlanStep::Drop { n: count(parser)? },
+        "chunk" => PlanStep::Chunk { n: count(parser)? },
+        "aperture" => PlanStep::Aperture { n: count(parser)? },
+        "unique" => PlanStep::Unique,
+        "interpose" => PlanStep::Interpose {
+            separator: constant(parser)?,
+        },
+        "zip" => {
+            let offset = parser.offset();
+            match constant(parser)? {
+                Value::Array(other) => PlanStep::Zip { other },
+                _ => {
+                    return Err(ParseError {
+                        offset,
+                        message: "`zip` takes an array".to_string(),
+                    })
+                }
+            }
+        }
+        "mapAdd" => PlanStep::MapAdd { n: number(parser)? },
+        "mapMul" => PlanStep::MapMul { n: number(parser)? },
+        "scaleOffset" => PlanStep::ScaleOffset {
+            a: number(parser)?,
+            b: number(parser)?,
+        },
+        "normalize" => PlanStep::Normalize {
+            mean: number(parser)?,
+            std: number(parser)?,
+        },
+        "filterGt" => PlanStep::FilterGt { n: number(parser)? },
+        "filterRange" => PlanStep::FilterRange {
+            lo: number(parser)?,
+            hi: number(parser)?,
+        },
+        "map" => PlanStep::Map {
+            function: expression(text, parser)?,
+        },
+        "filter" => PlanStep::Filter {
+            function: expression(text, parser)?,
+        },
+        _ => return Err(parser.error_at(&name_token, "unknown step")),
+    })
+}
+
+/// The source text of the expression at the parser's position.
+fn expression(text: &str, parser: &mut Parser) -> Result<String, ParseError> {
+    let start = parser.offset();
+    parser.expr()?;
+    Ok(text[start..parser.consumed_end()].to_string())
+}
+
+/// A constant argument, written as an expression that doesn't read the input.
+fn constant(parser: &mut Parser) -> Result<Value, ParseError> {
+    Ok(parser.expr()?.eval(&Value::Null))
+}
+
+/// A number argument, optionally negative.
+fn number(parser: &mut Parser) -> Result<f64, ParseError> {
+    let negative = parser.eat("-");
+    match parser.peek().map(|t| t.kind.clone()) {
+        Some(Kind::Number(n)) => {
+            parser.next();
+            let n = n.as_f64().unwrap_or(f64::NAN);
+            Ok(if negative { -n } else { n })
+        }
+        _ => Err(parser.error("expected a number")),
+    }
+}
+
+/// A non-negative whole number argument.
+fn count(parser: &mut Parser) -> Result<usize, ParseError> {
+    match parser.peek().map(|t| t.kind.clone()) {
+        Some(Kind::Number(n)) if n.as_u64().is_some() => {
+            parser.next();
+            Ok(n.as_u64().unwrap_or_default() as usize)
+        }
+        _ => Err(parser.error("expected a count")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stages() {
+        let plan = parse(
+            "filter .active and .age > 18 | map {id, total: .a - -1} | take 10 | \
+             scaleOffset 0.5 -1 | filterRange -2 2e3 | interpose \"|\" | zip [1, {a: 2}]",
+        )
+        .unwrap();
+        assert_eq!(
+            plan.steps,
+            vec![
+                PlanStep::Filter {
+                    function: ".active and .age > 18".into()
+                },
+                PlanStep::Map {
+                    function: "{id, total: .a - -1}".into()
+                },
+                PlanStep::Take { n: 10 },
+                PlanStep::ScaleOffset { a: 0.5, b: -1.0 },
+                PlanStep::FilterRange {
+                    lo: -2.0,
+                    hi: 2000.0
+                },
+                PlanStep::Interpose {
+                    separator: json!("|")
+                },
+                PlanStep::Zip {
+                    other: vec![json!(1), json!({"a": 2})]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_pipeline() {
+        assert!(parse("  ").unwrap().steps.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse("map .a | tkae 3").unwrap_err();
+        assert_eq!(error.message, "unknown step, found `tkae`");
+        assert_eq!(error.offset, 9);
+
+        assert_eq!(
+            parse("take -1").unwrap_err().message,
+            "expected a count, found `-`"
+        );
+        assert_eq!(
+            parse("take 2 unique").unwrap_err().message,
+            "expected `|`, found `unique`"
+        );
+        assert_eq!(parse("zip 3").unwrap_err().message, "`zip` takes an array");
+        assert!(parse("map").is_err());
+        assert!(parse("unique |").is_err());
+    }
+}
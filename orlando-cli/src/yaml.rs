@@ -0,0 +1,373 @@
+//! The subset of YAML that plan files are written in.
+//!
+//! Block mappings and sequences, comments, plain and quoted scalars, and
+//! single-line flow collections written as JSON (`other: [1, 2, 3]`) — enough
+//! for a plan:
+//!
+//! ```yaml
+//! version: 1
+//! steps:
+//!   - op: filter
+//!     fn: .active   # an expression
+//!   - op: take
+//!     n: 10
+//! ```
+//!
+//! Anchors, tags, block scalars (`|`, `>`) and multi-line flow collections
+//! are reported as errors rather than misread.
+
+use serde_json::{Map, Number, Value};
+use std::fmt;
+
+/// A document outside the supported subset, at a 1-based line number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parse a YAML document into a JSON value.
+pub fn parse(text: &str) -> Result<Value, Error> {
+    let mut lines = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let number = i + 1;
+        let content = strip_comment(raw).trim_end();
+        let trimmed = content.trim_start();
+        if trimmed.is_empty() || (lines.is_empty() && trimmed == "---") {
+            continue;
+        }
+        let indent = content.len() - trimmed.len();
+        if content[..indent].contains('\t') {
+            return Err(error(number, "tabs can't be used for indentation"));
+        }
+        lines.push(Line {
+            number,
+            indent,
+            text: trimmed.to_string(),
+        });
+    }
+
+    let mut parser = Document { lines, next: 0 };
+    if parser.lines.is_empty() {
+        return Ok(Value::Null);
+    }
+    let value = parser.block()?;
+    match parser.lines.get(parser.next) {
+        None => Ok(value),
+        Some(line) => Err(error(line.number, "unexpected indentation")),
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+struct Document {
+    lines: Vec<Line>,
+    next: usize,
+}
+
+impl Document {
+    /// The value starting at the current line, at that line's indentation.
+    fn block(&mut self) -> Result<Value, Error> {
+        let line = &self.lines[self.next];
+        if is_item(&line.text) {
+            self.sequence(line.indent)
+        } else if split_key(&line.text, line.number)?.is_some() {
+            self.mapping(line.indent)
+        } else {
+            let (number, text) = (line.number, line.text.clone());
+            self.next += 1;
+            scalar(&text, number)
+        }
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value, Error> {
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get(self.next) {
+            if line.indent != indent || !is_item(&line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start();
+            if rest.is_empty() {
+                self.next += 1;
+                items.push(self.nested(indent, false)?);
+            } else if is_item(rest) || split_key(rest, line.number)?.is_some() {
+                // `- key: value` and `- - x` start a collection indented to
+                // where the text after the dash begins
+                let offset = line.text.len() - rest.len();
+                let line = &mut self.lines[self.next];
+                line.indent += offset;
+                line.text = line.text[offset..].to_string();
+                items.push(self.block()?);
+            } else {
+                let (number, rest) = (line.number, rest.to_string());
+                self.next += 1;
+                items.push(scalar(&rest, number)?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value, Error> {
+        let mut map = Map::new();
+        while let Some(line) = self.lines.get(self.next) {
+            if line.indent != indent {
+                break;
+            }
+            let number = line.number;
+            let Some((key, rest)) = split_key(&line.text, number)? else {
+                return Err(error(number, "expected `key: value`"));
+            };
+            self.next += 1;
+            let value = if rest.is_empty() {
+                self.nested(indent, true)?
+            } else {
+                scalar(&rest, number)?
+            };
+            if map.insert(key.clone(), value).is_some() {
+                return Err(error(number, &format!("duplicate key `{key}`")));
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    /// The value under a key or dash with nothing after it: an indented
+    /// block, a sequence at the key's own indentation, or null.
+    fn nested(&mut self, indent: usize, under_key: bool) -> Result<Value, Error> {
+        match self.lines.get(self.next) {
+            Some(line) if line.indent > indent => self.block(),
+            Some(line) if under_key && line.indent == indent && is_item(&line.text) => {
+                self.sequence(indent)
+            }
+            _ => Ok(Value::Null),
+        }
+    }
+}
+
+fn error(line: usize, message: &str) -> Error {
+    Error {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Split `key: rest` into its key and the text after the colon, or `None`
+/// if the text isn't a mapping entry.
+fn split_key(text: &str, line: usize) -> Result<Option<(String, String)>, Error> {
+    if text.starts_with(['[', '{']) {
+        return Ok(None);
+    }
+    let (key, after) = if text.starts_with(['"', '\'']) {
+        let end = quoted_end(text).ok_or_else(|| error(line, "unterminated string"))?;
+        let after = text[end..].trim_start();
+        if !after.starts_with(':') {
+            return Ok(None);
+        }
+        let Value::String(key) = scalar(&text[..end], line)? else {
+            unreachable!("quoted scalars are strings");
+        };
+        (key, &after[1..])
+    } else {
+        let colon = text
+            .char_indices()
+            .find(|&(i, c)| c == ':' && text[i + 1..].chars().next().is_none_or(|c| c == ' '));
+        let Some((colon, _)) = colon else {
+            return Ok(None);
+        };
+        (text[..colon].trim_end().to_string(), &text[colon + 1..])
+    };
+    Ok(Some((key, after.trim().to_string())))
+}
+
+/// The byte offset just past the closing quote of a string starting `text`.
+fn quoted_end(text: &str) -> Option<usize> {
+    let quote = text.as_bytes()[0];
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote == b'"' => i += 2,
+            // `''` is an escaped quote in a single-quoted string
+            b'\'' if quote == b'\'' && bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            c if c == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// `text` without a trailing comment. A `#` starts a comment at the start of
+/// the line or after whitespace, outside quotes.
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q && !(q == '"' && previous == '\\') => quote = None,
+            Some(_) => {}
+            None if (c == '"' || c == '\'') && previous == ' ' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return &text[..i],
+            None => {}
+        }
+        previous = c;
+    }
+    text
+}
+
+fn scalar(text: &str, line: usize) -> Result<Value, Error> {
+    let unsupported = |what: &str| Err(error(line, &format!("{what} are not supported")));
+    match text.chars().next() {
+        Some('[' | '{') => {
+            return serde_json::from_str(text).map_err(|e| {
+                error(
+                    line,
+                    &format!("flow collections must be single-line JSON: {e}"),
+                )
+            })
+        }
+        Some('"') => {
+            return match quoted_end(text) {
+                Some(end) if end == text.len() => serde_json::from_str(text)
+                    .map_err(|e| error(line, &format!("invalid string: {e}"))),
+                _ => Err(error(line, "unexpected text after a string")),
+            }
+        }
+        Some('\'') => {
+            return match quoted_end(text) {
+                Some(end) if end == text.len() => {
+                    Ok(Value::String(text[1..end - 1].replace("''", "'")))
+                }
+                _ => Err(error(line, "unexpected text after a string")),
+            }
+        }
+        Some('|' | '>') => return unsupported("block scalars"),
+        Some('&' | '*') => return unsupported("anchors and aliases"),
+        Some('!') => return unsupported("tags"),
+        _ => {}
+    }
+    Ok(match text {
+        "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => number(text).unwrap_or_else(|| Value::String(text.to_string())),
+    })
+}
+
+fn number(text: &str) -> Option<Value> {
+    if let Ok(n) = text.parse::<i64>() {
+        return Some(Value::from(n));
+    }
+    // Rust also parses `inf`, `NaN` and `1.`, which YAML reads as strings
+    let numeric = text
+        .starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+        && text.chars().any(|c| c.is_ascii_digit())
+        && !text.ends_with('.')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+    if !numeric {
+        return None;
+    }
+    text.parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_document() {
+        let text = "\
+---
+# A plan
+version: 1
+steps:
+  - op: filter
+    fn: .active and .name != \"#1\"   # trailing comment
+  - op: interpose
+    separator: ', '
+  - op: zip
+    other: [1, {\"a\": 2}]
+  - op: unique
+";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({"version": 1, "steps": [
+                {"op": "filter", "fn": ".active and .name != \"#1\""},
+                {"op": "interpose", "separator": ", "},
+                {"op": "zip", "other": [1, {"a": 2}]},
+                {"op": "unique"},
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_nesting_and_scalars() {
+        let text = "\
+a:
+- 1
+- -2.5e3
+-
+  - x
+  - - y
+b:
+  c: ~
+  'it''s': yes
+  d:
+  e: url://host:80
+";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({
+                "a": [1, -2500.0, ["x", ["y"]]],
+                "b": {"c": null, "it's": "yes", "d": null, "e": "url://host:80"},
+            })
+        );
+        assert_eq!(parse("").unwrap(), Value::Null);
+        assert_eq!(parse("inf").unwrap(), json!("inf"));
+        assert_eq!(
+            parse("- true\n- .5\n- 1.").unwrap(),
+            json!([true, 0.5, "1."])
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text: &str| parse(text).unwrap_err();
+        assert_eq!(
+            error("a: |\n  text").to_string(),
+            "line 1: block scalars are not supported"
+        );
+        assert_eq!(
+            error("a: &x 1").message,
+            "anchors and aliases are not supported"
+        );
+        assert_eq!(error("a: !!int 1").message, "tags are not supported");
+        assert_eq!(error("a: 1\n  b: 2").line, 2);
+        assert_eq!(error("a: 1\na: 2").message, "duplicate key `a`");
+        assert_eq!(error("a: 1\nplain").message, "expected `key: value`");
+        assert_eq!(error("a:\n\t- 1").line, 2);
+        assert!(error("a: [1,\n  2]")
+            .message
+            .starts_with("flow collections"));
+    }
+}
@@ -0,0 +1,152 @@
+//! End-to-end tests of the `orlando` binary.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Run `orlando` with `args`, feeding `stdin`.
+fn orlando(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_orlando"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The pipeline may stop before reading everything
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// A file in a fresh temporary directory for this test.
+fn temp_file(test: &str, name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("orlando-cli-{}-{test}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+const PEOPLE: &str = r#"{"name": "Ada", "age": 36, "tags": ["math"]}
+{"name": "Bo", "age": 12}
+
+{"name": "Cy", "age": 51, "tags": []}
+{"name": "Di", "age": 19}
+"#;
+
+#[test]
+fn test_inline_pipeline_over_stdin() {
+    let output = orlando(
+        &["filter .age >= 18 | map {name, next: .age + 1} | take 2"],
+        PEOPLE,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "{\"name\":\"Ada\",\"next\":37}\n{\"name\":\"Cy\",\"next\":52}\n"
+    );
+}
+
+#[test]
+fn test_numeric_steps_and_json_output() {
+    let output = orlando(
+        &["-o", "json", "map .age | filterGt 15 | mapMul 2 | chunk 2"],
+        PEOPLE,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let value: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(value, serde_json::json!([[72.0, 102.0], [38.0]]));
+}
+
+#[test]
+fn test_chunk_flushes_partial_chunk() {
+    let output = orlando(&["chunk 2"], "1\n2\n3\n4\n5\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "[1,2]\n[3,4]\n[5]\n");
+
+    // Also after a take has stopped reading
+    let output = orlando(&["take 5 | chunk 2"], "1\n2\n3\n4\n5\n6\n7\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "[1,2]\n[3,4]\n[5]\n");
+}
+
+#[test]
+fn test_csv_in_and_out() {
+    let input = "city,temp,note\nOslo,35,\nRome,28,\nCairo,40,\"hot, \"\"dry\"\"\"\n";
+    let output = orlando(
+        &[
+            "-i",
+            "csv",
+            "-o",
+            "csv",
+            "filter .temp > 30 | map {city, f: .temp * 9 / 5 + 32, note}",
+        ],
+        input,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "city,f,note\nOslo,95,\nCairo,104,\"hot, \"\"dry\"\"\"\n"
+    );
+}
+
+#[test]
+fn test_plan_files() {
+    let yaml = temp_file(
+        "plan_files",
+        "plan.yaml",
+        "version: 1\nsteps:\n  - op: filter\n    fn: .tags   # present and not null\n  - op: map\n    fn: .name\n",
+    );
+    let json = temp_file(
+        "plan_files",
+        "plan.json",
+        r#"{"version": 1, "steps": [{"op": "drop", "n": 2}, {"op": "map", "fn": ".name"}]}"#,
+    );
+    let people = temp_file("plan_files", "people.jsonl", PEOPLE);
+
+    let output = orlando(&["--plan", yaml.to_str().unwrap()], PEOPLE);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\"Ada\"\n\"Cy\"\n");
+
+    // Files are read in turn, with `-` for stdin
+    let output = orlando(
+        &["-f", json.to_str().unwrap(), people.to_str().unwrap(), "-"],
+        "{\"name\": \"Ed\"}\n",
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\"Cy\"\n\"Di\"\n\"Ed\"\n");
+}
+
+#[test]
+fn test_errors_and_exit_codes() {
+    let output = orlando(&["map .a +"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        stderr(&output),
+        "orlando: pipeline: expected an expression, found the end of the input (at column 9)\n"
+    );
+
+    let output = orlando(&[], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = orlando(&["-o", "xml", "unique"], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    // Output produced before bad input is still written
+    let output = orlando(&["map .a"], "{\"a\": 1}\nnot json\n{\"a\": 2}\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "1\n");
+    assert!(stderr(&output).starts_with("orlando: -: line 2: "));
+
+    let output = orlando(&["unique", "/nonexistent/input.jsonl"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("orlando: /nonexistent/input.jsonl: "));
+}
@@ -0,0 +1,117 @@
+//! Derive macros for [`orlando-transducers`](https://docs.rs/orlando-transducers)
+//! optics.
+//!
+//! Use through the `derive` feature of `orlando-transducers`, which
+//! re-exports the macros from `orlando_transducers::optics`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields};
+
+/// Generate a `<field>_lens()` constructor for every field of a struct with
+/// named fields.
+///
+/// Each constructor returns an `orlando_transducers::optics::Lens` from the
+/// struct to the field, with the field's visibility. The struct and its field
+/// types must be `Clone`. Mark a field `#[lens(skip)]` to leave it out.
+///
+/// ```ignore
+/// use orlando_transducers::optics::Lenses;
+///
+/// #[derive(Clone, Lenses)]
+/// pub struct User {
+///     pub name: String,
+///     pub age: u32,
+///     #[lens(skip)]
+///     cache: Rc<RefCell<Cache>>,
+/// }
+///
+/// let older = User::age_lens().over(&user, |age| age + 1);
+/// ```
+#[proc_macro_derive(Lenses, attributes(lens))]
+pub fn derive_lenses(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_lenses(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_lenses(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "#[derive(Lenses)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[derive(Lenses)] can only be used on structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: ::core::clone::Clone + 'static));
+
+    let mut constructors = Vec::new();
+    for field in fields {
+        if is_skipped(field)? {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let vis = &field.vis;
+        let method = format_ident!("{}_lens", ident.unraw());
+        let doc = format!("A lens focusing on the `{}` field.", ident.unraw());
+        constructors.push(quote! {
+            #[doc = #doc]
+            #vis fn #method() -> ::orlando_transducers::optics::Lens<Self, #ty>
+            where
+                #ty: ::core::clone::Clone + 'static,
+            {
+                ::orlando_transducers::optics::Lens::new(
+                    |source: &Self| ::core::clone::Clone::clone(&source.#ident),
+                    |source: &Self, value: #ty| {
+                        let mut updated = ::core::clone::Clone::clone(source);
+                        updated.#ident = value;
+                        updated
+                    },
+                )
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#constructors)*
+        }
+    })
+}
+
+/// Whether a field carries `#[lens(skip)]`.
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("lens")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown lens attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
@@ -0,0 +1,87 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createRequire } from 'node:module';
+
+const { Pipeline } = createRequire(import.meta.url)('../index.js');
+
+test('map, filter and take', () => {
+  const pipeline = new Pipeline().map((x) => x * 2).filter((x) => x > 5).take(3);
+  assert.deepEqual(pipeline.toArray([1, 2, 3, 4, 5, 6, 7, 8]), [6, 8, 10]);
+});
+
+test('builders do not mutate', () => {
+  const base = new Pipeline().map((x) => x + 1);
+  assert.deepEqual(base.take(1).toArray([1, 2]), [2]);
+  assert.deepEqual(base.toArray([1, 2]), [2, 3]);
+});
+
+test('take stops calling back into JavaScript', () => {
+  const seen = [];
+  const pipeline = new Pipeline().tap((x) => seen.push(x)).take(2);
+  assert.deepEqual(pipeline.toArray([1, 2, 3, 4]), [1, 2]);
+  assert.deepEqual(seen, [1, 2]);
+});
+
+test('stateful operations reset between runs', () => {
+  const pipeline = new Pipeline().drop(1).take(2);
+  assert.deepEqual(pipeline.toArray([0, 1, 2, 3]), [1, 2]);
+  assert.deepEqual(pipeline.toArray([0, 1, 2, 3]), [1, 2]);
+});
+
+test('structural operations', () => {
+  assert.deepEqual(new Pipeline().chunk(2).toArray([0, 1, 2, 3, 4]), [[0, 1], [2, 3], [4]]);
+  assert.deepEqual(new Pipeline().aperture(2).toArray([0, 1, 2]), [[0, 1], [1, 2]]);
+  assert.deepEqual(new Pipeline().unique().toArray([1, 1, 2, 1]), [1, 2, 1]);
+  assert.deepEqual(new Pipeline().flatMap((x) => [x, x]).take(3).toArray([1, 2]), [1, 1, 2]);
+  assert.deepEqual(new Pipeline().dropWhile((x) => x < 2).takeWhile((x) => x < 4).toArray([0, 1, 2, 3, 4, 1]), [2, 3]);
+});
+
+test('take before chunk flushes the partial chunk', () => {
+  const source = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+  assert.deepEqual(new Pipeline().take(5).chunk(2).toArray(source), [[1, 2], [3, 4], [5]]);
+  // A chunk before the take is cut off with it
+  assert.deepEqual(new Pipeline().chunk(2).take(2).toArray(source), [[1, 2], [3, 4]]);
+  // Stopping in the terminal rules out the flush
+  assert.deepEqual(new Pipeline().take(5).chunk(2).first(source), [1, 2]);
+});
+
+test('terminals', () => {
+  const evens = new Pipeline().filter((x) => x % 2 === 0);
+  const source = [1, 2, 3, 4, 5, 6];
+  assert.equal(evens.count(source), 3);
+  assert.equal(evens.sum(source), 12);
+  assert.equal(evens.first(source), 2);
+  assert.equal(evens.find(source, (x) => x > 2), 4);
+  assert.equal(evens.find(source, (x) => x > 10), undefined);
+  assert.equal(evens.every(source, (x) => x > 0), true);
+  assert.equal(evens.some(source, (x) => x > 5), true);
+  assert.equal(evens.reduce(source, (acc, x) => acc + String(x), ''), '246');
+});
+
+test('numeric pipelines read a Float64Array in place', () => {
+  const pipeline = new Pipeline().mapMul(2).filterGt(4).take(2);
+  const result = pipeline.toFloat64Array(new Float64Array([1, 2, 3, 4, 5]));
+  assert.ok(result instanceof Float64Array);
+  assert.deepEqual(Array.from(result), [6, 8]);
+  assert.deepEqual(Array.from(pipeline.toFloat64Array([1, 2, 3, 4, 5])), [6, 8]);
+  assert.equal(new Pipeline().scaleOffset(2, 1).sum(new Float64Array([1, 2])), 8);
+});
+
+test('runInWorker resolves off the main thread', async () => {
+  const source = new Float64Array(10_000).map((_, i) => i);
+  const result = await new Pipeline().normalize(0, 10).filterRange(0, 1).runInWorker(source);
+  assert.equal(result.length, 10);
+  assert.throws(() => new Pipeline().map((x) => x).runInWorker(source), /runInWorker/);
+});
+
+test('plans round-trip through JSON', () => {
+  const double = (x) => x * 2;
+  const pipeline = new Pipeline().map(double, 'double').drop(1).chunk(2);
+  const json = pipeline.toPlanJson();
+  assert.equal(JSON.parse(json).version, 1);
+
+  const rebuilt = Pipeline.fromPlan(json, { double });
+  assert.deepEqual(rebuilt.toArray([1, 2, 3, 4]), [[4, 6], [8]]);
+  assert.throws(() => Pipeline.fromPlan(json), /double/);
+  assert.throws(() => new Pipeline().map(double).toPlanJson(), /unnamed/);
+});
@@ -0,0 +1,795 @@
+//! Native Node.js bindings for the Orlando pipeline engine.
+//!
+//! Exposes the `Pipeline` class of the WASM build as an N-API addon, for
+//! Node deployments where crossing the WASM-JS boundary dominates. The API
+//! matches the WASM `Pipeline`; in addition, `toFloat64Array` reads a
+//! `Float64Array` or `Buffer`-backed view in place, and `runInWorker` runs
+//! numeric pipelines on the libuv thread pool.
+//!
+//! ```javascript
+//! const { Pipeline } = require('@orlando-transducers/node');
+//!
+//! const pipeline = new Pipeline().map(x => x * 2).filter(x => x > 5).take(3);
+//! pipeline.toArray([1, 2, 3, 4, 5, 6]); // [6, 8, 10]
+//! ```
+//!
+//! Sources are arrays or typed arrays. `interpose` and `zip`, whose
+//! arguments are arbitrary JavaScript values, are only available in the WASM
+//! build.
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsNumber, JsObject, JsUnknown, NapiRaw, Task, ValueType};
+use napi_derive::napi;
+use orlando_transducers::plan::{Plan, PlanStep};
+use orlando_transducers::simd::{self, NumericOp};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+type Callback = Rc<FunctionRef<JsUnknown, JsUnknown>>;
+type Reducer = FunctionRef<(JsUnknown, JsUnknown), JsUnknown>;
+
+/// One operation of a [`Pipeline`].
+///
+/// `map` and `filter` keep the name they were registered under, if any, so
+/// they can be written to a plan.
+#[derive(Clone)]
+enum Operation {
+    Map(Callback, Option<String>),
+    Filter(Callback, Option<String>),
+    Reject(Callback),
+    FlatMap(Callback),
+    Tap(Callback),
+    Take(usize),
+    TakeWhile(Callback),
+    Drop(usize),
+    DropWhile(Callback),
+    /// Drop consecutive duplicates
+    Unique,
+    /// Group into arrays of fixed size; a trailing partial chunk is flushed at the end
+    Chunk(usize),
+    /// Sliding windows of fixed size
+    Aperture(usize),
+    MapAdd(f64),
+    MapMul(f64),
+    ScaleOffset(f64, f64),
+    Normalize(f64, f64),
+    FilterGt(f64),
+    FilterRange(f64, f64),
+}
+
+impl Operation {
+    /// Name used in plans and error messages.
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::Map(..) => "map",
+            Operation::Filter(..) => "filter",
+            Operation::Reject(_) => "reject",
+            Operation::FlatMap(_) => "flatMap",
+            Operation::Tap(_) => "tap",
+            Operation::Take(_) => "take",
+            Operation::TakeWhile(_) => "takeWhile",
+            Operation::Drop(_) => "drop",
+            Operation::DropWhile(_) => "dropWhile",
+            Operation::Unique => "unique",
+            Operation::Chunk(_) => "chunk",
+            Operation::Aperture(_) => "aperture",
+            Operation::MapAdd(_) => "mapAdd",
+            Operation::MapMul(_) => "mapMul",
+            Operation::ScaleOffset(..) => "scaleOffset",
+            Operation::Normalize(..) => "normalize",
+            Operation::FilterGt(_) => "filterGt",
+            Operation::FilterRange(..) => "filterRange",
+        }
+    }
+
+    /// The operation as a kernel of [`simd::run_f64`], if it runs on raw
+    /// `f64` values without calling back into JavaScript.
+    fn numeric(&self) -> Option<NumericOp<f64>> {
+        Some(match *self {
+            Operation::Take(n) => NumericOp::Take(n),
+            Operation::Drop(n) => NumericOp::Drop(n),
+            Operation::Unique => NumericOp::Unique,
+            Operation::MapAdd(n) => NumericOp::Add(n),
+            Operation::MapMul(n) => NumericOp::Mul(n),
+            Operation::ScaleOffset(a, b) => NumericOp::ScaleOffset(a, b),
+            Operation::Normalize(mean, std) => NumericOp::Normalize(mean, std),
+            Operation::FilterGt(n) => NumericOp::FilterGt(n),
+            Operation::FilterRange(lo, hi) => NumericOp::FilterRange(lo, hi),
+            _ => return None,
+        })
+    }
+
+    fn to_plan_step(&self, position: usize) -> Result<PlanStep> {
+        Ok(match self {
+            Operation::Take(n) => PlanStep::Take { n: *n },
+            Operation::Drop(n) => PlanStep::Drop { n: *n },
+            Operation::Chunk(n) => PlanStep::Chunk { n: *n },
+            Operation::Aperture(n) => PlanStep::Aperture { n: *n },
+            Operation::Unique => PlanStep::Unique,
+            Operation::MapAdd(n) => PlanStep::MapAdd { n: *n },
+            Operation::MapMul(n) => PlanStep::MapMul { n: *n },
+            Operation::ScaleOffset(a, b) => PlanStep::ScaleOffset { a: *a, b: *b },
+            Operation::Normalize(mean, std) => PlanStep::Normalize {
+                mean: *mean,
+                std: *std,
+            },
+            Operation::FilterGt(n) => PlanStep::FilterGt { n: *n },
+            Operation::FilterRange(lo, hi) => PlanStep::FilterRange { lo: *lo, hi: *hi },
+            Operation::Map(_, Some(name)) => PlanStep::Map {
+                function: name.clone(),
+            },
+            Operation::Filter(_, Some(name)) => PlanStep::Filter {
+                function: name.clone(),
+            },
+            other => {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!(
+                        "operation {} ({}) calls an unnamed JavaScript function and cannot be serialized",
+                        position,
+                        other.name()
+                    ),
+                ))
+            }
+        })
+    }
+
+    fn from_plan_step(step: PlanStep, functions: Option<&JsObject>) -> Result<Self> {
+        let lookup = |name: &str| -> Result<Callback> {
+            match functions {
+                Some(functions) if functions.has_named_property(name)? => Ok(Rc::new(
+                    functions
+                        .get_named_property::<Function<JsUnknown, JsUnknown>>(name)?
+                        .create_ref()?,
+                )),
+                _ => Err(invalid_plan(format!(
+                    "plan refers to unknown function `{}`",
+                    name
+                ))),
+            }
+        };
+
+        Ok(match step {
+            PlanStep::Take { n } => Operation::Take(n),
+            PlanStep::Drop { n } => Operation::Drop(n),
+            PlanStep::Chunk { n } => Operation::Chunk(n.max(1)),
+            PlanStep::Aperture { n } => Operation::Aperture(n.max(1)),
+            PlanStep::Unique => Operation::Unique,
+            PlanStep::MapAdd { n } => Operation::MapAdd(n),
+            PlanStep::MapMul { n } => Operation::MapMul(n),
+            PlanStep::ScaleOffset { a, b } => Operation::ScaleOffset(a, b),
+            PlanStep::Normalize { mean, std } => Operation::Normalize(mean, std),
+            PlanStep::FilterGt { n } => Operation::FilterGt(n),
+            PlanStep::FilterRange { lo, hi } => Operation::FilterRange(lo, hi),
+            PlanStep::Map { function } => Operation::Map(lookup(&function)?, Some(function)),
+            PlanStep::Filter { function } => Operation::Filter(lookup(&function)?, Some(function)),
+            PlanStep::Interpose { .. } | PlanStep::Zip { .. } => {
+                return Err(invalid_plan(
+                    "`interpose` and `zip` steps are only supported by the WASM build",
+                ))
+            }
+        })
+    }
+}
+
+fn invalid_plan(message: impl Into<String>) -> Error {
+    Error::new(Status::InvalidArg, message.into())
+}
+
+/// A second handle to the same JavaScript value.
+fn dup(value: &JsUnknown) -> JsUnknown {
+    // SAFETY: `JsUnknown` accepts a value of any type
+    unsafe { value.cast() }
+}
+
+/// The contents of `source` if it is a `Float64Array`, read in place.
+fn float64_slice<'a>(env: &Env, source: &'a JsObject) -> Option<&'a [f64]> {
+    // SAFETY: the slice borrows the typed array's buffer, which `source`
+    // keeps alive
+    unsafe { <&[f64]>::from_napi_value(env.raw(), source.raw()) }.ok()
+}
+
+fn truthy(value: JsUnknown) -> Result<bool> {
+    value.coerce_to_bool()?.get_value()
+}
+
+/// The value as a number, or NaN for non-numbers.
+fn as_f64(value: &JsUnknown) -> Result<f64> {
+    if value.get_type()? != ValueType::Number {
+        return Ok(f64::NAN);
+    }
+    // SAFETY: checked to be a number
+    unsafe { value.cast::<JsNumber>() }.get_double()
+}
+
+/// Whether a run should keep pulling elements from the source.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Stop,
+}
+
+/// Per-run state of the stateful operations, indexed by operation position.
+///
+/// Created fresh for every run, so a pipeline can be reused.
+struct State {
+    counts: Vec<usize>,
+    flags: Vec<bool>,
+    buffers: Vec<VecDeque<JsUnknown>>,
+    last: Vec<Option<JsUnknown>>,
+    /// The furthest downstream operation that stopped early; chunks at or
+    /// before it are never flushed
+    stopped_at: Option<usize>,
+}
+
+impl State {
+    fn new(len: usize) -> Self {
+        State {
+            counts: vec![0; len],
+            flags: vec![false; len],
+            buffers: (0..len).map(|_| VecDeque::new()).collect(),
+            last: (0..len).map(|_| None).collect(),
+            stopped_at: None,
+        }
+    }
+
+    /// Record that the operation at `idx` stopped early.
+    fn stop_at(&mut self, idx: usize) -> Result<Flow> {
+        self.stopped_at = Some(self.stopped_at.map_or(idx, |at| at.max(idx)));
+        Ok(Flow::Stop)
+    }
+}
+
+type Sink<'a> = dyn FnMut(JsUnknown) -> Result<Flow> + 'a;
+
+/// Runs a numeric pipeline over a copy of a `Float64Array` on the libuv
+/// thread pool.
+pub struct NumericRun {
+    plan: Vec<NumericOp<f64>>,
+    data: Vec<f64>,
+}
+
+impl Task for NumericRun {
+    type Output = Vec<f64>;
+    type JsValue = Float64Array;
+
+    fn compute(&mut self) -> Result<Vec<f64>> {
+        Ok(simd::run_f64(&self.plan, &self.data))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Vec<f64>) -> Result<Float64Array> {
+        Ok(Float64Array::new(output))
+    }
+}
+
+/// A composition of transducers applied to data in a single pass.
+///
+/// Every builder method returns a new pipeline, leaving the original
+/// unchanged.
+#[napi]
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    operations: Vec<Operation>,
+}
+
+impl Pipeline {
+    fn with(&self, op: Operation) -> Pipeline {
+        let mut operations = self.operations.clone();
+        operations.push(op);
+        Pipeline { operations }
+    }
+
+    /// The pipeline as a numeric plan, if every operation has a kernel.
+    fn numeric_plan(&self) -> Option<Vec<NumericOp<f64>>> {
+        self.operations.iter().map(Operation::numeric).collect()
+    }
+
+    /// Pass `value` through the operations from `from` onwards, handing
+    /// whatever comes out to `sink`.
+    fn push(
+        &self,
+        env: &Env,
+        mut value: JsUnknown,
+        from: usize,
+        state: &mut State,
+        sink: &mut Sink<'_>,
+    ) -> Result<Flow> {
+        let call = |f: &Callback, value: &JsUnknown| f.borrow_back(env)?.call(dup(value));
+        for idx in from..self.operations.len() {
+            match &self.operations[idx] {
+                Operation::Map(f, _) => value = call(f, &value)?,
+                Operation::Filter(pred, _) => {
+                    if !truthy(call(pred, &value)?)? {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::Reject(pred) => {
+                    if truthy(call(pred, &value)?)? {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::FlatMap(f) => {
+                    let items = call(f, &value)?.coerce_to_object()?;
+                    for i in 0..items.get_array_length()? {
+                        let item = items.get_element::<JsUnknown>(i)?;
+                        if self.push(env, item, idx + 1, state, sink)? == Flow::Stop {
+                            return Ok(Flow::Stop);
+                        }
+                    }
+                    return Ok(Flow::Continue);
+                }
+                Operation::Tap(f) => {
+                    call(f, &value)?;
+                }
+                Operation::Take(n) => {
+                    let taken = &mut state.counts[idx];
+                    if *taken >= *n {
+                        return state.stop_at(idx);
+                    }
+                    *taken += 1;
+                    // Stop as soon as the last element is through, without
+                    // pulling another one
+                    if *taken == *n {
+                        self.push(env, value, idx + 1, state, sink)?;
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::TakeWhile(pred) => {
+                    if !truthy(call(pred, &value)?)? {
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::Drop(n) => {
+                    if state.counts[idx] < *n {
+                        state.counts[idx] += 1;
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::DropWhile(pred) => {
+                    if !state.flags[idx] {
+                        if truthy(call(pred, &value)?)? {
+                            return Ok(Flow::Continue);
+                        }
+                        state.flags[idx] = true;
+                    }
+                }
+                Operation::Unique => {
+                    if let Some(last) = &state.last[idx] {
+                        if env.strict_equals(dup(last), dup(&value))? {
+                            return Ok(Flow::Continue);
+                        }
+                    }
+                    state.last[idx] = Some(dup(&value));
+                }
+                Operation::Chunk(n) => {
+                    let buffer = &mut state.buffers[idx];
+                    buffer.push_back(value);
+                    if buffer.len() < *n {
+                        return Ok(Flow::Continue);
+                    }
+                    value = to_js_array(env, buffer.drain(..))?;
+                }
+                Operation::Aperture(n) => {
+                    let window = &mut state.buffers[idx];
+                    window.push_back(value);
+                    if window.len() > *n {
+                        window.pop_front();
+                    }
+                    if window.len() < *n {
+                        return Ok(Flow::Continue);
+                    }
+                    value = to_js_array(env, window.iter().map(dup))?;
+                }
+                Operation::MapAdd(n) => value = number(env, as_f64(&value)? + n)?,
+                Operation::MapMul(n) => value = number(env, as_f64(&value)? * n)?,
+                Operation::ScaleOffset(a, b) => value = number(env, a * as_f64(&value)? + b)?,
+                Operation::Normalize(mean, std) => {
+                    value = number(env, (as_f64(&value)? - mean) / std)?
+                }
+                Operation::FilterGt(n) => {
+                    let x = as_f64(&value)?;
+                    if x.is_nan() || x <= *n {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::FilterRange(lo, hi) => {
+                    let x = as_f64(&value)?;
+                    if !(*lo <= x && x < *hi) {
+                        return Ok(Flow::Continue);
+                    }
+                }
+            }
+        }
+        sink(value)
+    }
+
+    /// Run the pipeline over an array or typed array, handing each output to
+    /// `sink` until either runs out or asks to stop.
+    fn run(&self, env: &Env, source: &JsObject, sink: &mut Sink<'_>) -> Result<()> {
+        let mut state = State::new(self.operations.len());
+        // Whether `sink` itself asked to stop, ruling out any flush
+        let sink_stopped = Cell::new(false);
+        let mut sink = |value| {
+            let flow = sink(value)?;
+            if flow == Flow::Stop {
+                sink_stopped.set(true);
+            }
+            Ok(flow)
+        };
+        let len = source
+            .get_named_property::<JsNumber>("length")?
+            .get_uint32()?;
+        for i in 0..len {
+            let item = source.get_element::<JsUnknown>(i)?;
+            if self.push(env, item, 0, &mut state, &mut sink)? == Flow::Stop {
+                break;
+            }
+        }
+
+        // Flush partial chunks, upstream first so their output can fill the
+        // chunks after them. An operation that stopped early only cuts off
+        // the chunks before it.
+        for idx in 0..self.operations.len() {
+            if sink_stopped.get() {
+                break;
+            }
+            if matches!(self.operations[idx], Operation::Chunk(_))
+                && !state.buffers[idx].is_empty()
+                && state.stopped_at.is_none_or(|at| idx > at)
+            {
+                let rest = to_js_array(env, state.buffers[idx].drain(..))?;
+                self.push(env, rest, idx + 1, &mut state, &mut sink)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn or_undefined(env: &Env, value: Option<JsUnknown>) -> Result<JsUnknown> {
+    match value {
+        Some(value) => Ok(value),
+        None => Ok(env.get_undefined()?.into_unknown()),
+    }
+}
+
+fn number(env: &Env, x: f64) -> Result<JsUnknown> {
+    Ok(env.create_double(x)?.into_unknown())
+}
+
+fn to_js_array(env: &Env, items: impl Iterator<Item = JsUnknown>) -> Result<JsUnknown> {
+    let mut array = env.create_array_with_length(0)?;
+    for (i, item) in items.enumerate() {
+        array.set_element(i as u32, item)?;
+    }
+    Ok(array.into_unknown())
+}
+
+#[napi]
+impl Pipeline {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transform each element. Give a `name` to make the step serializable
+    /// with `toPlan`.
+    #[napi]
+    pub fn map(&self, f: FunctionRef<JsUnknown, JsUnknown>, name: Option<String>) -> Pipeline {
+        self.with(Operation::Map(Rc::new(f), name))
+    }
+
+    /// Keep elements for which `pred` is truthy. Give a `name` to make the
+    /// step serializable with `toPlan`.
+    #[napi]
+    pub fn filter(
+        &self,
+        pred: FunctionRef<JsUnknown, JsUnknown>,
+        name: Option<String>,
+    ) -> Pipeline {
+        self.with(Operation::Filter(Rc::new(pred), name))
+    }
+
+    /// Drop elements for which `pred` is truthy.
+    #[napi]
+    pub fn reject(&self, pred: FunctionRef<JsUnknown, JsUnknown>) -> Pipeline {
+        self.with(Operation::Reject(Rc::new(pred)))
+    }
+
+    /// Map each element to an array and flatten the results.
+    #[napi]
+    pub fn flat_map(&self, f: FunctionRef<JsUnknown, JsUnknown>) -> Pipeline {
+        self.with(Operation::FlatMap(Rc::new(f)))
+    }
+
+    /// Call `f` on each element for its side effects.
+    #[napi]
+    pub fn tap(&self, f: FunctionRef<JsUnknown, JsUnknown>) -> Pipeline {
+        self.with(Operation::Tap(Rc::new(f)))
+    }
+
+    /// Keep the first `n` elements, then stop.
+    #[napi]
+    pub fn take(&self, n: u32) -> Pipeline {
+        self.with(Operation::Take(n as usize))
+    }
+
+    /// Keep elements while `pred` is truthy, then stop.
+    #[napi]
+    pub fn take_while(&self, pred: FunctionRef<JsUnknown, JsUnknown>) -> Pipeline {
+        self.with(Operation::TakeWhile(Rc::new(pred)))
+    }
+
+    /// Skip the first `n` elements.
+    #[napi]
+    pub fn drop(&self, n: u32) -> Pipeline {
+        self.with(Operation::Drop(n as usize))
+    }
+
+    /// Skip elements while `pred` is truthy.
+    #[napi]
+    pub fn drop_while(&self, pred: FunctionRef<JsUnknown, JsUnknown>) -> Pipeline {
+        self.with(Operation::DropWhile(Rc::new(pred)))
+    }
+
+    /// Drop consecutive duplicates (compared with `===`).
+    #[napi]
+    pub fn unique(&self) -> Pipeline {
+        self.with(Operation::Unique)
+    }
+
+    /// Group elements into arrays of `n`; a trailing partial chunk is
+    /// emitted at the end.
+    #[napi]
+    pub fn chunk(&self, n: u32) -> Pipeline {
+        self.with(Operation::Chunk(n.max(1) as usize))
+    }
+
+    /// Sliding windows of `n` elements.
+    #[napi]
+    pub fn aperture(&self, n: u32) -> Pipeline {
+        self.with(Operation::Aperture(n.max(1) as usize))
+    }
+
+    /// Add `n` to every element, without calling back into JavaScript.
+    #[napi]
+    pub fn map_add(&self, n: f64) -> Pipeline {
+        self.with(Operation::MapAdd(n))
+    }
+
+    /// Multiply every element by `n`, without calling back into JavaScript.
+    #[napi]
+    pub fn map_mul(&self, n: f64) -> Pipeline {
+        self.with(Operation::MapMul(n))
+    }
+
+    /// `a * x + b` for every element, without calling back into JavaScript.
+    #[napi]
+    pub fn scale_offset(&self, a: f64, b: f64) -> Pipeline {
+        self.with(Operation::ScaleOffset(a, b))
+    }
+
+    /// `(x - mean) / std` for every element, without calling back into
+    /// JavaScript.
+    #[napi]
+    pub fn normalize(&self, mean: f64, std: f64) -> Pipeline {
+        self.with(Operation::Normalize(mean, std))
+    }
+
+    /// Keep numbers greater than `n`, without calling back into JavaScript.
+    #[napi]
+    pub fn filter_gt(&self, n: f64) -> Pipeline {
+        self.with(Operation::FilterGt(n))
+    }
+
+    /// Keep numbers in the half-open range `[lo, hi)`, without calling back
+    /// into JavaScript.
+    #[napi]
+    pub fn filter_range(&self, lo: f64, hi: f64) -> Pipeline {
+        self.with(Operation::FilterRange(lo, hi))
+    }
+
+    /// Run the pipeline and collect the results into an array.
+    #[napi]
+    pub fn to_array(&self, env: Env, source: JsObject) -> Result<JsUnknown> {
+        let mut out = env.create_array_with_length(0)?;
+        let mut len = 0;
+        self.run(&env, &source, &mut |value| {
+            out.set_element(len, value)?;
+            len += 1;
+            Ok(Flow::Continue)
+        })?;
+        Ok(out.into_unknown())
+    }
+
+    /// Run the pipeline and collect the numeric results into a
+    /// `Float64Array`, skipping non-numbers.
+    ///
+    /// Pipelines of `take`, `drop`, `unique` and the numeric operations read
+    /// a `Float64Array` source in place without calling into JavaScript.
+    #[napi]
+    pub fn to_float64_array(&self, env: Env, source: JsObject) -> Result<Float64Array> {
+        if let Some(plan) = self.numeric_plan() {
+            if let Some(data) = float64_slice(&env, &source) {
+                return Ok(Float64Array::new(simd::run_f64(&plan, data)));
+            }
+        }
+        let mut out = Vec::new();
+        self.run(&env, &source, &mut |value| {
+            if value.get_type()? == ValueType::Number {
+                out.push(as_f64(&value)?);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(Float64Array::new(out))
+    }
+
+    /// Run a numeric pipeline over a `Float64Array` on the libuv thread
+    /// pool, resolving with a `Float64Array`.
+    ///
+    /// The source is copied before the main thread returns, so it may be
+    /// modified while the run is in flight. Pipelines with callbacks can't
+    /// leave the main thread and are rejected.
+    #[napi(ts_return_type = "Promise<Float64Array>")]
+    pub fn run_in_worker(&self, source: Float64Array) -> Result<AsyncTask<NumericRun>> {
+        let plan = self.numeric_plan().ok_or_else(|| {
+            Error::new(
+                Status::InvalidArg,
+                "runInWorker only runs pipelines of take, drop, unique and numeric operations",
+            )
+        })?;
+        Ok(AsyncTask::new(NumericRun {
+            plan,
+            data: source.to_vec(),
+        }))
+    }
+
+    /// Fold the results with `reducer(acc, value)`, starting from `initial`.
+    #[napi]
+    pub fn reduce(
+        &self,
+        env: Env,
+        source: JsObject,
+        reducer: Reducer,
+        initial: JsUnknown,
+    ) -> Result<JsUnknown> {
+        let reducer = reducer.borrow_back(&env)?;
+        let mut acc = Some(initial);
+        self.run(&env, &source, &mut |value| {
+            let prev = acc.take().expect("accumulator is restored after each call");
+            acc = Some(reducer.call((prev, value))?);
+            Ok(Flow::Continue)
+        })?;
+        Ok(acc.expect("accumulator is restored after each call"))
+    }
+
+    /// Number of results.
+    #[napi]
+    pub fn count(&self, env: Env, source: JsObject) -> Result<u32> {
+        let mut count = 0;
+        self.run(&env, &source, &mut |_| {
+            count += 1;
+            Ok(Flow::Continue)
+        })?;
+        Ok(count)
+    }
+
+    /// Sum of the results; non-numbers count as NaN.
+    #[napi]
+    pub fn sum(&self, env: Env, source: JsObject) -> Result<f64> {
+        if let Some(plan) = self.numeric_plan() {
+            if let Some(data) = float64_slice(&env, &source) {
+                return Ok(simd::sum(&simd::run_f64(&plan, data)));
+            }
+        }
+        let mut total = 0.0;
+        self.run(&env, &source, &mut |value| {
+            total += as_f64(&value)?;
+            Ok(Flow::Continue)
+        })?;
+        Ok(total)
+    }
+
+    /// The first result, or `undefined`. Stops after one result.
+    #[napi]
+    pub fn first(&self, env: Env, source: JsObject) -> Result<JsUnknown> {
+        let mut found = None;
+        self.run(&env, &source, &mut |value| {
+            found = Some(value);
+            Ok(Flow::Stop)
+        })?;
+        or_undefined(&env, found)
+    }
+
+    /// The first result matching `pred`, or `undefined`.
+    #[napi]
+    pub fn find(
+        &self,
+        env: Env,
+        source: JsObject,
+        pred: FunctionRef<JsUnknown, JsUnknown>,
+    ) -> Result<JsUnknown> {
+        let pred = pred.borrow_back(&env)?;
+        let mut found = None;
+        self.run(&env, &source, &mut |value| {
+            if truthy(pred.call(dup(&value))?)? {
+                found = Some(value);
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        or_undefined(&env, found)
+    }
+
+    /// Whether `pred` is truthy for every result. Stops at the first failure.
+    #[napi]
+    pub fn every(
+        &self,
+        env: Env,
+        source: JsObject,
+        pred: FunctionRef<JsUnknown, JsUnknown>,
+    ) -> Result<bool> {
+        let pred = pred.borrow_back(&env)?;
+        let mut all = true;
+        self.run(&env, &source, &mut |value| {
+            if !truthy(pred.call(value)?)? {
+                all = false;
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(all)
+    }
+
+    /// Whether `pred` is truthy for any result. Stops at the first match.
+    #[napi]
+    pub fn some(
+        &self,
+        env: Env,
+        source: JsObject,
+        pred: FunctionRef<JsUnknown, JsUnknown>,
+    ) -> Result<bool> {
+        let pred = pred.borrow_back(&env)?;
+        let mut any = false;
+        self.run(&env, &source, &mut |value| {
+            if truthy(pred.call(value)?)? {
+                any = true;
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(any)
+    }
+
+    /// Serialize the pipeline as a versioned JSON plan, as the WASM
+    /// `toPlanJson` does. `map` and `filter` steps need a `name`.
+    #[napi]
+    pub fn to_plan_json(&self) -> Result<String> {
+        let steps = self
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(position, op)| op.to_plan_step(position))
+            .collect::<Result<_>>()?;
+        Ok(Plan::new(steps).to_json())
+    }
+
+    /// Rebuild a pipeline from a JSON plan, looking up the functions named
+    /// by `map` and `filter` steps in `functions`.
+    #[napi]
+    pub fn from_plan(plan: String, functions: Option<JsObject>) -> Result<Pipeline> {
+        let plan = Plan::from_json(&plan).map_err(|e| invalid_plan(e.to_string()))?;
+        let operations = plan
+            .steps
+            .into_iter()
+            .map(|step| Operation::from_plan_step(step, functions.as_ref()))
+            .collect::<Result<_>>()?;
+        Ok(Pipeline { operations })
+    }
+}
+
+/// Create a new pipeline.
+#[napi]
+pub fn pipeline() -> Pipeline {
+    Pipeline::new()
+}
@@ -0,0 +1,51 @@
+from typing import Any, Callable, Generic, Iterable, Mapping, Optional, TypeVar
+
+import numpy as np
+import numpy.typing as npt
+
+In = TypeVar("In")
+Out = TypeVar("Out")
+U = TypeVar("U")
+A = TypeVar("A")
+
+class Pipeline(Generic[In, Out]):
+    """A composition of operations applied to data in a single pass."""
+
+    def __init__(self) -> None: ...
+
+    def map(self, f: Callable[[Out], U], name: Optional[str] = None) -> "Pipeline[In, U]": ...
+    def filter(self, pred: Callable[[Out], Any], name: Optional[str] = None) -> "Pipeline[In, Out]": ...
+    def reject(self, pred: Callable[[Out], Any]) -> "Pipeline[In, Out]": ...
+    def flat_map(self, f: Callable[[Out], Iterable[U]]) -> "Pipeline[In, U]": ...
+    def tap(self, f: Callable[[Out], Any]) -> "Pipeline[In, Out]": ...
+    def take(self, n: int) -> "Pipeline[In, Out]": ...
+    def take_while(self, pred: Callable[[Out], Any]) -> "Pipeline[In, Out]": ...
+    def drop(self, n: int) -> "Pipeline[In, Out]": ...
+    def drop_while(self, pred: Callable[[Out], Any]) -> "Pipeline[In, Out]": ...
+    def unique(self) -> "Pipeline[In, Out]": ...
+    def chunk(self, n: int) -> "Pipeline[In, list[Out]]": ...
+    def aperture(self, n: int) -> "Pipeline[In, list[Out]]": ...
+    def interpose(self, separator: U) -> "Pipeline[In, Out | U]": ...
+    def zip(self, other: Iterable[U]) -> "Pipeline[In, tuple[Out, U]]": ...
+    def map_add(self, n: float) -> "Pipeline[In, float]": ...
+    def map_mul(self, n: float) -> "Pipeline[In, float]": ...
+    def scale_offset(self, a: float, b: float) -> "Pipeline[In, float]": ...
+    def normalize(self, mean: float, std: float) -> "Pipeline[In, float]": ...
+    def filter_gt(self, n: float) -> "Pipeline[In, Out]": ...
+    def filter_range(self, lo: float, hi: float) -> "Pipeline[In, Out]": ...
+
+    def to_list(self, source: Iterable[In]) -> list[Out]: ...
+    def to_numpy(self, source: Iterable[In]) -> npt.NDArray[np.float64]: ...
+    def reduce(self, source: Iterable[In], f: Callable[[A, Out], A], initial: A) -> A: ...
+    def count(self, source: Iterable[In]) -> int: ...
+    def sum(self, source: Iterable[In]) -> Any: ...
+    def first(self, source: Iterable[In]) -> Optional[Out]: ...
+    def find(self, source: Iterable[In], pred: Callable[[Out], Any]) -> Optional[Out]: ...
+    def every(self, source: Iterable[In], pred: Callable[[Out], Any]) -> bool: ...
+    def some(self, source: Iterable[In], pred: Callable[[Out], Any]) -> bool: ...
+
+    def to_plan(self) -> str: ...
+    @staticmethod
+    def from_plan(
+        plan: str, functions: Optional[Mapping[str, Callable[..., Any]]] = None
+    ) -> "Pipeline[Any, Any]": ...
@@ -0,0 +1,756 @@
+//! Python bindings for the Orlando pipeline engine.
+//!
+//! Exposes a `Pipeline` class that mirrors the WASM `Pipeline`: a fluent,
+//! immutable builder of operations run in a single pass over any Python
+//! iterable, with early termination. Pipelines of numeric operations over
+//! `float64` NumPy arrays skip the per-element Python calls and run on the
+//! array's buffer with the `simd` kernels.
+//!
+//! ```python
+//! from orlando import Pipeline
+//!
+//! pipeline = Pipeline().map(lambda x: x * 2).filter(lambda x: x > 5).take(3)
+//! pipeline.to_list(range(100))  # [6, 8, 10]
+//! ```
+//!
+//! Pipelines convert to and from the versioned plans of
+//! [`orlando_transducers::plan`], so the same definition runs in Python, in
+//! the browser and natively.
+
+use numpy::{PyArray1, PyArrayMethods};
+use orlando_transducers::plan::{Plan, PlanStep};
+use orlando_transducers::simd::{self, NumericOp};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat, PyList, PyTuple};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// One operation of a [`Pipeline`].
+///
+/// `map` and `filter` keep the name they were registered under, if any, so
+/// they can be written to a plan.
+enum Operation {
+    Map(Py<PyAny>, Option<String>),
+    Filter(Py<PyAny>, Option<String>),
+    Reject(Py<PyAny>),
+    FlatMap(Py<PyAny>),
+    Tap(Py<PyAny>),
+    Take(usize),
+    TakeWhile(Py<PyAny>),
+    Drop(usize),
+    DropWhile(Py<PyAny>),
+    /// Drop consecutive duplicates
+    Unique,
+    /// Group into lists of fixed size; a trailing partial chunk is flushed at the end
+    Chunk(usize),
+    /// Sliding windows of fixed size
+    Aperture(usize),
+    /// Insert a separator between elements
+    Interpose(Py<PyAny>),
+    /// Pair elements with the same position in a list, stopping at its end
+    Zip(Vec<Py<PyAny>>),
+    MapAdd(f64),
+    MapMul(f64),
+    ScaleOffset(f64, f64),
+    Normalize(f64, f64),
+    FilterGt(f64),
+    FilterRange(f64, f64),
+}
+
+impl Operation {
+    /// Name used in plans and error messages.
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::Map(..) => "map",
+            Operation::Filter(..) => "filter",
+            Operation::Reject(_) => "reject",
+            Operation::FlatMap(_) => "flat_map",
+            Operation::Tap(_) => "tap",
+            Operation::Take(_) => "take",
+            Operation::TakeWhile(_) => "take_while",
+            Operation::Drop(_) => "drop",
+            Operation::DropWhile(_) => "drop_while",
+            Operation::Unique => "unique",
+            Operation::Chunk(_) => "chunk",
+            Operation::Aperture(_) => "aperture",
+            Operation::Interpose(_) => "interpose",
+            Operation::Zip(_) => "zip",
+            Operation::MapAdd(_) => "map_add",
+            Operation::MapMul(_) => "map_mul",
+            Operation::ScaleOffset(..) => "scale_offset",
+            Operation::Normalize(..) => "normalize",
+            Operation::FilterGt(_) => "filter_gt",
+            Operation::FilterRange(..) => "filter_range",
+        }
+    }
+
+    /// The operation as a kernel of [`simd::run_f64`], if it runs on raw
+    /// `f64` values without calling back into Python.
+    fn numeric(&self) -> Option<NumericOp<f64>> {
+        Some(match *self {
+            Operation::Take(n) => NumericOp::Take(n),
+            Operation::Drop(n) => NumericOp::Drop(n),
+            Operation::Unique => NumericOp::Unique,
+            Operation::MapAdd(n) => NumericOp::Add(n),
+            Operation::MapMul(n) => NumericOp::Mul(n),
+            Operation::ScaleOffset(a, b) => NumericOp::ScaleOffset(a, b),
+            Operation::Normalize(mean, std) => NumericOp::Normalize(mean, std),
+            Operation::FilterGt(n) => NumericOp::FilterGt(n),
+            Operation::FilterRange(lo, hi) => NumericOp::FilterRange(lo, hi),
+            _ => return None,
+        })
+    }
+
+    fn to_plan_step(&self, py: Python<'_>, position: usize) -> PyResult<PlanStep> {
+        Ok(match self {
+            Operation::Take(n) => PlanStep::Take { n: *n },
+            Operation::Drop(n) => PlanStep::Drop { n: *n },
+            Operation::Chunk(n) => PlanStep::Chunk { n: *n },
+            Operation::Aperture(n) => PlanStep::Aperture { n: *n },
+            Operation::Unique => PlanStep::Unique,
+            Operation::Interpose(separator) => PlanStep::Interpose {
+                separator: to_json(separator.bind(py))?,
+            },
+            Operation::Zip(other) => PlanStep::Zip {
+                other: other
+                    .iter()
+                    .map(|item| to_json(item.bind(py)))
+                    .collect::<PyResult<_>>()?,
+            },
+            Operation::MapAdd(n) => PlanStep::MapAdd { n: *n },
+            Operation::MapMul(n) => PlanStep::MapMul { n: *n },
+            Operation::ScaleOffset(a, b) => PlanStep::ScaleOffset { a: *a, b: *b },
+            Operation::Normalize(mean, std) => PlanStep::Normalize {
+                mean: *mean,
+                std: *std,
+            },
+            Operation::FilterGt(n) => PlanStep::FilterGt { n: *n },
+            Operation::FilterRange(lo, hi) => PlanStep::FilterRange { lo: *lo, hi: *hi },
+            Operation::Map(_, Some(name)) => PlanStep::Map {
+                function: name.clone(),
+            },
+            Operation::Filter(_, Some(name)) => PlanStep::Filter {
+                function: name.clone(),
+            },
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "operation {} ({}) calls an unnamed Python function and cannot be serialized",
+                    position,
+                    other.name()
+                )))
+            }
+        })
+    }
+
+    fn from_plan_step(
+        py: Python<'_>,
+        step: PlanStep,
+        functions: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let py_err = |message: String| PyValueError::new_err(message);
+        let lookup = |name: &str| -> PyResult<Py<PyAny>> {
+            functions
+                .map(|functions| functions.get_item(name))
+                .transpose()?
+                .flatten()
+                .map(Bound::unbind)
+                .ok_or_else(|| py_err(format!("plan refers to unknown function `{}`", name)))
+        };
+
+        Ok(match step {
+            PlanStep::Take { n } => Operation::Take(n),
+            PlanStep::Drop { n } => Operation::Drop(n),
+            PlanStep::Chunk { n } => Operation::Chunk(n.max(1)),
+            PlanStep::Aperture { n } => Operation::Aperture(n.max(1)),
+            PlanStep::Unique => Operation::Unique,
+            PlanStep::Interpose { separator } => {
+                Operation::Interpose(from_json(py, &separator)?.unbind())
+            }
+            PlanStep::Zip { other } => Operation::Zip(
+                other
+                    .iter()
+                    .map(|item| from_json(py, item).map(Bound::unbind))
+                    .collect::<PyResult<_>>()?,
+            ),
+            PlanStep::MapAdd { n } => Operation::MapAdd(n),
+            PlanStep::MapMul { n } => Operation::MapMul(n),
+            PlanStep::ScaleOffset { a, b } => Operation::ScaleOffset(a, b),
+            PlanStep::Normalize { mean, std } => Operation::Normalize(mean, std),
+            PlanStep::FilterGt { n } => Operation::FilterGt(n),
+            PlanStep::FilterRange { lo, hi } => Operation::FilterRange(lo, hi),
+            PlanStep::Map { function } => Operation::Map(lookup(&function)?, Some(function)),
+            PlanStep::Filter { function } => Operation::Filter(lookup(&function)?, Some(function)),
+        })
+    }
+}
+
+/// Convert a Python value to JSON through the `json` module.
+fn to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    let json = value.py().import("json")?.call_method1("dumps", (value,))?;
+    serde_json::from_str(&json.extract::<String>()?)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn from_json<'py>(py: Python<'py>, value: &serde_json::Value) -> PyResult<Bound<'py, PyAny>> {
+    py.import("json")?
+        .call_method1("loads", (value.to_string(),))
+}
+
+/// Whether a run should keep pulling elements from the source.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Stop,
+}
+
+/// Per-run state of the stateful operations, indexed by operation position.
+///
+/// Created fresh for every run, so a pipeline can be reused.
+struct State<'py> {
+    counts: Vec<usize>,
+    flags: Vec<bool>,
+    buffers: Vec<VecDeque<Bound<'py, PyAny>>>,
+    last: Vec<Option<Bound<'py, PyAny>>>,
+    /// The furthest downstream operation that stopped early; chunks at or
+    /// before it are never flushed
+    stopped_at: Option<usize>,
+}
+
+impl State<'_> {
+    fn new(len: usize) -> Self {
+        State {
+            counts: vec![0; len],
+            flags: vec![false; len],
+            buffers: (0..len).map(|_| VecDeque::new()).collect(),
+            last: vec![None; len],
+            stopped_at: None,
+        }
+    }
+
+    /// Record that the operation at `idx` stopped early.
+    fn stop_at(&mut self, idx: usize) -> PyResult<Flow> {
+        self.stopped_at = Some(self.stopped_at.map_or(idx, |at| at.max(idx)));
+        Ok(Flow::Stop)
+    }
+}
+
+type Sink<'a, 'py> = dyn FnMut(Bound<'py, PyAny>) -> PyResult<Flow> + 'a;
+
+/// A composition of operations applied to data in a single pass.
+///
+/// Every builder method returns a new pipeline, leaving the original
+/// unchanged, so partial pipelines can be shared.
+#[pyclass(frozen, module = "orlando")]
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    operations: Vec<Arc<Operation>>,
+}
+
+impl Pipeline {
+    fn with(&self, op: Operation) -> Pipeline {
+        let mut operations = self.operations.clone();
+        operations.push(Arc::new(op));
+        Pipeline { operations }
+    }
+
+    /// Pass `value` through the operations from `from` onwards, handing
+    /// whatever comes out to `sink`.
+    fn push<'py>(
+        &self,
+        mut value: Bound<'py, PyAny>,
+        from: usize,
+        state: &mut State<'py>,
+        sink: &mut Sink<'_, 'py>,
+    ) -> PyResult<Flow> {
+        let py = value.py();
+        for idx in from..self.operations.len() {
+            match &*self.operations[idx] {
+                Operation::Map(f, _) => value = f.bind(py).call1((value,))?,
+                Operation::Filter(pred, _) => {
+                    if !pred.bind(py).call1((&value,))?.is_truthy()? {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::Reject(pred) => {
+                    if pred.bind(py).call1((&value,))?.is_truthy()? {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::FlatMap(f) => {
+                    for item in f.bind(py).call1((value,))?.try_iter()? {
+                        if self.push(item?, idx + 1, state, sink)? == Flow::Stop {
+                            return Ok(Flow::Stop);
+                        }
+                    }
+                    return Ok(Flow::Continue);
+                }
+                Operation::Tap(f) => {
+                    f.bind(py).call1((&value,))?;
+                }
+                Operation::Take(n) => {
+                    let taken = &mut state.counts[idx];
+                    if *taken >= *n {
+                        return state.stop_at(idx);
+                    }
+                    *taken += 1;
+                    // Stop as soon as the last element is through, without
+                    // pulling another one
+                    if *taken == *n {
+                        self.push(value, idx + 1, state, sink)?;
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::TakeWhile(pred) => {
+                    if !pred.bind(py).call1((&value,))?.is_truthy()? {
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::Drop(n) => {
+                    if state.counts[idx] < *n {
+                        state.counts[idx] += 1;
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::DropWhile(pred) => {
+                    if !state.flags[idx] {
+                        if pred.bind(py).call1((&value,))?.is_truthy()? {
+                            return Ok(Flow::Continue);
+                        }
+                        state.flags[idx] = true;
+                    }
+                }
+                Operation::Unique => {
+                    if let Some(last) = &state.last[idx] {
+                        if last.eq(&value)? {
+                            return Ok(Flow::Continue);
+                        }
+                    }
+                    state.last[idx] = Some(value.clone());
+                }
+                Operation::Chunk(n) => {
+                    let buffer = &mut state.buffers[idx];
+                    buffer.push_back(value);
+                    if buffer.len() < *n {
+                        return Ok(Flow::Continue);
+                    }
+                    value = PyList::new(py, buffer.drain(..))?.into_any();
+                }
+                Operation::Aperture(n) => {
+                    let window = &mut state.buffers[idx];
+                    window.push_back(value);
+                    if window.len() > *n {
+                        window.pop_front();
+                    }
+                    if window.len() < *n {
+                        return Ok(Flow::Continue);
+                    }
+                    value = PyList::new(py, window.iter())?.into_any();
+                }
+                Operation::Interpose(separator) => {
+                    if state.flags[idx] {
+                        let separator = separator.bind(py).clone();
+                        if self.push(separator, idx + 1, state, sink)? == Flow::Stop {
+                            return Ok(Flow::Stop);
+                        }
+                    }
+                    state.flags[idx] = true;
+                }
+                Operation::Zip(other) => {
+                    let Some(paired) = other.get(state.counts[idx]) else {
+                        return state.stop_at(idx);
+                    };
+                    state.counts[idx] += 1;
+                    value = PyTuple::new(py, [value, paired.bind(py).clone()])?.into_any();
+                }
+                Operation::MapAdd(n) => value = float(&value, |x| x + n),
+                Operation::MapMul(n) => value = float(&value, |x| x * n),
+                Operation::ScaleOffset(a, b) => value = float(&value, |x| a * x + b),
+                Operation::Normalize(mean, std) => value = float(&value, |x| (x - mean) / std),
+                Operation::FilterGt(n) => {
+                    if !value.extract::<f64>().is_ok_and(|x| x > *n) {
+                        return Ok(Flow::Continue);
+                    }
+                }
+                Operation::FilterRange(lo, hi) => {
+                    if !value.extract::<f64>().is_ok_and(|x| *lo <= x && x < *hi) {
+                        return Ok(Flow::Continue);
+                    }
+                }
+            }
+        }
+        sink(value)
+    }
+
+    /// Run the pipeline over `source`, handing each output to `sink` until
+    /// either runs out or asks to stop.
+    fn run<'py>(&self, source: &Bound<'py, PyAny>, sink: &mut Sink<'_, 'py>) -> PyResult<()> {
+        let py = source.py();
+        let mut state = State::new(self.operations.len());
+        // Whether `sink` itself asked to stop, ruling out any flush
+        let sink_stopped = Cell::new(false);
+        let mut sink = |value| {
+            let flow = sink(value)?;
+            if flow == Flow::Stop {
+                sink_stopped.set(true);
+            }
+            Ok(flow)
+        };
+        for item in source.try_iter()? {
+            if self.push(item?, 0, &mut state, &mut sink)? == Flow::Stop {
+                break;
+            }
+        }
+
+        // Flush partial chunks, upstream first so their output can fill the
+        // chunks after them. An operation that stopped early only cuts off
+        // the chunks before it.
+        for idx in 0..self.operations.len() {
+            if sink_stopped.get() {
+                break;
+            }
+            if matches!(*self.operations[idx], Operation::Chunk(_))
+                && !state.buffers[idx].is_empty()
+                && state.stopped_at.is_none_or(|at| idx > at)
+            {
+                let rest = PyList::new(py, state.buffers[idx].drain(..))?.into_any();
+                self.push(rest, idx + 1, &mut state, &mut sink)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Results of a numeric pipeline over a contiguous `float64` array,
+    /// computed on the array's buffer. `None` when the fast path doesn't
+    /// apply.
+    fn run_numeric(&self, source: &Bound<'_, PyAny>) -> PyResult<Option<Vec<f64>>> {
+        let Some(plan) = self
+            .operations
+            .iter()
+            .map(|op| op.numeric())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        // An ndarray source means NumPy is loaded; checking for one
+        // shouldn't load it
+        let py = source.py();
+        if !py.import("sys")?.getattr("modules")?.contains("numpy")? {
+            return Ok(None);
+        }
+        let Ok(array) = source.cast::<PyArray1<f64>>() else {
+            return Ok(None);
+        };
+        let array = array.readonly();
+        let Ok(data) = array.as_slice() else {
+            return Ok(None);
+        };
+        Ok(Some(py.detach(|| simd::run_f64(&plan, data))))
+    }
+}
+
+/// Apply a numeric function, treating non-numbers as NaN.
+fn float<'py>(value: &Bound<'py, PyAny>, f: impl Fn(f64) -> f64) -> Bound<'py, PyAny> {
+    let x = f(value.extract::<f64>().unwrap_or(f64::NAN));
+    PyFloat::new(value.py(), x).into_any()
+}
+
+#[pymethods]
+impl Pipeline {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transform each element with `f`.
+    ///
+    /// Give a `name` to make the step serializable with `to_plan`; the plan
+    /// then refers to `f` by that name.
+    #[pyo3(signature = (f, name = None))]
+    fn map(&self, f: Py<PyAny>, name: Option<String>) -> Pipeline {
+        self.with(Operation::Map(f, name))
+    }
+
+    /// Keep elements for which `pred` is truthy.
+    ///
+    /// Give a `name` to make the step serializable with `to_plan`.
+    #[pyo3(signature = (pred, name = None))]
+    fn filter(&self, pred: Py<PyAny>, name: Option<String>) -> Pipeline {
+        self.with(Operation::Filter(pred, name))
+    }
+
+    /// Drop elements for which `pred` is truthy.
+    fn reject(&self, pred: Py<PyAny>) -> Pipeline {
+        self.with(Operation::Reject(pred))
+    }
+
+    /// Map each element to an iterable and flatten the results.
+    fn flat_map(&self, f: Py<PyAny>) -> Pipeline {
+        self.with(Operation::FlatMap(f))
+    }
+
+    /// Call `f` on each element for its side effects.
+    fn tap(&self, f: Py<PyAny>) -> Pipeline {
+        self.with(Operation::Tap(f))
+    }
+
+    /// Keep the first `n` elements, then stop pulling from the source.
+    fn take(&self, n: usize) -> Pipeline {
+        self.with(Operation::Take(n))
+    }
+
+    /// Keep elements while `pred` is truthy, then stop.
+    fn take_while(&self, pred: Py<PyAny>) -> Pipeline {
+        self.with(Operation::TakeWhile(pred))
+    }
+
+    /// Skip the first `n` elements.
+    fn drop(&self, n: usize) -> Pipeline {
+        self.with(Operation::Drop(n))
+    }
+
+    /// Skip elements while `pred` is truthy.
+    fn drop_while(&self, pred: Py<PyAny>) -> Pipeline {
+        self.with(Operation::DropWhile(pred))
+    }
+
+    /// Drop consecutive duplicates.
+    fn unique(&self) -> Pipeline {
+        self.with(Operation::Unique)
+    }
+
+    /// Group elements into lists of `n`; a trailing partial chunk is emitted
+    /// at the end.
+    fn chunk(&self, n: usize) -> PyResult<Pipeline> {
+        if n == 0 {
+            return Err(PyValueError::new_err("chunk size must be greater than 0"));
+        }
+        Ok(self.with(Operation::Chunk(n)))
+    }
+
+    /// Sliding windows of `n` elements, as lists.
+    fn aperture(&self, n: usize) -> PyResult<Pipeline> {
+        if n == 0 {
+            return Err(PyValueError::new_err(
+                "aperture size must be greater than 0",
+            ));
+        }
+        Ok(self.with(Operation::Aperture(n)))
+    }
+
+    /// Insert `separator` between elements.
+    fn interpose(&self, separator: Py<PyAny>) -> Pipeline {
+        self.with(Operation::Interpose(separator))
+    }
+
+    /// Pair each element with the element at the same position in `other`,
+    /// as a tuple, stopping at the end of the shorter side.
+    fn zip(&self, other: &Bound<'_, PyAny>) -> PyResult<Pipeline> {
+        let other = other
+            .try_iter()?
+            .map(|item| item.map(Bound::unbind))
+            .collect::<PyResult<_>>()?;
+        Ok(self.with(Operation::Zip(other)))
+    }
+
+    /// Add `n` to every number, without calling back into Python.
+    fn map_add(&self, n: f64) -> Pipeline {
+        self.with(Operation::MapAdd(n))
+    }
+
+    /// Multiply every number by `n`, without calling back into Python.
+    fn map_mul(&self, n: f64) -> Pipeline {
+        self.with(Operation::MapMul(n))
+    }
+
+    /// `a * x + b` for every number, without calling back into Python.
+    fn scale_offset(&self, a: f64, b: f64) -> Pipeline {
+        self.with(Operation::ScaleOffset(a, b))
+    }
+
+    /// `(x - mean) / std` for every number, without calling back into Python.
+    fn normalize(&self, mean: f64, std: f64) -> Pipeline {
+        self.with(Operation::Normalize(mean, std))
+    }
+
+    /// Keep numbers greater than `n`, without calling back into Python.
+    fn filter_gt(&self, n: f64) -> Pipeline {
+        self.with(Operation::FilterGt(n))
+    }
+
+    /// Keep numbers in the half-open range `[lo, hi)`, without calling back
+    /// into Python.
+    fn filter_range(&self, lo: f64, hi: f64) -> Pipeline {
+        self.with(Operation::FilterRange(lo, hi))
+    }
+
+    /// Run the pipeline and collect the results into a list.
+    fn to_list<'py>(&self, source: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyList>> {
+        if let Some(values) = self.run_numeric(source)? {
+            return PyList::new(source.py(), values);
+        }
+        let out = PyList::empty(source.py());
+        self.run(source, &mut |value| {
+            out.append(value)?;
+            Ok(Flow::Continue)
+        })?;
+        Ok(out)
+    }
+
+    /// Run the pipeline and collect the numeric results into a `float64`
+    /// NumPy array, skipping anything that isn't a number.
+    ///
+    /// Numeric pipelines (`take`, `drop`, `unique` and the numeric
+    /// operations) over a contiguous `float64` array run on its buffer
+    /// without calling into Python per element.
+    fn to_numpy<'py>(&self, source: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        // Raise ImportError rather than panic if NumPy is missing
+        source.py().import("numpy")?;
+        if let Some(values) = self.run_numeric(source)? {
+            return Ok(PyArray1::from_vec(source.py(), values));
+        }
+        let mut out = Vec::new();
+        self.run(source, &mut |value| {
+            if let Ok(x) = value.extract::<f64>() {
+                out.push(x);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(PyArray1::from_vec(source.py(), out))
+    }
+
+    /// Fold the results with `f(acc, value)`, starting from `initial`.
+    fn reduce<'py>(
+        &self,
+        source: &Bound<'py, PyAny>,
+        f: &Bound<'py, PyAny>,
+        initial: Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let mut acc = Some(initial);
+        self.run(source, &mut |value| {
+            let prev = acc.take().expect("accumulator is restored after each call");
+            acc = Some(f.call1((prev, value))?);
+            Ok(Flow::Continue)
+        })?;
+        Ok(acc.expect("accumulator is restored after each call"))
+    }
+
+    /// Number of results.
+    fn count(&self, source: &Bound<'_, PyAny>) -> PyResult<usize> {
+        let mut count = 0;
+        self.run(source, &mut |_| {
+            count += 1;
+            Ok(Flow::Continue)
+        })?;
+        Ok(count)
+    }
+
+    /// Sum of the results with `+`, starting from `0`.
+    fn sum<'py>(&self, source: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        if let Some(values) = self.run_numeric(source)? {
+            return Ok(PyFloat::new(source.py(), simd::sum(&values)).into_any());
+        }
+        let mut total = 0i64.into_pyobject(source.py())?.into_any();
+        self.run(source, &mut |value| {
+            total = total.add(value)?;
+            Ok(Flow::Continue)
+        })?;
+        Ok(total)
+    }
+
+    /// The first result, or `None`. Stops after one result.
+    fn first<'py>(&self, source: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let mut found = None;
+        self.run(source, &mut |value| {
+            found = Some(value);
+            Ok(Flow::Stop)
+        })?;
+        Ok(found)
+    }
+
+    /// The first result for which `pred` is truthy, or `None`.
+    fn find<'py>(
+        &self,
+        source: &Bound<'py, PyAny>,
+        pred: &Bound<'py, PyAny>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let mut found = None;
+        self.run(source, &mut |value| {
+            if pred.call1((&value,))?.is_truthy()? {
+                found = Some(value);
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(found)
+    }
+
+    /// Whether `pred` is truthy for every result. Stops at the first failure.
+    fn every(&self, source: &Bound<'_, PyAny>, pred: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let mut all = true;
+        self.run(source, &mut |value| {
+            if !pred.call1((value,))?.is_truthy()? {
+                all = false;
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(all)
+    }
+
+    /// Whether `pred` is truthy for any result. Stops at the first match.
+    fn some(&self, source: &Bound<'_, PyAny>, pred: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let mut any = false;
+        self.run(source, &mut |value| {
+            if pred.call1((value,))?.is_truthy()? {
+                any = true;
+                return Ok(Flow::Stop);
+            }
+            Ok(Flow::Continue)
+        })?;
+        Ok(any)
+    }
+
+    /// Serialize the pipeline as a versioned JSON plan.
+    ///
+    /// `map` and `filter` steps need a `name`; other callbacks can't be
+    /// serialized.
+    fn to_plan(&self, py: Python<'_>) -> PyResult<String> {
+        let steps = self
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(position, op)| op.to_plan_step(py, position))
+            .collect::<PyResult<_>>()?;
+        Ok(Plan::new(steps).to_json())
+    }
+
+    /// Rebuild a pipeline from a JSON plan, looking up the functions named by
+    /// `map` and `filter` steps in `functions`.
+    #[staticmethod]
+    #[pyo3(signature = (plan, functions = None))]
+    fn from_plan(
+        py: Python<'_>,
+        plan: &str,
+        functions: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Pipeline> {
+        let plan = Plan::from_json(plan).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let operations = plan
+            .steps
+            .into_iter()
+            .map(|step| Operation::from_plan_step(py, step, functions).map(Arc::new))
+            .collect::<PyResult<_>>()?;
+        Ok(Pipeline { operations })
+    }
+
+    fn __repr__(&self) -> String {
+        let names: Vec<_> = self.operations.iter().map(|op| op.name()).collect();
+        format!("Pipeline([{}])", names.join(", "))
+    }
+}
+
+#[pymodule]
+fn orlando(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Pipeline>()
+}
@@ -0,0 +1,111 @@
+import itertools
+import json
+
+import pytest
+
+from orlando import Pipeline
+
+
+def test_map_filter_take():
+    pipeline = Pipeline().map(lambda x: x * 2).filter(lambda x: x > 5).take(3)
+    assert pipeline.to_list(range(100)) == [6, 8, 10]
+
+
+def test_builders_do_not_mutate():
+    base = Pipeline().map(lambda x: x + 1)
+    assert base.take(1).to_list([1, 2]) == [2]
+    assert base.to_list([1, 2]) == [2, 3]
+
+
+def test_take_stops_pulling_from_the_source():
+    pulled = []
+    source = (pulled.append(n) or n for n in itertools.count())
+    assert Pipeline().take(3).to_list(source) == [0, 1, 2]
+    assert pulled == [0, 1, 2]
+
+
+def test_stateful_operations_reset_between_runs():
+    pipeline = Pipeline().drop(1).take(2)
+    assert pipeline.to_list(range(10)) == [1, 2]
+    assert pipeline.to_list(range(10)) == [1, 2]
+
+
+def test_structural_operations():
+    assert Pipeline().chunk(2).to_list(range(5)) == [[0, 1], [2, 3], [4]]
+    assert Pipeline().aperture(2).to_list(range(3)) == [[0, 1], [1, 2]]
+    assert Pipeline().interpose("|").to_list("abc") == ["a", "|", "b", "|", "c"]
+    assert Pipeline().zip("xy").to_list(range(5)) == [(0, "x"), (1, "y")]
+    assert Pipeline().unique().to_list([1, 1, 2, 1]) == [1, 2, 1]
+    assert Pipeline().flat_map(lambda x: [x, x]).take(3).to_list([1, 2]) == [1, 1, 2]
+
+
+def test_take_before_chunk_flushes_the_partial_chunk():
+    assert Pipeline().take(5).chunk(2).to_list(range(1, 11)) == [[1, 2], [3, 4], [5]]
+    # A chunk before the take is cut off with it
+    assert Pipeline().chunk(2).take(2).to_list(range(1, 11)) == [[1, 2], [3, 4]]
+    # Stopping in the sink rules out the flush
+    assert Pipeline().take(5).chunk(2).first(range(1, 11)) == [1, 2]
+
+
+def test_terminals():
+    pipeline = Pipeline().reject(lambda x: x % 3 == 0)
+    assert pipeline.count(range(10)) == 6
+    assert pipeline.sum(range(10)) == 27
+    assert pipeline.first(range(1, 10)) == 1
+    assert pipeline.find(range(10), lambda x: x > 4) == 5
+    assert pipeline.every(range(10), lambda x: x % 3 != 0)
+    assert not pipeline.some(range(10), lambda x: x == 3)
+    assert pipeline.reduce(range(5), lambda acc, x: acc + [x], []) == [1, 2, 4]
+    assert Pipeline().first([]) is None
+
+
+def test_numeric_operations():
+    pipeline = Pipeline().scale_offset(2, 1).filter_range(3, 8).map_add(0.5)
+    assert pipeline.to_list([0, 1, 2, 3, 4]) == [3.5, 5.5, 7.5]
+
+
+def test_exceptions_propagate():
+    with pytest.raises(ZeroDivisionError):
+        Pipeline().map(lambda x: 1 / x).to_list([1, 0])
+
+
+def test_plan_round_trip():
+    double = lambda x: x * 2  # noqa: E731
+    pipeline = Pipeline().map(double, name="double").filter_gt(4).take(2)
+
+    plan = pipeline.to_plan()
+    assert json.loads(plan) == {
+        "version": 1,
+        "steps": [
+            {"op": "map", "fn": "double"},
+            {"op": "filterGt", "n": 4.0},
+            {"op": "take", "n": 2},
+        ],
+    }
+
+    restored = Pipeline.from_plan(plan, {"double": double})
+    assert restored.to_list(range(10)) == [6, 8]
+
+
+def test_plan_errors():
+    with pytest.raises(ValueError):
+        Pipeline().map(lambda x: x).to_plan()
+    with pytest.raises(ValueError):
+        Pipeline.from_plan('{"version": 1, "steps": [{"op": "map", "fn": "missing"}]}')
+    with pytest.raises(ValueError):
+        Pipeline.from_plan('{"version": 99, "steps": []}')
+
+
+def test_numpy_sources():
+    np = pytest.importorskip("numpy")
+    data = np.arange(10, dtype=np.float64)
+
+    # Numeric pipelines run on the array buffer
+    numeric = Pipeline().map_mul(2).filter_gt(10).take(3)
+    result = numeric.to_numpy(data)
+    assert result.dtype == np.float64
+    assert result.tolist() == [12.0, 14.0, 16.0]
+    assert numeric.sum(data) == 42.0
+
+    # Other pipelines iterate the array
+    assert Pipeline().map(lambda x: x + 1).to_numpy(data[::2]).tolist() == [1, 3, 5, 7, 9]
@@ -0,0 +1,1000 @@
+//! # Aggregate: Several collectors in a single pass
+//!
+//! Each collector in [`crate::collectors`] runs the pipeline to completion on
+//! its own, so computing `count`, `sum`, `min` and `max` of the same stream
+//! means four traversals. An [`Aggregator`] separates the accumulation state
+//! from the traversal, and tuples of aggregators are aggregators too, so any
+//! combination can be computed in one pass with [`collect_many`].
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use orlando_transducers::aggregate::{self, collect_many};
+//! use orlando_transducers::transforms::Filter;
+//!
+//! let evens = Filter::new(|x: &i32| x % 2 == 0);
+//! let (count, sum, min, max) = collect_many(
+//!     &evens,
+//!     1..=10,
+//!     (aggregate::count(), aggregate::sum(), aggregate::min(), aggregate::max()),
+//! );
+//!
+//! assert_eq!(count, 5);
+//! assert_eq!(sum, 30);
+//! assert_eq!(min, Some(2));
+//! assert_eq!(max, Some(10));
+//! ```
+//!
+//! The [`collect_many!`](crate::collect_many!) macro builds the tuple for you:
+//!
+//! ```rust
+//! use orlando_transducers::{aggregate, collect_many, Map};
+//!
+//! let double = Map::new(|x: i32| x * 2);
+//! let (count, mean) = collect_many!(&double, vec![1, 2, 3], aggregate::count(), aggregate::mean());
+//! assert_eq!(count, 3);
+//! assert_eq!(mean, Some(4.0));
+//! ```
+//!
+//! [`pivot`] runs an aggregator per pair of keys instead, turning long-format
+//! records into a table, and [`rollup`] runs one per prefix of a list of
+//! keys, for subtotals at every level of a hierarchy.
+
+use crate::collectors::reduce;
+use crate::step::cont;
+use crate::transducer::Transducer;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A single-pass accumulation over elements of type `U`.
+///
+/// Aggregators observe elements by reference so several of them can share
+/// one traversal. Tuples of up to eight aggregators are aggregators whose
+/// output is the tuple of the individual outputs.
+pub trait Aggregator<U> {
+    /// Intermediate accumulation state.
+    type State;
+    /// Final result produced from the state.
+    type Output;
+
+    /// Create the initial state.
+    fn init(&self) -> Self::State;
+
+    /// Fold one element into the state.
+    fn step(&self, state: Self::State, value: &U) -> Self::State;
+
+    /// Produce the final result.
+    fn finish(&self, state: Self::State) -> Self::Output;
+}
+
+/// Run a transducer over a source, feeding every output element to an aggregator.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::aggregate::{self, collect_many};
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<i32>::new();
+/// let (first, last, all) = collect_many(
+///     &id,
+///     vec![3, 1, 4],
+///     (aggregate::first(), aggregate::last(), aggregate::to_vec()),
+/// );
+/// assert_eq!(first, Some(3));
+/// assert_eq!(last, Some(4));
+/// assert_eq!(all, vec![3, 1, 4]);
+/// ```
+pub fn collect_many<T, U, Iter, A>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    aggregator: A,
+) -> A::Output
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    A: Aggregator<U> + 'static,
+    A::State: 'static,
+{
+    let aggregator = Rc::new(aggregator);
+    let initial = aggregator.init();
+
+    let reducer = {
+        let aggregator = Rc::clone(&aggregator);
+        move |state: A::State, x: U| cont(aggregator.step(state, &x))
+    };
+
+    let state = reduce(transducer, source, initial, reducer);
+    aggregator.finish(state)
+}
+
+/// Compute several aggregators over one traversal.
+///
+/// `collect_many!(&pipeline, source, a, b, ...)` is shorthand for
+/// `collect_many(&pipeline, source, (a, b, ...))`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::{aggregate, collect_many, transducer::Identity};
+///
+/// let id = Identity::<i32>::new();
+/// let (count, sum) = collect_many!(&id, 1..=4, aggregate::count(), aggregate::sum());
+/// assert_eq!((count, sum), (4, 10));
+/// ```
+#[macro_export]
+macro_rules! collect_many {
+    ($transducer:expr, $source:expr, $($aggregator:expr),+ $(,)?) => {
+        $crate::aggregate::collect_many($transducer, $source, ($($aggregator,)+))
+    };
+}
+
+macro_rules! impl_aggregator_tuple {
+    ($($name:ident : $idx:tt),+) => {
+        impl<U, $($name),+> Aggregator<U> for ($($name,)+)
+        where
+            $($name: Aggregator<U>),+
+        {
+            type State = ($($name::State,)+);
+            type Output = ($($name::Output,)+);
+
+            fn init(&self) -> Self::State {
+                ($(self.$idx.init(),)+)
+            }
+
+            fn step(&self, state: Self::State, value: &U) -> Self::State {
+                ($(self.$idx.step(state.$idx, value),)+)
+            }
+
+            fn finish(&self, state: Self::State) -> Self::Output {
+                ($(self.$idx.finish(state.$idx),)+)
+            }
+        }
+    };
+}
+
+impl_aggregator_tuple!(A: 0);
+impl_aggregator_tuple!(A: 0, B: 1);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_aggregator_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+// ========================================
+// Built-in Aggregators
+// ========================================
+
+/// Counts elements. See [`count`].
+pub struct Count;
+
+/// Sums elements. See [`sum`].
+pub struct Sum;
+
+/// Tracks the smallest element. See [`min`].
+pub struct Min;
+
+/// Tracks the largest element. See [`max`].
+pub struct Max;
+
+/// Computes the arithmetic mean. See [`mean`].
+pub struct Mean;
+
+/// Keeps the first element. See [`first`].
+pub struct First;
+
+/// Keeps the last element. See [`last`].
+pub struct Last;
+
+/// Collects elements into a vector. See [`to_vec`].
+pub struct ToVec;
+
+/// Folds elements with a custom function. See [`fold`].
+pub struct FoldWith<Acc, F> {
+    initial: Acc,
+    f: F,
+}
+
+/// Count the number of elements.
+pub fn count() -> Count {
+    Count
+}
+
+/// Sum the elements, starting from `U::default()`.
+pub fn sum() -> Sum {
+    Sum
+}
+
+/// Find the minimum element (`None` for an empty stream).
+pub fn min() -> Min {
+    Min
+}
+
+/// Find the maximum element (`None` for an empty stream).
+pub fn max() -> Max {
+    Max
+}
+
+/// Compute the arithmetic mean as `f64` (`None` for an empty stream).
+pub fn mean() -> Mean {
+    Mean
+}
+
+/// Keep the first element.
+pub fn first() -> First {
+    First
+}
+
+/// Keep the last element.
+pub fn last() -> Last {
+    Last
+}
+
+/// Collect all elements into a vector.
+pub fn to_vec() -> ToVec {
+    ToVec
+}
+
+/// Fold elements with a custom function.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::aggregate::{self, collect_many};
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<&str>::new();
+/// let (longest, count) = collect_many(
+///     &id,
+///     vec!["a", "abc", "ab"],
+///     (aggregate::fold(0, |acc: usize, s: &&str| acc.max(s.len())), aggregate::count()),
+/// );
+/// assert_eq!(longest, 3);
+/// assert_eq!(count, 3);
+/// ```
+pub fn fold<Acc, F>(initial: Acc, f: F) -> FoldWith<Acc, F> {
+    FoldWith { initial, f }
+}
+
+impl<U> Aggregator<U> for Count {
+    type State = usize;
+    type Output = usize;
+
+    fn init(&self) -> usize {
+        0
+    }
+
+    fn step(&self, state: usize, _value: &U) -> usize {
+        state + 1
+    }
+
+    fn finish(&self, state: usize) -> usize {
+        state
+    }
+}
+
+impl<U> Aggregator<U> for Sum
+where
+    U: core::ops::Add<Output = U> + Default + Clone,
+{
+    type State = U;
+    type Output = U;
+
+    fn init(&self) -> U {
+        U::default()
+    }
+
+    fn step(&self, state: U, value: &U) -> U {
+        state + value.clone()
+    }
+
+    fn finish(&self, state: U) -> U {
+        state
+    }
+}
+
+impl<U: Ord + Clone> Aggregator<U> for Min {
+    type State = Option<U>;
+    type Output = Option<U>;
+
+    fn init(&self) -> Option<U> {
+        None
+    }
+
+    fn step(&self, state: Option<U>, value: &U) -> Option<U> {
+        match state {
+            Some(current) if current <= *value => Some(current),
+            _ => Some(value.clone()),
+        }
+    }
+
+    fn finish(&self, state: Option<U>) -> Option<U> {
+        state
+    }
+}
+
+impl<U: Ord + Clone> Aggregator<U> for Max {
+    type State = Option<U>;
+    type Output = Option<U>;
+
+    fn init(&self) -> Option<U> {
+        None
+    }
+
+    fn step(&self, state: Option<U>, value: &U) -> Option<U> {
+        match state {
+            Some(current) if current >= *value => Some(current),
+            _ => Some(value.clone()),
+        }
+    }
+
+    fn finish(&self, state: Option<U>) -> Option<U> {
+        state
+    }
+}
+
+impl<U: Into<f64> + Clone> Aggregator<U> for Mean {
+    type State = (f64, usize);
+    type Output = Option<f64>;
+
+    fn init(&self) -> (f64, usize) {
+        (0.0, 0)
+    }
+
+    fn step(&self, (total, n): (f64, usize), value: &U) -> (f64, usize) {
+        (total + value.clone().into(), n + 1)
+    }
+
+    fn finish(&self, (total, n): (f64, usize)) -> Option<f64> {
+        if n == 0 {
+            None
+        } else {
+            Some(total / n as f64)
+        }
+    }
+}
+
+impl<U: Clone> Aggregator<U> for First {
+    type State = Option<U>;
+    type Output = Option<U>;
+
+    fn init(&self) -> Option<U> {
+        None
+    }
+
+    fn step(&self, state: Option<U>, value: &U) -> Option<U> {
+        state.or_else(|| Some(value.clone()))
+    }
+
+    fn finish(&self, state: Option<U>) -> Option<U> {
+        state
+    }
+}
+
+impl<U: Clone> Aggregator<U> for Last {
+    type State = Option<U>;
+    type Output = Option<U>;
+
+    fn init(&self) -> Option<U> {
+        None
+    }
+
+    fn step(&self, _state: Option<U>, value: &U) -> Option<U> {
+        Some(value.clone())
+    }
+
+    fn finish(&self, state: Option<U>) -> Option<U> {
+        state
+    }
+}
+
+impl<U: Clone> Aggregator<U> for ToVec {
+    type State = Vec<U>;
+    type Output = Vec<U>;
+
+    fn init(&self) -> Vec<U> {
+        Vec::new()
+    }
+
+    fn step(&self, mut state: Vec<U>, value: &U) -> Vec<U> {
+        state.push(value.clone());
+        state
+    }
+
+    fn finish(&self, state: Vec<U>) -> Vec<U> {
+        state
+    }
+}
+
+impl<U, Acc, F> Aggregator<U> for FoldWith<Acc, F>
+where
+    Acc: Clone,
+    F: Fn(Acc, &U) -> Acc,
+{
+    type State = Acc;
+    type Output = Acc;
+
+    fn init(&self) -> Acc {
+        self.initial.clone()
+    }
+
+    fn step(&self, state: Acc, value: &U) -> Acc {
+        (self.f)(state, value)
+    }
+
+    fn finish(&self, state: Acc) -> Acc {
+        state
+    }
+}
+
+// ========================================
+// Pivot Tables
+// ========================================
+
+/// A table of aggregated values, produced by [`pivot`].
+///
+/// Rows and columns are in the order their keys first appeared, and a cell
+/// is `None` where no element had that pair of keys.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pivot<R, C, O> {
+    rows: Vec<R>,
+    columns: Vec<C>,
+    cells: Vec<Vec<Option<O>>>,
+}
+
+#[cfg(feature = "std")]
+impl<R, C, O> Pivot<R, C, O>
+where
+    R: Eq + Hash,
+    C: Eq + Hash,
+{
+    /// The row keys, in first-seen order.
+    pub fn rows(&self) -> &[R] {
+        &self.rows
+    }
+
+    /// The column keys, in first-seen order.
+    pub fn columns(&self) -> &[C] {
+        &self.columns
+    }
+
+    /// The cells as a dense matrix, indexed by row and then column in the
+    /// order of [`rows`](Pivot::rows) and [`columns`](Pivot::columns).
+    pub fn cells(&self) -> &[Vec<Option<O>>] {
+        &self.cells
+    }
+
+    /// The value for a pair of keys, if any element had both.
+    pub fn get(&self, row: &R, column: &C) -> Option<&O> {
+        let r = self.rows.iter().position(|key| key == row)?;
+        let c = self.columns.iter().position(|key| key == column)?;
+        self.cells[r][c].as_ref()
+    }
+
+    /// The table as a map from row key to a map from column key to value,
+    /// without the empty cells.
+    pub fn into_map(self) -> HashMap<R, HashMap<C, O>>
+    where
+        C: Clone,
+    {
+        let columns = self.columns;
+        self.rows
+            .into_iter()
+            .zip(self.cells)
+            .map(|(row, cells)| {
+                let values = columns
+                    .iter()
+                    .zip(cells)
+                    .filter_map(|(column, cell)| Some((column.clone(), cell?)))
+                    .collect();
+                (row, values)
+            })
+            .collect()
+    }
+}
+
+/// Accumulation state of [`pivot`]: keys by position, and a state per
+/// filled cell. Rows are only as long as the last column they have a value
+/// in.
+#[cfg(feature = "std")]
+struct PivotState<R, C, S> {
+    row_index: HashMap<R, usize>,
+    column_index: HashMap<C, usize>,
+    rows: Vec<R>,
+    columns: Vec<C>,
+    cells: Vec<Vec<Option<S>>>,
+}
+
+/// Position of `key` in `keys`, adding it if it is new.
+#[cfg(feature = "std")]
+fn key_slot<K: Eq + Hash + Clone>(
+    index: &mut HashMap<K, usize>,
+    keys: &mut Vec<K>,
+    key: K,
+) -> usize {
+    *index.entry(key).or_insert_with_key(|key| {
+        keys.push(key.clone());
+        keys.len() - 1
+    })
+}
+
+/// Aggregate a pipeline's output into a table keyed by row and column.
+///
+/// Each element is placed in the cell for its `row_key` and `col_key`, and
+/// `value_fn` of it is fed to that cell's copy of `agg` — one pass from
+/// long-format records (one per observation) to a wide table (one row per
+/// entity, one column per attribute), like a spreadsheet pivot table or a
+/// crosstab.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::aggregate::{self, pivot};
+/// use orlando_transducers::transducer::Identity;
+///
+/// // (region, quarter, revenue)
+/// let sales = vec![
+///     ("north", "Q1", 100),
+///     ("south", "Q1", 80),
+///     ("north", "Q2", 120),
+///     ("north", "Q1", 50),
+/// ];
+/// let id = Identity::new();
+/// let table = pivot(&id, sales, |s: &(&str, &str, i32)| s.0, |s| s.1, |s| s.2, aggregate::sum());
+///
+/// assert_eq!(table.rows(), &["north", "south"]);
+/// assert_eq!(table.columns(), &["Q1", "Q2"]);
+/// assert_eq!(
+///     table.cells(),
+///     &[vec![Some(150), Some(120)], vec![Some(80), None]]
+/// );
+/// assert_eq!(table.get(&"south", &"Q1"), Some(&80));
+/// ```
+#[cfg(feature = "std")]
+pub fn pivot<T, U, V, R, C, Iter, FR, FC, FV, A>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    row_key: FR,
+    col_key: FC,
+    value_fn: FV,
+    agg: A,
+) -> Pivot<R, C, A::Output>
+where
+    T: 'static,
+    U: 'static,
+    R: Eq + Hash + Clone + 'static,
+    C: Eq + Hash + Clone + 'static,
+    Iter: IntoIterator<Item = T>,
+    FR: Fn(&U) -> R + 'static,
+    FC: Fn(&U) -> C + 'static,
+    FV: Fn(&U) -> V + 'static,
+    A: Aggregator<V> + 'static,
+    A::State: 'static,
+{
+    let agg = Rc::new(agg);
+    let initial = PivotState {
+        row_index: HashMap::new(),
+        column_index: HashMap::new(),
+        rows: Vec::new(),
+        columns: Vec::new(),
+        cells: Vec::new(),
+    };
+
+    let reducer = {
+        let agg = Rc::clone(&agg);
+        move |mut state: PivotState<R, C, A::State>, x: U| {
+            let r = key_slot(&mut state.row_index, &mut state.rows, row_key(&x));
+            let c = key_slot(&mut state.column_index, &mut state.columns, col_key(&x));
+            if r == state.cells.len() {
+                state.cells.push(Vec::new());
+            }
+            let row = &mut state.cells[r];
+            if row.len() <= c {
+                row.resize_with(c + 1, || None);
+            }
+            let cell = row[c].take().unwrap_or_else(|| agg.init());
+            row[c] = Some(agg.step(cell, &value_fn(&x)));
+            cont(state)
+        }
+    };
+
+    let state = reduce(transducer, source, initial, reducer);
+    let width = state.columns.len();
+    let cells = state
+        .cells
+        .into_iter()
+        .map(|row| {
+            let mut row: Vec<_> = row
+                .into_iter()
+                .map(|cell| cell.map(|cell| agg.finish(cell)))
+                .collect();
+            row.resize_with(width, || None);
+            row
+        })
+        .collect();
+
+    Pivot {
+        rows: state.rows,
+        columns: state.columns,
+        cells,
+    }
+}
+
+// ========================================
+// Rollups
+// ========================================
+
+/// A tree of aggregates over a hierarchy of keys, produced by [`rollup`].
+///
+/// Each node holds the aggregate of every element beneath it, and its
+/// children are keyed by the next key in the order the keys first appeared.
+/// The root is the grand total.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rollup<K, O> {
+    value: O,
+    children: Vec<(K, Rollup<K, O>)>,
+}
+
+#[cfg(feature = "std")]
+impl<K: PartialEq, O> Rollup<K, O> {
+    /// The aggregate of every element under this node.
+    pub fn value(&self) -> &O {
+        &self.value
+    }
+
+    /// The next level down, in first-seen key order. Empty at the last key.
+    pub fn children(&self) -> &[(K, Rollup<K, O>)] {
+        &self.children
+    }
+
+    /// The node for a path of keys from here, e.g. `&[region, city]`.
+    pub fn get(&self, path: &[K]) -> Option<&Rollup<K, O>> {
+        path.iter().try_fold(self, |node, key| {
+            node.children
+                .iter()
+                .find(|(child, _)| child == key)
+                .map(|(_, node)| node)
+        })
+    }
+
+    /// Every node as a row of its key path and aggregate, parents before
+    /// their children — the rows of a SQL `GROUP BY ROLLUP`, with the grand
+    /// total first under an empty path.
+    pub fn into_rows(self) -> Vec<(Vec<K>, O)>
+    where
+        K: Clone,
+    {
+        fn walk<K: Clone, O>(node: Rollup<K, O>, path: &mut Vec<K>, rows: &mut Vec<(Vec<K>, O)>) {
+            rows.push((path.clone(), node.value));
+            for (key, child) in node.children {
+                path.push(key);
+                walk(child, path, rows);
+                path.pop();
+            }
+        }
+
+        let mut rows = Vec::new();
+        walk(self, &mut Vec::new(), &mut rows);
+        rows
+    }
+}
+
+/// Accumulation state of a [`Rollup`] node. `state` is only `None` while
+/// being stepped.
+#[cfg(feature = "std")]
+struct RollupNode<K, S> {
+    state: Option<S>,
+    index: HashMap<K, usize>,
+    children: Vec<(K, RollupNode<K, S>)>,
+}
+
+#[cfg(feature = "std")]
+impl<K, S> RollupNode<K, S> {
+    fn new(state: S) -> Self {
+        RollupNode {
+            state: Some(state),
+            index: HashMap::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn step<U, A: Aggregator<U, State = S>>(&mut self, agg: &A, value: &U) {
+        let state = self.state.take().expect("rollup node state");
+        self.state = Some(agg.step(state, value));
+    }
+
+    fn finish<U, A: Aggregator<U, State = S>>(self, agg: &A) -> Rollup<K, A::Output> {
+        Rollup {
+            value: agg.finish(self.state.expect("rollup node state")),
+            children: self
+                .children
+                .into_iter()
+                .map(|(key, child)| (key, child.finish(agg)))
+                .collect(),
+        }
+    }
+}
+
+/// Aggregate a pipeline's output at every level of a hierarchy of keys.
+///
+/// Groups by the first key, then within each group by the second, and so
+/// on, running `agg` over every group at every level as well as over
+/// everything — subtotals and a grand total in one pass, as with SQL's
+/// `GROUP BY ROLLUP` or a nested `d3.rollup`. Each element is folded into
+/// one group per level, so the cost per element grows with the number of
+/// keys rather than the number of groups.
+///
+/// The keys share a type; closures that capture nothing can be listed in
+/// an array, as they convert to a common function pointer type.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::aggregate::{self, rollup};
+/// use orlando_transducers::transducer::Identity;
+///
+/// // (region, city, sales)
+/// type Sale = (&'static str, &'static str, i32);
+/// let sales: Vec<Sale> = vec![
+///     ("eu", "paris", 5),
+///     ("us", "austin", 7),
+///     ("eu", "rome", 2),
+///     ("eu", "paris", 1),
+/// ];
+/// let totals = rollup(
+///     &Identity::new(),
+///     sales,
+///     [|s: &Sale| s.0, |s: &Sale| s.1],
+///     aggregate::fold(0, |total, s: &Sale| total + s.2),
+/// );
+///
+/// assert_eq!(*totals.value(), 15);
+/// assert_eq!(totals.get(&["eu"]).map(|eu| *eu.value()), Some(8));
+/// assert_eq!(totals.get(&["eu", "paris"]).map(|p| *p.value()), Some(6));
+/// assert_eq!(
+///     totals.into_rows(),
+///     vec![
+///         (vec![], 15),
+///         (vec!["eu"], 8),
+///         (vec!["eu", "paris"], 6),
+///         (vec!["eu", "rome"], 2),
+///         (vec!["us"], 7),
+///         (vec!["us", "austin"], 7),
+///     ]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn rollup<T, U, K, Iter, F, A>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    keys: impl IntoIterator<Item = F>,
+    agg: A,
+) -> Rollup<K, A::Output>
+where
+    T: 'static,
+    U: 'static,
+    K: Eq + Hash + Clone + 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(&U) -> K + 'static,
+    A: Aggregator<U> + 'static,
+    A::State: 'static,
+{
+    let agg = Rc::new(agg);
+    let keys: Vec<F> = keys.into_iter().collect();
+    let initial = RollupNode::new(agg.init());
+
+    let reducer = {
+        let agg = Rc::clone(&agg);
+        move |mut root: RollupNode<K, A::State>, x: U| {
+            root.step(&*agg, &x);
+            let mut node = &mut root;
+            for key_fn in &keys {
+                let key = key_fn(&x);
+                let slot = match node.index.get(&key) {
+                    Some(&slot) => slot,
+                    None => {
+                        node.index.insert(key.clone(), node.children.len());
+                        node.children.push((key, RollupNode::new(agg.init())));
+                        node.children.len() - 1
+                    }
+                };
+                node = &mut node.children[slot].1;
+                node.step(&*agg, &x);
+            }
+            cont(root)
+        }
+    };
+
+    reduce(transducer, source, initial, reducer).finish(&*agg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transducer::Identity;
+    use crate::transforms::{Filter, Map, Take};
+
+    #[test]
+    fn test_collect_many_basic_stats() {
+        let id = Identity::<i32>::new();
+        let (c, s, lo, hi) = collect_many(&id, vec![3, 1, 4, 1, 5], (count(), sum(), min(), max()));
+        assert_eq!(c, 5);
+        assert_eq!(s, 14);
+        assert_eq!(lo, Some(1));
+        assert_eq!(hi, Some(5));
+    }
+
+    #[test]
+    fn test_collect_many_single_traversal() {
+        use crate::transforms::Tap;
+        use core::cell::Cell;
+
+        let seen = Rc::new(Cell::new(0));
+        let seen_clone = Rc::clone(&seen);
+        let counting = Tap::new(move |_: &i32| seen_clone.set(seen_clone.get() + 1));
+        let _ = collect_many(&counting, 0..10, (count(), sum(), min(), max(), mean()));
+        assert_eq!(seen.get(), 10);
+    }
+
+    #[test]
+    fn test_collect_many_empty() {
+        let id = Identity::<i32>::new();
+        let (c, s, lo, m, f) = collect_many(
+            &id,
+            Vec::<i32>::new(),
+            (count(), sum(), min(), mean(), first()),
+        );
+        assert_eq!(c, 0);
+        assert_eq!(s, 0);
+        assert_eq!(lo, None);
+        assert_eq!(m, None);
+        assert_eq!(f, None);
+    }
+
+    #[test]
+    fn test_collect_many_with_pipeline() {
+        use crate::transducer::Transducer;
+
+        let pipeline = Map::new(|x: i32| x * 10)
+            .compose(Filter::new(|x: &i32| *x > 10))
+            .compose(Take::new(3));
+        let (all, last_val) = collect_many(&pipeline, 1..100, (to_vec(), last()));
+        assert_eq!(all, vec![20, 30, 40]);
+        assert_eq!(last_val, Some(40));
+    }
+
+    #[test]
+    fn test_collect_many_fold() {
+        let id = Identity::<i32>::new();
+        let (product, c) = collect_many(
+            &id,
+            vec![1, 2, 3, 4],
+            (fold(1, |acc: i32, x: &i32| acc * x), count()),
+        );
+        assert_eq!(product, 24);
+        assert_eq!(c, 4);
+    }
+
+    #[test]
+    fn test_collect_many_macro() {
+        let id = Identity::<f64>::new();
+        let (c, m) = crate::collect_many!(&id, vec![1.0, 2.0, 6.0], count(), mean());
+        assert_eq!(c, 3);
+        assert_eq!(m, Some(3.0));
+    }
+
+    #[test]
+    fn test_nested_tuples() {
+        let id = Identity::<i32>::new();
+        let ((lo, hi), c) = collect_many(&id, vec![2, 8, 5], ((min(), max()), count()));
+        assert_eq!((lo, hi, c), (Some(2), Some(8), 3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pivot_counts_and_means() {
+        // (student, subject, score), filtered before pivoting
+        let scores = vec![
+            ("ann", "math", 90),
+            ("ben", "art", 70),
+            ("ann", "math", 70),
+            ("cat", "math", -1),
+            ("ben", "math", 60),
+            ("ann", "art", 85),
+        ];
+        let valid = Filter::new(|s: &(&str, &str, i32)| s.2 >= 0);
+
+        let means = pivot(&valid, scores.clone(), |s| s.0, |s| s.1, |s| s.2, mean());
+        assert_eq!(means.rows(), &["ann", "ben"]);
+        assert_eq!(means.columns(), &["math", "art"]);
+        assert_eq!(
+            means.cells(),
+            &[
+                vec![Some(Some(80.0)), Some(Some(85.0))],
+                vec![Some(Some(60.0)), Some(Some(70.0))]
+            ]
+        );
+        assert_eq!(means.get(&"cat", &"math"), None);
+
+        // A ragged table padded with empty cells, and as nested maps
+        let id = Identity::<(u8, char)>::new();
+        let counts = pivot(
+            &id,
+            vec![(1, 'a'), (2, 'b'), (2, 'c'), (1, 'a')],
+            |p| p.0,
+            |p| p.1,
+            |_| (),
+            count(),
+        );
+        assert_eq!(
+            counts.cells(),
+            &[vec![Some(2), None, None], vec![None, Some(1), Some(1)]]
+        );
+        let map = counts.into_map();
+        assert_eq!(map[&1], HashMap::from([('a', 2)]));
+        assert_eq!(map[&2], HashMap::from([('b', 1), ('c', 1)]));
+
+        let empty = pivot(&id, Vec::new(), |p| p.0, |p| p.1, |p| p.0, sum());
+        assert!(empty.rows().is_empty() && empty.cells().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_rollup_levels_and_tuple_aggregators() {
+        // (year, month, day) with a count and the largest value per level
+        let days = vec![
+            (2024, 12, 30),
+            (2025, 1, 2),
+            (2024, 12, 31),
+            (2025, 2, 1),
+            (2025, 1, 9),
+        ];
+        let id = Identity::new();
+        type Day = (u16, u8, u8);
+        let keys = [|d: &Day| d.0, |d: &Day| u16::from(d.1)];
+        let tree = rollup(&id, days, keys, (count(), max()));
+
+        assert_eq!(*tree.value(), (5, Some((2025, 2, 1))));
+        let years: Vec<u16> = tree.children().iter().map(|(year, _)| *year).collect();
+        assert_eq!(years, vec![2024, 2025]);
+        assert_eq!(tree.get(&[2025]).unwrap().value().0, 3);
+        assert_eq!(
+            *tree.get(&[2025, 1]).unwrap().value(),
+            (2, Some((2025, 1, 9)))
+        );
+        assert!(tree.get(&[2025, 1]).unwrap().children().is_empty());
+        assert_eq!(tree.get(&[2023]), None);
+        assert_eq!(tree.get(&[]), Some(&tree));
+
+        let rows: Vec<(Vec<u16>, usize)> = tree
+            .into_rows()
+            .into_iter()
+            .map(|(path, (n, _))| (path, n))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (vec![], 5),
+                (vec![2024], 2),
+                (vec![2024, 12], 2),
+                (vec![2025], 3),
+                (vec![2025, 1], 2),
+                (vec![2025, 2], 1),
+            ]
+        );
+
+        // No keys is a plain aggregate; an empty source still has a root
+        let no_keys: Vec<fn(&i32) -> i32> = Vec::new();
+        let total = rollup(&Identity::new(), 1..=4, no_keys.clone(), sum());
+        assert_eq!((*total.value(), total.children().len()), (10, 0));
+        assert_eq!(*rollup(&Identity::new(), 0..0, no_keys, count()).value(), 0);
+    }
+}
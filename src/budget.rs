@@ -0,0 +1,265 @@
+//! # Budgets: Running a pipeline a slice at a time
+//!
+//! [`run_with_budget`] runs a pipeline until a [`Budget`] of source elements
+//! or time is used up, and returns what it produced so far together with a
+//! [`Cursor`] that carries on where it stopped. Between slices the caller is
+//! free to do other work, which is how long runs share a thread with an event
+//! loop or a render loop.
+//!
+//! Stateful stages keep their state from one slice to the next, so a source
+//! run in slices produces the same output as one run in a single go:
+//!
+//! ```rust
+//! use orlando_transducers::budget::{run_with_budget, Budget};
+//! use orlando_transducers::{Filter, Map, Take, Transducer};
+//!
+//! let pipeline = Map::new(|x: u32| x * 3)
+//!     .compose(Filter::new(|x: &u32| x % 2 == 0))
+//!     .compose(Take::new(4));
+//!
+//! let mut run = run_with_budget(&pipeline, 0..100u32, Budget::Elements(3));
+//! let mut output = run.output;
+//! while let Some(cursor) = run.cursor {
+//!     // ... handle other work, then carry on
+//!     run = cursor.resume(Budget::Elements(3));
+//!     output.extend(run.output);
+//! }
+//! assert_eq!(output, vec![0, 6, 12, 18]);
+//! ```
+//!
+//! A run is complete once the source is exhausted or the pipeline stops early
+//! (`take`, `take_while`). The source is only read as far as the budget
+//! allows, so it can be infinite.
+
+use crate::runner::Runner;
+use crate::step::Step;
+use crate::transducer::Transducer;
+use alloc::vec::Vec;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use core::time::Duration;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use std::time::Instant;
+
+/// How much work one slice of a budgeted run may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Consume at most this many source elements. A slice always consumes
+    /// at least one, so `Elements(0)` behaves like `Elements(1)` and a
+    /// resume loop still makes progress.
+    Elements(usize),
+    /// Stop once this much time has passed. The clock is read after each
+    /// element, so a slice consumes at least one element and overruns by up
+    /// to the time one element takes. Needs `std`, and isn't available on
+    /// `wasm32`, which has no clock.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    Duration(Duration),
+}
+
+/// One slice of a budgeted run.
+pub struct BudgetRun<T, U, I> {
+    /// What the pipeline produced during the slice
+    pub output: Vec<U>,
+    /// Where to carry on, or `None` once the run is complete
+    pub cursor: Option<Cursor<T, U, I>>,
+}
+
+impl<T, U, I> BudgetRun<T, U, I> {
+    /// Whether the run is complete.
+    ///
+    /// A slice whose budget ran out just as the source ended isn't done yet;
+    /// the next slice finds the end and returns no output.
+    pub fn is_done(&self) -> bool {
+        self.cursor.is_none()
+    }
+}
+
+/// A paused budgeted run: the pipeline, with the state of its stages, and the
+/// unread rest of the source.
+pub struct Cursor<T, U, I> {
+    runner: Runner<T, U>,
+    source: I,
+}
+
+impl<T, U, I> Cursor<T, U, I>
+where
+    I: Iterator<Item = T>,
+{
+    /// Source elements consumed so far, over every slice.
+    pub fn consumed(&self) -> usize {
+        self.runner.consumed()
+    }
+
+    /// Run another slice.
+    pub fn resume(mut self, budget: Budget) -> BudgetRun<T, U, I> {
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        let start = Instant::now();
+        let exhausted = |taken: usize| match budget {
+            Budget::Elements(n) => taken >= n.max(1),
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            Budget::Duration(limit) => taken > 0 && start.elapsed() >= limit,
+        };
+
+        let mut output = Vec::new();
+        let mut taken = 0;
+        while !exhausted(taken) {
+            let Some(item) = self.source.next() else {
+                return BudgetRun {
+                    output,
+                    cursor: None,
+                };
+            };
+            taken += 1;
+            match self.runner.push(output, item) {
+                Step::Continue(acc) => output = acc,
+                Step::Stop(acc) => {
+                    return BudgetRun {
+                        output: acc,
+                        cursor: None,
+                    }
+                }
+            }
+        }
+
+        BudgetRun {
+            output,
+            cursor: Some(self),
+        }
+    }
+
+    /// Run the rest of the source without a budget.
+    pub fn finish(mut self) -> Vec<U> {
+        let mut output = Vec::new();
+        for item in self.source.by_ref() {
+            match self.runner.push(output, item) {
+                Step::Continue(acc) => output = acc,
+                Step::Stop(acc) => return acc,
+            }
+        }
+        output
+    }
+}
+
+/// Run a pipeline until `budget` is used up, returning the output so far and a
+/// cursor to resume from.
+///
+/// See the [module docs](self) for running a whole source in slices.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::budget::{run_with_budget, Budget};
+/// use orlando_transducers::Map;
+///
+/// let run = run_with_budget(&Map::new(|x: u64| x * x), 1.., Budget::Elements(3));
+/// assert_eq!(run.output, vec![1, 4, 9]);
+///
+/// let cursor = run.cursor.unwrap();
+/// assert_eq!(cursor.consumed(), 3);
+/// assert_eq!(cursor.resume(Budget::Elements(2)).output, vec![16, 25]);
+/// ```
+pub fn run_with_budget<T, U, Iter>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    budget: Budget,
+) -> BudgetRun<T, U, Iter::IntoIter>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let cursor = Cursor {
+        runner: Runner::new(transducer),
+        source: source.into_iter(),
+    };
+    cursor.resume(budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Chunk, Filter, Map, Scan, Take};
+
+    /// Every slice's output, concatenated, and the number of slices.
+    fn run_in_slices<T, U, I>(mut run: BudgetRun<T, U, I>, budget: Budget) -> (Vec<U>, usize)
+    where
+        I: Iterator<Item = T>,
+    {
+        let mut output = Vec::new();
+        let mut slices = 1;
+        loop {
+            output.append(&mut run.output);
+            match run.cursor {
+                Some(cursor) => run = cursor.resume(budget),
+                None => return (output, slices),
+            }
+            slices += 1;
+        }
+    }
+
+    #[test]
+    fn test_slices_match_single_run() {
+        let pipeline = Scan::new(0, |acc: &i32, x: &i32| acc + x)
+            .compose(Filter::new(|x: &i32| x % 2 == 0))
+            .compose(Chunk::new(3));
+        let expected = to_vec(
+            &Scan::new(0, |acc: &i32, x: &i32| acc + x)
+                .compose(Filter::new(|x: &i32| x % 2 == 0))
+                .compose(Chunk::new(3)),
+            0..50,
+        );
+
+        let budget = Budget::Elements(7);
+        let (output, slices) = run_in_slices(run_with_budget(&pipeline, 0..50, budget), budget);
+        assert_eq!(output, expected);
+        // The eighth slice reads the last element and finds the end
+        assert_eq!(slices, 8);
+    }
+
+    #[test]
+    fn test_early_stop_completes_run() {
+        let run = run_with_budget(&Take::new(2), 0.., Budget::Elements(5));
+        assert_eq!(run.output, vec![0, 1]);
+        assert!(run.is_done());
+
+        let run = run_with_budget(&Map::new(|x: i32| x), 0..3, Budget::Elements(3));
+        assert!(!run.is_done());
+        let last = run.cursor.unwrap().resume(Budget::Elements(3));
+        assert!(last.output.is_empty() && last.is_done());
+    }
+
+    #[test]
+    fn test_zero_budget_and_finish() {
+        // A zero budget still consumes one element per slice
+        let budget = Budget::Elements(0);
+        let run = run_with_budget(&Map::new(|x: i32| x + 1), 0..5, budget);
+        assert_eq!(run.output, vec![1]);
+        let cursor = run.cursor.unwrap();
+        assert_eq!(cursor.consumed(), 1);
+        assert_eq!(cursor.finish(), vec![2, 3, 4, 5]);
+
+        let (output, slices) =
+            run_in_slices(run_with_budget(&Map::new(|x: i32| x), 0..3, budget), budget);
+        assert_eq!(output, vec![0, 1, 2]);
+        assert_eq!(slices, 4);
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let pipeline = Map::new(|x: i32| x * 2);
+
+        // An exhausted clock still lets each slice make progress
+        let budget = Budget::Duration(Duration::ZERO);
+        let (output, slices) = run_in_slices(run_with_budget(&pipeline, 0..4, budget), budget);
+        assert_eq!(output, vec![0, 2, 4, 6]);
+        assert_eq!(slices, 5);
+
+        let run = run_with_budget(
+            &pipeline,
+            0..1000,
+            Budget::Duration(Duration::from_secs(60)),
+        );
+        assert_eq!(run.output.len(), 1000);
+        assert!(run.is_done());
+    }
+}
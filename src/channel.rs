@@ -0,0 +1,270 @@
+//! # Channels: Pipelines as stages of a multithreaded graph
+//!
+//! [`from_receiver`] turns the receiving end of a channel into a source, and
+//! [`to_sender`] runs a pipeline and sends each output down a channel as soon
+//! as it is produced. Together they let a transducer pipeline run on its own
+//! thread between two other stages:
+//!
+//! ```rust
+//! use orlando_transducers::channel::{from_receiver, to_sender};
+//! use orlando_transducers::{Filter, Map, Transducer};
+//! use std::sync::mpsc;
+//! use std::thread;
+//!
+//! let (raw_tx, raw_rx) = mpsc::channel();
+//! let (out_tx, out_rx) = mpsc::channel();
+//!
+//! let stage = thread::spawn(move || {
+//!     let pipeline = Map::new(|x: i32| x * 10).compose(Filter::new(|x: &i32| *x > 10));
+//!     to_sender(&pipeline, from_receiver(raw_rx), out_tx)
+//! });
+//!
+//! for i in 0..4 {
+//!     raw_tx.send(i).unwrap();
+//! }
+//! drop(raw_tx);
+//!
+//! assert_eq!(out_rx.iter().collect::<Vec<_>>(), vec![20, 30]);
+//! assert_eq!(stage.join().unwrap(), 2);
+//! ```
+//!
+//! Termination travels along the graph as channel closure. The source ends
+//! when every sender of its channel is dropped. When the pipeline stops
+//! early (for example with `Take`), [`to_sender`] drops both ends, so the
+//! upstream stage's next send fails and the downstream stage sees its
+//! channel close. A downstream stage that hangs up likewise stops the
+//! pipeline.
+//!
+//! Both `std::sync::mpsc` channels (`Sender` and `SyncSender`) and, with the
+//! `crossbeam` feature, `crossbeam_channel` channels are supported.
+//!
+//! This module is only available on native targets.
+
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+use std::marker::PhantomData;
+use std::sync::mpsc;
+
+/// The receiving end of a channel.
+pub trait ChannelReceiver<T> {
+    /// Block until a value arrives, or return `None` once every sender is
+    /// gone.
+    fn recv_item(&self) -> Option<T>;
+}
+
+/// The sending end of a channel.
+pub trait ChannelSender<T> {
+    /// Send a value, blocking on a full bounded channel. Returns `false` if
+    /// the receiver is gone.
+    fn send_item(&self, item: T) -> bool;
+}
+
+impl<T> ChannelReceiver<T> for mpsc::Receiver<T> {
+    fn recv_item(&self) -> Option<T> {
+        self.recv().ok()
+    }
+}
+
+impl<T> ChannelSender<T> for mpsc::Sender<T> {
+    fn send_item(&self, item: T) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+impl<T> ChannelSender<T> for mpsc::SyncSender<T> {
+    fn send_item(&self, item: T) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> ChannelReceiver<T> for crossbeam_channel::Receiver<T> {
+    fn recv_item(&self) -> Option<T> {
+        self.recv().ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> ChannelSender<T> for crossbeam_channel::Sender<T> {
+    fn send_item(&self, item: T) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+/// Use the receiving end of a channel as a pipeline source.
+///
+/// The source blocks for each value and ends once every sender has been
+/// dropped. Dropping it (when the pipeline stops early) closes the channel,
+/// so senders upstream get an error on their next send.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::channel::from_receiver;
+/// use orlando_transducers::{sum, Map};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// std::thread::spawn(move || (1..=4).for_each(|i| tx.send(i).unwrap()));
+///
+/// assert_eq!(sum(&Map::new(|x: i32| x * x), from_receiver(rx)), 30);
+/// ```
+pub fn from_receiver<T, R: ChannelReceiver<T>>(receiver: R) -> FromReceiver<T, R> {
+    FromReceiver {
+        receiver,
+        _item: PhantomData,
+    }
+}
+
+/// Iterator returned by [`from_receiver`].
+pub struct FromReceiver<T, R> {
+    receiver: R,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T, R: ChannelReceiver<T>> Iterator for FromReceiver<T, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv_item()
+    }
+}
+
+/// Run a pipeline, sending each output down `sender` as soon as it is
+/// produced.
+///
+/// Stops when the source is exhausted, when the pipeline stops, or when the
+/// receiver hangs up. The sender and the source are dropped on return, which
+/// closes both channels. Returns the number of outputs delivered.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::channel::to_sender;
+/// use orlando_transducers::Take;
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::sync_channel(8);
+/// assert_eq!(to_sender(&Take::new(3), 0.., tx), 3);
+/// assert_eq!(rx.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+/// ```
+pub fn to_sender<T, U, Iter, S>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    sender: S,
+) -> usize
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    S: ChannelSender<U>,
+{
+    let transformed = transducer.apply(|mut acc: Vec<U>, x: U| {
+        acc.push(x);
+        cont(acc)
+    });
+    let mut buffer = Vec::new();
+    let mut sent = 0;
+
+    for item in source {
+        let (outputs, stopped) = match transformed(buffer, item) {
+            Step::Continue(outputs) => (outputs, false),
+            Step::Stop(outputs) => (outputs, true),
+        };
+        buffer = outputs;
+        for output in buffer.drain(..) {
+            if !sender.send_item(output) {
+                return sent;
+            }
+            sent += 1;
+        }
+        if stopped {
+            break;
+        }
+    }
+
+    sent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Map, Take};
+    use std::thread;
+
+    #[test]
+    fn test_stage_between_threads() {
+        let (in_tx, in_rx) = mpsc::channel();
+        let (out_tx, out_rx) = mpsc::channel();
+
+        let stage = thread::spawn(move || {
+            to_sender(&Map::new(|x: u32| x + 1), from_receiver(in_rx), out_tx)
+        });
+        for i in 0..100 {
+            in_tx.send(i).unwrap();
+        }
+        drop(in_tx);
+
+        assert_eq!(out_rx.iter().sum::<u32>(), 5050);
+        assert_eq!(stage.join().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_stop_closes_upstream_channel() {
+        let (in_tx, in_rx) = mpsc::sync_channel(1);
+        let (out_tx, out_rx) = mpsc::channel();
+
+        let producer = thread::spawn(move || {
+            let mut sent = 0;
+            // Unbounded producer: only ends when the stage hangs up
+            while in_tx.send(sent).is_ok() {
+                sent += 1;
+            }
+            sent
+        });
+
+        assert_eq!(to_sender(&Take::new(5), from_receiver(in_rx), out_tx), 5);
+        assert_eq!(out_rx.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(producer.join().unwrap() >= 5);
+    }
+
+    #[test]
+    fn test_receiver_hang_up_stops_pipeline() {
+        let (tx, rx) = mpsc::channel::<u64>();
+        drop(rx);
+        assert_eq!(to_sender(&Map::new(|x: u64| x), 0.., tx), 0);
+    }
+
+    #[test]
+    fn test_from_receiver_ends_when_senders_drop() {
+        let (tx, rx) = mpsc::channel();
+        let tx2 = tx.clone();
+        tx.send(1).unwrap();
+        tx2.send(2).unwrap();
+        drop((tx, tx2));
+        assert_eq!(to_vec(&Take::new(10), from_receiver(rx)), vec![1, 2]);
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn test_crossbeam_channels() {
+        let (in_tx, in_rx) = crossbeam_channel::bounded(4);
+        let (out_tx, out_rx) = crossbeam_channel::unbounded();
+
+        let stage = thread::spawn(move || {
+            to_sender(
+                &Map::new(|s: &'static str| s.len()).compose(Take::new(2)),
+                from_receiver(in_rx),
+                out_tx,
+            )
+        });
+        for word in ["a", "bb", "ccc"] {
+            // The third send may fail once the stage has stopped
+            let _ = in_tx.send(word);
+        }
+
+        assert_eq!(stage.join().unwrap(), 2);
+        assert_eq!(out_rx.iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
@@ -3,10 +3,15 @@
 //! Collectors are reducing functions that consume the output of a transducer
 //! and produce a final result.
 
+use crate::either::Either;
 use crate::step::{cont, Step};
 use crate::transducer::Transducer;
+use crate::transforms::ValidationError;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
 
 /// Execute a transducer over an iterator and collect results into a vector.
 ///
@@ -21,6 +26,39 @@ use std::hash::Hash;
 /// assert_eq!(result, vec![2, 4, 6]);
 /// ```
 pub fn to_vec<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> Vec<U>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let source = source.into_iter();
+    let capacity = transducer.size_hint(source.size_hint()).0;
+    to_vec_with_capacity(transducer, source, capacity)
+}
+
+/// Like [`to_vec`], but preallocating room for `capacity` elements.
+///
+/// [`to_vec`] preallocates the number of elements the pipeline is sure to
+/// produce, from the source's `size_hint` and the pipeline's
+/// [`Transducer::size_hint`]; that is nothing after a filter. Pass a capacity
+/// when you know better, e.g. roughly how many elements a filter keeps.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::to_vec_with_capacity;
+/// use orlando_transducers::transforms::Filter;
+///
+/// let evens = Filter::new(|x: &u32| x % 2 == 0);
+/// let result = to_vec_with_capacity(&evens, 0..1000u32, 500);
+/// assert_eq!(result.len(), 500);
+/// assert!(result.capacity() >= 500);
+/// ```
+pub fn to_vec_with_capacity<T, U, Iter>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    capacity: usize,
+) -> Vec<U>
 where
     T: 'static,
     U: 'static,
@@ -32,7 +70,7 @@ where
     };
 
     let transformed = transducer.apply(reducer);
-    let mut result = Vec::new();
+    let mut result = Vec::with_capacity(capacity);
 
     for item in source {
         match transformed(result, item) {
@@ -104,7 +142,7 @@ where
 pub fn sum<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> U
 where
     T: 'static,
-    U: std::ops::Add<Output = U> + Default + 'static,
+    U: core::ops::Add<Output = U> + Default + 'static,
     Iter: IntoIterator<Item = T>,
 {
     reduce(transducer, source, U::default(), |acc, x| cont(acc + x))
@@ -176,6 +214,81 @@ where
     reduce(transducer, source, None, |_acc, x| cont(Some(x)))
 }
 
+/// Find the last element that satisfies a predicate.
+///
+/// Runs in a single forward pass keeping only the most recent match, so the
+/// stream is never buffered or reversed. Reversing the source would not be
+/// equivalent anyway: stateful transducers like `Take` depend on input order.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::find_last;
+/// use orlando_transducers::transforms::Map;
+///
+/// let double = Map::new(|x: i32| x * 2);
+/// let result = find_last(&double, vec![1, 2, 3, 4, 5], |x| *x < 7);
+/// assert_eq!(result, Some(6));
+/// ```
+pub fn find_last<T, U, Iter, P>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    predicate: P,
+) -> Option<U>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    P: Fn(&U) -> bool + 'static,
+{
+    let reducer = move |acc: Option<U>, x: U| {
+        if predicate(&x) {
+            cont(Some(x))
+        } else {
+            cont(acc)
+        }
+    };
+
+    reduce(transducer, source, None, reducer)
+}
+
+/// Find the position of the last element that satisfies a predicate.
+///
+/// The position refers to the transformed stream, not the source.
+/// Like `find_last`, this is a single forward pass with constant memory.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::position_last;
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<i32>::new();
+/// let result = position_last(&id, vec![2, 3, 4, 5], |x| x % 2 == 0);
+/// assert_eq!(result, Some(2));
+/// ```
+pub fn position_last<T, U, Iter, P>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    predicate: P,
+) -> Option<usize>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    P: Fn(&U) -> bool + 'static,
+{
+    let reducer = move |(index, found): (usize, Option<usize>), x: U| {
+        if predicate(&x) {
+            cont((index + 1, Some(index)))
+        } else {
+            cont((index + 1, found))
+        }
+    };
+
+    reduce(transducer, source, (0, None), reducer).1
+}
+
 /// Test if all elements match a predicate.
 ///
 /// # Examples
@@ -279,6 +392,158 @@ where
     reduce(transducer, source, (Vec::new(), Vec::new()), reducer)
 }
 
+/// Split and transform elements in one pass by routing each through an [`Either`].
+///
+/// The function maps every element to `Either::Left(a)` or `Either::Right(b)`;
+/// lefts are collected into the first vector and rights into the second. A
+/// typical use is splitting parse results into successes and errors.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::partition_map;
+/// use orlando_transducers::either::Either;
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<&str>::new();
+/// let (numbers, errors): (Vec<i32>, Vec<String>) =
+///     partition_map(&id, vec!["1", "two", "3"], |s| match s.parse::<i32>() {
+///         Ok(n) => Either::Left(n),
+///         Err(_) => Either::Right(format!("not a number: {}", s)),
+///     });
+///
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(errors, vec!["not a number: two"]);
+/// ```
+pub fn partition_map<T, U, A, B, Iter, F>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    f: F,
+) -> (Vec<A>, Vec<B>)
+where
+    T: 'static,
+    U: 'static,
+    A: 'static,
+    B: 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(U) -> Either<A, B> + 'static,
+{
+    let reducer = move |mut acc: (Vec<A>, Vec<B>), x: U| {
+        match f(x) {
+            Either::Left(a) => acc.0.push(a),
+            Either::Right(b) => acc.1.push(b),
+        }
+        cont(acc)
+    };
+
+    reduce(transducer, source, (Vec::new(), Vec::new()), reducer)
+}
+
+/// Split the output of a [`Validate`](crate::transforms::Validate) stage into
+/// the valid values and the validation errors, in one pass.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::partition_valid;
+/// use orlando_transducers::transforms::{Map, Validate};
+/// use orlando_transducers::transducer::Transducer;
+///
+/// let ingest = Map::new(|s: &str| s.trim())
+///     .compose(Validate::new(|s: &&str| !s.is_empty(), |_| "blank row"));
+/// let (rows, errors) = partition_valid(&ingest, vec!["a", "  ", "b"]);
+///
+/// assert_eq!(rows, vec!["a", "b"]);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].index, 1);
+/// ```
+pub fn partition_valid<T, U, E, Iter>(
+    transducer: &impl Transducer<T, Result<U, ValidationError<E>>>,
+    source: Iter,
+) -> (Vec<U>, Vec<ValidationError<E>>)
+where
+    T: 'static,
+    U: 'static,
+    E: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    partition_map(transducer, source, Either::from)
+}
+
+/// Split a stream of pairs into two vectors in one pass.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::unzip;
+/// use orlando_transducers::transforms::Map;
+///
+/// let with_squares = Map::new(|x: i32| (x, x * x));
+/// let (xs, squares) = unzip(&with_squares, 1..=4);
+///
+/// assert_eq!(xs, vec![1, 2, 3, 4]);
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// ```
+pub fn unzip<T, A, B, Iter>(
+    transducer: &impl Transducer<T, (A, B)>,
+    source: Iter,
+) -> (Vec<A>, Vec<B>)
+where
+    T: 'static,
+    A: 'static,
+    B: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let reducer = |mut acc: (Vec<A>, Vec<B>), (a, b): (A, B)| {
+        acc.0.push(a);
+        acc.1.push(b);
+        cont(acc)
+    };
+
+    reduce(transducer, source, (Vec::new(), Vec::new()), reducer)
+}
+
+/// Split a stream of triples into three vectors in one pass.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::unzip3;
+/// use orlando_transducers::transforms::Map;
+///
+/// let split = Map::new(|s: &str| (s.len(), s.to_uppercase(), s.starts_with('a')));
+/// let (lens, upper, starts_a) = unzip3(&split, vec!["ab", "c"]);
+///
+/// assert_eq!(lens, vec![2, 1]);
+/// assert_eq!(upper, vec!["AB", "C"]);
+/// assert_eq!(starts_a, vec![true, false]);
+/// ```
+pub fn unzip3<T, A, B, C, Iter>(
+    transducer: &impl Transducer<T, (A, B, C)>,
+    source: Iter,
+) -> (Vec<A>, Vec<B>, Vec<C>)
+where
+    T: 'static,
+    A: 'static,
+    B: 'static,
+    C: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let reducer = |mut acc: (Vec<A>, Vec<B>, Vec<C>), (a, b, c): (A, B, C)| {
+        acc.0.push(a);
+        acc.1.push(b);
+        acc.2.push(c);
+        cont(acc)
+    };
+
+    reduce(
+        transducer,
+        source,
+        (Vec::new(), Vec::new(), Vec::new()),
+        reducer,
+    )
+}
+
 /// Find the first element that satisfies a predicate.
 ///
 /// Returns `Some(element)` if found, `None` otherwise.
@@ -318,6 +583,80 @@ where
     reduce(transducer, source, None, reducer)
 }
 
+/// Get the element at position `n` (zero-based) of the transformed stream.
+///
+/// Returns `None` if the stream has fewer than `n + 1` elements.
+/// Utilizes early termination to stop as soon as the element is reached.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::nth;
+/// use orlando_transducers::transforms::Filter;
+///
+/// let evens = Filter::new(|x: &i32| x % 2 == 0);
+/// assert_eq!(nth(&evens, 1..100, 2), Some(6));
+/// assert_eq!(nth(&evens, 1..5, 5), None);
+/// ```
+pub fn nth<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter, n: usize) -> Option<U>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    use crate::step::stop;
+
+    let reducer = move |(index, _found): (usize, Option<U>), x: U| {
+        if index == n {
+            stop((index, Some(x)))
+        } else {
+            cont((index + 1, None))
+        }
+    };
+
+    reduce(transducer, source, (0, None), reducer).1
+}
+
+/// Find the position of the first element that satisfies a predicate.
+///
+/// The position refers to the transformed stream, not the source.
+/// Returns `None` if no element matches.
+/// Utilizes early termination to stop as soon as a match is found.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::index_of;
+/// use orlando_transducers::transforms::Map;
+///
+/// let double = Map::new(|x: i32| x * 2);
+/// assert_eq!(index_of(&double, vec![1, 2, 3, 4], |x| *x > 5), Some(2));
+/// assert_eq!(index_of(&double, vec![1, 2], |x| *x > 5), None);
+/// ```
+pub fn index_of<T, U, Iter, P>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    predicate: P,
+) -> Option<usize>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    P: Fn(&U) -> bool + 'static,
+{
+    use crate::step::stop;
+
+    let reducer = move |(index, _found): (usize, Option<usize>), x: U| {
+        if predicate(&x) {
+            stop((index, Some(index)))
+        } else {
+            cont((index + 1, None))
+        }
+    };
+
+    reduce(transducer, source, (0, None), reducer).1
+}
+
 /// Group elements by a key function into a HashMap.
 ///
 /// Returns a HashMap where keys are produced by the key function and values
@@ -337,6 +676,7 @@ where
 /// assert_eq!(groups.get(&1), Some(&vec![1, 4]));
 /// assert_eq!(groups.get(&2), Some(&vec![2, 5]));
 /// ```
+#[cfg(feature = "std")]
 pub fn group_by<T, U, K, Iter, F>(
     transducer: &impl Transducer<T, U>,
     source: Iter,
@@ -358,6 +698,63 @@ where
     reduce(transducer, source, HashMap::new(), reducer)
 }
 
+/// Group elements by a key function, preserving the order in which keys first appear.
+///
+/// Like [`group_by`], but returns a `Vec` of `(key, group)` pairs instead of a
+/// `HashMap`, so iteration order is deterministic: groups are ordered by the first
+/// occurrence of their key, and elements within a group keep their stream order.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::group_by_ordered;
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<&str>::new();
+/// let groups = group_by_ordered(&id, vec!["pear", "apple", "plum", "avocado", "fig"], |s| {
+///     s.chars().next().unwrap()
+/// });
+///
+/// assert_eq!(
+///     groups,
+///     vec![
+///         ('p', vec!["pear", "plum"]),
+///         ('a', vec!["apple", "avocado"]),
+///         ('f', vec!["fig"]),
+///     ]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn group_by_ordered<T, U, K, Iter, F>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    key_fn: F,
+) -> Vec<(K, Vec<U>)>
+where
+    T: 'static,
+    U: 'static,
+    K: Eq + Hash + Clone + 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(&U) -> K + 'static,
+{
+    // Key -> position in `groups`, plus the groups themselves in first-seen order
+    type State<K, U> = (HashMap<K, usize>, Vec<(K, Vec<U>)>);
+
+    let reducer = move |(mut index, mut groups): State<K, U>, x: U| {
+        let key = key_fn(&x);
+        match index.get(&key) {
+            Some(&slot) => groups[slot].1.push(x),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push((key, vec![x]));
+            }
+        }
+        cont((index, groups))
+    };
+
+    reduce(transducer, source, (HashMap::new(), Vec::new()), reducer).1
+}
+
 /// Test if NO elements match a predicate (inverse of `some`).
 ///
 /// Returns true if all elements fail the predicate, false if any match.
@@ -429,6 +826,147 @@ where
     reduce(transducer, source, false, reducer)
 }
 
+/// Test if elements are in non-decreasing order.
+///
+/// Equivalent to `is_sorted_by` with `PartialOrd::partial_cmp`. Incomparable
+/// pairs (e.g. `NaN`) count as out of order.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::is_sorted;
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<i32>::new();
+/// assert!(is_sorted(&id, vec![1, 2, 2, 5]));
+/// assert!(!is_sorted(&id, vec![1, 3, 2]));
+/// ```
+pub fn is_sorted<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> bool
+where
+    T: 'static,
+    U: PartialOrd + 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    is_sorted_by(transducer, source, |a: &U, b: &U| {
+        a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Greater)
+    })
+}
+
+/// Test if elements are ordered according to a comparator.
+///
+/// Returns false as soon as a consecutive pair compares as `Greater`,
+/// so a violation early in the stream stops processing immediately.
+/// Useful as a cheap data-quality check on pre-sorted inputs.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::is_sorted_by;
+/// use orlando_transducers::transforms::Map;
+///
+/// // Check descending order of the doubled values
+/// let double = Map::new(|x: i32| x * 2);
+/// assert!(is_sorted_by(&double, vec![5, 3, 1], |a, b| b.cmp(a)));
+/// assert!(!is_sorted_by(&double, vec![5, 6, 1], |a, b| b.cmp(a)));
+/// ```
+pub fn is_sorted_by<T, U, Iter, F>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    comparator: F,
+) -> bool
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(&U, &U) -> core::cmp::Ordering + 'static,
+{
+    use crate::step::stop;
+
+    let reducer = move |(_sorted, prev): (bool, Option<U>), x: U| match prev {
+        Some(p) if comparator(&p, &x) == core::cmp::Ordering::Greater => stop((false, None)),
+        _ => cont((true, Some(x))),
+    };
+
+    reduce(transducer, source, (true, None), reducer).0
+}
+
+/// Test if elements are monotonic: entirely non-decreasing or entirely non-increasing.
+///
+/// Stops as soon as both directions have been violated.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::is_monotonic;
+/// use orlando_transducers::transducer::Identity;
+///
+/// let id = Identity::<i32>::new();
+/// assert!(is_monotonic(&id, vec![1, 2, 2, 3]));
+/// assert!(is_monotonic(&id, vec![3, 3, 1]));
+/// assert!(!is_monotonic(&id, vec![1, 3, 2]));
+/// ```
+pub fn is_monotonic<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> bool
+where
+    T: 'static,
+    U: PartialOrd + 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    use crate::step::stop;
+    use core::cmp::Ordering;
+
+    let reducer = move |(ascending, descending, prev): (bool, bool, Option<U>), x: U| {
+        let (ascending, descending) = match prev.map(|p| p.partial_cmp(&x)) {
+            None => (ascending, descending),
+            Some(Some(Ordering::Less)) => (ascending, false),
+            Some(Some(Ordering::Greater)) => (false, descending),
+            Some(Some(Ordering::Equal)) => (ascending, descending),
+            // Incomparable values (e.g. NaN) break both directions
+            Some(None) => (false, false),
+        };
+
+        if !ascending && !descending {
+            stop((false, false, None))
+        } else {
+            cont((ascending, descending, Some(x)))
+        }
+    };
+
+    let (ascending, descending, _) = reduce(transducer, source, (true, true, None), reducer);
+    ascending || descending
+}
+
+/// Test if all elements are equal to each other.
+///
+/// Returns true for empty and single-element streams. Stops as soon as a
+/// second distinct value is seen.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::all_equal;
+/// use orlando_transducers::transforms::Map;
+///
+/// let parity = Map::new(|x: i32| x % 2);
+/// assert!(all_equal(&parity, vec![2, 4, 6]));
+/// assert!(!all_equal(&parity, vec![2, 3, 6]));
+/// ```
+pub fn all_equal<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> bool
+where
+    T: 'static,
+    U: PartialEq + 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    use crate::step::stop;
+
+    let reducer = move |(_equal, first): (bool, Option<U>), x: U| match first {
+        None => cont((true, Some(x))),
+        Some(f) if f == x => cont((true, Some(f))),
+        Some(_) => stop((false, None)),
+    };
+
+    reduce(transducer, source, (true, None), reducer).0
+}
+
 /// Zip two iterators into pairs (helper function, not a transducer).
 ///
 /// This doesn't fit the single-input transducer model, so it's implemented
@@ -526,6 +1064,143 @@ where
     result
 }
 
+/// Merge several individually sorted iterators into one globally sorted iterator.
+///
+/// Unlike [`merge`], which interleaves round-robin and collects eagerly, this performs
+/// a lazy k-way merge using a binary heap holding one pending element per input. Only
+/// `k` elements are buffered at a time, so it is suitable for merging sorted shards in
+/// external-sort workflows. Equal elements are yielded in input order (the merge is stable).
+///
+/// Each input must already be sorted according to `cmp`; otherwise the output order is
+/// unspecified (but every element is still yielded exactly once).
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::merge_sorted;
+///
+/// let shards = vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]];
+/// let merged: Vec<i32> = merge_sorted(shards, |a: &i32, b: &i32| a.cmp(b)).collect();
+/// assert_eq!(merged, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+///
+/// The result is an ordinary iterator, so it can feed a transducer and stop early:
+///
+/// ```
+/// use orlando_transducers::{merge_sorted, to_vec, Take};
+///
+/// let shards = vec![(0..1_000_000).step_by(2), (1..1_000_000).step_by(2)];
+/// let head = to_vec(&Take::new(5), merge_sorted(shards, |a: &i32, b: &i32| a.cmp(b)));
+/// assert_eq!(head, vec![0, 1, 2, 3, 4]);
+/// ```
+pub fn merge_sorted<T, I, F>(iterators: Vec<I>, cmp: F) -> MergeSorted<I::IntoIter, F>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(&T, &T) -> core::cmp::Ordering,
+{
+    let mut sources: Vec<I::IntoIter> = iterators.into_iter().map(|i| i.into_iter()).collect();
+    let mut merged = MergeSorted {
+        heap: Vec::with_capacity(sources.len()),
+        sources: Vec::new(),
+        cmp,
+    };
+    for (index, source) in sources.iter_mut().enumerate() {
+        if let Some(head) = source.next() {
+            merged.push((head, index));
+        }
+    }
+    merged.sources = sources;
+    merged
+}
+
+/// Lazy k-way merge iterator returned by [`merge_sorted`].
+pub struct MergeSorted<I: Iterator, F> {
+    /// Min-heap of pending heads, tagged with the index of their source.
+    heap: Vec<(I::Item, usize)>,
+    sources: Vec<I>,
+    cmp: F,
+}
+
+impl<I, F> MergeSorted<I, F>
+where
+    I: Iterator,
+    F: Fn(&I::Item, &I::Item) -> core::cmp::Ordering,
+{
+    /// Heap order: by `cmp`, then by source index to keep the merge stable.
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (ref x, xi) = self.heap[a];
+        let (ref y, yi) = self.heap[b];
+        (self.cmp)(x, y).then(xi.cmp(&yi)) == core::cmp::Ordering::Less
+    }
+
+    fn push(&mut self, entry: (I::Item, usize)) {
+        self.heap.push(entry);
+        let mut child = self.heap.len() - 1;
+        while child > 0 {
+            let parent = (child - 1) / 2;
+            if !self.less(child, parent) {
+                break;
+            }
+            self.heap.swap(child, parent);
+            child = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut parent: usize) {
+        loop {
+            let left = 2 * parent + 1;
+            let right = left + 1;
+            let mut smallest = parent;
+            if left < self.heap.len() && self.less(left, smallest) {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.less(right, smallest) {
+                smallest = right;
+            }
+            if smallest == parent {
+                break;
+            }
+            self.heap.swap(parent, smallest);
+            parent = smallest;
+        }
+    }
+}
+
+impl<I, F> Iterator for MergeSorted<I, F>
+where
+    I: Iterator,
+    F: Fn(&I::Item, &I::Item) -> core::cmp::Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (value, index) = self.heap.swap_remove(0);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        if let Some(head) = self.sources[index].next() {
+            self.push((head, index));
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sources.iter().fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(lo, hi), source| {
+                let (s_lo, s_hi) = source.size_hint();
+                (
+                    lo.saturating_add(s_lo),
+                    hi.and_then(|h| s_hi.and_then(|s| h.checked_add(s))),
+                )
+            },
+        )
+    }
+}
+
 /// Compute the intersection of two iterators (elements in both A and B).
 ///
 /// Returns elements that appear in both iterators, preserving order from the first iterator.
@@ -550,6 +1225,7 @@ where
 /// let result = intersection(a, b);
 /// assert_eq!(result, vec![2, 2, 3]);
 /// ```
+#[cfg(feature = "std")]
 pub fn intersection<T, IterA, IterB>(iter_a: IterA, iter_b: IterB) -> Vec<T>
 where
     T: Eq + Hash + Clone,
@@ -587,6 +1263,7 @@ where
 /// let result = difference(a, b);
 /// assert_eq!(result, vec![1, 3]);
 /// ```
+#[cfg(feature = "std")]
 pub fn difference<T, IterA, IterB>(iter_a: IterA, iter_b: IterB) -> Vec<T>
 where
     T: Eq + Hash + Clone,
@@ -624,6 +1301,7 @@ where
 /// let result = union(a, b);
 /// assert_eq!(result, vec![1, 2, 3, 4, 5]);
 /// ```
+#[cfg(feature = "std")]
 pub fn union<T, IterA, IterB>(iter_a: IterA, iter_b: IterB) -> Vec<T>
 where
     T: Eq + Hash + Clone,
@@ -674,6 +1352,7 @@ where
 /// let result = symmetric_difference(a, b);
 /// assert_eq!(result, vec![1, 2, 3, 4]);
 /// ```
+#[cfg(feature = "std")]
 pub fn symmetric_difference<T, IterA, IterB>(iter_a: IterA, iter_b: IterB) -> Vec<T>
 where
     T: Eq + Hash + Clone,
@@ -735,6 +1414,7 @@ where
 /// let sample = reservoir_sample(&id, 1..1_000_000, 5);
 /// assert_eq!(sample.len(), 5);
 /// ```
+#[cfg(feature = "std")]
 pub fn reservoir_sample<T, U, Iter>(
     transducer: &impl Transducer<T, U>,
     source: Iter,
@@ -745,9 +1425,9 @@ where
     U: 'static + Clone,
     Iter: IntoIterator<Item = T>,
 {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
     use rand::Rng;
-    use std::cell::RefCell;
-    use std::rc::Rc;
 
     let rng = Rc::new(RefCell::new(rand::thread_rng()));
     let reservoir: Rc<RefCell<Vec<U>>> = Rc::new(RefCell::new(Vec::with_capacity(n)));
@@ -826,8 +1506,8 @@ where
     Iter: IntoIterator<Item = T>,
     F: Fn(&U) -> K + 'static,
 {
-    use std::cell::RefCell;
-    use std::rc::Rc;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
 
     let result: Rc<RefCell<Vec<Vec<U>>>> = Rc::new(RefCell::new(Vec::new()));
     let current_group: Rc<RefCell<Vec<U>>> = Rc::new(RefCell::new(Vec::new()));
@@ -925,10 +1605,10 @@ where
     U: Ord + Clone + 'static,
     Iter: IntoIterator<Item = T>,
 {
-    use std::cell::RefCell;
-    use std::cmp::Reverse;
-    use std::collections::BinaryHeap;
-    use std::rc::Rc;
+    use alloc::collections::BinaryHeap;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+    use core::cmp::Reverse;
 
     let heap: Rc<RefCell<BinaryHeap<Reverse<U>>>> =
         Rc::new(RefCell::new(BinaryHeap::with_capacity(k + 1)));
@@ -992,6 +1672,7 @@ where
 /// assert_eq!(freq.get("hello"), Some(&2));
 /// assert_eq!(freq.get("world"), Some(&2));
 /// ```
+#[cfg(feature = "std")]
 pub fn frequencies<T, U, Iter>(
     transducer: &impl Transducer<T, U>,
     source: Iter,
@@ -1009,6 +1690,63 @@ where
     reduce(transducer, source, HashMap::new(), reducer)
 }
 
+/// Collect the distinct elements, preserving first-seen order.
+///
+/// Unlike the `Unique` transducer, which only removes consecutive duplicates,
+/// this removes every repeat across the whole stream.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::{distinct, transducer::Identity};
+///
+/// let id = Identity::new();
+/// let result = distinct(&id, vec![3, 1, 3, 2, 1]);
+/// assert_eq!(result, vec![3, 1, 2]);
+/// ```
+#[cfg(feature = "std")]
+pub fn distinct<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> Vec<U>
+where
+    T: 'static,
+    U: Eq + Hash + Clone + 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let reducer = |(mut seen, mut result): (HashSet<U>, Vec<U>), x: U| {
+        if seen.insert(x.clone()) {
+            result.push(x);
+        }
+        cont((seen, result))
+    };
+
+    reduce(transducer, source, (HashSet::new(), Vec::new()), reducer).1
+}
+
+/// Count the number of distinct elements exactly.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::{distinct_count, transforms::Map};
+///
+/// let lower = Map::new(|s: &str| s.to_lowercase());
+/// let count = distinct_count(&lower, vec!["a", "B", "A", "b", "c"]);
+/// assert_eq!(count, 3);
+/// ```
+#[cfg(feature = "std")]
+pub fn distinct_count<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> usize
+where
+    T: 'static,
+    U: Eq + Hash + 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let reducer = |mut seen: HashSet<U>, x: U| {
+        seen.insert(x);
+        cont(seen)
+    };
+
+    reduce(transducer, source, HashSet::new(), reducer).len()
+}
+
 /// Zip two iterators, continuing until both are exhausted (unlike `zip`).
 ///
 /// When one iterator is shorter, uses the provided fill value for missing elements.
@@ -1238,7 +1976,7 @@ where
 pub fn product<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> U
 where
     T: 'static,
-    U: std::ops::Mul<Output = U> + From<u8> + 'static,
+    U: core::ops::Mul<Output = U> + From<u8> + 'static,
     Iter: IntoIterator<Item = T>,
 {
     reduce(transducer, source, U::from(1u8), |acc, x| cont(acc * x))
@@ -1276,9 +2014,8 @@ where
     if elements.is_empty() {
         None
     } else {
-        let len = elements.len();
-        let sum: f64 = elements.into_iter().map(|x| x.into()).sum();
-        Some(sum / (len as f64))
+        let values: Vec<f64> = elements.into_iter().map(|x| x.into()).collect();
+        Some(sum_f64(&values) / (values.len() as f64))
     }
 }
 
@@ -1318,7 +2055,7 @@ where
     }
 
     let mut values: Vec<f64> = elements.into_iter().map(|x| x.into()).collect();
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
 
     let len = values.len();
     if len % 2 == 1 {
@@ -1495,19 +2232,12 @@ where
         return None;
     }
 
-    let values: Vec<f64> = elements.into_iter().map(|x| x.into()).collect();
+    let mut values: Vec<f64> = elements.into_iter().map(|x| x.into()).collect();
     let n = values.len() as f64;
-    let mean_val = values.iter().sum::<f64>() / n;
-
-    let sum_squared_diff: f64 = values
-        .iter()
-        .map(|x| {
-            let diff = x - mean_val;
-            diff * diff
-        })
-        .sum();
+    let mean_val = sum_f64(&values) / n;
 
-    Some(sum_squared_diff / (n - 1.0))
+    values.iter_mut().for_each(|x| *x -= mean_val);
+    Some(sum_of_squares_f64(&values) / (n - 1.0))
 }
 
 /// Calculate the standard deviation of elements.
@@ -1526,6 +2256,7 @@ where
 /// // Std dev is approximately 2.138
 /// assert!((result.unwrap() - 2.138).abs() < 0.01);
 /// ```
+#[cfg(feature = "std")]
 pub fn std_dev<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> Option<f64>
 where
     T: 'static,
@@ -1535,6 +2266,28 @@ where
     variance(transducer, source).map(|v| v.sqrt())
 }
 
+// Sums for `mean` and `variance`: the vectorized kernels in `simd` with std,
+// plain folds without
+#[cfg(feature = "std")]
+fn sum_f64(values: &[f64]) -> f64 {
+    crate::simd::sum(values)
+}
+
+#[cfg(not(feature = "std"))]
+fn sum_f64(values: &[f64]) -> f64 {
+    values.iter().sum()
+}
+
+#[cfg(feature = "std")]
+fn sum_of_squares_f64(values: &[f64]) -> f64 {
+    crate::simd::sum_of_squares(values)
+}
+
+#[cfg(not(feature = "std"))]
+fn sum_of_squares_f64(values: &[f64]) -> f64 {
+    values.iter().map(|x| x * x).sum()
+}
+
 /// Calculate a quantile (percentile) value.
 ///
 /// `p` should be between 0.0 and 1.0, where 0.0 is the minimum,
@@ -1577,7 +2330,7 @@ where
     }
 
     let mut values: Vec<f64> = elements.into_iter().map(|x| x.into()).collect();
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
 
     let len = values.len();
     if len == 1 {
@@ -1586,8 +2339,12 @@ where
 
     // Linear interpolation
     let index = p * (len - 1) as f64;
-    let lower = index.floor() as usize;
-    let upper = index.ceil() as usize;
+    let lower = index as usize;
+    let upper = if (lower as f64) < index {
+        lower + 1
+    } else {
+        lower
+    };
 
     if lower == upper {
         Some(values[lower])
@@ -1621,6 +2378,7 @@ where
 /// // mod 3 = 1: [1] = 1 time, mod 3 = 2: [2] = 1 time
 /// assert_eq!(result, Some(0));
 /// ```
+#[cfg(feature = "std")]
 pub fn mode<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> Option<U>
 where
     T: 'static,
@@ -1717,7 +2475,7 @@ where
     T: 'static,
     U: Clone + 'static,
     Iter: IntoIterator<Item = T>,
-    F: Fn(&U, &U) -> std::cmp::Ordering,
+    F: Fn(&U, &U) -> core::cmp::Ordering,
 {
     let mut elements = to_vec(transducer, source);
     elements.sort_by(comparator);
@@ -1757,11 +2515,104 @@ where
     elements
 }
 
-// Phase 5b: Generators & Sequences
+// Phase 5b: Generators & Sequences
+
+/// Numeric types accepted by [`range`].
+///
+/// Implemented for all primitive integer and floating-point types. Elements are
+/// computed as `start + i * step` from an element count rather than by repeated
+/// addition, so float ranges do not accumulate rounding error.
+pub trait RangeNum: Copy + PartialOrd {
+    /// True if the step is zero.
+    fn is_zero(self) -> bool;
+
+    /// Number of elements `start + i * step` that lie strictly before `end`.
+    fn step_count(start: Self, end: Self, step: Self) -> usize;
+
+    /// The element at position `i`, i.e. `start + i * step`.
+    fn nth_step(start: Self, step: Self, i: usize) -> Self;
+}
+
+macro_rules! impl_range_num_int {
+    ($($t:ty),*) => {$(
+        impl RangeNum for $t {
+            #[inline]
+            fn is_zero(self) -> bool {
+                self == 0
+            }
+
+            #[inline]
+            fn step_count(start: Self, end: Self, step: Self) -> usize {
+                if start == end || (end > start) != (step > 0) {
+                    return 0;
+                }
+                // Magnitudes in the unsigned type of the same width, so the
+                // full range of every type fits
+                let count = end.abs_diff(start).div_ceil(step.abs_diff(0));
+                usize::try_from(count).unwrap_or(usize::MAX)
+            }
+
+            #[inline]
+            fn nth_step(start: Self, step: Self, i: usize) -> Self {
+                // Exact modulo 2^bits, and the element itself is in range
+                start.wrapping_add((i as $t).wrapping_mul(step))
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_range_num_float {
+    ($($t:ty),*) => {$(
+        impl RangeNum for $t {
+            #[inline]
+            fn is_zero(self) -> bool {
+                self == 0.0
+            }
+
+            fn step_count(start: Self, end: Self, step: Self) -> usize {
+                if !(start.is_finite() && end.is_finite() && step.is_finite()) {
+                    panic!("Range bounds and step must be finite");
+                }
+                let estimate = (end - start) / step;
+                if estimate <= 0.0 {
+                    return 0;
+                }
+                // Round up by hand, as `ceil` needs std
+                let mut count = estimate as usize;
+                if (count as $t) < estimate {
+                    count = count.saturating_add(1);
+                }
+                // The division can round up by one ulp; drop a final element that reaches `end`
+                while count > 0 {
+                    let last = Self::nth_step(start, step, count - 1);
+                    if (step > 0.0 && last < end) || (step < 0.0 && last > end) {
+                        break;
+                    }
+                    count -= 1;
+                }
+                count
+            }
+
+            #[inline]
+            fn nth_step(start: Self, step: Self, i: usize) -> Self {
+                start + (i as $t) * step
+            }
+        }
+    )*};
+}
+
+impl_range_num_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_range_num_float!(f32, f64);
 
 /// Generate a sequence of numbers from start to end (exclusive) with a given step.
 ///
-/// Similar to Python's range() or JavaScript's Array.from().
+/// Similar to Python's range() or JavaScript's Array.from(). Works with any
+/// primitive integer or float type (see [`RangeNum`]); float ranges are computed
+/// from an element count, so `range(0.0, 1.0, 0.1)` has exactly ten elements.
+///
+/// # Panics
+///
+/// Panics if `step` is zero, or if any argument of a float range is not finite.
 ///
 /// # Examples
 ///
@@ -1787,27 +2638,55 @@ where
 /// let desc = range(10, 0, -1);
 /// assert_eq!(desc, vec![10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
 /// ```
-pub fn range(start: i32, end: i32, step: i32) -> Vec<i32> {
-    if step == 0 {
+///
+/// ```
+/// use orlando_transducers::range;
+///
+/// // Floats and other integer types
+/// assert_eq!(range(0.0, 1.0, 0.25), vec![0.0, 0.25, 0.5, 0.75]);
+/// assert_eq!(range(0.0, 1.0, 0.1).len(), 10);
+/// assert_eq!(range(250u8, 255, 2), vec![250, 252, 254]);
+/// ```
+pub fn range<N: RangeNum>(start: N, end: N, step: N) -> Vec<N> {
+    if step.is_zero() {
         panic!("Step cannot be zero");
     }
 
-    let mut result = Vec::new();
-    let mut current = start;
+    let count = N::step_count(start, end, step);
+    (0..count).map(|i| N::nth_step(start, step, i)).collect()
+}
 
-    if step > 0 {
-        while current < end {
-            result.push(current);
-            current += step;
-        }
-    } else {
-        while current > end {
-            result.push(current);
-            current += step;
+/// Generate `n` evenly spaced values from `start` to `end`, inclusive.
+///
+/// Matches NumPy's `linspace`: the first element is exactly `start` and, for
+/// `n >= 2`, the last is exactly `end`. Returns an empty vector for `n == 0`
+/// and `[start]` for `n == 1`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::linspace;
+///
+/// assert_eq!(linspace(0.0, 1.0, 5), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+/// assert_eq!(linspace(2.0, -2.0, 3), vec![2.0, 0.0, -2.0]);
+/// ```
+pub fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+    match n {
+        0 => Vec::new(),
+        1 => vec![start],
+        _ => {
+            let step = (end - start) / (n - 1) as f64;
+            (0..n)
+                .map(|i| {
+                    if i == n - 1 {
+                        end
+                    } else {
+                        start + i as f64 * step
+                    }
+                })
+                .collect()
         }
     }
-
-    result
 }
 
 /// Repeat a value N times.
@@ -1922,6 +2801,25 @@ mod tests {
         assert_eq!(result, vec![2, 4, 6]);
     }
 
+    #[test]
+    fn test_to_vec_preallocates_from_size_hint() {
+        use crate::transforms::{RepeatEach, Take};
+
+        let result = to_vec(&Map::new(|x: u32| x + 1), 0..1000u32);
+        assert_eq!(result.capacity(), 1000);
+
+        // Take caps an unbounded source
+        let result = to_vec(&Take::new(10), 0u64..);
+        assert_eq!(result.capacity(), 10);
+
+        let result = to_vec(&RepeatEach::new(3), vec!['a', 'b']);
+        assert_eq!((result.len(), result.capacity()), (6, 6));
+
+        let result = to_vec_with_capacity(&Filter::new(|x: &i32| *x > 0), vec![1, -1], 64);
+        assert_eq!(result, vec![1]);
+        assert!(result.capacity() >= 64);
+    }
+
     #[test]
     fn test_sum() {
         let double = Map::new(|x: i32| x * 2);
@@ -1943,6 +2841,41 @@ mod tests {
         assert_eq!(result, Some(4));
     }
 
+    #[test]
+    fn test_find_last() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(find_last(&id, vec![1, 2, 3, 4, 5], |x| x % 2 == 0), Some(4));
+        assert_eq!(find_last(&id, vec![1, 3, 5], |x| x % 2 == 0), None);
+        assert_eq!(find_last(&id, Vec::<i32>::new(), |x| x % 2 == 0), None);
+    }
+
+    #[test]
+    fn test_find_last_respects_take() {
+        use crate::transforms::Take;
+        let pipeline = Map::new(|x: i32| x * 10).compose(Take::new(3));
+        assert_eq!(find_last(&pipeline, 1..100, |_| true), Some(30));
+    }
+
+    #[test]
+    fn test_position_last() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(
+            position_last(&id, vec![2, 3, 4, 5], |x| x % 2 == 0),
+            Some(2)
+        );
+        assert_eq!(position_last(&id, vec![1, 3, 5], |x| x % 2 == 0), None);
+        assert_eq!(position_last(&id, Vec::<i32>::new(), |_| true), None);
+    }
+
+    #[test]
+    fn test_position_last_with_filter() {
+        let evens = Filter::new(|x: &i32| x % 2 == 0);
+        // Filtered stream: [2, 4, 6, 8]; last < 7 is 6 at position 2
+        assert_eq!(position_last(&evens, 1..9, |x| *x < 7), Some(2));
+    }
+
     #[test]
     fn test_every() {
         use crate::transducer::Identity;
@@ -1978,6 +2911,71 @@ mod tests {
         assert_eq!(fail, Vec::<i32>::new());
     }
 
+    #[test]
+    fn test_partition_map_results() {
+        use crate::transducer::Identity;
+        let id = Identity::<Result<i32, String>>::new();
+        let source = vec![Ok(1), Err("a".to_string()), Ok(2), Err("b".to_string())];
+        let (oks, errs) = partition_map(&id, source, Either::from);
+
+        assert_eq!(oks, vec![1, 2]);
+        assert_eq!(errs, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_partition_map_transforms_both_sides() {
+        let double = Map::new(|x: i32| x * 2);
+        let (small, big) = partition_map(&double, 1..=5, |x| {
+            if x < 6 {
+                Either::Left(x as u8)
+            } else {
+                Either::Right(format!("{}!", x))
+            }
+        });
+
+        assert_eq!(small, vec![2, 4]);
+        assert_eq!(big, vec!["6!", "8!", "10!"]);
+    }
+
+    #[test]
+    fn test_partition_map_empty() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        let (l, r): (Vec<i32>, Vec<i32>) = partition_map(&id, Vec::new(), Either::Left);
+
+        assert!(l.is_empty());
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn test_partition_valid_keeps_going_past_errors() {
+        use crate::transforms::Validate;
+        let parse = Map::new(|s: &str| s.parse::<u8>())
+            .compose(Validate::new(
+                |r: &Result<u8, _>| r.is_ok(),
+                |_| "not a byte",
+            ))
+            .compose(Map::new(|r: Result<_, ValidationError<&str>>| {
+                r.map(Result::unwrap)
+            }));
+        let (bytes, errors) = partition_valid(&parse, vec!["1", "300", "2", "x"]);
+
+        assert_eq!(bytes, vec![1, 2]);
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError {
+                    index: 1,
+                    error: "not a byte"
+                },
+                ValidationError {
+                    index: 3,
+                    error: "not a byte"
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_partition_all_fail() {
         use crate::transducer::Identity;
@@ -1987,6 +2985,45 @@ mod tests {
         assert_eq!(fail, vec![1, 3, 5]);
     }
 
+    #[test]
+    fn test_unzip_pairs() {
+        use crate::transducer::Identity;
+        let id = Identity::<(i32, char)>::new();
+        let (nums, chars) = unzip(&id, vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        assert_eq!(nums, vec![1, 2, 3]);
+        assert_eq!(chars, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_unzip_with_pipeline() {
+        use crate::transforms::Take;
+        let pipeline = Map::new(|x: i32| (x, x % 2 == 0)).compose(Take::new(3));
+        let (nums, evens) = unzip(&pipeline, 1..);
+
+        assert_eq!(nums, vec![1, 2, 3]);
+        assert_eq!(evens, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_unzip_empty() {
+        use crate::transducer::Identity;
+        let id = Identity::<(i32, i32)>::new();
+        let (a, b) = unzip(&id, Vec::new());
+
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    fn test_unzip3() {
+        let pipeline = Map::new(|x: i32| (x, x * x, x.to_string()));
+        let (xs, squares, labels) = unzip3(&pipeline, 1..=3);
+
+        assert_eq!(xs, vec![1, 2, 3]);
+        assert_eq!(squares, vec![1, 4, 9]);
+        assert_eq!(labels, vec!["1", "2", "3"]);
+    }
+
     #[test]
     fn test_find() {
         use crate::transducer::Identity;
@@ -2018,6 +3055,51 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_nth() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(nth(&id, vec![10, 20, 30], 0), Some(10));
+        assert_eq!(nth(&id, vec![10, 20, 30], 2), Some(30));
+        assert_eq!(nth(&id, vec![10, 20, 30], 3), None);
+        assert_eq!(nth(&id, Vec::<i32>::new(), 0), None);
+    }
+
+    #[test]
+    fn test_nth_early_termination() {
+        use crate::transforms::Tap;
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let seen = Rc::new(Cell::new(0));
+        let seen_clone = Rc::clone(&seen);
+        let counting = Tap::new(move |_: &i32| seen_clone.set(seen_clone.get() + 1));
+        assert_eq!(nth(&counting, 0..1_000_000, 4), Some(4));
+        assert_eq!(seen.get(), 5);
+    }
+
+    #[test]
+    fn test_nth_with_filter() {
+        let odds = Filter::new(|x: &i32| x % 2 == 1);
+        assert_eq!(nth(&odds, 1..20, 3), Some(7));
+    }
+
+    #[test]
+    fn test_index_of() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(index_of(&id, vec![1, 3, 4, 6], |x| x % 2 == 0), Some(2));
+        assert_eq!(index_of(&id, vec![1, 3, 5], |x| x % 2 == 0), None);
+        assert_eq!(index_of(&id, Vec::<i32>::new(), |x| x % 2 == 0), None);
+    }
+
+    #[test]
+    fn test_index_of_counts_transformed_positions() {
+        // Position is relative to the filtered stream, not the source
+        let evens = Filter::new(|x: &i32| x % 2 == 0);
+        assert_eq!(index_of(&evens, 1..20, |x| *x == 8), Some(3));
+    }
+
     #[test]
     fn test_group_by() {
         use crate::transducer::Identity;
@@ -2059,6 +3141,49 @@ mod tests {
         assert_eq!(groups.len(), 1);
     }
 
+    #[test]
+    fn test_group_by_ordered_first_seen_order() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        let groups = group_by_ordered(&id, vec![5, 1, 4, 2, 3, 6], |x| x % 3);
+
+        assert_eq!(
+            groups,
+            vec![(2, vec![5, 2]), (1, vec![1, 4]), (0, vec![3, 6])]
+        );
+    }
+
+    #[test]
+    fn test_group_by_ordered_with_transform() {
+        let double = Map::new(|x: i32| x * 2);
+        let groups = group_by_ordered(&double, vec![1, 2, 3, 4, 5, 6], |x| x % 4);
+
+        assert_eq!(groups, vec![(2, vec![2, 6, 10]), (0, vec![4, 8, 12])]);
+    }
+
+    #[test]
+    fn test_group_by_ordered_empty() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        let groups = group_by_ordered(&id, Vec::<i32>::new(), |x| x % 3);
+
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_ordered_is_deterministic() {
+        use crate::transducer::Identity;
+        let id = Identity::<String>::new();
+        let words: Vec<String> = (0..50).map(|i| format!("k{}", i % 17)).collect();
+
+        let first = group_by_ordered(&id, words.clone(), |s| s.clone());
+        let second = group_by_ordered(&id, words, |s| s.clone());
+        let keys: Vec<_> = first.iter().map(|(k, _)| k.clone()).collect();
+
+        assert_eq!(first, second);
+        assert_eq!(keys[..3], ["k0", "k1", "k2"]);
+    }
+
     #[test]
     fn test_none() {
         use crate::transducer::Identity;
@@ -2127,6 +3252,86 @@ mod tests {
         assert!(!contains(&pipeline, vec![1, 2, 3], &5)); // No element maps to 5
     }
 
+    #[test]
+    fn test_is_sorted() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert!(is_sorted(&id, vec![1, 2, 2, 3]));
+        assert!(!is_sorted(&id, vec![1, 3, 2]));
+        assert!(is_sorted(&id, Vec::<i32>::new()));
+        assert!(is_sorted(&id, vec![42]));
+    }
+
+    #[test]
+    fn test_is_sorted_nan_is_unsorted() {
+        use crate::transducer::Identity;
+        let id = Identity::<f64>::new();
+        assert!(!is_sorted(&id, vec![1.0, f64::NAN, 2.0]));
+    }
+
+    #[test]
+    fn test_is_sorted_by_early_exit() {
+        use crate::transforms::Tap;
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let seen = Rc::new(Cell::new(0));
+        let seen_clone = Rc::clone(&seen);
+        let counting = Tap::new(move |_: &i32| seen_clone.set(seen_clone.get() + 1));
+        let data = vec![1, 5, 2, 3, 4, 5, 6, 7, 8];
+        assert!(!is_sorted_by(&counting, data, |a, b| a.cmp(b)));
+        assert_eq!(seen.get(), 3);
+    }
+
+    #[test]
+    fn test_is_sorted_by_key_order() {
+        let owned = Map::new(|s: &str| s.to_string());
+        let words = vec!["a", "bb", "ccc"];
+        assert!(is_sorted_by(&owned, words, |a, b| a.len().cmp(&b.len())));
+    }
+
+    #[test]
+    fn test_is_monotonic() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert!(is_monotonic(&id, vec![1, 2, 2, 3]));
+        assert!(is_monotonic(&id, vec![3, 2, 2, 1]));
+        assert!(is_monotonic(&id, vec![5, 5, 5]));
+        assert!(is_monotonic(&id, Vec::<i32>::new()));
+        assert!(!is_monotonic(&id, vec![1, 3, 2]));
+    }
+
+    #[test]
+    fn test_is_monotonic_with_transform() {
+        let negate = Map::new(|x: i32| -x);
+        assert!(is_monotonic(&negate, 1..10));
+        let evens = Filter::new(|x: &i32| x % 2 == 0);
+        assert!(!is_monotonic(&evens, vec![2, 1, 4, 3, 0]));
+    }
+
+    #[test]
+    fn test_all_equal() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert!(all_equal(&id, vec![7, 7, 7]));
+        assert!(all_equal(&id, vec![7]));
+        assert!(all_equal(&id, Vec::<i32>::new()));
+        assert!(!all_equal(&id, vec![7, 7, 8]));
+    }
+
+    #[test]
+    fn test_all_equal_early_exit() {
+        use crate::transforms::Tap;
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let seen = Rc::new(Cell::new(0));
+        let seen_clone = Rc::clone(&seen);
+        let counting = Tap::new(move |_: &i32| seen_clone.set(seen_clone.get() + 1));
+        assert!(!all_equal(&counting, vec![1, 2, 1, 1, 1, 1]));
+        assert_eq!(seen.get(), 2);
+    }
+
     #[test]
     fn test_zip() {
         let a = vec![1, 2, 3];
@@ -2214,6 +3419,87 @@ mod tests {
         assert_eq!(merged, vec![2, 11, 4, 12, 6, 13]);
     }
 
+    #[test]
+    fn test_merge_sorted_basic() {
+        let merged: Vec<i32> = merge_sorted(
+            vec![vec![1, 5, 9], vec![2, 3, 10], vec![4]],
+            |a: &i32, b: &i32| a.cmp(b),
+        )
+        .collect();
+        assert_eq!(merged, vec![1, 2, 3, 4, 5, 9, 10]);
+    }
+
+    #[test]
+    fn test_merge_sorted_empty_inputs() {
+        let merged: Vec<i32> =
+            merge_sorted(vec![vec![], vec![1, 2], vec![]], |a: &i32, b: &i32| {
+                a.cmp(b)
+            })
+            .collect();
+        assert_eq!(merged, vec![1, 2]);
+
+        let none: Vec<i32> =
+            merge_sorted(Vec::<Vec<i32>>::new(), |a: &i32, b: &i32| a.cmp(b)).collect();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_merge_sorted_is_stable() {
+        let shards = vec![
+            vec![(1, 'a'), (2, 'a')],
+            vec![(1, 'b'), (2, 'b')],
+            vec![(1, 'c')],
+        ];
+        let merged: Vec<(i32, char)> =
+            merge_sorted(shards, |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0)).collect();
+        assert_eq!(
+            merged,
+            vec![(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'b')]
+        );
+    }
+
+    #[test]
+    fn test_merge_sorted_descending() {
+        let merged: Vec<i32> =
+            merge_sorted(vec![vec![9, 4, 1], vec![8, 7, 0]], |a: &i32, b: &i32| {
+                b.cmp(a)
+            })
+            .collect();
+        assert_eq!(merged, vec![9, 8, 7, 4, 1, 0]);
+    }
+
+    #[test]
+    fn test_merge_sorted_is_lazy() {
+        use crate::transforms::Take;
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let pulled = Rc::new(Cell::new(0));
+        let shards: Vec<_> = (0..3)
+            .map(|offset| {
+                let pulled = Rc::clone(&pulled);
+                (0..).map(move |i| {
+                    pulled.set(pulled.get() + 1);
+                    i * 3 + offset
+                })
+            })
+            .collect();
+
+        let head = to_vec(
+            &Take::new(4),
+            merge_sorted(shards, |a: &i32, b: &i32| a.cmp(b)),
+        );
+        assert_eq!(head, vec![0, 1, 2, 3]);
+        // One buffered head per shard plus the replacements pulled so far
+        assert!(pulled.get() <= 3 + 4);
+    }
+
+    #[test]
+    fn test_merge_sorted_size_hint() {
+        let merged = merge_sorted(vec![vec![1, 3], vec![2]], |a: &i32, b: &i32| a.cmp(b));
+        assert_eq!(merged.size_hint(), (3, Some(3)));
+    }
+
     #[test]
     fn test_intersection_basic() {
         let a = vec![1, 2, 3, 4];
@@ -2371,6 +3657,28 @@ mod tests {
 
     // Phase 2b: New operations tests
 
+    #[test]
+    fn test_distinct() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(distinct(&id, vec![3, 1, 3, 2, 1, 2]), vec![3, 1, 2]);
+        assert_eq!(distinct(&id, Vec::<i32>::new()), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_distinct_with_transform() {
+        let modulo = Map::new(|x: i32| x % 3);
+        assert_eq!(distinct(&modulo, vec![4, 5, 7, 6, 9]), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_distinct_count() {
+        use crate::transducer::Identity;
+        let id = Identity::<i32>::new();
+        assert_eq!(distinct_count(&id, vec![1, 1, 2, 3, 3, 3]), 3);
+        assert_eq!(distinct_count(&id, Vec::<i32>::new()), 0);
+    }
+
     #[test]
     fn test_take_last_basic() {
         use crate::transducer::Identity;
@@ -3162,6 +4470,63 @@ mod tests {
         range(0, 10, 0);
     }
 
+    #[test]
+    fn test_range_float_no_accumulation() {
+        let tenths = range(0.0, 1.0, 0.1);
+        assert_eq!(tenths.len(), 10);
+        assert_eq!(tenths[3], 3.0 * 0.1);
+        assert!(tenths.iter().all(|x| *x < 1.0));
+
+        // 1.1 / 0.1 rounds up past 11; the endpoint must still be excluded
+        assert_eq!(range(0.0, 1.1, 0.1).len(), 11);
+    }
+
+    #[test]
+    fn test_range_float_descending() {
+        assert_eq!(range(1.0_f32, 0.0, -0.25), vec![1.0, 0.75, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn test_range_integer_types() {
+        assert_eq!(range(0u64, 10, 3), vec![0, 3, 6, 9]);
+        assert_eq!(range(-3i64, 3, 2), vec![-3, -1, 1]);
+        assert_eq!(range(250u8, 255, 2), vec![250, 252, 254]);
+        assert_eq!(range(10usize, 0, 1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_range_integer_bounds() {
+        assert_eq!(
+            range(u128::MAX - 4, u128::MAX, 2),
+            vec![u128::MAX - 4, u128::MAX - 2]
+        );
+        assert_eq!(range(u128::MAX, 0, 1), Vec::<u128>::new());
+        assert_eq!(
+            range(i128::MIN, i128::MAX, i128::MAX),
+            vec![i128::MIN, -1, i128::MAX - 1]
+        );
+        assert_eq!(range(i128::MAX, i128::MIN, i128::MIN), vec![i128::MAX, -1]);
+        assert_eq!(range(i8::MIN, i8::MAX, 1).len(), 255);
+        assert_eq!(range(i8::MAX, i8::MIN, -100), vec![127, 27, -73]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be finite")]
+    fn test_range_float_infinite() {
+        range(0.0, f64::INFINITY, 1.0);
+    }
+
+    #[test]
+    fn test_linspace() {
+        assert_eq!(linspace(0.0, 1.0, 5), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(linspace(3.0, 9.0, 1), vec![3.0]);
+        assert!(linspace(0.0, 1.0, 0).is_empty());
+
+        let fine = linspace(0.0, 0.3, 7);
+        assert_eq!(fine.len(), 7);
+        assert_eq!(*fine.last().unwrap(), 0.3);
+    }
+
     #[test]
     fn test_repeat_basic() {
         let zeros = repeat(0, 5);
@@ -0,0 +1,353 @@
+//! # Debug runs: Following elements through a pipeline
+//!
+//! [`debug_run`] runs a pipeline while each of its stages records which source
+//! elements reached it and which it passed on, so when an element is missing
+//! from the output you can see which stage dropped it:
+//!
+//! ```rust
+//! use orlando_transducers::debug::debug_run;
+//! use orlando_transducers::{Filter, Map, Transducer};
+//!
+//! let pipeline = Map::new(|line: &str| line.trim().to_string())
+//!     .compose(Filter::new(|line: &String| !line.is_empty()))
+//!     .compose(Filter::new(|line: &String| !line.starts_with('#')));
+//!
+//! let run = debug_run(&pipeline, vec!["a", "  ", "# note", "b"], 100);
+//! assert_eq!(run.output, vec!["a", "b"]);
+//! assert_eq!(run.dropped_by(1), Some("filter")); // the blank line
+//!
+//! print!("{}", run);
+//! // #0 "a"  -> map -> filter -> filter -> output
+//! // #1 "  "  -> map -> filter (dropped)
+//! // #2 "# note"  -> map -> filter -> filter (dropped)
+//! // #3 "b"  -> map -> filter -> filter -> output
+//! ```
+//!
+//! Elements are identified by their position in the source. Each stage keeps
+//! the positions of the last `n` elements that reached it and the last `n` it
+//! emitted in a ring buffer, and the run keeps the last `n` source elements
+//! (formatted with `Debug`), so memory stays bounded on long sources.
+//!
+//! An element a stage emits is recorded under the position of the source
+//! element being processed at the time. Stages that emit several elements for
+//! one input (`flat_map`) record that position several times; stages that
+//! hold elements back (`chunk`, `aperture`) emit under the position of the
+//! element that completed the group, so the elements before it look dropped.
+//!
+//! Composed pipelines are traced stage by stage; any other transducer is
+//! traced as a single stage named after its [`describe`] output.
+//!
+//! [`describe`]: crate::transducer::Transducer::describe
+
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::fmt;
+
+/// The last `capacity` items pushed.
+struct Ring<T> {
+    capacity: usize,
+    items: VecDeque<T>,
+}
+
+impl<T> Ring<T> {
+    fn new(capacity: usize) -> Self {
+        Ring {
+            capacity,
+            items: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.items.into()
+    }
+}
+
+/// The positions recorded by one stage.
+struct TracedStage {
+    name: String,
+    inputs: RefCell<Ring<usize>>,
+    outputs: RefCell<Ring<usize>>,
+}
+
+/// Collects the traces of the stages of a pipeline during a [`debug_run`].
+///
+/// Passed to [`Transducer::apply_traced`]; there is no need to create one
+/// directly.
+pub struct Tracer {
+    capacity: usize,
+    /// Position of the source element being processed
+    position: Rc<Cell<usize>>,
+    /// Stages in the order they were applied, which is last stage first
+    stages: RefCell<Vec<Rc<TracedStage>>>,
+}
+
+impl Tracer {
+    fn new(capacity: usize) -> Self {
+        Tracer {
+            capacity,
+            position: Rc::new(Cell::new(0)),
+            stages: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Apply `transducer` to `reducer` as a single traced stage.
+    pub(crate) fn trace<T, In, Out, Acc, R>(
+        &self,
+        transducer: &T,
+        reducer: R,
+    ) -> Box<dyn Fn(Acc, In) -> Step<Acc>>
+    where
+        T: Transducer<In, Out>,
+        R: Fn(Acc, Out) -> Step<Acc> + 'static,
+        Acc: 'static,
+        In: 'static,
+        Out: 'static,
+    {
+        let names: Vec<String> = transducer
+            .describe()
+            .stages
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        let stage = Rc::new(TracedStage {
+            name: names.join(" + "),
+            inputs: RefCell::new(Ring::new(self.capacity)),
+            outputs: RefCell::new(Ring::new(self.capacity)),
+        });
+        self.stages.borrow_mut().push(Rc::clone(&stage));
+
+        let emitted = Rc::clone(&stage);
+        let position = Rc::clone(&self.position);
+        let inner = transducer.apply(move |acc, x| {
+            emitted.outputs.borrow_mut().push(position.get());
+            reducer(acc, x)
+        });
+
+        let position = Rc::clone(&self.position);
+        Box::new(move |acc, x| {
+            stage.inputs.borrow_mut().push(position.get());
+            inner(acc, x)
+        })
+    }
+}
+
+/// What one stage saw during a [`debug_run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTrace {
+    /// The stage's name, as in [`Transducer::describe`]
+    pub name: String,
+    /// Source positions of the last `n` elements that reached the stage
+    pub inputs: Vec<usize>,
+    /// Source positions of the last `n` elements the stage emitted
+    pub outputs: Vec<usize>,
+}
+
+/// The result of a [`debug_run`].
+#[derive(Debug, Clone)]
+pub struct DebugRun<U> {
+    /// Everything the pipeline produced
+    pub output: Vec<U>,
+    /// The last `n` source elements with their positions, formatted with
+    /// `Debug`
+    pub source: Vec<(usize, String)>,
+    /// Each stage's trace, in pipeline order
+    pub stages: Vec<StageTrace>,
+}
+
+impl<U> DebugRun<U> {
+    /// The source element at `position`, if it is among the last `n`.
+    pub fn value(&self, position: usize) -> Option<&str> {
+        self.source
+            .iter()
+            .find(|(p, _)| *p == position)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The first stage that received the element at `position` without
+    /// emitting anything for it, or `None` if it made it through (or is no
+    /// longer in the stages' buffers).
+    pub fn dropped_by(&self, position: usize) -> Option<&str> {
+        self.stages
+            .iter()
+            .find(|s| s.inputs.contains(&position) && !s.outputs.contains(&position))
+            .map(|s| s.name.as_str())
+    }
+}
+
+/// One line per buffered source element, listing the stages it went through.
+impl<U> fmt::Display for DebugRun<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, value) in &self.source {
+            write!(f, "#{} {} ", position, value)?;
+            let mut emitted = true;
+            for stage in &self.stages {
+                if !stage.inputs.contains(position) {
+                    break;
+                }
+                write!(f, " -> {}", stage.name)?;
+                if !stage.outputs.contains(position) {
+                    emitted = false;
+                    break;
+                }
+            }
+            if emitted {
+                writeln!(f, " -> output")?;
+            } else {
+                writeln!(f, " (dropped)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run a pipeline, recording the last `n` elements entering and leaving each
+/// stage.
+///
+/// Runs like [`to_vec`](crate::collectors::to_vec), stopping early if the
+/// pipeline does; see the [module docs](self) for what is recorded.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::debug::debug_run;
+/// use orlando_transducers::{Filter, Take, Transducer};
+///
+/// let pipeline = Filter::new(|x: &i32| x % 3 == 0).compose(Take::new(2));
+/// let run = debug_run(&pipeline, 1..=100, 4);
+///
+/// assert_eq!(run.output, vec![3, 6]);
+/// // The source stopped at 6; the filter saw its last four elements
+/// assert_eq!(run.stages[0].inputs, vec![2, 3, 4, 5]);
+/// assert_eq!(run.stages[1].inputs, vec![2, 5]);
+/// assert_eq!(run.dropped_by(4), Some("filter"));
+/// ```
+pub fn debug_run<T, U, Iter>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    n: usize,
+) -> DebugRun<U>
+where
+    T: fmt::Debug + 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+{
+    let tracer = Tracer::new(n);
+    let transformed = transducer.apply_traced(
+        |mut output: Vec<U>, x| {
+            output.push(x);
+            cont(output)
+        },
+        &tracer,
+    );
+
+    let mut output = Vec::new();
+    let mut recent = Ring::new(n);
+    for (position, item) in source.into_iter().enumerate() {
+        tracer.position.set(position);
+        recent.push((position, format!("{:?}", item)));
+        match transformed(output, item) {
+            Step::Continue(acc) => output = acc,
+            Step::Stop(acc) => {
+                output = acc;
+                break;
+            }
+        }
+    }
+
+    let stages = tracer
+        .stages
+        .borrow()
+        .iter()
+        .rev()
+        .map(|stage| StageTrace {
+            name: stage.name.clone(),
+            inputs: stage.inputs.borrow().items.iter().copied().collect(),
+            outputs: stage.outputs.borrow().items.iter().copied().collect(),
+        })
+        .collect();
+
+    DebugRun {
+        output,
+        source: recent.into_vec(),
+        stages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instrument::{InstrumentExt, Metrics};
+    use crate::transducer::Identity;
+    use crate::transforms::{Chunk, Filter, FlatMap, Map};
+
+    #[test]
+    fn test_records_each_stage() {
+        let pipeline = Map::new(|x: i32| x * 10)
+            .compose(Filter::new(|x: &i32| *x > 15))
+            .compose(Map::new(|x: i32| x + 1));
+        let run = debug_run(&pipeline, vec![1, 2, 3], 10);
+
+        assert_eq!(run.output, vec![21, 31]);
+        let names: Vec<_> = run.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["map", "filter", "map"]);
+        assert_eq!(run.stages[1].inputs, vec![0, 1, 2]);
+        assert_eq!(run.stages[1].outputs, vec![1, 2]);
+        assert_eq!(run.dropped_by(0), Some("filter"));
+        assert_eq!(run.dropped_by(1), None);
+        assert_eq!(run.value(2), Some("3"));
+    }
+
+    #[test]
+    fn test_keeps_last_n() {
+        let run = debug_run(&Map::new(|x: u32| x), 0..1000u32, 3);
+        assert_eq!(run.output.len(), 1000);
+        assert_eq!(run.stages[0].inputs, vec![997, 998, 999]);
+        assert_eq!(run.source.len(), 3);
+        assert_eq!(run.value(996), None);
+
+        let run = debug_run(&Map::new(|x: u32| x), 0..10u32, 0);
+        assert!(run.source.is_empty() && run.stages[0].outputs.is_empty());
+    }
+
+    #[test]
+    fn test_expanding_and_buffering_stages() {
+        let pipeline = FlatMap::new(|x: i32| vec![x; x as usize]).compose(Chunk::new(2));
+        let run = debug_run(&pipeline, vec![0, 1, 2], 10);
+
+        assert_eq!(run.output, vec![vec![1, 2]]);
+        assert_eq!(run.stages[0].outputs, vec![1, 2, 2]);
+        assert_eq!(run.stages[1].outputs, vec![2]);
+        assert_eq!(run.dropped_by(0), Some("flat_map"));
+    }
+
+    #[test]
+    fn test_single_stage_and_identity() {
+        let metrics = Metrics::new();
+        let stage = Filter::new(|x: &i32| x % 2 == 0).instrument("even", &metrics);
+        let run = debug_run(&stage, 0..4, 10);
+        assert_eq!(run.stages.len(), 1);
+        assert_eq!(run.stages[0].name, "filter");
+        assert_eq!(metrics.stage("even").unwrap().inputs, 4);
+
+        let run = debug_run(&Identity::<i32>::new(), 0..2, 10);
+        assert!(run.stages.is_empty());
+        assert_eq!(run.to_string(), "#0 0  -> output\n#1 1  -> output\n");
+    }
+}
@@ -0,0 +1,170 @@
+//! A value that is one of two types.
+//!
+//! `Either<L, R>` is the sum type used by collectors and transducers that route
+//! each element down one of two branches, such as
+//! [`partition_map`](crate::collectors::partition_map). By convention `Left` is
+//! the first output and `Right` the second.
+
+use core::fmt;
+
+/// A value that is either `Left(L)` or `Right(R)`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::either::Either;
+///
+/// let parsed: Vec<Either<i32, String>> = vec!["1", "x", "3"]
+///     .into_iter()
+///     .map(|s| match s.parse() {
+///         Ok(n) => Either::Left(n),
+///         Err(_) => Either::Right(s.to_string()),
+///     })
+///     .collect();
+///
+/// assert!(parsed[0].is_left());
+/// assert_eq!(parsed[1].clone().right(), Some("x".to_string()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    /// The first alternative
+    Left(L),
+    /// The second alternative
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Returns true if this is `Left`
+    #[inline(always)]
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    /// Returns true if this is `Right`
+    #[inline(always)]
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// Convert to `Some` if `Left`, `None` otherwise
+    #[inline(always)]
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(l) => Some(l),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Convert to `Some` if `Right`, `None` otherwise
+    #[inline(always)]
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(r) => Some(r),
+        }
+    }
+
+    /// Map a function over the `Left` value, leaving `Right` untouched
+    #[inline(always)]
+    pub fn map_left<M, F>(self, f: F) -> Either<M, R>
+    where
+        F: FnOnce(L) -> M,
+    {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(r),
+        }
+    }
+
+    /// Map a function over the `Right` value, leaving `Left` untouched
+    #[inline(always)]
+    pub fn map_right<M, F>(self, f: F) -> Either<L, M>
+    where
+        F: FnOnce(R) -> M,
+    {
+        match self {
+            Either::Left(l) => Either::Left(l),
+            Either::Right(r) => Either::Right(f(r)),
+        }
+    }
+
+    /// Collapse both alternatives into a single value
+    #[inline(always)]
+    pub fn either<T, F, G>(self, f: F, g: G) -> T
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> T,
+    {
+        match self {
+            Either::Left(l) => f(l),
+            Either::Right(r) => g(r),
+        }
+    }
+
+    /// Swap `Left` and `Right`
+    #[inline(always)]
+    pub fn flip(self) -> Either<R, L> {
+        match self {
+            Either::Left(l) => Either::Right(l),
+            Either::Right(r) => Either::Left(r),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Either<T, E> {
+    /// `Ok` becomes `Left`, `Err` becomes `Right`
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(v) => Either::Left(v),
+            Err(e) => Either::Right(e),
+        }
+    }
+}
+
+impl<L: fmt::Display, R: fmt::Display> fmt::Display for Either<L, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Either::Left(l) => write!(f, "Left({})", l),
+            Either::Right(r) => write!(f, "Right({})", r),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let l: Either<i32, &str> = Either::Left(1);
+        let r: Either<i32, &str> = Either::Right("a");
+        assert!(l.is_left() && !l.is_right());
+        assert!(r.is_right() && !r.is_left());
+        assert_eq!(l.left(), Some(1));
+        assert_eq!(l.right(), None);
+        assert_eq!(r.right(), Some("a"));
+    }
+
+    #[test]
+    fn test_map_and_collapse() {
+        let l: Either<i32, i32> = Either::Left(2);
+        assert_eq!(l.map_left(|x| x * 10), Either::Left(20));
+        assert_eq!(l.map_right(|x| x * 10), Either::Left(2));
+        assert_eq!(l.either(|x| x + 1, |x| x - 1), 3);
+        assert_eq!(l.flip(), Either::Right(2));
+    }
+
+    #[test]
+    fn test_from_result() {
+        let ok: Either<i32, String> = Ok::<i32, String>(5).into();
+        let err: Either<i32, String> = Err::<i32, String>("bad".into()).into();
+        assert_eq!(ok, Either::Left(5));
+        assert_eq!(err, Either::Right("bad".to_string()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Either::<i32, i32>::Left(1)), "Left(1)");
+        assert_eq!(format!("{}", Either::<i32, i32>::Right(2)), "Right(2)");
+    }
+}
@@ -0,0 +1,164 @@
+//! Structured errors for the WASM API.
+//!
+//! Failures are thrown to JavaScript as ordinary `Error` objects named
+//! `OrlandoError`, carrying a machine-readable `code` and, where known, the
+//! operation that failed, the value it was processing and the original
+//! exception as `cause`:
+//!
+//! ```javascript
+//! try {
+//!   new Pipeline().strict().map(JSON.parse).toArray(lines);
+//! } catch (e) {
+//!   if (isOrlandoError(e) && e.code === 'CALLBACK_THREW') {
+//!     console.error(`${e.operationName} failed on`, e.value, e.cause);
+//!   }
+//! }
+//! ```
+
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ORLANDO_ERROR_TS: &str = r#"
+/** Machine-readable category of an `OrlandoError`. */
+export type OrlandoErrorCode =
+  | 'CALLBACK_THREW'
+  | 'INVALID_ARGUMENT'
+  | 'INVALID_PLAN'
+  | 'NOT_SERIALIZABLE'
+  | 'PARSE_ERROR';
+
+/** An `Error` thrown by Orlando, with diagnostics about what failed. */
+export interface OrlandoError extends Error {
+  name: 'OrlandoError';
+  code: OrlandoErrorCode;
+  /** Position of the failing operation in the pipeline's execution plan */
+  operation?: number;
+  /** Name of the failing operation, e.g. `'map'` */
+  operationName?: string;
+  /** The value the failing operation was processing */
+  value?: unknown;
+  /** Position of the source element being processed */
+  index?: number;
+  /** The original exception */
+  cause?: unknown;
+}
+"#;
+
+/// Machine-readable error categories, exposed to JavaScript as `error.code`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorCode {
+    /// A user callback threw in a strict pipeline
+    CallbackThrew,
+    /// An argument has the wrong shape (e.g. a malformed `where` spec)
+    InvalidArgument,
+    /// A serialized plan can't be turned back into a pipeline
+    InvalidPlan,
+    /// A pipeline holding callbacks was asked for a plan
+    NotSerializable,
+    /// Input read by a source isn't in the expected format
+    ParseError,
+}
+
+impl ErrorCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::CallbackThrew => "CALLBACK_THREW",
+            ErrorCode::InvalidArgument => "INVALID_ARGUMENT",
+            ErrorCode::InvalidPlan => "INVALID_PLAN",
+            ErrorCode::NotSerializable => "NOT_SERIALIZABLE",
+            ErrorCode::ParseError => "PARSE_ERROR",
+        }
+    }
+}
+
+/// An error on its way to JavaScript. Converting it into a `JsValue` builds
+/// the `OrlandoError` object.
+pub(crate) struct OrlandoError {
+    code: ErrorCode,
+    message: String,
+    operation: Option<(usize, &'static str)>,
+    value: Option<JsValue>,
+    index: Option<usize>,
+    cause: Option<JsValue>,
+}
+
+impl OrlandoError {
+    pub(crate) fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        OrlandoError {
+            code,
+            message: message.into(),
+            operation: None,
+            value: None,
+            index: None,
+            cause: None,
+        }
+    }
+
+    /// Record the failing operation's position and name.
+    pub(crate) fn at_operation(mut self, position: usize, name: &'static str) -> Self {
+        self.operation = Some((position, name));
+        self
+    }
+
+    /// Record the value being processed when the failure happened.
+    pub(crate) fn with_value(mut self, value: JsValue) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Record the position of the source element being processed.
+    pub(crate) fn at_element(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Keep the original exception as `cause`.
+    pub(crate) fn with_cause(mut self, cause: JsValue) -> Self {
+        self.cause = Some(cause);
+        self
+    }
+}
+
+impl From<OrlandoError> for JsValue {
+    fn from(err: OrlandoError) -> JsValue {
+        let mut message = err.message;
+        if let Some((position, name)) = err.operation {
+            message = format!("`{}` (operation {}): {}", name, position, message);
+        }
+        if let Some(index) = err.index {
+            message = format!("{} while processing element {}", message, index);
+        }
+
+        let error = js_sys::Error::new(&message);
+        error.set_name("OrlandoError");
+        let set = |key: &str, value: &JsValue| {
+            let _ = Reflect::set(&error, &key.into(), value);
+        };
+        set("code", &err.code.as_str().into());
+        if let Some((position, name)) = err.operation {
+            set("operation", &(position as f64).into());
+            set("operationName", &name.into());
+        }
+        if let Some(value) = &err.value {
+            set("value", value);
+        }
+        if let Some(index) = err.index {
+            set("index", &(index as f64).into());
+        }
+        if let Some(cause) = &err.cause {
+            set("cause", cause);
+        }
+        error.into()
+    }
+}
+
+/// Whether `value` is an error thrown by Orlando.
+#[wasm_bindgen(js_name = isOrlandoError, unchecked_return_type = "value is OrlandoError")]
+pub fn is_orlando_error(value: &JsValue) -> bool {
+    value
+        .dyn_ref::<js_sys::Error>()
+        .is_some_and(|error| error.name() == "OrlandoError")
+        && Reflect::get(value, &"code".into()).is_ok_and(|code| code.is_string())
+}
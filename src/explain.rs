@@ -0,0 +1,240 @@
+//! # Explain: Describing a pipeline's stages
+//!
+//! [`Transducer::describe`] lists the stages of a (possibly composed)
+//! transducer as [`StageInfo`]s: what each stage is, whether it keeps state
+//! between elements, and whether it can end the run early. [`Explain`]'s
+//! `Display` renders them like a database `EXPLAIN`:
+//!
+//! ```rust
+//! use orlando_transducers::{Filter, Map, Take, Transducer};
+//!
+//! let pipeline = Map::new(|x: i32| x * 2)
+//!     .compose(Filter::new(|x: &i32| x % 3 == 0))
+//!     .compose(Take::new(5));
+//!
+//! let plan = pipeline.describe();
+//! assert_eq!(plan.stages.len(), 3);
+//! assert!(plan.is_stateful() && plan.may_stop());
+//!
+//! print!("{}", pipeline.explain());
+//! // Pipeline (3 stages)
+//! //   1. map
+//! //   2. filter
+//! //   3. take(5)  [stateful, may stop]
+//! // Stateful: yes (take(5))
+//! // Early termination: possible (take(5))
+//! ```
+//!
+//! Transducers defined outside this crate are described by their type name,
+//! as stateless and never stopping, unless they override `describe`.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// One stage of a pipeline, as reported by [`Transducer::describe`].
+///
+/// [`Transducer::describe`]: crate::transducer::Transducer::describe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageInfo {
+    /// The stage's name and parameters, e.g. `take(5)`
+    pub name: String,
+    /// Whether the stage keeps state between elements
+    pub stateful: bool,
+    /// Whether the stage can end the run before the source is exhausted
+    pub may_stop: bool,
+    /// The operations fused into this stage, if it was built by merging
+    /// several (empty otherwise)
+    pub fused: Vec<String>,
+}
+
+impl StageInfo {
+    /// A stateless stage that never stops the run.
+    pub fn new(name: impl Into<String>) -> Self {
+        StageInfo {
+            name: name.into(),
+            stateful: false,
+            may_stop: false,
+            fused: Vec::new(),
+        }
+    }
+
+    /// Mark the stage as keeping state between elements.
+    pub fn stateful(mut self) -> Self {
+        self.stateful = true;
+        self
+    }
+
+    /// Mark the stage as able to end the run early.
+    pub fn may_stop(mut self) -> Self {
+        self.may_stop = true;
+        self
+    }
+}
+
+/// The stages of a pipeline, in the order elements flow through them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explain {
+    pub stages: Vec<StageInfo>,
+}
+
+impl Explain {
+    /// A description with a single stage.
+    pub fn stage(stage: StageInfo) -> Self {
+        Explain {
+            stages: vec![stage],
+        }
+    }
+
+    /// The stages of `self` followed by those of `next`.
+    pub fn then(mut self, next: Explain) -> Self {
+        self.stages.extend(next.stages);
+        self
+    }
+
+    /// Whether any stage keeps state between elements.
+    ///
+    /// Stateful pipelines depend on element order and can't be split across
+    /// threads element by element.
+    pub fn is_stateful(&self) -> bool {
+        self.stages.iter().any(|s| s.stateful)
+    }
+
+    /// Whether any stage can end the run before the source is exhausted.
+    pub fn may_stop(&self) -> bool {
+        self.stages.iter().any(|s| s.may_stop)
+    }
+}
+
+/// Names of the stages matching `select`, comma separated.
+fn names(stages: &[StageInfo], select: impl Fn(&StageInfo) -> bool) -> String {
+    stages
+        .iter()
+        .filter(|s| select(s))
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for Explain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.stages.len();
+        writeln!(
+            f,
+            "Pipeline ({} stage{})",
+            count,
+            if count == 1 { "" } else { "s" }
+        )?;
+        for (i, stage) in self.stages.iter().enumerate() {
+            write!(f, "  {}. {}", i + 1, stage.name)?;
+            let flags: Vec<&str> = [(stage.stateful, "stateful"), (stage.may_stop, "may stop")]
+                .into_iter()
+                .filter_map(|(set, flag)| set.then_some(flag))
+                .collect();
+            if !flags.is_empty() {
+                write!(f, "  [{}]", flags.join(", "))?;
+            }
+            if !stage.fused.is_empty() {
+                write!(f, "  (fused: {})", stage.fused.join(" + "))?;
+            }
+            writeln!(f)?;
+        }
+        if self.is_stateful() {
+            writeln!(f, "Stateful: yes ({})", names(&self.stages, |s| s.stateful))?;
+        } else {
+            writeln!(f, "Stateful: no")?;
+        }
+        if self.may_stop() {
+            writeln!(
+                f,
+                "Early termination: possible ({})",
+                names(&self.stages, |s| s.may_stop)
+            )
+        } else {
+            writeln!(f, "Early termination: no")
+        }
+    }
+}
+
+/// The last path segment of a type name, without generic arguments, for
+/// describing transducers that don't override `describe`.
+pub(crate) fn short_type_name<T: ?Sized>() -> String {
+    let full = core::any::type_name::<T>();
+    let base = full.split('<').next().unwrap_or(full);
+    base.rsplit("::").next().unwrap_or(base).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::step::Step;
+    use crate::transducer::{Identity, Transducer};
+    use crate::transforms::{Chunk, Filter, FlatMap, Map, Take, TakeWhile};
+
+    #[test]
+    fn test_composed_stages_in_order() {
+        let pipeline = Map::new(|x: i32| x + 1)
+            .compose(Filter::new(|x: &i32| *x > 0))
+            .compose(Chunk::new(3))
+            .compose(FlatMap::new(|v: Vec<i32>| v));
+
+        let names: Vec<_> = pipeline
+            .describe()
+            .stages
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["map", "filter", "chunk(3)", "flat_map"]);
+        assert!(pipeline.describe().is_stateful());
+        assert!(!pipeline.describe().may_stop());
+    }
+
+    #[test]
+    fn test_early_termination() {
+        let pipeline = Map::new(|x: i32| x).compose(TakeWhile::new(|x: &i32| *x < 10));
+        let plan = pipeline.describe();
+        assert!(plan.may_stop());
+        assert!(!plan.is_stateful());
+        assert!(pipeline
+            .explain()
+            .contains("Early termination: possible (take_while)"));
+    }
+
+    #[test]
+    fn test_identity_has_no_stages() {
+        let pipeline = Identity::<i32>::new().compose(Take::new(1));
+        assert_eq!(
+            pipeline.describe().stages,
+            vec![StageInfo::new("take(1)").stateful().may_stop()]
+        );
+    }
+
+    #[test]
+    fn test_explain_text() {
+        let text = Map::new(|x: i32| x).compose(Take::new(5)).explain();
+        assert_eq!(
+            text,
+            "Pipeline (2 stages)\n  1. map\n  2. take(5)  [stateful, may stop]\n\
+             Stateful: yes (take(5))\nEarly termination: possible (take(5))\n"
+        );
+    }
+
+    struct Custom;
+
+    impl Transducer<u8, u8> for Custom {
+        fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, u8) -> Step<Acc>>
+        where
+            R: Fn(Acc, u8) -> Step<Acc> + 'static,
+            Acc: 'static,
+        {
+            Box::new(reducer)
+        }
+    }
+
+    #[test]
+    fn test_external_transducer_uses_type_name() {
+        assert_eq!(Custom.describe().stages[0].name, "Custom");
+        assert_eq!(short_type_name::<Vec<Option<u8>>>(), "Vec");
+    }
+}
@@ -0,0 +1,368 @@
+//! # External: Collectors that spill to disk
+//!
+//! [`group_by_external`] groups a stream by key like
+//! [`group_by`](crate::collectors::group_by), but without holding every group
+//! in memory at once. Elements are hash-partitioned by key and buffered; when
+//! the buffer fills up, each partition is appended to its own temporary file.
+//! The groups are then read back one partition at a time, so peak memory is
+//! bounded by the buffer size and the largest partition rather than by the
+//! whole input.
+//!
+//! ```rust,no_run
+//! use orlando_transducers::external::{group_by_external, SpillConfig};
+//! use orlando_transducers::Map;
+//!
+//! # let events: Vec<(u64, String)> = Vec::new();
+//! let by_user = Map::new(|(user, page): (u64, String)| (user, page));
+//! let config = SpillConfig::new().partitions(256).max_in_memory(5_000_000);
+//!
+//! for group in group_by_external(&by_user, events, |(user, _)| *user, config).unwrap() {
+//!     let (user, visits) = group.unwrap();
+//!     println!("{}: {} page views", user, visits.len());
+//! }
+//! ```
+//!
+//! Elements and keys are written with `bincode`, so they must implement
+//! serde's `Serialize` and `Deserialize`. The temporary files are removed when
+//! the returned [`ExternalGroups`] is dropped.
+//!
+//! Requires the `spill` feature. Only available on native targets.
+
+use crate::collectors::reduce;
+use crate::step::{cont, stop};
+use crate::transducer::Transducer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::hash_map::{self, RandomState};
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::{BuildHasher, Hash};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Settings for [`group_by_external`].
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    partitions: usize,
+    max_in_memory: usize,
+    dir: Option<PathBuf>,
+}
+
+impl SpillConfig {
+    /// 64 partitions, spilling every 1,000,000 buffered elements, in the
+    /// system temporary directory.
+    pub fn new() -> Self {
+        SpillConfig {
+            partitions: 64,
+            max_in_memory: 1_000_000,
+            dir: None,
+        }
+    }
+
+    /// Number of partitions, and so of temporary files. Groups are read back
+    /// one partition at a time, so more partitions means less memory when
+    /// reading.
+    pub fn partitions(mut self, partitions: usize) -> Self {
+        assert!(partitions > 0, "partitions must be greater than 0");
+        self.partitions = partitions;
+        self
+    }
+
+    /// Number of elements buffered in memory before the buffer is written to
+    /// disk.
+    pub fn max_in_memory(mut self, elements: usize) -> Self {
+        assert!(elements > 0, "max_in_memory must be greater than 0");
+        self.max_in_memory = elements;
+        self
+    }
+
+    /// Directory to create the temporary files in, instead of the system
+    /// temporary directory.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+}
+
+impl Default for SpillConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Group elements by a key function, spilling partitions to temporary files
+/// when more than [`SpillConfig::max_in_memory`] elements are buffered.
+///
+/// Returns an iterator of `(key, group)` pairs in no particular order;
+/// elements within a group keep their stream order. If nothing needed to be
+/// spilled, no files are created. Errors creating or writing the files are
+/// returned here; errors reading them back are yielded by the iterator.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::external::{group_by_external, SpillConfig};
+/// use orlando_transducers::transducer::Identity;
+/// use std::collections::HashMap;
+///
+/// // Spill every 100 elements, so most of the data goes through disk
+/// let config = SpillConfig::new().partitions(8).max_in_memory(100);
+/// let groups = group_by_external(&Identity::<u32>::new(), 0..10_000, |x| x % 3, config).unwrap();
+/// assert!(groups.spilled());
+///
+/// let groups: HashMap<u32, Vec<u32>> = groups.map(Result::unwrap).collect();
+/// assert_eq!(groups[&1][..3], [1, 4, 7]);
+/// assert_eq!(groups[&0].len(), 3334);
+/// ```
+pub fn group_by_external<T, U, K, Iter, F>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    key_fn: F,
+    config: SpillConfig,
+) -> io::Result<ExternalGroups<K, U>>
+where
+    T: 'static,
+    U: Serialize + DeserializeOwned + 'static,
+    K: Eq + Hash + Serialize + DeserializeOwned + 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(&U) -> K + 'static,
+{
+    let initial = Spill {
+        hasher: RandomState::new(),
+        buffers: (0..config.partitions).map(|_| Vec::new()).collect(),
+        buffered: 0,
+        files: None,
+        error: None,
+        config,
+    };
+
+    let reducer = move |mut spill: Spill<K, U>, x: U| {
+        let key = key_fn(&x);
+        let partition = spill.partition(&key);
+        spill.buffers[partition].push((key, x));
+        spill.buffered += 1;
+        if spill.buffered >= spill.config.max_in_memory {
+            if let Err(e) = spill.flush() {
+                spill.error = Some(e);
+                return stop(spill);
+            }
+        }
+        cont(spill)
+    };
+
+    let spill = reduce(transducer, source, initial, reducer);
+    spill.finish()
+}
+
+/// Writer state while grouping.
+struct Spill<K, V> {
+    config: SpillConfig,
+    hasher: RandomState,
+    buffers: Vec<Vec<(K, V)>>,
+    buffered: usize,
+    /// The temporary directory and one writer per partition, created at the
+    /// first spill
+    files: Option<(tempfile::TempDir, Vec<BufWriter<File>>)>,
+    error: Option<io::Error>,
+}
+
+impl<K: Hash + Serialize, V: Serialize> Spill<K, V> {
+    fn partition(&self, key: &K) -> usize {
+        (self.hasher.hash_one(key) % self.buffers.len() as u64) as usize
+    }
+
+    /// Append every buffered element to its partition's file.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.files.is_none() {
+            let dir = match &self.config.dir {
+                Some(parent) => tempfile::Builder::new()
+                    .prefix("orlando-group-")
+                    .tempdir_in(parent)?,
+                None => tempfile::Builder::new()
+                    .prefix("orlando-group-")
+                    .tempdir()?,
+            };
+            let writers = (0..self.buffers.len())
+                .map(|i| File::create(partition_path(&dir, i)).map(BufWriter::new))
+                .collect::<io::Result<_>>()?;
+            self.files = Some((dir, writers));
+        }
+
+        let (_, writers) = self.files.as_mut().expect("files are created above");
+        for (buffer, writer) in self.buffers.iter_mut().zip(writers) {
+            for record in buffer.drain(..) {
+                bincode::serialize_into(&mut *writer, &record).map_err(|e| into_io(*e))?;
+            }
+        }
+        self.buffered = 0;
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<ExternalGroups<K, V>> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let dir = match self.files {
+            Some((dir, writers)) => {
+                for mut writer in writers {
+                    writer.flush()?;
+                }
+                Some(dir)
+            }
+            None => None,
+        };
+        Ok(ExternalGroups {
+            dir,
+            buffers: self.buffers.into_iter(),
+            partition: 0,
+            current: HashMap::new().into_iter(),
+        })
+    }
+}
+
+fn partition_path(dir: &tempfile::TempDir, partition: usize) -> PathBuf {
+    dir.path().join(format!("{}.bin", partition))
+}
+
+fn into_io(e: bincode::ErrorKind) -> io::Error {
+    match e {
+        bincode::ErrorKind::Io(e) => e,
+        other => io::Error::new(io::ErrorKind::InvalidData, other),
+    }
+}
+
+/// Iterator returned by [`group_by_external`], yielding one `(key, group)`
+/// pair at a time.
+///
+/// Each partition's groups are loaded into memory together when the first of
+/// them is requested.
+pub struct ExternalGroups<K, V> {
+    /// The spill directory, `None` if everything fit in memory
+    dir: Option<tempfile::TempDir>,
+    /// The elements still in memory for the partitions not yet read
+    buffers: std::vec::IntoIter<Vec<(K, V)>>,
+    partition: usize,
+    current: hash_map::IntoIter<K, Vec<V>>,
+}
+
+impl<K, V> ExternalGroups<K, V> {
+    /// Whether any elements were written to disk.
+    pub fn spilled(&self) -> bool {
+        self.dir.is_some()
+    }
+}
+
+impl<K: Eq + Hash + DeserializeOwned, V: DeserializeOwned> ExternalGroups<K, V> {
+    /// Load the groups of the next partition: the spilled elements first, then
+    /// the ones still in memory.
+    fn load(&mut self, buffer: Vec<(K, V)>) -> io::Result<HashMap<K, Vec<V>>> {
+        let mut groups: HashMap<K, Vec<V>> = HashMap::new();
+        if let Some(dir) = &self.dir {
+            let mut reader = BufReader::new(File::open(partition_path(dir, self.partition))?);
+            while !reader.fill_buf()?.is_empty() {
+                let (key, value) =
+                    bincode::deserialize_from(&mut reader).map_err(|e| into_io(*e))?;
+                groups.entry(key).or_default().push(value);
+            }
+        }
+        for (key, value) in buffer {
+            groups.entry(key).or_default().push(value);
+        }
+        Ok(groups)
+    }
+}
+
+impl<K: Eq + Hash + DeserializeOwned, V: DeserializeOwned> Iterator for ExternalGroups<K, V> {
+    type Item = io::Result<(K, Vec<V>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(group) = self.current.next() {
+                return Some(Ok(group));
+            }
+            let buffer = self.buffers.next()?;
+            let loaded = self.load(buffer);
+            self.partition += 1;
+            match loaded {
+                Ok(groups) => self.current = groups.into_iter(),
+                Err(e) => {
+                    // Skip the remaining partitions after a read error
+                    self.buffers = Vec::new().into_iter();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::group_by;
+    use crate::transducer::Identity;
+    use crate::transforms::{Filter, Map, Take};
+
+    fn sorted<K: Ord, V>(
+        groups: impl Iterator<Item = io::Result<(K, Vec<V>)>>,
+    ) -> Vec<(K, Vec<V>)> {
+        let mut groups: Vec<_> = groups.map(Result::unwrap).collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+        groups
+    }
+
+    #[test]
+    fn test_matches_in_memory_group_by() {
+        let pipeline =
+            Map::new(|x: u64| (x * 7919) % 1000).compose(Filter::new(|x: &u64| *x < 500));
+        let config = SpillConfig::new().partitions(5).max_in_memory(37);
+        let external = group_by_external(&pipeline, 0..5_000, |x| x % 50, config).unwrap();
+        assert!(external.spilled());
+
+        let mut expected: Vec<_> = group_by(&pipeline, 0..5_000, |x| x % 50)
+            .into_iter()
+            .collect();
+        expected.sort_by_key(|group| group.0);
+        assert_eq!(sorted(external), expected);
+    }
+
+    #[test]
+    fn test_small_input_stays_in_memory() {
+        let groups = group_by_external(
+            &Identity::<String>::new(),
+            vec!["ant".to_string(), "bee".to_string(), "asp".to_string()],
+            |s| s.chars().next().unwrap(),
+            SpillConfig::new(),
+        )
+        .unwrap();
+        assert!(!groups.spilled());
+        assert_eq!(
+            sorted(groups),
+            vec![
+                ('a', vec!["ant".to_string(), "asp".to_string()]),
+                ('b', vec!["bee".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_early_termination() {
+        let config = SpillConfig::new().partitions(2).max_in_memory(3);
+        let groups = group_by_external(&Take::new(10), 0u32.., |x| x % 2, config).unwrap();
+        assert_eq!(
+            sorted(groups),
+            vec![(0, vec![0, 2, 4, 6, 8]), (1, vec![1, 3, 5, 7, 9])]
+        );
+    }
+
+    #[test]
+    fn test_files_removed_on_drop() {
+        let parent = tempfile::tempdir().unwrap();
+        let config = SpillConfig::new().max_in_memory(1).dir(parent.path());
+        let mut groups = group_by_external(&Identity::<u8>::new(), 0..10, |x| *x, config).unwrap();
+        assert!(groups.next().is_some());
+        assert_eq!(std::fs::read_dir(parent.path()).unwrap().count(), 1);
+
+        drop(groups);
+        assert_eq!(std::fs::read_dir(parent.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,364 @@
+//! Experimental WebGPU path for numeric pipelines.
+//!
+//! Enabled by the `webgpu` feature. `sumGpu` and `toFloat32ArrayGpu` compile
+//! a pipeline made of the built-in numeric operations (`mapAdd`, `mapMul`,
+//! `scaleOffset`, `normalize`, `filterGt`, `filterRange`) into a compute
+//! shader with [`simd::wgsl`](crate::simd::wgsl) and run it over a
+//! `Float32Array` on the GPU:
+//!
+//! ```javascript
+//! const readings = new Float32Array(50_000_000);
+//! const total = await new Pipeline()
+//!   .scaleOffset(0.01, -40)
+//!   .filterRange(-10, 60)
+//!   .sumGpu(readings);
+//! ```
+//!
+//! Whenever the GPU can't be used, the result comes from the SIMD path
+//! instead, so the methods are safe to call anywhere: without WebGPU (Node,
+//! older browsers), for sources that aren't `Float32Array`s or are too small
+//! to be worth the copy to the GPU, for plans with `take`, `drop`, `unique`
+//! or a callback, and if the GPU reports an error. The device is requested
+//! once and shared by every pipeline; compiled shaders are cached.
+//!
+//! GPU arithmetic is `f32`, so results can differ from `sum`, which widens
+//! to `f64`, in the last digits.
+
+use crate::pipeline::Pipeline;
+use crate::simd::{wgsl, NumericOp};
+use js_sys::{Array, Float32Array, Function, Object, Promise, Reflect};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+#[wasm_bindgen(typescript_custom_section)]
+const GPU_TS: &str = r#"
+export interface Pipeline<In = any, Out = In> {
+  /** `sum` on the GPU for large Float32Arrays, falling back to the SIMD path. */
+  sumGpu(source: Float32Array | PipelineSource<In>): Promise<number>;
+  /** The numeric results as a Float32Array, mapped on the GPU when possible. */
+  toFloat32ArrayGpu(source: Float32Array | PipelineSource<In>): Promise<Float32Array>;
+}
+
+/** Whether `sumGpu` and `toFloat32ArrayGpu` can use a WebGPU device here. */
+export function gpuAvailable(): Promise<boolean>;
+"#;
+
+/// Inputs shorter than this stay on the SIMD path: copying them to the GPU
+/// and back costs more than the shader saves.
+const GPU_MIN_ELEMENTS: u32 = 1 << 20;
+
+// `GPUBufferUsage` and `GPUMapMode` flags
+const MAP_READ: u32 = 0x0001;
+const COPY_SRC: u32 = 0x0004;
+const COPY_DST: u32 = 0x0008;
+const STORAGE: u32 = 0x0080;
+const MAP_MODE_READ: u32 = 0x0001;
+
+thread_local! {
+    // `None` until a device has been requested, then the device, or `None`
+    // inside if there is no usable GPU
+    static GPU: RefCell<Option<Option<Rc<Gpu>>>> = const { RefCell::new(None) };
+}
+
+#[wasm_bindgen]
+impl Pipeline {
+    /// Sum the pipeline's numeric results, on the GPU when the source is a
+    /// large `Float32Array` and the pipeline compiles to a shader.
+    ///
+    /// Resolves to the same value as `sum` otherwise. See the `webgpu`
+    /// feature.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const energy = await new Pipeline().mapMul(gain).filterGt(noiseFloor).sumGpu(samples);
+    /// ```
+    #[wasm_bindgen(js_name = sumGpu)]
+    pub fn sum_gpu(&self, source: JsValue) -> Promise {
+        let pipeline = self.clone();
+
+        future_to_promise(async move {
+            if let Some((input, plan)) = gpu_job(&pipeline, &source) {
+                if let Some(shader) = wgsl::sum_shader(&plan) {
+                    let (x, y) = wgsl::dispatch_size(input.length() as usize);
+                    if let Ok(Some(partials)) = run(shader, &input, (x * y) as usize).await {
+                        let total: f64 = partials.to_vec().into_iter().map(f64::from).sum();
+                        return Ok(JsValue::from_f64(total));
+                    }
+                }
+            }
+            Ok(JsValue::from_f64(pipeline.sum(&source)?))
+        })
+    }
+
+    /// Collect the pipeline's numeric results into a `Float32Array`,
+    /// mapping on the GPU when the source is a large `Float32Array` and the
+    /// pipeline has only numeric maps.
+    ///
+    /// Filters change the output length, which the shader can't, so
+    /// pipelines with them run on the SIMD path. Non-numeric results are
+    /// skipped. See the `webgpu` feature.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const celsius = await new Pipeline().scaleOffset(5 / 9, -160 / 9).toFloat32ArrayGpu(fahrenheit);
+    /// ```
+    #[wasm_bindgen(js_name = toFloat32ArrayGpu)]
+    pub fn to_float32_array_gpu(&self, source: JsValue) -> Promise {
+        let pipeline = self.clone();
+
+        future_to_promise(async move {
+            if let Some((input, plan)) = gpu_job(&pipeline, &source) {
+                if let Some(shader) = wgsl::map_shader(&plan) {
+                    let len = input.length() as usize;
+                    if let Ok(Some(output)) = run(shader, &input, len).await {
+                        return Ok(output.into());
+                    }
+                }
+            }
+            let values: Vec<f32> = pipeline
+                .numeric_results(&source)?
+                .into_iter()
+                .map(|x| x as f32)
+                .collect();
+            Ok(Float32Array::from(values.as_slice()).into())
+        })
+    }
+}
+
+/// Whether a WebGPU device is available to `sumGpu` and
+/// `toFloat32ArrayGpu`.
+///
+/// # Examples (JavaScript)
+///
+/// ```javascript
+/// console.log(`summing on the ${(await gpuAvailable()) ? 'GPU' : 'CPU'}`);
+/// ```
+#[wasm_bindgen(js_name = gpuAvailable, skip_typescript)]
+pub fn gpu_available() -> Promise {
+    future_to_promise(async { Ok(gpu().await.is_some().into()) })
+}
+
+/// The input and the `f32` plan, if the source and pipeline are candidates
+/// for the GPU; whether the plan compiles depends on the shader.
+fn gpu_job(pipeline: &Pipeline, source: &JsValue) -> Option<(Float32Array, Vec<NumericOp<f32>>)> {
+    let input = source.dyn_ref::<Float32Array>()?;
+    if input.length() < GPU_MIN_ELEMENTS {
+        return None;
+    }
+    let plan = pipeline.numeric_plan()?.into_iter().map(narrow).collect();
+    Some((input.clone(), plan))
+}
+
+/// Run `shader` over `input` into `output_len` floats, or `None` if there is
+/// no GPU or the buffers would exceed its limits.
+async fn run(
+    shader: String,
+    input: &Float32Array,
+    output_len: usize,
+) -> Result<Option<Float32Array>, JsValue> {
+    let Some(gpu) = gpu().await else {
+        return Ok(None);
+    };
+    let (input_bytes, output_bytes) = (input.byte_length() as f64, (output_len * 4) as f64);
+    if input_bytes.max(output_bytes) > gpu.max_binding_size {
+        return Ok(None);
+    }
+    gpu.run(shader, input, output_len).await.map(Some)
+}
+
+/// The shared device, requesting it on first use.
+async fn gpu() -> Option<Rc<Gpu>> {
+    if let Some(gpu) = GPU.with(|cell| cell.borrow().clone()) {
+        return gpu;
+    }
+    let gpu = Gpu::request().await.ok().flatten().map(Rc::new);
+    GPU.with(|cell| *cell.borrow_mut() = Some(gpu.clone()));
+    gpu
+}
+
+/// A WebGPU device and the compute pipelines compiled on it, by shader.
+struct Gpu {
+    device: JsValue,
+    queue: JsValue,
+    max_binding_size: f64,
+    pipelines: RefCell<HashMap<String, JsValue>>,
+}
+
+impl Gpu {
+    /// Ask the browser for a device, `None` without WebGPU or a suitable
+    /// adapter.
+    async fn request() -> Result<Option<Gpu>, JsValue> {
+        let navigator = get(&js_sys::global(), "navigator")?;
+        let gpu = if navigator.is_object() {
+            get(&navigator, "gpu")?
+        } else {
+            JsValue::undefined()
+        };
+        if !gpu.is_object() {
+            return Ok(None);
+        }
+        let adapter = call_async(&gpu, "requestAdapter", &[]).await?;
+        if !adapter.is_object() {
+            return Ok(None);
+        }
+        let device = call_async(&adapter, "requestDevice", &[]).await?;
+
+        // Request a new device on the next call if this one is lost
+        let forget = Closure::once_into_js(|| GPU.with(|cell| *cell.borrow_mut() = None));
+        call(&get(&device, "lost")?, "then", &[forget])?;
+
+        let limits = get(&device, "limits")?;
+        Ok(Some(Gpu {
+            queue: get(&device, "queue")?,
+            max_binding_size: get(&limits, "maxStorageBufferBindingSize")?
+                .as_f64()
+                .unwrap_or(0.0),
+            device,
+            pipelines: RefCell::new(HashMap::new()),
+        }))
+    }
+
+    /// The compute pipeline for `shader`, compiling it on first use.
+    fn pipeline(&self, shader: String) -> Result<JsValue, JsValue> {
+        if let Some(pipeline) = self.pipelines.borrow().get(&shader) {
+            return Ok(pipeline.clone());
+        }
+        let module = call(
+            &self.device,
+            "createShaderModule",
+            &[object(&[("code", shader.as_str().into())])],
+        )?;
+        let compute = object(&[("module", module), ("entryPoint", "main".into())]);
+        let pipeline = call(
+            &self.device,
+            "createComputePipeline",
+            &[object(&[("layout", "auto".into()), ("compute", compute)])],
+        )?;
+        self.pipelines.borrow_mut().insert(shader, pipeline.clone());
+        Ok(pipeline)
+    }
+
+    /// Dispatch `shader` over `input` and read back `output_len` floats.
+    async fn run(
+        &self,
+        shader: String,
+        input: &Float32Array,
+        output_len: usize,
+    ) -> Result<Float32Array, JsValue> {
+        let pipeline = self.pipeline(shader)?;
+        let output_bytes = (output_len * 4) as f64;
+        let input_buffer = self.buffer(input.byte_length() as f64, STORAGE | COPY_DST)?;
+        let output_buffer = self.buffer(output_bytes, STORAGE | COPY_SRC)?;
+        let readback = self.buffer(output_bytes, MAP_READ | COPY_DST)?;
+        let buffers = [&input_buffer, &output_buffer, &readback];
+
+        let result = async {
+            call(
+                &self.queue,
+                "writeBuffer",
+                &[input_buffer.clone(), 0.into(), input.clone().into()],
+            )?;
+
+            let binding = |index: u32, buffer: &JsValue| {
+                let resource = object(&[("buffer", buffer.clone())]);
+                object(&[("binding", index.into()), ("resource", resource)])
+            };
+            let layout = call(&pipeline, "getBindGroupLayout", &[0.into()])?;
+            let entries = Array::of2(&binding(0, &input_buffer), &binding(1, &output_buffer));
+            let bind_group = call(
+                &self.device,
+                "createBindGroup",
+                &[object(&[("layout", layout), ("entries", entries.into())])],
+            )?;
+
+            let encoder = call(&self.device, "createCommandEncoder", &[])?;
+            let pass = call(&encoder, "beginComputePass", &[])?;
+            call(&pass, "setPipeline", std::slice::from_ref(&pipeline))?;
+            call(&pass, "setBindGroup", &[0.into(), bind_group])?;
+            let (x, y) = wgsl::dispatch_size(input.length() as usize);
+            call(&pass, "dispatchWorkgroups", &[x.into(), y.into()])?;
+            call(&pass, "end", &[])?;
+            call(
+                &encoder,
+                "copyBufferToBuffer",
+                &[
+                    output_buffer.clone(),
+                    0.into(),
+                    readback.clone(),
+                    0.into(),
+                    output_bytes.into(),
+                ],
+            )?;
+            let commands = call(&encoder, "finish", &[])?;
+            call(&self.queue, "submit", &[Array::of1(&commands).into()])?;
+
+            call_async(&readback, "mapAsync", &[MAP_MODE_READ.into()]).await?;
+            let mapped = call(&readback, "getMappedRange", &[])?;
+            // Copied out before `unmap` detaches the mapped range
+            let output = Float32Array::new(&mapped).slice(0, output_len as u32);
+            call(&readback, "unmap", &[])?;
+            Ok(output)
+        }
+        .await;
+
+        for buffer in buffers {
+            let _ = call(buffer, "destroy", &[]);
+        }
+        result
+    }
+
+    fn buffer(&self, size: f64, usage: u32) -> Result<JsValue, JsValue> {
+        let descriptor = object(&[("size", size.into()), ("usage", usage.into())]);
+        call(&self.device, "createBuffer", &[descriptor])
+    }
+}
+
+/// Narrow a pipeline's `f64` plan step to the `f32` the shaders use.
+fn narrow(op: NumericOp<f64>) -> NumericOp<f32> {
+    match op {
+        NumericOp::Add(n) => NumericOp::Add(n as f32),
+        NumericOp::Mul(n) => NumericOp::Mul(n as f32),
+        NumericOp::ScaleOffset(a, b) => NumericOp::ScaleOffset(a as f32, b as f32),
+        NumericOp::Normalize(mean, std) => NumericOp::Normalize(mean as f32, std as f32),
+        NumericOp::Clamp(lo, hi) => NumericOp::Clamp(lo as f32, hi as f32),
+        NumericOp::Abs => NumericOp::Abs,
+        NumericOp::FilterGt(n) => NumericOp::FilterGt(n as f32),
+        NumericOp::FilterGte(n) => NumericOp::FilterGte(n as f32),
+        NumericOp::FilterLt(n) => NumericOp::FilterLt(n as f32),
+        NumericOp::FilterLte(n) => NumericOp::FilterLte(n as f32),
+        NumericOp::FilterRange(lo, hi) => NumericOp::FilterRange(lo as f32, hi as f32),
+        NumericOp::Take(n) => NumericOp::Take(n),
+        NumericOp::Drop(n) => NumericOp::Drop(n),
+        NumericOp::Unique => NumericOp::Unique,
+    }
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    Reflect::get(target, &JsValue::from_str(key))
+}
+
+/// Call the method `name` of `target`.
+fn call(target: &JsValue, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    let method: Function = get(target, name)?.dyn_into()?;
+    method.apply(target, &args.iter().collect())
+}
+
+/// Call a method returning a Promise and await it.
+async fn call_async(target: &JsValue, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    JsFuture::from(Promise::resolve(&call(target, name, args)?)).await
+}
+
+/// A descriptor object with the given properties.
+fn object(entries: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+    for (key, value) in entries {
+        let _ = Reflect::set(&object, &JsValue::from_str(key), value);
+    }
+    object.into()
+}
@@ -0,0 +1,102 @@
+//! # I/O: Streaming sources and sinks for native pipelines
+//!
+//! Adapters that read records from a file, socket or any [`std::io::Read`]
+//! lazily, and write pipeline output as it is produced. A pipeline reads one
+//! record, pushes it through the transducer and writes the results before the
+//! next record is read, so inputs larger than memory stream through in constant
+//! space, and early termination (for example with `Take`) stops reading.
+//!
+//! Each format lives in its own submodule behind a feature flag:
+//!
+//! - [`csv`] - CSV records (`csv` feature)
+//! - [`json_lines`] - Newline-delimited JSON through serde (`json` feature)
+//! - [`arrow`] - Apache Arrow columns and record batches (`arrow` feature)
+//! - [`parquet`] - Parquet files, read row group by row group (`parquet` feature)
+//! - [`polars`] - Polars Series (`polars` feature)
+//! - [`framed`] - Lines and length-prefixed frames over Tokio's `AsyncRead` /
+//!   `AsyncWrite` (`tokio` feature)
+//! - [`protobuf`] - Length-delimited Protocol Buffers messages through prost
+//!   (`protobuf` feature)
+//! - [`flatbuffers`] - Streams of size-prefixed FlatBuffers (`flatbuffers`
+//!   feature)
+//!
+//! This module is only available on native targets.
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "flatbuffers")]
+pub mod flatbuffers;
+#[cfg(feature = "tokio")]
+pub mod framed;
+#[cfg(feature = "json")]
+pub mod json_lines;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+
+#[cfg(any(
+    feature = "csv",
+    feature = "json",
+    feature = "protobuf",
+    feature = "flatbuffers"
+))]
+use crate::step::{cont, Step};
+#[cfg(any(
+    feature = "csv",
+    feature = "json",
+    feature = "protobuf",
+    feature = "flatbuffers"
+))]
+use crate::transducer::Transducer;
+
+/// Run a pipeline, handing each output to `sink` as soon as it is produced.
+///
+/// The outputs of one input are gathered into a small buffer and passed on
+/// before the next input is pulled, so nothing accumulates across inputs.
+/// Stops at the first sink error. Returns the number of outputs sunk.
+#[cfg(any(
+    feature = "csv",
+    feature = "json",
+    feature = "protobuf",
+    feature = "flatbuffers"
+))]
+pub(crate) fn drain<T, U, Iter, E, F>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    mut sink: F,
+) -> Result<usize, E>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    F: FnMut(U) -> Result<(), E>,
+{
+    let transformed = transducer.apply(|mut acc: Vec<U>, x: U| {
+        acc.push(x);
+        cont(acc)
+    });
+    let mut buffer = Vec::new();
+    let mut written = 0;
+
+    for item in source {
+        let (outputs, stopped) = match transformed(buffer, item) {
+            Step::Continue(outputs) => (outputs, false),
+            Step::Stop(outputs) => (outputs, true),
+        };
+        buffer = outputs;
+        for output in buffer.drain(..) {
+            sink(output)?;
+            written += 1;
+        }
+        if stopped {
+            break;
+        }
+    }
+
+    Ok(written)
+}
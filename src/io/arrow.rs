@@ -0,0 +1,642 @@
+//! Apache Arrow interop: columns as sources, pipeline output as arrays.
+//!
+//! [`column`] reads one column of a [`RecordBatch`] as a lazy source. The
+//! iterator holds a reference-counted handle to the column's buffers and reads
+//! values in place, so no data is copied for numeric and boolean columns
+//! (string values are copied into owned `String`s). [`to_array`] and
+//! [`to_record_batch`] run a pipeline straight into Arrow builders, without an
+//! intermediate `Vec`, so Orlando can sit between Arrow-based tools such as
+//! DataFusion or Polars:
+//!
+//! ```rust
+//! use orlando_transducers::io::arrow::{column, to_record_batch};
+//! use orlando_transducers::{Filter, Map, Transducer};
+//! use arrow_array::{Float64Array, RecordBatch, StringArray};
+//! use std::sync::Arc;
+//!
+//! let batch = RecordBatch::try_from_iter([
+//!     ("city", Arc::new(StringArray::from(vec!["Oslo", "Lima", "Pune"])) as _),
+//!     ("temp", Arc::new(Float64Array::from(vec![4.5, 19.0, 31.5])) as _),
+//! ])
+//! .unwrap();
+//!
+//! let cities = column::<String>(&batch, "city").unwrap();
+//! let temps = column::<f64>(&batch, "temp").unwrap();
+//!
+//! let warm = Filter::new(|(_, t): &(String, f64)| *t > 10.0)
+//!     .compose(Map::new(|(city, t): (String, f64)| (city, t * 1.8 + 32.0)));
+//! let out = to_record_batch(&warm, cities.zip(temps), &["city", "temp_f"]).unwrap();
+//!
+//! assert_eq!(out.num_rows(), 2);
+//! assert_eq!(out.schema().field(1).name(), "temp_f");
+//! ```
+//!
+//! The supported value types are listed on [`ArrowValue`]; `Option<T>` reads
+//! and writes nullable columns.
+//!
+//! Requires the `arrow` feature.
+
+use crate::step::cont;
+use crate::transducer::Transducer;
+use arrow_array::builder::{ArrayBuilder, BooleanBuilder, PrimitiveBuilder, StringBuilder};
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, BooleanArray, PrimitiveArray, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// A Rust type that maps to an Arrow column type.
+///
+/// Implemented for the primitive integer and float types, `bool` and
+/// `String`, and for `Option<T>` of any of them, which maps to a nullable
+/// column.
+pub trait ArrowValue: Sized + 'static {
+    /// The Arrow array type values are read from
+    type Array: Array + Clone + 'static;
+    /// The builder values are collected into
+    type Builder: ArrayBuilder;
+
+    /// The Arrow data type of a column of this type.
+    fn data_type() -> DataType;
+
+    /// Whether the column may contain nulls.
+    fn nullable() -> bool {
+        false
+    }
+
+    /// Read the value at `index`, which is known to be in bounds (and not
+    /// null, unless this type is nullable).
+    fn read(array: &Self::Array, index: usize) -> Self;
+
+    /// Create an empty builder with room for `capacity` values.
+    fn builder(capacity: usize) -> Self::Builder;
+
+    /// Append this value to a builder.
+    fn append(self, builder: &mut Self::Builder);
+
+    /// Append a null to a builder.
+    fn append_null(builder: &mut Self::Builder);
+}
+
+macro_rules! primitive_value {
+    ($($t:ty => $arrow:ty, $data_type:expr;)*) => {
+        $(
+            impl ArrowValue for $t {
+                type Array = PrimitiveArray<$arrow>;
+                type Builder = PrimitiveBuilder<$arrow>;
+
+                fn data_type() -> DataType {
+                    $data_type
+                }
+
+                fn read(array: &Self::Array, index: usize) -> Self {
+                    array.value(index)
+                }
+
+                fn builder(capacity: usize) -> Self::Builder {
+                    PrimitiveBuilder::with_capacity(capacity)
+                }
+
+                fn append(self, builder: &mut Self::Builder) {
+                    builder.append_value(self);
+                }
+
+                fn append_null(builder: &mut Self::Builder) {
+                    builder.append_null();
+                }
+            }
+        )*
+    };
+}
+
+primitive_value! {
+    i8 => Int8Type, DataType::Int8;
+    i16 => Int16Type, DataType::Int16;
+    i32 => Int32Type, DataType::Int32;
+    i64 => Int64Type, DataType::Int64;
+    u8 => UInt8Type, DataType::UInt8;
+    u16 => UInt16Type, DataType::UInt16;
+    u32 => UInt32Type, DataType::UInt32;
+    u64 => UInt64Type, DataType::UInt64;
+    f32 => Float32Type, DataType::Float32;
+    f64 => Float64Type, DataType::Float64;
+}
+
+impl ArrowValue for bool {
+    type Array = BooleanArray;
+    type Builder = BooleanBuilder;
+
+    fn data_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn read(array: &Self::Array, index: usize) -> Self {
+        array.value(index)
+    }
+
+    fn builder(capacity: usize) -> Self::Builder {
+        BooleanBuilder::with_capacity(capacity)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        builder.append_value(self);
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        builder.append_null();
+    }
+}
+
+impl ArrowValue for String {
+    type Array = StringArray;
+    type Builder = StringBuilder;
+
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn read(array: &Self::Array, index: usize) -> Self {
+        array.value(index).to_string()
+    }
+
+    fn builder(capacity: usize) -> Self::Builder {
+        StringBuilder::with_capacity(capacity, capacity * 8)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        builder.append_value(self);
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        builder.append_null();
+    }
+}
+
+impl<T: ArrowValue> ArrowValue for Option<T> {
+    type Array = T::Array;
+    type Builder = T::Builder;
+
+    fn data_type() -> DataType {
+        T::data_type()
+    }
+
+    fn nullable() -> bool {
+        true
+    }
+
+    fn read(array: &Self::Array, index: usize) -> Self {
+        if array.is_null(index) {
+            None
+        } else {
+            Some(T::read(array, index))
+        }
+    }
+
+    fn builder(capacity: usize) -> Self::Builder {
+        T::builder(capacity)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        match self {
+            Some(value) => value.append(builder),
+            None => T::append_null(builder),
+        }
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        T::append_null(builder);
+    }
+}
+
+/// Read the column `name` of a batch as a lazy source of `T`.
+///
+/// Fails if there is no such column, if its Arrow type doesn't match `T`, or
+/// if it contains nulls and `T` is not an `Option`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::column;
+/// use orlando_transducers::{sum, Map};
+/// use arrow_array::{Int32Array, RecordBatch};
+/// use std::sync::Arc;
+///
+/// let batch = RecordBatch::try_from_iter([(
+///     "qty",
+///     Arc::new(Int32Array::from(vec![Some(3), None, Some(4)])) as _,
+/// )])
+/// .unwrap();
+///
+/// assert!(column::<i32>(&batch, "qty").is_err()); // has nulls
+/// let qty = column::<Option<i32>>(&batch, "qty").unwrap();
+/// assert_eq!(sum(&Map::new(|q: Option<i32>| q.unwrap_or(0)), qty), 7);
+/// ```
+pub fn column<T: ArrowValue>(batch: &RecordBatch, name: &str) -> Result<Column<T>, ArrowError> {
+    let array = batch.column_by_name(name).ok_or_else(|| {
+        ArrowError::SchemaError(format!("no column named '{}' in record batch", name))
+    })?;
+    column_of(array.as_ref(), name)
+}
+
+/// Read a single array as a lazy source of `T`, like [`column`].
+pub fn array<T: ArrowValue>(array: &dyn Array) -> Result<Column<T>, ArrowError> {
+    column_of(array, "array")
+}
+
+fn column_of<T: ArrowValue>(array: &dyn Array, name: &str) -> Result<Column<T>, ArrowError> {
+    let typed = array
+        .as_any()
+        .downcast_ref::<T::Array>()
+        .filter(|_| array.data_type() == &T::data_type())
+        .ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "'{}' has type {}, expected {}",
+                name,
+                array.data_type(),
+                T::data_type()
+            ))
+        })?;
+    if !T::nullable() && typed.null_count() > 0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "'{}' contains {} nulls; read it as an Option",
+            name,
+            typed.null_count()
+        )));
+    }
+
+    Ok(Column {
+        len: typed.len(),
+        array: typed.clone(),
+        index: 0,
+        _phantom: PhantomData,
+    })
+}
+
+/// Iterator returned by [`column`] and [`array`].
+///
+/// Cloning the underlying array only bumps reference counts, so the iterator
+/// owns its data without copying it and can outlive the batch.
+#[derive(Clone)]
+pub struct Column<T: ArrowValue> {
+    array: T::Array,
+    index: usize,
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: ArrowValue> Iterator for Column<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == self.len {
+            return None;
+        }
+        let value = T::read(&self.array, self.index);
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: ArrowValue> ExactSizeIterator for Column<T> {}
+
+/// Run a pipeline and collect its output into an Arrow array.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::to_array;
+/// use orlando_transducers::{Filter, Map, Transducer};
+/// use arrow_array::{Array, Float64Array};
+///
+/// let pipeline = Filter::new(|x: &i32| x % 2 == 0).compose(Map::new(|x: i32| x as f64 / 2.0));
+/// let array = to_array(&pipeline, 1..=6);
+/// let values = array.as_any().downcast_ref::<Float64Array>().unwrap();
+/// assert_eq!(values.values(), &[1.0, 2.0, 3.0]);
+/// ```
+pub fn to_array<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter) -> ArrayRef
+where
+    T: 'static,
+    U: ArrowValue,
+    Iter: IntoIterator<Item = T>,
+{
+    let source = source.into_iter();
+    let capacity = transducer.size_hint(source.size_hint()).0;
+    let mut builder = crate::collectors::reduce(
+        transducer,
+        source,
+        U::builder(capacity),
+        |mut builder, x: U| {
+            x.append(&mut builder);
+            cont(builder)
+        },
+    );
+    builder.finish()
+}
+
+/// A row of [`ArrowValue`]s: tuples of one to eight columns.
+pub trait ArrowRow: Sized + 'static {
+    /// One builder per column
+    type Builders;
+    /// One typed array per column
+    type Arrays: Clone;
+
+    /// The number of columns.
+    const WIDTH: usize;
+
+    /// The schema fields for the columns, given their names.
+    fn fields(names: &[&str]) -> Vec<Field>;
+
+    /// Create empty builders with room for `capacity` rows.
+    fn builders(capacity: usize) -> Self::Builders;
+
+    /// Append this row to the builders.
+    fn append(self, builders: &mut Self::Builders);
+
+    /// Finish each builder into a column.
+    fn finish(builders: Self::Builders) -> Vec<ArrayRef>;
+
+    /// Look up the named columns of a batch, checked as by [`column`].
+    fn arrays(batch: &RecordBatch, names: &[&str]) -> Result<Self::Arrays, ArrowError>;
+
+    /// Read the row at `index`, which is known to be in bounds.
+    fn read_row(arrays: &Self::Arrays, index: usize) -> Self;
+}
+
+macro_rules! tuple_row {
+    ($width:expr; $($t:ident $i:tt),+) => {
+        impl<$($t: ArrowValue),+> ArrowRow for ($($t,)+) {
+            type Builders = ($($t::Builder,)+);
+            type Arrays = ($($t::Array,)+);
+
+            const WIDTH: usize = $width;
+
+            fn fields(names: &[&str]) -> Vec<Field> {
+                vec![$(Field::new(names[$i], $t::data_type(), $t::nullable())),+]
+            }
+
+            fn builders(capacity: usize) -> Self::Builders {
+                ($($t::builder(capacity),)+)
+            }
+
+            fn append(self, builders: &mut Self::Builders) {
+                $(self.$i.append(&mut builders.$i);)+
+            }
+
+            fn finish(mut builders: Self::Builders) -> Vec<ArrayRef> {
+                vec![$(builders.$i.finish()),+]
+            }
+
+            fn arrays(batch: &RecordBatch, names: &[&str]) -> Result<Self::Arrays, ArrowError> {
+                Ok(($(column::<$t>(batch, names[$i])?.array,)+))
+            }
+
+            fn read_row(arrays: &Self::Arrays, index: usize) -> Self {
+                ($($t::read(&arrays.$i, index),)+)
+            }
+        }
+    };
+}
+
+tuple_row!(1; A 0);
+tuple_row!(2; A 0, B 1);
+tuple_row!(3; A 0, B 1, C 2);
+tuple_row!(4; A 0, B 1, C 2, D 3);
+tuple_row!(5; A 0, B 1, C 2, D 3, E 4);
+tuple_row!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_row!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_row!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+pub(crate) fn check_width<T: ArrowRow>(names: &[&str]) -> Result<(), ArrowError> {
+    if names.len() == T::WIDTH {
+        Ok(())
+    } else {
+        Err(ArrowError::InvalidArgumentError(format!(
+            "{} column names given for rows of {} values",
+            names.len(),
+            T::WIDTH
+        )))
+    }
+}
+
+/// Read the named columns of a batch as a lazy source of tuples.
+///
+/// Each column is checked as by [`column`]; `names` must have one entry per
+/// tuple field.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::rows;
+/// use orlando_transducers::{to_vec, Filter};
+/// use arrow_array::{BooleanArray, RecordBatch, StringArray};
+/// use std::sync::Arc;
+///
+/// let batch = RecordBatch::try_from_iter([
+///     ("user", Arc::new(StringArray::from(vec!["ada", "bob"])) as _),
+///     ("admin", Arc::new(BooleanArray::from(vec![true, false])) as _),
+/// ])
+/// .unwrap();
+///
+/// let admins = Filter::new(|(_, admin): &(String, bool)| *admin);
+/// let found = to_vec(&admins, rows::<(String, bool)>(&batch, &["user", "admin"]).unwrap());
+/// assert_eq!(found, vec![("ada".to_string(), true)]);
+/// ```
+pub fn rows<T: ArrowRow>(batch: &RecordBatch, names: &[&str]) -> Result<Rows<T>, ArrowError> {
+    check_width::<T>(names)?;
+    Ok(Rows {
+        arrays: T::arrays(batch, names)?,
+        index: 0,
+        len: batch.num_rows(),
+    })
+}
+
+/// Iterator returned by [`rows`].
+#[derive(Clone)]
+pub struct Rows<T: ArrowRow> {
+    arrays: T::Arrays,
+    index: usize,
+    len: usize,
+}
+
+impl<T: ArrowRow> Iterator for Rows<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == self.len {
+            return None;
+        }
+        let row = T::read_row(&self.arrays, self.index);
+        self.index += 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: ArrowRow> ExactSizeIterator for Rows<T> {}
+
+/// Run a pipeline whose outputs are tuples and collect them into a
+/// [`RecordBatch`], one column per tuple position.
+///
+/// `names` gives the column names and must have one entry per tuple field.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::to_record_batch;
+/// use orlando_transducers::Map;
+///
+/// let squares = Map::new(|x: i64| (x, x * x, x % 2 == 0));
+/// let batch = to_record_batch(&squares, 1..=4, &["n", "square", "even"]).unwrap();
+/// assert_eq!((batch.num_rows(), batch.num_columns()), (4, 3));
+/// ```
+pub fn to_record_batch<T, U, Iter>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    names: &[&str],
+) -> Result<RecordBatch, ArrowError>
+where
+    T: 'static,
+    U: ArrowRow,
+    Iter: IntoIterator<Item = T>,
+{
+    check_width::<U>(names)?;
+
+    let source = source.into_iter();
+    let capacity = transducer.size_hint(source.size_hint()).0;
+    let builders = crate::collectors::reduce(
+        transducer,
+        source,
+        U::builders(capacity),
+        |mut builders, row: U| {
+            row.append(&mut builders);
+            cont(builders)
+        },
+    );
+    let schema = Schema::new(U::fields(names));
+    RecordBatch::try_new(Arc::new(schema), U::finish(builders))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transducer::Identity;
+    use crate::transforms::{Map, Take};
+    use arrow_array::{Float64Array, Int64Array};
+
+    fn batch() -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+            (
+                "score",
+                Arc::new(Float64Array::from(vec![Some(0.5), None, Some(2.0), None])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d"])) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_column_reads_values() {
+        let batch = batch();
+        assert_eq!(
+            to_vec(&Identity::new(), column::<i64>(&batch, "id").unwrap()),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            to_vec(&Identity::new(), column::<String>(&batch, "name").unwrap()),
+            vec!["a", "b", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn test_column_nullable() {
+        let scores: Vec<_> = column::<Option<f64>>(&batch(), "score").unwrap().collect();
+        assert_eq!(scores, vec![Some(0.5), None, Some(2.0), None]);
+    }
+
+    #[test]
+    fn test_column_errors() {
+        let batch = batch();
+        assert!(matches!(
+            column::<i64>(&batch, "missing"),
+            Err(ArrowError::SchemaError(_))
+        ));
+        // Wrong type, including a same-width primitive of a different kind
+        assert!(column::<i32>(&batch, "id").is_err());
+        assert!(column::<u64>(&batch, "id").is_err());
+        // Nulls need an Option
+        assert!(column::<f64>(&batch, "score").is_err());
+    }
+
+    #[test]
+    fn test_column_outlives_batch_and_takes_early() {
+        let ids = column::<i64>(&batch(), "id").unwrap();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(to_vec(&Take::new(2), ids), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_to_array_nullable() {
+        let array = to_array(
+            &Map::new(|x: i32| if x % 2 == 0 { Some(x) } else { None }),
+            0..4,
+        );
+        let back: Vec<_> = super::array::<Option<i32>>(array.as_ref())
+            .unwrap()
+            .collect();
+        assert_eq!(back, vec![Some(0), None, Some(2), None]);
+    }
+
+    #[test]
+    fn test_to_record_batch_round_trip() {
+        let input = batch();
+        let source = rows::<(i64, Option<f64>)>(&input, &["id", "score"]).unwrap();
+        let pipeline =
+            Map::new(|(id, score): (i64, Option<f64>)| (id.to_string(), score.map(|s| s * 10.0)));
+
+        let out = to_record_batch(&pipeline, source, &["key", "scaled"]).unwrap();
+        let schema = out.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        assert!(!schema.field(0).is_nullable());
+        assert!(schema.field(1).is_nullable());
+        assert_eq!(
+            column::<Option<f64>>(&out, "scaled")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![Some(5.0), None, Some(20.0), None]
+        );
+    }
+
+    #[test]
+    fn test_rows_errors() {
+        let batch = batch();
+        assert!(rows::<(i64, String)>(&batch, &["id"]).is_err());
+        assert!(rows::<(i64, f64)>(&batch, &["id", "score"]).is_err());
+        assert!(rows::<(i64, String)>(&batch, &["id", "missing"]).is_err());
+    }
+
+    #[test]
+    fn test_to_record_batch_checks_names() {
+        let result = to_record_batch(&Map::new(|x: i32| (x, x)), 0..3, &["only_one"]);
+        assert!(result.is_err());
+    }
+}
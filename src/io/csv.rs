@@ -0,0 +1,254 @@
+//! CSV record sources and sinks.
+//!
+//! [`records`] and [`deserialize`] read rows lazily from any [`Read`], and
+//! [`to_csv`] runs a pipeline and writes each output row as soon as it is
+//! produced. Nothing is buffered beyond the current row, so files larger than
+//! memory can be filtered and reshaped in one pass:
+//!
+//! ```rust
+//! use orlando_transducers::io::csv::{records, to_csv};
+//! use orlando_transducers::{Filter, Map, Transducer};
+//!
+//! let input = "city,population\nOslo,709000\nBergen,291000\nTromsø,78000\n";
+//!
+//! let pipeline = Map::new(|row: csv::Result<csv::StringRecord>| row.unwrap())
+//!     .compose(Filter::new(|row: &csv::StringRecord| {
+//!         row[1].parse::<u32>().unwrap() > 100_000
+//!     }))
+//!     .compose(Map::new(|row: csv::StringRecord| (row[0].to_uppercase(), row[1].to_string())));
+//!
+//! let mut out = Vec::new();
+//! let written = to_csv(&pipeline, records(input.as_bytes()), &mut out).unwrap();
+//!
+//! assert_eq!(written, 2);
+//! assert_eq!(String::from_utf8(out).unwrap(), "OSLO,709000\nBERGEN,291000\n");
+//! ```
+//!
+//! Read errors are yielded as `Err` elements rather than ending the stream, so
+//! a pipeline decides whether to skip, collect or stop on malformed rows (see
+//! [`Validate`](crate::transforms::Validate) and
+//! [`partition_map`](crate::collectors::partition_map)).
+//!
+//! Requires the `csv` feature.
+
+use crate::transducer::Transducer;
+use ::csv::{DeserializeRecordsIntoIter, Reader, StringRecordsIntoIter, Writer};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Read, Write};
+
+/// Lazily read the rows of a CSV document as [`csv::StringRecord`]s.
+///
+/// The first row is treated as a header and not yielded. To read headerless
+/// input or change the delimiter, configure a [`csv::ReaderBuilder`] and call
+/// `into_records()` on the reader it builds.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::csv::records;
+/// use orlando_transducers::{first, Map};
+///
+/// let input = "id,name\n1,Ada\n2,Grace\n";
+/// let names = Map::new(|row: csv::Result<csv::StringRecord>| row.unwrap()[1].to_string());
+/// assert_eq!(first(&names, records(input.as_bytes())), Some("Ada".to_string()));
+/// ```
+pub fn records<R: Read>(reader: R) -> StringRecordsIntoIter<R> {
+    Reader::from_reader(reader).into_records()
+}
+
+/// Lazily read the rows of a CSV document as values of type `T`.
+///
+/// Columns are matched to struct fields by header name, as in
+/// [`csv::Reader::deserialize`]. Rows that fail to parse are yielded as `Err`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::csv::deserialize;
+/// use orlando_transducers::{sum, Map};
+///
+/// let input = "item,qty\napples,3\npears,4\n";
+/// let qty = Map::new(|row: csv::Result<(String, u32)>| row.unwrap().1);
+/// assert_eq!(sum(&qty, deserialize::<(String, u32), _>(input.as_bytes())), 7);
+/// ```
+pub fn deserialize<T, R>(reader: R) -> DeserializeRecordsIntoIter<R, T>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    Reader::from_reader(reader).into_deserialize()
+}
+
+/// Run a pipeline and write each output as a CSV row to `writer`.
+///
+/// Outputs are written with [`csv::Writer::serialize`]: tuples and sequences
+/// become plain rows, and structs get a header row from their field names.
+/// Each row is written as soon as the pipeline emits it. Returns the number of
+/// rows written (not counting a header), or the first write error.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::csv::to_csv;
+/// use orlando_transducers::{Map, Take, Transducer};
+///
+/// let squares = Map::new(|x: u32| (x, x * x)).compose(Take::new(3));
+/// let mut out = Vec::new();
+/// assert_eq!(to_csv(&squares, 1.., &mut out).unwrap(), 3);
+/// assert_eq!(String::from_utf8(out).unwrap(), "1,1\n2,4\n3,9\n");
+/// ```
+pub fn to_csv<T, U, Iter, W>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    writer: W,
+) -> ::csv::Result<usize>
+where
+    T: 'static,
+    U: Serialize + 'static,
+    Iter: IntoIterator<Item = T>,
+    W: Write,
+{
+    let mut writer = Writer::from_writer(writer);
+    let written = to_csv_writer(transducer, source, &mut writer)?;
+    writer.flush()?;
+    Ok(written)
+}
+
+/// Like [`to_csv`], but writing through a configured [`csv::Writer`].
+///
+/// Use this to choose the delimiter, quoting or line terminator with a
+/// [`csv::WriterBuilder`]. The writer is not flushed.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::csv::to_csv_writer;
+/// use orlando_transducers::Map;
+///
+/// let mut writer = csv::WriterBuilder::new().delimiter(b'\t').from_writer(Vec::new());
+/// to_csv_writer(&Map::new(|x: i32| (x, -x)), vec![1, 2], &mut writer).unwrap();
+/// assert_eq!(writer.into_inner().unwrap(), b"1\t-1\n2\t-2\n");
+/// ```
+pub fn to_csv_writer<T, U, Iter, W>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    writer: &mut Writer<W>,
+) -> ::csv::Result<usize>
+where
+    T: 'static,
+    U: Serialize + 'static,
+    Iter: IntoIterator<Item = T>,
+    W: Write,
+{
+    super::drain(transducer, source, |row| writer.serialize(row))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Filter, FlatMap, Map, Take};
+    use ::csv::StringRecord;
+    use serde::Deserialize;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Reading {
+        sensor: String,
+        value: f64,
+    }
+
+    #[test]
+    fn test_records_skip_header() {
+        let rows = to_vec(
+            &Map::new(|row: ::csv::Result<StringRecord>| row.unwrap()[0].to_string()),
+            records("a,b\n1,2\n3,4\n".as_bytes()),
+        );
+        assert_eq!(rows, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn test_deserialize_typed_rows() {
+        let input = "sensor,value\nt1,20.5\nt2,bad\nt3,19.0\n";
+        let ok = FlatMap::new(|row: ::csv::Result<Reading>| row.into_iter().collect::<Vec<_>>());
+        let readings = to_vec(&ok, deserialize::<Reading, _>(input.as_bytes()));
+
+        assert_eq!(
+            readings,
+            vec![
+                Reading {
+                    sensor: "t1".into(),
+                    value: 20.5
+                },
+                Reading {
+                    sensor: "t3".into(),
+                    value: 19.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_csv_writes_struct_header() {
+        let pipeline = Filter::new(|r: &Reading| r.value > 0.0);
+        let source = vec![
+            Reading {
+                sensor: "a".into(),
+                value: 1.5,
+            },
+            Reading {
+                sensor: "b".into(),
+                value: -1.0,
+            },
+        ];
+
+        let mut out = Vec::new();
+        assert_eq!(to_csv(&pipeline, source, &mut out).unwrap(), 1);
+        assert_eq!(String::from_utf8(out).unwrap(), "sensor,value\na,1.5\n");
+    }
+
+    #[test]
+    fn test_to_csv_round_trip() {
+        let input = "sensor,value\nt1,1\nt2,2\nt3,3\n";
+        let double = Map::new(|row: ::csv::Result<Reading>| {
+            let r = row.unwrap();
+            Reading {
+                value: r.value * 2.0,
+                ..r
+            }
+        });
+
+        let mut out = Vec::new();
+        to_csv(
+            &double,
+            deserialize::<Reading, _>(input.as_bytes()),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "sensor,value\nt1,2.0\nt2,4.0\nt3,6.0\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_stops_reading_after_take() {
+        let pulled = Rc::new(Cell::new(0));
+        let pulled_clone = Rc::clone(&pulled);
+        let source = (0..1_000_000).inspect(move |_| pulled_clone.set(pulled_clone.get() + 1));
+
+        let mut out = Vec::new();
+        assert_eq!(to_csv(&Take::new(2), source, &mut out).unwrap(), 2);
+        assert_eq!(pulled.get(), 2);
+    }
+
+    #[test]
+    fn test_to_csv_flat_map_writes_every_output() {
+        let pipeline = FlatMap::new(|x: i32| vec![(x, "a"), (x, "b")]);
+        let mut out = Vec::new();
+        assert_eq!(to_csv(&pipeline, vec![1, 2], &mut out).unwrap(), 4);
+        assert_eq!(String::from_utf8(out).unwrap(), "1,a\n1,b\n2,a\n2,b\n");
+    }
+}
@@ -0,0 +1,353 @@
+//! Streams of size-prefixed FlatBuffers.
+//!
+//! [`read`] splits a byte stream into size-prefixed flatbuffers (each buffer
+//! preceded by its length as a little-endian `u32`, as written by
+//! `FlatBufferBuilder::finish_size_prefixed`) and hands each one to a decode
+//! function, usually a call to `flatbuffers::size_prefixed_root` with the
+//! generated table type followed by a copy of the fields the pipeline needs.
+//! Buffers are read one at a time as the pipeline pulls them, so a pipeline
+//! that stops early leaves the rest of the stream unread. [`write_flatbuffers`]
+//! writes pipeline output in the same framing.
+//!
+//! ```rust
+//! use orlando_transducers::io::flatbuffers::{read, write_flatbuffers};
+//! use orlando_transducers::{FlatMap, Filter, Transducer};
+//!
+//! let mut log = Vec::new();
+//! let lines = ["GET /", "ERROR timeout", "GET /about", "ERROR refused"];
+//! write_flatbuffers(&FlatMap::new(|s: &str| vec![s]), lines, &mut log, |builder, line| {
+//!     builder.create_string(line)
+//! })
+//! .unwrap();
+//!
+//! // With generated code, decode with the table type and copy out its fields
+//! let messages = read(&log[..], |buf| {
+//!     flatbuffers::size_prefixed_root::<&str>(buf).map(str::to_owned)
+//! });
+//! let errors = FlatMap::new(|line: Result<String, _>| line.into_iter().collect::<Vec<_>>())
+//!     .compose(Filter::new(|line: &String| line.starts_with("ERROR")));
+//! let errors = orlando_transducers::to_vec(&errors, messages);
+//! assert_eq!(errors, vec!["ERROR timeout", "ERROR refused"]);
+//! ```
+//!
+//! A buffer that fails verification is yielded as an [`Error::Invalid`] with
+//! its position in the stream, and reading continues with the next buffer. A
+//! read error or a stream that ends partway through a buffer ends the source.
+//!
+//! Requires the `flatbuffers` feature.
+
+use crate::transducer::Transducer;
+use ::flatbuffers::{FlatBufferBuilder, InvalidFlatbuffer, WIPOffset};
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+/// Bytes in the length prefix of a size-prefixed flatbuffer
+const PREFIX: usize = 4;
+
+/// An error reading a stream of size-prefixed flatbuffers.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying reader failed; the source ends after this error
+    Io(io::Error),
+    /// The stream ended partway through the buffer with this (0-based)
+    /// index; the source ends after this error
+    Truncated { index: usize },
+    /// The buffer with this (0-based) index failed verification
+    Invalid {
+        index: usize,
+        source: InvalidFlatbuffer,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "read error: {}", e),
+            Error::Truncated { index } => write!(f, "buffer {}: truncated", index),
+            Error::Invalid { index, source } => write!(f, "buffer {}: {}", index, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Truncated { .. } => None,
+            Error::Invalid { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Lazily split `reader` into size-prefixed flatbuffers and decode each with
+/// `decode`.
+///
+/// `decode` receives the whole buffer, length prefix included, so it can be
+/// passed straight to `flatbuffers::size_prefixed_root`. Flatbuffer tables
+/// borrow the buffer, which is reused for the next element, so `decode` must
+/// return an owned value.
+///
+/// Wrap files and sockets in a [`std::io::BufReader`]; byte slices can be
+/// passed directly.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::flatbuffers::{read, write_flatbuffers};
+/// use orlando_transducers::{to_vec, Map, Take, Transducer};
+///
+/// let mut stream = Vec::new();
+/// let numbers = Map::new(|n: u32| n.to_string());
+/// write_flatbuffers(&numbers, 1..=100, &mut stream, |builder, n| builder.create_string(&n))
+///     .unwrap();
+///
+/// let values = read(&stream[..], |buf| {
+///     flatbuffers::size_prefixed_root::<&str>(buf).map(str::to_owned)
+/// });
+/// let first = Map::new(|n: Result<String, _>| n.unwrap()).compose(Take::new(3));
+/// assert_eq!(to_vec(&first, values), vec!["1", "2", "3"]);
+/// ```
+pub fn read<U, R, F>(reader: R, decode: F) -> Buffers<R, F>
+where
+    R: BufRead,
+    F: FnMut(&[u8]) -> Result<U, InvalidFlatbuffer>,
+{
+    Buffers {
+        reader: Some(reader),
+        decode,
+        buffer: Vec::new(),
+        index: 0,
+    }
+}
+
+/// Iterator returned by [`read`].
+pub struct Buffers<R, F> {
+    /// The buffer source, `None` once it is exhausted or has failed
+    reader: Option<R>,
+    decode: F,
+    buffer: Vec<u8>,
+    index: usize,
+}
+
+impl<U, R, F> Iterator for Buffers<R, F>
+where
+    R: BufRead,
+    F: FnMut(&[u8]) -> Result<U, InvalidFlatbuffer>,
+{
+    type Item = Result<U, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = self.reader.as_mut()?;
+        let index = self.index;
+        self.index += 1;
+
+        // The prefix is kept in the buffer so offsets and alignment match
+        // what the writer produced
+        self.buffer.clear();
+        let read = append(reader, &mut self.buffer, PREFIX as u64).and_then(|complete| {
+            if !complete {
+                return Ok(false);
+            }
+            let mut length = [0; PREFIX];
+            length.copy_from_slice(&self.buffer);
+            append(reader, &mut self.buffer, u32::from_le_bytes(length).into())
+        });
+
+        match read {
+            Ok(true) => {
+                Some((self.decode)(&self.buffer).map_err(|source| Error::Invalid { index, source }))
+            }
+            Ok(false) if self.buffer.is_empty() => {
+                self.reader = None;
+                None
+            }
+            Ok(false) => {
+                self.reader = None;
+                Some(Err(Error::Truncated { index }))
+            }
+            Err(e) => {
+                self.reader = None;
+                Some(Err(Error::Io(e)))
+            }
+        }
+    }
+}
+
+/// Append `length` bytes from `reader` to `buffer`, returning `false` if the
+/// stream ends first.
+///
+/// Reads through `take` so a corrupt length can't allocate more than the
+/// stream actually holds.
+fn append(reader: &mut impl BufRead, buffer: &mut Vec<u8>, length: u64) -> io::Result<bool> {
+    let read = reader.take(length).read_to_end(buffer)?;
+    Ok(read as u64 == length)
+}
+
+/// Run a pipeline and write each output to `writer` as a size-prefixed
+/// flatbuffer, as soon as it is produced.
+///
+/// `encode` builds one output into the builder and returns the offset of its
+/// root, usually a table built with the generated `create` function; the
+/// builder is reset between outputs. Stops at the first write error. Returns
+/// the number of buffers written.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::flatbuffers::write_flatbuffers;
+/// use orlando_transducers::Filter;
+///
+/// let mut out = Vec::new();
+/// let names = Filter::new(|name: &&str| !name.is_empty());
+/// let written = write_flatbuffers(&names, ["a", "", "b"], &mut out, |builder, name| {
+///     builder.create_string(name)
+/// })
+/// .unwrap();
+/// assert_eq!(written, 2);
+/// ```
+pub fn write_flatbuffers<T, U, Iter, W, F, Root>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    mut writer: W,
+    mut encode: F,
+) -> io::Result<usize>
+where
+    T: 'static,
+    U: 'static,
+    Iter: IntoIterator<Item = T>,
+    W: Write,
+    F: FnMut(&mut FlatBufferBuilder<'static>, U) -> WIPOffset<Root>,
+{
+    let mut builder = FlatBufferBuilder::new();
+    let written = super::drain(transducer, source, |output: U| {
+        builder.reset();
+        let root = encode(&mut builder, output);
+        builder.finish_size_prefixed(root, None);
+        writer.write_all(builder.finished_data())
+    })?;
+    writer.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Map, Take};
+    use ::flatbuffers::{
+        size_prefixed_root, Follow, ForwardsUOffset, Table, VOffsetT, Verifiable, Verifier,
+    };
+
+    /// `table Reading { sensor: string; value: double; }`, written the way
+    /// flatc generates it
+    struct Reading<'a> {
+        table: Table<'a>,
+    }
+
+    const VT_SENSOR: VOffsetT = 4;
+    const VT_VALUE: VOffsetT = 6;
+
+    impl<'a> Follow<'a> for Reading<'a> {
+        type Inner = Reading<'a>;
+
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Reading {
+                table: Table::new(buf, loc),
+            }
+        }
+    }
+
+    impl Verifiable for Reading<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<&str>>("sensor", VT_SENSOR, false)?
+                .visit_field::<f64>("value", VT_VALUE, false)?
+                .finish();
+            Ok(())
+        }
+    }
+
+    impl<'a> Reading<'a> {
+        fn sensor(&self) -> Option<&'a str> {
+            // Safety: the field was verified as a string
+            unsafe { self.table.get::<ForwardsUOffset<&str>>(VT_SENSOR, None) }
+        }
+
+        fn value(&self) -> f64 {
+            // Safety: the field was verified as a double
+            unsafe { self.table.get::<f64>(VT_VALUE, Some(0.0)).unwrap() }
+        }
+    }
+
+    fn create<'a>(
+        builder: &mut FlatBufferBuilder<'a>,
+        (sensor, value): (&str, f64),
+    ) -> WIPOffset<Reading<'a>> {
+        let sensor = builder.create_string(sensor);
+        let start = builder.start_table();
+        builder.push_slot(VT_VALUE, value, 0.0);
+        builder.push_slot_always(VT_SENSOR, sensor);
+        WIPOffset::new(builder.end_table(start).value())
+    }
+
+    fn decode(buf: &[u8]) -> Result<(String, f64), InvalidFlatbuffer> {
+        let reading = size_prefixed_root::<Reading>(buf)?;
+        Ok((reading.sensor().unwrap_or_default().into(), reading.value()))
+    }
+
+    fn encode(readings: &[(&'static str, f64)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let identity = Map::new(|r: (&'static str, f64)| r);
+        write_flatbuffers(&identity, readings.to_vec(), &mut out, create).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let readings = [("a", 1.5), ("", 0.0), ("b", -2.0)];
+        let back: Vec<_> = read(&encode(&readings)[..], decode)
+            .map(Result::unwrap)
+            .collect();
+        let expected: Vec<_> = readings.iter().map(|&(s, v)| (s.to_string(), v)).collect();
+        assert_eq!(back, expected);
+    }
+
+    #[test]
+    fn test_take_stops_reading() {
+        let stream = encode(&[("x", 1.0); 1000]);
+        let mut reader = &stream[..];
+        let first = to_vec(
+            &Map::new(|r: Result<(String, f64), Error>| r.unwrap()).compose(Take::new(2)),
+            read(&mut reader, decode),
+        );
+        assert_eq!(first.len(), 2);
+        assert_eq!(reader.len(), stream.len() / 1000 * 998);
+    }
+
+    #[test]
+    fn test_invalid_buffer_keeps_framing() {
+        let mut stream = encode(&[("a", 1.0)]);
+        // A root offset pointing past the end of its buffer
+        stream.extend_from_slice(&[4, 0, 0, 0, 0xff, 0, 0, 0]);
+        stream.extend(encode(&[("b", 2.0)]));
+
+        let results: Vec<_> = read(&stream[..], decode).collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(Error::Invalid { index: 1, .. })));
+        assert_eq!(results[2].as_ref().unwrap().0, "b");
+    }
+
+    #[test]
+    fn test_truncated_buffer_ends_stream() {
+        let mut stream = encode(&[("a", 1.0), ("b", 2.0)]);
+        stream.truncate(stream.len() - 3);
+
+        let mut buffers = read(&stream[..], decode);
+        assert!(buffers.next().unwrap().is_ok());
+        assert!(matches!(
+            buffers.next(),
+            Some(Err(Error::Truncated { index: 1 }))
+        ));
+        assert!(buffers.next().is_none());
+    }
+}
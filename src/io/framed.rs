@@ -0,0 +1,280 @@
+//! Framed async sources and sinks over Tokio's `AsyncRead` / `AsyncWrite`.
+//!
+//! [`lines`] and [`frames`] split an `AsyncRead` (a socket, file or child
+//! process pipe) into a stream of newline-delimited lines or length-prefixed
+//! frames. [`transduce`] runs a pipeline over any stream and sends each output
+//! to a [`Sink`] as soon as it is produced, awaiting the sink between inputs,
+//! and [`to_lines`] / [`to_frames`] write the output to an `AsyncWrite` in the
+//! same framings. Early termination stops reading.
+//!
+//! ```rust,no_run
+//! use orlando_transducers::io::framed::{lines, to_lines};
+//! use orlando_transducers::{FlatMap, Filter, Transducer};
+//! use tokio::io::{AsyncRead, AsyncWrite};
+//!
+//! // Forward the error lines of a log stream, e.g. the halves of a TcpStream
+//! async fn relay(input: impl AsyncRead, output: impl AsyncWrite + Unpin) {
+//!     let errors = FlatMap::new(|line: Result<String, _>| line.into_iter().collect::<Vec<_>>())
+//!         .compose(Filter::new(|line: &String| line.contains("ERROR")));
+//!
+//!     to_lines(&errors, lines(input), output).await.unwrap();
+//! }
+//! ```
+//!
+//! Read errors are yielded as `Err` elements. Pipelines hold their state in
+//! `Rc`s, so these futures are not `Send`: run them with `block_on`, on a
+//! current-thread runtime or in a `LocalSet`.
+//!
+//! Requires the `tokio` feature.
+
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::bytes::Bytes;
+use tokio_util::codec::{
+    FramedRead, FramedWrite, LengthDelimitedCodec, LinesCodec, LinesCodecError,
+};
+
+/// Split `reader` into lines, without their `\n` or `\r\n` terminators.
+///
+/// The stream yields `Result<String, LinesCodecError>`; invalid UTF-8 is an
+/// error. A final line without a terminator is still yielded.
+pub fn lines<R: AsyncRead>(reader: R) -> FramedRead<R, LinesCodec> {
+    FramedRead::new(reader, LinesCodec::new())
+}
+
+/// Split `reader` into frames, each prefixed with its length as a big-endian
+/// `u32`.
+///
+/// The stream yields `io::Result<BytesMut>`. Use
+/// [`LengthDelimitedCodec::builder`] with [`FramedRead::new`] for other
+/// header layouts or a different maximum frame length.
+pub fn frames<R: AsyncRead>(reader: R) -> FramedRead<R, LengthDelimitedCodec> {
+    FramedRead::new(reader, LengthDelimitedCodec::new())
+}
+
+/// Run a pipeline over an async stream, sending each output to `sink` as soon
+/// as it is produced.
+///
+/// Each input is pulled only after the outputs of the previous one have been
+/// sent, so a slow sink applies backpressure to the source. Stops when the
+/// source ends or the pipeline stops, then flushes the sink. Stops at the
+/// first sink error. Returns the number of outputs sent.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::framed::transduce;
+/// use orlando_transducers::{Map, Take, Transducer};
+/// use futures_util::{sink, stream};
+///
+/// let pipeline = Map::new(|x: i32| x * 2).compose(Take::new(3));
+/// let mut out = Vec::new();
+/// let mut collect = Box::pin(sink::unfold(&mut out, |out, x: i32| async move {
+///     out.push(x);
+///     Ok::<_, std::convert::Infallible>(out)
+/// }));
+///
+/// let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let sent = rt.block_on(transduce(&pipeline, stream::iter(1..), &mut collect)).unwrap();
+/// drop(collect);
+/// assert_eq!(sent, 3);
+/// assert_eq!(out, vec![2, 4, 6]);
+/// ```
+pub async fn transduce<T, U, S, K>(
+    transducer: &impl Transducer<T, U>,
+    source: S,
+    sink: &mut K,
+) -> Result<usize, K::Error>
+where
+    T: 'static,
+    U: 'static,
+    S: Stream<Item = T>,
+    K: Sink<U> + Unpin,
+{
+    let transformed = transducer.apply(|mut acc: Vec<U>, x: U| {
+        acc.push(x);
+        cont(acc)
+    });
+    let mut source = std::pin::pin!(source);
+    let mut buffer = Vec::new();
+    let mut sent = 0;
+
+    while let Some(item) = source.next().await {
+        let (outputs, stopped) = match transformed(buffer, item) {
+            Step::Continue(outputs) => (outputs, false),
+            Step::Stop(outputs) => (outputs, true),
+        };
+        buffer = outputs;
+        for output in buffer.drain(..) {
+            sink.feed(output).await?;
+            sent += 1;
+        }
+        if stopped {
+            break;
+        }
+    }
+
+    sink.flush().await?;
+    Ok(sent)
+}
+
+/// Run a pipeline over an async stream and write each output to `writer` as a
+/// line.
+///
+/// The writer is shut down when the pipeline is done, which closes the write
+/// half of a socket. Returns the number of lines written.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::framed::{lines, to_lines};
+/// use orlando_transducers::{FlatMap, Map, Transducer};
+///
+/// let input: &[u8] = b"alpha\nbeta\n";
+/// let upper = FlatMap::new(|line: Result<String, _>| line.into_iter().collect::<Vec<_>>())
+///     .compose(Map::new(|line: String| line.to_uppercase()));
+///
+/// let mut out = Vec::new();
+/// let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// rt.block_on(to_lines(&upper, lines(input), &mut out)).unwrap();
+/// assert_eq!(out, b"ALPHA\nBETA\n");
+/// ```
+pub async fn to_lines<T, S, W>(
+    transducer: &impl Transducer<T, String>,
+    source: S,
+    writer: W,
+) -> Result<usize, LinesCodecError>
+where
+    T: 'static,
+    S: Stream<Item = T>,
+    W: AsyncWrite + Unpin,
+{
+    let mut sink = FramedWrite::new(writer, LinesCodec::new());
+    let written = transduce(transducer, source, &mut sink).await?;
+    SinkExt::<String>::close(&mut sink).await?;
+    Ok(written)
+}
+
+/// Run a pipeline over an async stream and write each output to `writer` as a
+/// length-prefixed frame, in the format read by [`frames`].
+///
+/// The writer is shut down when the pipeline is done. Returns the number of
+/// frames written.
+pub async fn to_frames<T, S, W>(
+    transducer: &impl Transducer<T, Bytes>,
+    source: S,
+    writer: W,
+) -> std::io::Result<usize>
+where
+    T: 'static,
+    S: Stream<Item = T>,
+    W: AsyncWrite + Unpin,
+{
+    let mut sink = FramedWrite::new(writer, LengthDelimitedCodec::new());
+    let written = transduce(transducer, source, &mut sink).await?;
+    SinkExt::<Bytes>::close(&mut sink).await?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::{FlatMap, Map, Take};
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::ReadBuf;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn ok<T: 'static, E: 'static>() -> impl Transducer<Result<T, E>, T> {
+        FlatMap::new(|x: Result<T, E>| x.into_iter().collect::<Vec<_>>())
+    }
+
+    /// A reader that hands out one byte per read and counts them
+    struct Trickle<'a> {
+        data: &'a [u8],
+        read: usize,
+    }
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            if let Some((&byte, rest)) = self.data.split_first() {
+                buf.put_slice(&[byte]);
+                self.data = rest;
+                self.read += 1;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn test_lines_round_trip() {
+        let input: &[u8] = b"a\r\nbb\n\nccc";
+        let mut out = Vec::new();
+        let written = block_on(to_lines(
+            &ok::<String, LinesCodecError>(),
+            lines(input),
+            &mut out,
+        ))
+        .unwrap();
+        assert_eq!(written, 4);
+        assert_eq!(out, b"a\nbb\n\nccc\n");
+    }
+
+    #[test]
+    fn test_take_stops_reading() {
+        let data = "line\n".repeat(1000);
+        let mut reader = Trickle {
+            data: data.as_bytes(),
+            read: 0,
+        };
+        let mut out = Vec::new();
+        let pipeline = ok::<String, LinesCodecError>().compose(Take::new(2));
+        block_on(to_lines(&pipeline, lines(&mut reader), &mut out)).unwrap();
+
+        assert_eq!(out, b"line\nline\n");
+        assert_eq!(reader.read, 10);
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let words = ["orlando", "", "transducers"];
+        let mut encoded = Vec::new();
+        let to_bytes = Map::new(|w: &'static str| Bytes::from_static(w.as_bytes()));
+        let written = block_on(to_frames(
+            &to_bytes,
+            futures_util::stream::iter(words),
+            &mut encoded,
+        ))
+        .unwrap();
+        assert_eq!(written, 3);
+        assert_eq!(&encoded[..4], &[0, 0, 0, 7]);
+
+        let decoded: Vec<String> = block_on(
+            frames(&encoded[..])
+                .map(|frame| String::from_utf8(frame.unwrap().to_vec()).unwrap())
+                .collect(),
+        );
+        assert_eq!(decoded, words);
+    }
+
+    #[test]
+    fn test_read_errors_are_elements() {
+        let input: &[u8] = b"ok\n\xff\n";
+        let results: Vec<_> = block_on(lines(input).collect());
+        assert_eq!(results[0].as_deref().unwrap(), "ok");
+        assert!(results[1].is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Newline-delimited JSON (NDJSON / JSON Lines) through serde.
+//!
+//! [`read`] parses one typed record per line, lazily, and
+//! [`write_json_lines`] runs a pipeline and writes each output as a line of
+//! JSON as soon as it is produced. Together they give typed ETL over files
+//! and sockets without a hand-written parsing loop:
+//!
+//! ```rust
+//! use orlando_transducers::io::json_lines::{read, write_json_lines};
+//! use orlando_transducers::{Filter, FlatMap, Map, Transducer};
+//! use serde_json::{json, Value};
+//!
+//! let input = br#"{"user": "ada", "ms": 120}
+//! {"user": "bob", "ms": 950}
+//! {"user": "cy", "ms": 1730}
+//! "#;
+//!
+//! let slow = FlatMap::new(|line: Result<Value, _>| line.into_iter().collect::<Vec<_>>())
+//!     .compose(Filter::new(|event: &Value| event["ms"].as_u64() > Some(500)))
+//!     .compose(Map::new(|event: Value| json!({"slow": event["user"]})));
+//!
+//! let mut out = Vec::new();
+//! write_json_lines(&slow, read::<Value, _>(&input[..]), &mut out).unwrap();
+//! assert_eq!(out, b"{\"slow\":\"bob\"}\n{\"slow\":\"cy\"}\n");
+//! ```
+//!
+//! Blank lines are skipped. A line that fails to parse is yielded as an
+//! [`Error::Parse`] carrying its line number, and reading continues with the
+//! next line, as the WASM `jsonLines` source does.
+//!
+//! Requires the `json` feature.
+
+use crate::transducer::Transducer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::marker::PhantomData;
+
+/// An error reading a JSON Lines source.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying reader failed; the source ends after this error
+    Io(io::Error),
+    /// The line with this (1-based) number is not valid JSON for the target type
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "read error: {}", e),
+            Error::Parse { line, source } => write!(f, "line {}: {}", line, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parse { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Lazily parse each line of `reader` as a `T`.
+///
+/// Wrap files and sockets in a [`std::io::BufReader`]; byte slices can be
+/// passed directly. Lines are read one at a time as the pipeline pulls them,
+/// so stopping early leaves the rest of the input unread.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::json_lines::read;
+/// use orlando_transducers::{sum, Map};
+///
+/// let input = "[1, 2]\n\n[3, 4]\n";
+/// let totals = Map::new(|pair: Result<(u32, u32), _>| pair.map_or(0, |(a, b)| a + b));
+/// assert_eq!(sum(&totals, read::<(u32, u32), _>(input.as_bytes())), 10);
+/// ```
+pub fn read<T, R>(reader: R) -> JsonLines<R, T>
+where
+    T: DeserializeOwned,
+    R: BufRead,
+{
+    JsonLines {
+        reader: Some(reader),
+        line: String::new(),
+        lines: 0,
+        _phantom: PhantomData,
+    }
+}
+
+/// Iterator returned by [`read`].
+pub struct JsonLines<R, T> {
+    /// The line source, `None` once it is exhausted or has failed
+    reader: Option<R>,
+    line: String,
+    lines: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<R, T> Iterator for JsonLines<R, T>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let reader = self.reader.as_mut()?;
+            self.line.clear();
+            match reader.read_line(&mut self.line) {
+                Ok(0) => {
+                    self.reader = None;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.reader = None;
+                    return Some(Err(Error::Io(e)));
+                }
+            }
+
+            self.lines += 1;
+            let text = self.line.trim();
+            if text.is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str(text).map_err(|source| Error::Parse {
+                line: self.lines,
+                source,
+            }));
+        }
+    }
+}
+
+/// Run a pipeline and write each output to `writer` as one line of JSON.
+///
+/// Each line is written as soon as the pipeline emits it. Returns the number
+/// of lines written, or the first serialization or write error. The writer is
+/// flushed at the end.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::json_lines::write_json_lines;
+/// use orlando_transducers::{Map, Take, Transducer};
+///
+/// let pipeline = Map::new(|n: u32| (n, n * n)).compose(Take::new(2));
+/// let mut out = Vec::new();
+/// assert_eq!(write_json_lines(&pipeline, 1.., &mut out).unwrap(), 2);
+/// assert_eq!(String::from_utf8(out).unwrap(), "[1,1]\n[2,4]\n");
+/// ```
+pub fn write_json_lines<T, U, Iter, W>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    mut writer: W,
+) -> io::Result<usize>
+where
+    T: 'static,
+    U: Serialize + 'static,
+    Iter: IntoIterator<Item = T>,
+    W: Write,
+{
+    let written = super::drain(transducer, source, |value| {
+        serde_json::to_writer(&mut writer, &value)?;
+        writer.write_all(b"\n")
+    })?;
+    writer.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::{partition_map, to_vec};
+    use crate::either::Either;
+    use crate::transducer::Identity;
+    use crate::transforms::{Map, Take};
+    use serde::Deserialize;
+    use std::io::Read;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Event {
+        kind: String,
+        value: i64,
+    }
+
+    #[test]
+    fn test_read_typed_records() {
+        let input = "{\"kind\":\"a\",\"value\":1}\r\n{\"kind\":\"b\",\"value\":2}";
+        let events = to_vec(
+            &Map::new(|e: Result<Event, Error>| e.unwrap()),
+            read::<Event, _>(input.as_bytes()),
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event {
+                    kind: "a".into(),
+                    value: 1
+                },
+                Event {
+                    kind: "b".into(),
+                    value: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_reports_bad_lines_and_continues() {
+        let input = "1\n\nnot json\n3\n";
+        let (ok, bad) =
+            partition_map(
+                &Identity::new(),
+                read::<i32, _>(input.as_bytes()),
+                |r| match r {
+                    Ok(v) => Either::Left(v),
+                    Err(Error::Parse { line, .. }) => Either::Right(line),
+                    Err(e) => panic!("unexpected error: {}", e),
+                },
+            );
+        assert_eq!(ok, vec![1, 3]);
+        assert_eq!(bad, vec![3]);
+    }
+
+    #[test]
+    fn test_read_stops_at_io_error() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+
+        let mut lines = read::<i32, _>(io::BufReader::new(Failing));
+        assert!(matches!(lines.next(), Some(Err(Error::Io(_)))));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_read_is_lazy() {
+        let input = "1\n2\ngarbage that is never parsed\n";
+        let firsts = to_vec(
+            &Map::new(|r: Result<i32, Error>| r.unwrap()).compose(Take::new(2)),
+            read::<i32, _>(input.as_bytes()),
+        );
+        assert_eq!(firsts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_write_json_lines_round_trip() {
+        let events = vec![
+            Event {
+                kind: "a".into(),
+                value: 1,
+            },
+            Event {
+                kind: "b".into(),
+                value: -2,
+            },
+        ];
+        let mut out = Vec::new();
+        assert_eq!(
+            write_json_lines(&Identity::new(), events, &mut out).unwrap(),
+            2
+        );
+
+        let back = to_vec(
+            &Map::new(|e: Result<Event, Error>| e.unwrap().value),
+            read::<Event, _>(&out[..]),
+        );
+        assert_eq!(back, vec![1, -2]);
+    }
+}
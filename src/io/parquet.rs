@@ -0,0 +1,279 @@
+//! Streaming Parquet sources.
+//!
+//! [`rows`] and [`column`] read a Parquet file one record batch at a time and
+//! yield its rows as typed values. Only the requested columns are decoded, and
+//! data is read on demand: when a pipeline stops early (for example with
+//! `Take`), the remaining row groups are never read. Batches hold 1024 rows by
+//! default; with [`rows_with`], a batch size no larger than the row groups
+//! keeps a batch from reaching into the next group.
+//!
+//! ```rust,no_run
+//! use orlando_transducers::io::parquet::rows;
+//! use orlando_transducers::{to_vec, FlatMap, Filter, Take, Transducer};
+//!
+//! let file = std::fs::File::open("trips.parquet").unwrap();
+//! let long_trips = FlatMap::new(|row: Result<(String, f64), _>| row.into_iter().collect::<Vec<_>>())
+//!     .compose(Filter::new(|(_, miles): &(String, f64)| *miles > 50.0))
+//!     .compose(Take::new(10));
+//!
+//! let first_ten = to_vec(&long_trips, rows(file, &["vendor", "distance"]).unwrap());
+//! ```
+//!
+//! Rows are read through [`io::arrow`](crate::io::arrow), so the column types
+//! and null handling are those of [`ArrowValue`]. Read errors are yielded as
+//! `Err` elements and end the stream.
+//!
+//! Requires the `parquet` feature.
+
+use super::arrow::{self as arrow_io, ArrowRow, ArrowValue};
+use arrow_schema::ArrowError;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ProjectionMask;
+use parquet::errors::ParquetError;
+use parquet::file::reader::ChunkReader;
+
+/// Stream the named columns of a Parquet file as tuples.
+///
+/// `names` must have one entry per tuple field. Only those columns are
+/// decoded.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::to_record_batch;
+/// use orlando_transducers::io::parquet::rows;
+/// use orlando_transducers::{to_vec, Map};
+/// use bytes::Bytes;
+/// use parquet::arrow::ArrowWriter;
+///
+/// let batch = to_record_batch(&Map::new(|i: i64| (i, i % 3 == 0)), 0..6, &["n", "fizz"]).unwrap();
+/// let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), None).unwrap();
+/// writer.write(&batch).unwrap();
+/// let file = Bytes::from(writer.into_inner().unwrap());
+///
+/// let fizz = Map::new(|row: Result<(i64, bool), _>| row.unwrap());
+/// let back = to_vec(&fizz, rows::<(i64, bool), _>(file, &["n", "fizz"]).unwrap());
+/// assert_eq!(back[3], (3, true));
+/// ```
+pub fn rows<T, R>(reader: R, names: &[&str]) -> Result<ParquetRows<T>, ParquetError>
+where
+    T: ArrowRow,
+    R: ChunkReader + 'static,
+{
+    rows_with(ParquetRecordBatchReaderBuilder::try_new(reader)?, names)
+}
+
+/// Like [`rows`], but reading through a configured
+/// [`ParquetRecordBatchReaderBuilder`].
+///
+/// Use the builder to choose the batch size, restrict reading to some row
+/// groups, or set a row limit up front. The projection is replaced by the
+/// named columns.
+pub fn rows_with<T, R>(
+    builder: ParquetRecordBatchReaderBuilder<R>,
+    names: &[&str],
+) -> Result<ParquetRows<T>, ParquetError>
+where
+    T: ArrowRow,
+    R: ChunkReader + 'static,
+{
+    arrow_io::check_width::<T>(names)?;
+    let mask = ProjectionMask::columns(builder.parquet_schema(), names.iter().copied());
+    Ok(ParquetRows {
+        batches: Some(builder.with_projection(mask).build()?),
+        names: names.iter().map(|name| name.to_string()).collect(),
+        current: None,
+    })
+}
+
+/// Stream a single column of a Parquet file.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::arrow::to_record_batch;
+/// use orlando_transducers::io::parquet::column;
+/// use orlando_transducers::{sum, Map};
+/// use bytes::Bytes;
+/// use parquet::arrow::ArrowWriter;
+///
+/// let batch = to_record_batch(&Map::new(|i: i32| (i, -i)), 1..=4, &["a", "b"]).unwrap();
+/// let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), None).unwrap();
+/// writer.write(&batch).unwrap();
+/// let file = Bytes::from(writer.into_inner().unwrap());
+///
+/// let values = Map::new(|v: Result<i32, _>| v.unwrap());
+/// assert_eq!(sum(&values, column::<i32, _>(file, "a").unwrap()), 10);
+/// ```
+pub fn column<T, R>(
+    reader: R,
+    name: &str,
+) -> Result<impl Iterator<Item = Result<T, ArrowError>>, ParquetError>
+where
+    T: ArrowValue,
+    R: ChunkReader + 'static,
+{
+    Ok(rows::<(T,), R>(reader, &[name])?.map(|row| row.map(|(value,)| value)))
+}
+
+/// Iterator returned by [`rows`] and [`rows_with`].
+pub struct ParquetRows<T: ArrowRow> {
+    /// The batch reader, `None` once it is exhausted or has failed
+    batches: Option<ParquetRecordBatchReader>,
+    names: Vec<String>,
+    current: Option<arrow_io::Rows<T>>,
+}
+
+impl<T: ArrowRow> Iterator for ParquetRows<T> {
+    type Item = Result<T, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(Ok(row));
+            }
+
+            // Current batch exhausted: decode the next one
+            let batch = match self.batches.as_mut()?.next() {
+                Some(Ok(batch)) => batch,
+                Some(Err(e)) => {
+                    self.batches = None;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.batches = None;
+                    return None;
+                }
+            };
+            let names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+            match arrow_io::rows::<T>(&batch, &names) {
+                Ok(rows) => self.current = Some(rows),
+                Err(e) => {
+                    self.batches = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::io::arrow::to_record_batch;
+    use crate::transducer::Transducer;
+    use crate::transforms::{FlatMap, Map, Take};
+    use bytes::Bytes;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::reader::Length;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Ten row groups of 100 rows: (id, label, maybe_score)
+    fn file() -> Bytes {
+        let batch = to_record_batch(
+            &Map::new(|i: i64| (i, format!("row{}", i), (i % 2 == 0).then_some(i as f64))),
+            0..1000,
+            &["id", "label", "score"],
+        )
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .build();
+        let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        Bytes::from(writer.into_inner().unwrap())
+    }
+
+    /// A reader that counts how many times data is fetched
+    struct Counting {
+        bytes: Bytes,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl Length for Counting {
+        fn len(&self) -> u64 {
+            self.bytes.len() as u64
+        }
+    }
+
+    impl ChunkReader for Counting {
+        type T = <Bytes as ChunkReader>::T;
+
+        fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.bytes.get_read(start)
+        }
+
+        fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.bytes.get_bytes(start, length)
+        }
+    }
+
+    fn ok<T: 'static>() -> impl Transducer<Result<T, ArrowError>, T> {
+        FlatMap::new(|row: Result<T, ArrowError>| row.into_iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_rows_across_row_groups() {
+        let all = to_vec(
+            &ok(),
+            rows::<(i64, Option<f64>), _>(file(), &["id", "score"]).unwrap(),
+        );
+        assert_eq!(all.len(), 1000);
+        assert_eq!(all[998], (998, Some(998.0)));
+        assert_eq!(all[999], (999, None));
+    }
+
+    #[test]
+    fn test_column() {
+        let labels = to_vec(
+            &ok::<String>().compose(Take::new(2)),
+            column::<String, _>(file(), "label").unwrap(),
+        );
+        assert_eq!(labels, vec!["row0", "row1"]);
+    }
+
+    #[test]
+    fn test_type_mismatch_is_an_element_error() {
+        let mut values = column::<i32, _>(file(), "id").unwrap();
+        assert!(matches!(values.next(), Some(Err(_))));
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn test_name_count_checked() {
+        assert!(rows::<(i64, String), _>(file(), &["id"]).is_err());
+    }
+
+    #[test]
+    fn test_take_stops_reading_row_groups() {
+        let bytes = file();
+        let reads_for = |take: usize| {
+            let reads = Arc::new(AtomicUsize::new(0));
+            let reader = Counting {
+                bytes: bytes.clone(),
+                reads: Arc::clone(&reads),
+            };
+            // One batch per row group, so a batch never spans two groups
+            let builder = ParquetRecordBatchReaderBuilder::try_new(reader)
+                .unwrap()
+                .with_batch_size(100);
+            let opened = reads.load(Ordering::SeqCst);
+
+            let ids = to_vec(
+                &ok::<(i64,)>().compose(Take::new(take)),
+                rows_with::<(i64,), _>(builder, &["id"]).unwrap(),
+            );
+            assert_eq!(ids.len(), take);
+            reads.load(Ordering::SeqCst) - opened
+        };
+
+        // Reads grow with the row groups actually consumed
+        assert!(reads_for(150) < reads_for(450));
+        assert!(reads_for(450) < reads_for(1000));
+    }
+}
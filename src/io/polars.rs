@@ -0,0 +1,358 @@
+//! Polars interop: Series as sources, pipeline output as Series.
+//!
+//! [`from_series`] reads a [`Series`] as a lazy source. The iterator holds a
+//! reference-counted handle to the Series' chunks and reads values in place,
+//! so no data is copied for numeric and boolean columns (string values are
+//! copied into owned `String`s). [`to_series`] runs a pipeline straight into
+//! a Polars builder, without an intermediate `Vec`, so the result can be added
+//! to a `DataFrame` as a column:
+//!
+//! ```rust
+//! use orlando_transducers::io::polars::{from_series, to_series};
+//! use orlando_transducers::{Filter, Map, Transducer};
+//! use polars::prelude::*;
+//!
+//! let mut df = df!("temp" => [4.5, 19.0, 31.5]).unwrap();
+//!
+//! let temps = from_series::<f64>(df.column("temp").unwrap().as_materialized_series()).unwrap();
+//! let fahrenheit = to_series(&Map::new(|t: f64| t * 1.8 + 32.0), temps, "temp_f");
+//! df.with_column(fahrenheit).unwrap();
+//!
+//! assert_eq!(df.get_column_names(), ["temp", "temp_f"]);
+//! ```
+//!
+//! The supported value types are listed on [`PolarsValue`]; `Option<T>` reads
+//! and writes columns with nulls.
+//!
+//! Requires the `polars` feature.
+
+use crate::step::cont;
+use crate::transducer::Transducer;
+use polars::datatypes::{PolarsDataType, StaticArray};
+use polars::prelude::*;
+
+/// A Rust type that maps to a Polars column type.
+///
+/// Implemented for `i32`, `i64`, `u32`, `u64`, `f32`, `f64`, `bool` and
+/// `String`, and for `Option<T>` of any of them, which maps to a column with
+/// nulls.
+pub trait PolarsValue: Sized + 'static {
+    /// The Polars data type of a column of this type
+    type Type: PolarsPhysicalType;
+    /// The builder values are collected into
+    type Builder;
+
+    /// Whether the column may contain nulls.
+    fn nullable() -> bool {
+        false
+    }
+
+    /// Read the value at `index` of one chunk, which is known to be in bounds
+    /// (and not null, unless this type is nullable).
+    fn read(array: &<Self::Type as PolarsDataType>::Array, index: usize) -> Self;
+
+    /// Create an empty builder with room for `capacity` values.
+    fn builder(name: PlSmallStr, capacity: usize) -> Self::Builder;
+
+    /// Append this value to a builder.
+    fn append(self, builder: &mut Self::Builder);
+
+    /// Append a null to a builder.
+    fn append_null(builder: &mut Self::Builder);
+
+    /// Finish a builder into a Series.
+    fn finish(builder: Self::Builder) -> Series;
+}
+
+macro_rules! primitive_value {
+    ($($t:ty => $polars:ty;)*) => {
+        $(
+            impl PolarsValue for $t {
+                type Type = $polars;
+                type Builder = PrimitiveChunkedBuilder<$polars>;
+
+                fn read(array: &<Self::Type as PolarsDataType>::Array, index: usize) -> Self {
+                    array.value(index)
+                }
+
+                fn builder(name: PlSmallStr, capacity: usize) -> Self::Builder {
+                    PrimitiveChunkedBuilder::new(name, capacity)
+                }
+
+                fn append(self, builder: &mut Self::Builder) {
+                    builder.append_value(self);
+                }
+
+                fn append_null(builder: &mut Self::Builder) {
+                    builder.append_null();
+                }
+
+                fn finish(builder: Self::Builder) -> Series {
+                    builder.finish().into_series()
+                }
+            }
+        )*
+    };
+}
+
+primitive_value! {
+    i32 => Int32Type;
+    i64 => Int64Type;
+    u32 => UInt32Type;
+    u64 => UInt64Type;
+    f32 => Float32Type;
+    f64 => Float64Type;
+}
+
+impl PolarsValue for bool {
+    type Type = BooleanType;
+    type Builder = BooleanChunkedBuilder;
+
+    fn read(array: &<Self::Type as PolarsDataType>::Array, index: usize) -> Self {
+        array.value(index)
+    }
+
+    fn builder(name: PlSmallStr, capacity: usize) -> Self::Builder {
+        BooleanChunkedBuilder::new(name, capacity)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        builder.append_value(self);
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        builder.append_null();
+    }
+
+    fn finish(builder: Self::Builder) -> Series {
+        builder.finish().into_series()
+    }
+}
+
+impl PolarsValue for String {
+    type Type = StringType;
+    type Builder = StringChunkedBuilder;
+
+    fn read(array: &<Self::Type as PolarsDataType>::Array, index: usize) -> Self {
+        array.value(index).to_string()
+    }
+
+    fn builder(name: PlSmallStr, capacity: usize) -> Self::Builder {
+        StringChunkedBuilder::new(name, capacity)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        builder.append_value(self);
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        builder.append_null();
+    }
+
+    fn finish(builder: Self::Builder) -> Series {
+        builder.finish().into_series()
+    }
+}
+
+impl<T: PolarsValue> PolarsValue for Option<T> {
+    type Type = T::Type;
+    type Builder = T::Builder;
+
+    fn nullable() -> bool {
+        true
+    }
+
+    fn read(array: &<Self::Type as PolarsDataType>::Array, index: usize) -> Self {
+        // `get` is `None` for a null slot
+        array.get(index).map(|_| T::read(array, index))
+    }
+
+    fn builder(name: PlSmallStr, capacity: usize) -> Self::Builder {
+        T::builder(name, capacity)
+    }
+
+    fn append(self, builder: &mut Self::Builder) {
+        match self {
+            Some(value) => value.append(builder),
+            None => T::append_null(builder),
+        }
+    }
+
+    fn append_null(builder: &mut Self::Builder) {
+        T::append_null(builder);
+    }
+
+    fn finish(builder: Self::Builder) -> Series {
+        T::finish(builder)
+    }
+}
+
+/// Read a Series as a lazy source of `T`.
+///
+/// Fails if the Series' data type doesn't match `T`, or if it contains nulls
+/// and `T` is not an `Option`. Use `Column::as_materialized_series` to read a
+/// `DataFrame` column.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::polars::from_series;
+/// use orlando_transducers::{sum, Map};
+/// use polars::prelude::*;
+///
+/// let qty = Series::new("qty".into(), [Some(3), None, Some(4)]);
+///
+/// assert!(from_series::<i32>(&qty).is_err()); // has nulls
+/// let qty = from_series::<Option<i32>>(&qty).unwrap();
+/// assert_eq!(sum(&Map::new(|q: Option<i32>| q.unwrap_or(0)), qty), 7);
+/// ```
+pub fn from_series<T: PolarsValue>(series: &Series) -> PolarsResult<Values<T>> {
+    let chunked = series.unpack::<T::Type>()?;
+    if !T::nullable() && chunked.null_count() > 0 {
+        return Err(PolarsError::ComputeError(
+            format!(
+                "'{}' contains {} nulls; read it as an Option",
+                series.name(),
+                chunked.null_count()
+            )
+            .into(),
+        ));
+    }
+
+    Ok(Values {
+        chunked: chunked.clone(),
+        lengths: chunked.chunk_lengths().collect(),
+        chunk: 0,
+        index: 0,
+        remaining: chunked.len(),
+    })
+}
+
+/// Iterator returned by [`from_series`].
+///
+/// Cloning the underlying chunks only bumps reference counts, so the iterator
+/// owns its data without copying it and can outlive the Series.
+#[derive(Clone)]
+pub struct Values<T: PolarsValue> {
+    chunked: ChunkedArray<T::Type>,
+    lengths: Vec<usize>,
+    /// Position of the next value: a chunk, and an index within it
+    chunk: usize,
+    index: usize,
+    remaining: usize,
+}
+
+impl<T: PolarsValue> Iterator for Values<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let len = *self.lengths.get(self.chunk)?;
+            if self.index < len {
+                let array = self.chunked.downcast_get(self.chunk)?;
+                let value = T::read(array, self.index);
+                self.index += 1;
+                self.remaining -= 1;
+                return Some(value);
+            }
+            self.chunk += 1;
+            self.index = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: PolarsValue> ExactSizeIterator for Values<T> {}
+
+/// Run a pipeline and collect its output into a Series named `name`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::polars::to_series;
+/// use orlando_transducers::{Filter, Map, Transducer};
+/// use polars::prelude::*;
+///
+/// let pipeline = Filter::new(|x: &i32| x % 2 == 0).compose(Map::new(|x: i32| x as f64 / 2.0));
+/// let series = to_series(&pipeline, 1..=6, "half");
+/// assert_eq!(series, Series::new("half".into(), [1.0, 2.0, 3.0]));
+/// ```
+pub fn to_series<T, U, Iter>(transducer: &impl Transducer<T, U>, source: Iter, name: &str) -> Series
+where
+    T: 'static,
+    U: PolarsValue,
+    Iter: IntoIterator<Item = T>,
+{
+    let source = source.into_iter();
+    let capacity = transducer.size_hint(source.size_hint()).0;
+    let builder = crate::collectors::reduce(
+        transducer,
+        source,
+        U::builder(name.into(), capacity),
+        |mut builder, x: U| {
+            x.append(&mut builder);
+            cont(builder)
+        },
+    );
+    U::finish(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transducer::Identity;
+    use crate::transforms::{Map, Take};
+
+    #[test]
+    fn test_round_trip_types() {
+        let words = ["a", "bb", "ccc"].map(String::from).to_vec();
+        let series = to_series(&Identity::new(), words.clone(), "word");
+        assert_eq!(series.dtype(), &DataType::String);
+        assert_eq!(
+            to_vec(&Identity::new(), from_series::<String>(&series).unwrap()),
+            words
+        );
+
+        let flags = to_series(&Map::new(|x: u64| x > 1), 0..4u64, "flag");
+        assert_eq!(
+            to_vec(&Identity::new(), from_series::<bool>(&flags).unwrap()),
+            vec![false, false, true, true]
+        );
+    }
+
+    #[test]
+    fn test_nulls() {
+        let series = to_series(&Map::new(|x: i64| (x % 2 == 0).then_some(x)), 0..5, "even");
+        assert_eq!(series.null_count(), 2);
+        assert!(from_series::<i64>(&series).is_err());
+        assert_eq!(
+            to_vec(
+                &Identity::new(),
+                from_series::<Option<i64>>(&series).unwrap()
+            ),
+            vec![Some(0), None, Some(2), None, Some(4)]
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let series = Series::new("x".into(), [1.0f32, 2.0]);
+        assert!(from_series::<f64>(&series).is_err());
+        assert!(from_series::<f32>(&series).is_ok());
+    }
+
+    #[test]
+    fn test_reads_across_chunks() {
+        let mut series = Series::new("n".into(), [1u32, 2, 3]);
+        series.append(&Series::new("n".into(), [4u32, 5])).unwrap();
+        assert_eq!(series.n_chunks(), 2);
+
+        let values = from_series::<u32>(&series).unwrap();
+        assert_eq!(values.len(), 5);
+        assert_eq!(to_vec(&Take::new(4), values), vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,302 @@
+//! Length-delimited Protocol Buffers messages through prost.
+//!
+//! [`read`] decodes a stream of messages, each prefixed with its length as a
+//! varint (the framing of `Message::encode_length_delimited` and of Java's
+//! `writeDelimitedTo`), into typed pipeline elements. Messages are read one at
+//! a time as the pipeline pulls them, so a pipeline that stops early leaves
+//! the rest of the stream unread. [`write_protobuf`] writes pipeline output
+//! in the same framing.
+//!
+//! ```rust
+//! use orlando_transducers::io::protobuf::{read, write_protobuf};
+//! use orlando_transducers::{FlatMap, Filter, Transducer};
+//!
+//! #[derive(Clone, PartialEq, prost::Message)]
+//! struct Span {
+//!     #[prost(string, tag = "1")]
+//!     name: String,
+//!     #[prost(uint64, tag = "2")]
+//!     duration_us: u64,
+//! }
+//!
+//! let mut telemetry = Vec::new();
+//! let spans = ["db", "render", "db"].map(|name| Span { name: name.into(), duration_us: 1500 });
+//! write_protobuf(&FlatMap::new(|s: Span| vec![s]), spans, &mut telemetry).unwrap();
+//!
+//! let db = FlatMap::new(|span: Result<Span, _>| span.into_iter().collect::<Vec<_>>())
+//!     .compose(Filter::new(|span: &Span| span.name == "db"));
+//! let db_spans: Vec<Span> = orlando_transducers::to_vec(&db, read::<Span, _>(&telemetry[..]));
+//! assert_eq!(db_spans.len(), 2);
+//! ```
+//!
+//! A message that fails to decode is yielded as an [`Error::Decode`] with its
+//! position in the stream, and reading continues with the next message, since
+//! the length prefix keeps the framing intact. A read error or a stream that
+//! ends partway through a message ends the source.
+//!
+//! Requires the `protobuf` feature.
+
+use crate::transducer::Transducer;
+use prost::Message;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::marker::PhantomData;
+
+/// An error reading a length-delimited protobuf source.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying reader failed; the source ends after this error
+    Io(io::Error),
+    /// The stream ended partway through the message with this (0-based)
+    /// index, or its length prefix is malformed; the source ends after this
+    /// error
+    Truncated { index: usize },
+    /// The message with this (0-based) index could not be decoded
+    Decode {
+        index: usize,
+        source: prost::DecodeError,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "read error: {}", e),
+            Error::Truncated { index } => write!(f, "message {}: truncated", index),
+            Error::Decode { index, source } => write!(f, "message {}: {}", index, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Truncated { .. } => None,
+            Error::Decode { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Lazily decode length-delimited messages of type `M` from `reader`.
+///
+/// Wrap files and sockets in a [`std::io::BufReader`]; byte slices can be
+/// passed directly.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::protobuf::read;
+/// use orlando_transducers::{to_vec, Map, Take, Transducer};
+/// use prost::Message;
+///
+/// let mut stream = Vec::new();
+/// for n in 1..=100u64 {
+///     n.encode_length_delimited(&mut stream).unwrap();
+/// }
+///
+/// let first = Map::new(|n: Result<u64, _>| n.unwrap()).compose(Take::new(3));
+/// assert_eq!(to_vec(&first, read::<u64, _>(&stream[..])), vec![1, 2, 3]);
+/// ```
+pub fn read<M, R>(reader: R) -> Messages<R, M>
+where
+    M: Message + Default,
+    R: BufRead,
+{
+    Messages {
+        reader: Some(reader),
+        buffer: Vec::new(),
+        index: 0,
+        _phantom: PhantomData,
+    }
+}
+
+/// Iterator returned by [`read`].
+pub struct Messages<R, M> {
+    /// The message source, `None` once it is exhausted or has failed
+    reader: Option<R>,
+    buffer: Vec<u8>,
+    index: usize,
+    _phantom: PhantomData<M>,
+}
+
+/// Read a varint length prefix. `Ok(None)` at a clean end of stream.
+fn read_length(reader: &mut impl BufRead) -> io::Result<Option<Option<u64>>> {
+    let mut length = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = match reader.fill_buf()? {
+            [] if shift == 0 => return Ok(None),
+            [] => return Ok(Some(None)),
+            [byte, ..] => *byte,
+        };
+        reader.consume(1);
+        length |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(Some(Some(length)));
+        }
+    }
+    // More than ten bytes: not a varint
+    Ok(Some(None))
+}
+
+impl<R, M> Iterator for Messages<R, M>
+where
+    R: BufRead,
+    M: Message + Default,
+{
+    type Item = Result<M, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = self.reader.as_mut()?;
+        let index = self.index;
+        self.index += 1;
+
+        let length = match read_length(reader) {
+            Ok(None) => {
+                self.reader = None;
+                return None;
+            }
+            Ok(Some(Some(length))) => length,
+            Ok(Some(None)) => {
+                self.reader = None;
+                return Some(Err(Error::Truncated { index }));
+            }
+            Err(e) => {
+                self.reader = None;
+                return Some(Err(Error::Io(e)));
+            }
+        };
+
+        // Read through `take` so a corrupt length can't allocate more than the
+        // stream actually holds
+        self.buffer.clear();
+        match reader.by_ref().take(length).read_to_end(&mut self.buffer) {
+            Ok(n) if n as u64 == length => {}
+            Ok(_) => {
+                self.reader = None;
+                return Some(Err(Error::Truncated { index }));
+            }
+            Err(e) => {
+                self.reader = None;
+                return Some(Err(Error::Io(e)));
+            }
+        }
+
+        Some(M::decode(&self.buffer[..]).map_err(|source| Error::Decode { index, source }))
+    }
+}
+
+/// Run a pipeline and write each output to `writer` as a length-delimited
+/// message, as soon as it is produced.
+///
+/// Stops at the first write error. Returns the number of messages written.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::io::protobuf::{read, write_protobuf};
+/// use orlando_transducers::{to_vec, Filter, Map};
+///
+/// let mut out = Vec::new();
+/// let evens = Filter::new(|n: &u32| n % 2 == 0);
+/// assert_eq!(write_protobuf(&evens, 0..10u32, &mut out).unwrap(), 5);
+///
+/// let back = to_vec(&Map::new(|n: Result<u32, _>| n.unwrap()), read::<u32, _>(&out[..]));
+/// assert_eq!(back, vec![0, 2, 4, 6, 8]);
+/// ```
+pub fn write_protobuf<T, U, Iter, W>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    mut writer: W,
+) -> io::Result<usize>
+where
+    T: 'static,
+    U: Message + 'static,
+    Iter: IntoIterator<Item = T>,
+    W: Write,
+{
+    let mut buffer = Vec::new();
+    let written = super::drain(transducer, source, |message: U| {
+        buffer.clear();
+        message
+            .encode_length_delimited(&mut buffer)
+            .map_err(io::Error::other)?;
+        writer.write_all(&buffer)
+    })?;
+    writer.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Map, Take};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Reading {
+        #[prost(string, tag = "1")]
+        sensor: String,
+        #[prost(double, tag = "2")]
+        value: f64,
+    }
+
+    fn reading(sensor: &str, value: f64) -> Reading {
+        Reading {
+            sensor: sensor.into(),
+            value,
+        }
+    }
+
+    fn encode(messages: &[Reading]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_protobuf(&Map::new(|r: Reading| r), messages.to_vec(), &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let readings = vec![reading("a", 1.5), reading("", 0.0), reading("b", -2.0)];
+        let back: Vec<Reading> = read(&encode(&readings)[..]).map(Result::unwrap).collect();
+        assert_eq!(back, readings);
+    }
+
+    #[test]
+    fn test_take_stops_reading() {
+        let stream = encode(&vec![reading("x", 1.0); 1000]);
+        let mut reader = &stream[..];
+        let first = to_vec(
+            &Map::new(|r: Result<Reading, Error>| r.unwrap()).compose(Take::new(2)),
+            read::<Reading, _>(&mut reader),
+        );
+        assert_eq!(first.len(), 2);
+        assert_eq!(reader.len(), stream.len() / 1000 * 998);
+    }
+
+    #[test]
+    fn test_decode_error_keeps_framing() {
+        let mut stream = encode(&[reading("a", 1.0)]);
+        // A two-byte message with an invalid wire type
+        stream.extend_from_slice(&[2, 0x0f, 0x00]);
+        stream.extend(encode(&[reading("b", 2.0)]));
+
+        let results: Vec<_> = read::<Reading, _>(&stream[..]).collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(Error::Decode { index: 1, .. })));
+        assert_eq!(results[2].as_ref().unwrap().sensor, "b");
+    }
+
+    #[test]
+    fn test_truncated_message_ends_stream() {
+        let mut stream = encode(&[reading("a", 1.0), reading("b", 2.0)]);
+        stream.truncate(stream.len() - 3);
+
+        let mut messages = read::<Reading, _>(&stream[..]);
+        assert!(messages.next().unwrap().is_ok());
+        assert!(matches!(
+            messages.next(),
+            Some(Err(Error::Truncated { index: 1 }))
+        ));
+        assert!(messages.next().is_none());
+    }
+}
@@ -20,6 +20,8 @@
 
 use crate::step::Step;
 use crate::transducer::Transducer;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 /// Extension trait that adds `.transduce()` to any iterator.
 pub trait TransduceExt: Iterator + Sized {
@@ -145,6 +147,8 @@ type StepFn<In, Out> = Box<dyn Fn(Vec<Out>, In) -> Step<Vec<Out>>>;
 /// Type-erased transducer for use in PipelineBuilder.
 trait ErasedTransducer<In: 'static, Out: 'static> {
     fn apply_erased(&self, reducer: Reducer<Out>) -> StepFn<In, Out>;
+
+    fn size_hint_erased(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>);
 }
 
 /// Wrapper to make any Transducer into an ErasedTransducer.
@@ -161,6 +165,10 @@ impl<In: 'static, Out: 'static, T: Transducer<In, Out>> ErasedTransducer<In, Out
         // The closure captures the Box and calls through it.
         self.inner.apply(move |acc, x| reducer(acc, x))
     }
+
+    fn size_hint_erased(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        self.inner.size_hint(input)
+    }
 }
 
 impl PipelineBuilder<(), ()> {
@@ -223,8 +231,10 @@ impl<In: 'static, Out: 'static> PipelineBuilder<In, Out> {
             crate::step::cont(acc)
         });
 
+        let source = source.into_iter();
+        let capacity = self.transducer.size_hint_erased(source.size_hint()).0;
         let step_fn = self.transducer.apply_erased(reducer);
-        let mut result = Vec::new();
+        let mut result = Vec::with_capacity(capacity);
 
         for item in source {
             match step_fn(result, item) {
@@ -299,7 +309,7 @@ mod tests {
     #[test]
     fn test_transduce_empty_iterator() {
         let pipeline = crate::transforms::Map::new(|x: i32| x * 2);
-        let result: Vec<i32> = std::iter::empty().transduce(&pipeline).collect();
+        let result: Vec<i32> = core::iter::empty().transduce(&pipeline).collect();
         assert_eq!(result, Vec::<i32>::new());
     }
 
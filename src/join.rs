@@ -0,0 +1,751 @@
+//! # Joins: Combining two sources by key
+//!
+//! Relational joins between two iterators, each returning a lazy iterator
+//! that can feed a transducer and stop early:
+//!
+//! - [`hash_join`] builds a hash table from the right side and streams the
+//!   left side past it.
+//! - [`merge_join`] walks two sources already sorted by key side by side,
+//!   holding only the rows that share the current key.
+//! - [`asof_join`] pairs each left row with the latest right row at or
+//!   before its timestamp.
+//!
+//! ```rust
+//! use orlando_transducers::join::{hash_join, JoinType, Joined};
+//!
+//! let users = vec![(1, "ada"), (2, "bo"), (3, "cy")];
+//! let orders = vec![(1, 9.5), (3, 20.0), (1, 4.0)];
+//!
+//! let rows: Vec<_> = hash_join(users, orders, |u| u.0, |o| o.0, JoinType::Inner)
+//!     .filter_map(Joined::both)
+//!     .map(|(user, order)| (user.1, order.1))
+//!     .collect();
+//! assert_eq!(rows, vec![("ada", 9.5), ("ada", 4.0), ("cy", 20.0)]);
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::iter::Peekable;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Which unmatched rows a join keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinType {
+    /// Only rows with a match on both sides.
+    #[default]
+    Inner,
+    /// Every left row, with or without a match.
+    Left,
+    /// Every row from both sides, with or without a match.
+    Outer,
+}
+
+/// A row of a join: a matched pair, or a row from one side with no match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Joined<L, R> {
+    /// A left row and a right row with equal keys.
+    Both(L, R),
+    /// A left row with no match, from a [`JoinType::Left`] or
+    /// [`JoinType::Outer`] join.
+    Left(L),
+    /// A right row with no match, from a [`JoinType::Outer`] join.
+    Right(R),
+}
+
+impl<L, R> Joined<L, R> {
+    /// The matched pair, if this row has both sides.
+    pub fn both(self) -> Option<(L, R)> {
+        match self {
+            Joined::Both(left, right) => Some((left, right)),
+            _ => None,
+        }
+    }
+
+    /// Both sides as options, as in a SQL result with `NULL`s.
+    pub fn into_options(self) -> (Option<L>, Option<R>) {
+        match self {
+            Joined::Both(left, right) => (Some(left), Some(right)),
+            Joined::Left(left) => (Some(left), None),
+            Joined::Right(right) => (None, Some(right)),
+        }
+    }
+
+    /// The left side, if present.
+    pub fn left(&self) -> Option<&L> {
+        match self {
+            Joined::Both(left, _) | Joined::Left(left) => Some(left),
+            Joined::Right(_) => None,
+        }
+    }
+
+    /// The right side, if present.
+    pub fn right(&self) -> Option<&R> {
+        match self {
+            Joined::Both(_, right) | Joined::Right(right) => Some(right),
+            Joined::Left(_) => None,
+        }
+    }
+}
+
+/// Join two sources on equal keys using a hash table (helper function, not
+/// a transducer).
+///
+/// The right side is read up front into a table keyed by `right_key`; the
+/// left side is then streamed, each row yielding [`Joined::Both`] with every
+/// right row sharing its key, in right-input order. Left rows without a
+/// match yield [`Joined::Left`] for [`JoinType::Left`] and
+/// [`JoinType::Outer`], and an outer join ends with [`Joined::Right`] for
+/// every right row that was never matched, in input order.
+///
+/// Memory is proportional to the right side, so put the smaller source
+/// there. Right rows are cloned into each pair they appear in, and left rows
+/// into all but their last.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::join::{hash_join, JoinType, Joined};
+///
+/// let left = vec![("a", 1), ("b", 2), ("c", 3)];
+/// let right = vec![("b", "x"), ("d", "y")];
+///
+/// let outer: Vec<_> = hash_join(left, right, |l| l.0, |r| r.0, JoinType::Outer).collect();
+/// assert_eq!(
+///     outer,
+///     vec![
+///         Joined::Left(("a", 1)),
+///         Joined::Both(("b", 2), ("b", "x")),
+///         Joined::Left(("c", 3)),
+///         Joined::Right(("d", "y")),
+///     ]
+/// );
+/// ```
+///
+/// Joined rows can feed a pipeline, which stops the join early:
+///
+/// ```
+/// use orlando_transducers::join::{hash_join, JoinType, Joined};
+/// use orlando_transducers::{to_vec, Map, Take, Transducer};
+///
+/// let names = vec![(1, "ada"), (2, "bo")];
+/// let ids = 1..;
+/// let pipeline = Map::new(|row: Joined<i32, (i32, &str)>| row.right().map(|r| r.1))
+///     .compose(Take::new(3));
+/// let joined = hash_join(ids, names, |id| *id, |n| n.0, JoinType::Left);
+/// assert_eq!(to_vec(&pipeline, joined), vec![Some("ada"), Some("bo"), None]);
+/// ```
+#[cfg(feature = "std")]
+pub fn hash_join<L, R, K, IterL, IterR, FL, FR>(
+    left: IterL,
+    right: IterR,
+    left_key: FL,
+    right_key: FR,
+    join_type: JoinType,
+) -> HashJoin<IterL::IntoIter, R, K, FL>
+where
+    IterL: IntoIterator<Item = L>,
+    IterR: IntoIterator<Item = R>,
+    FL: Fn(&L) -> K,
+    FR: Fn(&R) -> K,
+    K: Eq + Hash,
+{
+    let mut rows = Vec::new();
+    let mut table: HashMap<K, Vec<usize>> = HashMap::new();
+    for row in right {
+        table.entry(right_key(&row)).or_default().push(rows.len());
+        rows.push((row, false));
+    }
+    HashJoin {
+        left: left.into_iter(),
+        left_key,
+        table,
+        rows,
+        join_type,
+        current: None,
+        unmatched: Vec::new().into_iter(),
+    }
+}
+
+/// Lazy join iterator returned by [`hash_join`].
+#[cfg(feature = "std")]
+pub struct HashJoin<I: Iterator, R, K, F> {
+    left: I,
+    left_key: F,
+    /// Indices into `rows` by key.
+    table: HashMap<K, Vec<usize>>,
+    /// Right rows in input order, with whether each has been matched.
+    rows: Vec<(R, bool)>,
+    join_type: JoinType,
+    /// The left row being matched, its key, and the next match to yield.
+    current: Option<(I::Item, K, usize)>,
+    /// Right rows left over at the end of an outer join.
+    unmatched: std::vec::IntoIter<(R, bool)>,
+}
+
+#[cfg(feature = "std")]
+impl<I, R, K, F> Iterator for HashJoin<I, R, K, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    R: Clone,
+    K: Eq + Hash,
+    F: Fn(&I::Item) -> K,
+{
+    type Item = Joined<I::Item, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((row, key, next)) = &mut self.current {
+                let matches = &self.table[key];
+                let index = matches[*next];
+                *next += 1;
+                let right = &mut self.rows[index];
+                right.1 = true;
+                let right = right.0.clone();
+                // The last match takes the left row rather than a clone
+                if *next == matches.len() {
+                    let (row, _, _) = self.current.take().expect("matching a left row");
+                    return Some(Joined::Both(row, right));
+                }
+                return Some(Joined::Both(row.clone(), right));
+            }
+
+            let Some(row) = self.left.next() else { break };
+            let key = (self.left_key)(&row);
+            if self.table.contains_key(&key) {
+                self.current = Some((row, key, 0));
+            } else if self.join_type != JoinType::Inner {
+                return Some(Joined::Left(row));
+            }
+        }
+
+        if self.join_type == JoinType::Outer && !self.rows.is_empty() {
+            self.table.clear();
+            self.unmatched = std::mem::take(&mut self.rows).into_iter();
+        }
+        self.unmatched
+            .find(|(_, matched)| !matched)
+            .map(|(row, _)| Joined::Right(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.join_type {
+            JoinType::Inner => (0, None),
+            _ => (self.left.size_hint().0, None),
+        }
+    }
+}
+
+/// Join two sources sorted by key, walking both in step (helper function,
+/// not a transducer).
+///
+/// Yields the same rows as [`hash_join`] but in key order, with unmatched
+/// right rows of an outer join in place rather than at the end. Neither
+/// side is read ahead further than the current key, so memory stays
+/// constant apart from the right rows sharing a key (which are paired with
+/// each left row of that key), and both sources can be unbounded.
+///
+/// Each source must already be sorted by its key in ascending order;
+/// otherwise rows may go unmatched, though every row is still read.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::join::{merge_join, JoinType, Joined};
+///
+/// let left = vec![(1, 'a'), (2, 'b'), (2, 'c'), (5, 'd')];
+/// let right = vec![(2, "x"), (2, "y"), (3, "z")];
+///
+/// let rows: Vec<_> = merge_join(left, right, |l| l.0, |r| r.0, JoinType::Outer)
+///     .map(|row| match row {
+///         Joined::Both(l, r) => format!("{}{}", l.1, r.1),
+///         Joined::Left(l) => format!("{}-", l.1),
+///         Joined::Right(r) => format!("-{}", r.1),
+///     })
+///     .collect();
+/// assert_eq!(rows, vec!["a-", "bx", "by", "cx", "cy", "-z", "d-"]);
+/// ```
+///
+/// Sorted sources can be unbounded, with a pipeline deciding when to stop:
+///
+/// ```
+/// use orlando_transducers::join::{merge_join, JoinType, Joined};
+/// use orlando_transducers::{to_vec, Map, Take, Transducer};
+///
+/// let multiples_of_two = (0..).step_by(2);
+/// let multiples_of_three = (0..).step_by(3);
+/// let pipeline = Map::new(|row: Joined<u64, u64>| row.both().unwrap().0).compose(Take::new(4));
+/// let joined = merge_join(multiples_of_two, multiples_of_three, |l| *l, |r| *r, JoinType::Inner);
+/// assert_eq!(to_vec(&pipeline, joined), vec![0, 6, 12, 18]);
+/// ```
+pub fn merge_join<L, R, K, IterL, IterR, FL, FR>(
+    left: IterL,
+    right: IterR,
+    left_key: FL,
+    right_key: FR,
+    join_type: JoinType,
+) -> MergeJoin<IterL::IntoIter, IterR::IntoIter, K, FL, FR>
+where
+    IterL: IntoIterator<Item = L>,
+    IterR: IntoIterator<Item = R>,
+    FL: Fn(&L) -> K,
+    FR: Fn(&R) -> K,
+    K: Ord,
+{
+    MergeJoin {
+        left: left.into_iter(),
+        right: right.into_iter().peekable(),
+        left_key,
+        right_key,
+        join_type,
+        pending: None,
+        group: Vec::new(),
+        group_key: None,
+        group_matched: false,
+        left_done: false,
+        out: VecDeque::new(),
+    }
+}
+
+/// Lazy join iterator returned by [`merge_join`].
+pub struct MergeJoin<IL: Iterator, IR: Iterator, K, FL, FR> {
+    left: IL,
+    right: Peekable<IR>,
+    left_key: FL,
+    right_key: FR,
+    join_type: JoinType,
+    /// The next left row and its key, waiting for the right side to reach it.
+    pending: Option<(IL::Item, K)>,
+    /// The right rows with the current key, and whether any left row matched.
+    group: Vec<IR::Item>,
+    group_key: Option<K>,
+    group_matched: bool,
+    left_done: bool,
+    /// Rows ready to yield: the pairs for one left row, or a flushed group.
+    out: VecDeque<Joined<IL::Item, IR::Item>>,
+}
+
+impl<IL, IR, K, FL, FR> MergeJoin<IL, IR, K, FL, FR>
+where
+    IL: Iterator,
+    IR: Iterator,
+    IL::Item: Clone,
+    IR::Item: Clone,
+    K: Ord,
+    FL: Fn(&IL::Item) -> K,
+    FR: Fn(&IR::Item) -> K,
+{
+    /// Drop the current group, yielding its rows if an outer join never
+    /// matched them.
+    fn flush_group(&mut self) {
+        self.group_key = None;
+        if self.join_type == JoinType::Outer && !self.group_matched {
+            self.out.extend(self.group.drain(..).map(Joined::Right));
+        } else {
+            self.group.clear();
+        }
+    }
+
+    /// Pair a left row with every row of the current group.
+    fn pair(&mut self, row: IL::Item) {
+        self.group_matched = true;
+        let (last, rest) = self.group.split_last().expect("groups are never empty");
+        for right in rest {
+            self.out.push_back(Joined::Both(row.clone(), right.clone()));
+        }
+        self.out.push_back(Joined::Both(row, last.clone()));
+    }
+
+    /// Advance by one left row, or one right row once the left side has
+    /// run out. Returns `false` when the join is finished.
+    fn step(&mut self) -> bool {
+        if self.left_done {
+            self.flush_group();
+            let row = match self.join_type {
+                JoinType::Outer => self.right.next(),
+                _ => None,
+            };
+            return match row {
+                Some(row) => {
+                    self.out.push_back(Joined::Right(row));
+                    true
+                }
+                None => !self.out.is_empty(),
+            };
+        }
+
+        let Some((_, key)) = &self.pending else {
+            match self.left.next() {
+                Some(row) => {
+                    let key = (self.left_key)(&row);
+                    self.pending = Some((row, key));
+                }
+                None => self.left_done = true,
+            }
+            return true;
+        };
+
+        let next = match &self.group_key {
+            Some(group_key) if group_key == key => Next::Pair,
+            Some(_) => Next::Flush,
+            None => match self.right.peek() {
+                Some(right) => match (self.right_key)(right).cmp(key) {
+                    Ordering::Less => Next::SkipRight,
+                    Ordering::Equal => Next::Group,
+                    Ordering::Greater => Next::Unmatched,
+                },
+                None => Next::Unmatched,
+            },
+        };
+        match next {
+            Next::Flush => self.flush_group(),
+            Next::SkipRight => {
+                let row = self.right.next().expect("a peeked right row");
+                if self.join_type == JoinType::Outer {
+                    self.out.push_back(Joined::Right(row));
+                }
+            }
+            Next::Group => {
+                let key = &self.pending.as_ref().expect("a pending left row").1;
+                while let Some(row) = self.right.next_if(|right| (self.right_key)(right) == *key) {
+                    self.group.push(row);
+                }
+                let (row, key) = self.pending.take().expect("a pending left row");
+                self.group_key = Some(key);
+                self.group_matched = false;
+                self.pair(row);
+            }
+            Next::Pair => {
+                let (row, _) = self.pending.take().expect("a pending left row");
+                self.pair(row);
+            }
+            Next::Unmatched => {
+                let (row, _) = self.pending.take().expect("a pending left row");
+                if self.join_type != JoinType::Inner {
+                    self.out.push_back(Joined::Left(row));
+                }
+            }
+        }
+        true
+    }
+}
+
+/// What [`MergeJoin::step`] does with the pending left row.
+enum Next {
+    /// Pair it with the current group, which has its key.
+    Pair,
+    /// Drop the current group, which has a smaller key.
+    Flush,
+    /// Pass a right row with a smaller key.
+    SkipRight,
+    /// Read the right rows with its key into a new group, and pair it.
+    Group,
+    /// Yield it without a match.
+    Unmatched,
+}
+
+impl<IL, IR, K, FL, FR> Iterator for MergeJoin<IL, IR, K, FL, FR>
+where
+    IL: Iterator,
+    IR: Iterator,
+    IL::Item: Clone,
+    IR::Item: Clone,
+    K: Ord,
+    FL: Fn(&IL::Item) -> K,
+    FR: Fn(&IR::Item) -> K,
+{
+    type Item = Joined<IL::Item, IR::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.out.pop_front() {
+                return Some(row);
+            }
+            if !self.step() {
+                return None;
+            }
+        }
+    }
+}
+
+/// Pair each row of one time-sorted source with the latest row of another
+/// at or before it (helper function, not a transducer).
+///
+/// Yields every left row with the right row whose timestamp is the latest
+/// not after its own, provided that is no more than `tolerance` earlier,
+/// and `None` otherwise — the join for aligning trades with the quote in
+/// force, or metrics with the last deployment. Pass `u64::MAX` as the
+/// tolerance to accept any earlier row. Of right rows sharing a timestamp,
+/// the last is used.
+///
+/// Both sources must be sorted by timestamp. They are walked in step,
+/// keeping only the latest right row, so either can be unbounded. A left
+/// row older than a right row already read goes unmatched.
+/// Timestamps are `u64`s in the sources' own unit, as in
+/// [`window`](crate::window).
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::join::asof_join;
+///
+/// // (time, price) trades and (time, bid) quotes
+/// let trades = vec![(10, 100.5), (25, 101.0), (90, 99.0)];
+/// let quotes = vec![(5, 100.0), (20, 100.8), (30, 101.2)];
+///
+/// let aligned: Vec<_> = asof_join(trades, quotes, |t| t.0, |q| q.0, 30)
+///     .map(|(trade, quote)| (trade.0, quote.map(|q| q.1)))
+///     .collect();
+/// // The last quote is too old for the trade at 90
+/// assert_eq!(aligned, vec![(10, Some(100.0)), (25, Some(100.8)), (90, None)]);
+/// ```
+pub fn asof_join<L, R, IterL, IterR, FL, FR>(
+    left: IterL,
+    right: IterR,
+    left_ts: FL,
+    right_ts: FR,
+    tolerance: u64,
+) -> AsofJoin<IterL::IntoIter, IterR::IntoIter, FL, FR>
+where
+    IterL: IntoIterator<Item = L>,
+    IterR: IntoIterator<Item = R>,
+    FL: Fn(&L) -> u64,
+    FR: Fn(&R) -> u64,
+{
+    AsofJoin {
+        left: left.into_iter(),
+        right: right.into_iter().peekable(),
+        left_ts,
+        right_ts,
+        tolerance,
+        latest: None,
+    }
+}
+
+/// Lazy join iterator returned by [`asof_join`].
+pub struct AsofJoin<IL, IR: Iterator, FL, FR> {
+    left: IL,
+    right: Peekable<IR>,
+    left_ts: FL,
+    right_ts: FR,
+    tolerance: u64,
+    /// The latest right row read so far, with its timestamp.
+    latest: Option<(u64, IR::Item)>,
+}
+
+impl<IL, IR, FL, FR> Iterator for AsofJoin<IL, IR, FL, FR>
+where
+    IL: Iterator,
+    IR: Iterator,
+    IR::Item: Clone,
+    FL: Fn(&IL::Item) -> u64,
+    FR: Fn(&IR::Item) -> u64,
+{
+    type Item = (IL::Item, Option<IR::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.left.next()?;
+        let ts = (self.left_ts)(&row);
+        while let Some(right) = self.right.next_if(|right| (self.right_ts)(right) <= ts) {
+            self.latest = Some(((self.right_ts)(&right), right));
+        }
+        let matched = match &self.latest {
+            Some((right_ts, right))
+                if ts
+                    .checked_sub(*right_ts)
+                    .is_some_and(|age| age <= self.tolerance) =>
+            {
+                Some(right.clone())
+            }
+            _ => None,
+        };
+        Some((row, matched))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.left.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_join_duplicates_and_join_types() {
+        let left = vec![(1, 'a'), (2, 'b'), (1, 'c'), (4, 'd')];
+        let right = vec![(1, 10), (3, 30), (1, 11), (2, 20), (5, 50)];
+        let join = |join_type| {
+            hash_join(left.clone(), right.clone(), |l| l.0, |r| r.0, join_type)
+                .map(|row| {
+                    let (l, r) = row.into_options();
+                    (l.map(|l| l.1), r.map(|r| r.1))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let inner = vec![
+            (Some('a'), Some(10)),
+            (Some('a'), Some(11)),
+            (Some('b'), Some(20)),
+            (Some('c'), Some(10)),
+            (Some('c'), Some(11)),
+        ];
+        assert_eq!(join(JoinType::Inner), inner);
+
+        let mut with_left = inner.clone();
+        with_left.push((Some('d'), None));
+        assert_eq!(join(JoinType::Left), with_left);
+
+        let mut outer = with_left;
+        outer.extend([(None, Some(30)), (None, Some(50))]);
+        assert_eq!(join(JoinType::Outer), outer);
+
+        // Empty sides
+        let none: Vec<(i32, char)> = Vec::new();
+        let all_right: Vec<_> = hash_join(none, right.clone(), |l| l.0, |r| r.0, JoinType::Outer)
+            .filter_map(|row| row.right().map(|r| r.1))
+            .collect();
+        assert_eq!(all_right, vec![10, 30, 11, 20, 50]);
+        assert_eq!(
+            hash_join(
+                left,
+                Vec::<(i32, i32)>::new(),
+                |l| l.0,
+                |r| r.0,
+                JoinType::Inner
+            )
+            .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_joined_accessors() {
+        let both: Joined<i32, &str> = Joined::Both(1, "x");
+        assert_eq!((both.left(), both.right()), (Some(&1), Some(&"x")));
+        assert_eq!(both.both(), Some((1, "x")));
+        assert_eq!(Joined::<i32, &str>::Right("y").both(), None);
+        assert_eq!(Joined::<i32, &str>::Left(2).into_options(), (Some(2), None));
+        assert_eq!(JoinType::default(), JoinType::Inner);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_merge_join_agrees_with_hash_join() {
+        // Sorted sides with duplicate keys, gaps and keys on one side only
+        let left: Vec<(u8, usize)> = [0, 1, 1, 3, 4, 4, 4, 7, 9, 9]
+            .into_iter()
+            .zip(0..)
+            .collect();
+        let right: Vec<(u8, usize)> = [1, 1, 2, 4, 5, 5, 7, 7, 10]
+            .into_iter()
+            .zip(100..)
+            .collect();
+
+        for join_type in [JoinType::Inner, JoinType::Left, JoinType::Outer] {
+            let sorted = |rows: Vec<Joined<(u8, usize), (u8, usize)>>| {
+                let mut rows: Vec<_> = rows.into_iter().map(Joined::into_options).collect();
+                rows.sort();
+                rows
+            };
+            let merged: Vec<_> =
+                merge_join(left.clone(), right.clone(), |l| l.0, |r| r.0, join_type).collect();
+            let hashed: Vec<_> =
+                hash_join(left.clone(), right.clone(), |l| l.0, |r| r.0, join_type).collect();
+            assert_eq!(sorted(merged.clone()), sorted(hashed), "{join_type:?}");
+
+            // In key order
+            let keys: Vec<u8> = merged
+                .iter()
+                .map(|row| row.left().or(row.right()).unwrap().0)
+                .collect();
+            assert!(keys.windows(2).all(|w| w[0] <= w[1]), "{join_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_merge_join_empty_sides() {
+        let empty: Vec<i32> = Vec::new();
+        let rows = |left: &[i32], right: &[i32], join_type| {
+            merge_join(left.to_vec(), right.to_vec(), |l| *l, |r| *r, join_type)
+                .map(Joined::into_options)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rows(&empty, &[1, 2], JoinType::Outer),
+            vec![(None, Some(1)), (None, Some(2))]
+        );
+        assert_eq!(rows(&empty, &[1, 2], JoinType::Left), vec![]);
+        assert_eq!(rows(&[3], &empty, JoinType::Left), vec![(Some(3), None)]);
+        assert_eq!(rows(&[3], &empty, JoinType::Inner), vec![]);
+    }
+
+    #[test]
+    fn test_asof_join_matches_latest_within_tolerance() {
+        let left = vec![0, 5, 5, 12, 20, 41, 100];
+        let right = vec![
+            (3, 'a'),
+            (5, 'b'),
+            (5, 'c'),
+            (10, 'd'),
+            (40, 'e'),
+            (200, 'f'),
+        ];
+        let matched = |tolerance| {
+            asof_join(left.clone(), right.clone(), |l| *l, |r| r.0, tolerance)
+                .map(|(l, r)| (l, r.map(|r| r.1)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            matched(u64::MAX),
+            vec![
+                (0, None),
+                (5, Some('c')),
+                (5, Some('c')),
+                (12, Some('d')),
+                (20, Some('d')),
+                (41, Some('e')),
+                (100, Some('e')),
+            ]
+        );
+        // Exactly at the tolerance still matches
+        assert_eq!(
+            matched(2),
+            vec![
+                (0, None),
+                (5, Some('c')),
+                (5, Some('c')),
+                (12, Some('d')),
+                (20, None),
+                (41, Some('e')),
+                (100, None),
+            ]
+        );
+
+        // Unbounded right side, bounded left
+        let ticks = asof_join(vec![7, 15], (0..).step_by(4), |l| *l, |r| *r, 0);
+        assert_eq!(ticks.collect::<Vec<_>>(), vec![(7, None), (15, None)]);
+        let ticks = asof_join(vec![7, 15], (0..).step_by(4), |l| *l, |r| *r, 3);
+        assert_eq!(ticks.size_hint(), (2, Some(2)));
+        assert_eq!(
+            ticks.collect::<Vec<_>>(),
+            vec![(7, Some(4)), (15, Some(12))]
+        );
+    }
+
+    #[test]
+    fn test_asof_join_out_of_order_left_goes_unmatched() {
+        // 5 arrives after the right row at 8 was read for 10; it must not
+        // underflow or pick up a row from its future.
+        let joined: Vec<_> =
+            asof_join(vec![10u64, 5, 9], vec![8u64], |l| *l, |r| *r, u64::MAX).collect();
+        assert_eq!(joined, vec![(10, Some(8)), (5, None), (9, Some(8))]);
+    }
+}
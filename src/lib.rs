@@ -69,6 +69,13 @@
 //! // result: [6, 12, 18, 24, 30]
 //! ```
 //!
+//! ## `no_std`
+//!
+//! With `default-features = false` the crate is `no_std + alloc`: transducers,
+//! transforms and the collectors that don't need hashing, randomness or
+//! floating-point math run on embedded targets. The rest is behind the default
+//! `std` feature.
+//!
 //! ## Performance
 //!
 //! Orlando leverages:
@@ -79,67 +86,167 @@
 //!
 //! Benchmarks show 3-5x performance improvement over pure JavaScript array chaining.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod aggregate;
+pub mod budget;
 pub mod collectors;
-pub mod geometric_optics;
+pub mod debug;
+pub mod either;
+pub mod explain;
+pub mod instrument;
 pub mod iter_ext;
+pub mod join;
 pub mod logic;
+pub mod runner;
+pub mod step;
+pub mod transducer;
+pub mod transforms;
+pub mod window;
+
+#[cfg(feature = "std")]
+pub mod geometric_optics;
+
+#[cfg(feature = "std")]
 pub mod optics;
+
+#[cfg(feature = "std")]
 pub mod profunctor;
+
+#[cfg(feature = "std")]
 pub mod signal;
+
+#[cfg(feature = "std")]
 pub mod simd;
-pub mod step;
+
+#[cfg(feature = "std")]
+pub mod sources;
+
+#[cfg(feature = "std")]
 pub mod stream;
-pub mod transducer;
-pub mod transforms;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(feature = "std")]
+pub mod timeseries;
+
+#[cfg(feature = "json")]
+pub mod plan;
+
+#[cfg(feature = "json")]
+pub mod records;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "std"))]
+pub mod channel;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "spill"))]
+pub mod external;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "std"))]
+pub mod io;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "std"))]
+pub mod memory;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "std"))]
+pub mod parallel;
+
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub mod pipeline;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+pub mod error_wasm;
+
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub mod geometric_optics_wasm;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "webgpu"))]
+pub mod gpu_wasm;
+
+#[cfg(all(target_arch = "wasm32", feature = "nodejs"))]
+pub mod node_wasm;
+
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub mod optics_wasm;
 
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+pub mod sources_wasm;
+
 // Re-export main types for convenience
+pub use explain::{Explain, StageInfo};
 pub use step::{cont, is_stopped, stop, unwrap_step, Step};
 pub use transducer::{Compose, Identity, Transducer};
 
 // Re-export common transforms
 pub use transforms::{
-    Aperture, Chunk, Drop, DropWhile, Filter, FlatMap, Interpose, Map, Reject, RepeatEach, Scan,
-    Take, TakeWhile, Tap, Unique, UniqueBy,
+    Aperture, Chunk, Drop, DropWhile, EveryNth, Filter, FlatMap, Interpose, Map, Reject,
+    RepeatEach, Scan, SplitBytes, Take, TakeWhile, Tap, Unique, Validate, ValidationError,
 };
+#[cfg(feature = "std")]
+pub use transforms::{FilterBy, MapOver, UniqueBy, Zoom};
+
+// Re-export event-time windowing
+pub use window::{LatePolicy, SessionWindow, Window, WindowByTime};
+
+// Re-export streaming time-series metrics
+#[cfg(feature = "std")]
+pub use timeseries::{AnomalyZScore, Decayed, Derivative, Ewma, Fill, FillGaps};
+
+// Re-export joins
+#[cfg(feature = "std")]
+pub use join::hash_join;
+pub use join::{asof_join, merge_join, JoinType, Joined};
+
+// Re-export single-pass multi-collector entry point
+pub use aggregate::{collect_many, Aggregator};
+#[cfg(feature = "std")]
+pub use aggregate::{pivot, rollup, Pivot, Rollup};
 
 // Re-export collectors
 pub use collectors::{
-    cartesian_product, contains, count, cycle, difference, drop_last, every, find, first,
-    frequencies, group_by, intersection, last, max, max_by, mean, median, merge, min, min_by, mode,
-    none, partition, partition_by, product, quantile, range, reduce, repeat, reservoir_sample,
-    reverse, some, sort_by, sort_with, std_dev, sum, symmetric_difference, take_last, to_vec,
-    top_k, unfold, union, variance, zip, zip_longest, zip_with,
+    all_equal, cartesian_product, contains, count, cycle, drop_last, every, find, find_last, first,
+    index_of, is_monotonic, is_sorted, is_sorted_by, last, linspace, max, max_by, mean, median,
+    merge, merge_sorted, min, min_by, none, nth, partition, partition_by, partition_map,
+    partition_valid, position_last, product, quantile, range, reduce, repeat, reverse, some,
+    sort_by, sort_with, sum, take_last, to_vec, to_vec_with_capacity, top_k, unfold, unzip, unzip3,
+    variance, zip, zip_longest, zip_with,
 };
+#[cfg(feature = "std")]
+pub use collectors::{
+    difference, distinct, distinct_count, frequencies, group_by, group_by_ordered, intersection,
+    mode, reservoir_sample, std_dev, symmetric_difference, union,
+};
+
+// Re-export the two-way sum type
+pub use either::Either;
 
 // Re-export logic functions and conditional transducers
-pub use logic::{all_pass, any_pass, both, complement, either, IfElse, Unless, When};
+pub use logic::{
+    all_pass, all_pass_tuple, any_pass, any_pass_tuple, both, complement, either, on_field,
+    zip_preds, Cond, IfElse, IfElseEither, Unless, When,
+};
 
 // Re-export optics
-pub use optics::{ComposedLens, Fold, Iso, Lens, Optional, Prism, Traversal};
+#[cfg(feature = "std")]
+pub use optics::{ComposedLens, Fold, Getter, Iso, Lens, Optional, Prism, SyncLens, Traversal};
 
-// Re-export additional optic types from Karpal
-pub use karpal_optics::{Getter, Review, Setter};
+// Re-export additional optic types from Karpal; its getter is renamed so the
+// root `Getter` is the composable one from `optics`
+#[cfg(feature = "std")]
+pub use karpal_optics::{Getter as ProfunctorGetter, Review, Setter};
 
 // Re-export geometric optics
+#[cfg(feature = "std")]
 pub use geometric_optics::{
     blade_grade, blades_at_grade_count, component_get, component_set, grade_extract, grade_indices,
     grade_involution, grade_mask, grade_project, grade_project_max, has_grade, is_pure_grade, norm,
     norm_squared, normalize,
 };
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub use pipeline::Pipeline;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub use geometric_optics_wasm::{
     blade_grade as wasm_blade_grade, blades_at_grade_count as wasm_blades_at_grade_count,
     component_get as wasm_component_get, component_set as wasm_component_set,
@@ -150,21 +257,34 @@ pub use geometric_optics_wasm::{
     mv_normalize, mv_reverse,
 };
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+pub use error_wasm::is_orlando_error;
+
+#[cfg(all(target_arch = "wasm32", feature = "webgpu"))]
+pub use gpu_wasm::gpu_available;
+
+#[cfg(all(target_arch = "wasm32", feature = "nodejs"))]
+pub use node_wasm::{from_node_readable, to_node_writable};
+
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 pub use optics_wasm::{
-    fold, iso, lens, lens_path, optional, prism, traversal, JsFold, JsIso, JsLens, JsOptional,
-    JsPrism, JsTraversal,
+    each, fold, iso, lens, lens_from, lens_path, optional, prism, traversal, JsFold, JsIso, JsLens,
+    JsOptional, JsPrism, JsTraversal,
 };
 
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
+pub use sources_wasm::json_lines;
+
 // WASM initialization
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "std"))]
 use wasm_bindgen::prelude::*;
 
-#[cfg(all(target_arch = "wasm32", not(test)))]
+#[cfg(all(target_arch = "wasm32", feature = "std", not(test)))]
 #[wasm_bindgen(start)]
 pub fn main() {
-    // WASM initialization
-    // Future: Add console_error_panic_hook feature for better debugging
+    // WASM initialization: route Rust panics to console.error with a stack trace
+    #[cfg(feature = "panic-hook")]
+    console_error_panic_hook::set_once();
 }
 
 #[cfg(test)]
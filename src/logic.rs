@@ -3,10 +3,16 @@
 //! This module provides utilities for combining predicates and creating conditional
 //! transformations, inspired by Ramda's logic functions.
 
+use crate::either::Either;
+use crate::explain::{Explain, StageInfo};
+#[cfg(feature = "std")]
+use crate::optics::{Getter, Lens};
 use crate::step::Step;
 use crate::transducer::Transducer;
-use std::marker::PhantomData;
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 // ========================================
 // Type Aliases
@@ -18,6 +24,13 @@ pub type BoxedPredicate<T> = Box<dyn Fn(&T) -> bool>;
 /// Type alias for a vector of boxed predicates
 pub type PredicateVec<T> = Vec<BoxedPredicate<T>>;
 
+/// Type alias for a boxed transform function
+pub type BoxedTransform<T> = Box<dyn Fn(T) -> T>;
+
+/// Type alias for one branch of a [`Cond`]: a predicate and the transform
+/// it selects
+pub type CondBranch<T> = (BoxedPredicate<T>, BoxedTransform<T>);
+
 // ========================================
 // Predicate Combinators
 // ========================================
@@ -95,11 +108,65 @@ where
     move |x| !pred(x)
 }
 
+/// A set of predicates over `T` that [`all_pass_tuple`] and
+/// [`any_pass_tuple`] can combine.
+///
+/// Implemented for [`PredicateVec`], for predicates assembled at runtime,
+/// and for tuples of two to eight predicates. A tuple keeps each closure's
+/// own type, so the combined predicate is monomorphized and inlined instead
+/// of calling through a box per predicate.
+pub trait Predicates<T> {
+    /// Whether every predicate holds, stopping at the first that doesn't.
+    fn all(&self, x: &T) -> bool;
+
+    /// Whether any predicate holds, stopping at the first that does.
+    fn any(&self, x: &T) -> bool;
+}
+
+impl<T> Predicates<T> for PredicateVec<T> {
+    fn all(&self, x: &T) -> bool {
+        self.iter().all(|pred| pred(x))
+    }
+
+    fn any(&self, x: &T) -> bool {
+        self.iter().any(|pred| pred(x))
+    }
+}
+
+macro_rules! tuple_predicates {
+    ($($P:ident $i:tt),+) => {
+        impl<T, $($P),+> Predicates<T> for ($($P,)+)
+        where
+            $($P: Fn(&T) -> bool),+
+        {
+            #[inline(always)]
+            fn all(&self, x: &T) -> bool {
+                true $(&& (self.$i)(x))+
+            }
+
+            #[inline(always)]
+            fn any(&self, x: &T) -> bool {
+                false $(|| (self.$i)(x))+
+            }
+        }
+    };
+}
+
+tuple_predicates!(P0 0, P1 1);
+tuple_predicates!(P0 0, P1 1, P2 2);
+tuple_predicates!(P0 0, P1 1, P2 2, P3 3);
+tuple_predicates!(P0 0, P1 1, P2 2, P3 3, P4 4);
+tuple_predicates!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+tuple_predicates!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+tuple_predicates!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
 /// Combines multiple predicates with AND logic.
 ///
 /// Returns a new predicate that is true only when ALL input predicates are true.
 /// Short-circuits on the first false predicate.
 ///
+/// See [`all_pass_tuple`] for predicates known at compile time.
+///
 /// # Examples
 ///
 /// ```
@@ -121,11 +188,36 @@ pub fn all_pass<T>(predicates: PredicateVec<T>) -> impl Fn(&T) -> bool {
     move |x| predicates.iter().all(|pred| pred(x))
 }
 
+/// Like [`all_pass`], for a tuple of predicates (see [`Predicates`]).
+///
+/// The closures keep their own types, so the combined predicate is inlined
+/// rather than calling through a box per predicate.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::logic::all_pass_tuple;
+///
+/// let is_valid = all_pass_tuple((
+///     |x: &i32| *x > 0,
+///     |x: &i32| *x < 100,
+///     |x: &i32| x % 2 == 0,
+/// ));
+///
+/// assert!(is_valid(&50));
+/// assert!(!is_valid(&3));
+/// ```
+pub fn all_pass_tuple<T, P: Predicates<T>>(predicates: P) -> impl Fn(&T) -> bool {
+    move |x| predicates.all(x)
+}
+
 /// Combines multiple predicates with OR logic.
 ///
 /// Returns a new predicate that is true when ANY input predicate is true.
 /// Short-circuits on the first true predicate.
 ///
+/// See [`any_pass_tuple`] for predicates known at compile time.
+///
 /// # Examples
 ///
 /// ```
@@ -148,6 +240,108 @@ pub fn any_pass<T>(predicates: PredicateVec<T>) -> impl Fn(&T) -> bool {
     move |x| predicates.iter().any(|pred| pred(x))
 }
 
+/// Like [`any_pass`], for a tuple of predicates (see [`Predicates`]).
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::logic::any_pass_tuple;
+///
+/// let is_round = any_pass_tuple((|x: &i32| *x == 0, |x: &i32| x % 10 == 0));
+///
+/// assert!(is_round(&50));
+/// assert!(!is_round(&7));
+/// ```
+pub fn any_pass_tuple<T, P: Predicates<T>>(predicates: P) -> impl Fn(&T) -> bool {
+    move |x| predicates.any(x)
+}
+
+/// Something that reads a field out of a structure: a getter closure, a
+/// [`Lens`] or a [`Getter`].
+pub trait Field<S, A> {
+    /// Read the field from `source`.
+    fn field(&self, source: &S) -> A;
+}
+
+impl<S, A, G> Field<S, A> for G
+where
+    G: Fn(&S) -> A,
+{
+    fn field(&self, source: &S) -> A {
+        self(source)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Clone, A: Clone> Field<S, A> for Lens<S, A> {
+    fn field(&self, source: &S) -> A {
+        self.get(source)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Clone, A: Clone> Field<S, A> for Getter<S, A> {
+    fn field(&self, source: &S) -> A {
+        self.get(source)
+    }
+}
+
+/// Lifts a predicate on a field to a predicate on the whole structure.
+///
+/// The field is read with a getter closure, a [`Lens`] or a [`Getter`], so
+/// `Filter`, `When` and `Unless` can test records without writing the
+/// projection by hand each time.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::logic::{both, on_field};
+/// use orlando_transducers::optics::Lens;
+///
+/// #[derive(Clone)]
+/// struct User { name: String, age: u32 }
+///
+/// let age = Lens::new(|u: &User| u.age, |u: &User, age| User { age, ..u.clone() });
+/// let is_adult = on_field(age, |age: &u32| *age >= 18);
+/// let is_named_a = on_field(|u: &User| u.name.clone(), |name: &String| name.starts_with('A'));
+/// let check = both(is_adult, is_named_a);
+///
+/// assert!(check(&User { name: "Ada".into(), age: 36 }));
+/// assert!(!check(&User { name: "Ada".into(), age: 12 }));
+/// assert!(!check(&User { name: "Bob".into(), age: 36 }));
+/// ```
+pub fn on_field<S, A, G, P>(getter: G, pred: P) -> impl Fn(&S) -> bool
+where
+    G: Field<S, A>,
+    P: Fn(&A) -> bool,
+{
+    move |x| pred(&getter.field(x))
+}
+
+/// Combines a predicate per position into a predicate on pairs.
+///
+/// The result is true when `pred1` holds for the first element and `pred2`
+/// for the second, checking the second only if the first passes.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::logic::zip_preds;
+///
+/// let valid_entry = zip_preds(|k: &String| !k.is_empty(), |v: &i32| *v >= 0);
+///
+/// assert!(valid_entry(&("count".to_string(), 3)));
+/// assert!(!valid_entry(&("".to_string(), 3)));
+/// assert!(!valid_entry(&("count".to_string(), -1)));
+/// ```
+pub fn zip_preds<A, B, P1, P2>(pred1: P1, pred2: P2) -> impl Fn(&(A, B)) -> bool
+where
+    P1: Fn(&A) -> bool,
+    P2: Fn(&B) -> bool,
+{
+    move |(a, b)| pred1(a) && pred2(b)
+}
+
 // ========================================
 // Conditional Transducers
 // ========================================
@@ -209,6 +403,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("when"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 /// Conditional transformation - applies transform only when predicate is false.
@@ -269,6 +471,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("unless"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 /// Branch on condition - applies different transforms based on predicate.
@@ -338,6 +548,181 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("if_else"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// Branch on condition into different output types.
+///
+/// Like [`IfElse`], but `on_true` and `on_false` may return different types:
+/// their results are wrapped in [`Either::Left`] and [`Either::Right`]. Pass
+/// the output to [`partition_map`](crate::collectors::partition_map) with
+/// `std::convert::identity` to collect each branch separately.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::collectors::partition_map;
+/// use orlando_transducers::logic::IfElseEither;
+///
+/// let parse = IfElseEither::new(
+///     |s: &&str| s.chars().all(|c| c.is_ascii_digit()),
+///     |s: &str| s.parse::<u32>().unwrap(),
+///     |s: &str| format!("skipped {}", s),
+/// );
+/// let (numbers, skipped) = partition_map(&parse, vec!["12", "n/a", "7"], std::convert::identity);
+///
+/// assert_eq!(numbers, vec![12, 7]);
+/// assert_eq!(skipped, vec!["skipped n/a"]);
+/// ```
+pub struct IfElseEither<P, F1, F2, T, A, B> {
+    predicate: Rc<P>,
+    on_true: Rc<F1>,
+    on_false: Rc<F2>,
+    _phantom: PhantomData<(T, A, B)>,
+}
+
+impl<P, F1, F2, T, A, B> IfElseEither<P, F1, F2, T, A, B>
+where
+    P: Fn(&T) -> bool,
+    F1: Fn(T) -> A,
+    F2: Fn(T) -> B,
+{
+    pub fn new(predicate: P, on_true: F1, on_false: F2) -> Self {
+        IfElseEither {
+            predicate: Rc::new(predicate),
+            on_true: Rc::new(on_true),
+            on_false: Rc::new(on_false),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, F1, F2, T, A, B> Transducer<T, Either<A, B>> for IfElseEither<P, F1, F2, T, A, B>
+where
+    P: Fn(&T) -> bool + 'static,
+    F1: Fn(T) -> A + 'static,
+    F2: Fn(T) -> B + 'static,
+    T: 'static,
+    A: 'static,
+    B: 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, Either<A, B>) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let predicate = Rc::clone(&self.predicate);
+        let on_true = Rc::clone(&self.on_true);
+        let on_false = Rc::clone(&self.on_false);
+
+        Box::new(move |acc, val| {
+            if predicate(&val) {
+                reducer(acc, Either::Left(on_true(val)))
+            } else {
+                reducer(acc, Either::Right(on_false(val)))
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("if_else_either"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// Builds a [`CondBranch`], boxing the predicate and transform.
+pub fn branch<T, P, F>(predicate: P, transform: F) -> CondBranch<T>
+where
+    P: Fn(&T) -> bool + 'static,
+    F: Fn(T) -> T + 'static,
+{
+    (Box::new(predicate), Box::new(transform))
+}
+
+/// Multi-way conditional transformation, like Ramda's `cond`.
+///
+/// Applies the transform of the first branch whose predicate matches, or
+/// `default` when none does. Replaces a chain of nested `IfElse`s with one
+/// stage.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::logic::{branch, Cond};
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let grade = Cond::new(
+///     vec![
+///         branch(|x: &i32| *x >= 90, |_| 4),
+///         branch(|x: &i32| *x >= 80, |_| 3),
+///         branch(|x: &i32| *x >= 70, |_| 2),
+///     ],
+///     |_| 0,
+/// );
+/// let result = to_vec(&grade, vec![95, 85, 72, 40]);
+/// assert_eq!(result, vec![4, 3, 2, 0]);
+/// ```
+pub struct Cond<F, T> {
+    branches: Rc<Vec<CondBranch<T>>>,
+    default: Rc<F>,
+}
+
+impl<F, T> Cond<F, T>
+where
+    F: Fn(T) -> T,
+{
+    pub fn new(branches: Vec<CondBranch<T>>, default: F) -> Self {
+        Cond {
+            branches: Rc::new(branches),
+            default: Rc::new(default),
+        }
+    }
+}
+
+impl<F, T> Transducer<T, T> for Cond<F, T>
+where
+    F: Fn(T) -> T + 'static,
+    T: 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, T) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let branches = Rc::clone(&self.branches);
+        let default = Rc::clone(&self.default);
+
+        Box::new(move |acc, val| {
+            let transform = branches
+                .iter()
+                .find(|(predicate, _)| predicate(&val))
+                .map(|(_, transform)| transform);
+            match transform {
+                Some(transform) => reducer(acc, transform(val)),
+                None => reducer(acc, default(val)),
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("cond"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 #[cfg(test)]
@@ -389,6 +774,14 @@ mod tests {
         assert!(is_valid(&50));
         assert!(!is_valid(&3));
         assert!(!is_valid(&150));
+
+        // Inline boxes coerce without an annotation
+        let in_range = all_pass(vec![
+            Box::new(|x: &i32| *x > 0),
+            Box::new(|x: &i32| *x < 10),
+        ]);
+        assert!(in_range(&5));
+        assert!(!in_range(&50));
     }
 
     #[test]
@@ -427,4 +820,98 @@ mod tests {
         let result = to_vec(&abs_with_sign, vec![-4, 3, -6, 5]);
         assert_eq!(result, vec![-2, 6, -3, 10]);
     }
+
+    #[test]
+    fn test_tuple_predicates_short_circuit() {
+        use core::cell::Cell;
+
+        let calls = Cell::new(0);
+        let counted = |x: &i32| {
+            calls.set(calls.get() + 1);
+            *x > 0
+        };
+        let is_valid = all_pass_tuple((|x: &i32| x % 2 == 0, counted, |x: &i32| *x < 100));
+        assert!(is_valid(&50));
+        assert!(!is_valid(&150));
+        assert!(!is_valid(&3));
+        assert_eq!(calls.get(), 2);
+
+        let is_special = any_pass_tuple((|x: &i32| *x == 0, |x: &i32| x % 10 == 0));
+        assert!(is_special(&0));
+        assert!(is_special(&50));
+        assert!(!is_special(&7));
+    }
+
+    #[test]
+    fn test_on_field_and_zip_preds_in_pipelines() {
+        use crate::transforms::Filter;
+
+        let total = Getter::new(|order: &(String, f64)| order.1);
+        let large = Filter::new(on_field(total, |t: &f64| *t > 10.0));
+        let orders = vec![("a".to_string(), 5.0), ("b".to_string(), 25.0)];
+        assert_eq!(to_vec(&large, orders), vec![("b".to_string(), 25.0)]);
+
+        let cap_first = When::new(
+            zip_preds(|x: &i32| *x > 100, |label: &&str| *label != "raw"),
+            |(_, label): (i32, &str)| (100, label),
+        );
+        let pairs = vec![(150, "scaled"), (150, "raw"), (50, "scaled")];
+        assert_eq!(
+            to_vec(&cap_first, pairs),
+            vec![(100, "scaled"), (150, "raw"), (50, "scaled")]
+        );
+    }
+
+    #[test]
+    fn test_if_else_either_composes() {
+        use crate::transforms::{Filter, Map};
+
+        let route = Map::new(|x: i32| x * 3)
+            .compose(IfElseEither::new(
+                |x: &i32| x % 2 == 0,
+                |x: i32| x as f64 / 2.0,
+                |x: i32| x.to_string(),
+            ))
+            .compose(Filter::new(|e: &Either<f64, String>| {
+                e.is_left() || e != &Either::Right("9".into())
+            }));
+
+        assert_eq!(
+            to_vec(&route, 1..=4),
+            vec![
+                Either::Right("3".to_string()),
+                Either::Left(3.0),
+                Either::Left(6.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cond_first_match_wins() {
+        let fizz_buzz = Cond::new(
+            vec![
+                branch(
+                    |x: &String| x.parse::<i32>().unwrap() % 15 == 0,
+                    |_| "FizzBuzz".into(),
+                ),
+                branch(
+                    |x: &String| x.parse::<i32>().unwrap() % 3 == 0,
+                    |_| "Fizz".into(),
+                ),
+                branch(
+                    |x: &String| x.parse::<i32>().unwrap() % 5 == 0,
+                    |_| "Buzz".into(),
+                ),
+            ],
+            |x| x,
+        );
+        let source: Vec<String> = [1, 3, 5, 15].iter().map(|n| n.to_string()).collect();
+        assert_eq!(
+            to_vec(&fizz_buzz, source),
+            vec!["1", "Fizz", "Buzz", "FizzBuzz"]
+        );
+
+        let no_branches = Cond::new(Vec::new(), |x: i32| x + 1);
+        assert_eq!(to_vec(&no_branches, vec![1, 2]), vec![2, 3]);
+    }
 }
@@ -0,0 +1,338 @@
+//! # Memory: Counting the allocations of a pipeline run
+//!
+//! [`measure_allocations`] runs a closure and reports how many allocations it
+//! made and how many bytes they took, so claims like "this pipeline makes no
+//! intermediate allocations" can be checked in a test or benchmark.
+//!
+//! Counting needs [`CountingAllocator`] installed as the global allocator. It
+//! is opt-in: add it to the binary, test or benchmark doing the measuring, not
+//! to a library.
+//!
+//! ```rust
+//! use orlando_transducers::memory::{measure_allocations, CountingAllocator};
+//! use orlando_transducers::{sum, to_vec, Filter, Map, Transducer};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+//!
+//! let pipeline = Map::new(|x: u64| x * 3).compose(Filter::new(|x: &u64| x % 2 == 0));
+//!
+//! // Folding to a number allocates only the composed reducer closures
+//! let (total, stats) = measure_allocations(|| sum(&pipeline, 0..100_000u64));
+//! assert!(stats.allocations < 10);
+//!
+//! // Collecting allocates for the Vec as it grows, and nothing per element
+//! let (evens, stats) = measure_allocations(|| to_vec(&pipeline, 0..100_000u64));
+//! assert_eq!(evens.len(), 50_000);
+//! assert!(stats.allocations < 40);
+//! # let _ = total;
+//! ```
+//!
+//! Only allocations made by the calling thread are counted, so measurements
+//! aren't disturbed by other threads (such as other tests running in
+//! parallel), but work the closure hands to other threads isn't counted
+//! either. Native targets only.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once a [`CountingAllocator`] has served an allocation.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            deallocations: Cell::new(0),
+            reallocations: Cell::new(0),
+            allocated: Cell::new(0),
+            freed: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// Running totals for one thread. Accessing them never allocates, so they can
+/// be updated from inside the allocator.
+struct Counters {
+    allocations: Cell<u64>,
+    deallocations: Cell<u64>,
+    reallocations: Cell<u64>,
+    allocated: Cell<u64>,
+    freed: Cell<u64>,
+    /// The highest value of `live()` since the innermost measurement started
+    peak: Cell<i64>,
+}
+
+impl Counters {
+    /// Bytes allocated minus bytes freed. Negative when the thread has freed
+    /// memory allocated by other threads.
+    fn live(&self) -> i64 {
+        self.allocated.get() as i64 - self.freed.get() as i64
+    }
+
+    fn allocated(&self, bytes: usize) {
+        self.allocated.set(self.allocated.get() + bytes as u64);
+        self.peak.set(self.peak.get().max(self.live()));
+    }
+
+    fn freed(&self, bytes: usize) {
+        self.freed.set(self.freed.get() + bytes as u64);
+    }
+}
+
+/// Update this thread's counters, unless its thread-local storage is already
+/// gone (while the thread shuts down).
+fn count(update: impl FnOnce(&Counters)) {
+    let _ = COUNTERS.try_with(update);
+}
+
+/// A global allocator that counts the allocations of each thread for
+/// [`measure_allocations`], delegating the allocations themselves to another
+/// allocator.
+///
+/// Install it in the binary doing the measuring:
+///
+/// ```rust
+/// use orlando_transducers::memory::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+/// ```
+///
+/// Counting adds a few thread-local updates to every allocation.
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl CountingAllocator<System> {
+    /// Count allocations served by the system allocator.
+    pub const fn system() -> Self {
+        CountingAllocator { inner: System }
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Count allocations served by `inner`, e.g. another global allocator.
+    pub const fn new(inner: A) -> Self {
+        CountingAllocator { inner }
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to `inner`, which upholds the
+// `GlobalAlloc` contract; the counting around it doesn't allocate.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            count(|c| {
+                c.allocations.set(c.allocations.get() + 1);
+                c.allocated(layout.size());
+            });
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(|c| {
+                c.allocations.set(c.allocations.get() + 1);
+                c.allocated(layout.size());
+            });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        count(|c| {
+            c.deallocations.set(c.deallocations.get() + 1);
+            c.freed(layout.size());
+        });
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(|c| {
+                c.reallocations.set(c.reallocations.get() + 1);
+                if new_size > layout.size() {
+                    c.allocated(new_size - layout.size());
+                } else {
+                    c.freed(layout.size() - new_size);
+                }
+            });
+        }
+        new_ptr
+    }
+}
+
+/// What a closure measured by [`measure_allocations`] allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// New allocations
+    pub allocations: u64,
+    /// Allocations freed, including ones made before the measurement
+    pub deallocations: u64,
+    /// Allocations resized in place or moved, such as a growing `Vec`
+    pub reallocations: u64,
+    /// Bytes allocated, counting growth by reallocation
+    pub bytes_allocated: u64,
+    /// Bytes freed, counting shrinking by reallocation
+    pub bytes_freed: u64,
+    /// The most bytes held at once beyond what was held when the measurement
+    /// started
+    pub peak_bytes: u64,
+}
+
+impl AllocationStats {
+    /// Whether nothing was allocated or grown.
+    pub fn is_allocation_free(&self) -> bool {
+        self.allocations == 0 && self.bytes_allocated == 0
+    }
+}
+
+/// Whether a [`CountingAllocator`] is the global allocator.
+pub fn is_installed() -> bool {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        // Allocate once so an installed allocator gets the chance to say so
+        drop(std::hint::black_box(Box::new(0u8)));
+    }
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Run `f` and report what it allocated on the calling thread.
+///
+/// Measurements can be nested.
+///
+/// # Panics
+///
+/// Panics if [`CountingAllocator`] is not the global allocator, since every
+/// count would silently be zero.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::memory::{measure_allocations, CountingAllocator};
+/// use orlando_transducers::{reduce, Map};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+///
+/// let pipeline = Map::new(|x: u32| x as u64);
+/// let (sum, stats) = measure_allocations(|| {
+///     reduce(&pipeline, 0..1000u32, 0u64, |acc, x| orlando_transducers::cont(acc + x))
+/// });
+/// assert_eq!(sum, 499_500);
+/// assert!(stats.allocations <= 2); // the boxed reducer, not one per element
+/// ```
+pub fn measure_allocations<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    assert!(
+        is_installed(),
+        "measure_allocations requires CountingAllocator as the #[global_allocator]"
+    );
+
+    let snapshot = || {
+        COUNTERS.with(|c| {
+            (
+                c.allocations.get(),
+                c.deallocations.get(),
+                c.reallocations.get(),
+                c.allocated.get(),
+                c.freed.get(),
+            )
+        })
+    };
+    // Track the peak from the current level, restoring an enclosing
+    // measurement's peak afterwards
+    let (live, outer_peak) = COUNTERS.with(|c| {
+        let outer_peak = c.peak.get();
+        c.peak.set(c.live());
+        (c.live(), outer_peak)
+    });
+    let before = snapshot();
+
+    let result = f();
+
+    let after = snapshot();
+    let peak = COUNTERS.with(|c| {
+        let peak = c.peak.get();
+        c.peak.set(peak.max(outer_peak));
+        peak
+    });
+
+    let stats = AllocationStats {
+        allocations: after.0 - before.0,
+        deallocations: after.1 - before.1,
+        reallocations: after.2 - before.2,
+        bytes_allocated: after.3 - before.3,
+        bytes_freed: after.4 - before.4,
+        peak_bytes: (peak - live).max(0) as u64,
+    };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::{sum, to_vec};
+    use crate::transforms::{Filter, Map};
+    use crate::Transducer;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+
+    #[test]
+    fn test_counts_allocations() {
+        let (v, stats) = measure_allocations(|| vec![0u8; 1000]);
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(stats.bytes_allocated, 1000);
+        assert_eq!(stats.peak_bytes, 1000);
+        drop(v);
+
+        let (_, stats) = measure_allocations(|| {
+            let mut v = Vec::with_capacity(4);
+            v.extend(0..100u32);
+            v.len()
+        });
+        assert_eq!(stats.allocations, 1);
+        assert!(stats.reallocations > 0);
+        assert_eq!(stats.bytes_allocated, stats.bytes_freed);
+        assert!(stats.peak_bytes >= 400);
+    }
+
+    #[test]
+    fn test_no_allocation() {
+        let (n, stats) = measure_allocations(|| (0..100u64).sum::<u64>());
+        assert_eq!(n, 4950);
+        assert!(stats.is_allocation_free());
+        assert_eq!(stats, AllocationStats::default());
+    }
+
+    #[test]
+    fn test_pipeline_allocations_dont_scale_with_input() {
+        let pipeline = Map::new(|x: i64| x + 1).compose(Filter::new(|x: &i64| x % 3 == 0));
+        let (_, small) = measure_allocations(|| sum(&pipeline, 0..10));
+        let (_, large) = measure_allocations(|| sum(&pipeline, 0..100_000));
+        assert_eq!(small.allocations, large.allocations);
+
+        let (out, stats) = measure_allocations(|| to_vec(&pipeline, 0..100_000));
+        assert_eq!(out.len(), 33_333);
+        assert!(stats.allocations + stats.reallocations < 40);
+    }
+
+    #[test]
+    fn test_nested_measurements() {
+        let ((inner, _), outer) = measure_allocations(|| {
+            let _held = Box::new([0u8; 64]);
+            measure_allocations(|| std::hint::black_box(vec![0u8; 32]).len())
+        });
+        assert_eq!(inner, 32);
+        assert_eq!(outer.allocations, 2);
+        assert_eq!(outer.peak_bytes, 96);
+    }
+}
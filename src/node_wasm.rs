@@ -0,0 +1,261 @@
+//! Adapters between Orlando pipelines and Node.js streams.
+//!
+//! Enabled by the `nodejs` feature. `fromNodeReadable` turns a `Readable`
+//! into an async iterable that the async terminals consume one chunk at a
+//! time, and `toNodeWritable` turns a `Writable` into a callback for
+//! `forEachAsync` that waits for `'drain'` whenever the stream's buffer is
+//! full. Together they put a pipeline in the middle of a stream chain with
+//! backpressure in both directions:
+//!
+//! ```javascript
+//! const { fromNodeReadable, toNodeWritable, Pipeline } = require('orlando-transducers');
+//!
+//! const out = fs.createWriteStream('errors.log');
+//! await new Pipeline()
+//!   .filter(line => line.includes('ERROR'))
+//!   .map(line => line + '\n')
+//!   .forEachAsync(fromNodeReadable(lines), toNodeWritable(out));
+//! out.end();
+//! ```
+//!
+//! Streams are used through their event-emitter interface only, so any object
+//! with the same `on`/`pause`/`resume` (readable) or `write`/`once`
+//! (writable) methods works too.
+
+use crate::sources_wasm::iter_result;
+use js_sys::{Function, Object, Promise, Reflect};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen(typescript_custom_section)]
+const NODE_STREAMS_TS: &str = r#"
+/** The parts of a Node.js `Readable` used by `fromNodeReadable`. */
+export interface NodeReadableLike {
+  on(event: string, listener: (...args: any[]) => void): unknown;
+  pause(): unknown;
+  resume(): unknown;
+  destroy?(error?: Error): unknown;
+}
+
+/** The parts of a Node.js `Writable` used by `toNodeWritable`. */
+export interface NodeWritableLike {
+  write(chunk: any): boolean;
+  on(event: string, listener: (...args: any[]) => void): unknown;
+  once(event: string, listener: (...args: any[]) => void): unknown;
+}
+"#;
+
+/// Chunks read from a `Readable` that haven't been asked for yet, and the
+/// consumer waiting for the next one, if any.
+#[derive(Default)]
+struct Reader {
+    buffered: VecDeque<JsValue>,
+    ended: bool,
+    error: Option<JsValue>,
+    waiting: Option<(Function, Function)>,
+}
+
+/// Wrap a Node.js `Readable` as an async iterable.
+///
+/// The stream is paused whenever a chunk arrives that nobody has asked for
+/// yet and resumed when the next chunk is requested, so a slow pipeline
+/// holds the producer back instead of buffering the whole input. Stopping
+/// early (e.g. after `take`) destroys the stream. A stream `'error'` rejects
+/// the pending read.
+///
+/// # Examples (JavaScript)
+///
+/// ```javascript
+/// const rows = await new Pipeline()
+///   .filter(row => row.amount > 100)
+///   .toArrayAsync(fromNodeReadable(fs.createReadStream('sales.csv').pipe(csv())));
+/// ```
+#[wasm_bindgen(
+    js_name = fromNodeReadable,
+    unchecked_return_type = "AsyncIterableIterator<any>"
+)]
+pub fn from_node_readable(
+    #[wasm_bindgen(unchecked_param_type = "NodeReadableLike")] stream: JsValue,
+) -> Result<Object, JsValue> {
+    let reader = Rc::new(RefCell::new(Reader::default()));
+
+    let on_data = {
+        let reader = Rc::clone(&reader);
+        let stream = stream.clone();
+        Closure::<dyn FnMut(JsValue)>::new(move |chunk: JsValue| {
+            let waiting = reader.borrow_mut().waiting.take();
+            match waiting {
+                Some((resolve, _)) => {
+                    let _ = resolve.call1(&JsValue::NULL, &iter_result(&chunk, false));
+                }
+                None => {
+                    reader.borrow_mut().buffered.push_back(chunk);
+                    let _ = call_method(&stream, "pause", &[]);
+                }
+            }
+        })
+    };
+    let on_end = {
+        let reader = Rc::clone(&reader);
+        Closure::<dyn FnMut()>::new(move || {
+            let waiting = {
+                let mut reader = reader.borrow_mut();
+                reader.ended = true;
+                reader.waiting.take()
+            };
+            if let Some((resolve, _)) = waiting {
+                let _ = resolve.call1(&JsValue::NULL, &iter_result(&JsValue::UNDEFINED, true));
+            }
+        })
+    };
+    let on_error = {
+        let reader = Rc::clone(&reader);
+        Closure::<dyn FnMut(JsValue)>::new(move |error: JsValue| {
+            let waiting = {
+                let mut reader = reader.borrow_mut();
+                reader.error = Some(error.clone());
+                reader.waiting.take()
+            };
+            if let Some((_, reject)) = waiting {
+                let _ = reject.call1(&JsValue::NULL, &error);
+            }
+        })
+    };
+    call_method(&stream, "on", &["data".into(), on_data.into_js_value()])?;
+    call_method(&stream, "on", &["end".into(), on_end.into_js_value()])?;
+    call_method(&stream, "on", &["error".into(), on_error.into_js_value()])?;
+
+    let next = {
+        let reader = Rc::clone(&reader);
+        let stream = stream.clone();
+        Closure::<dyn FnMut() -> Promise>::new(move || {
+            let mut state = reader.borrow_mut();
+            if let Some(chunk) = state.buffered.pop_front() {
+                return Promise::resolve(&iter_result(&chunk, false));
+            }
+            if let Some(error) = &state.error {
+                return Promise::reject(error);
+            }
+            if state.ended {
+                return Promise::resolve(&iter_result(&JsValue::UNDEFINED, true));
+            }
+
+            let mut resolvers = None;
+            let promise = Promise::new(&mut |resolve, reject| resolvers = Some((resolve, reject)));
+            state.waiting = resolvers;
+            drop(state);
+            if let Err(error) = call_method(&stream, "resume", &[]) {
+                return Promise::reject(&error);
+            }
+            promise
+        })
+    };
+    let close = {
+        let reader = Rc::clone(&reader);
+        Closure::<dyn FnMut() -> Promise>::new(move || {
+            {
+                let mut state = reader.borrow_mut();
+                state.ended = true;
+                state.buffered.clear();
+            }
+            if Reflect::get(&stream, &"destroy".into()).is_ok_and(|f| f.is_function()) {
+                let _ = call_method(&stream, "destroy", &[]);
+            }
+            Promise::resolve(&iter_result(&JsValue::UNDEFINED, true))
+        })
+    };
+
+    let iterator = Object::new();
+    Reflect::set(&iterator, &"next".into(), &next.into_js_value())?;
+    Reflect::set(&iterator, &"return".into(), &close.into_js_value())?;
+    let this = Function::new_no_args("return this");
+    Reflect::set(&iterator, &js_sys::Symbol::async_iterator(), &this)?;
+    Ok(iterator)
+}
+
+/// Error raised by the `'error'` event and the consumer waiting for
+/// `'drain'`, if any.
+#[derive(Default)]
+struct Writer {
+    error: Option<JsValue>,
+    waiting: Option<(Function, Function)>,
+}
+
+/// Turn a Node.js `Writable` into a callback for `forEachAsync`.
+///
+/// Each call writes one chunk. When `write` reports a full buffer the
+/// callback returns a Promise that resolves on `'drain'`, so `forEachAsync`
+/// stops pulling from its source until the stream catches up. A stream
+/// `'error'` rejects the pending write and makes later calls throw. The
+/// stream is not ended; call `end()` once `forEachAsync` resolves.
+///
+/// # Examples (JavaScript)
+///
+/// ```javascript
+/// const gzip = zlib.createGzip();
+/// gzip.pipe(fs.createWriteStream('out.ndjson.gz'));
+///
+/// await new Pipeline()
+///   .map(record => JSON.stringify(record) + '\n')
+///   .forEachAsync(records, toNodeWritable(gzip));
+/// gzip.end();
+/// ```
+#[wasm_bindgen(
+    js_name = toNodeWritable,
+    unchecked_return_type = "(chunk: any) => Promise<void> | undefined"
+)]
+pub fn to_node_writable(
+    #[wasm_bindgen(unchecked_param_type = "NodeWritableLike")] stream: JsValue,
+) -> Result<Function, JsValue> {
+    let writer = Rc::new(RefCell::new(Writer::default()));
+
+    let on_error = {
+        let writer = Rc::clone(&writer);
+        Closure::<dyn FnMut(JsValue)>::new(move |error: JsValue| {
+            let waiting = {
+                let mut writer = writer.borrow_mut();
+                writer.error = Some(error.clone());
+                writer.waiting.take()
+            };
+            if let Some((_, reject)) = waiting {
+                let _ = reject.call1(&JsValue::NULL, &error);
+            }
+        })
+    };
+    call_method(&stream, "on", &["error".into(), on_error.into_js_value()])?;
+
+    let write =
+        Closure::<dyn FnMut(JsValue) -> Result<JsValue, JsValue>>::new(move |chunk: JsValue| {
+            if let Some(error) = &writer.borrow().error {
+                return Err(error.clone());
+            }
+            if call_method(&stream, "write", &[chunk])?.is_truthy() {
+                return Ok(JsValue::UNDEFINED);
+            }
+
+            let mut resolvers = None;
+            let promise = Promise::new(&mut |resolve, reject| resolvers = Some((resolve, reject)));
+            writer.borrow_mut().waiting = resolvers;
+            let on_drain = {
+                let writer = Rc::clone(&writer);
+                Closure::<dyn FnMut()>::new(move || {
+                    if let Some((resolve, _)) = writer.borrow_mut().waiting.take() {
+                        let _ = resolve.call0(&JsValue::NULL);
+                    }
+                })
+            };
+            call_method(&stream, "once", &["drain".into(), on_drain.into_js_value()])?;
+            Ok(promise.into())
+        });
+    Ok(write.into_js_value().unchecked_into())
+}
+
+/// Call `target[name](...args)`.
+fn call_method(target: &JsValue, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    let method: Function = Reflect::get(target, &name.into())?.dyn_into()?;
+    let args: js_sys::Array = args.iter().collect();
+    method.apply(target, &args)
+}
@@ -8,6 +8,8 @@
 //! - **Traversal**: Multiple foci (collection elements)
 //! - **Prism**: Focus on a variant of a sum type
 //! - **Iso**: Lossless bidirectional conversion
+//! - **Getter**: Read-only focus on exactly one value
+//! - **Fold**: Read-only focus on zero or more values
 //!
 //! ## Lens Laws
 //!
@@ -54,11 +56,24 @@
 //! assert_eq!(upper_user.name, "ALICE");
 //! ```
 
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use karpal_profunctor::{Choice, Profunctor, Strong, Traversing};
 
+#[cfg(feature = "json")]
+pub mod json;
+pub mod laws;
+
+/// Derive `<field>_lens()` constructors for a struct's named fields.
+///
+/// Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use orlando_derive::Lenses;
+
 // Type aliases to satisfy clippy type_complexity lint.
 // Using Rc (not Box) so closures can be shared into profunctor transform closures.
 type GetterFn<S, A> = Rc<dyn Fn(&S) -> A>;
@@ -67,6 +82,8 @@ type OptionalGetter<S, A> = Rc<dyn Fn(&S) -> Option<A>>;
 type ReviewerFn<S, A> = Rc<dyn Fn(A) -> S>;
 type FoldGetter<S, A> = Rc<dyn Fn(&S) -> Vec<A>>;
 type TraversalSetter<S, A> = Rc<dyn Fn(&S, &dyn Fn(A) -> A) -> S>;
+type SyncGetterFn<S, A> = Arc<dyn Fn(&S) -> A + Send + Sync>;
+type SyncSetterFn<S, A> = Arc<dyn Fn(&S, A) -> S + Send + Sync>;
 
 /// A Lens focuses on a part A of a structure S, allowing both reading and updating.
 ///
@@ -79,6 +96,7 @@ type TraversalSetter<S, A> = Rc<dyn Fn(&S, &dyn Fn(A) -> A) -> S>;
 /// since both use closure-based storage (unlike Karpal where `Lens` uses fn ptrs).
 pub type ComposedLens<S, A> = Lens<S, A>;
 
+#[derive(Clone)]
 pub struct Lens<S, A>
 where
     S: Clone,
@@ -209,14 +227,12 @@ where
         )
     }
 
-    /// Compose with another lens, focusing deeper. Alias for `compose()`.
-    pub fn then<B>(self, inner: Lens<A, B>) -> Lens<S, B>
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
     where
-        B: Clone + 'static,
-        A: 'static,
-        S: 'static,
+        Self: ComposeOptic<O>,
     {
-        self.compose(inner)
+        self.clone().compose_optic(inner)
     }
 
     /// Convert this Lens into a Traversal (a Lens is a single-focus Traversal).
@@ -234,6 +250,17 @@ where
         )
     }
 
+    /// Convert this Lens into an Optional whose focus always exists.
+    pub fn to_optional(&self) -> Optional<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let get = self.get.clone();
+        let set = self.set.clone();
+        Optional::new(move |s: &S| Some(get(s)), move |s: &S, a: A| set(s, a))
+    }
+
     /// Convert this Lens into a read-only Fold.
     pub fn to_fold(&self) -> Fold<S, A>
     where
@@ -244,9 +271,21 @@ where
         Fold::new(move |s: &S| vec![get(s)])
     }
 
-    /// Compose two lenses to focus deeper into nested structures.
+    /// Convert this Lens into a read-only Getter.
+    pub fn to_getter(&self) -> Getter<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let get = self.get.clone();
+        Getter::new(move |s: &S| get(s))
+    }
+
+    /// Compose with another optic to focus deeper into nested structures.
     ///
-    /// Given `Lens<S, A>` and `Lens<A, B>`, produces `Lens<S, B>`.
+    /// Given `Lens<S, A>` and `Lens<A, B>`, produces `Lens<S, B>`. Composing
+    /// with a `Prism<A, B>` produces an `Optional<S, B>`, since the variant
+    /// may not match (see [`ComposeOptic`]).
     ///
     /// # Example
     ///
@@ -269,36 +308,114 @@ where
     /// let city = user_city_lens.get(&user);
     /// assert_eq!(city, "NYC");
     /// ```
-    pub fn compose<B>(self, other: Lens<A, B>) -> Lens<S, B>
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
+}
+
+/// A Lens that can be shared across threads.
+///
+/// `Lens` keeps its functions in `Rc`s, so it is neither `Send` nor `Sync`.
+/// `SyncLens` keeps them in `Arc`s instead and requires them to be
+/// `Send + Sync`, so one lens can be cloned into worker threads or stored in
+/// a `static`-like shared registry. `to_lens` turns it into an ordinary
+/// `Lens` for composing with other optic kinds.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::SyncLens;
+///
+/// let first = SyncLens::new(|p: &(i32, i32)| p.0, |p: &(i32, i32), a| (a, p.1));
+/// let worker = {
+///     let first = first.clone();
+///     std::thread::spawn(move || first.over(&(1, 2), |a| a * 10))
+/// };
+/// assert_eq!(worker.join().unwrap(), (10, 2));
+/// assert_eq!(first.get(&(3, 4)), 3);
+/// ```
+#[derive(Clone)]
+pub struct SyncLens<S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    get: SyncGetterFn<S, A>,
+    set: SyncSetterFn<S, A>,
+    _phantom: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A> SyncLens<S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    /// Create a new thread-safe lens from getter and setter functions.
+    pub fn new<G, S2>(get_fn: G, set_fn: S2) -> Self
+    where
+        G: Fn(&S) -> A + Send + Sync + 'static,
+        S2: Fn(&S, A) -> S + Send + Sync + 'static,
+    {
+        SyncLens {
+            get: Arc::new(get_fn),
+            set: Arc::new(set_fn),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Extract the focused value from the source.
+    pub fn get(&self, source: &S) -> A {
+        (self.get)(source)
+    }
+
+    /// Update the focused value immutably, returning a new source.
+    pub fn set(&self, source: &S, value: A) -> S {
+        (self.set)(source, value)
+    }
+
+    /// Transform the focused value using a function.
+    pub fn over<F>(&self, source: &S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        self.set(source, f(self.get(source)))
+    }
+
+    /// Convert this SyncLens into an ordinary (single-threaded) Lens.
+    pub fn to_lens(&self) -> Lens<S, A>
     where
-        B: Clone + 'static,
-        A: 'static,
         S: 'static,
+        A: 'static,
     {
-        let self_get = self.get;
-        let self_set = self.set;
-        let other_get = other.get;
-        let other_set = other.set;
+        let get = self.get.clone();
+        let set = self.set.clone();
+        Lens::new(move |s: &S| get(s), move |s: &S, a: A| set(s, a))
+    }
 
-        let self_get_2 = self_get.clone();
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
 
-        Lens::new(
-            move |source: &S| {
-                let intermediate = self_get(source);
-                other_get(&intermediate)
-            },
-            move |source: &S, value: B| {
-                let intermediate = self_get_2(source);
-                let updated_intermediate = other_set(&intermediate, value);
-                self_set(source, updated_intermediate)
-            },
-        )
+    /// Compose with another `SyncLens`, keeping the result thread-safe.
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
     }
 }
 
 /// An Optional focuses on a part A of a structure S that may not exist.
 ///
 /// Like Lens, but the focused value may be None (e.g., nullable fields in JavaScript).
+#[derive(Clone)]
 pub struct Optional<S, A>
 where
     S: Clone,
@@ -358,6 +475,24 @@ where
         }
     }
 
+    /// Convert this Optional into a Traversal with zero or one focus.
+    pub fn to_traversal(&self) -> Traversal<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let get = self.get.clone();
+        let get_2 = self.get.clone();
+        let set = self.set.clone();
+        Traversal::new(
+            move |s: &S| get(s).into_iter().collect(),
+            move |s: &S, f: &dyn Fn(A) -> A| match get_2(s) {
+                Some(a) => set(s, f(a)),
+                None => s.clone(),
+            },
+        )
+    }
+
     /// Convert this Optional into a read-only Fold.
     pub fn to_fold(&self) -> Fold<S, A>
     where
@@ -367,6 +502,221 @@ where
         let get = self.get.clone();
         Fold::new(move |s: &S| get(s).into_iter().collect())
     }
+
+    /// Turn this Optional into a Lens that creates the focus when it's
+    /// missing.
+    ///
+    /// A missing focus reads as `default()`, and `set`/`over` write through
+    /// this optional's setter, which then inserts the value. Upserting each
+    /// level that may be missing and composing the resulting lenses builds
+    /// missing intermediate structure on `set`, like Ramda's `assocPath`.
+    ///
+    /// Only setters that can create the focus insert anything: a hand-written
+    /// setter like `|u, a| User { address: Some(a), ..u.clone() }` does, as do
+    /// [`Optional::entry`] and [`Optional::index_padded`]. The composition of
+    /// two optionals, `Optional::index` and `Optional::at` leave a missing
+    /// focus alone, so their upserted lens reads the default back after a
+    /// `set` instead of the value written; upsert each level individually,
+    /// through an inserting optional.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::optics::{Lens, Optional};
+    ///
+    /// #[derive(Clone, Debug, Default, PartialEq)]
+    /// struct Address { zip: String }
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct User { address: Option<Address> }
+    ///
+    /// let address = Optional::new(
+    ///     |u: &User| u.address.clone(),
+    ///     |_: &User, a: Address| User { address: Some(a) },
+    /// );
+    /// let zip = Lens::new(|a: &Address| a.zip.clone(), |_: &Address, zip| Address { zip });
+    ///
+    /// let user_zip = address.upsert(Address::default).compose(zip);
+    /// let user = user_zip.set(&User { address: None }, "10001".into());
+    /// assert_eq!(user.address, Some(Address { zip: "10001".into() }));
+    /// ```
+    pub fn upsert<D>(&self, default: D) -> Lens<S, A>
+    where
+        D: Fn() -> A + 'static,
+        S: 'static,
+        A: 'static,
+    {
+        let get = self.get.clone();
+        let set = self.set.clone();
+        Lens::new(
+            move |s: &S| get(s).unwrap_or_else(&default),
+            move |s: &S, a: A| set(s, a),
+        )
+    }
+
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with another optic to focus deeper into nested structures.
+    ///
+    /// The result focuses on nothing when this optional's focus is missing,
+    /// in which case `set` and `over` return the source unchanged.
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
+}
+
+impl<T> Optional<Vec<T>, T>
+where
+    T: Clone + 'static,
+{
+    /// An Optional focusing on the element at index `i` of a `Vec`.
+    ///
+    /// Out of range, `get` returns `None` and `set` leaves the `Vec` unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::optics::Optional;
+    ///
+    /// let second = Optional::index(1);
+    /// assert_eq!(second.get(&vec![1, 2, 3]), Some(2));
+    /// assert_eq!(second.set(&vec![1, 2, 3], 20), vec![1, 20, 3]);
+    /// assert_eq!(second.set(&vec![1], 20), vec![1]);
+    /// ```
+    pub fn index(i: usize) -> Self {
+        Optional::new(
+            move |v: &Vec<T>| v.get(i).cloned(),
+            move |v: &Vec<T>, x: T| {
+                let mut v = v.clone();
+                if let Some(slot) = v.get_mut(i) {
+                    *slot = x;
+                }
+                v
+            },
+        )
+    }
+
+    /// Like [`Optional::index`], but `set` past the end first pads the `Vec`
+    /// with `fill()`, so the element is always written.
+    ///
+    /// Use it with [`Optional::upsert`] to create a missing element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::optics::Optional;
+    ///
+    /// let third = Optional::index_padded(2, || 0);
+    /// assert_eq!(third.get(&vec![1]), None);
+    /// assert_eq!(third.set(&vec![1], 5), vec![1, 0, 5]);
+    /// ```
+    pub fn index_padded<F>(i: usize, fill: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+    {
+        Optional::new(
+            move |v: &Vec<T>| v.get(i).cloned(),
+            move |v: &Vec<T>, x: T| {
+                let mut v = v.clone();
+                if i >= v.len() {
+                    v.resize_with(i + 1, &fill);
+                }
+                v[i] = x;
+                v
+            },
+        )
+    }
+
+    /// An Optional focusing on the first element of a `Vec`, if any.
+    pub fn first() -> Self {
+        Optional::index(0)
+    }
+
+    /// An Optional focusing on the last element of a `Vec`, if any.
+    pub fn last() -> Self {
+        Optional::new(
+            |v: &Vec<T>| v.last().cloned(),
+            |v: &Vec<T>, x: T| {
+                let mut v = v.clone();
+                if let Some(slot) = v.last_mut() {
+                    *slot = x;
+                }
+                v
+            },
+        )
+    }
+}
+
+impl<K, V> Optional<HashMap<K, V>, V>
+where
+    K: Clone + Eq + Hash + 'static,
+    V: Clone + 'static,
+{
+    /// An Optional focusing on the value stored under `key` in a `HashMap`.
+    ///
+    /// When the key is absent, `get` returns `None` and `set` leaves the map
+    /// unchanged; entries are never inserted or removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::optics::Optional;
+    /// use std::collections::HashMap;
+    ///
+    /// let scores = HashMap::from([("alice", 3), ("bob", 5)]);
+    /// let bob = Optional::at("bob");
+    /// assert_eq!(bob.get(&scores), Some(5));
+    /// assert_eq!(bob.over(&scores, |n| n + 1)["bob"], 6);
+    /// assert_eq!(Optional::at("carol").set(&scores, 1), scores);
+    /// ```
+    pub fn at(key: K) -> Self {
+        let key_2 = key.clone();
+        Optional::new(
+            move |m: &HashMap<K, V>| m.get(&key).cloned(),
+            move |m: &HashMap<K, V>, x: V| {
+                let mut m = m.clone();
+                if let Some(slot) = m.get_mut(&key_2) {
+                    *slot = x;
+                }
+                m
+            },
+        )
+    }
+
+    /// Like [`Optional::at`], but `set` inserts `key` when it's absent.
+    ///
+    /// Use it with [`Optional::upsert`] to create a missing entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::optics::Optional;
+    /// use std::collections::HashMap;
+    ///
+    /// let scores = HashMap::from([("alice", 3)]);
+    /// let carol = Optional::entry("carol").upsert(|| 0);
+    /// assert_eq!(carol.get(&scores), 0);
+    /// assert_eq!(carol.over(&scores, |n| n + 1)["carol"], 1);
+    /// ```
+    pub fn entry(key: K) -> Self {
+        let key_2 = key.clone();
+        Optional::new(
+            move |m: &HashMap<K, V>| m.get(&key).cloned(),
+            move |m: &HashMap<K, V>, x: V| {
+                let mut m = m.clone();
+                m.insert(key_2.clone(), x);
+                m
+            },
+        )
+    }
 }
 
 /// A Prism focuses on a variant A of a sum type S.
@@ -407,6 +757,7 @@ where
 /// let rect = Shape::Rectangle(2.0, 3.0);
 /// assert_eq!(circle_prism.preview(&rect), None);
 /// ```
+#[derive(Clone)]
 pub struct Prism<S, A>
 where
     S: Clone,
@@ -499,6 +850,27 @@ where
         )
     }
 
+    /// Convert this Prism into an Optional focusing on the variant.
+    ///
+    /// Setting through the Optional only replaces a matching variant; other
+    /// variants are returned unchanged.
+    pub fn to_optional(&self) -> Optional<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let preview = self.preview_fn.clone();
+        let matches = self.preview_fn.clone();
+        let review = self.review_fn.clone();
+        Optional::new(
+            move |s: &S| preview(s),
+            move |s: &S, a: A| match matches(s) {
+                Some(_) => review(a),
+                None => s.clone(),
+            },
+        )
+    }
+
     /// Convert this Prism into a read-only Fold.
     pub fn to_fold(&self) -> Fold<S, A>
     where
@@ -508,6 +880,56 @@ where
         let preview = self.preview_fn.clone();
         Fold::new(move |s: &S| preview(s).into_iter().collect())
     }
+
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with another optic to focus inside the variant.
+    ///
+    /// Two prisms compose into a `Prism` (a variant of a variant); a prism
+    /// followed by a `Lens` or `Optional` gives an `Optional`, which focuses
+    /// on nothing when the variant doesn't match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use orlando_transducers::optics::{Lens, Prism};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Card { last4: String }
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Payment { Card(Card), Cash }
+    ///
+    /// let card = Prism::new(
+    ///     |p: &Payment| match p {
+    ///         Payment::Card(c) => Some(c.clone()),
+    ///         _ => None,
+    ///     },
+    ///     Payment::Card,
+    /// );
+    /// let last4 = Lens::new(
+    ///     |c: &Card| c.last4.clone(),
+    ///     |_: &Card, last4: String| Card { last4 },
+    /// );
+    ///
+    /// let card_last4 = card.compose(last4);
+    /// let paid = Payment::Card(Card { last4: "4242".into() });
+    /// assert_eq!(card_last4.get(&paid), Some("4242".to_string()));
+    /// assert_eq!(card_last4.get(&Payment::Cash), None);
+    /// assert_eq!(card_last4.set(&Payment::Cash, "0000".into()), Payment::Cash);
+    /// ```
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
 }
 
 /// An Iso represents a lossless bidirectional conversion between types S and A.
@@ -539,6 +961,7 @@ where
 /// let back = celsius_fahrenheit.from(f);
 /// assert!((back - c).abs() < 1e-10);
 /// ```
+#[derive(Clone)]
 pub struct Iso<S, A>
 where
     S: Clone,
@@ -638,6 +1061,28 @@ where
         Prism::new(move |s: &S| Some(to_fn(s)), move |a: A| from_fn(a))
     }
 
+    /// Convert this Iso into a Lens without consuming it.
+    pub fn to_lens(&self) -> Lens<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let to_fn = self.to_fn.clone();
+        let from_fn = self.from_fn.clone();
+        Lens::new(move |s: &S| to_fn(s), move |_s: &S, a: A| from_fn(a))
+    }
+
+    /// Convert this Iso into a Prism without consuming it.
+    pub fn to_prism(&self) -> Prism<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let to_fn = self.to_fn.clone();
+        let from_fn = self.from_fn.clone();
+        Prism::new(move |s: &S| Some(to_fn(s)), move |a: A| from_fn(a))
+    }
+
     /// Convert this Iso into a Traversal (every Iso is a single-focus Traversal).
     pub fn to_traversal(&self) -> Traversal<S, A>
     where
@@ -662,6 +1107,141 @@ where
         let to = self.to_fn.clone();
         Fold::new(move |s: &S| vec![to(s)])
     }
+
+    /// Convert this Iso into a read-only Getter.
+    pub fn to_getter(&self) -> Getter<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let to = self.to_fn.clone();
+        Getter::new(move |s: &S| to(s))
+    }
+
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with another optic.
+    ///
+    /// An Iso never changes the kind of the optic it is composed with: two
+    /// isos give an `Iso`, and an Iso on either side of a `Lens`, `Prism`,
+    /// `Optional` or `Traversal` gives that optic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use orlando_transducers::optics::{Iso, Lens};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Reading { celsius: f64 }
+    ///
+    /// let celsius = Lens::new(
+    ///     |r: &Reading| r.celsius,
+    ///     |_: &Reading, celsius: f64| Reading { celsius },
+    /// );
+    /// let to_fahrenheit = Iso::new(|c: &f64| c * 1.8 + 32.0, |f: f64| (f - 32.0) / 1.8);
+    ///
+    /// let fahrenheit = celsius.compose(to_fahrenheit);
+    /// let reading = Reading { celsius: 100.0 };
+    /// assert_eq!(fahrenheit.get(&reading), 212.0);
+    /// assert_eq!(fahrenheit.set(&reading, 32.0), Reading { celsius: 0.0 });
+    /// ```
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
+}
+
+/// A Getter extracts exactly one value of type A from a structure S (read-only).
+///
+/// A Getter is a Lens without a setter: any function `&S -> A` is one, so it
+/// suits derived values (full names, totals) that can't be written back.
+/// Composed with a Lens or Iso it stays a Getter; composed with an optic
+/// that may focus on zero or many values it becomes a `Fold`.
+///
+/// ## Usage
+///
+/// ```rust
+/// use orlando_transducers::optics::{Getter, Lens};
+///
+/// #[derive(Clone)]
+/// struct Order { items: Vec<(String, f64)> }
+///
+/// let items = Lens::new(
+///     |o: &Order| o.items.clone(),
+///     |_: &Order, items| Order { items },
+/// );
+/// let total = items.compose(Getter::new(|items: &Vec<(String, f64)>| {
+///     items.iter().map(|(_, price)| price).sum::<f64>()
+/// }));
+///
+/// let order = Order { items: vec![("tea".into(), 3.5), ("cake".into(), 4.0)] };
+/// assert_eq!(total.get(&order), 7.5);
+/// ```
+#[derive(Clone)]
+pub struct Getter<S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    get: GetterFn<S, A>,
+    _phantom: PhantomData<(S, A)>,
+}
+
+impl<S, A> Getter<S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    /// Create a new getter from an extraction function.
+    pub fn new<G>(get_fn: G) -> Self
+    where
+        G: Fn(&S) -> A + 'static,
+    {
+        Getter {
+            get: Rc::new(get_fn),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Extract the focused value.
+    pub fn get(&self, source: &S) -> A {
+        (self.get)(source)
+    }
+
+    /// Convert this Getter into a Fold with exactly one focus.
+    pub fn to_fold(&self) -> Fold<S, A>
+    where
+        S: 'static,
+        A: 'static,
+    {
+        let get = self.get.clone();
+        Fold::new(move |s: &S| vec![get(s)])
+    }
+
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with any optic, reading through it. The result is a Getter
+    /// when `other` is a Getter, Lens or Iso, and a Fold otherwise.
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
 }
 
 /// A Fold extracts zero or more values of type A from a structure S (read-only).
@@ -680,6 +1260,7 @@ where
 /// let data = vec![1, 2, 3, 4, 5, 6];
 /// assert_eq!(even_fold.fold_of(&data), vec![2, 4, 6]);
 /// ```
+#[derive(Clone)]
 pub struct Fold<S, A>
 where
     S: Clone,
@@ -748,16 +1329,24 @@ where
         self.fold_of(source).into_iter().find(|a| f(a))
     }
 
-    /// Compose with another Fold, focusing deeper.
-    pub fn then<B>(self, inner: Fold<A, B>) -> Fold<S, B>
+    /// Compose with another Fold, focusing deeper. Use `compose()` for
+    /// other optic kinds.
+    pub fn then<B>(&self, inner: Fold<A, B>) -> Fold<S, B>
     where
         S: 'static,
         A: 'static,
         B: Clone + 'static,
     {
-        let outer = self.fold_fn;
-        let inner = inner.fold_fn;
-        Fold::new(move |s: &S| outer(s).into_iter().flat_map(|a| inner(&a)).collect())
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with any optic, reading through it. The result is always a
+    /// Fold.
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
     }
 }
 
@@ -783,6 +1372,7 @@ where
 /// assert_eq!(each.get_all(&data), vec![1, 2, 3]);
 /// assert_eq!(each.over_all(&data, |x| x * 2), vec![2, 4, 6]);
 /// ```
+#[derive(Clone)]
 pub struct Traversal<S, A>
 where
     S: Clone,
@@ -824,6 +1414,31 @@ where
         (self.over_all_fn)(source, &f)
     }
 
+    /// Transform all focused values using a function. Alias for `over_all()`.
+    pub fn over<F>(&self, source: &S, f: F) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        self.over_all(source, f)
+    }
+
+    /// Combine all focused values, left to right, into a single result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use orlando_transducers::optics::Traversal;
+    ///
+    /// let each = Traversal::<Vec<u32>, u32>::each();
+    /// assert_eq!(each.fold(&vec![1, 2, 3], 0, |total, x| total + x), 6);
+    /// ```
+    pub fn fold<R, F>(&self, source: &S, init: R, f: F) -> R
+    where
+        F: Fn(R, A) -> R,
+    {
+        self.get_all(source).into_iter().fold(init, f)
+    }
+
     /// Set all focused values to a single value.
     pub fn set_all(&self, source: &S, value: A) -> S
     where
@@ -870,13 +1485,257 @@ where
         Fold::new(move |s: &S| get_all(s))
     }
 
-    /// Compose with another Traversal, focusing deeper.
-    pub fn then<B>(self, inner: Traversal<A, B>) -> Traversal<S, B>
-    where
-        S: 'static,
-        A: 'static,
-        B: Clone + 'static,
-    {
+    /// Compose with another optic, focusing deeper. Alias for `compose()`.
+    pub fn then<O>(&self, inner: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(inner)
+    }
+
+    /// Compose with a Traversal or Lens to focus inside every element.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use orlando_transducers::optics::{Lens, Traversal};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Item { price: u32 }
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Order { items: Vec<Item> }
+    ///
+    /// let items = Lens::new(
+    ///     |o: &Order| o.items.clone(),
+    ///     |_: &Order, items: Vec<Item>| Order { items },
+    /// );
+    /// let price = Lens::new(|i: &Item| i.price, |_: &Item, price: u32| Item { price });
+    ///
+    /// // Every price of every item in the order
+    /// let prices = items.compose(Traversal::each()).compose(price);
+    ///
+    /// let order = Order { items: vec![Item { price: 10 }, Item { price: 25 }] };
+    /// assert_eq!(prices.get_all(&order), vec![10, 25]);
+    /// assert_eq!(prices.fold(&order, 0, |total, p| total + p), 35);
+    ///
+    /// let discounted = prices.over(&order, |p| p - p / 10);
+    /// assert_eq!(discounted.items, vec![Item { price: 9 }, Item { price: 23 }]);
+    /// ```
+    pub fn compose<O>(&self, other: O) -> <Self as ComposeOptic<O>>::Output
+    where
+        Self: ComposeOptic<O>,
+    {
+        self.clone().compose_optic(other)
+    }
+}
+
+impl<A> Traversal<Vec<A>, A>
+where
+    A: Clone + 'static,
+{
+    /// A Traversal focusing on every element of a `Vec`.
+    pub fn each() -> Self {
+        Traversal::new(
+            |v: &Vec<A>| v.clone(),
+            |v: &Vec<A>, f: &dyn Fn(A) -> A| v.iter().cloned().map(f).collect(),
+        )
+    }
+}
+
+/// Composition of two optics, focusing through `Self` and then `Inner`.
+///
+/// The result is the most specific optic both parts can be viewed as. Every
+/// pair of optic kinds composes, in either order:
+///
+/// | Composed with | Result |
+/// |---------------|--------|
+/// | same kind | that kind |
+/// | `Iso` | the other optic's kind |
+/// | `Lens` and `Prism` or `Optional` | `Optional` |
+/// | `Prism` and `Optional` | `Optional` |
+/// | `Getter` and `Lens` | `Getter` |
+/// | `Getter` and `Optional`, `Prism` or `Traversal` | `Fold` |
+/// | anything but a `Getter` or `Fold`, and `Traversal` | `Traversal` |
+/// | anything and `Fold` | `Fold` |
+///
+/// Each optic's `compose` and `then` methods accept anything this trait is
+/// implemented for, so `user_address.compose(zip)` type-checks whatever
+/// kinds the two parts are. Both methods borrow `self` and accept the inner
+/// optic by value or by reference: optics hold their functions in `Rc`s, so
+/// cloning one is cheap and the same optic can be reused in any number of
+/// compositions and pipelines.
+pub trait ComposeOptic<Inner> {
+    /// The optic produced by the composition.
+    type Output;
+
+    /// Compose `self` with `inner`, focusing deeper.
+    fn compose_optic(self, inner: Inner) -> Self::Output;
+}
+
+// Composing with a borrowed optic clones it, which only copies `Rc`s.
+impl<'a, T, O> ComposeOptic<&'a O> for T
+where
+    T: ComposeOptic<O>,
+    O: Clone,
+{
+    type Output = T::Output;
+
+    fn compose_optic(self, inner: &'a O) -> T::Output {
+        self.compose_optic(inner.clone())
+    }
+}
+
+impl<S, A, B> ComposeOptic<Lens<A, B>> for Lens<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Lens<S, B>;
+
+    fn compose_optic(self, inner: Lens<A, B>) -> Lens<S, B> {
+        let outer_get = self.get;
+        let outer_set = self.set;
+        let inner_get = inner.get;
+        let inner_set = inner.set;
+        let outer_get_2 = outer_get.clone();
+
+        Lens::new(
+            move |s: &S| inner_get(&outer_get(s)),
+            move |s: &S, b: B| outer_set(s, inner_set(&outer_get_2(s), b)),
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<SyncLens<A, B>> for SyncLens<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = SyncLens<S, B>;
+
+    fn compose_optic(self, inner: SyncLens<A, B>) -> SyncLens<S, B> {
+        let outer_get = self.get.clone();
+        let outer_get_2 = self.get;
+        let outer_set = self.set;
+        let inner_get = inner.get;
+        let inner_set = inner.set;
+        SyncLens::new(
+            move |s: &S| inner_get(&outer_get(s)),
+            move |s: &S, b: B| outer_set(s, inner_set(&outer_get_2(s), b)),
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Prism<A, B>> for Lens<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Optional<S, B>;
+
+    fn compose_optic(self, inner: Prism<A, B>) -> Optional<S, B> {
+        self.to_optional().compose_optic(inner)
+    }
+}
+
+impl<S, A, B> ComposeOptic<Prism<A, B>> for Optional<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Optional<S, B>;
+
+    fn compose_optic(self, inner: Prism<A, B>) -> Optional<S, B> {
+        let outer_get = self.get;
+        let outer_set = self.set;
+        let preview = inner.preview_fn;
+        let review = inner.review_fn;
+        let outer_get_2 = outer_get.clone();
+        let matches = preview.clone();
+
+        Optional::new(
+            move |s: &S| outer_get(s).and_then(|a| preview(&a)),
+            move |s: &S, b: B| match outer_get_2(s) {
+                Some(a) if matches(&a).is_some() => outer_set(s, review(b)),
+                _ => s.clone(),
+            },
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Prism<A, B>> for Prism<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Prism<S, B>;
+
+    fn compose_optic(self, inner: Prism<A, B>) -> Prism<S, B> {
+        let outer_preview = self.preview_fn;
+        let outer_review = self.review_fn;
+        let inner_preview = inner.preview_fn;
+        let inner_review = inner.review_fn;
+
+        Prism::new(
+            move |s: &S| outer_preview(s).and_then(|a| inner_preview(&a)),
+            move |b: B| outer_review(inner_review(b)),
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Lens<A, B>> for Prism<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Optional<S, B>;
+
+    fn compose_optic(self, inner: Lens<A, B>) -> Optional<S, B> {
+        self.compose_optic(inner.to_optional())
+    }
+}
+
+impl<S, A, B> ComposeOptic<Optional<A, B>> for Prism<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Optional<S, B>;
+
+    fn compose_optic(self, inner: Optional<A, B>) -> Optional<S, B> {
+        let preview = self.preview_fn;
+        let review = self.review_fn;
+        let inner_get = inner.get;
+        let inner_set = inner.set;
+        let preview_2 = preview.clone();
+
+        Optional::new(
+            move |s: &S| preview(s).and_then(|a| inner_get(&a)),
+            move |s: &S, b: B| match preview_2(s) {
+                Some(a) => review(inner_set(&a, b)),
+                None => s.clone(),
+            },
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Traversal<A, B>> for Traversal<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Traversal<S, B>;
+
+    fn compose_optic(self, inner: Traversal<A, B>) -> Traversal<S, B> {
         let outer_get = self.get_all_fn;
         let outer_over = self.over_all_fn;
         let inner_get = inner.get_all_fn;
@@ -897,6 +1756,178 @@ where
     }
 }
 
+impl<S, A, B> ComposeOptic<Lens<A, B>> for Traversal<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Traversal<S, B>;
+
+    fn compose_optic(self, inner: Lens<A, B>) -> Traversal<S, B> {
+        self.compose_optic(inner.to_traversal())
+    }
+}
+
+impl<S, A, B> ComposeOptic<Traversal<A, B>> for Lens<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Traversal<S, B>;
+
+    fn compose_optic(self, inner: Traversal<A, B>) -> Traversal<S, B> {
+        self.to_traversal().compose_optic(inner)
+    }
+}
+
+impl<S, A, B> ComposeOptic<Iso<A, B>> for Iso<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Iso<S, B>;
+
+    fn compose_optic(self, inner: Iso<A, B>) -> Iso<S, B> {
+        let outer_to = self.to_fn;
+        let outer_from = self.from_fn;
+        let inner_to = inner.to_fn;
+        let inner_from = inner.from_fn;
+
+        Iso::new(
+            move |s: &S| inner_to(&outer_to(s)),
+            move |b: B| outer_from(inner_from(b)),
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Optional<A, B>> for Optional<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Optional<S, B>;
+
+    fn compose_optic(self, inner: Optional<A, B>) -> Optional<S, B> {
+        let outer_get = self.get;
+        let outer_set = self.set;
+        let inner_get = inner.get;
+        let inner_set = inner.set;
+        let outer_get_2 = outer_get.clone();
+
+        Optional::new(
+            move |s: &S| outer_get(s).and_then(|a| inner_get(&a)),
+            move |s: &S, b: B| match outer_get_2(s) {
+                Some(a) => outer_set(s, inner_set(&a, b)),
+                None => s.clone(),
+            },
+        )
+    }
+}
+
+impl<S, A, B> ComposeOptic<Fold<A, B>> for Fold<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Fold<S, B>;
+
+    fn compose_optic(self, inner: Fold<A, B>) -> Fold<S, B> {
+        let outer = self.fold_fn;
+        let inner = inner.fold_fn;
+        Fold::new(move |s: &S| outer(s).into_iter().flat_map(|a| inner(&a)).collect())
+    }
+}
+
+impl<S, A, B> ComposeOptic<Getter<A, B>> for Getter<S, A>
+where
+    S: Clone + 'static,
+    A: Clone + 'static,
+    B: Clone + 'static,
+{
+    type Output = Getter<S, B>;
+
+    fn compose_optic(self, inner: Getter<A, B>) -> Getter<S, B> {
+        let outer = self.get;
+        let inner = inner.get;
+        Getter::new(move |s: &S| inner(&outer(s)))
+    }
+}
+
+// Compositions that view one side as a weaker optic and defer to an
+// existing impl: `widen!(Outer, Inner => Output, outer_conversion, inner_conversion)`
+// where either conversion may be `_` to leave that side as it is.
+macro_rules! widen {
+    ($outer:ident, $inner:ident => $output:ident, $to_outer:tt, $to_inner:tt) => {
+        impl<S, A, B> ComposeOptic<$inner<A, B>> for $outer<S, A>
+        where
+            S: Clone + 'static,
+            A: Clone + 'static,
+            B: Clone + 'static,
+        {
+            type Output = $output<S, B>;
+
+            fn compose_optic(self, inner: $inner<A, B>) -> $output<S, B> {
+                widen!(@convert self, $to_outer).compose_optic(widen!(@convert inner, $to_inner))
+            }
+        }
+    };
+    (@convert $optic:ident, _) => {
+        $optic
+    };
+    (@convert $optic:ident, $conversion:ident) => {
+        $optic.$conversion()
+    };
+}
+
+// An Iso takes on the kind of the optic it is composed with
+widen!(Iso, Lens => Lens, to_lens, _);
+widen!(Lens, Iso => Lens, _, to_lens);
+widen!(Iso, Prism => Prism, to_prism, _);
+widen!(Prism, Iso => Prism, _, to_prism);
+widen!(Iso, Optional => Optional, to_prism, _);
+widen!(Optional, Iso => Optional, _, to_prism);
+widen!(Iso, Traversal => Traversal, to_lens, _);
+widen!(Traversal, Iso => Traversal, _, to_lens);
+
+widen!(Lens, Optional => Optional, to_optional, _);
+widen!(Optional, Lens => Optional, _, to_optional);
+widen!(Optional, Traversal => Traversal, to_traversal, _);
+widen!(Traversal, Optional => Traversal, _, to_traversal);
+widen!(Prism, Traversal => Traversal, to_traversal, _);
+widen!(Traversal, Prism => Traversal, _, to_traversal);
+
+// A Getter reads through lenses and isos, and becomes a Fold when the
+// other side may focus on zero or many values
+widen!(Lens, Getter => Getter, to_getter, _);
+widen!(Getter, Lens => Getter, _, to_getter);
+widen!(Iso, Getter => Getter, to_getter, _);
+widen!(Getter, Iso => Getter, _, to_getter);
+widen!(Optional, Getter => Fold, to_fold, to_fold);
+widen!(Getter, Optional => Fold, to_fold, to_fold);
+widen!(Prism, Getter => Fold, to_fold, to_fold);
+widen!(Getter, Prism => Fold, to_fold, to_fold);
+widen!(Traversal, Getter => Fold, to_fold, to_fold);
+widen!(Getter, Traversal => Fold, to_fold, to_fold);
+
+// Anything composed with a Fold, on either side, is read-only
+widen!(Lens, Fold => Fold, to_fold, _);
+widen!(Optional, Fold => Fold, to_fold, _);
+widen!(Prism, Fold => Fold, to_fold, _);
+widen!(Iso, Fold => Fold, to_fold, _);
+widen!(Traversal, Fold => Fold, to_fold, _);
+widen!(Fold, Lens => Fold, _, to_fold);
+widen!(Fold, Optional => Fold, _, to_fold);
+widen!(Fold, Prism => Fold, _, to_fold);
+widen!(Fold, Iso => Fold, _, to_fold);
+widen!(Fold, Traversal => Fold, _, to_fold);
+widen!(Getter, Fold => Fold, to_fold, _);
+widen!(Fold, Getter => Fold, _, to_fold);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1090,6 +2121,88 @@ mod tests {
         assert_eq!(updated.address.unwrap().city, "Boston");
     }
 
+    #[test]
+    fn test_optics_are_reused_across_compositions() {
+        let address = Optional::new(
+            |u: &User| u.address.clone(),
+            |u: &User, address: Address| User {
+                address: Some(address),
+                ..u.clone()
+            },
+        );
+        let city = Lens::new(
+            |a: &Address| a.city.clone(),
+            |a: &Address, city: String| Address { city, ..a.clone() },
+        );
+        let zip = Lens::new(
+            |a: &Address| a.zip.clone(),
+            |a: &Address, zip: String| Address { zip, ..a.clone() },
+        );
+
+        // Neither `compose` nor a borrowed inner optic consumes anything
+        let user_city = address.compose(&city);
+        let user_zip = address.compose(zip.clone());
+        let city_len = city.then(Getter::new(|c: &String| c.len()));
+
+        let user = User {
+            name: "Alice".to_string(),
+            age: 30,
+            address: Some(Address {
+                city: "NYC".to_string(),
+                zip: "10001".to_string(),
+            }),
+        };
+        assert_eq!(user_city.get(&user), Some("NYC".to_string()));
+        assert_eq!(user_zip.get(&user), Some("10001".to_string()));
+        assert_eq!(city_len.get(&user.address.clone().unwrap()), 3);
+
+        let copy = city.clone();
+        assert_eq!(copy.get(&user.address.unwrap()), "NYC");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_sync_lens_shared_across_threads() {
+        let address = SyncLens::new(
+            |u: &(String, Address)| u.1.clone(),
+            |u: &(String, Address), a: Address| (u.0.clone(), a),
+        );
+        let city = SyncLens::new(
+            |a: &Address| a.city.clone(),
+            |a: &Address, city: String| Address { city, ..a.clone() },
+        );
+        let user_city = address.compose(&city);
+
+        let handles: Vec<_> = ["Oslo", "Lima"]
+            .into_iter()
+            .map(|name| {
+                let user_city = user_city.clone();
+                std::thread::spawn(move || {
+                    let user = (
+                        "Alice".to_string(),
+                        Address {
+                            city: "NYC".to_string(),
+                            zip: "10001".to_string(),
+                        },
+                    );
+                    user_city.get(&user_city.set(&user, name.to_string()))
+                })
+            })
+            .collect();
+        let cities: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(cities, vec!["Oslo", "Lima"]);
+
+        let as_lens: Lens<(String, Address), String> = user_city.to_lens();
+        let user = (
+            "Bob".to_string(),
+            Address {
+                city: "LA".to_string(),
+                zip: "90001".to_string(),
+            },
+        );
+        assert_eq!(as_lens.get(&user), "LA");
+    }
+
     #[test]
     fn test_optional_get_some() {
         let address_lens = Optional::new(
@@ -1119,6 +2232,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_optional_upsert_lens_laws_on_missing_focus() {
+        use crate::optics::laws::{check_get_put, check_put_get, check_put_put};
+
+        let scores = HashMap::from([("alice", 3)]);
+        let carol = Optional::entry("carol").upsert(|| 0);
+        assert!(check_put_get(&carol, &scores, 7));
+        assert!(check_put_put(&carol, &scores, 7, 8));
+        assert!(check_get_put(&carol, &carol.set(&scores, 7)));
+        assert_eq!(carol.set(&scores, 7)["alice"], 3);
+
+        let fourth = Optional::index_padded(3, || 0).upsert(|| -1);
+        let short = vec![1];
+        assert_eq!(fourth.get(&short), -1);
+        assert!(check_put_get(&fourth, &short, 7));
+        assert!(check_put_put(&fourth, &short, 7, 8));
+        assert!(check_get_put(&fourth, &fourth.set(&short, 7)));
+        assert_eq!(fourth.set(&short, 7), vec![1, 0, 0, 7]);
+
+        // `at` and `index` never insert, so upserting them can't create the focus
+        assert!(!check_put_get(
+            &Optional::at("carol").upsert(|| 0),
+            &scores,
+            7
+        ));
+        assert!(!check_put_get(&Optional::index(3).upsert(|| -1), &short, 7));
+    }
+
+    #[test]
+    fn test_optional_upsert_builds_missing_levels() {
+        let address = Optional::new(
+            |u: &User| u.address.clone(),
+            |u: &User, address: Address| User {
+                address: Some(address),
+                ..u.clone()
+            },
+        );
+        let zip = Lens::new(
+            |a: &Address| a.zip.clone(),
+            |a: &Address, zip: String| Address { zip, ..a.clone() },
+        );
+        let user_zip = address
+            .upsert(|| Address {
+                city: "Unknown".to_string(),
+                zip: String::new(),
+            })
+            .compose(&zip);
+
+        let user = User {
+            name: "Bob".to_string(),
+            age: 41,
+            address: None,
+        };
+        assert_eq!(user_zip.get(&user), "");
+        let updated = user_zip.set(&user, "90210".to_string());
+        assert_eq!(
+            updated.address,
+            Some(Address {
+                city: "Unknown".to_string(),
+                zip: "90210".to_string(),
+            })
+        );
+
+        // An existing focus is updated in place
+        let moved = user_zip.over(&updated, |z| z + "-1234");
+        assert_eq!(moved.address.unwrap().zip, "90210-1234");
+
+        // Without upsert, setting through the missing level is a no-op
+        assert_eq!(address.compose(zip).set(&user, "90210".to_string()), user);
+    }
+
     #[test]
     fn test_optional_get_none() {
         let address_lens = Optional::new(
@@ -1637,6 +2821,87 @@ mod tests {
         assert_eq!(prism.review((4.0, 5.0)), Shape::Rectangle(4.0, 5.0));
     }
 
+    #[derive(Clone, Debug, PartialEq)]
+    struct Drawing {
+        title: String,
+        shape: Shape,
+    }
+
+    fn shape_lens() -> Lens<Drawing, Shape> {
+        Lens::new(
+            |d: &Drawing| d.shape.clone(),
+            |d: &Drawing, shape: Shape| Drawing {
+                title: d.title.clone(),
+                shape,
+            },
+        )
+    }
+
+    fn width_lens() -> Lens<(f64, f64), f64> {
+        Lens::new(
+            |(w, _): &(f64, f64)| *w,
+            |(_, h): &(f64, f64), w: f64| (w, *h),
+        )
+    }
+
+    #[test]
+    fn test_lens_compose_prism() {
+        let radius = shape_lens().compose(circle_prism());
+        let circle = Drawing {
+            title: "sun".into(),
+            shape: Shape::Circle(2.0),
+        };
+        let square = Drawing {
+            title: "box".into(),
+            shape: Shape::Rectangle(1.0, 1.0),
+        };
+
+        assert_eq!(radius.get(&circle), Some(2.0));
+        assert_eq!(radius.get(&square), None);
+        assert_eq!(radius.over(&circle, |r| r * 3.0).shape, Shape::Circle(6.0));
+        // A non-matching variant is never replaced
+        assert_eq!(radius.set(&square, 9.0), square);
+    }
+
+    #[test]
+    fn test_prism_compose_lens_and_optional() {
+        let width = rectangle_prism().compose(width_lens());
+        assert_eq!(width.get(&Shape::Rectangle(2.0, 3.0)), Some(2.0));
+        assert_eq!(width.get(&Shape::Circle(1.0)), None);
+        assert_eq!(
+            width.set(&Shape::Rectangle(2.0, 3.0), 5.0),
+            Shape::Rectangle(5.0, 3.0)
+        );
+        assert_eq!(width.set(&Shape::Circle(1.0), 5.0), Shape::Circle(1.0));
+
+        let positive = Optional::new(|r: &f64| (*r > 0.0).then_some(*r), |_: &f64, r: f64| r);
+        let positive_radius = circle_prism().then(positive);
+        assert_eq!(positive_radius.get(&Shape::Circle(4.0)), Some(4.0));
+        assert_eq!(positive_radius.get(&Shape::Circle(-4.0)), None);
+    }
+
+    #[test]
+    fn test_prism_compose_prism() {
+        // Option<Shape> → Shape → radius
+        let some: Prism<Option<Shape>, Shape> = Prism::new(|o: &Option<Shape>| o.clone(), Some);
+        let radius = some.compose(circle_prism());
+
+        assert_eq!(radius.preview(&Some(Shape::Circle(1.5))), Some(1.5));
+        assert_eq!(radius.preview(&Some(Shape::Rectangle(1.0, 2.0))), None);
+        assert_eq!(radius.preview(&None), None);
+        assert_eq!(radius.review(2.5), Some(Shape::Circle(2.5)));
+    }
+
+    #[test]
+    fn test_prism_to_optional() {
+        let radius = circle_prism().to_optional();
+        assert_eq!(radius.set(&Shape::Circle(1.0), 2.0), Shape::Circle(2.0));
+        assert_eq!(
+            radius.set(&Shape::Rectangle(1.0, 2.0), 2.0),
+            Shape::Rectangle(1.0, 2.0)
+        );
+    }
+
     // ===== Iso tests =====
 
     fn celsius_fahrenheit_iso() -> Iso<f64, f64> {
@@ -1732,6 +2997,60 @@ mod tests {
         assert_eq!(iso.from(3), "xxx".to_string());
     }
 
+    #[test]
+    fn test_iso_to_lens_and_prism_keep_iso() {
+        let iso = celsius_fahrenheit_iso();
+        assert!((iso.to_lens().get(&100.0) - 212.0).abs() < 1e-10);
+        assert_eq!(iso.to_prism().preview(&0.0), Some(32.0));
+        // The iso is still usable
+        assert!((iso.from(212.0) - 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_iso_compose_iso() {
+        let chars: Iso<String, Vec<char>> = Iso::new(
+            |s: &String| s.chars().collect(),
+            |cs: Vec<char>| cs.into_iter().collect(),
+        );
+        let reversed: Iso<Vec<char>, Vec<char>> = Iso::new(
+            |cs: &Vec<char>| cs.iter().rev().cloned().collect(),
+            |cs: Vec<char>| cs.into_iter().rev().collect(),
+        );
+        let backwards = chars.compose(reversed);
+
+        assert_eq!(backwards.to(&"abc".to_string()), vec!['c', 'b', 'a']);
+        assert_eq!(backwards.from(vec!['z', 'y']), "yz");
+    }
+
+    #[test]
+    fn test_iso_compose_keeps_optic_kind() {
+        // Lens ∘ Iso = Lens
+        let shape = shape_lens();
+        let same_shape: Lens<Drawing, Shape> =
+            shape.compose(Iso::new(|s: &Shape| s.clone(), |s| s));
+        let sun = Drawing {
+            title: "sun".into(),
+            shape: Shape::Circle(1.0),
+        };
+        assert_eq!(same_shape.get(&sun), Shape::Circle(1.0));
+
+        // Prism ∘ Iso = Prism
+        let diameter = circle_prism().compose(Iso::new(|r: &f64| r * 2.0, |d: f64| d / 2.0));
+        assert_eq!(diameter.preview(&Shape::Circle(1.5)), Some(3.0));
+        assert_eq!(diameter.review(10.0), Shape::Circle(5.0));
+
+        // Iso ∘ Traversal = Traversal
+        let chars: Iso<String, Vec<char>> = Iso::new(
+            |s: &String| s.chars().collect(),
+            |cs: Vec<char>| cs.into_iter().collect(),
+        );
+        let each_char = chars.compose(Traversal::each());
+        assert_eq!(
+            each_char.over(&"abc".to_string(), |c| c.to_ascii_uppercase()),
+            "ABC"
+        );
+    }
+
     // ===== Fold tests =====
 
     #[test]
@@ -1766,6 +3085,49 @@ mod tests {
         assert_eq!(even_fold.first(&vec![1, 3, 5]), None);
     }
 
+    #[test]
+    fn test_getter_get_and_compose() {
+        let full_name = Getter::new(|u: &User| format!("{} ({})", u.name, u.age));
+        let length = Getter::new(|s: &String| s.len());
+        let user = User {
+            name: "Alice".to_string(),
+            age: 30,
+            address: None,
+        };
+        assert_eq!(full_name.get(&user), "Alice (30)");
+        assert_eq!(full_name.compose(length).get(&user), 10);
+
+        let name = Lens::new(
+            |u: &User| u.name.clone(),
+            |u: &User, name: String| User { name, ..u.clone() },
+        );
+        let name_length: Getter<User, usize> = name.compose(Getter::new(|s: &String| s.len()));
+        assert_eq!(name_length.get(&user), 5);
+    }
+
+    #[test]
+    fn test_getter_compose_partial_optic_gives_fold() {
+        let address = Optional::new(
+            |u: &User| u.address.clone(),
+            |u: &User, address: Address| User {
+                address: Some(address),
+                ..u.clone()
+            },
+        );
+        let cities: Fold<User, String> = address.compose(Getter::new(|a: &Address| a.city.clone()));
+        let user = User {
+            name: "Bob".to_string(),
+            age: 41,
+            address: None,
+        };
+        assert!(cities.is_empty(&user));
+
+        let lengths = Getter::new(|v: &Vec<String>| v.clone())
+            .compose(Traversal::each())
+            .compose(Getter::new(|s: &String| s.len()));
+        assert_eq!(lengths.fold_of(&vec!["ab".into(), "c".into()]), vec![2, 1]);
+    }
+
     #[test]
     fn test_fold_on_struct() {
         // Fold that extracts all names from a team
@@ -1883,6 +3245,203 @@ mod tests {
         assert_eq!(updated.name, "Dev"); // other fields unchanged
     }
 
+    #[test]
+    fn test_traversal_each_and_fold() {
+        let each = Traversal::<Vec<i32>, i32>::each();
+        let data = vec![3, 1, 4];
+        assert_eq!(each.over(&data, |x| x * 10), vec![30, 10, 40]);
+        assert_eq!(each.fold(&data, 0, |acc, x| acc.max(x)), 4);
+        assert_eq!(each.fold(&Vec::new(), 7, |acc, x| acc + x), 7);
+    }
+
+    #[test]
+    fn test_traversal_compose_lens() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Article {
+            tags: Vec<String>,
+        }
+
+        let tags = Lens::new(
+            |a: &Article| a.tags.clone(),
+            |_: &Article, tags: Vec<String>| Article { tags },
+        );
+        let all_tags = Traversal::<Vec<Article>, Article>::each()
+            .compose(tags)
+            .compose(Traversal::each());
+
+        let articles = vec![
+            Article {
+                tags: vec!["Rust".into(), "WASM".into()],
+            },
+            Article {
+                tags: vec!["Optics".into()],
+            },
+        ];
+        assert_eq!(all_tags.get_all(&articles), vec!["Rust", "WASM", "Optics"]);
+
+        let lowered = all_tags.over(&articles, |t| t.to_lowercase());
+        assert_eq!(lowered[0].tags, vec!["rust", "wasm"]);
+        assert_eq!(lowered[1].tags, vec!["optics"]);
+        assert_eq!(all_tags.fold(&articles, 0, |n, t| n + t.len()), 14);
+    }
+
+    #[test]
+    fn test_optional_index_first_last() {
+        let v = vec![10, 20, 30];
+        assert_eq!(Optional::index(1).get(&v), Some(20));
+        assert_eq!(Optional::index(3).get(&v), None);
+        assert_eq!(Optional::index(3).set(&v, 0), v);
+        assert_eq!(Optional::first().over(&v, |x| x + 1), vec![11, 20, 30]);
+        assert_eq!(Optional::last().set(&v, 0), vec![10, 20, 0]);
+        assert_eq!(Optional::<Vec<i32>, i32>::last().get(&Vec::new()), None);
+    }
+
+    #[test]
+    fn test_optional_at_key() {
+        let mut teams = HashMap::new();
+        teams.insert(
+            "red".to_string(),
+            vec!["Ann".to_string(), "Ben".to_string()],
+        );
+
+        let red_captain = Optional::at("red".to_string()).compose(Optional::first());
+        assert_eq!(red_captain.get(&teams), Some("Ann".to_string()));
+        let renamed = red_captain.set(&teams, "Cy".to_string());
+        assert_eq!(renamed["red"], vec!["Cy", "Ben"]);
+
+        let blue = Optional::at("blue".to_string());
+        assert_eq!(blue.get(&teams), None);
+        assert_eq!(blue.set(&teams, Vec::new()), teams);
+    }
+
+    fn address_optional() -> Optional<User, Address> {
+        Optional::new(
+            |user: &User| user.address.clone(),
+            |user: &User, address: Address| User {
+                address: Some(address),
+                ..user.clone()
+            },
+        )
+    }
+
+    fn zip_lens() -> Lens<Address, String> {
+        Lens::new(
+            |a: &Address| a.zip.clone(),
+            |a: &Address, zip: String| Address { zip, ..a.clone() },
+        )
+    }
+
+    #[test]
+    fn test_optional_compose_lens() {
+        let user_zip: Optional<User, String> = address_optional().compose(zip_lens());
+
+        let mut user = User {
+            name: "Alice".to_string(),
+            age: 30,
+            address: Some(Address {
+                city: "NYC".to_string(),
+                zip: "10001".to_string(),
+            }),
+        };
+        assert_eq!(user_zip.get(&user), Some("10001".to_string()));
+        let moved = user_zip.set(&user, "10002".to_string());
+        assert_eq!(moved.address.unwrap().zip, "10002");
+
+        user.address = None;
+        assert_eq!(user_zip.get(&user), None);
+        assert_eq!(user_zip.set(&user, "10002".to_string()), user);
+    }
+
+    #[test]
+    fn test_lens_compose_optional() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Account {
+            owner: User,
+        }
+
+        let owner = Lens::new(
+            |a: &Account| a.owner.clone(),
+            |_: &Account, owner: User| Account { owner },
+        );
+        let owner_address: Optional<Account, Address> = owner.compose(address_optional());
+        let owner_zip = owner_address.compose(zip_lens());
+
+        let account = Account {
+            owner: User {
+                name: "Bob".to_string(),
+                age: 41,
+                address: None,
+            },
+        };
+        assert_eq!(owner_zip.get(&account), None);
+        assert_eq!(owner_zip.over(&account, |z| z + "-0000"), account);
+    }
+
+    #[test]
+    fn test_optional_compose_optional() {
+        let first_char = Optional::new(
+            |s: &String| s.chars().next(),
+            |s: &String, c: char| {
+                let mut out = c.to_string();
+                out.extend(s.chars().skip(1));
+                out
+            },
+        );
+        let head = Optional::new(
+            |v: &Vec<String>| v.first().cloned(),
+            |v: &Vec<String>, x: String| {
+                let mut v = v.clone();
+                if let Some(first) = v.first_mut() {
+                    *first = x;
+                }
+                v
+            },
+        );
+        let initial = head.compose(first_char);
+
+        let names = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(initial.get(&names), Some('a'));
+        assert_eq!(
+            initial.over(&names, |c| c.to_ascii_uppercase()),
+            vec!["Alice".to_string(), "bob".to_string()]
+        );
+        assert_eq!(initial.get(&vec![String::new()]), None);
+        assert_eq!(initial.get(&Vec::new()), None);
+    }
+
+    #[test]
+    fn test_traversal_compose_prism() {
+        let circles: Traversal<Vec<Shape>, f64> = Traversal::each().compose(circle_prism());
+        let shapes = vec![
+            Shape::Circle(1.0),
+            Shape::Rectangle(2.0, 3.0),
+            Shape::Circle(4.0),
+        ];
+
+        assert_eq!(circles.get_all(&shapes), vec![1.0, 4.0]);
+        assert_eq!(
+            circles.over(&shapes, |r| r * 2.0),
+            vec![
+                Shape::Circle(2.0),
+                Shape::Rectangle(2.0, 3.0),
+                Shape::Circle(8.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_with_fold_is_read_only() {
+        let widths = Fold::new(|shapes: &Vec<Shape>| shapes.clone())
+            .compose(rectangle_prism())
+            .compose(width_lens());
+        let shapes = vec![Shape::Rectangle(2.0, 3.0), Shape::Circle(1.0)];
+        assert_eq!(widths.fold_of(&shapes), vec![2.0]);
+
+        let radii = circle_prism().compose(Fold::new(|r: &f64| vec![*r, r * 2.0]));
+        assert_eq!(radii.fold_of(&Shape::Circle(1.5)), vec![1.5, 3.0]);
+        assert!(radii.fold_of(&Shape::Triangle(1.0, 1.0, 1.0)).is_empty());
+    }
+
     // Property-based tests for Prism laws
     #[cfg(not(target_arch = "wasm32"))]
     mod prism_laws_properties {
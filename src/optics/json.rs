@@ -0,0 +1,201 @@
+//! Optics over [`serde_json::Value`].
+//!
+//! `key` and `index` step into objects and arrays, `json_path` follows a
+//! whole path with the same rules as [`records::path`](crate::records::path),
+//! and `as_str`, `as_f64`, `as_i64` and `as_bool` are prisms onto scalar
+//! values. They compose like any other optic, so native pipelines can focus
+//! on nested JSON the way `path()` does in JavaScript:
+//!
+//! ```rust
+//! use orlando_transducers::optics::json::{as_str, index, key};
+//! use serde_json::json;
+//!
+//! let first_tag = key("tags").compose(index(0)).compose(as_str());
+//! let post = json!({"title": "Optics", "tags": ["rust", "fp"]});
+//!
+//! assert_eq!(first_tag.get(&post), Some("rust".to_string()));
+//! assert_eq!(
+//!     first_tag.over(&post, |t| t.to_uppercase()),
+//!     json!({"title": "Optics", "tags": ["RUST", "fp"]})
+//! );
+//! ```
+//!
+//! A missing index or path focuses on nothing, and setting it returns the
+//! value unchanged. `key` adds a missing key to an object, so upserting each
+//! level that may be missing creates nested structure, as
+//! [`records::assoc_path`](crate::records::assoc_path) does:
+//!
+//! ```rust
+//! use orlando_transducers::optics::json::key;
+//! use serde_json::json;
+//!
+//! let zip = key("address").upsert(|| json!({})).compose(key("zip"));
+//! assert_eq!(
+//!     zip.set(&json!({"name": "Bob"}), json!("10001")),
+//!     json!({"name": "Bob", "address": {"zip": "10001"}})
+//! );
+//! ```
+//!
+//! Requires the `json` feature.
+
+use super::{Optional, Prism};
+use crate::records::{path, path_mut};
+use serde_json::{Number, Value};
+
+/// An Optional focusing on the value under `name` in a JSON object.
+///
+/// Like `optional(prop)` in JavaScript, setting a key the object doesn't have
+/// adds it. Setting on anything but an object returns it unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::json::key;
+/// use serde_json::json;
+///
+/// let name = key("name");
+/// assert_eq!(name.get(&json!({"name": "Ada"})), Some(json!("Ada")));
+/// assert_eq!(name.get(&json!([1, 2])), None);
+/// assert_eq!(name.set(&json!({}), json!("Ada")), json!({"name": "Ada"}));
+/// ```
+pub fn key(name: &str) -> Optional<Value, Value> {
+    let get_name = name.to_string();
+    let set_name = name.to_string();
+    Optional::new(
+        move |v: &Value| v.get(&get_name).cloned(),
+        move |v: &Value, x: Value| {
+            let mut v = v.clone();
+            if let Some(map) = v.as_object_mut() {
+                map.insert(set_name.clone(), x);
+            }
+            v
+        },
+    )
+}
+
+/// An Optional focusing on element `i` of a JSON array.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::json::index;
+/// use serde_json::json;
+///
+/// assert_eq!(index(1).get(&json!([10, 20])), Some(json!(20)));
+/// assert_eq!(index(1).set(&json!([10, 20]), json!(0)), json!([10, 0]));
+/// ```
+pub fn index(i: usize) -> Optional<Value, Value> {
+    Optional::new(
+        move |v: &Value| v.as_array().and_then(|items| items.get(i)).cloned(),
+        move |v: &Value, x: Value| {
+            let mut v = v.clone();
+            if let Some(slot) = v.as_array_mut().and_then(|items| items.get_mut(i)) {
+                *slot = x;
+            }
+            v
+        },
+    )
+}
+
+/// An Optional following a path of object keys and array indices.
+///
+/// Segments are resolved as in [`records::path`](crate::records::path): on
+/// an array, a segment that parses as an index addresses that element.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::json::json_path;
+/// use serde_json::json;
+///
+/// let city = json_path(&["users", "0", "address", "city"]);
+/// let doc = json!({"users": [{"address": {"city": "NYC"}}]});
+///
+/// assert_eq!(city.get(&doc), Some(json!("NYC")));
+/// assert_eq!(
+///     city.set(&doc, json!("LA")),
+///     json!({"users": [{"address": {"city": "LA"}}]})
+/// );
+/// ```
+pub fn json_path<S: AsRef<str>>(segments: &[S]) -> Optional<Value, Value> {
+    let get_segments: Vec<String> = segments.iter().map(|s| s.as_ref().to_string()).collect();
+    let set_segments = get_segments.clone();
+    Optional::new(
+        move |v: &Value| path(v, &get_segments).cloned(),
+        move |v: &Value, x: Value| {
+            let mut v = v.clone();
+            if let Some(slot) = path_mut(&mut v, &set_segments) {
+                *slot = x;
+            }
+            v
+        },
+    )
+}
+
+/// A Prism onto JSON strings.
+pub fn as_str() -> Prism<Value, String> {
+    Prism::new(|v: &Value| v.as_str().map(str::to_string), Value::String)
+}
+
+/// A Prism onto JSON numbers as `f64`.
+///
+/// JSON has no NaN or infinity, so reviewing a non-finite number gives
+/// `null`.
+pub fn as_f64() -> Prism<Value, f64> {
+    Prism::new(
+        |v: &Value| v.as_f64(),
+        |x: f64| Number::from_f64(x).map_or(Value::Null, Value::Number),
+    )
+}
+
+/// A Prism onto JSON numbers that are integers representable as `i64`.
+pub fn as_i64() -> Prism<Value, i64> {
+    Prism::new(|v: &Value| v.as_i64(), |x: i64| Value::Number(x.into()))
+}
+
+/// A Prism onto JSON booleans.
+pub fn as_bool() -> Prism<Value, bool> {
+    Prism::new(|v: &Value| v.as_bool(), Value::Bool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_key_and_index_compose() {
+        let doc = json!({"orders": [{"total": 12.5}, {"total": 3}]});
+        let second_total = key("orders")
+            .compose(index(1))
+            .compose(key("total"))
+            .compose(as_f64());
+
+        assert_eq!(second_total.get(&doc), Some(3.0));
+        assert_eq!(
+            second_total.over(&doc, |t| t * 2.0),
+            json!({"orders": [{"total": 12.5}, {"total": 6.0}]})
+        );
+        assert_eq!(second_total.get(&json!({"orders": []})), None);
+        assert_eq!(key("orders").compose(index(5)).set(&doc, json!(0)), doc);
+    }
+
+    #[test]
+    fn test_scalar_prisms() {
+        assert_eq!(as_str().preview(&json!("x")), Some("x".to_string()));
+        assert_eq!(as_str().preview(&json!(1)), None);
+        assert_eq!(as_i64().preview(&json!(7)), Some(7));
+        assert_eq!(as_i64().preview(&json!(7.5)), None);
+        assert_eq!(as_bool().review(true), json!(true));
+        assert_eq!(as_f64().review(f64::NAN), Value::Null);
+    }
+
+    #[test]
+    fn test_json_path_matches_records_path() {
+        let doc = json!({"a": [{"b": "deep"}]});
+        let deep = json_path(&["a", "0", "b"]).compose(as_str());
+        assert_eq!(deep.get(&doc), Some("deep".to_string()));
+        assert_eq!(json_path::<&str>(&[]).get(&doc), Some(doc.clone()));
+        assert_eq!(json_path(&["a", "x"]).set(&doc, json!(1)), doc);
+    }
+}
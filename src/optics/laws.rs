@@ -0,0 +1,149 @@
+//! Checks for the lens laws, for validating hand-written lenses.
+//!
+//! Each `check_*` function tests one law for one input and returns whether
+//! it holds, so it drops into any test framework:
+//!
+//! ```rust
+//! use orlando_transducers::optics::laws::{check_get_put, check_put_get, check_put_put};
+//! use orlando_transducers::optics::Lens;
+//!
+//! let first = Lens::new(|p: &(i32, char)| p.0, |p: &(i32, char), a| (a, p.1));
+//!
+//! assert!(check_get_put(&first, &(1, 'x')));
+//! assert!(check_put_get(&first, &(1, 'x'), 7));
+//! assert!(check_put_put(&first, &(1, 'x'), 7, 8));
+//! ```
+//!
+//! With the `proptest` feature, `assert_lens_laws` runs all three checks
+//! against generated sources and values, and `lens_law_cases` provides the
+//! inputs for custom property tests.
+
+use super::Lens;
+
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+use proptest::prelude::*;
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+use proptest::test_runner::TestRunner;
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+use std::fmt::Debug;
+
+/// GetPut: setting what you got changes nothing, `set(s, get(s)) == s`.
+pub fn check_get_put<S, A>(lens: &Lens<S, A>, source: &S) -> bool
+where
+    S: Clone + PartialEq,
+    A: Clone,
+{
+    lens.set(source, lens.get(source)) == *source
+}
+
+/// PutGet: you get back what you set, `get(set(s, a)) == a`.
+pub fn check_put_get<S, A>(lens: &Lens<S, A>, source: &S, value: A) -> bool
+where
+    S: Clone,
+    A: Clone + PartialEq,
+{
+    lens.get(&lens.set(source, value.clone())) == value
+}
+
+/// PutPut: the last set wins, `set(set(s, a1), a2) == set(s, a2)`.
+pub fn check_put_put<S, A>(lens: &Lens<S, A>, source: &S, first: A, second: A) -> bool
+where
+    S: Clone + PartialEq,
+    A: Clone,
+{
+    lens.set(&lens.set(source, first), second.clone()) == lens.set(source, second)
+}
+
+/// Inputs for the lens laws: a source and two values to set.
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+pub fn lens_law_cases<S, A>(
+    sources: impl Strategy<Value = S>,
+    values: impl Strategy<Value = A> + Clone,
+) -> impl Strategy<Value = (S, A, A)>
+where
+    S: Debug,
+    A: Debug,
+{
+    (sources, values.clone(), values)
+}
+
+/// Check all three lens laws against generated inputs.
+///
+/// # Panics
+///
+/// Panics with the (shrunk) failing input and the violated law if any law
+/// fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use orlando_transducers::optics::laws::assert_lens_laws;
+/// use orlando_transducers::optics::Lens;
+/// use proptest::prelude::*;
+///
+/// let first = Lens::new(|p: &(i32, bool)| p.0, |p: &(i32, bool), a| (a, p.1));
+/// assert_lens_laws(&first, any::<(i32, bool)>(), any::<i32>());
+/// ```
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+pub fn assert_lens_laws<S, A>(
+    lens: &Lens<S, A>,
+    sources: impl Strategy<Value = S>,
+    values: impl Strategy<Value = A> + Clone,
+) where
+    S: Clone + PartialEq + Debug,
+    A: Clone + PartialEq + Debug,
+{
+    let result = TestRunner::default().run(
+        &lens_law_cases(sources, values),
+        |(source, first, second)| {
+            prop_assert!(check_get_put(lens, &source), "GetPut failed");
+            prop_assert!(check_put_get(lens, &source, first.clone()), "PutGet failed");
+            prop_assert!(check_put_put(lens, &source, first, second), "PutPut failed");
+            Ok(())
+        },
+    );
+    if let Err(error) = result {
+        panic!("lens laws violated: {}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first() -> Lens<(i32, char), i32> {
+        Lens::new(|p: &(i32, char)| p.0, |p: &(i32, char), a| (a, p.1))
+    }
+
+    // Adds to the focus instead of replacing it, breaking all three laws
+    fn accumulating() -> Lens<(i32, char), i32> {
+        Lens::new(|p: &(i32, char)| p.0, |p: &(i32, char), a| (p.0 + a, p.1))
+    }
+
+    #[test]
+    fn test_checks_accept_lawful_lens() {
+        assert!(check_get_put(&first(), &(3, 'a')));
+        assert!(check_put_get(&first(), &(3, 'a'), 9));
+        assert!(check_put_put(&first(), &(3, 'a'), 9, -1));
+    }
+
+    #[test]
+    fn test_checks_reject_unlawful_lens() {
+        assert!(!check_get_put(&accumulating(), &(3, 'a')));
+        assert!(!check_put_get(&accumulating(), &(3, 'a'), 9));
+        assert!(!check_put_put(&accumulating(), &(3, 'a'), 9, 1));
+    }
+
+    #[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_assert_lens_laws_passes() {
+        assert_lens_laws(&first(), any::<(i32, char)>(), any::<i32>());
+    }
+
+    #[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+    #[test]
+    #[should_panic(expected = "lens laws violated")]
+    fn test_assert_lens_laws_reports_violation() {
+        assert_lens_laws(&accumulating(), any::<(i32, char)>(), -100i32..100);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides JavaScript bindings for functional lenses,
 //! allowing type-safe access and updates to nested data structures.
 
-use js_sys::{Function, Object, Reflect};
+use js_sys::{Array, Function, Object, Reflect};
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -120,6 +120,86 @@ impl JsLens {
             }),
         }
     }
+
+    /// View this lens as an optional, so it can be composed with optionals.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const city = lens('user').toOptional().compose(optional('address')).compose(optional('city'));
+    /// ```
+    #[wasm_bindgen(js_name = toOptional)]
+    pub fn to_optional(&self) -> JsOptional {
+        JsOptional {
+            get_fn: self.get_fn.clone(),
+            set_fn: self.set_fn.clone(),
+        }
+    }
+
+    /// View the lens as a traversal with exactly one focus, so it can be
+    /// composed with traversals.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const prices = lens('items').toTraversal().compose(each()).compose(lens('price').toTraversal());
+    /// prices.overAll(order, p => p * 0.9);
+    /// ```
+    #[wasm_bindgen(js_name = toTraversal)]
+    pub fn to_traversal(&self) -> JsTraversal {
+        let get = self.get_fn.clone();
+        let get_2 = self.get_fn.clone();
+        let set = self.set_fn.clone();
+
+        JsTraversal {
+            get_all_fn: Rc::new(move |source: &JsValue| Array::of1(&get(source)).into()),
+            over_all_fn: Rc::new(move |source: &JsValue, f: &Function| {
+                let current = get_2(source);
+                let updated = f
+                    .call1(&JsValue::null(), &current)
+                    .unwrap_or_else(|_| current.clone());
+                set(source, updated)
+            }),
+        }
+    }
+}
+
+/// Create a lens from a getter and a setter function.
+///
+/// The setter receives the source and the new value and must return an
+/// updated copy of the source rather than modifying it.
+///
+/// # Arguments
+///
+/// * `getter` - `source => focus`
+/// * `setter` - `(source, value) => newSource`
+///
+/// # Examples
+///
+/// ```javascript
+/// const celsius = lensFrom(
+///   t => (t.fahrenheit - 32) / 1.8,
+///   (t, c) => ({ ...t, fahrenheit: c * 1.8 + 32 }),
+/// );
+/// celsius.set({ fahrenheit: 32 }, 100); // { fahrenheit: 212 }
+/// ```
+#[wasm_bindgen(js_name = lensFrom)]
+pub fn lens_from(getter: &Function, setter: &Function) -> JsLens {
+    let getter = getter.clone();
+    let setter = setter.clone();
+
+    JsLens {
+        get_fn: Rc::new(move |source: &JsValue| {
+            getter
+                .call1(&JsValue::null(), source)
+                .unwrap_or(JsValue::undefined())
+        }),
+        set_fn: Rc::new(move |source: &JsValue, value: JsValue| {
+            setter
+                .call2(&JsValue::null(), source, &value)
+                .unwrap_or_else(|_| source.clone())
+        }),
+    }
 }
 
 /// Create a lens that focuses on a property of an object.
@@ -170,7 +250,7 @@ pub fn lens(prop: &str) -> JsLens {
 ///
 /// # Arguments
 ///
-/// * `path` - Array of property names representing the path
+/// * `path` - Array of property names, or a dot-separated path string
 ///
 /// # Returns
 ///
@@ -182,12 +262,23 @@ pub fn lens(prop: &str) -> JsLens {
 /// const cityLens = lensPath(['address', 'city']);
 /// const user = { name: "Alice", address: { city: "NYC", zip: "10001" } };
 /// console.log(cityLens.get(user)); // "NYC"
+/// console.log(lensPath('address.zip').get(user)); // "10001"
 /// ```
 #[wasm_bindgen(js_name = lensPath)]
 pub fn lens_path(path: &JsValue) -> Result<JsLens, JsValue> {
-    let arr = path
-        .dyn_ref::<js_sys::Array>()
-        .ok_or_else(|| JsValue::from_str("path must be an array"))?;
+    let split;
+    let arr = match path.as_string() {
+        Some(dotted) => {
+            split = dotted
+                .split('.')
+                .map(JsValue::from)
+                .collect::<js_sys::Array>();
+            &split
+        }
+        None => path
+            .dyn_ref::<js_sys::Array>()
+            .ok_or_else(|| JsValue::from_str("path must be an array or a string"))?,
+    };
 
     if arr.length() == 0 {
         return Err(JsValue::from_str("path cannot be empty"));
@@ -313,6 +404,82 @@ impl JsOptional {
             self.set(source, updated)
         }
     }
+
+    /// Compose two optionals to focus deeper into nested structures.
+    ///
+    /// The composition focuses on nothing if either level is missing, in
+    /// which case `set` and `over` return the source unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const city = optional('address').compose(optional('city'));
+    /// city.get({ name: "Bob" });                   // undefined
+    /// city.set({ address: { city: "NYC" } }, "LA"); // { address: { city: "LA" } }
+    /// ```
+    #[wasm_bindgen]
+    pub fn compose(&self, other: &JsOptional) -> JsOptional {
+        let self_get = self.get_fn.clone();
+        let self_set = self.set_fn.clone();
+        let other_get = other.get_fn.clone();
+        let other_set = other.set_fn.clone();
+
+        // Clone for the setter closure
+        let self_get_2 = self_get.clone();
+
+        JsOptional {
+            get_fn: Rc::new(move |source: &JsValue| {
+                let intermediate = self_get(source);
+                if intermediate.is_undefined() || intermediate.is_null() {
+                    JsValue::undefined()
+                } else {
+                    other_get(&intermediate)
+                }
+            }),
+            set_fn: Rc::new(move |source: &JsValue, value: JsValue| {
+                let intermediate = self_get_2(source);
+                if intermediate.is_undefined() || intermediate.is_null() {
+                    source.clone()
+                } else {
+                    self_set(source, other_set(&intermediate, value))
+                }
+            }),
+        }
+    }
+
+    /// Turn this optional into a lens that creates the focus when it's
+    /// missing.
+    ///
+    /// A missing (`undefined` or `null`) focus reads as `defaultFn()`, and
+    /// `set`/`over` write the property even when it wasn't there. Upsert each
+    /// level that may be missing to build intermediate objects on `set`,
+    /// like Ramda's `assocPath`; a composition of optionals still leaves a
+    /// missing level alone.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const zip = optional('address').upsert(() => ({})).compose(lens('zip'));
+    /// zip.set({ name: "Bob" }, "10001");  // { name: "Bob", address: { zip: "10001" } }
+    /// ```
+    #[wasm_bindgen]
+    pub fn upsert(&self, default_fn: &Function) -> JsLens {
+        let get = self.get_fn.clone();
+        let default_fn = default_fn.clone();
+        JsLens {
+            get_fn: Rc::new(move |source: &JsValue| {
+                let value = get(source);
+                if value.is_undefined() || value.is_null() {
+                    default_fn
+                        .call0(&JsValue::null())
+                        .unwrap_or(JsValue::undefined())
+                } else {
+                    value
+                }
+            }),
+            set_fn: self.set_fn.clone(),
+        }
+    }
 }
 
 /// Create an optional lens that focuses on a property that may not exist.
@@ -416,6 +583,37 @@ impl JsPrism {
             self.review(updated)
         }
     }
+
+    /// View the prism as an optional focusing on the variant's value.
+    ///
+    /// Setting only replaces a matching variant; other variants are returned
+    /// unchanged. Use it to compose prisms with lenses and optionals.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const okValue = optional('result').compose(okPrism.toOptional());
+    /// okValue.get({ result: { tag: 'ok', value: 42 } });       // 42
+    /// okValue.set({ result: { tag: 'err', error: 'x' } }, 1);  // unchanged
+    /// ```
+    #[wasm_bindgen(js_name = toOptional)]
+    pub fn to_optional(&self) -> JsOptional {
+        let preview = self.preview_fn.clone();
+        let matches = self.preview_fn.clone();
+        let review = self.review_fn.clone();
+
+        JsOptional {
+            get_fn: Rc::new(move |source: &JsValue| preview(source)),
+            set_fn: Rc::new(move |source: &JsValue, value: JsValue| {
+                let current = matches(source);
+                if current.is_undefined() || current.is_null() {
+                    source.clone()
+                } else {
+                    review(value)
+                }
+            }),
+        }
+    }
 }
 
 /// Create a prism from match and build functions.
@@ -509,6 +707,39 @@ impl JsIso {
             from_fn: Rc::new(move |s: JsValue| (original_to)(&s)),
         }
     }
+
+    /// Compose two isomorphisms: convert with this one, then with `other`.
+    #[wasm_bindgen]
+    pub fn compose(&self, other: &JsIso) -> JsIso {
+        let outer_to = self.to_fn.clone();
+        let outer_from = self.from_fn.clone();
+        let inner_to = other.to_fn.clone();
+        let inner_from = other.from_fn.clone();
+
+        JsIso {
+            to_fn: Rc::new(move |s: &JsValue| inner_to(&outer_to(s))),
+            from_fn: Rc::new(move |b: JsValue| outer_from(inner_from(b))),
+        }
+    }
+
+    /// View the isomorphism as a lens, so it can be composed with lenses.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const fahrenheit = lens('celsius').compose(iso(c => c * 1.8 + 32, f => (f - 32) / 1.8).toLens());
+    /// fahrenheit.get({ celsius: 100 }); // 212
+    /// ```
+    #[wasm_bindgen(js_name = toLens)]
+    pub fn to_lens(&self) -> JsLens {
+        let to_fn = self.to_fn.clone();
+        let from_fn = self.from_fn.clone();
+
+        JsLens {
+            get_fn: Rc::new(move |source: &JsValue| to_fn(source)),
+            set_fn: Rc::new(move |_source: &JsValue, value: JsValue| from_fn(value)),
+        }
+    }
 }
 
 /// Create an isomorphism from to and from functions.
@@ -664,6 +895,99 @@ impl JsTraversal {
             .unchecked_into();
         (self.over_all_fn)(source, &bound)
     }
+
+    /// Combine all focused values, left to right, into a single result.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// each().fold([1, 2, 3], (total, x) => total + x, 0); // 6
+    /// ```
+    #[wasm_bindgen]
+    pub fn fold(&self, source: &JsValue, reducer: &Function, initial: JsValue) -> JsValue {
+        let this = JsValue::null();
+        self.get_all(source)
+            .dyn_into::<Array>()
+            .map(|foci| {
+                foci.iter().fold(initial.clone(), |acc, value| {
+                    reducer.call2(&this, &acc, &value).unwrap_or(acc)
+                })
+            })
+            .unwrap_or(initial)
+    }
+
+    /// Compose two traversals, focusing on every inner focus of every outer
+    /// focus.
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const allTags = each().compose(lens('tags').toTraversal()).compose(each());
+    /// allTags.getAll(articles);                         // every tag of every article
+    /// allTags.overAll(articles, t => t.toLowerCase());
+    /// ```
+    #[wasm_bindgen]
+    pub fn compose(&self, other: &JsTraversal) -> JsTraversal {
+        let outer_get = self.get_all_fn.clone();
+        let outer_over = self.over_all_fn.clone();
+        let inner_get = other.get_all_fn.clone();
+        let inner_over = other.over_all_fn.clone();
+
+        JsTraversal {
+            get_all_fn: Rc::new(move |source: &JsValue| {
+                let foci = Array::new();
+                if let Ok(outer) = outer_get(source).dyn_into::<Array>() {
+                    for value in outer.iter() {
+                        if let Ok(inner) = inner_get(&value).dyn_into::<Array>() {
+                            inner.for_each(&mut |focus, _, _| {
+                                foci.push(&focus);
+                            });
+                        }
+                    }
+                }
+                foci.into()
+            }),
+            over_all_fn: Rc::new(move |source: &JsValue, f: &Function| {
+                let inner_over = inner_over.clone();
+                let f = f.clone();
+                let over_inner =
+                    Closure::<dyn FnMut(JsValue) -> JsValue>::new(move |value: JsValue| {
+                        inner_over(&value, &f)
+                    });
+                outer_over(source, over_inner.as_ref().unchecked_ref())
+            }),
+        }
+    }
+}
+
+/// A traversal focusing on every element of an array.
+///
+/// # Examples
+///
+/// ```javascript
+/// each().overAll([1, 2, 3], x => x * 2); // [2, 4, 6]
+/// ```
+#[wasm_bindgen]
+pub fn each() -> JsTraversal {
+    JsTraversal {
+        get_all_fn: Rc::new(|source: &JsValue| match source.dyn_ref::<Array>() {
+            Some(array) => array.slice(0, array.length()).into(),
+            None => Array::new().into(),
+        }),
+        over_all_fn: Rc::new(
+            |source: &JsValue, f: &Function| match source.dyn_ref::<Array>() {
+                Some(array) => array
+                    .iter()
+                    .map(|value| {
+                        f.call1(&JsValue::null(), &value)
+                            .unwrap_or_else(|_| value.clone())
+                    })
+                    .collect::<Array>()
+                    .into(),
+                None => source.clone(),
+            },
+        ),
+    }
 }
 
 /// Create a traversal from getAll and overAll functions.
@@ -0,0 +1,693 @@
+//! # Parallel: Running pipelines on several threads
+//!
+//! [`broadcast`] reads a source once and feeds every element to several
+//! pipelines, each running on its own thread, and returns all of their
+//! results together. It is the fan-out of structured concurrency: the threads
+//! are scoped to the call, so they have all finished by the time it returns.
+//!
+//! ```rust
+//! use orlando_transducers::parallel::broadcast;
+//! use orlando_transducers::{Filter, Map, Take};
+//!
+//! let (evens, squares, first) = broadcast(
+//!     1..=6,
+//!     (
+//!         || Filter::new(|x: &i32| x % 2 == 0),
+//!         || Map::new(|x: i32| x * x),
+//!         || Take::new(2),
+//!     ),
+//! );
+//! assert_eq!(evens, vec![2, 4, 6]);
+//! assert_eq!(squares, vec![1, 4, 9, 16, 25, 36]);
+//! assert_eq!(first, vec![1, 2]);
+//! ```
+//!
+//! Pipelines hold their functions in `Rc`s and can't move between threads,
+//! so each branch is given as a function that builds its pipeline; it is
+//! called on the branch's own thread. Elements are cloned once per branch and
+//! sent in batches, so the source is read ahead of the slowest branch by at
+//! most a few batches.
+//!
+//! [`par_group_reduce`] is the map-reduce counterpart: the pipeline runs on
+//! the calling thread, its output is hashed by key to one of several shards,
+//! and each shard folds its own groups with an [`Aggregator`]. Since a key
+//! only ever reaches one shard, merging the shards is just collecting their
+//! groups into one map:
+//!
+//! ```rust
+//! use orlando_transducers::aggregate;
+//! use orlando_transducers::parallel::par_group_reduce;
+//! use orlando_transducers::Map;
+//!
+//! let words = "the cat sat on the mat the end".split(' ').map(String::from);
+//! let lengths = Map::new(|w: String| (w.len(), w));
+//! let by_length = par_group_reduce(&lengths, words, 4, |(len, _)| *len, aggregate::count());
+//! assert_eq!(by_length[&3], 7);
+//! assert_eq!(by_length[&2], 1);
+//! ```
+//!
+//! [`par_to_vec_ordered`] and [`par_to_vec_unordered`] split one pipeline's
+//! work instead: the source is cut into chunks, and each worker thread takes
+//! the next chunk as soon as it has finished its last, so a few slow chunks
+//! don't leave the other workers idle. The ordered mode puts the chunks'
+//! output back in input order; the unordered mode skips that, for results
+//! that are summed, counted or otherwise combined in any order.
+//!
+//! Native targets only.
+
+use crate::aggregate::Aggregator;
+use crate::collectors::reduce;
+use crate::step::{cont, stop, Step};
+use crate::transducer::Transducer;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+use std::panic;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::Mutex;
+use std::thread::{self, Scope, ScopedJoinHandle};
+
+/// Elements sent to a branch or shard, or run by a worker, at a time.
+const BATCH: usize = 1024;
+
+/// Batches a branch or shard may have queued before the source waits for it.
+const QUEUED_BATCHES: usize = 4;
+
+/// A shard of [`par_group_reduce`]: the channel that sends it elements with
+/// their keys, and its thread, which returns the shard's groups.
+type Shard<'scope, K, U, O> = (
+    SyncSender<Vec<(K, U)>>,
+    ScopedJoinHandle<'scope, HashMap<K, O>>,
+);
+
+/// Pipelines to [`broadcast`] a source to: tuples of up to eight functions
+/// that build a pipeline, collecting into a tuple of `Vec`s.
+pub trait Branches<T, Out> {
+    /// Feed every element of `source` to each branch, on a thread per
+    /// branch.
+    fn run<I: Iterator<Item = T>>(self, source: I) -> Out;
+}
+
+/// Feed every element of `source` to several pipelines at once, each on its
+/// own thread, and collect the output of each.
+///
+/// `branches` is a tuple of functions that build the pipelines; the result
+/// is a tuple of their outputs in the same order. The source is read once,
+/// and only until every branch has stopped (`take`, `take_while`), so it can
+/// be infinite as long as they all stop. A panic in a branch is resumed on
+/// the calling thread once the others have finished.
+///
+/// See the [module docs](self) for an example.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::parallel::broadcast;
+/// use orlando_transducers::{Filter, Take, Transducer};
+///
+/// let (small, large) = broadcast(
+///     0u64..,
+///     (
+///         || Filter::new(|x: &u64| *x < 10).compose(Take::new(3)),
+///         || Filter::new(|x: &u64| *x > 1_000).compose(Take::new(2)),
+///     ),
+/// );
+/// assert_eq!(small, vec![0, 1, 2]);
+/// assert_eq!(large, vec![1_001, 1_002]);
+/// ```
+pub fn broadcast<T, Iter, B, Out>(source: Iter, branches: B) -> Out
+where
+    Iter: IntoIterator<Item = T>,
+    B: Branches<T, Out>,
+{
+    branches.run(source.into_iter())
+}
+
+/// Start a branch on its own thread: it builds its pipeline with `make` and
+/// folds the batches it receives into a `Vec`, returning early (and so
+/// closing its channel) once the pipeline stops.
+fn spawn_branch<'scope, T, U, F, P>(
+    scope: &'scope Scope<'scope, '_>,
+    make: F,
+) -> (SyncSender<Vec<T>>, ScopedJoinHandle<'scope, Vec<U>>)
+where
+    T: Send + 'static,
+    U: Send + 'static,
+    F: FnOnce() -> P + Send + 'scope,
+    P: Transducer<T, U>,
+{
+    let (sender, receiver) = sync_channel::<Vec<T>>(QUEUED_BATCHES);
+    let handle = scope.spawn(move || {
+        let reducer = make().apply(push);
+        let mut output = Vec::new();
+        for batch in receiver {
+            for item in batch {
+                match reducer(output, item) {
+                    Step::Continue(acc) => output = acc,
+                    Step::Stop(acc) => return acc,
+                }
+            }
+        }
+        output
+    });
+    (sender, handle)
+}
+
+/// Read `source` in batches, sending a copy of each batch to every branch
+/// still running. Stops early once no branch is.
+fn feed<T: Clone>(source: impl Iterator<Item = T>, senders: Vec<SyncSender<Vec<T>>>) {
+    let mut senders: Vec<Option<SyncSender<Vec<T>>>> = senders.into_iter().map(Some).collect();
+    let mut send = |batch: Vec<T>| {
+        for slot in senders.iter_mut() {
+            // A branch that has stopped has dropped its receiver
+            if slot
+                .as_ref()
+                .is_some_and(|s| s.send(batch.clone()).is_err())
+            {
+                *slot = None;
+            }
+        }
+        senders.iter().any(Option::is_some)
+    };
+
+    let mut batch = Vec::with_capacity(BATCH);
+    for item in source {
+        batch.push(item);
+        if batch.len() == BATCH && !send(mem::replace(&mut batch, Vec::with_capacity(BATCH))) {
+            return;
+        }
+    }
+    if !batch.is_empty() {
+        send(batch);
+    }
+}
+
+/// A thread's result, resuming its panic if it had one.
+fn join<R>(handle: ScopedJoinHandle<'_, R>) -> R {
+    handle
+        .join()
+        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+macro_rules! branches {
+    ($($make:ident $pipeline:ident $out:ident $index:tt),+) => {
+        impl<T, $($make, $pipeline, $out),+> Branches<T, ($(Vec<$out>,)+)> for ($($make,)+)
+        where
+            T: Clone + Send + 'static,
+            $(
+                $make: FnOnce() -> $pipeline + Send,
+                $pipeline: Transducer<T, $out>,
+                $out: Send + 'static,
+            )+
+        {
+            fn run<I: Iterator<Item = T>>(self, source: I) -> ($(Vec<$out>,)+) {
+                thread::scope(|scope| {
+                    let branches = ($(spawn_branch(scope, self.$index),)+);
+                    // Moving the senders in lets each branch see the end of
+                    // the source once `feed` drops them
+                    feed(source, vec![$(branches.$index.0),+]);
+                    ($(join(branches.$index.1),)+)
+                })
+            }
+        }
+    };
+}
+
+branches!(F0 P0 U0 0);
+branches!(F0 P0 U0 0, F1 P1 U1 1);
+branches!(F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2);
+branches!(F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2, F3 P3 U3 3);
+branches!(F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2, F3 P3 U3 3, F4 P4 U4 4);
+branches!(F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2, F3 P3 U3 3, F4 P4 U4 4, F5 P5 U5 5);
+branches!(
+    F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2, F3 P3 U3 3, F4 P4 U4 4, F5 P5 U5 5, F6 P6 U6 6
+);
+branches!(
+    F0 P0 U0 0, F1 P1 U1 1, F2 P2 U2 2, F3 P3 U3 3, F4 P4 U4 4, F5 P5 U5 5, F6 P6 U6 6,
+    F7 P7 U7 7
+);
+
+/// Group the output of a pipeline by key and fold each group with an
+/// aggregator, spreading the groups over `shards` threads.
+///
+/// The pipeline and `key_fn` run on the calling thread. Each element is sent,
+/// with its key, to the shard its key hashes to, and that shard keeps the
+/// aggregator state for every key it owns, so groups are never split between
+/// shards and no combining step is needed. Tuples of aggregators compute
+/// several results per group. A panic in a shard stops the pipeline and is
+/// resumed on the calling thread.
+///
+/// # Panics
+///
+/// If `shards` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::aggregate;
+/// use orlando_transducers::parallel::par_group_reduce;
+/// use orlando_transducers::Filter;
+///
+/// let odd = Filter::new(|x: &u32| x % 2 == 1);
+/// let by_digit = par_group_reduce(&odd, 0..1_000, 3, |x| x % 10, (aggregate::count(), aggregate::max()));
+/// assert_eq!(by_digit.len(), 5);
+/// assert_eq!(by_digit[&7], (100, Some(997)));
+/// ```
+pub fn par_group_reduce<T, U, K, Iter, F, A>(
+    transducer: &impl Transducer<T, U>,
+    source: Iter,
+    shards: usize,
+    key_fn: F,
+    aggregator: A,
+) -> HashMap<K, A::Output>
+where
+    T: 'static,
+    U: Send + 'static,
+    K: Eq + Hash + Send + 'static,
+    Iter: IntoIterator<Item = T>,
+    F: Fn(&U) -> K + 'static,
+    A: Aggregator<U> + Sync,
+    A::Output: Send,
+{
+    assert!(shards > 0, "par_group_reduce needs at least one shard");
+
+    thread::scope(|scope| {
+        let (senders, handles): (Vec<_>, Vec<_>) =
+            (0..shards).map(|_| spawn_shard(scope, &aggregator)).unzip();
+        let initial = Shards {
+            hasher: RandomState::new(),
+            buffers: (0..shards).map(|_| Vec::with_capacity(BATCH)).collect(),
+            senders,
+        };
+
+        let reducer = move |mut shards: Shards<K, U>, x: U| {
+            let key = key_fn(&x);
+            let shard = shards.partition(&key);
+            shards.buffers[shard].push((key, x));
+            if shards.buffers[shard].len() == BATCH && !shards.send(shard) {
+                return stop(shards);
+            }
+            cont(shards)
+        };
+
+        let mut shards = reduce(transducer, source, initial, reducer);
+        for shard in 0..shards.buffers.len() {
+            shards.send(shard);
+        }
+        // Dropping the senders lets each shard see the end of the input
+        drop(shards);
+
+        let mut groups = HashMap::new();
+        for handle in handles {
+            groups.extend(join(handle));
+        }
+        groups
+    })
+}
+
+/// Start a shard on its own thread, folding the keyed elements it receives
+/// into a state per key.
+fn spawn_shard<'scope, K, U, A>(
+    scope: &'scope Scope<'scope, '_>,
+    aggregator: &'scope A,
+) -> Shard<'scope, K, U, A::Output>
+where
+    K: Eq + Hash + Send + 'scope,
+    U: Send + 'scope,
+    A: Aggregator<U> + Sync,
+    A::Output: Send,
+{
+    let (sender, receiver) = sync_channel::<Vec<(K, U)>>(QUEUED_BATCHES);
+    let handle = scope.spawn(move || {
+        // `step` takes the state by value; the `Option` lets it be moved
+        // out of the map and back without hashing the key twice
+        let mut states: HashMap<K, Option<A::State>> = HashMap::new();
+        for batch in receiver {
+            for (key, x) in batch {
+                let slot = states.entry(key).or_insert_with(|| Some(aggregator.init()));
+                let state = slot.take().expect("state is put back after every step");
+                *slot = Some(aggregator.step(state, &x));
+            }
+        }
+        states
+            .into_iter()
+            .filter_map(|(key, state)| Some((key, aggregator.finish(state?))))
+            .collect()
+    });
+    (sender, handle)
+}
+
+/// The feeding side of [`par_group_reduce`]: a buffer and channel per shard.
+struct Shards<K, U> {
+    hasher: RandomState,
+    buffers: Vec<Vec<(K, U)>>,
+    senders: Vec<SyncSender<Vec<(K, U)>>>,
+}
+
+impl<K: Hash, U> Shards<K, U> {
+    /// The shard that owns `key`.
+    fn partition(&self, key: &K) -> usize {
+        (self.hasher.hash_one(key) % self.buffers.len() as u64) as usize
+    }
+
+    /// Send a shard its buffered elements, returning false if it has
+    /// panicked and hung up.
+    fn send(&mut self, shard: usize) -> bool {
+        let batch = mem::replace(&mut self.buffers[shard], Vec::with_capacity(BATCH));
+        batch.is_empty() || self.senders[shard].send(batch).is_ok()
+    }
+}
+
+/// Run a pipeline over `source` on `workers` threads, a chunk at a time,
+/// and collect its output in input order.
+///
+/// Each worker builds the pipeline with `make` and takes the next chunk of
+/// the source whenever it is free. The pipeline is applied afresh to each
+/// chunk, so it should work element by element (`map`, `filter`,
+/// `flat_map`, ...): stateful stages such as `take`, `scan` or `unique` see
+/// every chunk on its own. A panic in a worker is resumed on the calling
+/// thread once the others have finished.
+///
+/// # Panics
+///
+/// If `workers` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::parallel::par_to_vec_ordered;
+/// use orlando_transducers::{Filter, Map, Transducer};
+///
+/// let primes = par_to_vec_ordered(2..10_000u32, 4, || {
+///     Filter::new(|n: &u32| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+///         .compose(Map::new(|n: u32| n * 10))
+/// });
+/// assert_eq!(primes[..5], [20, 30, 50, 70, 110]);
+/// assert_eq!(primes.len(), 1_229);
+/// ```
+pub fn par_to_vec_ordered<T, U, Iter, F, P>(source: Iter, workers: usize, make: F) -> Vec<U>
+where
+    T: Send + 'static,
+    U: Send + 'static,
+    Iter: IntoIterator<Item = T>,
+    Iter::IntoIter: Send,
+    F: Fn() -> P + Sync,
+    P: Transducer<T, U>,
+{
+    let mut chunks: Vec<(usize, Vec<U>)> =
+        run_chunked::<_, _, _, _, _, Vec<(usize, Vec<U>)>>(source.into_iter(), workers, make)
+            .into_iter()
+            .flatten()
+            .collect();
+    chunks.sort_unstable_by_key(|(index, _)| *index);
+    chunks.into_iter().flat_map(|(_, output)| output).collect()
+}
+
+/// Like [`par_to_vec_ordered`], but with the output in no particular order.
+///
+/// Each worker appends the output of all its chunks to one `Vec`, so there
+/// is no per-chunk buffering or reordering. Use it when the result is
+/// combined in a way that doesn't depend on order, or sorted afterwards.
+///
+/// # Panics
+///
+/// If `workers` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::parallel::par_to_vec_unordered;
+/// use orlando_transducers::Map;
+///
+/// let squares = par_to_vec_unordered(1..=100_000u64, 4, || Map::new(|x: u64| x * x));
+/// assert_eq!(squares.len(), 100_000);
+/// assert_eq!(squares.iter().sum::<u64>(), 333_338_333_350_000);
+/// ```
+pub fn par_to_vec_unordered<T, U, Iter, F, P>(source: Iter, workers: usize, make: F) -> Vec<U>
+where
+    T: Send + 'static,
+    U: Send + 'static,
+    Iter: IntoIterator<Item = T>,
+    Iter::IntoIter: Send,
+    F: Fn() -> P + Sync,
+    P: Transducer<T, U>,
+{
+    run_chunked::<_, _, _, _, _, Vec<U>>(source.into_iter(), workers, make)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Run `make`'s pipeline over `source` on `workers` threads, each taking
+/// the next chunk whenever it is free, and return what each thread kept.
+fn run_chunked<T, U, I, F, P, O>(source: I, workers: usize, make: F) -> Vec<O>
+where
+    T: Send + 'static,
+    U: 'static,
+    I: Iterator<Item = T> + Send,
+    F: Fn() -> P + Sync,
+    P: Transducer<T, U>,
+    O: ChunkOutput<U>,
+{
+    assert!(workers > 0, "parallel runs need at least one worker");
+
+    let queue = Mutex::new(Chunks { source, next: 0 });
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let pipeline = make();
+                    let mut kept = O::default();
+                    while let Some((index, chunk)) = next_chunk(&queue) {
+                        // Applied per chunk, so each starts with fresh state
+                        let reducer = pipeline.apply(push);
+                        let mut output = kept.start();
+                        for item in chunk {
+                            match reducer(output, item) {
+                                Step::Continue(acc) => output = acc,
+                                Step::Stop(acc) => {
+                                    output = acc;
+                                    break;
+                                }
+                            }
+                        }
+                        kept.finish(index, output);
+                    }
+                    kept
+                })
+            })
+            .collect();
+        handles.into_iter().map(join).collect()
+    })
+}
+
+/// The source of a chunked run, shared by its workers.
+struct Chunks<I> {
+    source: I,
+    next: usize,
+}
+
+/// The next chunk of the source and its position, or `None` once the source
+/// is exhausted (or has panicked while another worker was reading it).
+fn next_chunk<I: Iterator>(queue: &Mutex<Chunks<I>>) -> Option<(usize, Vec<I::Item>)> {
+    let mut chunks = queue.lock().ok()?;
+    let chunk: Vec<I::Item> = chunks.source.by_ref().take(BATCH).collect();
+    if chunk.is_empty() {
+        return None;
+    }
+    let index = chunks.next;
+    chunks.next += 1;
+    Some((index, chunk))
+}
+
+/// How a worker of a chunked run keeps the output of the chunks it runs.
+trait ChunkOutput<U>: Default + Send {
+    /// The `Vec` to collect the next chunk's output into.
+    fn start(&mut self) -> Vec<U>;
+
+    /// Keep the output of the chunk at `index`.
+    fn finish(&mut self, index: usize, output: Vec<U>);
+}
+
+/// Unordered: every chunk's output goes on the end of one `Vec`.
+impl<U: Send> ChunkOutput<U> for Vec<U> {
+    fn start(&mut self) -> Vec<U> {
+        mem::take(self)
+    }
+
+    fn finish(&mut self, _index: usize, output: Vec<U>) {
+        *self = output;
+    }
+}
+
+/// Ordered: each chunk's output is kept with its position.
+impl<U: Send> ChunkOutput<U> for Vec<(usize, Vec<U>)> {
+    fn start(&mut self) -> Vec<U> {
+        Vec::new()
+    }
+
+    fn finish(&mut self, index: usize, output: Vec<U>) {
+        self.push((index, output));
+    }
+}
+
+/// Reducer collecting a pipeline's output into a `Vec`.
+fn push<U>(mut acc: Vec<U>, x: U) -> Step<Vec<U>> {
+    acc.push(x);
+    cont(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate;
+    use crate::collectors::{group_by, to_vec};
+    use crate::transforms::{Chunk, Filter, FlatMap, Map, Scan, Take};
+
+    #[test]
+    fn test_branches_match_separate_runs() {
+        let running = || Scan::new(0u64, |acc: &u64, x: &u64| acc + x);
+        let chunks = || Map::new(|x: u64| x % 7).compose(Chunk::new(100));
+        let source: Vec<u64> = (0..10_000).collect();
+
+        let (sums, chunked) = broadcast(source.clone(), (running, chunks));
+        assert_eq!(sums, to_vec(&running(), source.clone()));
+        assert_eq!(chunked, to_vec(&chunks(), source));
+    }
+
+    #[test]
+    fn test_stops_reading_once_every_branch_stops() {
+        let (a, b) = broadcast(
+            0..,
+            (
+                || Take::new(5),
+                || Filter::new(|x: &i32| x % 1_000 == 999).compose(Take::new(3)),
+            ),
+        );
+        assert_eq!(a, vec![0, 1, 2, 3, 4]);
+        assert_eq!(b, vec![999, 1_999, 2_999]);
+    }
+
+    #[test]
+    fn test_single_branch_and_empty_source() {
+        let (out,) = broadcast(Vec::<String>::new(), (|| Map::new(|s: String| s.len()),));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "branch failed")]
+    fn test_branch_panic_is_resumed() {
+        let _ = broadcast(
+            0..10,
+            (
+                || Map::new(|x: i32| x),
+                || Map::new(|_: i32| -> i32 { panic!("branch failed") }),
+            ),
+        );
+    }
+
+    #[test]
+    fn test_group_reduce_matches_group_by() {
+        let pipeline = || Map::new(|x: u64| x * 3);
+        let grouped = par_group_reduce(
+            &pipeline(),
+            0..20_000,
+            4,
+            |x| x % 37,
+            (aggregate::sum(), aggregate::to_vec()),
+        );
+        let expected = group_by(&pipeline(), 0..20_000, |x| x % 37);
+
+        assert_eq!(grouped.len(), expected.len());
+        for (key, group) in expected {
+            let (sum, all) = &grouped[&key];
+            assert_eq!(*sum, group.iter().sum::<u64>());
+            // Elements reach their shard in stream order
+            assert_eq!(*all, group);
+        }
+    }
+
+    #[test]
+    fn test_group_reduce_stops_with_pipeline() {
+        let grouped = par_group_reduce(&Take::new(10), 0.., 2, |x: &u32| x % 2, aggregate::count());
+        assert_eq!(grouped[&0], 5);
+        assert_eq!(grouped[&1], 5);
+    }
+
+    #[test]
+    fn test_group_reduce_single_shard_and_empty_source() {
+        let id = Map::new(|s: &str| s);
+        let grouped = par_group_reduce(
+            &id,
+            vec!["a", "bb", "cc"],
+            1,
+            |s| s.len(),
+            aggregate::count(),
+        );
+        assert_eq!(grouped, HashMap::from([(1, 1), (2, 2)]));
+
+        let empty = par_group_reduce(&id, Vec::new(), 3, |s| s.len(), aggregate::count());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "shard failed")]
+    fn test_group_reduce_shard_panic_is_resumed() {
+        let failing = aggregate::fold(0, |_: i32, _: &i32| -> i32 { panic!("shard failed") });
+        let _ = par_group_reduce(&Map::new(|x: i32| x), 0.., 2, |x| *x, failing);
+    }
+
+    /// A pipeline whose cost varies a lot between elements, so chunks finish
+    /// out of order.
+    fn uneven() -> impl Transducer<u64, u64> {
+        Filter::new(|x: &u64| x % 3 != 1)
+            .compose(Map::new(|x: u64| {
+                (0..x % 500).fold(x, |acc, i| acc.wrapping_mul(31) ^ i)
+            }))
+            .compose(FlatMap::new(|x: u64| vec![x; (x % 3) as usize]))
+    }
+
+    #[test]
+    fn test_ordered_matches_serial_run() {
+        let expected = to_vec(&uneven(), 0..50_000);
+        for workers in [1, 3, 8] {
+            assert_eq!(par_to_vec_ordered(0..50_000, workers, uneven), expected);
+        }
+    }
+
+    #[test]
+    fn test_unordered_has_same_elements() {
+        let mut expected = to_vec(&uneven(), 0..50_000);
+        let mut output = par_to_vec_unordered(0..50_000, 4, uneven);
+        expected.sort_unstable();
+        output.sort_unstable();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_stateful_stages_see_each_chunk_alone() {
+        let firsts = par_to_vec_ordered(0..(3 * BATCH), 2, || Take::new(1));
+        assert_eq!(firsts, vec![0, BATCH, 2 * BATCH]);
+    }
+
+    #[test]
+    fn test_chunked_empty_source() {
+        let output = par_to_vec_ordered(Vec::<i32>::new(), 2, || Map::new(|x: i32| x));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "worker failed")]
+    fn test_worker_panic_is_resumed() {
+        let _ = par_to_vec_unordered(0..10_000, 3, || {
+            Map::new(|x: i32| {
+                if x == 5_000 {
+                    panic!("worker failed")
+                } else {
+                    x
+                }
+            })
+        });
+    }
+}
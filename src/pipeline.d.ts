@@ -0,0 +1,242 @@
+/**
+ * Anything a pipeline can consume: arrays, typed arrays, Sets, Maps,
+ * generators and other iterables, or a bare iterator.
+ */
+export type PipelineSource<T> = Iterable<T> | Iterator<T>;
+
+/** Sources accepted by the async terminals (`toArrayAsync`, `forEachAsync`). */
+export type AsyncPipelineSource<T> = AsyncIterable<T> | PipelineSource<T>;
+
+/** Operators accepted for a field in a `where` spec. */
+export interface WhereOperators {
+  $eq?: unknown;
+  $ne?: unknown;
+  $gt?: unknown;
+  $gte?: unknown;
+  $lt?: unknown;
+  $lte?: unknown;
+  $in?: readonly unknown[];
+  $nin?: readonly unknown[];
+  $exists?: boolean;
+}
+
+/**
+ * A `where` spec: each key is a field name or dotted path, each value a
+ * literal to match, an operator object, or a nested spec.
+ */
+export interface WhereSpec {
+  [field: string]: WhereOperators | WhereSpec | string | number | boolean | null;
+}
+
+/** One step of a serialized pipeline, as produced by `toPlan`. */
+export type PlanStep =
+  | { op: 'take' | 'drop' | 'chunk' | 'aperture' | 'mapAdd' | 'mapMul' | 'filterGt'; n: number }
+  | { op: 'filterRange'; lo: number; hi: number }
+  | { op: 'scaleOffset'; a: number; b: number }
+  | { op: 'normalize'; mean: number; std: number }
+  | { op: 'unique' }
+  | { op: 'interpose'; separator: unknown }
+  | { op: 'zip'; other: unknown[] }
+  | { op: 'map' | 'filter'; fn: string };
+
+/** A versioned plan, as produced by `toPlanJson` and the Rust `plan` module. */
+export interface Plan {
+  version: number;
+  steps: PlanStep[];
+}
+
+/** One stage of a pipeline, as reported by `describe`. */
+export interface PipelineStage {
+  /** The operation and its parameters, e.g. `take(3)`, or `fused` */
+  name: string;
+  stateful: boolean;
+  mayStop: boolean;
+  /** The maps and filters fused into the stage */
+  fused: Array<'map' | 'filter'>;
+}
+
+/** A value recorded by `debugRun`, with the source position being processed. */
+export interface TraceEntry<T = unknown> {
+  position: number;
+  value: T;
+}
+
+/** The result of `debugRun`. */
+export interface DebugRun<In, Out> {
+  output: Out[];
+  /** The last `n` source elements */
+  source: TraceEntry<In>[];
+  /** The last `n` values that reached and left each operation */
+  stages: { name: string; inputs: TraceEntry[]; outputs: TraceEntry[] }[];
+}
+
+/** How much one slice of `runWithBudget` may do; it ends when either runs out. */
+export interface Budget {
+  /** Source elements to read; at least one is always read */
+  maxElements?: number;
+  /** Milliseconds to run for, checked after each element */
+  maxMs?: number;
+}
+
+/** One slice of a budgeted run, from `runWithBudget` or `resume`. */
+export class BudgetRun<Out> {
+  private constructor();
+  free(): void;
+  /** What the pipeline produced during this slice */
+  readonly output: Out[];
+  /** Whether the source is exhausted or the pipeline stopped early */
+  readonly done: boolean;
+  /** Source elements read so far, over every slice */
+  readonly consumed: number;
+  /** Run the next slice; each slice can be resumed once. */
+  resume(budget: Budget): BudgetRun<Out>;
+  /** Abandon the run, closing an iterable source. */
+  cancel(): void;
+}
+
+/** A pipeline that input is pushed into a batch at a time, from `runner`. */
+export class Runner<In, Out> {
+  private constructor();
+  free(): void;
+  /** Push a batch through the pipeline, returning what it produced */
+  feed(batch: PipelineSource<In>): Out[];
+  /** End the input, returning what is still buffered (a partial `chunk`) */
+  finish(): Out[];
+  /** Whether the pipeline stopped early or `finish` was called */
+  readonly done: boolean;
+  /** Source elements consumed so far, over every batch */
+  readonly consumed: number;
+}
+
+/** The stages of a pipeline, as produced by `describe`. */
+export interface PipelineDescription {
+  stages: PipelineStage[];
+  stateful: boolean;
+  mayStop: boolean;
+  /** How many operations `freeze()` would merge away */
+  pendingFusions: number;
+}
+
+/**
+ * A composition of transducers applied to data in a single pass.
+ *
+ * `In` is the element type of the sources the pipeline accepts and `Out` the
+ * element type it produces. Builder methods return a new pipeline and never
+ * modify the receiver.
+ *
+ * @example
+ * const names = new Pipeline<User>()
+ *   .filter(u => u.active)
+ *   .map(u => u.name)        // Pipeline<User, string>
+ *   .toArray(users);         // string[]
+ */
+export class Pipeline<In = any, Out = In> {
+  free(): void;
+  constructor();
+
+  /** Make exceptions thrown by callbacks abort execution. */
+  strict(): Pipeline<In, Out>;
+
+  map<U>(f: (x: Out) => U): Pipeline<In, U>;
+  filter<S extends Out>(pred: (x: Out) => x is S): Pipeline<In, S>;
+  filter(pred: (x: Out) => unknown): Pipeline<In, Out>;
+  flatMap<U>(f: (x: Out) => readonly U[]): Pipeline<In, U>;
+  take(n: number): Pipeline<In, Out>;
+  takeWhile(pred: (x: Out) => unknown): Pipeline<In, Out>;
+  drop(n: number): Pipeline<In, Out>;
+  dropWhile(pred: (x: Out) => unknown): Pipeline<In, Out>;
+  tap(f: (x: Out) => void): Pipeline<In, Out>;
+  reject<S extends Out>(pred: (x: Out) => x is S): Pipeline<In, Exclude<Out, S>>;
+  reject(pred: (x: Out) => unknown): Pipeline<In, Out>;
+  unique(): Pipeline<In, Out>;
+  uniqueBy(keyFn: (x: Out) => unknown): Pipeline<In, Out>;
+  chunk(n: number): Pipeline<In, Out[]>;
+  aperture(size: number): Pipeline<In, Out[]>;
+  scan<A>(f: (acc: A, x: Out) => A, initial: A): Pipeline<In, A>;
+  interpose<S>(separator: S): Pipeline<In, Out | S>;
+  zip<V>(other: readonly V[]): Pipeline<In, [Out, V]>;
+  zipWith<V, U>(other: readonly V[], f: (x: Out, y: V) => U): Pipeline<In, U>;
+
+  mapAdd(n: number): Pipeline<In, number>;
+  mapMul(n: number): Pipeline<In, number>;
+  scaleOffset(a: number, b: number): Pipeline<In, number>;
+  normalize(mean: number, std: number): Pipeline<In, number>;
+  filterGt(n: number): Pipeline<In, number>;
+  filterRange(lo: number, hi: number): Pipeline<In, number>;
+
+  pluck<K extends keyof Out>(key: K): Pipeline<In, Out[K]>;
+  pluck(path: string): Pipeline<In, any>;
+  project<K extends keyof Out>(keys: readonly K[]): Pipeline<In, Pick<Out, K>>;
+  compact(): Pipeline<In, Exclude<Out, null | undefined | false | 0 | ''>>;
+  flatten(depth: number): Pipeline<In, any>;
+  whereMatches(spec: Partial<Out>): Pipeline<In, Out>;
+  where(spec: WhereSpec): Pipeline<In, Out>;
+
+  viewLens(lens: JsLens): Pipeline<In, any>;
+  overLens(lens: JsLens, f: (focus: any) => unknown): Pipeline<In, Out>;
+  filterLens(lens: JsLens, pred: (focus: any) => unknown): Pipeline<In, Out>;
+  setLens(lens: JsLens, value: unknown): Pipeline<In, Out>;
+
+  toArray(source: PipelineSource<In>): Out[];
+  reduce<A>(source: PipelineSource<In>, reducer: (acc: A, x: Out) => A, initial: A): A;
+  forEach(source: PipelineSource<In>, f: (x: Out) => void, signal?: AbortSignal): void;
+  count(source: PipelineSource<In>): number;
+  sum(source: PipelineSource<In>): number;
+  mean(source: PipelineSource<In>): number | undefined;
+  median(source: PipelineSource<In>): number | undefined;
+  stdDev(source: PipelineSource<In>): number | undefined;
+  quantile(source: PipelineSource<In>, p: number): number | undefined;
+  toFloat64Array(source: PipelineSource<In>): Float64Array;
+  first(source: PipelineSource<In>): Out | undefined;
+  find<S extends Out>(source: PipelineSource<In>, pred: (x: Out) => x is S): S | undefined;
+  find(source: PipelineSource<In>, pred: (x: Out) => unknown): Out | undefined;
+  every(source: PipelineSource<In>, pred: (x: Out) => unknown): boolean;
+  some(source: PipelineSource<In>, pred: (x: Out) => unknown): boolean;
+  groupBy<K>(source: PipelineSource<In>, keyFn: (x: Out) => K): Map<K, Out[]>;
+  indexBy<K>(source: PipelineSource<In>, keyFn: (x: Out) => K): Map<K, Out>;
+  toObject<K extends PropertyKey>(source: PipelineSource<In>, keyFn: (x: Out) => K): Record<K, Out>;
+  toObject<K extends PropertyKey, V>(
+    source: PipelineSource<In>,
+    keyFn: (x: Out) => K,
+    valueFn: (x: Out) => V,
+  ): Record<K, V>;
+  frequencies(source: PipelineSource<In>): Map<Out, number>;
+  partition<S extends Out>(
+    source: PipelineSource<In>,
+    pred: (x: Out) => x is S,
+  ): [S[], Exclude<Out, S>[]];
+  partition(source: PipelineSource<In>, pred: (x: Out) => unknown): [Out[], Out[]];
+  splitAt(source: PipelineSource<In>, n: number): [Out[], Out[]];
+  span(source: PipelineSource<In>, pred: (x: Out) => unknown): [Out[], Out[]];
+
+  toArrayAsync(source: AsyncPipelineSource<In>): Promise<Out[]>;
+  forEachAsync(source: AsyncPipelineSource<In>, f: (x: Out) => void | Promise<void>): Promise<void>;
+  toStream(source: PipelineSource<In>): ReadableStream<Out>;
+  runWithBudget(source: PipelineSource<In>, budget: Budget): BudgetRun<Out>;
+  runner(): Runner<In, Out>;
+
+  clone(): Pipeline<In, Out>;
+  freeze(): Pipeline<In, Out>;
+  describe(): PipelineDescription;
+  explain(): string;
+  debugRun(source: PipelineSource<In>, n: number): DebugRun<In, Out>;
+
+  toPlan(): PlanStep[];
+  toPlanJson(): string;
+  static fromPlan(
+    plan: readonly PlanStep[] | Plan | string,
+    functions?: Record<string, (value: any) => unknown>,
+  ): Pipeline;
+  runInWorker(source: PipelineSource<In>, worker: Worker): Promise<Float64Array | Out[]>;
+
+  logExecution(source: PipelineSource<In>): Out[];
+}
+
+/**
+ * Feed every element of `source` to each pipeline in one pass, resolving to
+ * the output of each in the same order.
+ */
+export function broadcast<In, const P extends readonly Pipeline<In, any>[]>(
+  source: AsyncPipelineSource<In>,
+  pipelines: P,
+): Promise<{ [K in keyof P]: P[K] extends Pipeline<In, infer Out> ? Out[] : never }>;
@@ -3,10 +3,20 @@
 //! This module provides a fluent API for building transducer pipelines
 //! that can be called from JavaScript via WASM.
 
-use js_sys::{Array, Function, Object, Reflect};
+use crate::error_wasm::{ErrorCode, OrlandoError};
+use crate::explain::{Explain, StageInfo};
+use crate::simd::NumericOp;
+use js_sys::{Array, Date, Float64Array, Function, Object, Promise, Reflect};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::rc::Rc;
+use wasm_bindgen::convert::TryFromJsValue;
 use wasm_bindgen::prelude::*;
-use web_sys::console;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{
+    console, AbortSignal, MessageEvent, ReadableStream, ReadableStreamDefaultController, Worker,
+};
 
 /// A pipeline represents a composition of transducers that can be applied to data.
 ///
@@ -25,27 +35,389 @@ use web_sys::console;
 /// const result = pipeline.toArray([1, 2, 3, 4, 5, 6]);
 /// console.log(result); // [6, 8, 10]
 /// ```
-#[wasm_bindgen]
+#[wasm_bindgen(skip_typescript)]
+#[derive(Clone)]
 pub struct Pipeline {
     operations: Vec<Operation>,
+    /// Abort on exceptions from operation callbacks instead of substituting
+    /// a fallback value
+    strict: bool,
+}
+
+// The generated declarations type every callback and source as `any`, so the
+// class is declared by hand with its element types as generic parameters.
+#[wasm_bindgen(typescript_custom_section)]
+const PIPELINE_TS: &str = include_str!("pipeline.d.ts");
+
+/// An exception thrown by a user callback.
+///
+/// Carries the value a lenient (non-strict) pipeline substitutes for the
+/// failed call, so each operation keeps its own fallback.
+struct Thrown<T> {
+    error: JsValue,
+    fallback: T,
+}
+
+impl<T> Thrown<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> Thrown<U> {
+        Thrown {
+            error: self.error,
+            fallback: f(self.fallback),
+        }
+    }
+}
+
+/// Result of calling a user callback.
+type Outcome<T> = Result<T, Thrown<T>>;
+
+/// Build a [`Thrown`] from a JS exception, for use with `map_err`.
+fn lenient<T>(fallback: T) -> impl FnOnce(JsValue) -> Thrown<T> {
+    move |error| Thrown { error, fallback }
 }
 
+type MapFn = Rc<dyn Fn(&JsValue) -> Outcome<JsValue>>;
+type PredFn = Rc<dyn Fn(&JsValue) -> Outcome<bool>>;
+type KeyFn = Rc<dyn Fn(&JsValue) -> Outcome<JsValue>>;
+type FlatMapFn = Rc<dyn Fn(&JsValue) -> Outcome<Vec<JsValue>>>;
+type TapFn = Rc<dyn Fn(&JsValue) -> Outcome<()>>;
+
 /// Internal representation of pipeline operations
 #[derive(Clone)]
 enum Operation {
-    Map(Rc<dyn Fn(JsValue) -> JsValue>),
-    Filter(Rc<dyn Fn(&JsValue) -> bool>),
-    /// Fused Map + Filter for better performance
-    MapFilter {
-        map: Rc<dyn Fn(JsValue) -> JsValue>,
-        filter: Rc<dyn Fn(&JsValue) -> bool>,
-    },
-    FlatMap(Rc<dyn Fn(JsValue) -> Vec<JsValue>>),
+    Map(MapFn),
+    Filter(PredFn),
+    /// A run of maps and filters executed as one operation
+    Fused(Vec<Stage>),
+    FlatMap(FlatMapFn),
     Take(usize),
-    TakeWhile(Rc<dyn Fn(&JsValue) -> bool>),
+    TakeWhile(PredFn),
     Drop(usize),
-    DropWhile(Rc<dyn Fn(&JsValue) -> bool>),
-    Tap(Rc<dyn Fn(&JsValue)>),
+    DropWhile(PredFn),
+    Tap(TapFn),
+    /// Drop consecutive duplicates
+    Unique,
+    /// Drop consecutive elements with equal keys
+    UniqueBy(KeyFn),
+    /// Group into arrays of fixed size; a trailing partial chunk is flushed at the end
+    Chunk(usize),
+    /// Sliding windows of fixed size
+    Aperture(usize),
+    /// Running accumulation, emitting each intermediate accumulator
+    Scan {
+        f: Function,
+        initial: JsValue,
+    },
+    /// Insert a separator between elements
+    Interpose(JsValue),
+    /// Pair elements with the same position in an array, stopping at the
+    /// end of the shorter side
+    Zip(Array),
+    /// Combine elements with the same position in an array
+    ZipWith {
+        other: Array,
+        f: Function,
+    },
+    /// Add a constant to numeric values
+    MapAdd(f64),
+    /// Multiply numeric values by a constant
+    MapMul(f64),
+    /// `a * x + b` for numeric values
+    ScaleOffset(f64, f64),
+    /// `(x - mean) / std` for numeric values
+    Normalize(f64, f64),
+    /// Keep numbers greater than a bound
+    FilterGt(f64),
+    /// Keep numbers in the half-open range `[lo, hi)`
+    FilterRange(f64, f64),
+}
+
+/// One step of an [`Operation::Fused`] run.
+#[derive(Clone)]
+enum Stage {
+    Map(MapFn),
+    Filter(PredFn),
+}
+
+impl Operation {
+    /// Name used in plans and error messages.
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::Map(_) => "map",
+            Operation::Filter(_) => "filter",
+            Operation::Fused(stages) => match stages.first() {
+                Some(Stage::Filter(_)) => "filter",
+                _ => "map",
+            },
+            Operation::FlatMap(_) => "flatMap",
+            Operation::Take(_) => "take",
+            Operation::TakeWhile(_) => "takeWhile",
+            Operation::Drop(_) => "drop",
+            Operation::DropWhile(_) => "dropWhile",
+            Operation::Tap(_) => "tap",
+            Operation::Unique => "unique",
+            Operation::UniqueBy(_) => "uniqueBy",
+            Operation::Chunk(_) => "chunk",
+            Operation::Aperture(_) => "aperture",
+            Operation::Scan { .. } => "scan",
+            Operation::Interpose(_) => "interpose",
+            Operation::Zip(_) => "zip",
+            Operation::ZipWith { .. } => "zipWith",
+            Operation::MapAdd(_) => "mapAdd",
+            Operation::MapMul(_) => "mapMul",
+            Operation::ScaleOffset(..) => "scaleOffset",
+            Operation::Normalize(..) => "normalize",
+            Operation::FilterGt(_) => "filterGt",
+            Operation::FilterRange(..) => "filterRange",
+        }
+    }
+
+    /// Describe the operation for `describe` and `explain`: its name and
+    /// parameters, whether it keeps state between elements, whether it can end
+    /// the run early, and what was fused into it.
+    fn describe(&self) -> StageInfo {
+        let name = match self {
+            Operation::Fused(_) => "fused".to_string(),
+            Operation::Take(n)
+            | Operation::Drop(n)
+            | Operation::Chunk(n)
+            | Operation::Aperture(n) => {
+                format!("{}({})", self.name(), n)
+            }
+            Operation::MapAdd(n) | Operation::MapMul(n) | Operation::FilterGt(n) => {
+                format!("{}({})", self.name(), n)
+            }
+            Operation::ScaleOffset(a, b)
+            | Operation::Normalize(a, b)
+            | Operation::FilterRange(a, b) => format!("{}({}, {})", self.name(), a, b),
+            _ => self.name().to_string(),
+        };
+        let mut stage = StageInfo::new(name);
+        if let Operation::Fused(stages) = self {
+            stage.fused = stages
+                .iter()
+                .map(|stage| match stage {
+                    Stage::Map(_) => "map".to_string(),
+                    Stage::Filter(_) => "filter".to_string(),
+                })
+                .collect();
+        }
+        if matches!(
+            self,
+            Operation::Take(_)
+                | Operation::Drop(_)
+                | Operation::DropWhile(_)
+                | Operation::Unique
+                | Operation::UniqueBy(_)
+                | Operation::Chunk(_)
+                | Operation::Aperture(_)
+                | Operation::Scan { .. }
+                | Operation::Interpose(_)
+                | Operation::Zip(_)
+                | Operation::ZipWith { .. }
+        ) {
+            stage = stage.stateful();
+        }
+        if matches!(
+            self,
+            Operation::Take(_)
+                | Operation::TakeWhile(_)
+                | Operation::Zip(_)
+                | Operation::ZipWith { .. }
+        ) {
+            stage = stage.may_stop();
+        }
+        stage
+    }
+
+    /// Describe the operation as a plain object, or fail if it holds a
+    /// JavaScript callback that can't leave this thread.
+    fn to_plan_step(&self, position: usize) -> Result<Object, JsValue> {
+        let step = Object::new();
+        let set = |key: &str, value: &JsValue| Reflect::set(&step, &key.into(), value);
+
+        set("op", &self.name().into())?;
+        match self {
+            Operation::Take(n)
+            | Operation::Drop(n)
+            | Operation::Chunk(n)
+            | Operation::Aperture(n) => {
+                set("n", &(*n as f64).into())?;
+            }
+            Operation::MapAdd(n) | Operation::MapMul(n) | Operation::FilterGt(n) => {
+                set("n", &(*n).into())?;
+            }
+            Operation::FilterRange(lo, hi) => {
+                set("lo", &(*lo).into())?;
+                set("hi", &(*hi).into())?;
+            }
+            Operation::ScaleOffset(a, b) => {
+                set("a", &(*a).into())?;
+                set("b", &(*b).into())?;
+            }
+            Operation::Normalize(mean, std) => {
+                set("mean", &(*mean).into())?;
+                set("std", &(*std).into())?;
+            }
+            Operation::Interpose(separator) => {
+                set("separator", separator)?;
+            }
+            Operation::Zip(other) => {
+                set("other", other)?;
+            }
+            Operation::Unique => {}
+            other => {
+                return Err(OrlandoError::new(
+                    ErrorCode::NotSerializable,
+                    "calls a JavaScript function and cannot be serialized",
+                )
+                .at_operation(position, other.name())
+                .into())
+            }
+        }
+        Ok(step)
+    }
+
+    /// Rebuild an operation from a plan step, resolving the names in `map`
+    /// and `filter` steps against the `functions` object.
+    fn from_plan_step(step: &JsValue, functions: &JsValue) -> Result<Operation, JsValue> {
+        let number = |key: &str| {
+            Reflect::get(step, &key.into())?
+                .as_f64()
+                .ok_or_else(|| invalid_plan(format!("plan step is missing `{}`", key)))
+        };
+        let count = |key: &str| number(key).map(|n| n as usize);
+
+        let op = Reflect::get(step, &"op".into())?
+            .as_string()
+            .ok_or_else(|| invalid_plan("plan step is missing `op`"))?;
+        Ok(match op.as_str() {
+            "take" => Operation::Take(count("n")?),
+            "drop" => Operation::Drop(count("n")?),
+            "chunk" => Operation::Chunk(count("n")?.max(1)),
+            "aperture" => Operation::Aperture(count("n")?.max(1)),
+            "unique" => Operation::Unique,
+            "interpose" => Operation::Interpose(Reflect::get(step, &"separator".into())?),
+            "zip" => Operation::Zip(
+                Reflect::get(step, &"other".into())?
+                    .dyn_into()
+                    .map_err(|_| invalid_plan("plan step is missing `other`"))?,
+            ),
+            "mapAdd" => Operation::MapAdd(number("n")?),
+            "mapMul" => Operation::MapMul(number("n")?),
+            "scaleOffset" => Operation::ScaleOffset(number("a")?, number("b")?),
+            "normalize" => Operation::Normalize(number("mean")?, number("std")?),
+            "filterGt" => Operation::FilterGt(number("n")?),
+            "filterRange" => Operation::FilterRange(number("lo")?, number("hi")?),
+            "map" | "filter" => {
+                let name = Reflect::get(step, &"fn".into())?
+                    .as_string()
+                    .ok_or_else(|| invalid_plan("plan step is missing `fn`"))?;
+                let f: Function = if functions.is_object() {
+                    Reflect::get(functions, &name.as_str().into())?
+                } else {
+                    JsValue::undefined()
+                }
+                .dyn_into()
+                .map_err(|_| invalid_plan(format!("plan refers to unknown function `{}`", name)))?;
+
+                let this = JsValue::null();
+                if op == "map" {
+                    Operation::Map(Rc::new(move |val: &JsValue| -> Outcome<JsValue> {
+                        f.call1(&this, val).map_err(lenient(JsValue::undefined()))
+                    }))
+                } else {
+                    Operation::Filter(Rc::new(move |val: &JsValue| -> Outcome<bool> {
+                        call_predicate(&f, val)
+                    }))
+                }
+            }
+            other => return Err(invalid_plan(format!("unknown plan operation `{}`", other))),
+        })
+    }
+
+    /// Whether the operation can run on raw `f64` values in the typed array
+    /// fast path without calling back into JavaScript.
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Operation::Take(_)
+                | Operation::Drop(_)
+                | Operation::Unique
+                | Operation::MapAdd(_)
+                | Operation::MapMul(_)
+                | Operation::ScaleOffset(..)
+                | Operation::Normalize(..)
+                | Operation::FilterGt(_)
+                | Operation::FilterRange(..)
+        )
+    }
+
+    /// The operation as a run of fusable stages, if it is a map or filter.
+    fn stages(&self) -> Option<Vec<Stage>> {
+        match self {
+            Operation::Map(f) => Some(vec![Stage::Map(f.clone())]),
+            Operation::Filter(pred) => Some(vec![Stage::Filter(pred.clone())]),
+            Operation::Fused(stages) => Some(stages.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation maps every element to exactly one element.
+    fn is_map_only(&self) -> bool {
+        match self {
+            Operation::Map(_) => true,
+            Operation::Fused(stages) => stages.iter().all(|stage| matches!(stage, Stage::Map(_))),
+            _ => false,
+        }
+    }
+
+    /// Merge `self` with the operation that follows it, if the pair has a
+    /// single-operation equivalent. Otherwise both are handed back unchanged.
+    fn fuse(self, next: Operation) -> Result<Operation, (Operation, Operation)> {
+        match (self, next) {
+            (Operation::Take(a), Operation::Take(b)) => Ok(Operation::Take(a.min(b))),
+            (Operation::Drop(a), Operation::Drop(b)) => Ok(Operation::Drop(a.saturating_add(b))),
+            // A NaN bound rejects everything, which `max`/`min` would lose
+            (Operation::FilterGt(a), Operation::FilterGt(b)) if !a.is_nan() && !b.is_nan() => {
+                Ok(Operation::FilterGt(a.max(b)))
+            }
+            (Operation::FilterRange(lo1, hi1), Operation::FilterRange(lo2, hi2))
+                if ![lo1, hi1, lo2, hi2].iter().any(|bound| bound.is_nan()) =>
+            {
+                Ok(Operation::FilterRange(lo1.max(lo2), hi1.min(hi2)))
+            }
+            (first, second) => match (first.stages(), second.stages()) {
+                (Some(mut stages), Some(rest)) => {
+                    stages.extend(rest);
+                    Ok(Operation::Fused(stages))
+                }
+                _ => Err((first, second)),
+            },
+        }
+    }
+}
+
+/// Append `op`, fusing it with the current last operation where possible.
+fn push_fused(ops: &mut Vec<Operation>, op: Operation) {
+    // `take` commutes with one-to-one maps. Moving it in front of them means
+    // the extra element that ends the take is never mapped.
+    if matches!(op, Operation::Take(_)) && ops.last().is_some_and(Operation::is_map_only) {
+        let map = ops.pop().expect("checked above");
+        push_fused(ops, op);
+        ops.push(map);
+        return;
+    }
+
+    match ops.pop() {
+        Some(prev) => match prev.fuse(op) {
+            Ok(fused) => ops.push(fused),
+            Err((prev, op)) => {
+                ops.push(prev);
+                ops.push(op);
+            }
+        },
+        None => ops.push(op),
+    }
 }
 
 #[wasm_bindgen]
@@ -55,6 +427,35 @@ impl Pipeline {
     pub fn new() -> Pipeline {
         Pipeline {
             operations: Vec::new(),
+            strict: false,
+        }
+    }
+
+    /// Make exceptions thrown by operation callbacks abort execution.
+    ///
+    /// By default callbacks are treated leniently: a throwing `map` yields
+    /// `undefined`, a throwing predicate counts as `false` and `tap` errors
+    /// are ignored, and the same holds for the callbacks taken by terminals
+    /// such as `reduce`, `find` or `groupBy` (a throwing reducer keeps the
+    /// accumulator). In strict mode the terminal throws instead (async
+    /// terminals reject, streams error) with an `Error` whose `cause` is the
+    /// original exception and whose `index` is the position of the source
+    /// element being processed.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// try {
+    ///   new Pipeline().strict().map(JSON.parse).toArray(lines);
+    /// } catch (e) {
+    ///   console.error(`line ${e.index} is not JSON`, e.cause);
+    /// }
+    /// ```
+    #[wasm_bindgen]
+    pub fn strict(&self) -> Pipeline {
+        Pipeline {
+            operations: self.operations.clone(),
+            strict: true,
         }
     }
 
@@ -68,13 +469,16 @@ impl Pipeline {
         let f = f.clone();
         let mut ops = self.operations.clone();
 
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue {
+        let map_fn = Rc::new(move |val: &JsValue| -> Outcome<JsValue> {
             let this = JsValue::null();
-            f.call1(&this, &val).unwrap_or(JsValue::undefined())
-        }) as Rc<dyn Fn(JsValue) -> JsValue>;
+            f.call1(&this, val).map_err(lenient(JsValue::undefined()))
+        }) as MapFn;
 
         ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Add a filter operation to the pipeline.
@@ -87,26 +491,17 @@ impl Pipeline {
         let pred = pred.clone();
         let mut ops = self.operations.clone();
 
-        let filter_fn = Rc::new(move |val: &JsValue| -> bool {
-            let this = JsValue::null();
-            match pred.call1(&this, val) {
-                Ok(result) => result.as_bool().unwrap_or(false),
-                Err(_) => false,
-            }
-        }) as Rc<dyn Fn(&JsValue) -> bool>;
-
-        // OPTIMIZATION: Fuse Map + Filter into a single operation
-        // This reduces function call overhead and improves cache locality
-        if let Some(Operation::Map(map_fn)) = ops.pop() {
-            ops.push(Operation::MapFilter {
-                map: map_fn,
-                filter: filter_fn,
-            });
-        } else {
-            ops.push(Operation::Filter(filter_fn));
-        }
+        let filter_fn =
+            Rc::new(move |val: &JsValue| -> Outcome<bool> { call_predicate(&pred, val) }) as PredFn;
+
+        // OPTIMIZATION: Fuse with a preceding map or filter into a single operation
+        // This reduces dispatch overhead and improves cache locality
+        push_fused(&mut ops, Operation::Filter(filter_fn));
 
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Add a flatMap operation to the pipeline.
@@ -121,23 +516,22 @@ impl Pipeline {
         let f = f.clone();
         let mut ops = self.operations.clone();
 
-        let flatmap_fn = Rc::new(move |val: JsValue| -> Vec<JsValue> {
+        let flatmap_fn = Rc::new(move |val: &JsValue| -> Outcome<Vec<JsValue>> {
             let this = JsValue::null();
-            match f.call1(&this, &val) {
-                Ok(result) => {
-                    // Convert JsValue array to Vec<JsValue>
-                    if let Ok(array) = result.dyn_into::<Array>() {
-                        (0..array.length()).map(|i| array.get(i)).collect()
-                    } else {
-                        vec![]
-                    }
-                }
-                Err(_) => vec![],
+            let result = f.call1(&this, val).map_err(lenient(vec![]))?;
+            // Convert JsValue array to Vec<JsValue>
+            if let Ok(array) = result.dyn_into::<Array>() {
+                Ok((0..array.length()).map(|i| array.get(i)).collect())
+            } else {
+                Ok(vec![])
             }
-        }) as Rc<dyn Fn(JsValue) -> Vec<JsValue>>;
+        }) as FlatMapFn;
 
         ops.push(Operation::FlatMap(flatmap_fn));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Take the first n elements.
@@ -149,7 +543,10 @@ impl Pipeline {
     pub fn take(&self, n: usize) -> Pipeline {
         let mut ops = self.operations.clone();
         ops.push(Operation::Take(n));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Take elements while predicate is true.
@@ -162,13 +559,12 @@ impl Pipeline {
         let pred = pred.clone();
         let mut ops = self.operations.clone();
         ops.push(Operation::TakeWhile(Rc::new(move |val| {
-            let this = JsValue::null();
-            match pred.call1(&this, val) {
-                Ok(result) => result.as_bool().unwrap_or(false),
-                Err(_) => false,
-            }
+            call_predicate(&pred, val)
         })));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Skip the first n elements.
@@ -180,7 +576,10 @@ impl Pipeline {
     pub fn drop(&self, n: usize) -> Pipeline {
         let mut ops = self.operations.clone();
         ops.push(Operation::Drop(n));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Skip elements while predicate is true.
@@ -193,13 +592,12 @@ impl Pipeline {
         let pred = pred.clone();
         let mut ops = self.operations.clone();
         ops.push(Operation::DropWhile(Rc::new(move |val| {
-            let this = JsValue::null();
-            match pred.call1(&this, val) {
-                Ok(result) => result.as_bool().unwrap_or(false),
-                Err(_) => false,
-            }
+            call_predicate(&pred, val)
         })));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
     /// Perform side effects without transforming values.
@@ -213,548 +611,2941 @@ impl Pipeline {
         let mut ops = self.operations.clone();
         ops.push(Operation::Tap(Rc::new(move |val| {
             let this = JsValue::null();
-            let _ = f.call1(&this, val);
+            f.call1(&this, val).map(|_| ()).map_err(lenient(()))
         })));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Extract a property from each object (JavaScript convenience).
+    /// Remove elements that match a predicate (inverse of `filter`).
     ///
-    /// This is cleaner than `.map(x => x.propertyName)` for extracting properties.
+    /// # Examples (JavaScript)
     ///
-    /// # Arguments
+    /// ```javascript
+    /// new Pipeline().reject(x => x < 0).toArray([-1, 2, -3, 4]);
+    /// // [2, 4]
+    /// ```
+    #[wasm_bindgen]
+    pub fn reject(&self, pred: &Function) -> Pipeline {
+        let pred = pred.clone();
+        let mut ops = self.operations.clone();
+        ops.push(Operation::Filter(Rc::new(move |val| {
+            call_predicate(&pred, val)
+                .map(|matched| !matched)
+                .map_err(|thrown| thrown.map(|matched| !matched))
+        })));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Remove consecutive duplicate values.
     ///
-    /// * `property_name` - The name of the property to extract
+    /// Values are compared with `===` semantics.
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const users = [
-    ///   { name: 'Alice', age: 30 },
-    ///   { name: 'Bob', age: 25 }
-    /// ];
-    /// const names = new Pipeline().pluck('name').toArray(users);
-    /// // names: ['Alice', 'Bob']
+    /// new Pipeline().unique().toArray([1, 1, 2, 2, 3, 1]);
+    /// // [1, 2, 3, 1]
     /// ```
     #[wasm_bindgen]
-    pub fn pluck(&self, property_name: &str) -> Pipeline {
-        let prop_key = JsValue::from_str(property_name);
+    pub fn unique(&self) -> Pipeline {
         let mut ops = self.operations.clone();
-
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue {
-            // Use Reflect.get to extract the property
-            Reflect::get(&val, &prop_key).unwrap_or(JsValue::undefined())
-        }) as Rc<dyn Fn(JsValue) -> JsValue>;
-
-        ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+        ops.push(Operation::Unique);
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Extract multiple properties from each object, creating new objects with only those keys.
-    ///
-    /// This is equivalent to `.map(x => ({ key1: x.key1, key2: x.key2, ... }))`.
+    /// Remove consecutive values whose keys are equal.
     ///
     /// # Arguments
     ///
-    /// * `keys` - A JavaScript array of property name strings to extract
+    /// * `key_fn` - A JavaScript function computing the comparison key
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const users = [
-    ///   { id: 1, name: 'Alice', age: 30, email: 'alice@example.com' },
-    ///   { id: 2, name: 'Bob', age: 25, email: 'bob@example.com' }
-    /// ];
-    /// const projected = new Pipeline().project(['id', 'name']).toArray(users);
-    /// // [{ id: 1, name: 'Alice' }, { id: 2, name: 'Bob' }]
+    /// new Pipeline().uniqueBy(u => u.id).toArray(events);
     /// ```
-    #[wasm_bindgen]
-    pub fn project(&self, keys: &JsValue) -> Pipeline {
-        let keys_array: Array = keys.clone().dyn_into().unwrap_or_else(|_| Array::new());
-
-        // Pre-extract key strings to avoid repeated conversion
-        let key_strings: Vec<JsValue> = (0..keys_array.length())
-            .map(|i| keys_array.get(i))
-            .collect();
-
+    #[wasm_bindgen(js_name = uniqueBy)]
+    pub fn unique_by(&self, key_fn: &Function) -> Pipeline {
+        let key_fn = key_fn.clone();
         let mut ops = self.operations.clone();
-
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue {
-            let result = Object::new();
-            for key in &key_strings {
-                if let Ok(prop_val) = Reflect::get(&val, key) {
-                    let _ = Reflect::set(&result, key, &prop_val);
-                }
-            }
-            result.into()
-        }) as Rc<dyn Fn(JsValue) -> JsValue>;
-
-        ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+        ops.push(Operation::UniqueBy(Rc::new(move |val| {
+            let this = JsValue::null();
+            key_fn
+                .call1(&this, val)
+                .map_err(lenient(JsValue::undefined()))
+        })));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Remove all falsy values from the stream.
+    /// Group elements into arrays of size `n`.
     ///
-    /// Filters out `null`, `undefined`, `false`, `0`, `NaN`, and `''` (empty string).
+    /// A final, shorter chunk is emitted when the source is exhausted.
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const data = [1, null, 'hello', undefined, 0, false, '', 42];
-    /// const result = new Pipeline().compact().toArray(data);
-    /// // [1, 'hello', 42]
+    /// new Pipeline().chunk(3).toArray([1, 2, 3, 4, 5, 6, 7]);
+    /// // [[1, 2, 3], [4, 5, 6], [7]]
     /// ```
     #[wasm_bindgen]
-    pub fn compact(&self) -> Pipeline {
+    pub fn chunk(&self, n: usize) -> Pipeline {
         let mut ops = self.operations.clone();
-
-        let filter_fn = Rc::new(move |val: &JsValue| -> bool {
-            val.as_bool() != Some(false)
-                && !val.is_null()
-                && !val.is_undefined()
-                && val.as_f64() != Some(0.0)
-                && val.as_string().is_none_or(|s| !s.is_empty())
-                && !is_nan(val)
-        }) as Rc<dyn Fn(&JsValue) -> bool>;
-
-        ops.push(Operation::Filter(filter_fn));
-        Pipeline { operations: ops }
+        ops.push(Operation::Chunk(n.max(1)));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Flatten nested arrays to a given depth.
-    ///
-    /// Each element in the stream that is an array will be expanded. Nesting
-    /// is flattened recursively up to the specified depth.
-    ///
-    /// # Arguments
-    ///
-    /// * `depth` - Maximum depth to flatten. Use 1 for a single level of flattening.
+    /// Emit sliding windows of `size` consecutive elements.
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const data = [[1, 2], [3, [4, 5]], [6]];
-    /// const result = new Pipeline().flatten(1).toArray(data);
-    /// // [1, 2, 3, [4, 5], 6]
-    ///
-    /// const deep = new Pipeline().flatten(2).toArray(data);
-    /// // [1, 2, 3, 4, 5, 6]
+    /// new Pipeline().aperture(3).toArray([1, 2, 3, 4, 5]);
+    /// // [[1, 2, 3], [2, 3, 4], [3, 4, 5]]
     /// ```
     #[wasm_bindgen]
-    pub fn flatten(&self, depth: usize) -> Pipeline {
+    pub fn aperture(&self, size: usize) -> Pipeline {
         let mut ops = self.operations.clone();
-
-        let flatmap_fn = Rc::new(move |val: JsValue| -> Vec<JsValue> { flatten_value(val, depth) })
-            as Rc<dyn Fn(JsValue) -> Vec<JsValue>>;
-
-        ops.push(Operation::FlatMap(flatmap_fn));
-        Pipeline { operations: ops }
+        ops.push(Operation::Aperture(size.max(1)));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Filter objects that match all properties in a spec object.
-    ///
-    /// Keeps only elements where every key in the spec has a matching value.
-    /// Comparison uses strict equality (`===`-like semantics).
+    /// Accumulate values, emitting every intermediate result.
     ///
     /// # Arguments
     ///
-    /// * `spec` - A JavaScript object whose key-value pairs must all match
+    /// * `f` - A JavaScript function (acc, val) => acc
+    /// * `initial` - Initial accumulator value
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const users = [
-    ///   { name: 'Alice', active: true, role: 'admin' },
-    ///   { name: 'Bob', active: false, role: 'user' },
-    ///   { name: 'Charlie', active: true, role: 'user' }
-    /// ];
-    ///
-    /// const result = new Pipeline()
-    ///   .where_matches({ active: true })
-    ///   .toArray(users);
-    /// // [{ name: 'Alice', ... }, { name: 'Charlie', ... }]
+    /// new Pipeline().scan((sum, x) => sum + x, 0).toArray([1, 2, 3, 4]);
+    /// // [1, 3, 6, 10]
     /// ```
-    #[wasm_bindgen(js_name = whereMatches)]
-    pub fn where_matches(&self, spec: &JsValue) -> Pipeline {
-        let spec_obj = spec.clone();
-
-        // Pre-extract spec keys and values
-        let spec_keys = Object::keys(&Object::from(spec_obj.clone()));
-        let mut spec_entries: Vec<(JsValue, JsValue)> = Vec::new();
-        for i in 0..spec_keys.length() {
-            let key = spec_keys.get(i);
-            if let Ok(val) = Reflect::get(&spec_obj, &key) {
-                spec_entries.push((key, val));
-            }
-        }
-
+    #[wasm_bindgen]
+    pub fn scan(&self, f: &Function, initial: JsValue) -> Pipeline {
         let mut ops = self.operations.clone();
-
-        let filter_fn = Rc::new(move |val: &JsValue| -> bool {
-            for (key, expected) in &spec_entries {
-                match Reflect::get(val, key) {
-                    Ok(actual) => {
-                        if !js_strict_eq(&actual, expected) {
-                            return false;
-                        }
-                    }
-                    Err(_) => return false,
-                }
-            }
-            true
-        }) as Rc<dyn Fn(&JsValue) -> bool>;
-
-        ops.push(Operation::Filter(filter_fn));
-        Pipeline { operations: ops }
+        ops.push(Operation::Scan {
+            f: f.clone(),
+            initial,
+        });
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Apply a lens to extract the focused value from each element.
-    ///
-    /// Equivalent to `.map(x => myLens.get(x))` but avoids the JS function call overhead.
-    ///
-    /// # Arguments
-    ///
-    /// * `optic` - A JsLens to apply
+    /// Insert a separator between elements.
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const nameLens = lens('name');
-    /// const users = [{ name: "Alice" }, { name: "Bob" }];
-    /// const names = new Pipeline().viewLens(nameLens).toArray(users);
-    /// // names: ["Alice", "Bob"]
+    /// new Pipeline().interpose(',').toArray(['a', 'b', 'c']);
+    /// // ['a', ',', 'b', ',', 'c']
     /// ```
-    #[wasm_bindgen(js_name = viewLens)]
-    pub fn view_lens(&self, optic: &crate::optics_wasm::JsLens) -> Pipeline {
-        let get_fn = optic.get_fn.clone();
+    #[wasm_bindgen]
+    pub fn interpose(&self, separator: JsValue) -> Pipeline {
         let mut ops = self.operations.clone();
+        ops.push(Operation::Interpose(separator));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
 
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue { get_fn(&val) })
-            as Rc<dyn Fn(JsValue) -> JsValue>;
+    /// Pair each element with the element at the same position in `other`.
+    ///
+    /// Produces `[element, otherElement]` arrays and stops when either side
+    /// runs out, so a long (or infinite) source is cut to `other`'s length.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().map(x => x * 10).zip(['a', 'b']).toArray([1, 2, 3]);
+    /// // [[10, 'a'], [20, 'b']]
+    /// ```
+    #[wasm_bindgen]
+    pub fn zip(&self, other: &Array) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::Zip(other.clone()));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
 
-        ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+    /// Combine each element with the element at the same position in `other`
+    /// using `f(element, otherElement)`.
+    ///
+    /// Stops when either side runs out, like [`zip`](Pipeline::zip).
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().zipWith(weights, (x, w) => x * w).sum(values);
+    /// // weighted sum
+    /// ```
+    #[wasm_bindgen(js_name = zipWith)]
+    pub fn zip_with(&self, other: &Array, f: &Function) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::ZipWith {
+            other: other.clone(),
+            f: f.clone(),
+        });
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Transform each element's focused value through a lens using a function.
+    /// Add `n` to every element, without calling back into JavaScript.
     ///
-    /// Equivalent to `.map(x => myLens.over(x, fn))`.
+    /// Equivalent to `map(x => x + n)` for numbers. Non-numeric values
+    /// become `NaN`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().mapAdd(10).toArray([1, 2, 3]);
+    /// // [11, 12, 13]
+    /// ```
+    #[wasm_bindgen(js_name = mapAdd)]
+    pub fn map_add(&self, n: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::MapAdd(n));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Multiply every element by `n`, without calling back into JavaScript.
+    ///
+    /// Equivalent to `map(x => x * n)` for numbers. Non-numeric values
+    /// become `NaN`.
+    #[wasm_bindgen(js_name = mapMul)]
+    pub fn map_mul(&self, n: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::MapMul(n));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Compute `a * x + b` for every element in one step, without calling
+    /// back into JavaScript.
+    ///
+    /// Equivalent to `map(x => a * x + b)` for numbers. Non-numeric values
+    /// become `NaN`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// // Celsius to Fahrenheit
+    /// new Pipeline().scaleOffset(1.8, 32).toArray([0, 100]);
+    /// // [32, 212]
+    /// ```
+    #[wasm_bindgen(js_name = scaleOffset)]
+    pub fn scale_offset(&self, a: f64, b: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::ScaleOffset(a, b));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Standardize every element as `(x - mean) / std` in one step, without
+    /// calling back into JavaScript.
+    ///
+    /// Non-numeric values become `NaN`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().normalize(10, 4).toArray([2, 10, 18]);
+    /// // [-2, 0, 2]
+    /// ```
+    #[wasm_bindgen]
+    pub fn normalize(&self, mean: f64, std: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::Normalize(mean, std));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Keep numbers greater than `n`, without calling back into JavaScript.
+    ///
+    /// Equivalent to `filter(x => x > n)` for numbers. Non-numeric values
+    /// are dropped.
+    #[wasm_bindgen(js_name = filterGt)]
+    pub fn filter_gt(&self, n: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::FilterGt(n));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Keep numbers in the half-open range `[lo, hi)`, without calling back
+    /// into JavaScript.
+    ///
+    /// Non-numeric values are dropped.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().filterRange(0, 10).toArray([-5, 0, 5, 10, 15]);
+    /// // [0, 5]
+    /// ```
+    #[wasm_bindgen(js_name = filterRange)]
+    pub fn filter_range(&self, lo: f64, hi: f64) -> Pipeline {
+        let mut ops = self.operations.clone();
+        ops.push(Operation::FilterRange(lo, hi));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Extract a property from each object (JavaScript convenience).
+    ///
+    /// This is cleaner than `.map(x => x.propertyName)` for extracting properties.
+    /// A dotted name is followed as a path, as with `path`, and numeric
+    /// segments index arrays. If the path is missing, a property whose name
+    /// literally contains the dots is used instead.
     ///
     /// # Arguments
     ///
-    /// * `optic` - A JsLens to apply
-    /// * `f` - A JavaScript function to transform the focused value
+    /// * `property_name` - The name of the property, or a dotted path, to extract
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const priceLens = lens('price');
-    /// const items = [{ name: "A", price: 10 }, { name: "B", price: 20 }];
-    /// const discounted = new Pipeline()
-    ///   .overLens(priceLens, p => p * 0.9)
-    ///   .toArray(items);
-    /// // [{ name: "A", price: 9 }, { name: "B", price: 18 }]
+    /// const users = [
+    ///   { name: 'Alice', age: 30 },
+    ///   { name: 'Bob', age: 25 }
+    /// ];
+    /// const names = new Pipeline().pluck('name').toArray(users);
+    /// // names: ['Alice', 'Bob']
+    ///
+    /// new Pipeline().pluck('items.0.id').toArray(orders);
+    /// // id of each order's first item
     /// ```
-    #[wasm_bindgen(js_name = overLens)]
-    pub fn over_lens(&self, optic: &crate::optics_wasm::JsLens, f: &Function) -> Pipeline {
-        let get_fn = optic.get_fn.clone();
-        let set_fn = optic.set_fn.clone();
-        let f = f.clone();
+    #[wasm_bindgen]
+    pub fn pluck(&self, property_name: &str) -> Pipeline {
+        let field = FieldPath::new(property_name);
         let mut ops = self.operations.clone();
 
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue {
-            let current = get_fn(&val);
-            let this = JsValue::null();
-            let updated = f.call1(&this, &current).unwrap_or_else(|_| current.clone());
-            set_fn(&val, updated)
-        }) as Rc<dyn Fn(JsValue) -> JsValue>;
+        let map_fn =
+            Rc::new(move |val: &JsValue| -> Outcome<JsValue> { Ok(field.get(val)) }) as MapFn;
 
         ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Filter elements based on a predicate applied to the focused value of a lens.
+    /// Extract multiple properties from each object, creating new objects with only those keys.
     ///
-    /// Equivalent to `.filter(x => pred(myLens.get(x)))`.
+    /// This is equivalent to `.map(x => ({ key1: x.key1, key2: x.key2, ... }))`.
     ///
     /// # Arguments
     ///
-    /// * `optic` - A JsLens to extract the value to test
-    /// * `pred` - A JavaScript predicate function
+    /// * `keys` - A JavaScript array of property name strings to extract
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const ageLens = lens('age');
-    /// const users = [{ name: "Alice", age: 25 }, { name: "Bob", age: 17 }];
-    /// const adults = new Pipeline()
-    ///   .filterLens(ageLens, a => a >= 18)
-    ///   .toArray(users);
-    /// // [{ name: "Alice", age: 25 }]
+    /// const users = [
+    ///   { id: 1, name: 'Alice', age: 30, email: 'alice@example.com' },
+    ///   { id: 2, name: 'Bob', age: 25, email: 'bob@example.com' }
+    /// ];
+    /// const projected = new Pipeline().project(['id', 'name']).toArray(users);
+    /// // [{ id: 1, name: 'Alice' }, { id: 2, name: 'Bob' }]
     /// ```
-    #[wasm_bindgen(js_name = filterLens)]
-    pub fn filter_lens(&self, optic: &crate::optics_wasm::JsLens, pred: &Function) -> Pipeline {
-        let get_fn = optic.get_fn.clone();
-        let pred = pred.clone();
+    #[wasm_bindgen]
+    pub fn project(&self, keys: &JsValue) -> Pipeline {
+        let keys_array: Array = keys.clone().dyn_into().unwrap_or_else(|_| Array::new());
+
+        // Pre-extract key strings to avoid repeated conversion
+        let key_strings: Vec<JsValue> = (0..keys_array.length())
+            .map(|i| keys_array.get(i))
+            .collect();
+
         let mut ops = self.operations.clone();
 
-        let filter_fn = Rc::new(move |val: &JsValue| -> bool {
-            let focused = get_fn(val);
-            let this = JsValue::null();
-            match pred.call1(&this, &focused) {
-                Ok(result) => result.as_bool().unwrap_or(false),
-                Err(_) => false,
+        let map_fn = Rc::new(move |val: &JsValue| -> Outcome<JsValue> {
+            let result = Object::new();
+            for key in &key_strings {
+                if let Ok(prop_val) = Reflect::get(val, key) {
+                    let _ = Reflect::set(&result, key, &prop_val);
+                }
             }
-        }) as Rc<dyn Fn(&JsValue) -> bool>;
+            Ok(result.into())
+        }) as MapFn;
+
+        ops.push(Operation::Map(map_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Remove all falsy values from the stream.
+    ///
+    /// Filters out `null`, `undefined`, `false`, `0`, `NaN`, and `''` (empty string).
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const data = [1, null, 'hello', undefined, 0, false, '', 42];
+    /// const result = new Pipeline().compact().toArray(data);
+    /// // [1, 'hello', 42]
+    /// ```
+    #[wasm_bindgen]
+    pub fn compact(&self) -> Pipeline {
+        let mut ops = self.operations.clone();
+
+        let filter_fn = Rc::new(move |val: &JsValue| -> Outcome<bool> {
+            Ok(val.as_bool() != Some(false)
+                && !val.is_null()
+                && !val.is_undefined()
+                && val.as_f64() != Some(0.0)
+                && val.as_string().is_none_or(|s| !s.is_empty())
+                && !is_nan(val))
+        }) as PredFn;
 
         ops.push(Operation::Filter(filter_fn));
-        Pipeline { operations: ops }
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
     }
 
-    /// Set the focused value of a lens on every element.
+    /// Flatten nested arrays to a given depth.
     ///
-    /// Equivalent to `.map(x => myLens.set(x, value))`.
+    /// Each element in the stream that is an array will be expanded. Nesting
+    /// is flattened recursively up to the specified depth.
     ///
     /// # Arguments
     ///
-    /// * `optic` - A JsLens to apply
-    /// * `value` - The value to set
+    /// * `depth` - Maximum depth to flatten. Use 1 for a single level of flattening.
     ///
     /// # Examples (JavaScript)
     ///
     /// ```javascript
-    /// const statusLens = lens('status');
-    /// const items = [{ id: 1, status: "draft" }, { id: 2, status: "draft" }];
-    /// const published = new Pipeline()
-    ///   .setLens(statusLens, "published")
-    ///   .toArray(items);
-    /// // [{ id: 1, status: "published" }, { id: 2, status: "published" }]
+    /// const data = [[1, 2], [3, [4, 5]], [6]];
+    /// const result = new Pipeline().flatten(1).toArray(data);
+    /// // [1, 2, 3, [4, 5], 6]
+    ///
+    /// const deep = new Pipeline().flatten(2).toArray(data);
+    /// // [1, 2, 3, 4, 5, 6]
     /// ```
-    #[wasm_bindgen(js_name = setLens)]
-    pub fn set_lens(&self, optic: &crate::optics_wasm::JsLens, value: JsValue) -> Pipeline {
-        let set_fn = optic.set_fn.clone();
+    #[wasm_bindgen]
+    pub fn flatten(&self, depth: usize) -> Pipeline {
+        let mut ops = self.operations.clone();
+
+        let flatmap_fn = Rc::new(move |val: &JsValue| -> Outcome<Vec<JsValue>> {
+            Ok(flatten_value(val.clone(), depth))
+        }) as FlatMapFn;
+
+        ops.push(Operation::FlatMap(flatmap_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Filter objects that match all properties in a spec object.
+    ///
+    /// Keeps only elements where every key in the spec has a matching value.
+    /// Comparison uses strict equality (`===`-like semantics).
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - A JavaScript object whose key-value pairs must all match
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const users = [
+    ///   { name: 'Alice', active: true, role: 'admin' },
+    ///   { name: 'Bob', active: false, role: 'user' },
+    ///   { name: 'Charlie', active: true, role: 'user' }
+    /// ];
+    ///
+    /// const result = new Pipeline()
+    ///   .where_matches({ active: true })
+    ///   .toArray(users);
+    /// // [{ name: 'Alice', ... }, { name: 'Charlie', ... }]
+    /// ```
+    #[wasm_bindgen(js_name = whereMatches)]
+    pub fn where_matches(&self, spec: &JsValue) -> Pipeline {
+        let spec_obj = spec.clone();
+
+        // Pre-extract spec keys and values
+        let spec_keys = Object::keys(&Object::from(spec_obj.clone()));
+        let mut spec_entries: Vec<(JsValue, JsValue)> = Vec::new();
+        for i in 0..spec_keys.length() {
+            let key = spec_keys.get(i);
+            if let Ok(val) = Reflect::get(&spec_obj, &key) {
+                spec_entries.push((key, val));
+            }
+        }
+
         let mut ops = self.operations.clone();
 
-        let map_fn = Rc::new(move |val: JsValue| -> JsValue { set_fn(&val, value.clone()) })
-            as Rc<dyn Fn(JsValue) -> JsValue>;
+        let filter_fn = Rc::new(move |val: &JsValue| -> Outcome<bool> {
+            for (key, expected) in &spec_entries {
+                match Reflect::get(val, key) {
+                    Ok(actual) => {
+                        if !js_strict_eq(&actual, expected) {
+                            return Ok(false);
+                        }
+                    }
+                    Err(_) => return Ok(false),
+                }
+            }
+            Ok(true)
+        }) as PredFn;
+
+        ops.push(Operation::Filter(filter_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Filter objects by a spec of field conditions, evaluated in Rust.
+    ///
+    /// A Ramda-`where` analog for JSON rows. Each key is a property name or
+    /// dotted path; each value is either a literal (matched with `===`), a
+    /// nested spec object, or an object of operators:
+    ///
+    /// * `$eq`, `$ne` - strict (in)equality
+    /// * `$gt`, `$gte`, `$lt`, `$lte` - comparison of numbers or of strings
+    /// * `$in`, `$nin` - membership in an array
+    /// * `$exists` - whether the field is defined
+    ///
+    /// Throws if the spec uses an unknown operator.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline()
+    ///   .where({
+    ///     status: 'active',
+    ///     age: { $gte: 18, $lt: 65 },
+    ///     role: { $in: ['admin', 'editor'] },
+    ///     'address.country': 'NZ',
+    ///   })
+    ///   .toArray(rows);
+    /// ```
+    #[wasm_bindgen(js_name = "where")]
+    pub fn where_(&self, spec: &JsValue) -> Result<Pipeline, JsValue> {
+        let spec = WhereSpec::compile(spec)?;
+        let mut ops = self.operations.clone();
+
+        let filter_fn =
+            Rc::new(move |val: &JsValue| -> Outcome<bool> { Ok(spec.matches(val)) }) as PredFn;
+
+        ops.push(Operation::Filter(filter_fn));
+        Ok(Pipeline {
+            operations: ops,
+            strict: self.strict,
+        })
+    }
+
+    /// Apply a lens to extract the focused value from each element.
+    ///
+    /// Equivalent to `.map(x => myLens.get(x))` but avoids the JS function call overhead.
+    ///
+    /// # Arguments
+    ///
+    /// * `optic` - A JsLens to apply
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const nameLens = lens('name');
+    /// const users = [{ name: "Alice" }, { name: "Bob" }];
+    /// const names = new Pipeline().viewLens(nameLens).toArray(users);
+    /// // names: ["Alice", "Bob"]
+    /// ```
+    #[wasm_bindgen(js_name = viewLens)]
+    pub fn view_lens(&self, optic: &crate::optics_wasm::JsLens) -> Pipeline {
+        let get_fn = optic.get_fn.clone();
+        let mut ops = self.operations.clone();
+
+        let map_fn = Rc::new(move |val: &JsValue| -> Outcome<JsValue> { Ok(get_fn(val)) }) as MapFn;
+
+        ops.push(Operation::Map(map_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Transform each element's focused value through a lens using a function.
+    ///
+    /// Equivalent to `.map(x => myLens.over(x, fn))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `optic` - A JsLens to apply
+    /// * `f` - A JavaScript function to transform the focused value
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const priceLens = lens('price');
+    /// const items = [{ name: "A", price: 10 }, { name: "B", price: 20 }];
+    /// const discounted = new Pipeline()
+    ///   .overLens(priceLens, p => p * 0.9)
+    ///   .toArray(items);
+    /// // [{ name: "A", price: 9 }, { name: "B", price: 18 }]
+    /// ```
+    #[wasm_bindgen(js_name = overLens)]
+    pub fn over_lens(&self, optic: &crate::optics_wasm::JsLens, f: &Function) -> Pipeline {
+        let get_fn = optic.get_fn.clone();
+        let set_fn = optic.set_fn.clone();
+        let f = f.clone();
+        let mut ops = self.operations.clone();
+
+        let map_fn = Rc::new(move |val: &JsValue| -> Outcome<JsValue> {
+            let current = get_fn(val);
+            let this = JsValue::null();
+            match f.call1(&this, &current) {
+                Ok(updated) => Ok(set_fn(val, updated)),
+                // Leniently, a failed update leaves the focus unchanged
+                Err(error) => Err(Thrown {
+                    error,
+                    fallback: set_fn(val, current),
+                }),
+            }
+        }) as MapFn;
+
+        ops.push(Operation::Map(map_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Filter elements based on a predicate applied to the focused value of a lens.
+    ///
+    /// Equivalent to `.filter(x => pred(myLens.get(x)))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `optic` - A JsLens to extract the value to test
+    /// * `pred` - A JavaScript predicate function
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const ageLens = lens('age');
+    /// const users = [{ name: "Alice", age: 25 }, { name: "Bob", age: 17 }];
+    /// const adults = new Pipeline()
+    ///   .filterLens(ageLens, a => a >= 18)
+    ///   .toArray(users);
+    /// // [{ name: "Alice", age: 25 }]
+    /// ```
+    #[wasm_bindgen(js_name = filterLens)]
+    pub fn filter_lens(&self, optic: &crate::optics_wasm::JsLens, pred: &Function) -> Pipeline {
+        let get_fn = optic.get_fn.clone();
+        let pred = pred.clone();
+        let mut ops = self.operations.clone();
+
+        let filter_fn =
+            Rc::new(move |val: &JsValue| -> Outcome<bool> { call_predicate(&pred, &get_fn(val)) })
+                as PredFn;
+
+        ops.push(Operation::Filter(filter_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Set the focused value of a lens on every element.
+    ///
+    /// Equivalent to `.map(x => myLens.set(x, value))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `optic` - A JsLens to apply
+    /// * `value` - The value to set
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const statusLens = lens('status');
+    /// const items = [{ id: 1, status: "draft" }, { id: 2, status: "draft" }];
+    /// const published = new Pipeline()
+    ///   .setLens(statusLens, "published")
+    ///   .toArray(items);
+    /// // [{ id: 1, status: "published" }, { id: 2, status: "published" }]
+    /// ```
+    #[wasm_bindgen(js_name = setLens)]
+    pub fn set_lens(&self, optic: &crate::optics_wasm::JsLens, value: JsValue) -> Pipeline {
+        let set_fn = optic.set_fn.clone();
+        let mut ops = self.operations.clone();
+
+        let map_fn =
+            Rc::new(move |val: &JsValue| -> Outcome<JsValue> { Ok(set_fn(val, value.clone())) })
+                as MapFn;
+
+        ops.push(Operation::Map(map_fn));
+        Pipeline {
+            operations: ops,
+            strict: self.strict,
+        }
+    }
+
+    /// Execute the pipeline and collect results into an array.
+    ///
+    /// Like every terminal operation, `source` may be an `Array` or any
+    /// iterable (`Set`, `Map`, generator, iterator). Iterables are consumed
+    /// lazily, so early termination (e.g. `take`) stops pulling from the
+    /// source and closes it via the iterator's `return()` method.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - JavaScript array or iterable to process
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// function* naturals() { let n = 0; while (true) yield n++; }
+    /// new Pipeline().filter(x => x % 2 === 0).take(3).toArray(naturals());
+    /// // [0, 2, 4]
+    /// ```
+    #[wasm_bindgen(js_name = toArray)]
+    pub fn to_array(&self, source: &JsValue) -> Result<Array, JsValue> {
+        if let Some(values) = self.run_numeric(source) {
+            return Ok(Array::from(&Float64Array::from(values.as_slice())));
+        }
+
+        let result = Array::new();
+        self.run(source, |v| {
+            result.push(&v);
+            Ok(true)
+        })?;
+        Ok(result)
+    }
+
+    /// Reduce the source array with a custom reducer function.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - JavaScript array or iterable to process
+    /// * `reducer` - JavaScript function (acc, val) => acc
+    /// * `initial` - Initial accumulator value
+    #[wasm_bindgen]
+    pub fn reduce(
+        &self,
+        source: &JsValue,
+        reducer: &Function,
+        initial: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let mut acc = initial;
+        self.run(source, |v| {
+            let this = JsValue::null();
+            let outcome = reducer.call2(&this, &acc, &v).map_err(lenient(acc.clone()));
+            acc = self.settle_terminal(outcome, "reduce", &v)?;
+            Ok(true)
+        })?;
+        Ok(acc)
+    }
+
+    /// Call `f` for each element produced by the pipeline.
+    ///
+    /// An optional `AbortSignal` is checked before every source element is
+    /// processed and before every output reaches `f`; once it is aborted
+    /// (for example by `f` itself, or by a `tap` further up) the pipeline
+    /// stops as if it had hit `take`, closing an iterable source.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const controller = new AbortController();
+    /// new Pipeline().map(parse).forEach(lines, record => {
+    ///   if (!store.insert(record)) controller.abort();
+    /// }, controller.signal);
+    /// ```
+    #[wasm_bindgen(js_name = forEach)]
+    pub fn for_each(
+        &self,
+        source: &JsValue,
+        f: &Function,
+        signal: Option<AbortSignal>,
+    ) -> Result<(), JsValue> {
+        let aborted = || signal.as_ref().is_some_and(AbortSignal::aborted);
+        if aborted() {
+            return Ok(());
+        }
+
+        self.run_until(source, aborted, |v| {
+            if aborted() {
+                return Ok(false);
+            }
+            let outcome = f.call1(&JsValue::null(), &v).map(drop).map_err(lenient(()));
+            self.settle_terminal(outcome, "forEach", &v)?;
+            Ok(!aborted())
+        })
+    }
+
+    /// Count the elements produced by the pipeline.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const n = new Pipeline().filter(x => x % 2 === 0).count([1, 2, 3, 4]);
+    /// // 2
+    /// ```
+    #[wasm_bindgen]
+    pub fn count(&self, source: &JsValue) -> Result<u32, JsValue> {
+        if let Some(values) = self.run_numeric(source) {
+            return Ok(values.len() as u32);
+        }
+
+        let mut n = 0;
+        self.run(source, |_| {
+            n += 1;
+            Ok(true)
+        })?;
+        Ok(n)
+    }
+
+    /// Sum the numeric elements produced by the pipeline.
+    ///
+    /// Non-numeric values are ignored, as in the standalone `product`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const total = new Pipeline().map(x => x * 2).sum([1, 2, 3]);
+    /// // 12
+    /// ```
+    #[wasm_bindgen]
+    pub fn sum(&self, source: &JsValue) -> Result<f64, JsValue> {
+        if let Some(values) = self.run_numeric(source) {
+            return Ok(crate::simd::sum(&values));
+        }
+
+        let mut total = 0.0;
+        self.run(source, |v| {
+            if let Some(num) = v.as_f64() {
+                total += num;
+            }
+            Ok(true)
+        })?;
+        Ok(total)
+    }
+
+    /// Arithmetic mean of the numeric results, or `undefined` if there are none.
+    ///
+    /// Computed in a single pass without materializing the results.
+    /// Non-numeric results are ignored.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const avgOrder = new Pipeline()
+    ///   .filter(o => o.status === 'paid')
+    ///   .pluck('total')
+    ///   .mean(orders);
+    /// ```
+    #[wasm_bindgen]
+    pub fn mean(&self, source: &JsValue) -> Result<JsValue, JsValue> {
+        let (total, count) = match self.run_numeric(source) {
+            Some(values) => (crate::simd::sum(&values), values.len()),
+            None => {
+                let (mut total, mut count) = (0.0, 0);
+                self.run(source, |v| {
+                    if let Some(num) = v.as_f64() {
+                        total += num;
+                        count += 1;
+                    }
+                    Ok(true)
+                })?;
+                (total, count)
+            }
+        };
+        Ok(if count == 0 {
+            JsValue::undefined()
+        } else {
+            JsValue::from_f64(total / count as f64)
+        })
+    }
+
+    /// Median of the numeric results, or `undefined` if there are none.
+    ///
+    /// Only the numbers themselves are buffered (for sorting), not the
+    /// intermediate JavaScript values. Non-numeric results are ignored.
+    #[wasm_bindgen]
+    pub fn median(&self, source: &JsValue) -> Result<JsValue, JsValue> {
+        Ok(median_of(&mut self.numeric_results(source)?))
+    }
+
+    /// Sample standard deviation of the numeric results, or `undefined` if
+    /// there are fewer than two.
+    ///
+    /// Computed in a single pass with Welford's algorithm. Non-numeric
+    /// results are ignored.
+    #[wasm_bindgen(js_name = stdDev)]
+    pub fn std_dev(&self, source: &JsValue) -> Result<JsValue, JsValue> {
+        let (mut count, mut mean, mut m2) = (0usize, 0.0, 0.0);
+        let mut push = |x: f64| {
+            count += 1;
+            let delta = x - mean;
+            mean += delta / count as f64;
+            m2 += delta * (x - mean);
+        };
+        match self.run_numeric(source) {
+            Some(values) => values.into_iter().for_each(&mut push),
+            None => self.run(source, |v| {
+                if let Some(num) = v.as_f64() {
+                    push(num);
+                }
+                Ok(true)
+            })?,
+        }
+        Ok(if count < 2 {
+            JsValue::undefined()
+        } else {
+            JsValue::from_f64((m2 / (count - 1) as f64).sqrt())
+        })
+    }
+
+    /// Quantile `p` (between 0 and 1) of the numeric results, with linear
+    /// interpolation; `undefined` if there are none or `p` is out of range.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const p95 = new Pipeline().pluck('latencyMs').quantile(requests, 0.95);
+    /// ```
+    #[wasm_bindgen]
+    pub fn quantile(&self, source: &JsValue, p: f64) -> Result<JsValue, JsValue> {
+        if !(0.0..=1.0).contains(&p) {
+            return Ok(JsValue::undefined());
+        }
+        Ok(quantile_of(&mut self.numeric_results(source)?, p))
+    }
+
+    /// Collect the results into a `Float64Array`.
+    ///
+    /// Typed array sources run through a pipeline of numeric operations are
+    /// processed entirely in Rust, so neither the input nor the output is
+    /// boxed element by element. Other sources are processed normally and
+    /// non-numeric results become `NaN`, as with `Float64Array.from`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const samples = new Float64Array(1_000_000);
+    /// const head = new Pipeline().drop(10).take(100).toFloat64Array(samples);
+    /// ```
+    #[wasm_bindgen(js_name = toFloat64Array)]
+    pub fn to_float64_array(&self, source: &JsValue) -> Result<Float64Array, JsValue> {
+        if let Some(values) = self.run_numeric(source) {
+            return Ok(Float64Array::from(values.as_slice()));
+        }
+
+        let mut values = Vec::new();
+        self.run(source, |v| {
+            values.push(v.as_f64().unwrap_or(f64::NAN));
+            Ok(true)
+        })?;
+        Ok(Float64Array::from(values.as_slice()))
+    }
+
+    /// Return the first element produced by the pipeline, or `undefined`.
+    ///
+    /// Stops processing as soon as an element is produced.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const first = new Pipeline().filter(x => x > 10).first(hugeArray);
+    /// ```
+    #[wasm_bindgen]
+    pub fn first(&self, source: &JsValue) -> Result<JsValue, JsValue> {
+        let mut found = JsValue::undefined();
+        self.run(source, |v| {
+            found = v;
+            Ok(false)
+        })?;
+        Ok(found)
+    }
+
+    /// Return the first produced element that satisfies a predicate, or `undefined`.
+    ///
+    /// Stops processing as soon as a match is found.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const admin = new Pipeline().pluck('user').find(events, u => u.role === 'admin');
+    /// ```
+    #[wasm_bindgen]
+    pub fn find(&self, source: &JsValue, pred: &Function) -> Result<JsValue, JsValue> {
+        let mut found = JsValue::undefined();
+        self.run(source, |v| {
+            if self.settle_terminal(call_predicate(pred, &v), "find", &v)? {
+                found = v;
+                Ok(false)
+            } else {
+                Ok(true)
+            }
+        })?;
+        Ok(found)
+    }
+
+    /// Test whether every produced element satisfies a predicate.
+    ///
+    /// Stops at the first element that fails. Returns `true` for an empty result.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const allPositive = new Pipeline().map(x => x * 2).every([1, 2, 3], x => x > 0);
+    /// // true
+    /// ```
+    #[wasm_bindgen]
+    pub fn every(&self, source: &JsValue, pred: &Function) -> Result<bool, JsValue> {
+        let mut all = true;
+        self.run(source, |v| {
+            all = self.settle_terminal(call_predicate(pred, &v), "every", &v)?;
+            Ok(all)
+        })?;
+        Ok(all)
+    }
+
+    /// Test whether any produced element satisfies a predicate.
+    ///
+    /// Stops at the first element that passes. Returns `false` for an empty result.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const anyLarge = new Pipeline().map(x => x * 2).some([1, 2, 3], x => x > 5);
+    /// // true
+    /// ```
+    #[wasm_bindgen]
+    pub fn some(&self, source: &JsValue, pred: &Function) -> Result<bool, JsValue> {
+        let mut any = false;
+        self.run(source, |v| {
+            any = self.settle_terminal(call_predicate(pred, &v), "some", &v)?;
+            Ok(!any)
+        })?;
+        Ok(any)
+    }
+
+    /// Group produced elements by the result of a key function.
+    ///
+    /// Returns a `Map` from key to an array of elements, in first-seen key
+    /// order. Keys are compared with `SameValueZero`, like `Map.groupBy`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const byParity = new Pipeline().groupBy([1, 2, 3, 4], x => x % 2 === 0 ? 'even' : 'odd');
+    /// // Map { 'odd' => [1, 3], 'even' => [2, 4] }
+    /// ```
+    #[wasm_bindgen(js_name = groupBy)]
+    pub fn group_by(&self, source: &JsValue, key_fn: &Function) -> Result<js_sys::Map, JsValue> {
+        let groups = js_sys::Map::new();
+        self.run(source, |v| {
+            let key = self.call_key(key_fn, "groupBy", &v)?;
+            let existing = groups.get(&key);
+            if existing.is_undefined() {
+                groups.set(&key, &Array::of1(&v));
+            } else {
+                Array::from(&existing).push(&v);
+            }
+            Ok(true)
+        })?;
+        Ok(groups)
+    }
+
+    /// Index produced elements by a key function into a `Map`.
+    ///
+    /// Each key maps to the last element that produced it. Keys are
+    /// compared with `SameValueZero`, like `groupBy`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const usersById = new Pipeline().filter(u => u.active).indexBy(users, u => u.id);
+    /// usersById.get(42);
+    /// ```
+    #[wasm_bindgen(js_name = indexBy)]
+    pub fn index_by(&self, source: &JsValue, key_fn: &Function) -> Result<js_sys::Map, JsValue> {
+        let index = js_sys::Map::new();
+        self.run(source, |v| {
+            let key = self.call_key(key_fn, "indexBy", &v)?;
+            index.set(&key, &v);
+            Ok(true)
+        })?;
+        Ok(index)
+    }
+
+    /// Collect produced elements into a plain object keyed by a key function.
+    ///
+    /// Values are the elements themselves, or `valueFn(element)` when given.
+    /// Keys are converted to property keys as in `Object.fromEntries`, and
+    /// later elements overwrite earlier ones with the same key.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const prices = new Pipeline()
+    ///   .filter(p => p.inStock)
+    ///   .toObject(products, p => p.sku, p => p.price);
+    /// // { 'A-1': 9.99, 'B-7': 24.5 }
+    /// ```
+    #[wasm_bindgen(js_name = toObject)]
+    pub fn to_object(
+        &self,
+        source: &JsValue,
+        key_fn: &Function,
+        value_fn: Option<Function>,
+    ) -> Result<Object, JsValue> {
+        let entries = Array::new();
+        self.run(source, |v| {
+            let key = self.call_key(key_fn, "toObject", &v)?;
+            let value = match &value_fn {
+                Some(f) => self.call_key(f, "toObject", &v)?,
+                None => v,
+            };
+            entries.push(&Array::of2(&key, &value));
+            Ok(true)
+        })?;
+        // `fromEntries` defines own data properties, so keys like `__proto__`
+        // can't reach the prototype
+        Object::from_entries(&entries)
+    }
+
+    /// Count occurrences of each produced element.
+    ///
+    /// Returns a `Map` from element to count, in first-seen order. Elements
+    /// are compared with `SameValueZero`, so objects count by identity.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const freq = new Pipeline().pluck('tag').frequencies(posts);
+    /// // Map { 'rust' => 3, 'wasm' => 1 }
+    /// ```
+    #[wasm_bindgen]
+    pub fn frequencies(&self, source: &JsValue) -> Result<js_sys::Map, JsValue> {
+        let counts = js_sys::Map::new();
+        self.run(source, |v| {
+            let current = counts.get(&v).as_f64().unwrap_or(0.0);
+            counts.set(&v, &JsValue::from_f64(current + 1.0));
+            Ok(true)
+        })?;
+        Ok(counts)
+    }
+
+    /// Split produced elements into `[pass, fail]` by a predicate.
+    ///
+    /// The JavaScript counterpart of the Rust `partition` collector: one pass,
+    /// preserving order within each array.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const [evens, odds] = new Pipeline().partition([1, 2, 3, 4, 5], x => x % 2 === 0);
+    /// // [[2, 4], [1, 3, 5]]
+    /// ```
+    #[wasm_bindgen]
+    pub fn partition(&self, source: &JsValue, pred: &Function) -> Result<Array, JsValue> {
+        let (pass, fail) = (Array::new(), Array::new());
+        self.run(source, |v| {
+            if self.settle_terminal(call_predicate(pred, &v), "partition", &v)? {
+                pass.push(&v);
+            } else {
+                fail.push(&v);
+            }
+            Ok(true)
+        })?;
+        Ok(Array::of2(&pass, &fail))
+    }
+
+    /// Split produced elements into `[firstN, rest]` at an index.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const [head, tail] = new Pipeline().map(x => x * 10).splitAt([1, 2, 3, 4], 1);
+    /// // [[10], [20, 30, 40]]
+    /// ```
+    #[wasm_bindgen(js_name = splitAt)]
+    pub fn split_at(&self, source: &JsValue, n: usize) -> Result<Array, JsValue> {
+        let (head, tail) = (Array::new(), Array::new());
+        let mut seen = 0;
+        self.run(source, |v| {
+            if seen < n {
+                head.push(&v);
+            } else {
+                tail.push(&v);
+            }
+            seen += 1;
+            Ok(true)
+        })?;
+        Ok(Array::of2(&head, &tail))
+    }
+
+    /// Split produced elements into `[prefix, rest]`, where `prefix` is the
+    /// longest run from the start that satisfies the predicate.
+    ///
+    /// The predicate is not called again after the first failing element.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const [header, body] = new Pipeline().span(lines, line => line.startsWith('#'));
+    /// ```
+    #[wasm_bindgen]
+    pub fn span(&self, source: &JsValue, pred: &Function) -> Result<Array, JsValue> {
+        let (prefix, rest) = (Array::new(), Array::new());
+        let mut in_prefix = true;
+        self.run(source, |v| {
+            in_prefix = in_prefix && self.settle_terminal(call_predicate(pred, &v), "span", &v)?;
+            if in_prefix {
+                prefix.push(&v);
+            } else {
+                rest.push(&v);
+            }
+            Ok(true)
+        })?;
+        Ok(Array::of2(&prefix, &rest))
+    }
+
+    /// Execute the pipeline over an async iterable, resolving to an array.
+    ///
+    /// Accepts anything usable with `for await`: async iterables (fetch body
+    /// readers wrapped in async generators, Node `Readable` streams) as well as
+    /// ordinary iterables. Elements are processed as they arrive, and early
+    /// termination closes the source via its `return()` method.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// async function* lines(response) { ... }
+    ///
+    /// const errors = await new Pipeline()
+    ///   .filter(line => line.includes('ERROR'))
+    ///   .take(10)
+    ///   .toArrayAsync(lines(await fetch('/log')));
+    /// ```
+    #[wasm_bindgen(js_name = toArrayAsync)]
+    pub fn to_array_async(&self, source: JsValue) -> Promise {
+        let pipeline = self.clone();
+
+        future_to_promise(async move {
+            let result = Array::new();
+            pipeline
+                .run_async(&source, |v| {
+                    result.push(&v);
+                    Ok(None)
+                })
+                .await?;
+            Ok(result.into())
+        })
+    }
+
+    /// Execute the pipeline over an async iterable, calling `f` for each result.
+    ///
+    /// If `f` returns a Promise it is awaited before the next element is
+    /// pulled, giving natural backpressure. The returned Promise resolves when
+    /// the source is exhausted (or the pipeline stops) and rejects if the
+    /// source or `f` throws.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// await new Pipeline()
+    ///   .map(chunk => chunk.toUpperCase())
+    ///   .forEachAsync(stream, async chunk => { await writer.write(chunk); });
+    /// ```
+    #[wasm_bindgen(js_name = forEachAsync)]
+    pub fn for_each_async(&self, source: JsValue, f: Function) -> Promise {
+        let pipeline = self.clone();
+
+        future_to_promise(async move {
+            pipeline
+                .run_async(&source, |v| {
+                    let returned = f.call1(&JsValue::null(), &v)?;
+                    Ok(returned.dyn_into::<Promise>().ok())
+                })
+                .await?;
+            Ok(JsValue::undefined())
+        })
+    }
+
+    /// Expose the pipeline output as a WHATWG `ReadableStream`.
+    ///
+    /// The stream is pull-based: elements are read from `source` and pushed
+    /// through the pipeline only as the consumer asks for them, so piping
+    /// into a slow destination applies backpressure instead of building the
+    /// whole result in memory. Cancelling the stream closes the source.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const csv = new Pipeline()
+    ///   .map(row => row.join(',') + '\n')
+    ///   .toStream(rows());
+    ///
+    /// await csv
+    ///   .pipeThrough(new TextEncoderStream())
+    ///   .pipeThrough(new CompressionStream('gzip'))
+    ///   .pipeTo(fileHandle);
+    /// ```
+    #[wasm_bindgen(js_name = toStream)]
+    pub fn to_stream(&self, source: &JsValue) -> Result<ReadableStream, JsValue> {
+        let pipeline = self.clone();
+        let cursor = Rc::new(RefCell::new(Cursor::new(pipeline, source)));
+
+        let pull_cursor = Rc::clone(&cursor);
+        let pull =
+            Closure::<dyn FnMut(ReadableStreamDefaultController) -> Result<(), JsValue>>::new(
+                move |controller: ReadableStreamDefaultController| match pull_cursor
+                    .borrow_mut()
+                    .next()?
+                {
+                    Some(val) => controller.enqueue_with_chunk(&val),
+                    None => controller.close(),
+                },
+            );
+        let cancel = Closure::<dyn FnMut()>::new(move || cursor.borrow_mut().cancel());
+
+        let underlying_source = Object::new();
+        Reflect::set(&underlying_source, &"pull".into(), &pull.into_js_value())?;
+        Reflect::set(
+            &underlying_source,
+            &"cancel".into(),
+            &cancel.into_js_value(),
+        )?;
+        ReadableStream::new_with_underlying_source(&underlying_source)
+    }
+
+    /// Run the pipeline until a budget of source elements or milliseconds is
+    /// used up, returning the output so far and a way to carry on.
+    ///
+    /// `budget` is `{ maxElements, maxMs }`; the slice ends when either runs
+    /// out. Time is checked after each element, so a slice always reads at
+    /// least one. Operations keep their state between slices and the source
+    /// is read only as far as each budget allows, so long runs can be split
+    /// into slices that leave the main thread free in between.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// let run = pipeline.runWithBudget(records, { maxMs: 8 });
+    /// render(run.output);
+    /// while (!run.done) {
+    ///   await new Promise(requestAnimationFrame);
+    ///   run = run.resume({ maxMs: 8 });
+    ///   render(run.output);
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = runWithBudget)]
+    pub fn run_with_budget(
+        &self,
+        source: &JsValue,
+        budget: &JsValue,
+    ) -> Result<BudgetRun, JsValue> {
+        let budget = SliceBudget::from_js(budget)?;
+        BudgetRun::slice(Cursor::new(self.clone(), source), budget)
+    }
+
+    /// Create a runner that input is pushed into a batch at a time, for
+    /// sources that deliver data in pieces such as WebSocket messages.
+    ///
+    /// Operations keep their state between batches, so `take`, `unique`,
+    /// `scan` and `chunk` carry on where the previous batch left off instead
+    /// of starting over. `finish()` flushes what is still buffered once the
+    /// input ends.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const runner = new Pipeline()
+    ///   .filter(tick => tick.symbol === 'ACME')
+    ///   .chunk(10)
+    ///   .runner();
+    ///
+    /// socket.onmessage = (event) => {
+    ///   render(runner.feed(JSON.parse(event.data)));
+    ///   if (runner.done) socket.close();
+    /// };
+    /// socket.onclose = () => render(runner.finish());
+    /// ```
+    pub fn runner(&self) -> Runner {
+        Runner {
+            state: ProcessState::new(self.operations.len()),
+            pipeline: self.clone(),
+            done: false,
+            consumed: 0,
+        }
+    }
+
+    /// Return a copy of the pipeline.
+    ///
+    /// Pipelines are immutable - every builder method returns a new pipeline
+    /// and terminals keep no state between runs - so a copy is only needed to
+    /// hand out an independent handle, e.g. one that survives `free()` on the
+    /// original.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn clone_pipeline(&self) -> Pipeline {
+        self.clone()
+    }
+
+    /// Compile the pipeline for repeated use.
+    ///
+    /// Returns an equivalent pipeline with every applicable fusion applied
+    /// once up front, so each element goes through fewer operations:
+    ///
+    /// - runs of maps and filters (including `reject`, `compact`, `where`,
+    ///   `pluck`, ...) become a single operation
+    /// - consecutive `take`s, `drop`s, `filterGt`s and `filterRange`s merge
+    ///   into one
+    /// - a `take` directly after maps moves in front of them, so the element
+    ///   that ends the take is not mapped first
+    ///
+    /// Build the pipeline once, freeze it, and run the frozen pipeline on as
+    /// many inputs as needed.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const activeNames = new Pipeline()
+    ///   .pluck('user')
+    ///   .where({ active: true })
+    ///   .pluck('name')
+    ///   .freeze();
+    ///
+    /// for (const batch of batches) {
+    ///   render(activeNames.toArray(batch));
+    /// }
+    /// ```
+    #[wasm_bindgen]
+    pub fn freeze(&self) -> Pipeline {
+        let mut operations = Vec::with_capacity(self.operations.len());
+        for op in self.operations.iter().cloned() {
+            push_fused(&mut operations, op);
+        }
+        Pipeline {
+            operations,
+            strict: self.strict,
+        }
+    }
+
+    /// Serialize the pipeline into a plain, structured-cloneable plan.
+    ///
+    /// Only operations that don't call back into JavaScript can be
+    /// serialized (`take`, `drop`, `unique`, `chunk`, `aperture`,
+    /// `interpose`, `zip` and the numeric operations); anything else is an
+    /// error.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().mapMul(2).take(3).toPlan();
+    /// // [{ op: 'mapMul', n: 2 }, { op: 'take', n: 3 }]
+    /// ```
+    #[wasm_bindgen(js_name = toPlan)]
+    pub fn to_plan(&self) -> Result<Array, JsValue> {
+        self.operations
+            .iter()
+            .enumerate()
+            .map(|(position, op)| op.to_plan_step(position).map(JsValue::from))
+            .collect()
+    }
+
+    /// Serialize the pipeline as a versioned JSON plan.
+    ///
+    /// The plan is `{ version, steps }` with the steps of `toPlan`, the
+    /// format read by `fromPlan` and by the Rust `plan` module, so a
+    /// pipeline built in the browser can be sent to a server and run there.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// new Pipeline().filterGt(0).take(10).toPlanJson();
+    /// // '{"version":1,"steps":[{"op":"filterGt","n":0},{"op":"take","n":10}]}'
+    /// ```
+    #[wasm_bindgen(js_name = toPlanJson)]
+    pub fn to_plan_json(&self) -> Result<String, JsValue> {
+        let steps = self.to_plan()?;
+        let plan = Object::new();
+        Reflect::set(&plan, &"version".into(), &PLAN_VERSION.into())?;
+        Reflect::set(&plan, &"steps".into(), &steps)?;
+        Ok(js_sys::JSON::stringify(&plan)?.into())
+    }
+
+    /// Describe the pipeline's stages, like `EXPLAIN` for a query.
+    ///
+    /// Returns `{ stages, stateful, mayStop, pendingFusions }`. Each stage is
+    /// `{ name, stateful, mayStop, fused }`: its name and parameters, whether
+    /// it keeps state between elements, whether it can end the run before the
+    /// input is exhausted, and the maps and filters fused into it (empty
+    /// unless the pipeline is frozen). `pendingFusions` is the number of
+    /// operations `freeze()` would merge away.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const pipeline = new Pipeline().map(x => x * 2).filter(x => x > 5).take(3);
+    /// pipeline.describe().pendingFusions; // 1
+    ///
+    /// pipeline.freeze().describe().stages;
+    /// // [{ name: 'fused', stateful: false, mayStop: false, fused: ['map', 'filter'] },
+    /// //  { name: 'take(3)', stateful: true, mayStop: true, fused: [] }]
+    /// ```
+    #[wasm_bindgen(js_name = describe)]
+    pub fn describe_pipeline(&self) -> Result<Object, JsValue> {
+        let explain = self.explain_stages();
+        let stages = Array::new();
+        for stage in &explain.stages {
+            let entry = Object::new();
+            Reflect::set(&entry, &"name".into(), &stage.name.as_str().into())?;
+            Reflect::set(&entry, &"stateful".into(), &stage.stateful.into())?;
+            Reflect::set(&entry, &"mayStop".into(), &stage.may_stop.into())?;
+            let fused: Array = stage
+                .fused
+                .iter()
+                .map(|name| JsValue::from(name.as_str()))
+                .collect();
+            Reflect::set(&entry, &"fused".into(), &fused)?;
+            stages.push(&entry);
+        }
+
+        let description = Object::new();
+        Reflect::set(&description, &"stages".into(), &stages)?;
+        Reflect::set(
+            &description,
+            &"stateful".into(),
+            &explain.is_stateful().into(),
+        )?;
+        Reflect::set(&description, &"mayStop".into(), &explain.may_stop().into())?;
+        Reflect::set(
+            &description,
+            &"pendingFusions".into(),
+            &(self.pending_fusions() as u32).into(),
+        )?;
+        Ok(description)
+    }
+
+    /// Render `describe()` as text, one line per stage.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// console.log(new Pipeline().map(x => x + 1).filter(x => x > 0).take(5).freeze().explain());
+    /// // Pipeline (2 stages)
+    /// //   1. fused  (fused: map + filter)
+    /// //   2. take(5)  [stateful, may stop]
+    /// // Stateful: yes (take(5))
+    /// // Early termination: possible (take(5))
+    /// ```
+    #[wasm_bindgen]
+    pub fn explain(&self) -> String {
+        let mut text = self.explain_stages().to_string();
+        let pending = self.pending_fusions();
+        if pending > 0 {
+            text.push_str(&format!(
+                "Pending fusions: {} (apply with freeze())\n",
+                pending
+            ));
+        }
+        text
+    }
+
+    /// Run the pipeline, recording the last `n` values that reach and leave
+    /// each operation, to see where an element was dropped or how it was
+    /// transformed.
+    ///
+    /// Returns `{ output, source, stages }`: everything the pipeline
+    /// produced, the last `n` source elements, and for each operation
+    /// `{ name, inputs, outputs }`. Every recorded value is a
+    /// `{ position, value }` pair, where `position` is the index of the source
+    /// element being processed when it was recorded. The typed array fast
+    /// path is not used, so numeric pipelines are traced as well.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const run = new Pipeline()
+    ///   .map(s => s.trim())
+    ///   .filter(s => s.length > 0)
+    ///   .debugRun(['a', '  ', 'b'], 100);
+    ///
+    /// run.output; // ['a', 'b']
+    /// run.stages[1].inputs.map(e => e.position);  // [0, 1, 2]
+    /// run.stages[1].outputs.map(e => e.position); // [0, 2]: '  ' was dropped by the filter
+    /// ```
+    #[wasm_bindgen(js_name = debugRun)]
+    pub fn debug_run(&self, source: &JsValue, n: usize) -> Result<Object, JsValue> {
+        let mut state = ProcessState::new(self.operations.len());
+        state.trace = Some(Box::new(Trace::new(self.operations.len(), n)));
+        let output = Array::new();
+        let mut sink = |v: JsValue| {
+            output.push(&v);
+            true
+        };
+        let mut recent = VecDeque::new();
+        let mut index: usize = 0;
+        let mut outcome = Ok(true);
+
+        for_each_source(source, |val| {
+            if n > 0 {
+                if recent.len() == n {
+                    recent.pop_front();
+                }
+                recent.push_back((index, val.clone()));
+            }
+            if let Some(trace) = state.trace.as_mut() {
+                trace.position = index;
+            }
+            let results = self.process_value_with_state(val, &mut state);
+            outcome = deliver(results, &mut sink);
+            index += 1;
+            matches!(outcome, Ok(true))
+        });
+
+        if outcome.is_ok() {
+            outcome = self.flush(&mut state, &mut sink);
+        }
+        if let Err(error) = outcome {
+            return Err(strict_error(error, index.saturating_sub(1)));
+        }
+
+        let stages = Array::new();
+        if let Some(trace) = state.trace {
+            for (idx, op) in self.operations.iter().enumerate() {
+                let inputs = trace_entries(&trace.inputs[idx])?;
+                let outputs = trace_entries(&trace.outputs[idx])?;
+                let stage = Object::new();
+                Reflect::set(&stage, &"name".into(), &op.describe().name.as_str().into())?;
+                Reflect::set(&stage, &"inputs".into(), &inputs)?;
+                Reflect::set(&stage, &"outputs".into(), &outputs)?;
+                stages.push(&stage);
+            }
+        }
+
+        let source = trace_entries(&recent)?;
+        let run = Object::new();
+        Reflect::set(&run, &"output".into(), &output)?;
+        Reflect::set(&run, &"source".into(), &source)?;
+        Reflect::set(&run, &"stages".into(), &stages)?;
+        Ok(run)
+    }
+
+    /// The pipeline's operations as stages, in order.
+    fn explain_stages(&self) -> Explain {
+        Explain {
+            stages: self.operations.iter().map(Operation::describe).collect(),
+        }
+    }
+
+    /// How many operations `freeze()` would merge away.
+    fn pending_fusions(&self) -> usize {
+        self.operations.len() - self.freeze().operations.len()
+    }
+
+    /// Rebuild a pipeline from a plan.
+    ///
+    /// Accepts the step array produced by `toPlan`, a versioned
+    /// `{ version, steps }` plan, or that plan as a JSON string (as written by
+    /// `toPlanJson` or the Rust `plan` module). `map` and `filter` steps name
+    /// their function as `{ op: 'map', fn: 'name' }`; the names are looked up
+    /// in `functions`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// // Plan received from a server
+    /// const plan = '{"version":1,"steps":[{"op":"filter","fn":"active"},{"op":"take","n":5}]}';
+    ///
+    /// const pipeline = Pipeline.fromPlan(plan, {
+    ///   active: user => user.lastSeen > cutoff,
+    /// });
+    /// ```
+    #[wasm_bindgen(js_name = fromPlan)]
+    pub fn from_plan(plan: &JsValue, functions: &JsValue) -> Result<Pipeline, JsValue> {
+        let plan = match plan.as_string() {
+            Some(json) => js_sys::JSON::parse(&json)
+                .map_err(|_| invalid_plan("plan string is not valid JSON"))?,
+            None => plan.clone(),
+        };
+
+        let steps: Array = if let Some(steps) = plan.dyn_ref::<Array>() {
+            steps.clone()
+        } else if plan.is_object() {
+            let version = Reflect::get(&plan, &"version".into())?
+                .as_f64()
+                .ok_or_else(|| invalid_plan("plan is missing `version`"))?;
+            if version > f64::from(PLAN_VERSION) {
+                return Err(invalid_plan(format!(
+                    "unsupported plan version {} (expected at most {})",
+                    version, PLAN_VERSION
+                )));
+            }
+            Reflect::get(&plan, &"steps".into())?
+                .dyn_into()
+                .map_err(|_| invalid_plan("plan `steps` must be an array"))?
+        } else {
+            return Err(invalid_plan(
+                "plan must be an array of steps or a `{ version, steps }` object",
+            ));
+        };
+
+        let operations = steps
+            .iter()
+            .map(|step| Operation::from_plan_step(&step, functions))
+            .collect::<Result<_, _>>()?;
+        Ok(Pipeline {
+            operations,
+            strict: false,
+        })
+    }
+
+    /// Execute the pipeline in a Web Worker, resolving with the results.
+    ///
+    /// The pipeline is sent as a plan (see `toPlan`), so it may only contain
+    /// operations that don't call back into JavaScript. `worker` must run the
+    /// `orlando-worker.js` shim shipped with the package. Typed array sources
+    /// have their buffer transferred rather than copied (detaching it for the
+    /// caller) and resolve to a `Float64Array`; other sources are
+    /// structured-cloned and resolve to an `Array`.
+    ///
+    /// # Examples (JavaScript)
+    ///
+    /// ```javascript
+    /// const worker = new Worker(
+    ///   new URL('orlando-transducers/orlando-worker.js', import.meta.url),
+    ///   { type: 'module' },
+    /// );
+    ///
+    /// const hot = await new Pipeline()
+    ///   .mapMul(1.8)
+    ///   .mapAdd(32)
+    ///   .filterGt(100)
+    ///   .runInWorker(readings, worker); // readings: Float64Array
+    /// ```
+    #[wasm_bindgen(js_name = runInWorker)]
+    pub fn run_in_worker(&self, source: JsValue, worker: Worker) -> Result<Promise, JsValue> {
+        thread_local! {
+            static NEXT_JOB_ID: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+        }
+
+        let plan = self.to_plan()?;
+        let id = NEXT_JOB_ID.with(|next| {
+            let id = next.get();
+            next.set(id.wrapping_add(1));
+            id
+        });
+
+        let message = Object::new();
+        Reflect::set(&message, &"id".into(), &id.into())?;
+        Reflect::set(&message, &"plan".into(), &plan)?;
+        Reflect::set(&message, &"source".into(), &source)?;
+
+        // Typed arrays hand their buffer over instead of copying it
+        let transfer = Array::new();
+        if js_sys::ArrayBuffer::is_view(&source) && !source.is_instance_of::<js_sys::DataView>() {
+            transfer.push(&Reflect::get(&source, &"buffer".into())?);
+        }
+
+        let mut settle = None;
+        let response = Promise::new(&mut |resolve, reject| settle = Some((resolve, reject)));
+        let (resolve, reject) = settle.expect("Promise executor runs synchronously");
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let data = event.data();
+            if Reflect::get(&data, &"id".into())
+                .ok()
+                .and_then(|v| v.as_f64())
+                == Some(id as f64)
+            {
+                let _ = resolve.call1(&JsValue::null(), &data);
+            }
+        });
+        let on_error = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            let _ = reject.call1(&JsValue::null(), &event);
+        });
+
+        worker.add_event_listener_with_callback("message", on_message.as_ref().unchecked_ref())?;
+        worker.add_event_listener_with_callback("error", on_error.as_ref().unchecked_ref())?;
+        worker.post_message_with_transfer(&message, &transfer)?;
+
+        Ok(future_to_promise(async move {
+            let outcome = JsFuture::from(response).await;
+
+            let _ = worker.remove_event_listener_with_callback(
+                "message",
+                on_message.as_ref().unchecked_ref(),
+            );
+            let _ = worker
+                .remove_event_listener_with_callback("error", on_error.as_ref().unchecked_ref());
+
+            let data = outcome?;
+            let error = Reflect::get(&data, &"error".into())?;
+            if !error.is_undefined() {
+                return Err(error);
+            }
+            Reflect::get(&data, &"result".into())
+        }))
+    }
+
+    /// Log pipeline execution to console (for debugging).
+    #[wasm_bindgen(js_name = logExecution)]
+    pub fn log_execution(&self, source: &JsValue) -> Result<Array, JsValue> {
+        console::log_1(&"Pipeline execution:".into());
+
+        let pipeline = self.tap(&Function::new_with_args("x", "console.log('Value:', x)"));
+
+        pipeline.to_array(source)
+    }
+
+    // Drive the pipeline over a source, handing each output to `sink` until
+    // the pipeline stops or `sink` returns `Ok(false)`. In strict mode a
+    // callback exception, from an operation or from the terminal's own
+    // callback in `sink`, is returned as the error for the terminal to throw.
+    fn run<F>(&self, source: &JsValue, sink: F) -> Result<(), JsValue>
+    where
+        F: FnMut(JsValue) -> Result<bool, OrlandoError>,
+    {
+        self.run_until(source, || false, sink)
+    }
+
+    // `run`, checking `halted` before each source element is processed. Once
+    // it returns true the source is closed and buffered values are dropped.
+    fn run_until<H, F>(&self, source: &JsValue, halted: H, mut sink: F) -> Result<(), JsValue>
+    where
+        H: Fn() -> bool,
+        F: FnMut(JsValue) -> Result<bool, OrlandoError>,
+    {
+        let mut state = ProcessState::new(self.operations.len());
+        let mut index: usize = 0;
+        let mut outcome = Ok(true);
+        let open = Cell::new(true);
+        let mut failed = None;
+        let mut emit = |v| {
+            let more = sink(v).unwrap_or_else(|error| {
+                failed = Some(error);
+                false
+            });
+            open.set(more);
+            more
+        };
+
+        for_each_source(source, |val| {
+            if halted() {
+                return false;
+            }
+            let results = self.process_value_with_state(val, &mut state);
+            outcome = deliver(results, &mut emit);
+            index += 1;
+            matches!(outcome, Ok(true))
+        });
+
+        // Only the sink stopping rules out emitting buffered values
+        if outcome.is_ok() && open.get() && !halted() {
+            outcome = self.flush(&mut state, &mut emit);
+        }
+        match (outcome, failed) {
+            (Err(error), _) | (Ok(_), Some(error)) => {
+                Err(strict_error(error, index.saturating_sub(1)))
+            }
+            (Ok(_), None) => Ok(()),
+        }
+    }
+
+    // Typed array fast path: when the source is a numeric typed array and
+    // every operation is numeric, process the whole buffer natively. Returns
+    // `None` when the general `JsValue` path must be used instead.
+    fn run_numeric(&self, source: &JsValue) -> Option<Vec<f64>> {
+        let plan = self.numeric_plan()?;
+        let values = typed_array_values(source)?;
+        Some(crate::simd::run_f64(&plan, &values))
+    }
+
+    // The pipeline as a numeric plan, or `None` if any operation needs the
+    // general `JsValue` path.
+    pub(crate) fn numeric_plan(&self) -> Option<Vec<NumericOp<f64>>> {
+        if !self.operations.iter().all(Operation::is_numeric) {
+            return None;
+        }
+        let plan = self
+            .operations
+            .iter()
+            .map(|op| match op {
+                Operation::Take(n) => NumericOp::Take(*n),
+                Operation::Drop(n) => NumericOp::Drop(*n),
+                // `===` semantics, so NaNs are never collapsed
+                Operation::Unique => NumericOp::Unique,
+                Operation::MapAdd(n) => NumericOp::Add(*n),
+                Operation::MapMul(n) => NumericOp::Mul(*n),
+                Operation::ScaleOffset(a, b) => NumericOp::ScaleOffset(*a, *b),
+                Operation::Normalize(mean, std) => NumericOp::Normalize(*mean, *std),
+                Operation::FilterGt(n) => NumericOp::FilterGt(*n),
+                Operation::FilterRange(lo, hi) => NumericOp::FilterRange(*lo, *hi),
+                _ => unreachable!("checked by is_numeric"),
+            })
+            .collect();
+        Some(plan)
+    }
+
+    // Numeric results of the pipeline, skipping non-numbers. Uses the typed
+    // array fast path when possible.
+    pub(crate) fn numeric_results(&self, source: &JsValue) -> Result<Vec<f64>, JsValue> {
+        if let Some(values) = self.run_numeric(source) {
+            return Ok(values);
+        }
+
+        let mut values = Vec::new();
+        self.run(source, |v| {
+            if let Some(num) = v.as_f64() {
+                values.push(num);
+            }
+            Ok(true)
+        })?;
+        Ok(values)
+    }
+
+    // Async counterpart of `run`. The sink may hand back a Promise, which is
+    // awaited before the next element is pulled from the source.
+    async fn run_async<F>(&self, source: &JsValue, mut sink: F) -> Result<(), JsValue>
+    where
+        F: FnMut(JsValue) -> Result<Option<Promise>, JsValue>,
+    {
+        let Some((iterator, is_async)) = js_async_iterator(source)? else {
+            return Ok(());
+        };
+        let next_fn: Function = Reflect::get(&iterator, &JsValue::from_str("next"))?.dyn_into()?;
+        let mut state = ProcessState::new(self.operations.len());
+        let mut index: usize = 0;
+
+        loop {
+            let step = JsFuture::from(Promise::resolve(&next_fn.call0(&iterator)?)).await?;
+            if Reflect::get(&step, &JsValue::from_str("done"))?.is_truthy() {
+                break;
+            }
+
+            let mut value = Reflect::get(&step, &JsValue::from_str("value"))?;
+            if !is_async {
+                // `for await` over a sync iterable awaits each value
+                value = JsFuture::from(Promise::resolve(&value)).await?;
+            }
+
+            let mut outputs = Vec::new();
+            let delivered = deliver(self.process_value_with_state(value, &mut state), &mut |v| {
+                outputs.push(v);
+                true
+            })
+            .map_err(|error| strict_error(error, index));
+            index += 1;
+
+            let fed = match delivered {
+                Ok(more) => feed_async(&mut sink, outputs).await.map(|()| more),
+                Err(error) => Err(error),
+            };
+            match fed {
+                Ok(true) => {}
+                Ok(false) => {
+                    close_async_iterator(&iterator).await?;
+                    break;
+                }
+                Err(error) => {
+                    // Close the source so a generator runs its `finally`
+                    // cleanup, but report the original error
+                    let _ = close_async_iterator(&iterator).await;
+                    return Err(error);
+                }
+            }
+        }
+
+        let mut pending_outputs = Vec::new();
+        self.flush(&mut state, &mut |v| {
+            pending_outputs.push(v);
+            true
+        })
+        .map_err(|error| strict_error(error, index.saturating_sub(1)))?;
+        feed_async(&mut sink, pending_outputs).await
+    }
+
+    // Emit values still buffered by operations once the source is exhausted
+    // or an operation upstream of them stopped early. Returns `Ok(false)` if
+    // the pipeline or `sink` stopped.
+    fn flush<F: FnMut(JsValue) -> bool>(
+        &self,
+        state: &mut ProcessState,
+        sink: &mut F,
+    ) -> Result<bool, OrlandoError> {
+        let mut open = true;
+        for (idx, op) in self.operations.iter().enumerate() {
+            if !matches!(op, Operation::Chunk(_)) || state.stopped_at.is_some_and(|at| idx <= at) {
+                continue;
+            }
+            let pending = std::mem::take(&mut state.buffers[idx]);
+            if pending.is_empty() {
+                continue;
+            }
+            let chunk: Array = pending.into_iter().collect();
+            let results = self.process_value_from(chunk.into(), idx + 1, state);
+            deliver(results, &mut |v| {
+                open = sink(v);
+                open
+            })?;
+            if !open {
+                return Ok(false);
+            }
+        }
+        Ok(state.stopped_at.is_none())
+    }
+
+    // Internal helper to process a single value through the pipeline
+    fn process_value_with_state(
+        &self,
+        val: JsValue,
+        state: &mut ProcessState,
+    ) -> Vec<ProcessResult> {
+        self.process_value_from(val, 0, state)
+    }
+
+    // Process a value starting from a specific operation index
+    #[allow(unused_assignments)]
+    fn process_value_from(
+        &self,
+        mut val: JsValue,
+        start_idx: usize,
+        state: &mut ProcessState,
+    ) -> Vec<ProcessResult> {
+        for (idx, op) in self.operations.iter().enumerate().skip(start_idx) {
+            if let Some(trace) = state.trace.as_mut() {
+                trace.reach(idx, &val);
+            }
+
+            // Unwrap a callback outcome, bailing out with an error describing
+            // the failing operation in strict mode
+            macro_rules! settle {
+                ($outcome:expr) => {
+                    settle!(op.name(), $outcome)
+                };
+                ($name:expr, $outcome:expr) => {
+                    match self.settle($outcome, idx, $name, &val) {
+                        Ok(value) => value,
+                        Err(error) => return vec![ProcessResult::Error(error)],
+                    }
+                };
+            }
+
+            match op {
+                Operation::Map(f) => {
+                    val = settle!(f(&val));
+                }
+                Operation::Filter(pred) => {
+                    if !settle!(pred(&val)) {
+                        return vec![ProcessResult::Skip];
+                    }
+                }
+                // OPTIMIZED: a run of maps and filters in a single operation
+                // This eliminates one match arm and state lookup per stage
+                Operation::Fused(stages) => {
+                    for stage in stages {
+                        match stage {
+                            Stage::Map(f) => val = settle!("map", f(&val)),
+                            Stage::Filter(pred) => {
+                                if !settle!("filter", pred(&val)) {
+                                    return vec![ProcessResult::Skip];
+                                }
+                            }
+                        }
+                    }
+                }
+                Operation::FlatMap(f) => {
+                    // Expand the value into multiple values
+                    let expanded = settle!(f(&val));
+                    let mut results = Vec::new();
+
+                    // Process each expanded value through the remaining operations
+                    for expanded_val in expanded {
+                        let sub_results = self.process_value_from(expanded_val, idx + 1, state);
+
+                        // Check if we should stop early
+                        let should_stop = sub_results.iter().any(ProcessResult::halts);
+
+                        results.extend(sub_results);
+
+                        if should_stop {
+                            break;
+                        }
+                    }
+
+                    return results;
+                }
+                Operation::Take(n) => {
+                    state.counts[idx] += 1;
+                    if state.counts[idx] > *n {
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::TakeWhile(pred) => {
+                    if !settle!(pred(&val)) {
+                        return state.stop_at(idx);
+                    }
+                }
+                Operation::Drop(n) => {
+                    if state.counts[idx] < *n {
+                        state.counts[idx] += 1;
+                        return vec![ProcessResult::Skip];
+                    }
+                }
+                Operation::DropWhile(pred) => {
+                    // flags[idx] records that dropping has finished
+                    if !state.flags[idx] && settle!(pred(&val)) {
+                        return vec![ProcessResult::Skip];
+                    }
+                    state.flags[idx] = true;
+                }
+                Operation::Tap(f) => {
+                    settle!(f(&val));
+                }
+                Operation::Unique => {
+                    if state.values[idx].as_ref() == Some(&val) {
+                        return vec![ProcessResult::Skip];
+                    }
+                    state.values[idx] = Some(val.clone());
+                }
+                Operation::UniqueBy(key_fn) => {
+                    let key = settle!(key_fn(&val));
+                    if state.values[idx].as_ref() == Some(&key) {
+                        return vec![ProcessResult::Skip];
+                    }
+                    state.values[idx] = Some(key);
+                }
+                Operation::Chunk(size) => {
+                    let buffer = &mut state.buffers[idx];
+                    buffer.push(val);
+                    if buffer.len() < *size {
+                        return vec![ProcessResult::Skip];
+                    }
+                    val = std::mem::take(buffer).into_iter().collect::<Array>().into();
+                }
+                Operation::Aperture(size) => {
+                    let buffer = &mut state.buffers[idx];
+                    buffer.push(val);
+                    if buffer.len() > *size {
+                        buffer.remove(0);
+                    }
+                    if buffer.len() < *size {
+                        return vec![ProcessResult::Skip];
+                    }
+                    val = buffer.iter().collect::<Array>().into();
+                }
+                Operation::Scan { f, initial } => {
+                    let acc = state.values[idx].take().unwrap_or_else(|| initial.clone());
+                    let this = JsValue::null();
+                    let next = settle!(f
+                        .call2(&this, &acc, &val)
+                        .map_err(lenient(JsValue::undefined())));
+                    state.values[idx] = Some(next.clone());
+                    val = next;
+                }
+                Operation::Interpose(separator) => {
+                    if !state.flags[idx] {
+                        state.flags[idx] = true;
+                    } else {
+                        let mut results =
+                            self.process_value_from(separator.clone(), idx + 1, state);
+                        if results.iter().any(ProcessResult::halts) {
+                            return results;
+                        }
+                        results.extend(self.process_value_from(val, idx + 1, state));
+                        return results;
+                    }
+                }
+                Operation::Zip(other) => {
+                    let Some(paired) = zip_partner(other, &mut state.counts[idx]) else {
+                        return state.stop_at(idx);
+                    };
+                    val = Array::of2(&val, &paired).into();
+                }
+                Operation::ZipWith { other, f } => {
+                    let Some(paired) = zip_partner(other, &mut state.counts[idx]) else {
+                        return state.stop_at(idx);
+                    };
+                    let this = JsValue::null();
+                    val = settle!(f
+                        .call2(&this, &val, &paired)
+                        .map_err(lenient(JsValue::undefined())));
+                }
+                Operation::MapAdd(n) => {
+                    val = JsValue::from_f64(val.as_f64().unwrap_or(f64::NAN) + n);
+                }
+                Operation::MapMul(n) => {
+                    val = JsValue::from_f64(val.as_f64().unwrap_or(f64::NAN) * n);
+                }
+                Operation::ScaleOffset(a, b) => {
+                    val = JsValue::from_f64(a * val.as_f64().unwrap_or(f64::NAN) + b);
+                }
+                Operation::Normalize(mean, std) => {
+                    val = JsValue::from_f64((val.as_f64().unwrap_or(f64::NAN) - mean) / std);
+                }
+                Operation::FilterGt(n) => {
+                    if !val.as_f64().is_some_and(|x| x > *n) {
+                        return vec![ProcessResult::Skip];
+                    }
+                }
+                Operation::FilterRange(lo, hi) => {
+                    if !val.as_f64().is_some_and(|x| *lo <= x && x < *hi) {
+                        return vec![ProcessResult::Skip];
+                    }
+                }
+            }
+        }
+
+        if let Some(trace) = state.trace.as_mut() {
+            trace.reach(self.operations.len(), &val);
+        }
+        vec![ProcessResult::Continue(val)]
+    }
+
+    // Settle the outcome of a terminal's own callback, which counts as the
+    // operation after the last one.
+    fn settle_terminal<T>(
+        &self,
+        outcome: Outcome<T>,
+        name: &'static str,
+        val: &JsValue,
+    ) -> Result<T, OrlandoError> {
+        self.settle(outcome, self.operations.len(), name, val)
+    }
+
+    // Call a terminal's key or value function, which yields `undefined` in
+    // lenient mode when it throws.
+    fn call_key(
+        &self,
+        f: &Function,
+        name: &'static str,
+        val: &JsValue,
+    ) -> Result<JsValue, OrlandoError> {
+        let outcome = f
+            .call1(&JsValue::null(), val)
+            .map_err(lenient(JsValue::undefined()));
+        self.settle_terminal(outcome, name, val)
+    }
+
+    // Resolve a callback outcome according to the pipeline's error mode. In
+    // strict mode the error records the operation and the value it was given.
+    fn settle<T>(
+        &self,
+        outcome: Outcome<T>,
+        position: usize,
+        name: &'static str,
+        val: &JsValue,
+    ) -> Result<T, OrlandoError> {
+        match outcome {
+            Ok(value) => Ok(value),
+            Err(thrown) if self.strict => Err(OrlandoError::new(
+                ErrorCode::CallbackThrew,
+                "callback threw",
+            )
+            .at_operation(position, name)
+            .with_value(val.clone())
+            .with_cause(thrown.error)),
+            Err(thrown) => Ok(thrown.fallback),
+        }
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum ProcessResult {
+    Continue(JsValue),
+    Skip,
+    Stop(Option<JsValue>),
+    /// A callback threw in strict mode
+    Error(OrlandoError),
+}
+
+impl ProcessResult {
+    /// Whether processing must not continue past this result.
+    fn halts(&self) -> bool {
+        matches!(self, ProcessResult::Stop(_) | ProcessResult::Error(_))
+    }
+}
+
+/// State maintained during pipeline processing.
+///
+/// Each vector has one slot per operation, so repeated operations (e.g. two
+/// `take`s) keep independent state.
+struct ProcessState {
+    /// `take` / `drop`: elements seen so far
+    counts: Vec<usize>,
+    /// `dropWhile`: finished dropping; `interpose`: first element emitted
+    flags: Vec<bool>,
+    /// `unique` / `uniqueBy`: previous value or key; `scan`: accumulator
+    values: Vec<Option<JsValue>>,
+    /// `chunk` / `aperture`: buffered elements
+    buffers: Vec<Vec<JsValue>>,
+    /// `debugRun`: the values passing between operations
+    trace: Option<Box<Trace>>,
+    /// The furthest downstream operation that stopped early; buffers at or
+    /// before it can never be emitted
+    stopped_at: Option<usize>,
+}
+
+impl ProcessState {
+    fn new(op_count: usize) -> Self {
+        ProcessState {
+            counts: vec![0; op_count],
+            flags: vec![false; op_count],
+            values: vec![None; op_count],
+            buffers: vec![Vec::new(); op_count],
+            trace: None,
+            stopped_at: None,
+        }
+    }
+
+    /// Record that the operation at `idx` stopped early.
+    fn stop_at(&mut self, idx: usize) -> Vec<ProcessResult> {
+        self.stopped_at = Some(self.stopped_at.map_or(idx, |at| at.max(idx)));
+        vec![ProcessResult::Stop(None)]
+    }
+}
+
+/// The last `capacity` values that reached and left each operation during a
+/// `debugRun`, with the source position being processed at the time.
+struct Trace {
+    /// Position of the source element being processed
+    position: usize,
+    capacity: usize,
+    inputs: Vec<VecDeque<(usize, JsValue)>>,
+    outputs: Vec<VecDeque<(usize, JsValue)>>,
+}
+
+impl Trace {
+    fn new(op_count: usize, capacity: usize) -> Self {
+        Trace {
+            position: 0,
+            capacity,
+            inputs: vec![VecDeque::new(); op_count],
+            outputs: vec![VecDeque::new(); op_count],
+        }
+    }
+
+    /// Record `val` reaching operation `idx`, and so leaving the one before
+    /// it. `idx` is the operation count for a value leaving the pipeline.
+    fn reach(&mut self, idx: usize, val: &JsValue) {
+        let (position, capacity) = (self.position, self.capacity);
+        let record = |buffer: &mut VecDeque<(usize, JsValue)>| {
+            if capacity == 0 {
+                return;
+            }
+            if buffer.len() == capacity {
+                buffer.pop_front();
+            }
+            buffer.push_back((position, val.clone()));
+        };
+        if let Some(buffer) = idx
+            .checked_sub(1)
+            .and_then(|prev| self.outputs.get_mut(prev))
+        {
+            record(buffer);
+        }
+        if let Some(buffer) = self.inputs.get_mut(idx) {
+            record(buffer);
+        }
+    }
+}
+
+/// Trace entries as an array of `{ position, value }` objects.
+fn trace_entries(buffer: &VecDeque<(usize, JsValue)>) -> Result<Array, JsValue> {
+    let entries = Array::new();
+    for (position, value) in buffer {
+        let entry = Object::new();
+        Reflect::set(&entry, &"position".into(), &(*position as u32).into())?;
+        Reflect::set(&entry, &"value".into(), value)?;
+        entries.push(&entry);
+    }
+    Ok(entries)
+}
+
+/// Pull-based reader over an array or JS iterable, the counterpart of
+/// [`for_each_source`] for consumers that ask for one element at a time.
+enum SourceCursor {
+    Array { array: Array, index: u32 },
+    Iterator(js_sys::Iterator),
+    Empty,
+}
+
+impl SourceCursor {
+    fn new(source: &JsValue) -> Self {
+        if Array::is_array(source) {
+            return SourceCursor::Array {
+                array: source.clone().unchecked_into(),
+                index: 0,
+            };
+        }
+        match js_iterator(source) {
+            Some(iterator) => SourceCursor::Iterator(iterator),
+            None => SourceCursor::Empty,
+        }
+    }
+
+    fn next(&mut self) -> Option<JsValue> {
+        let next = match self {
+            SourceCursor::Array { array, index } => {
+                let val = (*index < array.length()).then(|| array.get(*index));
+                *index += 1;
+                val
+            }
+            SourceCursor::Iterator(iterator) => iterator
+                .next()
+                .ok()
+                .filter(|next| !next.done())
+                .map(|next| next.value()),
+            SourceCursor::Empty => None,
+        };
+        if next.is_none() {
+            *self = SourceCursor::Empty;
+        }
+        next
+    }
+
+    /// Stop reading, closing an unfinished iterator with `return()`.
+    fn close(&mut self) {
+        if let SourceCursor::Iterator(iterator) = self {
+            close_iterator(iterator);
+        }
+        *self = SourceCursor::Empty;
+    }
+}
+
+/// Pull-based execution of a pipeline, producing one output at a time.
+///
+/// Used by terminals that hand control to the consumer, such as `toStream`.
+struct Cursor {
+    pipeline: Pipeline,
+    state: ProcessState,
+    source: SourceCursor,
+    /// Outputs produced but not yet handed out (`flatMap`, `interpose`, ...)
+    pending: VecDeque<JsValue>,
+    finished: bool,
+    /// Source elements consumed so far
+    index: usize,
+}
+
+impl Cursor {
+    fn new(pipeline: Pipeline, source: &JsValue) -> Self {
+        Cursor {
+            state: ProcessState::new(pipeline.operations.len()),
+            pipeline,
+            source: SourceCursor::new(source),
+            pending: VecDeque::new(),
+            finished: false,
+            index: 0,
+        }
+    }
+
+    /// Produce the next output; fails with the exception if a callback throws
+    /// in strict mode.
+    fn next(&mut self) -> Result<Option<JsValue>, JsValue> {
+        loop {
+            if let Some(val) = self.pending.pop_front() {
+                return Ok(Some(val));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+            self.advance()?;
+        }
+    }
+
+    /// Read one source element (or flush once the source is exhausted),
+    /// queueing whatever it produces in `pending`.
+    fn advance(&mut self) -> Result<(), JsValue> {
+        let pending = &mut self.pending;
+        let mut push = |v| {
+            pending.push_back(v);
+            true
+        };
+        let delivered = match self.source.next() {
+            Some(val) => {
+                self.index += 1;
+                let results = self.pipeline.process_value_with_state(val, &mut self.state);
+                deliver(results, &mut push)
+            }
+            None => {
+                self.finished = true;
+                self.pipeline.flush(&mut self.state, &mut push)
+            }
+        };
+        match delivered {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                self.finished = true;
+                self.source.close();
+                self.pipeline
+                    .flush(&mut self.state, &mut |v| {
+                        self.pending.push_back(v);
+                        true
+                    })
+                    .map_err(|error| strict_error(error, self.index.saturating_sub(1)))?;
+                Ok(())
+            }
+            Err(error) => {
+                self.cancel();
+                Err(strict_error(error, self.index.saturating_sub(1)))
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.finished = true;
+        self.pending.clear();
+        self.source.close();
+    }
+}
+
+/// How much one slice of `runWithBudget` may do, from a JS
+/// `{ maxElements, maxMs }` object. The slice ends when either runs out.
+#[derive(Clone, Copy)]
+struct SliceBudget {
+    max_elements: Option<usize>,
+    max_ms: Option<f64>,
+}
+
+impl SliceBudget {
+    fn from_js(budget: &JsValue) -> Result<Self, JsValue> {
+        if !budget.is_object() {
+            return Err(invalid_argument(
+                "budget must be an object like { maxElements, maxMs }",
+            ));
+        }
+        let limit = |key: &str| -> Result<Option<f64>, JsValue> {
+            let value = Reflect::get(budget, &JsValue::from_str(key))?;
+            if value.is_undefined() {
+                return Ok(None);
+            }
+            match value.as_f64() {
+                Some(n) if n >= 0.0 => Ok(Some(n)),
+                _ => Err(invalid_argument(format!(
+                    "budget.{} must be a non-negative number",
+                    key
+                ))),
+            }
+        };
+        let max_elements = limit("maxElements")?.map(|n| n as usize);
+        let max_ms = limit("maxMs")?;
+        if max_elements.is_none() && max_ms.is_none() {
+            return Err(invalid_argument("budget needs maxElements or maxMs"));
+        }
+        Ok(SliceBudget {
+            max_elements,
+            max_ms,
+        })
+    }
+}
+
+/// One slice of a budgeted run, returned by `Pipeline.runWithBudget` and
+/// `BudgetRun.resume`.
+#[wasm_bindgen(skip_typescript)]
+pub struct BudgetRun {
+    output: Array,
+    done: bool,
+    consumed: usize,
+    /// Taken by `resume`, which hands it to the next slice
+    cursor: Option<Cursor>,
+}
+
+impl BudgetRun {
+    fn slice(mut cursor: Cursor, budget: SliceBudget) -> Result<BudgetRun, JsValue> {
+        let start = Date::now();
+        let first = cursor.index;
+        let output = Array::new();
+
+        loop {
+            while let Some(val) = cursor.pending.pop_front() {
+                output.push(&val);
+            }
+            if cursor.finished {
+                return Ok(BudgetRun {
+                    output,
+                    done: true,
+                    consumed: cursor.index,
+                    cursor: None,
+                });
+            }
+
+            // Neither limit counts until an element has been read, so every
+            // slice makes progress
+            let taken = cursor.index - first;
+            let out_of_elements = budget.max_elements.is_some_and(|n| taken >= n.max(1));
+            let out_of_time = budget
+                .max_ms
+                .is_some_and(|ms| taken > 0 && Date::now() - start >= ms);
+            if out_of_elements || out_of_time {
+                return Ok(BudgetRun {
+                    output,
+                    done: false,
+                    consumed: cursor.index,
+                    cursor: Some(cursor),
+                });
+            }
+
+            cursor.advance()?;
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl BudgetRun {
+    /// What the pipeline produced during this slice.
+    #[wasm_bindgen(getter)]
+    pub fn output(&self) -> Array {
+        self.output.clone()
+    }
 
-        ops.push(Operation::Map(map_fn));
-        Pipeline { operations: ops }
+    /// Whether the run is complete: the source is exhausted or the pipeline
+    /// stopped early.
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.done
     }
 
-    /// Execute the pipeline and collect results into an array.
-    ///
-    /// # Arguments
+    /// Source elements consumed so far, over every slice.
+    #[wasm_bindgen(getter)]
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Run the next slice, carrying on where this one stopped.
     ///
-    /// * `source` - JavaScript array to process
-    #[wasm_bindgen(js_name = toArray)]
-    pub fn to_array(&self, source: &Array) -> Array {
-        let result = Array::new();
-        let mut should_stop = false;
-        let mut state = ProcessState::new();
+    /// Resuming a completed run returns another completed, empty slice. Each
+    /// slice can be resumed once; resume the slice it returns after that.
+    pub fn resume(&mut self, budget: &JsValue) -> Result<BudgetRun, JsValue> {
+        let budget = SliceBudget::from_js(budget)?;
+        if self.done {
+            return Ok(BudgetRun {
+                output: Array::new(),
+                done: true,
+                consumed: self.consumed,
+                cursor: None,
+            });
+        }
+        match self.cursor.take() {
+            Some(cursor) => BudgetRun::slice(cursor, budget),
+            None => Err(invalid_argument(
+                "this slice was already resumed or cancelled; resume the slice it returned",
+            )),
+        }
+    }
 
-        for i in 0..source.length() {
-            if should_stop {
-                break;
-            }
+    /// Abandon the run, closing an iterable source so generators can run
+    /// their cleanup.
+    pub fn cancel(&mut self) {
+        if let Some(mut cursor) = self.cursor.take() {
+            cursor.cancel();
+        }
+    }
+}
 
-            let val = source.get(i);
-            let results = self.process_value_with_state(val, &mut state);
+/// Push-based execution of a pipeline, returned by `Pipeline.runner`.
+#[wasm_bindgen(skip_typescript)]
+pub struct Runner {
+    pipeline: Pipeline,
+    state: ProcessState,
+    done: bool,
+    /// Source elements consumed so far
+    consumed: usize,
+}
 
-            for res in results {
-                match res {
-                    ProcessResult::Continue(v) => {
-                        result.push(&v);
-                    }
-                    ProcessResult::Skip => {
-                        // Skip this value
-                    }
-                    ProcessResult::Stop(v) => {
-                        if let Some(val) = v {
-                            result.push(&val);
-                        }
-                        should_stop = true;
-                        break;
-                    }
-                }
+#[wasm_bindgen]
+impl Runner {
+    /// Push a batch (an array or iterable) through the pipeline, returning
+    /// what it produced.
+    ///
+    /// Once the pipeline stops early the rest of the batch is left unread,
+    /// partial chunks after the stopping operation are emitted, and `done`
+    /// is set; feeding a finished runner returns an empty array.
+    /// A callback throwing in strict mode finishes the runner too.
+    pub fn feed(&mut self, batch: &JsValue) -> Result<Array, JsValue> {
+        let output = Array::new();
+        if self.done {
+            return Ok(output);
+        }
+        let mut push = |v: JsValue| {
+            output.push(&v);
+            true
+        };
+        let mut outcome = Ok(true);
+        for_each_source(batch, |val| {
+            self.consumed += 1;
+            let results = self.pipeline.process_value_with_state(val, &mut self.state);
+            outcome = deliver(results, &mut push);
+            matches!(outcome, Ok(true))
+        });
+        if let Ok(false) = outcome {
+            self.done = true;
+            outcome = self.pipeline.flush(&mut self.state, &mut push);
+        }
+        match outcome {
+            Ok(_) => Ok(output),
+            Err(error) => {
+                self.done = true;
+                Err(strict_error(error, self.consumed.saturating_sub(1)))
             }
         }
-
-        result
     }
 
-    /// Reduce the source array with a custom reducer function.
-    ///
-    /// # Arguments
-    ///
-    /// * `source` - JavaScript array to process
-    /// * `reducer` - JavaScript function (acc, val) => acc
-    /// * `initial` - Initial accumulator value
-    #[wasm_bindgen]
-    pub fn reduce(&self, source: &Array, reducer: &Function, initial: JsValue) -> JsValue {
-        let mut acc = initial;
-        let mut should_stop = false;
-        let mut state = ProcessState::new();
+    /// End the input, returning what is still buffered, such as a partial
+    /// `chunk`. The runner is done afterwards.
+    pub fn finish(&mut self) -> Result<Array, JsValue> {
+        let output = Array::new();
+        if self.done {
+            return Ok(output);
+        }
+        self.done = true;
+        let mut push = |v: JsValue| {
+            output.push(&v);
+            true
+        };
+        match self.pipeline.flush(&mut self.state, &mut push) {
+            Ok(_) => Ok(output),
+            Err(error) => Err(strict_error(error, self.consumed.saturating_sub(1))),
+        }
+    }
 
-        for i in 0..source.length() {
-            if should_stop {
-                break;
-            }
+    /// Whether the runner needs no more input: the pipeline stopped early or
+    /// `finish()` was called.
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.done
+    }
 
-            let val = source.get(i);
-            let results = self.process_value_with_state(val, &mut state);
+    /// Source elements consumed so far, over every batch.
+    #[wasm_bindgen(getter)]
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+}
 
-            for res in results {
-                match res {
-                    ProcessResult::Continue(v) => {
-                        let this = JsValue::null();
-                        acc = reducer.call2(&this, &acc, &v).unwrap_or(acc);
-                    }
-                    ProcessResult::Skip => {}
-                    ProcessResult::Stop(v) => {
-                        if let Some(val) = v {
-                            let this = JsValue::null();
-                            acc = reducer.call2(&this, &acc, &val).unwrap_or(acc);
-                        }
-                        should_stop = true;
-                        break;
-                    }
-                }
+/// Feed each element of an array or JS iterable to `f` until it returns `false`.
+///
+/// Arrays are read by index. Other values are consumed through the iterable
+/// protocol (`Symbol.iterator`), or used directly if they are already
+/// iterators; iteration stopped early is closed with `return()` so generators
+/// can run their cleanup. Values that are neither are treated as empty.
+fn for_each_source<F: FnMut(JsValue) -> bool>(source: &JsValue, mut f: F) {
+    if Array::is_array(source) {
+        let array: &Array = source.unchecked_ref();
+        for i in 0..array.length() {
+            if !f(array.get(i)) {
+                return;
             }
         }
-
-        acc
+        return;
     }
 
-    /// Log pipeline execution to console (for debugging).
-    #[wasm_bindgen(js_name = logExecution)]
-    pub fn log_execution(&self, source: &Array) -> Array {
-        console::log_1(&"Pipeline execution:".into());
+    let Some(iterator) = js_iterator(source) else {
+        return;
+    };
+
+    loop {
+        let next = match iterator.next() {
+            Ok(next) => next,
+            Err(_) => return,
+        };
+        if next.done() {
+            return;
+        }
+        if !f(next.value()) {
+            close_iterator(&iterator);
+            return;
+        }
+    }
+}
 
-        let pipeline = self.tap(&Function::new_with_args("x", "console.log('Value:', x)"));
+/// Copy a numeric typed array into a `Vec<f64>` in one bulk transfer.
+///
+/// Returns `None` for anything else, including `BigInt64Array`s.
+fn typed_array_values(source: &JsValue) -> Option<Vec<f64>> {
+    macro_rules! widen {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(array) = source.dyn_ref::<$ty>() {
+                    return Some(array.to_vec().into_iter().map(f64::from).collect());
+                }
+            )*
+        };
+    }
 
-        pipeline.to_array(source)
+    if let Some(array) = source.dyn_ref::<Float64Array>() {
+        return Some(array.to_vec());
     }
+    widen!(
+        js_sys::Float32Array,
+        js_sys::Int32Array,
+        js_sys::Uint32Array,
+        js_sys::Int16Array,
+        js_sys::Uint16Array,
+        js_sys::Int8Array,
+        js_sys::Uint8Array,
+        js_sys::Uint8ClampedArray
+    );
+    None
+}
 
-    // Internal helper to process a single value through the pipeline
-    fn process_value_with_state(
-        &self,
-        val: JsValue,
-        state: &mut ProcessState,
-    ) -> Vec<ProcessResult> {
-        self.process_value_from(val, 0, state)
+/// Obtain an iterator from an iterable or iterator-like value.
+fn js_iterator(source: &JsValue) -> Option<js_sys::Iterator> {
+    if source.is_null() || source.is_undefined() {
+        return None;
     }
 
-    // Process a value starting from a specific operation index
-    #[allow(unused_assignments)]
-    fn process_value_from(
-        &self,
-        mut val: JsValue,
-        start_idx: usize,
-        state: &mut ProcessState,
-    ) -> Vec<ProcessResult> {
-        for (idx, op) in self.operations.iter().enumerate().skip(start_idx) {
-            match op {
-                Operation::Map(f) => {
-                    val = f(val);
-                }
-                Operation::Filter(pred) => {
-                    if !pred(&val) {
-                        return vec![ProcessResult::Skip];
-                    }
-                }
-                // OPTIMIZED: Fused Map + Filter in single operation
-                // This eliminates one function call and one match arm per element
-                Operation::MapFilter { map, filter } => {
-                    val = map(val);
-                    if !filter(&val) {
-                        return vec![ProcessResult::Skip];
-                    }
-                }
-                Operation::FlatMap(f) => {
-                    // Expand the value into multiple values
-                    let expanded = f(val);
-                    let mut results = Vec::new();
+    let iter_fn = Reflect::get(source, &js_sys::Symbol::iterator()).ok()?;
+    if let Some(iter_fn) = iter_fn.dyn_ref::<Function>() {
+        let iterator = iter_fn.call0(source).ok()?;
+        return looks_like_iterator(&iterator).then(|| iterator.unchecked_into());
+    }
 
-                    // Process each expanded value through the remaining operations
-                    for expanded_val in expanded {
-                        let sub_results = self.process_value_from(expanded_val, idx + 1, state);
+    looks_like_iterator(source).then(|| source.clone().unchecked_into())
+}
 
-                        // Check if we should stop early
-                        let should_stop = sub_results
-                            .iter()
-                            .any(|r| matches!(r, ProcessResult::Stop(_)));
+/// Obtain an iterator for `for await`-style consumption.
+///
+/// Returns the iterator and whether it came from `Symbol.asyncIterator`;
+/// falls back to the sync iterable protocol, and to `None` for values that
+/// are not iterable at all.
+pub(crate) fn js_async_iterator(source: &JsValue) -> Result<Option<(JsValue, bool)>, JsValue> {
+    if source.is_null() || source.is_undefined() {
+        return Ok(None);
+    }
 
-                        results.extend(sub_results);
+    let async_fn = Reflect::get(source, &js_sys::Symbol::async_iterator())?;
+    if let Some(async_fn) = async_fn.dyn_ref::<Function>() {
+        return Ok(Some((async_fn.call0(source)?, true)));
+    }
 
-                        if should_stop {
-                            break;
-                        }
-                    }
+    Ok(js_iterator(source).map(|iterator| (iterator.into(), false)))
+}
 
-                    return results;
-                }
-                Operation::Take(n) => {
-                    state.take_count += 1;
-                    if state.take_count > *n {
-                        return vec![ProcessResult::Stop(None)];
-                    }
-                }
-                Operation::TakeWhile(pred) => {
-                    if !pred(&val) {
-                        return vec![ProcessResult::Stop(None)];
-                    }
-                }
-                Operation::Drop(n) => {
-                    if state.drop_count < *n {
-                        state.drop_count += 1;
-                        return vec![ProcessResult::Skip];
-                    }
-                }
-                Operation::DropWhile(pred) => {
-                    if !state.dropping && pred(&val) {
-                        return vec![ProcessResult::Skip];
-                    } else {
-                        state.dropping = false;
-                    }
-                }
-                Operation::Tap(f) => {
-                    f(&val);
-                }
-            }
+/// Signal early exit to an async (or sync) iterator by calling its optional
+/// `return()` method, awaiting the result.
+async fn close_async_iterator(iterator: &JsValue) -> Result<(), JsValue> {
+    if let Ok(return_fn) = Reflect::get(iterator, &JsValue::from_str("return")) {
+        if let Some(return_fn) = return_fn.dyn_ref::<Function>() {
+            let closed = return_fn.call0(iterator)?;
+            JsFuture::from(Promise::resolve(&closed)).await?;
         }
-
-        vec![ProcessResult::Continue(val)]
     }
+    Ok(())
 }
 
-impl Default for Pipeline {
-    fn default() -> Self {
-        Self::new()
+/// Hand values to an async sink in order, awaiting any Promise it returns
+/// before the next value.
+async fn feed_async<F>(sink: &mut F, values: Vec<JsValue>) -> Result<(), JsValue>
+where
+    F: FnMut(JsValue) -> Result<Option<Promise>, JsValue>,
+{
+    for v in values {
+        if let Some(pending) = sink(v)? {
+            JsFuture::from(pending).await?;
+        }
     }
+    Ok(())
 }
 
-enum ProcessResult {
-    Continue(JsValue),
-    Skip,
-    Stop(Option<JsValue>),
+/// True if the value is an object with a callable `next` method.
+fn looks_like_iterator(val: &JsValue) -> bool {
+    val.is_object()
+        && Reflect::get(val, &JsValue::from_str("next")).is_ok_and(|next| next.is_function())
 }
 
-/// State maintained during pipeline processing
-struct ProcessState {
-    take_count: usize,
-    drop_count: usize,
-    dropping: bool,
+/// Signal early exit to an iterator by calling its optional `return()` method.
+fn close_iterator(iterator: &js_sys::Iterator) {
+    if let Ok(return_fn) = Reflect::get(iterator, &JsValue::from_str("return")) {
+        if let Some(return_fn) = return_fn.dyn_ref::<Function>() {
+            let _ = return_fn.call0(iterator);
+        }
+    }
 }
 
-impl ProcessState {
-    fn new() -> Self {
-        ProcessState {
-            take_count: 0,
-            drop_count: 0,
-            dropping: false,
+/// Hand process results to a sink, returning `Ok(false)` once processing
+/// should stop and the exception if a callback threw in strict mode.
+fn deliver<F: FnMut(JsValue) -> bool>(
+    results: Vec<ProcessResult>,
+    sink: &mut F,
+) -> Result<bool, OrlandoError> {
+    for res in results {
+        match res {
+            ProcessResult::Continue(v) => {
+                if !sink(v) {
+                    return Ok(false);
+                }
+            }
+            ProcessResult::Skip => {}
+            ProcessResult::Stop(v) => {
+                if let Some(val) = v {
+                    sink(val);
+                }
+                return Ok(false);
+            }
+            ProcessResult::Error(error) => return Err(error),
         }
     }
+    Ok(true)
+}
+
+/// Finish an error raised in strict mode by recording which source element
+/// was being processed, and convert it for JavaScript.
+fn strict_error(error: OrlandoError, index: usize) -> JsValue {
+    error.at_element(index).into()
+}
+
+/// Version of the `{ version, steps }` plan format; matches
+/// `orlando_transducers::plan::VERSION`.
+const PLAN_VERSION: u32 = 1;
+
+fn invalid_plan(message: impl Into<String>) -> JsValue {
+    OrlandoError::new(ErrorCode::InvalidPlan, message).into()
 }
 
 // Export convenience functions
@@ -765,6 +3556,131 @@ pub fn create_pipeline() -> Pipeline {
     Pipeline::new()
 }
 
+/// Feed every element of `source` to several pipelines in one pass, resolving
+/// to an array with the output of each.
+///
+/// `source` may be async (anything usable with `for await`), so slow sources
+/// such as network streams are read once for all the pipelines instead of
+/// once each. Each pipeline keeps its own state, and the source is closed
+/// once every pipeline has stopped. The pipelines passed in are copied and
+/// stay usable.
+///
+/// # JavaScript Example
+///
+/// ```javascript
+/// import { broadcast, Pipeline } from 'orlando-transducers';
+///
+/// const [errors, slowest, count] = await broadcast(logLines(response), [
+///   new Pipeline().filter(l => l.level === 'error'),
+///   new Pipeline().filter(l => l.ms > 1000).take(10),
+///   new Pipeline().map(() => 1),
+/// ]);
+/// ```
+#[wasm_bindgen(skip_typescript)]
+pub fn broadcast(source: JsValue, pipelines: &Array) -> Result<Promise, JsValue> {
+    let mut branches = pipelines
+        .iter()
+        .map(|pipeline| owned_pipeline(&pipeline).map(Branch::new))
+        .collect::<Result<Vec<_>, JsValue>>()?;
+
+    Ok(future_to_promise(async move {
+        let Some((iterator, is_async)) = js_async_iterator(&source)? else {
+            return Ok(Branch::outputs(&branches).into());
+        };
+        let next_fn: Function = Reflect::get(&iterator, &JsValue::from_str("next"))?.dyn_into()?;
+        let mut index: usize = 0;
+
+        while branches.iter().any(|branch| branch.live) {
+            let step = JsFuture::from(Promise::resolve(&next_fn.call0(&iterator)?)).await?;
+            if Reflect::get(&step, &JsValue::from_str("done"))?.is_truthy() {
+                for branch in branches.iter_mut().filter(|branch| branch.live) {
+                    branch.finish(index)?;
+                }
+                return Ok(Branch::outputs(&branches).into());
+            }
+
+            let mut value = Reflect::get(&step, &JsValue::from_str("value"))?;
+            if !is_async {
+                // `for await` over a sync iterable awaits each value
+                value = JsFuture::from(Promise::resolve(&value)).await?;
+            }
+            for branch in branches.iter_mut().filter(|branch| branch.live) {
+                branch.process(value.clone(), index)?;
+            }
+            index += 1;
+        }
+
+        close_async_iterator(&iterator).await?;
+        Ok(Branch::outputs(&branches).into())
+    }))
+}
+
+/// One pipeline fed by `broadcast`, with its state and output so far.
+struct Branch {
+    pipeline: Pipeline,
+    state: ProcessState,
+    output: Array,
+    /// False once the pipeline has stopped or the source has ended
+    live: bool,
+}
+
+impl Branch {
+    fn new(pipeline: Pipeline) -> Branch {
+        Branch {
+            state: ProcessState::new(pipeline.operations.len()),
+            pipeline,
+            output: Array::new(),
+            live: true,
+        }
+    }
+
+    /// Push the source element at `index` through the pipeline.
+    fn process(&mut self, value: JsValue, index: usize) -> Result<(), JsValue> {
+        let output = &self.output;
+        let results = self
+            .pipeline
+            .process_value_with_state(value, &mut self.state);
+        self.live = deliver(results, &mut |v| {
+            output.push(&v);
+            true
+        })
+        .map_err(|error| strict_error(error, index))?;
+        if !self.live {
+            self.finish(index + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Flush what the pipeline still buffers once `consumed` elements have
+    /// been read and the source has ended.
+    fn finish(&mut self, consumed: usize) -> Result<(), JsValue> {
+        let output = &self.output;
+        self.live = false;
+        self.pipeline
+            .flush(&mut self.state, &mut |v| {
+                output.push(&v);
+                true
+            })
+            .map_err(|error| strict_error(error, consumed.saturating_sub(1)))?;
+        Ok(())
+    }
+
+    fn outputs(branches: &[Branch]) -> Array {
+        branches.iter().map(|branch| &branch.output).collect()
+    }
+}
+
+/// An independent copy of a JS `Pipeline` object, leaving the original
+/// usable (converting the object itself would take it over).
+fn owned_pipeline(value: &JsValue) -> Result<Pipeline, JsValue> {
+    let copy = Reflect::get(value, &JsValue::from_str("clone"))
+        .ok()
+        .and_then(|clone| clone.dyn_into::<Function>().ok())
+        .and_then(|clone| clone.call0(value).ok());
+    copy.and_then(|copy| Pipeline::try_from_js_value(copy).ok())
+        .ok_or_else(|| invalid_argument("broadcast expects an array of Pipelines"))
+}
+
 // ============================================================================
 // Multi-Input Operations (Phase 2a)
 // ============================================================================
@@ -1166,6 +4082,11 @@ pub fn median(source: &Array) -> JsValue {
         }
     }
 
+    median_of(&mut values)
+}
+
+/// Median of `values`, sorting them in place; `undefined` when empty.
+fn median_of(values: &mut [f64]) -> JsValue {
     if values.is_empty() {
         return JsValue::undefined();
     }
@@ -1350,6 +4271,12 @@ pub fn quantile(source: &Array, p: f64) -> JsValue {
         }
     }
 
+    quantile_of(&mut values, p)
+}
+
+/// Linearly interpolated quantile of `values`, sorting them in place;
+/// `undefined` when empty. `p` must already be within `[0, 1]`.
+fn quantile_of(values: &mut [f64], p: f64) -> JsValue {
     if values.is_empty() {
         return JsValue::undefined();
     }
@@ -1435,23 +4362,49 @@ pub fn mode(source: &Array) -> JsValue {
 /// ```
 #[wasm_bindgen]
 pub fn path(obj: &JsValue, path_array: &Array) -> JsValue {
-    let mut current = obj.clone();
+    let keys: Vec<JsValue> = path_array.iter().collect();
+    if keys.iter().any(|key| !key.is_string()) {
+        return JsValue::undefined();
+    }
+    get_in(obj, &keys)
+}
 
-    for i in 0..path_array.length() {
-        let key = path_array.get(i);
+/// A property name or dotted path, as accepted by `pluck` and `where`.
+struct FieldPath {
+    key: JsValue,
+    segments: Vec<JsValue>,
+}
 
-        if let Some(key_str) = key.as_string() {
-            match Reflect::get(&current, &JsValue::from_str(&key_str)) {
-                Ok(value) => {
-                    if value.is_undefined() {
-                        return JsValue::undefined();
-                    }
-                    current = value;
-                }
-                Err(_) => return JsValue::undefined(),
+impl FieldPath {
+    fn new(name: &str) -> Self {
+        FieldPath {
+            key: JsValue::from_str(name),
+            segments: name.split('.').map(JsValue::from_str).collect(),
+        }
+    }
+
+    /// Follow the path, falling back to a property literally named with dots.
+    fn get(&self, obj: &JsValue) -> JsValue {
+        if self.segments.len() > 1 {
+            let found = get_in(obj, &self.segments);
+            if !found.is_undefined() {
+                return found;
             }
-        } else {
-            return JsValue::undefined();
+        }
+        Reflect::get(obj, &self.key).unwrap_or(JsValue::undefined())
+    }
+}
+
+/// Follow `keys` into `obj`, returning `undefined` as soon as a level is missing.
+///
+/// Keys are looked up with `Reflect.get`, so numeric strings index arrays.
+fn get_in(obj: &JsValue, keys: &[JsValue]) -> JsValue {
+    let mut current = obj.clone();
+
+    for key in keys {
+        match Reflect::get(&current, key) {
+            Ok(value) if !value.is_undefined() => current = value,
+            _ => return JsValue::undefined(),
         }
     }
 
@@ -1837,11 +4790,159 @@ fn flatten_value(val: JsValue, depth: usize) -> Vec<JsValue> {
     }
 }
 
+/// Call a JavaScript predicate, treating errors and non-`true` results as `false`.
+/// Next element of a `zip` partner array, or `None` once it is exhausted.
+fn zip_partner(other: &Array, position: &mut usize) -> Option<JsValue> {
+    let index = u32::try_from(*position)
+        .ok()
+        .filter(|&i| i < other.length())?;
+    *position += 1;
+    Some(other.get(index))
+}
+
+fn call_predicate(pred: &Function, val: &JsValue) -> Outcome<bool> {
+    let this = JsValue::null();
+    pred.call1(&this, val)
+        .map(|result| result.as_bool().unwrap_or(false))
+        .map_err(lenient(false))
+}
+
 /// Check if a JsValue is NaN.
 fn is_nan(val: &JsValue) -> bool {
     js_sys::Number::is_nan(val)
 }
 
+/// A compiled `where` spec: the value at every path must meet its condition.
+struct WhereSpec {
+    entries: Vec<(FieldPath, Condition)>,
+}
+
+/// A single test from a `where` spec.
+enum Condition {
+    Equals(JsValue),
+    NotEquals(JsValue),
+    /// Passes when the ordering of actual vs operand is accepted
+    Compare(fn(Ordering) -> bool, JsValue),
+    In(Vec<JsValue>),
+    NotIn(Vec<JsValue>),
+    Exists(bool),
+    Nested(WhereSpec),
+}
+
+impl WhereSpec {
+    fn compile(spec: &JsValue) -> Result<Self, JsValue> {
+        if !is_plain_object(spec) {
+            return Err(invalid_argument("where spec must be an object"));
+        }
+
+        let mut entries = Vec::new();
+        for key in Object::keys(spec.unchecked_ref::<Object>()).iter() {
+            let name = key.as_string().unwrap_or_default();
+            for condition in Condition::compile(&Reflect::get(spec, &key)?)? {
+                entries.push((FieldPath::new(&name), condition));
+            }
+        }
+        Ok(WhereSpec { entries })
+    }
+
+    fn matches(&self, val: &JsValue) -> bool {
+        self.entries
+            .iter()
+            .all(|(field, condition)| condition.test(&field.get(val)))
+    }
+}
+
+impl Condition {
+    /// Compile a spec value: a literal, an operator object or a nested spec.
+    fn compile(expected: &JsValue) -> Result<Vec<Condition>, JsValue> {
+        if !is_plain_object(expected) {
+            return Ok(vec![Condition::Equals(expected.clone())]);
+        }
+
+        let keys = Object::keys(expected.unchecked_ref::<Object>());
+        let operators = keys
+            .iter()
+            .filter(|key| key.as_string().is_some_and(|k| k.starts_with('$')))
+            .count();
+        if operators == 0 {
+            return Ok(vec![Condition::Nested(WhereSpec::compile(expected)?)]);
+        }
+        if operators != keys.length() as usize {
+            return Err(invalid_argument(
+                "where spec cannot mix operators and fields in one object",
+            ));
+        }
+
+        keys.iter()
+            .map(|key| {
+                let operator = key.as_string().unwrap_or_default();
+                let operand = Reflect::get(expected, &key)?;
+                Ok(match operator.as_str() {
+                    "$eq" => Condition::Equals(operand),
+                    "$ne" => Condition::NotEquals(operand),
+                    "$gt" => Condition::Compare(Ordering::is_gt, operand),
+                    "$gte" => Condition::Compare(Ordering::is_ge, operand),
+                    "$lt" => Condition::Compare(Ordering::is_lt, operand),
+                    "$lte" => Condition::Compare(Ordering::is_le, operand),
+                    "$in" => Condition::In(operand_array(&operand, &operator)?),
+                    "$nin" => Condition::NotIn(operand_array(&operand, &operator)?),
+                    "$exists" => Condition::Exists(operand.is_truthy()),
+                    other => {
+                        return Err(invalid_argument(format!(
+                            "unknown where operator `{}`",
+                            other
+                        )))
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn test(&self, actual: &JsValue) -> bool {
+        match self {
+            Condition::Equals(expected) => js_strict_eq(actual, expected),
+            Condition::NotEquals(expected) => !js_strict_eq(actual, expected),
+            Condition::Compare(accept, operand) => js_compare(actual, operand).is_some_and(*accept),
+            Condition::In(options) => options.iter().any(|o| js_strict_eq(actual, o)),
+            Condition::NotIn(options) => !options.iter().any(|o| js_strict_eq(actual, o)),
+            Condition::Exists(expected) => actual.is_undefined() != *expected,
+            Condition::Nested(spec) => spec.matches(actual),
+        }
+    }
+}
+
+fn operand_array(operand: &JsValue, operator: &str) -> Result<Vec<JsValue>, JsValue> {
+    operand
+        .dyn_ref::<Array>()
+        .map(|array| array.iter().collect())
+        .ok_or_else(|| invalid_argument(format!("`{}` expects an array", operator)))
+}
+
+fn invalid_argument(message: impl Into<String>) -> JsValue {
+    OrlandoError::new(ErrorCode::InvalidArgument, message).into()
+}
+
+/// True for object literals (`{}` or `Object.create(null)`), as opposed to
+/// arrays, dates and other class instances.
+fn is_plain_object(val: &JsValue) -> bool {
+    if !val.is_object() || Array::is_array(val) {
+        return false;
+    }
+    let proto = Object::get_prototype_of(val);
+    proto.is_null() || Object::get_prototype_of(&proto).is_null()
+}
+
+/// Order two numbers or two strings, as JavaScript's relational operators do.
+fn js_compare(a: &JsValue, b: &JsValue) -> Option<Ordering> {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x.partial_cmp(&y),
+        _ => match (a.as_string(), b.as_string()) {
+            (Some(x), Some(y)) => Some(x.cmp(&y)),
+            _ => None,
+        },
+    }
+}
+
 /// Strict equality comparison for JsValue, mirroring JavaScript `===`.
 fn js_strict_eq(a: &JsValue, b: &JsValue) -> bool {
     // wasm_bindgen's == uses Object.is() semantics, which is close to ===
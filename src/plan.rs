@@ -0,0 +1,751 @@
+//! # Plans: Serializable pipelines over JSON values
+//!
+//! A [`Plan`] describes a pipeline as data: a version number and a list of
+//! steps, each an operation with its constants. Plans use the same step
+//! layout as the WASM `Pipeline.toPlan` / `Pipeline.fromPlan`, so a pipeline
+//! built on a server can be shipped to the browser and run there, and a
+//! pipeline built in the browser can run natively.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "steps": [
+//!     { "op": "filter", "fn": "isActive" },
+//!     { "op": "map", "fn": "score" },
+//!     { "op": "filterRange", "lo": 0, "hi": 100 },
+//!     { "op": "take", "n": 10 }
+//!   ]
+//! }
+//! ```
+//!
+//! Functions can't be serialized, so `map` and `filter` steps refer to them
+//! by name. Each side registers its own implementation under that name: a
+//! [`Registry`] here, and an object of functions passed to `fromPlan` in
+//! JavaScript.
+//!
+//! [`Plan`] implements `Serialize` and `Deserialize`, so any serde format
+//! works as a transport. [`Plan::to_json`] and [`Plan::from_json`] cover the
+//! common case; for CBOR, use a serde CBOR crate such as `ciborium` and
+//! check the version with [`Plan::compile`] as usual.
+//!
+//! Requires the `json` feature.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use orlando_transducers::plan::{Plan, Registry};
+//! use orlando_transducers::to_vec;
+//! use serde_json::json;
+//!
+//! let plan = Plan::from_json(
+//!     r#"{"version": 1, "steps": [{"op": "map", "fn": "price"}, {"op": "mapMul", "n": 2}]}"#,
+//! )
+//! .unwrap();
+//!
+//! let registry = Registry::new().map("price", |item| item["price"].clone());
+//! let pipeline = plan.compile(&registry).unwrap();
+//!
+//! let items = vec![json!({"price": 3}), json!({"price": 4.5})];
+//! assert_eq!(to_vec(&pipeline, items), vec![json!(6.0), json!(9.0)]);
+//! ```
+
+use crate::step::{cont, stop, Step};
+use crate::transducer::Transducer;
+use crate::transforms::{Aperture, Drop, Interpose, Take, Unique};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// The plan format version written by this crate.
+///
+/// Plans with a newer version are rejected rather than run with steps this
+/// version doesn't understand.
+pub const VERSION: u32 = 1;
+
+/// A pipeline described as data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub version: u32,
+    pub steps: Vec<PlanStep>,
+}
+
+/// One step of a [`Plan`], tagged by its `op` name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum PlanStep {
+    /// Keep the first `n` elements
+    Take { n: usize },
+    /// Skip the first `n` elements
+    Drop { n: usize },
+    /// Group into arrays of `n`; a trailing partial chunk is emitted when the
+    /// input ends (see [`CompiledPlan::run`])
+    Chunk { n: usize },
+    /// Sliding windows of `n` elements
+    Aperture { n: usize },
+    /// Drop consecutive duplicates
+    Unique,
+    /// Insert a separator between elements
+    Interpose { separator: Value },
+    /// Pair elements with the same position in `other`, stopping at its end
+    Zip { other: Vec<Value> },
+    /// Add a constant to numbers
+    MapAdd { n: f64 },
+    /// Multiply numbers by a constant
+    MapMul { n: f64 },
+    /// `a * x + b` for numbers
+    ScaleOffset { a: f64, b: f64 },
+    /// `(x - mean) / std` for numbers
+    Normalize { mean: f64, std: f64 },
+    /// Keep numbers greater than `n`
+    FilterGt { n: f64 },
+    /// Keep numbers in the half-open range `[lo, hi)`
+    FilterRange { lo: f64, hi: f64 },
+    /// Transform each element with a registered function
+    Map {
+        #[serde(rename = "fn")]
+        function: String,
+    },
+    /// Keep elements matching a registered predicate
+    Filter {
+        #[serde(rename = "fn")]
+        function: String,
+    },
+}
+
+/// An error reading or compiling a [`Plan`].
+#[derive(Debug)]
+pub enum PlanError {
+    /// The plan is not valid JSON for the plan schema
+    Json(serde_json::Error),
+    /// The plan was written by a newer version of the format
+    UnsupportedVersion(u32),
+    /// The step at this position names a function missing from the registry
+    UnknownFunction { position: usize, name: String },
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::Json(e) => write!(f, "invalid plan: {}", e),
+            PlanError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported plan version {} (expected at most {})",
+                version, VERSION
+            ),
+            PlanError::UnknownFunction { position, name } => {
+                write!(f, "step {} refers to unknown function `{}`", position, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlanError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Plan {
+    /// A plan of the current [`VERSION`].
+    pub fn new(steps: Vec<PlanStep>) -> Self {
+        Plan {
+            version: VERSION,
+            steps,
+        }
+    }
+
+    /// Parse a plan from JSON, rejecting versions newer than [`VERSION`].
+    pub fn from_json(json: &str) -> Result<Plan, PlanError> {
+        let plan: Plan = serde_json::from_str(json).map_err(PlanError::Json)?;
+        plan.check_version()?;
+        Ok(plan)
+    }
+
+    /// Write the plan as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::plan::{Plan, PlanStep};
+    ///
+    /// let plan = Plan::new(vec![PlanStep::MapMul { n: 2.0 }, PlanStep::Take { n: 3 }]);
+    /// assert_eq!(
+    ///     plan.to_json(),
+    ///     r#"{"version":1,"steps":[{"op":"mapMul","n":2.0},{"op":"take","n":3}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("plans always serialize")
+    }
+
+    /// Build a transducer over [`Value`]s that runs the plan.
+    ///
+    /// `map` and `filter` steps are resolved against `registry`. Numeric steps
+    /// treat non-numbers as NaN, and a result that isn't a finite number
+    /// becomes `null`, as with `JSON.stringify`.
+    pub fn compile(&self, registry: &Registry) -> Result<CompiledPlan, PlanError> {
+        self.check_version()?;
+        let unknown = |position: usize, name: &str| PlanError::UnknownFunction {
+            position,
+            name: name.to_string(),
+        };
+
+        let stages = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(position, step)| {
+                Ok(match step.clone() {
+                    PlanStep::Take { n } => Stage::Take(n),
+                    PlanStep::Drop { n } => Stage::Drop(Drop::new(n)),
+                    PlanStep::Chunk { n } => Stage::Chunk(n.max(1)),
+                    PlanStep::Aperture { n } => Stage::Aperture(Aperture::new(n.max(1))),
+                    PlanStep::Unique => Stage::Unique(Unique::new()),
+                    PlanStep::Interpose { separator } => {
+                        Stage::Interpose(Interpose::new(separator))
+                    }
+                    PlanStep::Zip { other } => Stage::Zip(Rc::new(other)),
+                    PlanStep::MapAdd { n } => numeric(move |x| x + n),
+                    PlanStep::MapMul { n } => numeric(move |x| x * n),
+                    PlanStep::ScaleOffset { a, b } => numeric(move |x| a * x + b),
+                    PlanStep::Normalize { mean, std } => numeric(move |x| (x - mean) / std),
+                    PlanStep::FilterGt { n } => {
+                        Stage::Filter(Rc::new(move |v: &Value| v.as_f64().is_some_and(|x| x > n)))
+                    }
+                    PlanStep::FilterRange { lo, hi } => Stage::Filter(Rc::new(move |v: &Value| {
+                        v.as_f64().is_some_and(|x| lo <= x && x < hi)
+                    })),
+                    PlanStep::Map { function } => Stage::Map(Rc::clone(
+                        registry
+                            .maps
+                            .get(&function)
+                            .ok_or_else(|| unknown(position, &function))?,
+                    )),
+                    PlanStep::Filter { function } => Stage::Filter(Rc::clone(
+                        registry
+                            .filters
+                            .get(&function)
+                            .ok_or_else(|| unknown(position, &function))?,
+                    )),
+                })
+            })
+            .collect::<Result<_, PlanError>>()?;
+
+        Ok(CompiledPlan { stages })
+    }
+
+    fn check_version(&self) -> Result<(), PlanError> {
+        if self.version > VERSION {
+            return Err(PlanError::UnsupportedVersion(self.version));
+        }
+        Ok(())
+    }
+}
+
+type MapFn = Rc<dyn Fn(Value) -> Value>;
+type PredFn = Rc<dyn Fn(&Value) -> bool>;
+
+/// Named functions that `map` and `filter` plan steps refer to.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::plan::{Plan, PlanStep, Registry};
+/// use orlando_transducers::to_vec;
+/// use serde_json::json;
+///
+/// let registry = Registry::new()
+///     .filter("even", |v| v.as_i64().is_some_and(|n| n % 2 == 0))
+///     .map("negate", |v| json!(-v.as_i64().unwrap()));
+///
+/// let plan = Plan::new(vec![
+///     PlanStep::Filter { function: "even".into() },
+///     PlanStep::Map { function: "negate".into() },
+/// ]);
+/// let pipeline = plan.compile(&registry).unwrap();
+/// assert_eq!(to_vec(&pipeline, (1..=4).map(|n| json!(n))), vec![json!(-2), json!(-4)]);
+/// ```
+#[derive(Default, Clone)]
+pub struct Registry {
+    maps: HashMap<String, MapFn>,
+    filters: HashMap<String, PredFn>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a function for `map` steps named `name`.
+    pub fn map<F>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(Value) -> Value + 'static,
+    {
+        self.maps.insert(name.into(), Rc::new(f));
+        self
+    }
+
+    /// Register a predicate for `filter` steps named `name`.
+    pub fn filter<P>(mut self, name: impl Into<String>, pred: P) -> Self
+    where
+        P: Fn(&Value) -> bool + 'static,
+    {
+        self.filters.insert(name.into(), Rc::new(pred));
+        self
+    }
+}
+
+/// A [`Plan`] resolved against a [`Registry`], ready to run.
+///
+/// Like the transforms they are built from, stateful steps (`take`, `chunk`,
+/// `zip`, ...) start afresh on every run.
+///
+/// [`run`](CompiledPlan::run) and [`start`](CompiledPlan::start) emit a
+/// trailing partial `chunk` once the input ends, as `Pipeline.fromPlan`
+/// does in JavaScript and Python. Used as a [`Transducer`], with
+/// [`to_vec`](crate::collectors::to_vec) and the other collectors, a plan
+/// can't see where its input ends, so the partial chunk is dropped there.
+pub struct CompiledPlan {
+    stages: Vec<Stage>,
+}
+
+enum Stage {
+    Take(usize),
+    Drop(Drop<Value>),
+    Chunk(usize),
+    Aperture(Aperture<Value>),
+    Unique(Unique<Value>),
+    Interpose(Interpose<Value>),
+    /// The other side
+    Zip(Rc<Vec<Value>>),
+    Map(MapFn),
+    Filter(PredFn),
+}
+
+/// A numeric map step over JSON values.
+fn numeric(f: impl Fn(f64) -> f64 + 'static) -> Stage {
+    Stage::Map(Rc::new(move |v: Value| {
+        let x = f(v.as_f64().unwrap_or(f64::NAN));
+        serde_json::Number::from_f64(x).map_or(Value::Null, Value::Number)
+    }))
+}
+
+type Reducer<Acc> = Rc<dyn Fn(Acc, Value) -> Step<Acc>>;
+
+/// The furthest downstream step that stopped early. Chunks at or before it
+/// can never be emitted.
+type StoppedAt = Rc<Cell<Option<usize>>>;
+
+fn record_stop(stopped_at: &StoppedAt, position: usize) {
+    stopped_at.set(Some(
+        stopped_at.get().map_or(position, |at| at.max(position)),
+    ));
+}
+
+/// A `chunk` step's buffer and the steps after it, for flushing.
+struct PendingChunk<Acc> {
+    position: usize,
+    buffer: Rc<RefCell<Vec<Value>>>,
+    downstream: Reducer<Acc>,
+}
+
+impl CompiledPlan {
+    /// Run the plan over `source`, collecting its output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::plan::{Plan, Registry};
+    /// use serde_json::json;
+    ///
+    /// let plan = Plan::from_json(r#"{"version": 1, "steps": [{"op": "chunk", "n": 2}]}"#).unwrap();
+    /// let pipeline = plan.compile(&Registry::new()).unwrap();
+    /// assert_eq!(
+    ///     pipeline.run((1..=5).map(|n| json!(n))),
+    ///     vec![json!([1, 2]), json!([3, 4]), json!([5])]
+    /// );
+    /// ```
+    pub fn run<I: IntoIterator<Item = Value>>(&self, source: I) -> Vec<Value> {
+        let mut run = self.start();
+        let mut output = Vec::new();
+        for value in source {
+            output.extend(run.push(value));
+            if run.is_done() {
+                break;
+            }
+        }
+        output.extend(run.finish());
+        output
+    }
+
+    /// Start a run that input is pushed into an element at a time, for
+    /// output as it is produced.
+    pub fn start(&self) -> PlanRun {
+        let stopped_at = StoppedAt::default();
+        let (reducer, chunks) = self.build(
+            |mut acc: Vec<Value>, v| {
+                acc.push(v);
+                cont(acc)
+            },
+            &stopped_at,
+        );
+        PlanRun {
+            reducer,
+            chunks,
+            stopped_at,
+            done: false,
+        }
+    }
+
+    /// Wrap `reducer` in the stages, returning the chain along with the
+    /// `chunk` buffers, upstream first.
+    fn build<Acc, R>(
+        &self,
+        reducer: R,
+        stopped_at: &StoppedAt,
+    ) -> (Reducer<Acc>, Vec<PendingChunk<Acc>>)
+    where
+        R: Fn(Acc, Value) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        // Wrap the reducer from the last stage back to the first
+        let mut next: Reducer<Acc> = Rc::new(reducer);
+        let mut chunks = Vec::new();
+        for (position, stage) in self.stages.iter().enumerate().rev() {
+            next = match stage {
+                Stage::Take(n) => {
+                    let (n, count, stopped_at) = (*n, Cell::new(0), Rc::clone(stopped_at));
+                    Rc::new(move |acc, v| {
+                        if count.get() >= n {
+                            record_stop(&stopped_at, position);
+                            return stop(acc);
+                        }
+                        count.set(count.get() + 1);
+                        let result = next(acc, v);
+                        if count.get() < n {
+                            return result;
+                        }
+                        record_stop(&stopped_at, position);
+                        match result {
+                            Step::Continue(acc) | Step::Stop(acc) => stop(acc),
+                        }
+                    })
+                }
+                Stage::Drop(drop) => drop.apply(move |acc, v| next(acc, v)).into(),
+                Stage::Chunk(size) => {
+                    let size = *size;
+                    let buffer = Rc::new(RefCell::new(Vec::with_capacity(size)));
+                    chunks.push(PendingChunk {
+                        position,
+                        buffer: Rc::clone(&buffer),
+                        downstream: Rc::clone(&next),
+                    });
+                    Rc::new(move |acc, v| {
+                        let items = {
+                            let mut buf = buffer.borrow_mut();
+                            buf.push(v);
+                            if buf.len() < size {
+                                return cont(acc);
+                            }
+                            std::mem::take(&mut *buf)
+                        };
+                        next(acc, Value::Array(items))
+                    })
+                }
+                Stage::Aperture(aperture) => aperture
+                    .apply(move |acc, items: Vec<Value>| next(acc, Value::Array(items)))
+                    .into(),
+                Stage::Unique(unique) => unique.apply(move |acc, v| next(acc, v)).into(),
+                Stage::Interpose(interpose) => interpose.apply(move |acc, v| next(acc, v)).into(),
+                Stage::Zip(other) => {
+                    let (other, index) = (Rc::clone(other), Cell::new(0));
+                    let stopped_at = Rc::clone(stopped_at);
+                    Rc::new(move |acc, v| match other.get(index.get()) {
+                        Some(paired) => {
+                            index.set(index.get() + 1);
+                            next(acc, Value::Array(vec![v, paired.clone()]))
+                        }
+                        None => {
+                            record_stop(&stopped_at, position);
+                            stop(acc)
+                        }
+                    })
+                }
+                Stage::Map(f) => {
+                    let f = Rc::clone(f);
+                    Rc::new(move |acc, v| next(acc, f(v)))
+                }
+                Stage::Filter(pred) => {
+                    let pred = Rc::clone(pred);
+                    Rc::new(move |acc, v| if pred(&v) { next(acc, v) } else { cont(acc) })
+                }
+            };
+        }
+        chunks.reverse();
+        (next, chunks)
+    }
+}
+
+impl Transducer<Value, Value> for CompiledPlan {
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, Value) -> Step<Acc>>
+    where
+        R: Fn(Acc, Value) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let (next, _) = self.build(reducer, &StoppedAt::default());
+        Box::new(move |acc, v| next(acc, v))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        self.stages.iter().fold(input, |hint, stage| match stage {
+            Stage::Take(n) => Take::<Value>::new(*n).size_hint(hint),
+            Stage::Drop(drop) => drop.size_hint(hint),
+            // The partial chunk may or may not be emitted
+            Stage::Chunk(n) => (hint.0 / n, hint.1.map(|m| m.div_ceil(*n))),
+            Stage::Aperture(aperture) => aperture.size_hint(hint),
+            Stage::Unique(unique) => unique.size_hint(hint),
+            Stage::Interpose(interpose) => interpose.size_hint(hint),
+            Stage::Zip(other) => {
+                let upper = hint.1.map_or(other.len(), |n| n.min(other.len()));
+                (hint.0.min(other.len()), Some(upper))
+            }
+            Stage::Map(_) => hint,
+            Stage::Filter(_) => (0, hint.1),
+        })
+    }
+}
+
+/// A run of a [`CompiledPlan`] in progress, returned by
+/// [`CompiledPlan::start`].
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::plan::{Plan, PlanStep, Registry};
+/// use serde_json::json;
+///
+/// let plan = Plan::new(vec![PlanStep::Take { n: 3 }, PlanStep::Chunk { n: 2 }]);
+/// let mut run = plan.compile(&Registry::new()).unwrap().start();
+///
+/// assert!(run.push(json!(1)).is_empty());
+/// assert_eq!(run.push(json!(2)), vec![json!([1, 2])]);
+/// assert!(run.push(json!(3)).is_empty());
+/// // The take is done, but the chunk after it still holds 3
+/// assert!(run.is_done());
+/// assert_eq!(run.finish(), vec![json!([3])]);
+/// ```
+pub struct PlanRun {
+    reducer: Reducer<Vec<Value>>,
+    chunks: Vec<PendingChunk<Vec<Value>>>,
+    stopped_at: StoppedAt,
+    done: bool,
+}
+
+impl PlanRun {
+    /// Push one element through the plan, returning what it produced.
+    ///
+    /// Once the plan has stopped early, further input is ignored.
+    pub fn push(&mut self, value: Value) -> Vec<Value> {
+        if self.done {
+            return Vec::new();
+        }
+        match (self.reducer)(Vec::new(), value) {
+            Step::Continue(output) => output,
+            Step::Stop(output) => {
+                self.done = true;
+                output
+            }
+        }
+    }
+
+    /// Whether the plan has stopped early and needs no more input.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// End the input, returning the partial chunks still buffered by `chunk`
+    /// steps after any step that stopped early.
+    pub fn finish(self) -> Vec<Value> {
+        let mut output = Vec::new();
+        for chunk in &self.chunks {
+            if self.stopped_at.get().is_some_and(|at| chunk.position <= at) {
+                continue;
+            }
+            let items = std::mem::take(&mut *chunk.buffer.borrow_mut());
+            if !items.is_empty() {
+                output = match (chunk.downstream)(output, Value::Array(items)) {
+                    Step::Continue(output) | Step::Stop(output) => output,
+                };
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use serde_json::json;
+
+    fn run(plan: &Plan, registry: &Registry, source: Vec<Value>) -> Vec<Value> {
+        to_vec(&plan.compile(registry).unwrap(), source)
+    }
+
+    #[test]
+    fn test_steps_use_the_wasm_layout() {
+        let plan = Plan::new(vec![
+            PlanStep::Unique,
+            PlanStep::FilterRange { lo: 0.0, hi: 1.5 },
+            PlanStep::Map {
+                function: "f".into(),
+            },
+            PlanStep::Interpose {
+                separator: json!("|"),
+            },
+        ]);
+        let value: Value = serde_json::from_str(&plan.to_json()).unwrap();
+        assert_eq!(
+            value,
+            json!({"version": 1, "steps": [
+                {"op": "unique"},
+                {"op": "filterRange", "lo": 0.0, "hi": 1.5},
+                {"op": "map", "fn": "f"},
+                {"op": "interpose", "separator": "|"},
+            ]})
+        );
+        assert_eq!(Plan::from_json(&plan.to_json()).unwrap(), plan);
+    }
+
+    #[test]
+    fn test_numeric_steps() {
+        let plan = Plan::from_json(
+            r#"{"version": 1, "steps": [
+                {"op": "scaleOffset", "a": 2, "b": 1},
+                {"op": "filterGt", "n": 4},
+                {"op": "normalize", "mean": 5, "std": 2}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            run(&plan, &Registry::new(), vec![json!(1), json!(2), json!(7)]),
+            vec![json!(0.0), json!(5.0)]
+        );
+
+        // Non-numbers become NaN, which JSON can only write as null
+        let add = Plan::new(vec![PlanStep::MapAdd { n: 1.0 }]);
+        assert_eq!(
+            run(&add, &Registry::new(), vec![json!("x"), json!(1)]),
+            vec![Value::Null, json!(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_structural_steps() {
+        let plan = Plan::new(vec![
+            PlanStep::Drop { n: 1 },
+            PlanStep::Zip {
+                other: vec![json!("a"), json!("b"), json!("c")],
+            },
+            PlanStep::Chunk { n: 2 },
+        ]);
+        assert_eq!(
+            run(&plan, &Registry::new(), (0..10).map(|n| json!(n)).collect()),
+            vec![json!([[1, "a"], [2, "b"]])]
+        );
+    }
+
+    #[test]
+    fn test_run_flushes_partial_chunks() {
+        let chunk = |steps: Vec<PlanStep>| {
+            let plan = Plan::new(steps);
+            plan.compile(&Registry::new())
+                .unwrap()
+                .run((1..=10).map(|n| json!(n)))
+        };
+
+        assert_eq!(
+            chunk(vec![PlanStep::Chunk { n: 4 }]),
+            vec![json!([1, 2, 3, 4]), json!([5, 6, 7, 8]), json!([9, 10])]
+        );
+        // A chunk after a step that stopped is still flushed
+        assert_eq!(
+            chunk(vec![PlanStep::Take { n: 5 }, PlanStep::Chunk { n: 2 }]),
+            vec![json!([1, 2]), json!([3, 4]), json!([5])]
+        );
+        assert_eq!(
+            chunk(vec![
+                PlanStep::Zip {
+                    other: vec![json!("a"), json!("b"), json!("c")],
+                },
+                PlanStep::Chunk { n: 2 },
+            ]),
+            vec![json!([[1, "a"], [2, "b"]]), json!([[3, "c"]])]
+        );
+        // ...but one before it is cut off with the rest of the input
+        assert_eq!(
+            chunk(vec![PlanStep::Chunk { n: 3 }, PlanStep::Take { n: 2 }]),
+            vec![json!([1, 2, 3]), json!([4, 5, 6])]
+        );
+        // Flushing one chunk can fill and flush the next
+        assert_eq!(
+            chunk(vec![
+                PlanStep::Take { n: 7 },
+                PlanStep::Chunk { n: 3 },
+                PlanStep::Chunk { n: 2 },
+            ]),
+            vec![json!([[1, 2, 3], [4, 5, 6]]), json!([[7]])]
+        );
+    }
+
+    #[test]
+    fn test_take_stops_early() {
+        let registry = Registry::new().map("boom", |v| {
+            assert_ne!(v, json!(3), "pulled past the take");
+            v
+        });
+        let plan = Plan::new(vec![
+            PlanStep::Map {
+                function: "boom".into(),
+            },
+            PlanStep::Take { n: 2 },
+        ]);
+        assert_eq!(
+            run(&plan, &registry, (1..=5).map(|n| json!(n)).collect()),
+            vec![json!(1), json!(2)]
+        );
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        let plan = Plan::new(vec![
+            PlanStep::Take { n: 1 },
+            PlanStep::Filter {
+                function: "missing".into(),
+            },
+        ]);
+        let registry = Registry::new().map("missing", |v| v);
+        assert!(matches!(
+            plan.compile(&registry),
+            Err(PlanError::UnknownFunction { position: 1, ref name }) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        assert!(matches!(
+            Plan::from_json(r#"{"version": 2, "steps": []}"#),
+            Err(PlanError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            Plan::from_json(r#"{"version": 1, "steps": [{"op": "teleport"}]}"#),
+            Err(PlanError::Json(_))
+        ));
+    }
+}
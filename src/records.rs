@@ -0,0 +1,331 @@
+//! # Records: Nested access and update for `serde_json::Value`
+//!
+//! Native counterparts of the JavaScript `path`, `pathOr` and `evolve` helpers,
+//! operating on [`serde_json::Value`] so Rust pipelines over JSON records read
+//! the same as their JS equivalents.
+//!
+//! Path segments address object keys; on arrays, a segment that parses as an
+//! index addresses that element (as `Reflect.get` does in JavaScript).
+//!
+//! Requires the `json` feature.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use orlando_transducers::records;
+//! use orlando_transducers::{to_vec, Filter, Map, Transducer};
+//! use serde_json::json;
+//!
+//! let users = vec![
+//!     json!({"name": "Alice", "address": {"city": "NYC"}}),
+//!     json!({"name": "Bob"}),
+//! ];
+//!
+//! let pipeline = Map::new(|u: serde_json::Value| {
+//!     records::path_or(&u, &["address", "city"], &json!("unknown")).clone()
+//! })
+//! .compose(Filter::new(|city: &serde_json::Value| city != "unknown"));
+//!
+//! assert_eq!(to_vec(&pipeline, users), vec![json!("NYC")]);
+//! ```
+
+use serde_json::{Map, Value};
+
+/// Look up a segment in a single level of a value.
+fn child<'a>(value: &'a Value, segment: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    }
+}
+
+fn child_mut<'a>(value: &'a mut Value, segment: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Object(map) => map.get_mut(segment),
+        Value::Array(items) => segment
+            .parse::<usize>()
+            .ok()
+            .and_then(move |i| items.get_mut(i)),
+        _ => None,
+    }
+}
+
+/// Access a nested value, returning `None` if any part of the path is missing.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::records::path;
+/// use serde_json::json;
+///
+/// let user = json!({"profile": {"name": "Alice", "tags": ["admin", "ops"]}});
+///
+/// assert_eq!(path(&user, &["profile", "name"]), Some(&json!("Alice")));
+/// assert_eq!(path(&user, &["profile", "tags", "1"]), Some(&json!("ops")));
+/// assert_eq!(path(&user, &["profile", "age"]), None);
+/// ```
+pub fn path<'a, S: AsRef<str>>(value: &'a Value, segments: &[S]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| child(current, segment.as_ref()))
+}
+
+/// Mutable access to a nested value, returning `None` if any part of the path is missing.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::records::path_mut;
+/// use serde_json::json;
+///
+/// let mut user = json!({"profile": {"visits": 1}});
+/// if let Some(visits) = path_mut(&mut user, &["profile", "visits"]) {
+///     *visits = json!(2);
+/// }
+/// assert_eq!(user, json!({"profile": {"visits": 2}}));
+/// ```
+pub fn path_mut<'a, S: AsRef<str>>(value: &'a mut Value, segments: &[S]) -> Option<&'a mut Value> {
+    segments.iter().try_fold(value, |current, segment| {
+        child_mut(current, segment.as_ref())
+    })
+}
+
+/// Access a nested value with a default.
+///
+/// Returns `default` if any part of the path is missing. A present `null` is
+/// returned as-is, matching `pathOr` in JavaScript where only `undefined`
+/// falls back to the default.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::records::path_or;
+/// use serde_json::json;
+///
+/// let user = json!({"profile": {"name": "Alice"}});
+/// let anonymous = json!("Anonymous");
+///
+/// assert_eq!(path_or(&user, &["profile", "name"], &anonymous), &json!("Alice"));
+/// assert_eq!(path_or(&user, &["profile", "nick"], &anonymous), &json!("Anonymous"));
+/// ```
+pub fn path_or<'a, S: AsRef<str>>(
+    value: &'a Value,
+    segments: &[S],
+    default: &'a Value,
+) -> &'a Value {
+    path(value, segments).unwrap_or(default)
+}
+
+/// Return a copy of `value` with `new_value` stored at the given path.
+///
+/// Missing or non-object intermediate levels are replaced by empty objects,
+/// so the path always exists afterwards. Array elements can be replaced by
+/// index but arrays are never extended: a path through an index past the
+/// end of an array leaves the value unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::records::assoc_path;
+/// use serde_json::json;
+///
+/// let user = json!({"name": "Alice"});
+/// let updated = assoc_path(&user, &["profile", "city"], json!("NYC"));
+///
+/// assert_eq!(updated, json!({"name": "Alice", "profile": {"city": "NYC"}}));
+/// assert_eq!(user, json!({"name": "Alice"})); // original untouched
+/// ```
+pub fn assoc_path<S: AsRef<str>>(value: &Value, segments: &[S], new_value: Value) -> Value {
+    let mut result = value.clone();
+    set_path(&mut result, segments, new_value);
+    result
+}
+
+/// Store `new_value` at the given path in place. See [`assoc_path`].
+pub fn set_path<S: AsRef<str>>(value: &mut Value, segments: &[S], new_value: Value) {
+    let Some((last, parents)) = segments.split_last() else {
+        *value = new_value;
+        return;
+    };
+
+    let mut current = value;
+    for segment in parents {
+        match descend_or_create(current, segment.as_ref()) {
+            Some(child) => current = child,
+            None => return,
+        }
+    }
+
+    match current {
+        Value::Array(items) => {
+            if let Some(slot) = last
+                .as_ref()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| items.get_mut(i))
+            {
+                *slot = new_value;
+            }
+        }
+        other => {
+            if !other.is_object() {
+                *other = Value::Object(Map::new());
+            }
+            if let Value::Object(map) = other {
+                map.insert(last.as_ref().to_string(), new_value);
+            }
+        }
+    }
+}
+
+/// The child of `current` at `segment`, creating it if missing; `None` for
+/// an array without that index, which is left alone.
+fn descend_or_create<'a>(current: &'a mut Value, segment: &str) -> Option<&'a mut Value> {
+    if current.is_array() {
+        return child_mut(current, segment);
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    match current {
+        Value::Object(map) => Some(
+            map.entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Map::new())),
+        ),
+        _ => unreachable!("replaced with an object above"),
+    }
+}
+
+/// A value transformation used by [`evolve`].
+pub type ValueFn<'a> = &'a dyn Fn(&Value) -> Value;
+
+/// Return a copy of `value` with the given paths transformed.
+///
+/// Each transformation is keyed by a dot-separated path (`"profile.bio"`), as
+/// in the JavaScript `evolve`. Paths that do not exist are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::records::{evolve, ValueFn};
+/// use serde_json::{json, Value};
+///
+/// let user = json!({"name": "alice", "age": 25, "profile": {"bio": "hello"}});
+///
+/// let upper = |v: &Value| json!(v.as_str().unwrap_or_default().to_uppercase());
+/// let inc = |v: &Value| json!(v.as_i64().unwrap_or(0) + 1);
+/// let evolved = evolve(&user, &[("name", &upper as ValueFn), ("age", &inc)]);
+///
+/// assert_eq!(evolved, json!({"name": "ALICE", "age": 26, "profile": {"bio": "hello"}}));
+/// ```
+pub fn evolve(value: &Value, transformations: &[(&str, ValueFn<'_>)]) -> Value {
+    let mut result = value.clone();
+    for (key, transform) in transformations {
+        let segments: Vec<&str> = key.split('.').collect();
+        if let Some(target) = path_mut(&mut result, &segments) {
+            *target = transform(target);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "user": {
+                "name": "Alice",
+                "address": {"city": "NYC", "zip": null},
+                "orders": [{"id": 1}, {"id": 2}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_path_nested_and_arrays() {
+        let v = sample();
+        assert_eq!(path(&v, &["user", "address", "city"]), Some(&json!("NYC")));
+        assert_eq!(path(&v, &["user", "orders", "1", "id"]), Some(&json!(2)));
+        assert_eq!(path(&v, &["user", "orders", "5"]), None);
+        assert_eq!(path(&v, &["user", "name", "first"]), None);
+    }
+
+    #[test]
+    fn test_path_empty_returns_root() {
+        let v = sample();
+        assert_eq!(path::<&str>(&v, &[]), Some(&v));
+    }
+
+    #[test]
+    fn test_path_with_owned_segments() {
+        let v = sample();
+        let segments: Vec<String> = "user.name".split('.').map(String::from).collect();
+        assert_eq!(path(&v, &segments), Some(&json!("Alice")));
+    }
+
+    #[test]
+    fn test_path_or_keeps_null() {
+        let v = sample();
+        let fallback = json!("none");
+        assert_eq!(
+            path_or(&v, &["user", "address", "zip"], &fallback),
+            &Value::Null
+        );
+        assert_eq!(path_or(&v, &["user", "phone"], &fallback), &fallback);
+    }
+
+    #[test]
+    fn test_assoc_path_creates_and_replaces() {
+        let v = sample();
+        let updated = assoc_path(&v, &["user", "address", "zip"], json!("10001"));
+        assert_eq!(
+            path(&updated, &["user", "address", "zip"]),
+            Some(&json!("10001"))
+        );
+
+        let replaced = assoc_path(&v, &["user", "name", "first"], json!("A"));
+        assert_eq!(
+            path(&replaced, &["user", "name"]),
+            Some(&json!({"first": "A"}))
+        );
+
+        let in_array = assoc_path(&v, &["user", "orders", "0", "id"], json!(10));
+        assert_eq!(
+            path(&in_array, &["user", "orders", "0", "id"]),
+            Some(&json!(10))
+        );
+    }
+
+    #[test]
+    fn test_set_path_does_not_extend_arrays() {
+        let mut v = json!({"xs": [1, 2]});
+        set_path(&mut v, &["xs", "5"], json!(9));
+        assert_eq!(v, json!({"xs": [1, 2]}));
+
+        // Nor replaced, when the index is an intermediate step
+        set_path(&mut v, &["xs", "5", "id"], json!(9));
+        assert_eq!(v, json!({"xs": [1, 2]}));
+    }
+
+    #[test]
+    fn test_evolve_dotted_and_missing() {
+        let v = sample();
+        let shout = |v: &Value| json!(format!("{}!", v.as_str().unwrap_or_default()));
+        let evolved = evolve(
+            &v,
+            &[
+                ("user.address.city", &shout as ValueFn),
+                ("user.missing", &shout),
+            ],
+        );
+        assert_eq!(
+            path(&evolved, &["user", "address", "city"]),
+            Some(&json!("NYC!"))
+        );
+        assert_eq!(path(&evolved, &["user", "missing"]), None);
+    }
+}
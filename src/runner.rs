@@ -0,0 +1,217 @@
+//! # Runner: Feeding a pipeline input as it arrives
+//!
+//! A [`Runner`] is a pipeline that input is pushed into a batch at a time,
+//! for sources that deliver data in pieces: messages from a socket, lines
+//! from a child process, pages from an API. Each [`Runner::feed`] returns the
+//! output for that batch and, unless the pipeline stopped early, the runner
+//! back in [`Feed::Pending`] to be fed the next one.
+//!
+//! Stateful stages (`scan`, `unique`, `take`, `chunk`, ...) are applied once
+//! and keep their state from one batch to the next, so feeding a source in
+//! batches produces the same output as running it in a single go:
+//!
+//! ```rust
+//! use orlando_transducers::runner::{Feed, Runner};
+//! use orlando_transducers::{Scan, Take, Transducer};
+//!
+//! let running_total = Scan::new(0, |acc: &i32, x: &i32| acc + x).compose(Take::new(5));
+//! let mut runner = Runner::new(&running_total);
+//!
+//! let messages = vec![vec![1, 2], vec![3], vec![4, 5, 6, 7]];
+//! let mut totals = Vec::new();
+//! for batch in messages {
+//!     match runner.feed(batch) {
+//!         Feed::Pending { output, runner: next } => {
+//!             totals.extend(output);
+//!             runner = next;
+//!         }
+//!         Feed::Done { output } => {
+//!             totals.extend(output);
+//!             break; // no more input needed
+//!         }
+//!     }
+//! }
+//! assert_eq!(totals, vec![1, 3, 6, 10, 15]);
+//! ```
+//!
+//! Where [`budget::run_with_budget`](crate::budget::run_with_budget) pulls
+//! from a source it owns, a runner is pushed to by the caller and never
+//! waits for input.
+
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// A pipeline applied to a reducer that collects into a `Vec`.
+type Collecting<T, U> = Box<dyn Fn(Vec<U>, T) -> Step<Vec<U>>>;
+
+/// A pipeline, with the state of its stages, waiting for input.
+pub struct Runner<T, U> {
+    reducer: Collecting<T, U>,
+    consumed: usize,
+}
+
+/// What a [`Runner`] made of one batch of input.
+pub enum Feed<T, U> {
+    /// The whole batch went through; feed `runner` the next one
+    Pending {
+        output: Vec<U>,
+        runner: Runner<T, U>,
+    },
+    /// The pipeline stopped early (`take`, `take_while`), possibly partway
+    /// through the batch, and needs no more input
+    Done { output: Vec<U> },
+}
+
+impl<T, U> Feed<T, U> {
+    /// What the pipeline produced from the batch.
+    pub fn output(&self) -> &[U] {
+        match self {
+            Feed::Pending { output, .. } | Feed::Done { output } => output,
+        }
+    }
+
+    /// Whether the pipeline has stopped.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Feed::Done { .. })
+    }
+
+    /// The batch's output, and the runner to feed next unless the pipeline
+    /// has stopped.
+    pub fn into_parts(self) -> (Vec<U>, Option<Runner<T, U>>) {
+        match self {
+            Feed::Pending { output, runner } => (output, Some(runner)),
+            Feed::Done { output } => (output, None),
+        }
+    }
+}
+
+impl<T: 'static, U: 'static> Runner<T, U> {
+    /// Apply `transducer`, ready for the first batch.
+    pub fn new(transducer: &impl Transducer<T, U>) -> Self {
+        Runner {
+            reducer: transducer.apply(|mut acc: Vec<U>, x: U| {
+                acc.push(x);
+                cont(acc)
+            }),
+            consumed: 0,
+        }
+    }
+}
+
+impl<T, U> Runner<T, U> {
+    /// Input elements consumed so far, over every batch.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Push a batch of input through the pipeline.
+    ///
+    /// Input after the point where the pipeline stops is not read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::runner::Runner;
+    /// use orlando_transducers::Unique;
+    ///
+    /// let (output, runner) = Runner::new(&Unique::new()).feed(vec![1, 1, 2]).into_parts();
+    /// assert_eq!(output, vec![1, 2]);
+    ///
+    /// // A repeat is still recognised when it starts the next batch
+    /// let (output, _) = runner.unwrap().feed(vec![2, 3, 3, 1]).into_parts();
+    /// assert_eq!(output, vec![3, 1]);
+    /// ```
+    pub fn feed<I: IntoIterator<Item = T>>(mut self, batch: I) -> Feed<T, U> {
+        let mut output = Vec::new();
+        for item in batch {
+            match self.push(output, item) {
+                Step::Continue(acc) => output = acc,
+                Step::Stop(acc) => return Feed::Done { output: acc },
+            }
+        }
+        Feed::Pending {
+            output,
+            runner: self,
+        }
+    }
+
+    /// Push one element, appending what it produces to `output`.
+    pub(crate) fn push(&mut self, output: Vec<U>, item: T) -> Step<Vec<U>> {
+        self.consumed += 1;
+        (self.reducer)(output, item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::to_vec;
+    use crate::transforms::{Chunk, Filter, Scan, Take, TakeWhile};
+
+    /// Feed `batches` in turn, returning all the output and whether the
+    /// pipeline stopped.
+    fn feed_all<T, U>(mut runner: Runner<T, U>, batches: Vec<Vec<T>>) -> (Vec<U>, bool) {
+        let mut all = Vec::new();
+        for batch in batches {
+            match runner.feed(batch) {
+                Feed::Pending {
+                    output,
+                    runner: next,
+                } => {
+                    all.extend(output);
+                    runner = next;
+                }
+                Feed::Done { output } => {
+                    all.extend(output);
+                    return (all, true);
+                }
+            }
+        }
+        (all, false)
+    }
+
+    #[test]
+    fn test_batches_match_single_run() {
+        let pipeline = || {
+            Scan::new(0, |acc: &i32, x: &i32| acc + x)
+                .compose(Filter::new(|x: &i32| x % 2 == 0))
+                .compose(Chunk::new(3))
+        };
+        let expected = to_vec(&pipeline(), 0..40);
+
+        let batches: Vec<Vec<i32>> = vec![
+            (0..1).collect(),
+            vec![],
+            (1..13).collect(),
+            (13..14).collect(),
+            (14..40).collect(),
+        ];
+        let (output, done) = feed_all(Runner::new(&pipeline()), batches);
+        assert_eq!(output, expected);
+        assert!(!done);
+    }
+
+    #[test]
+    fn test_stops_partway_through_batch() {
+        let runner = Runner::new(&Take::new(3));
+        let Feed::Pending { output, runner } = runner.feed(vec![1, 2]) else {
+            panic!("take(3) stopped after two elements");
+        };
+        assert_eq!(output, vec![1, 2]);
+        assert_eq!(runner.consumed(), 2);
+
+        let last = runner.feed(vec![3, 4, 5]);
+        assert!(last.is_done());
+        assert_eq!(last.output(), &[3]);
+    }
+
+    #[test]
+    fn test_unbounded_batch_is_read_only_until_stop() {
+        let runner = Runner::new(&TakeWhile::new(|x: &u64| *x < 4));
+        let feed = runner.feed(0..);
+        assert!(feed.is_done());
+        assert_eq!(feed.output(), &[0, 1, 2, 3]);
+    }
+}
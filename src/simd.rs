@@ -3,9 +3,20 @@
 //! This module provides vectorized implementations for common operations
 //! when working with numeric data. It uses WASM SIMD instructions for
 //! high performance.
+//!
+//! [`run_f64`], [`run_f32`] and [`run_i32`] execute a whole numeric plan
+//! over a slice, one vectorized pass per step:
+//!
+//! ```rust
+//! use orlando_transducers::simd::{run_f64, NumericOp};
+//!
+//! let plan = [NumericOp::Mul(2.0), NumericOp::Clamp(0.0, 5.0), NumericOp::FilterGt(1.0)];
+//! assert_eq!(run_f64(&plan, &[-1.0, 0.5, 1.0, 2.0, 3.0]), vec![2.0, 4.0, 5.0]);
+//! ```
 
 #[cfg(target_arch = "wasm32")]
 use std::arch::wasm32::*;
+use std::sync::OnceLock;
 
 /// Threshold for using SIMD operations (in elements)
 #[allow(dead_code)]
@@ -175,6 +186,727 @@ pub fn mul_f64_simd(a: &[f64], b: &[f64]) -> Vec<f64> {
     a.iter().zip(b.iter()).map(|(&x, &y)| x * y).collect()
 }
 
+// ========================================
+// Kernel selection
+// ========================================
+
+mod scalar;
+
+/// Instruction set the numeric kernels run with, see [`simd_level`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimdLevel {
+    /// One element at a time, the reference implementation
+    Scalar,
+    /// The target's baseline vector instructions (SSE2 on x86_64)
+    Baseline,
+    /// AVX2, detected at runtime on x86_64
+    Avx2,
+    /// NEON, part of the aarch64 baseline
+    Neon,
+    /// WASM SIMD128, enabled at compile time
+    Simd128,
+}
+
+/// The instruction set used by [`run_f64`], [`sum`], [`filter`] and the
+/// other kernels in this module.
+///
+/// Probed once, on first use. Setting the `ORLANDO_SIMD` environment
+/// variable to `scalar`, or enabling the `simd-scalar` feature, forces the
+/// element-at-a-time kernels, e.g. to rule them in or out when debugging.
+pub fn simd_level() -> SimdLevel {
+    static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+    *LEVEL.get_or_init(detect_level)
+}
+
+fn detect_level() -> SimdLevel {
+    let forced = std::env::var("ORLANDO_SIMD").is_ok_and(|v| v.eq_ignore_ascii_case("scalar"));
+    if cfg!(feature = "simd-scalar") || forced {
+        return SimdLevel::Scalar;
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return SimdLevel::Avx2;
+        }
+    }
+    if cfg!(target_arch = "aarch64") {
+        SimdLevel::Neon
+    } else if cfg!(all(target_arch = "wasm32", target_feature = "simd128")) {
+        SimdLevel::Simd128
+    } else {
+        SimdLevel::Baseline
+    }
+}
+
+// Call the scalar kernel or a vector kernel, as selected by `simd_level`.
+// The vector kernels are `#[inline(always)]`, so the AVX2 wrappers in
+// `avx2` compile a copy of them with AVX2 enabled.
+macro_rules! dispatch {
+    ($scalar:expr, $vector:ident($($arg:expr),*)) => {
+        match simd_level() {
+            SimdLevel::Scalar => $scalar,
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: `simd_level` only reports AVX2 when the CPU has it
+            SimdLevel::Avx2 => unsafe { avx2::$vector($($arg),*) },
+            _ => $vector($($arg),*),
+        }
+    };
+}
+
+pub mod bytes;
+mod hybrid;
+#[cfg(feature = "webgpu")]
+pub mod wgsl;
+
+pub use hybrid::{Hybrid, DEFAULT_CHUNK_BYTES};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::*;
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn run_vector<T: SimdElement>(plan: &[NumericOp<T>], data: &[T]) -> Vec<T> {
+        super::run_vector(plan, data)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn apply_op_vector<T: SimdElement>(op: NumericOp<T>, values: &mut Vec<T>) {
+        super::apply_op_vector(op, values)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn filter_vector<T: SimdElement>(
+        data: &[T],
+        keep: impl Fn(T) -> bool,
+    ) -> Vec<T> {
+        super::filter_vector(data, keep)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn sum_vector<T: SimdElement>(data: &[T]) -> T {
+        super::sum_vector(data)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn min_vector<T: SimdElement>(data: &[T]) -> Option<T> {
+        super::min_vector(data)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn max_vector<T: SimdElement>(data: &[T]) -> Option<T> {
+        super::max_vector(data)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn dot_vector<T: SimdElement>(a: &[T], b: &[T]) -> T {
+        super::dot_vector(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn axpy_vector<T: SimdElement>(a: T, x: &[T], y: &mut [T]) {
+        super::axpy_vector(a, x, y)
+    }
+}
+
+// ========================================
+// Numeric plan executor
+// ========================================
+
+/// Elements processed per step by the plan kernels.
+///
+/// The kernels work on fixed-size chunks so the compiler lowers each step to
+/// vector instructions (SSE2/AVX on x86, NEON on ARM, SIMD128 on WASM),
+/// with a scalar loop for the tail.
+const LANES: usize = 8;
+
+/// One step of a numeric plan run by [`run_f64`], [`run_f32`] and [`run_i32`].
+///
+/// Integer arithmetic wraps on overflow, and integer division by zero gives
+/// 0, so no plan panics.
+///
+/// With the `serde` feature, steps serialize as `{"op": "scaleOffset",
+/// "args": [2.0, 1.0]}`, with `args` omitted for `abs` and `unique`, so a
+/// plan can be loaded from a configuration file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "op", content = "args", rename_all = "camelCase")
+)]
+pub enum NumericOp<T> {
+    /// Add a constant to every element
+    Add(T),
+    /// Multiply every element by a constant
+    Mul(T),
+    /// `a * x + b` for every element, in one pass
+    ScaleOffset(T, T),
+    /// `(x - mean) / std` for every element, in one pass (integer division
+    /// for `i32`, giving 0 for every element if `std` is zero)
+    Normalize(T, T),
+    /// Limit every element to `lo..=hi` (NaN passes through)
+    Clamp(T, T),
+    /// Absolute value of every element
+    Abs,
+    /// Keep elements greater than the value
+    FilterGt(T),
+    /// Keep elements greater than or equal to the value
+    FilterGte(T),
+    /// Keep elements less than the value
+    FilterLt(T),
+    /// Keep elements less than or equal to the value
+    FilterLte(T),
+    /// Keep elements in `lo..hi`
+    FilterRange(T, T),
+    /// Keep the first `n` elements
+    Take(usize),
+    /// Skip the first `n` elements
+    Drop(usize),
+    /// Drop consecutive duplicates (NaNs are never equal)
+    Unique,
+}
+
+/// Element types the numeric plan executor runs over.
+pub trait SimdElement: Copy + PartialOrd + sealed::Sealed {
+    #[doc(hidden)]
+    fn add(self, other: Self) -> Self;
+    #[doc(hidden)]
+    fn mul(self, other: Self) -> Self;
+    #[doc(hidden)]
+    fn sub(self, other: Self) -> Self;
+    #[doc(hidden)]
+    fn div(self, other: Self) -> Self;
+    #[doc(hidden)]
+    fn abs(self) -> Self;
+    #[doc(hidden)]
+    fn min(self, other: Self) -> Self;
+    #[doc(hidden)]
+    fn max(self, other: Self) -> Self;
+    #[doc(hidden)]
+    const ZERO: Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f64 {}
+    impl Sealed for f32 {}
+    impl Sealed for i32 {}
+}
+
+macro_rules! float_element {
+    ($t:ty) => {
+        impl SimdElement for $t {
+            #[inline(always)]
+            fn add(self, other: Self) -> Self {
+                self + other
+            }
+            #[inline(always)]
+            fn mul(self, other: Self) -> Self {
+                self * other
+            }
+            #[inline(always)]
+            fn sub(self, other: Self) -> Self {
+                self - other
+            }
+            #[inline(always)]
+            fn div(self, other: Self) -> Self {
+                self / other
+            }
+            #[inline(always)]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            #[inline(always)]
+            fn min(self, other: Self) -> Self {
+                <$t>::min(self, other)
+            }
+            #[inline(always)]
+            fn max(self, other: Self) -> Self {
+                <$t>::max(self, other)
+            }
+            const ZERO: Self = 0.0;
+        }
+    };
+}
+
+float_element!(f64);
+float_element!(f32);
+
+impl SimdElement for i32 {
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        self.wrapping_add(other)
+    }
+    #[inline(always)]
+    fn mul(self, other: Self) -> Self {
+        self.wrapping_mul(other)
+    }
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        self.wrapping_sub(other)
+    }
+    #[inline(always)]
+    fn div(self, other: Self) -> Self {
+        if other == 0 {
+            0
+        } else {
+            self.wrapping_div(other)
+        }
+    }
+    #[inline(always)]
+    fn abs(self) -> Self {
+        self.wrapping_abs()
+    }
+    #[inline(always)]
+    fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+    #[inline(always)]
+    fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+    const ZERO: Self = 0;
+}
+
+/// Run a numeric plan over `f64` values.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::{run_f64, NumericOp};
+///
+/// let plan = [NumericOp::Add(-2.0), NumericOp::Abs, NumericOp::FilterLt(2.0)];
+/// assert_eq!(run_f64(&plan, &[0.0, 1.0, 2.0, 3.0, 5.0]), vec![1.0, 0.0, 1.0]);
+/// ```
+pub fn run_f64(plan: &[NumericOp<f64>], data: &[f64]) -> Vec<f64> {
+    run(plan, data)
+}
+
+/// Run a numeric plan over `f32` values.
+pub fn run_f32(plan: &[NumericOp<f32>], data: &[f32]) -> Vec<f32> {
+    run(plan, data)
+}
+
+/// Run a numeric plan over `i32` values.
+pub fn run_i32(plan: &[NumericOp<i32>], data: &[i32]) -> Vec<i32> {
+    run(plan, data)
+}
+
+fn run<T: SimdElement>(plan: &[NumericOp<T>], data: &[T]) -> Vec<T> {
+    dispatch!(
+        run_plan::<T, scalar::Scalar>(plan, data),
+        run_vector(plan, data)
+    )
+}
+
+#[inline(always)]
+fn run_vector<T: SimdElement>(plan: &[NumericOp<T>], data: &[T]) -> Vec<T> {
+    run_plan::<T, Vector>(plan, data)
+}
+
+#[inline(always)]
+fn run_plan<T: SimdElement, K: Kernels>(plan: &[NumericOp<T>], data: &[T]) -> Vec<T> {
+    // Leading takes and drops only narrow the input, so apply them before
+    // copying it
+    let mut input = data;
+    let mut steps = plan;
+    while let Some((op, rest)) = steps.split_first() {
+        match op {
+            NumericOp::Take(n) => input = &input[..(*n).min(input.len())],
+            NumericOp::Drop(n) => input = &input[(*n).min(input.len())..],
+            _ => break,
+        }
+        steps = rest;
+    }
+
+    let mut values = input.to_vec();
+    for op in steps {
+        apply_op::<T, K>(*op, &mut values);
+        if values.is_empty() {
+            break;
+        }
+    }
+    values
+}
+
+// Apply one step of a plan to `values` in place, as selected by
+// `simd_level`
+fn apply_step<T: SimdElement>(op: NumericOp<T>, values: &mut Vec<T>) {
+    dispatch!(
+        apply_op::<T, scalar::Scalar>(op, values),
+        apply_op_vector(op, values)
+    )
+}
+
+#[inline(always)]
+fn apply_op_vector<T: SimdElement>(op: NumericOp<T>, values: &mut Vec<T>) {
+    apply_op::<T, Vector>(op, values)
+}
+
+#[inline(always)]
+fn apply_op<T: SimdElement, K: Kernels>(op: NumericOp<T>, values: &mut Vec<T>) {
+    match op {
+        NumericOp::Add(k) => K::map(values, |x| x.add(k)),
+        NumericOp::Mul(k) => K::map(values, |x| x.mul(k)),
+        NumericOp::ScaleOffset(a, b) => K::map(values, |x| a.mul(x).add(b)),
+        NumericOp::Normalize(mean, std) => K::map(values, |x| x.sub(mean).div(std)),
+        NumericOp::Clamp(lo, hi) => K::map(values, |x| {
+            if x < lo {
+                lo
+            } else if x > hi {
+                hi
+            } else {
+                x
+            }
+        }),
+        NumericOp::Abs => K::map(values, T::abs),
+        NumericOp::FilterGt(k) => K::retain(values, |x| x > k),
+        NumericOp::FilterGte(k) => K::retain(values, |x| x >= k),
+        NumericOp::FilterLt(k) => K::retain(values, |x| x < k),
+        NumericOp::FilterLte(k) => K::retain(values, |x| x <= k),
+        NumericOp::FilterRange(lo, hi) => K::retain(values, |x| lo <= x && x < hi),
+        NumericOp::Take(n) => values.truncate(n),
+        NumericOp::Drop(n) => {
+            values.drain(..n.min(values.len()));
+        }
+        NumericOp::Unique => values.dedup_by(|a, b| a == b),
+    }
+}
+
+// Element-wise kernels of the plan executor: `Vector` works a chunk of
+// lanes at a time, `scalar::Scalar` one element at a time.
+trait Kernels {
+    fn map<T: Copy>(values: &mut [T], f: impl Fn(T) -> T);
+    fn retain<T: Copy>(values: &mut Vec<T>, keep: impl Fn(T) -> bool);
+}
+
+struct Vector;
+
+impl Kernels for Vector {
+    #[inline(always)]
+    fn map<T: Copy>(values: &mut [T], f: impl Fn(T) -> T) {
+        map_in_place(values, f)
+    }
+
+    #[inline(always)]
+    fn retain<T: Copy>(values: &mut Vec<T>, keep: impl Fn(T) -> bool) {
+        retain_in_place(values, keep)
+    }
+}
+
+#[inline(always)]
+fn map_in_place<T: Copy>(values: &mut [T], f: impl Fn(T) -> T) {
+    let mut chunks = values.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        for x in chunk.iter_mut() {
+            *x = f(*x);
+        }
+    }
+    for x in chunks.into_remainder() {
+        *x = f(*x);
+    }
+}
+
+/// Keep the elements of `data` that satisfy `keep`.
+///
+/// The predicate is evaluated on a group of lanes at a time and the passing
+/// elements are packed with a shuffle table, so there is no branch per
+/// element. Comparisons against a constant vectorize best.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd;
+///
+/// let data = [0.5f32, 2.0, -1.0, 3.5, 0.0, 9.0];
+/// assert_eq!(simd::filter(&data, |x| x > 1.0), vec![2.0, 3.5, 9.0]);
+/// ```
+pub fn filter<T: SimdElement>(data: &[T], keep: impl Fn(T) -> bool) -> Vec<T> {
+    dispatch!(scalar::filter(data, keep), filter_vector(data, keep))
+}
+
+#[inline(always)]
+fn filter_vector<T: SimdElement>(data: &[T], keep: impl Fn(T) -> bool) -> Vec<T> {
+    let mut values = data.to_vec();
+    retain_in_place(&mut values, keep);
+    values
+}
+
+/// Lanes per compaction group, one 128-bit vector of 32-bit elements.
+const GROUP: usize = 4;
+
+/// For each keep mask of a group, the lanes to move to the front. Unused
+/// slots repeat lane 0; they are overwritten by the next group.
+const COMPACT_LANES: [[u8; GROUP]; 1 << GROUP] = {
+    let mut table = [[0u8; GROUP]; 1 << GROUP];
+    let mut mask = 0;
+    while mask < table.len() {
+        let (mut lane, mut slot) = (0, 0);
+        while lane < GROUP {
+            if mask & (1 << lane) != 0 {
+                table[mask][slot] = lane as u8;
+                slot += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// `COMPACT_LANES` as byte indices for a 16-byte swizzle of 32-bit lanes.
+#[cfg(target_arch = "wasm32")]
+const COMPACT_BYTES: [[u8; 16]; 1 << GROUP] = {
+    let mut table = [[0u8; 16]; 1 << GROUP];
+    let mut mask = 0;
+    while mask < table.len() {
+        let mut byte = 0;
+        while byte < 16 {
+            table[mask][byte] = COMPACT_LANES[mask][byte / 4] * 4 + (byte % 4) as u8;
+            byte += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+// Compare a group into a bit mask, then write the whole group packed to the
+// front of `values` and advance by the number of kept lanes. Writing at
+// `kept <= start` never clobbers an unread group.
+#[inline(always)]
+fn retain_in_place<T: Copy>(values: &mut Vec<T>, keep: impl Fn(T) -> bool) {
+    let len = values.len();
+    let mut kept = 0;
+    let mut start = 0;
+    while start + GROUP <= len {
+        let mut group = [values[start]; GROUP];
+        group.copy_from_slice(&values[start..start + GROUP]);
+        let mut mask = 0;
+        for (lane, &x) in group.iter().enumerate() {
+            mask |= (keep(x) as usize) << lane;
+        }
+        compact_group(&group, mask, &mut values[kept..kept + GROUP]);
+        kept += mask.count_ones() as usize;
+        start += GROUP;
+    }
+    for i in start..len {
+        let x = values[i];
+        values[kept] = x;
+        kept += keep(x) as usize;
+    }
+    values.truncate(kept);
+}
+
+#[inline(always)]
+fn compact_group<T: Copy>(group: &[T; GROUP], mask: usize, out: &mut [T]) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if std::mem::size_of::<T>() == 4 {
+            // SAFETY: `group` and `out` each hold GROUP 4-byte elements, i.e.
+            // exactly 16 bytes, and the swizzle only moves whole lanes of a
+            // `Copy` type
+            unsafe {
+                let lanes = v128_load(group.as_ptr() as *const v128);
+                let indices = v128_load(COMPACT_BYTES[mask].as_ptr() as *const v128);
+                v128_store(out.as_mut_ptr() as *mut v128, i8x16_swizzle(lanes, indices));
+            }
+            return;
+        }
+    }
+    for (slot, &lane) in out.iter_mut().zip(&COMPACT_LANES[mask]) {
+        *slot = group[lane as usize];
+    }
+}
+
+// ========================================
+// Reductions
+// ========================================
+
+/// Length of the blocks summed over lane accumulators before the block
+/// totals are combined pairwise.
+const PAIRWISE_BLOCK: usize = 256;
+
+/// Sum of the elements.
+///
+/// Floats are summed pairwise over lane accumulators, which keeps the
+/// rounding error at O(log n) instead of the O(n) of a running total.
+/// Integer sums wrap on overflow.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd;
+///
+/// assert_eq!(simd::sum(&[1.0, 2.0, 3.5]), 6.5);
+/// assert_eq!(simd::sum::<i32>(&[]), 0);
+/// ```
+pub fn sum<T: SimdElement>(data: &[T]) -> T {
+    dispatch!(scalar::sum(data), sum_vector(data))
+}
+
+#[inline(always)]
+fn sum_vector<T: SimdElement>(data: &[T]) -> T {
+    pairwise(data.len(), |range| {
+        fold_lanes(&data[range], T::ZERO, |acc, x| acc.add(x))
+            .into_iter()
+            .fold(T::ZERO, T::add)
+    })
+}
+
+/// Smallest element, or `None` if `data` is empty.
+///
+/// NaNs are ignored unless every element is NaN.
+pub fn min<T: SimdElement>(data: &[T]) -> Option<T> {
+    dispatch!(scalar::min(data), min_vector(data))
+}
+
+#[inline(always)]
+fn min_vector<T: SimdElement>(data: &[T]) -> Option<T> {
+    let &first = data.first()?;
+    Some(
+        fold_lanes(data, first, T::min)
+            .into_iter()
+            .fold(first, T::min),
+    )
+}
+
+/// Largest element, or `None` if `data` is empty.
+///
+/// NaNs are ignored unless every element is NaN.
+pub fn max<T: SimdElement>(data: &[T]) -> Option<T> {
+    dispatch!(scalar::max(data), max_vector(data))
+}
+
+#[inline(always)]
+fn max_vector<T: SimdElement>(data: &[T]) -> Option<T> {
+    let &first = data.first()?;
+    Some(
+        fold_lanes(data, first, T::max)
+            .into_iter()
+            .fold(first, T::max),
+    )
+}
+
+/// Dot product of two equally long slices.
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd;
+///
+/// assert_eq!(simd::dot(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+/// ```
+pub fn dot<T: SimdElement>(a: &[T], b: &[T]) -> T {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "dot product of slices with different lengths"
+    );
+    dispatch!(scalar::dot(a, b), dot_vector(a, b))
+}
+
+#[inline(always)]
+fn dot_vector<T: SimdElement>(a: &[T], b: &[T]) -> T {
+    pairwise(a.len(), |range| {
+        let (a, b) = (&a[range.clone()], &b[range]);
+        let mut acc = [T::ZERO; LANES];
+        let (chunks_a, chunks_b) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+        let tail = chunks_a.remainder().iter().zip(chunks_b.remainder());
+        for (ca, cb) in chunks_a.zip(chunks_b) {
+            for lane in 0..LANES {
+                acc[lane] = acc[lane].add(ca[lane].mul(cb[lane]));
+            }
+        }
+        tail.fold(acc.into_iter().fold(T::ZERO, T::add), |total, (&x, &y)| {
+            total.add(x.mul(y))
+        })
+    })
+}
+
+/// Sum of the squared elements, the dot product of `data` with itself.
+pub fn sum_of_squares<T: SimdElement>(data: &[T]) -> T {
+    dot(data, data)
+}
+
+/// `y = a * x + y`, element-wise in one pass (BLAS `axpy`).
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd;
+///
+/// let mut y = [1.0, 1.0, 1.0];
+/// simd::axpy(2.0, &[1.0, 2.0, 3.0], &mut y);
+/// assert_eq!(y, [3.0, 5.0, 7.0]);
+/// ```
+pub fn axpy<T: SimdElement>(a: T, x: &[T], y: &mut [T]) {
+    assert_eq!(x.len(), y.len(), "axpy of slices with different lengths");
+    dispatch!(scalar::axpy(a, x, y), axpy_vector(a, x, y))
+}
+
+#[inline(always)]
+fn axpy_vector<T: SimdElement>(a: T, x: &[T], y: &mut [T]) {
+    let (mut xs, mut ys) = (x.chunks_exact(LANES), y.chunks_exact_mut(LANES));
+    for (cx, cy) in (&mut xs).zip(&mut ys) {
+        for lane in 0..LANES {
+            cy[lane] = a.mul(cx[lane]).add(cy[lane]);
+        }
+    }
+    for (&xi, yi) in xs.remainder().iter().zip(ys.into_remainder()) {
+        *yi = a.mul(xi).add(*yi);
+    }
+}
+
+// Total `block` over consecutive PAIRWISE_BLOCK ranges of `0..len` and add
+// the totals pairwise. Equally sized partial sums are merged as they appear,
+// like carries in a binary counter, so this needs no recursion (which would
+// leave the caller's target features behind).
+#[inline(always)]
+fn pairwise<T: SimdElement>(len: usize, block: impl Fn(std::ops::Range<usize>) -> T) -> T {
+    let mut partials = [T::ZERO; usize::BITS as usize];
+    let mut depth = 0;
+    for (i, start) in (0..len).step_by(PAIRWISE_BLOCK).enumerate() {
+        let mut total = block(start..(start + PAIRWISE_BLOCK).min(len));
+        let mut carries = i;
+        while carries & 1 == 1 {
+            depth -= 1;
+            total = partials[depth].add(total);
+            carries >>= 1;
+        }
+        partials[depth] = total;
+        depth += 1;
+    }
+    partials[..depth]
+        .iter()
+        .rev()
+        .fold(T::ZERO, |total, &partial| partial.add(total))
+}
+
+// Fold `data` into LANES independent accumulators, so each step of the loop
+// is one vector operation. The tail is folded into the first lane.
+#[inline(always)]
+fn fold_lanes<T: Copy>(data: &[T], init: T, f: impl Fn(T, T) -> T) -> [T; LANES] {
+    let mut acc = [init; LANES];
+    let chunks = data.chunks_exact(LANES);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        for lane in 0..LANES {
+            acc[lane] = f(acc[lane], chunk[lane]);
+        }
+    }
+    for &x in tail {
+        acc[0] = f(acc[0], x);
+    }
+    acc
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +939,207 @@ mod tests {
         let result = mul_f64_simd(&a, &b);
         assert_eq!(result, vec![2.0, 6.0, 12.0, 20.0]);
     }
+
+    #[test]
+    fn test_run_f64_matches_scalar() {
+        let data: Vec<f64> = (0..37).map(|i| i as f64 - 18.5).collect();
+        let plan = [
+            NumericOp::Drop(2),
+            NumericOp::Mul(1.5),
+            NumericOp::Abs,
+            NumericOp::Clamp(1.0, 20.0),
+            NumericOp::FilterRange(2.0, 20.0),
+            NumericOp::Add(0.25),
+            NumericOp::Take(10),
+        ];
+        let expected: Vec<f64> = data[2..]
+            .iter()
+            .map(|x| (x * 1.5).abs().clamp(1.0, 20.0))
+            .filter(|x| (2.0..20.0).contains(x))
+            .map(|x| x + 0.25)
+            .take(10)
+            .collect();
+        assert_eq!(run_f64(&plan, &data), expected);
+    }
+
+    #[test]
+    fn test_run_f32_and_i32() {
+        let plan = [
+            NumericOp::FilterGte(2.0),
+            NumericOp::Mul(-1.0),
+            NumericOp::Unique,
+        ];
+        assert_eq!(run_f32(&plan, &[1.0, 2.0, 2.0, 3.0]), vec![-2.0, -3.0]);
+
+        let plan = [NumericOp::Abs, NumericOp::FilterLte(3), NumericOp::Add(1)];
+        assert_eq!(run_i32(&plan, &[-4, -3, 0, 2, 9]), vec![4, 1, 3]);
+        assert_eq!(run_i32(&[NumericOp::Add(1)], &[i32::MAX]), vec![i32::MIN]);
+    }
+
+    #[test]
+    fn test_run_clamp_keeps_nan() {
+        let result = run_f64(&[NumericOp::Clamp(0.0, 1.0)], &[f64::NAN, 2.0]);
+        assert!(result[0].is_nan());
+        assert_eq!(result[1], 1.0);
+        assert!(run_f64(&[NumericOp::FilterGt(0.0)], &[f64::NAN]).is_empty());
+    }
+
+    #[test]
+    fn test_sum_pairwise() {
+        let data: Vec<f64> = (1..=1000).map(f64::from).collect();
+        assert_eq!(sum(&data), 500_500.0);
+        assert_eq!(sum(&[0.1f32; 3]), 0.1 + 0.1 + 0.1);
+        assert_eq!(sum(&[i32::MAX, 1]), i32::MIN);
+
+        let tenths = vec![0.1f32; 1_000_000];
+        let exact = 100_000.0;
+        let running = scalar::sum(&tenths);
+        assert!((sum_vector(&tenths) - exact).abs() < (running - exact).abs() / 100.0);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let data: Vec<i32> = (0..100).map(|i| (i * 37) % 101 - 50).collect();
+        assert_eq!(min(&data), data.iter().copied().min());
+        assert_eq!(max(&data), data.iter().copied().max());
+        assert_eq!(min::<f64>(&[]), None);
+        assert_eq!(max(&[f64::NAN, 2.0, -1.0]), Some(2.0));
+        assert_eq!(min(&[f64::NAN, 2.0, -1.0]), Some(-1.0));
+    }
+
+    #[test]
+    fn test_dot_and_sum_of_squares() {
+        let a: Vec<f64> = (0..600).map(f64::from).collect();
+        let b = vec![2.0; 600];
+        assert_eq!(dot(&a, &b), 2.0 * 599.0 * 600.0 / 2.0);
+        assert_eq!(sum_of_squares(&[3, 4]), 25);
+    }
+
+    #[test]
+    fn test_filter_compaction() {
+        // One group for every keep mask, plus a tail; kept values are distinct
+        let data: Vec<f32> = (0..(16 * GROUP + 3))
+            .map(|i| {
+                let bit = (i / GROUP) >> (i % GROUP) & 1;
+                if bit == 1 || i >= 16 * GROUP {
+                    i as f32
+                } else {
+                    -1.0
+                }
+            })
+            .collect();
+        let expected: Vec<f32> = data.iter().copied().filter(|&x| x >= 0.0).collect();
+        assert_eq!(filter(&data, |x| x >= 0.0), expected);
+
+        let ints: Vec<i32> = (0..64).collect();
+        assert_eq!(
+            filter(&ints, |x| x % 5 == 0),
+            vec![0, 5, 10, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60]
+        );
+        assert!(filter::<f64>(&[], |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_scalar_and_vector_kernels_agree() {
+        let data: Vec<f64> = (0..1000)
+            .map(|i| ((i * 7919) % 211) as f64 - 100.0)
+            .collect();
+        let plan = [
+            NumericOp::Abs,
+            NumericOp::Clamp(3.0, 90.0),
+            NumericOp::FilterLte(60.0),
+            NumericOp::Unique,
+        ];
+        assert_eq!(
+            run_plan::<f64, scalar::Scalar>(&plan, &data),
+            run_vector(&plan, &data)
+        );
+        assert_eq!(
+            scalar::filter(&data, |x| x > 0.0),
+            filter_vector(&data, |x| x > 0.0)
+        );
+        assert_eq!(scalar::min(&data), min_vector(&data));
+        assert_eq!(scalar::max(&data), max_vector(&data));
+        // Integer sums are exact in any order
+        let ints: Vec<i32> = data.iter().map(|&x| x as i32).collect();
+        assert_eq!(scalar::sum(&ints), sum_vector(&ints));
+        assert_eq!(scalar::dot(&ints, &ints), dot_vector(&ints, &ints));
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_avx2_kernels() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+        let data: Vec<f32> = (0..999).map(|i| (i % 37) as f32 - 18.0).collect();
+        let plan = [NumericOp::Mul(0.5), NumericOp::FilterGt(-3.0)];
+        // SAFETY: AVX2 was detected above
+        unsafe {
+            assert_eq!(avx2::run_vector(&plan, &data), run_vector(&plan, &data));
+            assert_eq!(avx2::sum_vector(&data), sum_vector(&data));
+            assert_eq!(avx2::max_vector(&data), Some(18.0));
+        }
+    }
+
+    #[test]
+    fn test_simd_level() {
+        let level = simd_level();
+        if cfg!(feature = "simd-scalar") {
+            assert_eq!(level, SimdLevel::Scalar);
+        } else if cfg!(target_arch = "aarch64") && level != SimdLevel::Scalar {
+            assert_eq!(level, SimdLevel::Neon);
+        }
+        assert_eq!(simd_level(), level);
+    }
+
+    #[test]
+    fn test_fused_ops() {
+        let data: Vec<f64> = (0..21).map(f64::from).collect();
+        let scaled = run_f64(&[NumericOp::ScaleOffset(0.5, -1.0)], &data);
+        assert_eq!(
+            scaled,
+            data.iter().map(|x| 0.5 * x - 1.0).collect::<Vec<_>>()
+        );
+
+        let normalized = run_f32(&[NumericOp::Normalize(10.0, 4.0)], &[2.0, 10.0, 18.0]);
+        assert_eq!(normalized, vec![-2.0, 0.0, 2.0]);
+        assert_eq!(
+            run_i32(&[NumericOp::Normalize(10, 3)], &[1, 16]),
+            vec![-3, 2]
+        );
+        assert_eq!(
+            run_i32(&[NumericOp::Normalize(10, 0)], &[1, 16]),
+            vec![0, 0]
+        );
+
+        let mut y: Vec<f64> = vec![1.0; 21];
+        axpy(3.0, &data, &mut y);
+        assert_eq!(y, data.iter().map(|x| 3.0 * x + 1.0).collect::<Vec<_>>());
+        let mut y2 = vec![1.0; 21];
+        scalar::axpy(3.0, &data, &mut y2);
+        assert_eq!(y, y2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_numeric_op_serde() {
+        let plan: Vec<NumericOp<f64>> = serde_json::from_str(
+            r#"[{ "op": "scaleOffset", "args": [2.0, 1.0] }, { "op": "abs" }, { "op": "take", "args": 2 }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                NumericOp::ScaleOffset(2.0, 1.0),
+                NumericOp::Abs,
+                NumericOp::Take(2)
+            ]
+        );
+        assert_eq!(run_f64(&plan, &[-3.0, 0.0, 4.0]), vec![5.0, 1.0]);
+        assert_eq!(
+            serde_json::to_string(&NumericOp::FilterGt(1)).unwrap(),
+            r#"{"op":"filterGt","args":1}"#
+        );
+    }
 }
@@ -0,0 +1,291 @@
+//! Byte kernels for text and log pipelines.
+//!
+//! `find_byte` and `split` locate delimiters a block at a time, the ASCII
+//! case conversions are branch-free per byte, and `from_utf8` skips ASCII
+//! runs a block at a time before handing multi-byte text to the standard
+//! library validator. All of them follow [`simd_level`](super::simd_level).
+//!
+//! Byte chunks read from a file or socket can be split into lines with the
+//! [`SplitBytes`](crate::transforms::SplitBytes) transducer and decoded with
+//! a `Map`:
+//!
+//! ```rust
+//! use orlando_transducers::collectors::to_vec;
+//! use orlando_transducers::simd::bytes;
+//! use orlando_transducers::transducer::Transducer;
+//! use orlando_transducers::transforms::{Map, SplitBytes};
+//!
+//! let lines = SplitBytes::new(b'\n').compose(Map::new(|mut line: Vec<u8>| {
+//!     bytes::make_ascii_lowercase(&mut line);
+//!     bytes::from_utf8(&line).map(str::to_string)
+//! }));
+//! let chunks = vec![b"GET /a\nPO".to_vec(), b"ST /b\n".to_vec()];
+//!
+//! assert_eq!(
+//!     to_vec(&lines, chunks),
+//!     vec![Ok("get /a".to_string()), Ok("post /b".to_string())]
+//! );
+//! ```
+
+use super::{simd_level, SimdLevel};
+use std::str::Utf8Error;
+
+/// Bytes examined per step of the block kernels.
+const BLOCK: usize = 32;
+
+/// Position of the first `needle` in `haystack`.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::bytes::find_byte;
+///
+/// assert_eq!(find_byte(b"key=value", b'='), Some(3));
+/// assert_eq!(find_byte(b"no delimiter", b'\n'), None);
+/// ```
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    dispatch!(
+        haystack.iter().position(|&b| b == needle),
+        find_byte_vector(haystack, needle)
+    )
+}
+
+// Compare whole blocks until one contains the needle, then locate it there
+#[inline(always)]
+fn find_byte_vector(haystack: &[u8], needle: u8) -> Option<usize> {
+    let chunks = haystack.chunks_exact(BLOCK);
+    let tail = chunks.remainder();
+    for (i, chunk) in chunks.enumerate() {
+        if chunk.iter().fold(false, |found, &b| found | (b == needle)) {
+            return chunk
+                .iter()
+                .position(|&b| b == needle)
+                .map(|lane| i * BLOCK + lane);
+        }
+    }
+    let start = haystack.len() - tail.len();
+    tail.iter().position(|&b| b == needle).map(|i| start + i)
+}
+
+/// Split `haystack` on every `delimiter`, like `slice::split` but searching
+/// with [`find_byte`].
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::bytes::split;
+///
+/// let fields: Vec<&[u8]> = split(b"a,b,,c", b',').collect();
+/// assert_eq!(fields, [&b"a"[..], b"b", b"", b"c"]);
+/// ```
+pub fn split(haystack: &[u8], delimiter: u8) -> Split<'_> {
+    Split {
+        rest: Some(haystack),
+        delimiter,
+    }
+}
+
+/// Iterator returned by [`split`].
+#[derive(Clone, Debug)]
+pub struct Split<'a> {
+    rest: Option<&'a [u8]>,
+    delimiter: u8,
+}
+
+impl<'a> Iterator for Split<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let rest = self.rest?;
+        match find_byte(rest, self.delimiter) {
+            Some(i) => {
+                self.rest = Some(&rest[i + 1..]);
+                Some(&rest[..i])
+            }
+            None => self.rest.take(),
+        }
+    }
+}
+
+/// Whether every byte is ASCII.
+pub fn is_ascii(bytes: &[u8]) -> bool {
+    ascii_prefix(bytes) == bytes.len()
+}
+
+/// Validate UTF-8, like `std::str::from_utf8`.
+///
+/// ASCII is checked a block at a time; from the first block holding a
+/// non-ASCII byte on, the standard library validator takes over.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::bytes::from_utf8;
+///
+/// assert_eq!(from_utf8("naïve".as_bytes()), Ok("naïve"));
+/// assert_eq!(from_utf8(b"ok\xff").unwrap_err().valid_up_to(), 2);
+/// ```
+pub fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    let ascii = ascii_prefix(bytes);
+    match std::str::from_utf8(&bytes[ascii..]) {
+        // SAFETY: `bytes[..ascii]` is ASCII and the rest was just validated,
+        // so the whole slice is UTF-8
+        Ok(_) => Ok(unsafe { std::str::from_utf8_unchecked(bytes) }),
+        // Validate again from the start so the error offset is right
+        Err(_) => std::str::from_utf8(bytes),
+    }
+}
+
+// Length of an all-ASCII prefix of `bytes`: `bytes.len()` if every byte is
+// ASCII, otherwise at most the position of the first non-ASCII byte
+fn ascii_prefix(bytes: &[u8]) -> usize {
+    dispatch!(
+        bytes
+            .iter()
+            .position(|b| !b.is_ascii())
+            .unwrap_or(bytes.len()),
+        ascii_prefix_vector(bytes)
+    )
+}
+
+// The vector kernel stops at the start of the first block with a non-ASCII
+// byte
+#[inline(always)]
+fn ascii_prefix_vector(bytes: &[u8]) -> usize {
+    let chunks = bytes.chunks_exact(BLOCK);
+    let tail = chunks.remainder();
+    for (i, chunk) in chunks.enumerate() {
+        if chunk.iter().fold(0, |high, &b| high | b) & 0x80 != 0 {
+            return i * BLOCK;
+        }
+    }
+    if tail.is_ascii() {
+        bytes.len()
+    } else {
+        bytes.len() - tail.len()
+    }
+}
+
+/// Convert ASCII letters to lower case in place, leaving other bytes alone.
+pub fn make_ascii_lowercase(bytes: &mut [u8]) {
+    dispatch!(bytes.make_ascii_lowercase(), lowercase_vector(bytes))
+}
+
+/// Convert ASCII letters to upper case in place, leaving other bytes alone.
+pub fn make_ascii_uppercase(bytes: &mut [u8]) {
+    dispatch!(bytes.make_ascii_uppercase(), uppercase_vector(bytes))
+}
+
+/// Copy of `bytes` with ASCII letters in lower case.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::bytes::to_ascii_lowercase;
+///
+/// assert_eq!(to_ascii_lowercase("ERROR: Ünïcode".as_bytes()), "error: Ünïcode".as_bytes());
+/// ```
+pub fn to_ascii_lowercase(bytes: &[u8]) -> Vec<u8> {
+    let mut folded = bytes.to_vec();
+    make_ascii_lowercase(&mut folded);
+    folded
+}
+
+/// Copy of `bytes` with ASCII letters in upper case.
+pub fn to_ascii_uppercase(bytes: &[u8]) -> Vec<u8> {
+    let mut folded = bytes.to_vec();
+    make_ascii_uppercase(&mut folded);
+    folded
+}
+
+// Set or clear the case bit (0x20) on ASCII letters only, without a branch
+#[inline(always)]
+fn lowercase_vector(bytes: &mut [u8]) {
+    for b in bytes {
+        *b |= ((b.wrapping_sub(b'A') < 26) as u8) << 5;
+    }
+}
+
+#[inline(always)]
+fn uppercase_vector(bytes: &mut [u8]) {
+    for b in bytes {
+        *b &= !(((b.wrapping_sub(b'a') < 26) as u8) << 5);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_byte_vector(haystack: &[u8], needle: u8) -> Option<usize> {
+        super::find_byte_vector(haystack, needle)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn ascii_prefix_vector(bytes: &[u8]) -> usize {
+        super::ascii_prefix_vector(bytes)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn lowercase_vector(bytes: &mut [u8]) {
+        super::lowercase_vector(bytes)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn uppercase_vector(bytes: &mut [u8]) {
+        super::uppercase_vector(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_byte_across_blocks() {
+        let mut haystack = vec![b'x'; 100];
+        assert_eq!(find_byte_vector(&haystack, b'\n'), None);
+        for i in [0, 31, 32, 63, 95, 99] {
+            haystack[i] = b'\n';
+            assert_eq!(find_byte_vector(&haystack, b'\n'), Some(i));
+            assert_eq!(find_byte(&haystack, b'\n'), Some(i));
+            haystack[i] = b'x';
+        }
+    }
+
+    #[test]
+    fn test_split_matches_slice_split() {
+        let text = b"\nfirst line\nsecond, longer line that spans a block\n\nlast";
+        let ours: Vec<&[u8]> = split(text, b'\n').collect();
+        let std: Vec<&[u8]> = text.split(|&b| b == b'\n').collect();
+        assert_eq!(ours, std);
+        assert_eq!(split(b"", b'\n').count(), 1);
+    }
+
+    #[test]
+    fn test_case_folding_matches_std() {
+        let all: Vec<u8> = (0..=255).collect();
+        let mut lower = all.clone();
+        lowercase_vector(&mut lower);
+        assert_eq!(lower, all.to_ascii_lowercase());
+        let mut upper = all.clone();
+        uppercase_vector(&mut upper);
+        assert_eq!(upper, all.to_ascii_uppercase());
+    }
+
+    #[test]
+    fn test_from_utf8_matches_std() {
+        let long_ascii = "a".repeat(70);
+        let inputs: Vec<Vec<u8>> = vec![
+            long_ascii.clone().into_bytes(),
+            format!("{}é{}", long_ascii, long_ascii).into_bytes(),
+            [long_ascii.as_bytes(), b"\xe2\x82", b"tail"].concat(),
+            [long_ascii.as_bytes(), b"\xff"].concat(),
+            b"\xc3".to_vec(),
+        ];
+        for input in &inputs {
+            assert_eq!(from_utf8(input), std::str::from_utf8(input));
+            assert_eq!(is_ascii(input), input.is_ascii());
+        }
+        assert_eq!(ascii_prefix_vector(&inputs[1]), 64);
+    }
+}
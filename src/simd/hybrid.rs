@@ -0,0 +1,200 @@
+//! Chunked execution of a numeric plan followed by scalar transducers.
+
+use super::{apply_step, NumericOp, SimdElement};
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+
+/// Default chunk size in bytes, so a chunk fits in the L1 data cache of
+/// most cores alongside the plan's working set.
+pub const DEFAULT_CHUNK_BYTES: usize = 16 * 1024;
+
+/// Runs a vectorized numeric plan and then a scalar transducer over a slice,
+/// a chunk at a time.
+///
+/// Each chunk is copied into a small reusable buffer, the plan runs over it
+/// with the kernels of [`run_f64`](super::run_f64), and the survivors are
+/// streamed through the transducer while they are still in cache. Take,
+/// drop and unique steps carry their state from one chunk to the next, so
+/// the result is the same as running the plan over the whole slice first.
+/// A take that is exhausted, or a transducer that stops, ends the run
+/// without reading further chunks.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::{Hybrid, NumericOp};
+/// use orlando_transducers::transforms::{Map, Take};
+/// use orlando_transducers::transducer::Transducer;
+///
+/// let readings: Vec<f64> = (0..100_000).map(|i| (i % 500) as f64).collect();
+/// let hybrid = Hybrid::new(vec![NumericOp::Mul(0.1), NumericOp::FilterGt(49.0)]).chunk_size(1024);
+/// let labels = Map::new(|x: f64| format!("{:.1}", x)).compose(Take::new(3));
+///
+/// assert_eq!(hybrid.to_vec(&readings, &labels), vec!["49.1", "49.2", "49.3"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Hybrid<T> {
+    plan: Vec<NumericOp<T>>,
+    chunk_size: usize,
+}
+
+impl<T: SimdElement + 'static> Hybrid<T> {
+    /// A hybrid executor with chunks of [`DEFAULT_CHUNK_BYTES`].
+    pub fn new(plan: Vec<NumericOp<T>>) -> Self {
+        Hybrid {
+            plan,
+            chunk_size: (DEFAULT_CHUNK_BYTES / std::mem::size_of::<T>()).max(1),
+        }
+    }
+
+    /// Set the number of elements per chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than 0");
+        self.chunk_size = size;
+        self
+    }
+
+    /// Reduce the plan's output, passed through `suffix`, with `reducer`.
+    pub fn reduce<U, Acc, X, R>(&self, source: &[T], suffix: &X, init: Acc, reducer: R) -> Acc
+    where
+        X: Transducer<T, U>,
+        U: 'static,
+        Acc: 'static,
+        R: Fn(Acc, U) -> Step<Acc> + 'static,
+    {
+        let transformed = suffix.apply(reducer);
+        let mut state = PlanState::new(&self.plan);
+        let mut buffer = Vec::with_capacity(self.chunk_size);
+        let mut acc = init;
+
+        for chunk in source.chunks(self.chunk_size) {
+            buffer.clear();
+            buffer.extend_from_slice(chunk);
+            let exhausted = state.run(&self.plan, &mut buffer);
+
+            for x in buffer.drain(..) {
+                match transformed(acc, x) {
+                    Step::Continue(new_acc) => acc = new_acc,
+                    Step::Stop(final_acc) => return final_acc,
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+        acc
+    }
+
+    /// Collect the plan's output, passed through `suffix`, into a vector.
+    pub fn to_vec<U, X>(&self, source: &[T], suffix: &X) -> Vec<U>
+    where
+        X: Transducer<T, U>,
+        U: 'static,
+    {
+        self.reduce(source, suffix, Vec::new(), |mut acc: Vec<U>, x| {
+            acc.push(x);
+            cont(acc)
+        })
+    }
+}
+
+// What the positional steps of a plan have seen in earlier chunks
+enum Carry<T> {
+    Stateless,
+    Remaining(usize),
+    Last(Option<T>),
+}
+
+struct PlanState<T> {
+    carries: Vec<Carry<T>>,
+}
+
+impl<T: SimdElement> PlanState<T> {
+    fn new(plan: &[NumericOp<T>]) -> Self {
+        let carries = plan
+            .iter()
+            .map(|op| match op {
+                NumericOp::Take(n) | NumericOp::Drop(n) => Carry::Remaining(*n),
+                NumericOp::Unique => Carry::Last(None),
+                _ => Carry::Stateless,
+            })
+            .collect();
+        PlanState { carries }
+    }
+
+    // Run the plan over one chunk in place. Returns true once a take has
+    // been used up, so later chunks can't produce anything.
+    fn run(&mut self, plan: &[NumericOp<T>], values: &mut Vec<T>) -> bool {
+        let mut exhausted = false;
+        for (op, carry) in plan.iter().zip(&mut self.carries) {
+            match (op, carry) {
+                (NumericOp::Take(_), Carry::Remaining(remaining)) => {
+                    values.truncate(*remaining);
+                    *remaining -= values.len();
+                    exhausted |= *remaining == 0;
+                }
+                (NumericOp::Drop(_), Carry::Remaining(remaining)) => {
+                    let dropped = (*remaining).min(values.len());
+                    values.drain(..dropped);
+                    *remaining -= dropped;
+                }
+                (NumericOp::Unique, Carry::Last(last)) => {
+                    values.dedup_by(|a, b| a == b);
+                    if last.is_some() && values.first() == last.as_ref() {
+                        values.remove(0);
+                    }
+                    if let Some(&x) = values.last() {
+                        *last = Some(x);
+                    }
+                }
+                (op, _) => apply_step(*op, values),
+            }
+        }
+        exhausted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simd::run_f64;
+    use crate::transducer::Identity;
+    use crate::transforms::{Map, Take};
+
+    #[test]
+    fn test_chunked_matches_whole_slice() {
+        let data: Vec<f64> = (0..5000).map(|i| ((i / 3) % 97) as f64).collect();
+        let plan = vec![
+            NumericOp::Drop(10),
+            NumericOp::Unique,
+            NumericOp::FilterGte(20.0),
+            NumericOp::Take(300),
+            NumericOp::Mul(2.0),
+        ];
+        let whole = run_f64(&plan, &data);
+        for size in [1, 7, 64, 4096, 10_000] {
+            let hybrid = Hybrid::new(plan.clone()).chunk_size(size);
+            assert_eq!(
+                hybrid.to_vec(&data, &Identity::new()),
+                whole,
+                "chunk size {}",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn test_stops_early() {
+        let data: Vec<i32> = (0..1_000_000).collect();
+        let hybrid = Hybrid::new(vec![NumericOp::Add(1)]).chunk_size(100);
+        let squares = Map::new(|x: i32| x * x).compose(Take::new(3));
+        assert_eq!(hybrid.to_vec(&data, &squares), vec![1, 4, 9]);
+
+        let taken = Hybrid::new(vec![NumericOp::Take(5)]).chunk_size(2);
+        assert_eq!(taken.to_vec(&data, &Identity::new()), vec![0, 1, 2, 3, 4]);
+    }
+}
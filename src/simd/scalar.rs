@@ -0,0 +1,48 @@
+//! Element-at-a-time kernels, selected by `SimdLevel::Scalar`.
+//!
+//! They are the reference the vector kernels must agree with, apart from
+//! float sums, which run left to right here instead of pairwise.
+
+use super::{Kernels, SimdElement};
+
+pub(super) struct Scalar;
+
+impl Kernels for Scalar {
+    fn map<T: Copy>(values: &mut [T], f: impl Fn(T) -> T) {
+        for x in values.iter_mut() {
+            *x = f(*x);
+        }
+    }
+
+    fn retain<T: Copy>(values: &mut Vec<T>, keep: impl Fn(T) -> bool) {
+        values.retain(|&x| keep(x));
+    }
+}
+
+pub(super) fn filter<T: SimdElement>(data: &[T], keep: impl Fn(T) -> bool) -> Vec<T> {
+    data.iter().copied().filter(|&x| keep(x)).collect()
+}
+
+pub(super) fn sum<T: SimdElement>(data: &[T]) -> T {
+    data.iter().fold(T::ZERO, |total, &x| total.add(x))
+}
+
+pub(super) fn min<T: SimdElement>(data: &[T]) -> Option<T> {
+    data.iter().copied().reduce(T::min)
+}
+
+pub(super) fn max<T: SimdElement>(data: &[T]) -> Option<T> {
+    data.iter().copied().reduce(T::max)
+}
+
+pub(super) fn dot<T: SimdElement>(a: &[T], b: &[T]) -> T {
+    a.iter()
+        .zip(b)
+        .fold(T::ZERO, |total, (&x, &y)| total.add(x.mul(y)))
+}
+
+pub(super) fn axpy<T: SimdElement>(a: T, x: &[T], y: &mut [T]) {
+    for (&xi, yi) in x.iter().zip(y) {
+        *yi = a.mul(xi).add(*yi);
+    }
+}
@@ -0,0 +1,261 @@
+//! WGSL compute shaders for numeric plans.
+//!
+//! The element-wise steps of a [`NumericOp`] plan (the maps and the
+//! threshold filters) are independent per element, so a plan made only of
+//! those compiles to a compute shader with one invocation per element:
+//!
+//! - [`map_shader`] writes every transformed element to an output buffer of
+//!   the same length, for plans without filters.
+//! - [`sum_shader`] adds up the elements that pass the filters within each
+//!   workgroup, writing one partial sum per workgroup for the caller to add.
+//!
+//! `Take`, `Drop` and `Unique` depend on an element's position or its
+//! neighbour, and constants that are NaN or infinite have no WGSL literal;
+//! plans with either compile to `None`, and should run with
+//! [`run_f32`](super::run_f32) instead.
+//!
+//! Both shaders read `array<f32>` from binding 0 and write `array<f32>` to
+//! binding 1 of group 0, and expect the workgroup counts of
+//! [`dispatch_size`]. With the `webgpu` feature, the WASM `Pipeline` runs
+//! them through the browser's WebGPU API (`sumGpu`, `toFloat32ArrayGpu`).
+//!
+//! ```rust
+//! use orlando_transducers::simd::wgsl::{dispatch_size, sum_shader, WORKGROUP_SIZE};
+//! use orlando_transducers::simd::NumericOp;
+//!
+//! let plan = [NumericOp::ScaleOffset(2.0, 1.0), NumericOp::FilterGt(10.0)];
+//! let shader = sum_shader(&plan).unwrap();
+//! assert!(shader.contains("x = 2.0 * x + 1.0;"));
+//!
+//! // One partial sum per workgroup
+//! let (x, y) = dispatch_size(1_000_000);
+//! assert!(x * y * WORKGROUP_SIZE >= 1_000_000);
+//!
+//! assert!(sum_shader(&[NumericOp::Take(10)]).is_none());
+//! ```
+//!
+//! Arithmetic is `f32` throughout, and partial sums are added in a
+//! different order than a sequential sum, so results can differ from the
+//! CPU path in the last digits.
+
+use super::NumericOp;
+use std::fmt::Write;
+
+/// Invocations per workgroup in the generated shaders.
+pub const WORKGROUP_SIZE: u32 = 256;
+
+/// The most workgroups a dispatch may have in one dimension under the
+/// default WebGPU limits.
+const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65_535;
+
+/// Shader that applies an element-wise plan, writing one output per input.
+///
+/// Returns `None` if the plan has a filter, or a step or constant that
+/// can't run on the GPU.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::simd::wgsl::map_shader;
+/// use orlando_transducers::simd::NumericOp;
+///
+/// let shader = map_shader(&[NumericOp::Normalize(10.0, 2.5), NumericOp::Abs]).unwrap();
+/// assert!(shader.contains("x = (x - 10.0) / 2.5;"));
+///
+/// assert!(map_shader(&[NumericOp::FilterGt(0.0)]).is_none());
+/// ```
+pub fn map_shader(plan: &[NumericOp<f32>]) -> Option<String> {
+    if plan.iter().any(is_filter) {
+        return None;
+    }
+    let steps = steps(plan)?;
+    Some(format!(
+        "{BINDINGS}
+@compute @workgroup_size({WORKGROUP_SIZE})
+fn main(
+    @builtin(workgroup_id) group: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+    @builtin(local_invocation_index) local: u32,
+) {{
+    let i = (group.x + group.y * groups.x) * {WORKGROUP_SIZE}u + local;
+    if (i >= arrayLength(&input)) {{
+        return;
+    }}
+    var x = input[i];
+{steps}    output[i] = x;
+}}
+"
+    ))
+}
+
+/// Shader that applies an element-wise plan and sums the elements that pass
+/// its filters, one partial sum per workgroup.
+///
+/// The output buffer needs one `f32` per workgroup dispatched; workgroups
+/// past the end of the input write zero.
+///
+/// Returns `None` if the plan has a step or constant that can't run on the
+/// GPU.
+pub fn sum_shader(plan: &[NumericOp<f32>]) -> Option<String> {
+    let steps = steps(plan)?;
+    let half = WORKGROUP_SIZE / 2;
+    Some(format!(
+        "{BINDINGS}
+var<workgroup> partial: array<f32, {WORKGROUP_SIZE}>;
+
+@compute @workgroup_size({WORKGROUP_SIZE})
+fn main(
+    @builtin(workgroup_id) group: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+    @builtin(local_invocation_index) local: u32,
+) {{
+    let index = group.x + group.y * groups.x;
+    let i = index * {WORKGROUP_SIZE}u + local;
+    var value = 0.0;
+    if (i < arrayLength(&input)) {{
+        var x = input[i];
+        var keep = true;
+{steps}        value = select(0.0, x, keep);
+    }}
+    partial[local] = value;
+    workgroupBarrier();
+    for (var stride = {half}u; stride > 0u; stride = stride >> 1u) {{
+        if (local < stride) {{
+            partial[local] = partial[local] + partial[local + stride];
+        }}
+        workgroupBarrier();
+    }}
+    if (local == 0u) {{
+        output[index] = partial[0];
+    }}
+}}
+"
+    ))
+}
+
+/// Workgroup counts `(x, y)` covering `len` elements.
+///
+/// Large inputs need more workgroups than one dimension allows, so they
+/// are spread over a second; the shaders number workgroups row by row.
+pub fn dispatch_size(len: usize) -> (u32, u32) {
+    let groups = len.div_ceil(WORKGROUP_SIZE as usize).max(1);
+    let x = groups.min(MAX_WORKGROUPS_PER_DIMENSION as usize);
+    (x as u32, groups.div_ceil(x) as u32)
+}
+
+const BINDINGS: &str = "@group(0) @binding(0) var<storage, read> input: array<f32>;
+@group(0) @binding(1) var<storage, read_write> output: array<f32>;
+";
+
+fn is_filter(op: &NumericOp<f32>) -> bool {
+    matches!(
+        op,
+        NumericOp::FilterGt(_)
+            | NumericOp::FilterGte(_)
+            | NumericOp::FilterLt(_)
+            | NumericOp::FilterLte(_)
+            | NumericOp::FilterRange(..)
+    )
+}
+
+/// The plan as WGSL statements updating `x` and `keep`, indented for the
+/// shader bodies.
+fn steps(plan: &[NumericOp<f32>]) -> Option<String> {
+    let mut body = String::new();
+    for op in plan {
+        let statement = match *op {
+            NumericOp::Add(n) => format!("x = x + {};", literal(n)?),
+            NumericOp::Mul(n) => format!("x = x * {};", literal(n)?),
+            NumericOp::ScaleOffset(a, b) => format!("x = {} * x + {};", literal(a)?, literal(b)?),
+            NumericOp::Normalize(mean, std) => {
+                format!("x = (x - {}) / {};", literal(mean)?, literal(std)?)
+            }
+            // Comparisons rather than `clamp`, so NaN passes through as on
+            // the CPU
+            NumericOp::Clamp(lo, hi) => {
+                let (lo, hi) = (literal(lo)?, literal(hi)?);
+                format!("x = select(x, {lo}, x < {lo}); x = select(x, {hi}, x > {hi});")
+            }
+            NumericOp::Abs => "x = abs(x);".to_string(),
+            NumericOp::FilterGt(n) => format!("keep = keep && x > {};", literal(n)?),
+            NumericOp::FilterGte(n) => format!("keep = keep && x >= {};", literal(n)?),
+            NumericOp::FilterLt(n) => format!("keep = keep && x < {};", literal(n)?),
+            NumericOp::FilterLte(n) => format!("keep = keep && x <= {};", literal(n)?),
+            NumericOp::FilterRange(lo, hi) => {
+                format!(
+                    "keep = keep && x >= {} && x < {};",
+                    literal(lo)?,
+                    literal(hi)?
+                )
+            }
+            NumericOp::Take(_) | NumericOp::Drop(_) | NumericOp::Unique => return None,
+        };
+        let _ = writeln!(body, "        {statement}");
+    }
+    Some(body)
+}
+
+/// A WGSL `f32` literal, parenthesized when negative.
+fn literal(value: f32) -> Option<String> {
+    if !value.is_finite() {
+        return None;
+    }
+    // `{:?}` always includes a decimal point or exponent, so WGSL reads the
+    // literal as a float rather than an integer
+    Some(if value.is_sign_negative() {
+        format!("({value:?})")
+    } else {
+        format!("{value:?}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_shader_body() {
+        let shader = map_shader(&[
+            NumericOp::Add(-1.5),
+            NumericOp::Mul(1e-7),
+            NumericOp::Clamp(0.0, 4.0),
+        ])
+        .unwrap();
+        assert!(shader.contains("x = x + (-1.5);"));
+        assert!(shader.contains("x = x * 1e-7;"));
+        assert!(shader.contains("x = select(x, 0.0, x < 0.0); x = select(x, 4.0, x > 4.0);"));
+        assert!(shader.contains("@workgroup_size(256)"));
+        assert!(!shader.contains("keep"));
+    }
+
+    #[test]
+    fn test_sum_shader_filters_and_reduction() {
+        let shader = sum_shader(&[NumericOp::FilterRange(1.0, 2.0), NumericOp::Abs]).unwrap();
+        assert!(shader.contains("keep = keep && x >= 1.0 && x < 2.0;"));
+        assert!(shader.contains("var<workgroup> partial: array<f32, 256>;"));
+        assert!(shader.contains("stride = 128u"));
+        // Every invocation reaches the barriers, even past the end of the input
+        assert!(!shader.contains("return;"));
+    }
+
+    #[test]
+    fn test_unsupported_plans() {
+        assert!(map_shader(&[NumericOp::Unique]).is_none());
+        assert!(sum_shader(&[NumericOp::Drop(1)]).is_none());
+        assert!(sum_shader(&[NumericOp::FilterLt(f32::INFINITY)]).is_none());
+        assert!(map_shader(&[NumericOp::Add(f32::NAN)]).is_none());
+        assert!(map_shader(&[NumericOp::FilterLte(0.0)]).is_none());
+        // An empty plan is a copy (or a plain sum)
+        assert!(map_shader(&[]).is_some());
+    }
+
+    #[test]
+    fn test_dispatch_size() {
+        assert_eq!(dispatch_size(0), (1, 1));
+        assert_eq!(dispatch_size(256), (1, 1));
+        assert_eq!(dispatch_size(257), (2, 1));
+        let (x, y) = dispatch_size(100_000_000);
+        assert_eq!((x, y), (65_535, 6));
+        assert!(x as usize * y as usize * 256 >= 100_000_000);
+    }
+}
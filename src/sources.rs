@@ -0,0 +1,383 @@
+//! Lazy sources that feed transducers.
+//!
+//! The helpers in [`crate::collectors`] such as
+//! [`cartesian_product`](crate::collectors::cartesian_product) and
+//! [`zip`](crate::collectors::zip) build their whole result eagerly. The sources
+//! here are plain iterators that produce elements on demand, so they can be passed
+//! straight to a collector and stop as soon as the pipeline terminates (for
+//! example with `Take`). Huge products are never materialized.
+//!
+//! ```rust
+//! use orlando_transducers::sources::cartesian_iter;
+//! use orlando_transducers::{to_vec, Filter, Take, Transducer};
+//!
+//! // A billion-element product, of which only three elements are ever produced
+//! let pipeline = Filter::new(|(a, b): &(u64, u64)| (a + b) % 7 == 0).compose(Take::new(3));
+//! let hits = to_vec(&pipeline, cartesian_iter(0..1_000_000_u64, 0..1_000_u64));
+//! assert_eq!(hits, vec![(0, 0), (0, 7), (0, 14)]);
+//! ```
+//!
+//! [`lines`] and [`bytes`] read from any [`BufRead`] (a buffered file, socket or
+//! stdin) the same way: one line or byte at a time, so a pipeline that stops
+//! early leaves the rest of the input unread.
+
+use std::io::{self, BufRead};
+
+/// Lazily produce every pair `(a, b)` with `a` from the first source and `b` from the second.
+///
+/// Pairs are produced in row-major order: for each `a`, every `b` in turn. The first
+/// source is consumed lazily; the second is restarted for every `a` by cloning its
+/// iterator, so it must be `Clone` (ranges, slice iterators and `vec::IntoIter` are).
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::sources::cartesian_iter;
+///
+/// let pairs: Vec<_> = cartesian_iter(vec![1, 2], vec!['a', 'b']).collect();
+/// assert_eq!(pairs, vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+/// ```
+pub fn cartesian_iter<A, B>(a: A, b: B) -> CartesianIter<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    A::Item: Clone,
+    B: IntoIterator,
+    B::IntoIter: Clone,
+{
+    let b = b.into_iter();
+    CartesianIter {
+        outer: a.into_iter(),
+        current: None,
+        inner: b.clone(),
+        inner_template: b,
+    }
+}
+
+/// Iterator returned by [`cartesian_iter`].
+#[derive(Clone)]
+pub struct CartesianIter<A: Iterator, B> {
+    outer: A,
+    current: Option<A::Item>,
+    inner: B,
+    inner_template: B,
+}
+
+impl<A, B> Iterator for CartesianIter<A, B>
+where
+    A: Iterator,
+    A::Item: Clone,
+    B: Iterator + Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                self.current = Some(self.outer.next()?);
+                self.inner = self.inner_template.clone();
+            }
+            match self.inner.next() {
+                Some(b) => return self.current.clone().map(|a| (a, b)),
+                // Inner exhausted: advance the outer source
+                None => self.current = None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (outer_lo, outer_hi) = self.outer.size_hint();
+        let (inner_lo, inner_hi) = self.inner_template.size_hint();
+        let (rest_lo, rest_hi) = if self.current.is_some() {
+            self.inner.size_hint()
+        } else {
+            (0, Some(0))
+        };
+
+        let lo = outer_lo.saturating_mul(inner_lo).saturating_add(rest_lo);
+        let hi = match (outer_hi, inner_hi, rest_hi) {
+            (Some(o), Some(i), Some(r)) => o.checked_mul(i).and_then(|n| n.checked_add(r)),
+            _ => None,
+        };
+        (lo, hi)
+    }
+}
+
+/// Lazily pair up elements of two sources, stopping when either is exhausted.
+///
+/// This is the lazy counterpart of [`zip`](crate::collectors::zip): nothing is
+/// buffered, so it can feed a pipeline over unbounded sources.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::sources::zip_iter;
+/// use orlando_transducers::{to_vec, Map, Take, Transducer};
+///
+/// let pipeline = Map::new(|(i, c): (usize, char)| format!("{}{}", c, i)).compose(Take::new(3));
+/// let labels = to_vec(&pipeline, zip_iter(0.., "abcdef".chars()));
+/// assert_eq!(labels, vec!["a0", "b1", "c2"]);
+/// ```
+pub fn zip_iter<A, B>(a: A, b: B) -> std::iter::Zip<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+{
+    a.into_iter().zip(b)
+}
+
+/// Lazily read the lines of `reader`, without their `\n` or `\r\n` terminators.
+///
+/// Each line is read only when the pipeline asks for it, so early termination
+/// (for example with `Take`) stops reading. A read error, including invalid
+/// UTF-8, is yielded as an `Err` element and ends the stream.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::sources::lines;
+/// use orlando_transducers::{to_vec, FlatMap, Filter, Take, Transducer};
+///
+/// let log = "INFO start\nERROR disk full\nINFO retry\nERROR disk full\n".as_bytes();
+/// let errors = FlatMap::new(|line: std::io::Result<String>| line.into_iter().collect::<Vec<_>>())
+///     .compose(Filter::new(|line: &String| line.starts_with("ERROR")))
+///     .compose(Take::new(1));
+/// assert_eq!(to_vec(&errors, lines(log)), vec!["ERROR disk full"]);
+/// ```
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        reader: Some(reader),
+    }
+}
+
+/// Iterator returned by [`lines`].
+pub struct Lines<R> {
+    /// The reader, `None` once it is exhausted or has failed
+    reader: Option<R>,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        match self.reader.as_mut()?.read_line(&mut line) {
+            Ok(0) => {
+                self.reader = None;
+                None
+            }
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Some(Ok(line))
+            }
+            Err(e) => {
+                self.reader = None;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Lazily read the bytes of `reader` one at a time.
+///
+/// Bytes are served from the reader's buffer, which is refilled only when the
+/// pipeline needs more, so early termination stops reading. A read error is
+/// yielded as an `Err` element and ends the stream.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::sources::bytes;
+/// use orlando_transducers::{to_vec, Map, TakeWhile, Transducer};
+///
+/// // Read a header up to the first NUL byte
+/// let input: &[u8] = b"v2\0payload";
+/// let header = Map::new(|b: std::io::Result<u8>| b.unwrap_or(0))
+///     .compose(TakeWhile::new(|b: &u8| *b != 0));
+/// assert_eq!(to_vec(&header, bytes(input)), b"v2");
+/// ```
+pub fn bytes<R: BufRead>(reader: R) -> Bytes<R> {
+    Bytes {
+        reader: Some(reader),
+    }
+}
+
+/// Iterator returned by [`bytes`].
+pub struct Bytes<R> {
+    /// The reader, `None` once it is exhausted or has failed
+    reader: Option<R>,
+}
+
+impl<R: BufRead> Iterator for Bytes<R> {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = self.reader.as_mut()?;
+        loop {
+            match reader.fill_buf() {
+                Ok([]) => {
+                    self.reader = None;
+                    return None;
+                }
+                Ok(buf) => {
+                    let byte = buf[0];
+                    reader.consume(1);
+                    return Some(Ok(byte));
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.reader = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::{cartesian_product, to_vec};
+    use crate::transducer::{Identity, Transducer};
+    use crate::transforms::{Filter, FlatMap, Map, Take};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_cartesian_iter_matches_eager() {
+        let lazy: Vec<_> = cartesian_iter(vec![1, 2, 3], vec!["x", "y"]).collect();
+        assert_eq!(lazy, cartesian_product(vec![1, 2, 3], vec!["x", "y"]));
+    }
+
+    #[test]
+    fn test_cartesian_iter_empty_sides() {
+        assert_eq!(cartesian_iter(Vec::<i32>::new(), 0..3).count(), 0);
+        assert_eq!(cartesian_iter(0..3, Vec::<i32>::new()).count(), 0);
+    }
+
+    #[test]
+    fn test_cartesian_iter_respects_take() {
+        let pulled = Rc::new(Cell::new(0));
+        let pulled_clone = Rc::clone(&pulled);
+        let outer = (0..u64::MAX).inspect(move |_| pulled_clone.set(pulled_clone.get() + 1));
+
+        let pipeline = Take::new(5);
+        let result = to_vec(&pipeline, cartesian_iter(outer, 0..2_u64));
+
+        assert_eq!(result, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)]);
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn test_cartesian_iter_with_pipeline() {
+        let pipeline = Map::new(|(a, b): (i32, i32)| a * b).compose(Filter::new(|x: &i32| *x > 2));
+        assert_eq!(
+            to_vec(&pipeline, cartesian_iter(1..=2, 1..=3)),
+            vec![3, 4, 6]
+        );
+    }
+
+    #[test]
+    fn test_cartesian_iter_size_hint() {
+        let mut it = cartesian_iter(0..3, 0..4);
+        assert_eq!(it.size_hint(), (12, Some(12)));
+        it.next();
+        assert_eq!(it.size_hint(), (11, Some(11)));
+    }
+
+    #[test]
+    fn test_zip_iter_unbounded() {
+        let id = Identity::<(u32, u32)>::new();
+        let pipeline = id.compose(Take::new(3));
+        assert_eq!(
+            to_vec(&pipeline, zip_iter(0.., (10..).step_by(10))),
+            vec![(0, 10), (1, 20), (2, 30)]
+        );
+    }
+
+    #[test]
+    fn test_zip_iter_shortest_wins() {
+        let pairs: Vec<_> = zip_iter(vec![1, 2, 3], vec!['a']).collect();
+        assert_eq!(pairs, vec![(1, 'a')]);
+    }
+
+    /// A reader over `data` that counts the bytes handed out
+    struct Counting<'a> {
+        data: &'a [u8],
+        read: Rc<Cell<usize>>,
+    }
+
+    impl io::Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.data.read(buf)?;
+            self.read.set(self.read.get() + n);
+            Ok(n)
+        }
+    }
+
+    fn ok<T: 'static>() -> impl Transducer<io::Result<T>, T> {
+        FlatMap::new(|x: io::Result<T>| x.into_iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_lines_strip_terminators() {
+        let input = "a\r\nb\n\nc".as_bytes();
+        assert_eq!(to_vec(&ok(), lines(input)), vec!["a", "b", "", "c"]);
+        assert_eq!(lines(&b""[..]).count(), 0);
+    }
+
+    #[test]
+    fn test_lines_take_stops_reading() {
+        let text = "line\n".repeat(10_000);
+        let read = Rc::new(Cell::new(0));
+        let reader = io::BufReader::with_capacity(
+            64,
+            Counting {
+                data: text.as_bytes(),
+                read: Rc::clone(&read),
+            },
+        );
+
+        let first = to_vec(&ok::<String>().compose(Take::new(3)), lines(reader));
+        assert_eq!(first, vec!["line", "line", "line"]);
+        assert!(read.get() <= 64);
+    }
+
+    #[test]
+    fn test_lines_invalid_utf8_ends_stream() {
+        let mut it = lines(&b"ok\n\xff\nnever\n"[..]);
+        assert_eq!(it.next().unwrap().unwrap(), "ok");
+        assert!(it.next().unwrap().is_err());
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_bytes_take_stops_reading() {
+        let data = vec![7u8; 10_000];
+        let read = Rc::new(Cell::new(0));
+        let reader = io::BufReader::with_capacity(
+            16,
+            Counting {
+                data: &data,
+                read: Rc::clone(&read),
+            },
+        );
+
+        let head = to_vec(&ok::<u8>().compose(Take::new(20)), bytes(reader));
+        assert_eq!(head, vec![7; 20]);
+        assert_eq!(read.get(), 32);
+    }
+
+    #[test]
+    fn test_bytes_reads_everything() {
+        let total: u32 = to_vec(&ok::<u8>(), bytes(&b"\x01\x02\x03"[..]))
+            .into_iter()
+            .map(u32::from)
+            .sum();
+        assert_eq!(total, 6);
+    }
+}
@@ -0,0 +1,209 @@
+//! Incremental sources for the WASM API.
+//!
+//! These wrap raw input as async iterables that the async terminals
+//! (`toArrayAsync`, `forEachAsync`) consume element by element, so large
+//! inputs are processed as they arrive instead of being loaded whole.
+
+use crate::error_wasm::{ErrorCode, OrlandoError};
+use crate::pipeline::js_async_iterator;
+use js_sys::{ArrayBuffer, Function, Object, Promise, Reflect, Uint8Array};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+/// Newline-delimited JSON decoder state.
+struct LineReader {
+    /// The chunk source, `None` once it is exhausted or closed
+    source: Option<(JsValue, Function, bool)>,
+    /// Bytes after the last newline seen so far
+    partial: Vec<u8>,
+    /// Parsed values not yet handed out
+    ready: VecDeque<JsValue>,
+    /// Number of lines read so far
+    lines: usize,
+}
+
+impl LineReader {
+    /// Append a chunk and parse every line it completes.
+    fn push(&mut self, chunk: &JsValue) -> Result<(), JsValue> {
+        self.partial.extend(chunk_bytes(chunk)?);
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(());
+        };
+        let rest = self.partial.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        // A newline byte never occurs inside a multi-byte UTF-8 sequence, so
+        // splitting before decoding is safe
+        complete[..end]
+            .split(|&b| b == b'\n')
+            .try_for_each(|line| self.parse(line))
+    }
+
+    /// Parse the trailing line once the source is exhausted.
+    fn finish(&mut self) -> Result<(), JsValue> {
+        self.source = None;
+        let last = std::mem::take(&mut self.partial);
+        self.parse(&last)
+    }
+
+    fn parse(&mut self, line: &[u8]) -> Result<(), JsValue> {
+        self.lines += 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(());
+        }
+
+        let text = std::str::from_utf8(line).map_err(|_| {
+            OrlandoError::new(
+                ErrorCode::ParseError,
+                format!("line {} is not valid UTF-8", self.lines),
+            )
+        })?;
+        let value = js_sys::JSON::parse(text).map_err(|error| {
+            OrlandoError::new(
+                ErrorCode::ParseError,
+                format!("line {} is not valid JSON", self.lines),
+            )
+            .with_value(text.into())
+            .with_cause(error)
+        })?;
+        self.ready.push_back(value);
+        Ok(())
+    }
+}
+
+/// Parse newline-delimited JSON (NDJSON / JSON Lines) incrementally.
+///
+/// `chunks` is an async or sync iterable of `Uint8Array` (including Node
+/// `Buffer`s), `ArrayBuffer` or string chunks, such as `response.body` from
+/// `fetch` or a Node `Readable`. Chunk boundaries may fall anywhere, even
+/// inside a line or a multi-byte character. Each complete line is parsed with
+/// `JSON.parse` and emitted as soon as it arrives, so only the current partial
+/// line is held in memory. Blank lines are skipped and `\r\n` line endings are
+/// accepted.
+///
+/// A line that isn't valid JSON rejects the consuming terminal with an
+/// `OrlandoError` whose code is `PARSE_ERROR`, whose `value` is the line and
+/// whose `cause` is the `SyntaxError`. Stopping early (e.g. after `take`)
+/// closes `chunks`.
+///
+/// # Examples (JavaScript)
+///
+/// ```javascript
+/// const response = await fetch('/events.ndjson');
+///
+/// const failures = await new Pipeline()
+///   .filter(event => event.status >= 500)
+///   .take(100)
+///   .toArrayAsync(jsonLines(response.body));
+/// ```
+#[wasm_bindgen(js_name = jsonLines, unchecked_return_type = "AsyncIterableIterator<any>")]
+pub fn json_lines(
+    #[wasm_bindgen(
+        unchecked_param_type = "AsyncIterable<Uint8Array | ArrayBuffer | string> | Iterable<Uint8Array | ArrayBuffer | string>"
+    )]
+    chunks: &JsValue,
+) -> Result<Object, JsValue> {
+    let source = match js_async_iterator(chunks)? {
+        Some((iterator, is_async)) => {
+            let next_fn: Function = Reflect::get(&iterator, &"next".into())?.dyn_into()?;
+            Some((iterator, next_fn, is_async))
+        }
+        None => None,
+    };
+    let reader = Rc::new(RefCell::new(LineReader {
+        source,
+        partial: Vec::new(),
+        ready: VecDeque::new(),
+        lines: 0,
+    }));
+
+    let next = {
+        let reader = Rc::clone(&reader);
+        Closure::<dyn FnMut() -> Promise>::new(move || {
+            let reader = Rc::clone(&reader);
+            future_to_promise(async move { next_value(&reader).await })
+        })
+    };
+    let close = Closure::<dyn FnMut() -> Promise>::new(move || {
+        let source = {
+            let mut reader = reader.borrow_mut();
+            reader.ready.clear();
+            reader.source.take()
+        };
+        future_to_promise(async move {
+            if let Some((iterator, _, _)) = source {
+                let return_fn = Reflect::get(&iterator, &"return".into())?;
+                if let Some(return_fn) = return_fn.dyn_ref::<Function>() {
+                    let closed = return_fn.call0(&iterator)?;
+                    JsFuture::from(Promise::resolve(&closed)).await?;
+                }
+            }
+            Ok(iter_result(&JsValue::UNDEFINED, true))
+        })
+    });
+
+    let iterator = Object::new();
+    Reflect::set(&iterator, &"next".into(), &next.into_js_value())?;
+    Reflect::set(&iterator, &"return".into(), &close.into_js_value())?;
+    let this = Function::new_no_args("return this");
+    Reflect::set(&iterator, &js_sys::Symbol::async_iterator(), &this)?;
+    Ok(iterator)
+}
+
+/// Produce the next parsed line, pulling chunks from the source as needed.
+async fn next_value(reader: &RefCell<LineReader>) -> Result<JsValue, JsValue> {
+    loop {
+        let (iterator, next_fn, is_async) = {
+            let mut reader = reader.borrow_mut();
+            if let Some(value) = reader.ready.pop_front() {
+                return Ok(iter_result(&value, false));
+            }
+            match &reader.source {
+                Some(source) => source.clone(),
+                None => return Ok(iter_result(&JsValue::UNDEFINED, true)),
+            }
+        };
+
+        let step = JsFuture::from(Promise::resolve(&next_fn.call0(&iterator)?)).await?;
+        if Reflect::get(&step, &"done".into())?.is_truthy() {
+            reader.borrow_mut().finish()?;
+            continue;
+        }
+        let mut chunk = Reflect::get(&step, &"value".into())?;
+        if !is_async {
+            chunk = JsFuture::from(Promise::resolve(&chunk)).await?;
+        }
+        reader.borrow_mut().push(&chunk)?;
+    }
+}
+
+/// The bytes of a `Uint8Array`, `ArrayBuffer` or string chunk.
+fn chunk_bytes(chunk: &JsValue) -> Result<Vec<u8>, JsValue> {
+    if let Some(bytes) = chunk.dyn_ref::<Uint8Array>() {
+        return Ok(bytes.to_vec());
+    }
+    if let Some(buffer) = chunk.dyn_ref::<ArrayBuffer>() {
+        return Ok(Uint8Array::new(buffer).to_vec());
+    }
+    if let Some(text) = chunk.as_string() {
+        return Ok(text.into_bytes());
+    }
+    Err(OrlandoError::new(
+        ErrorCode::InvalidArgument,
+        "jsonLines expects Uint8Array, ArrayBuffer or string chunks",
+    )
+    .with_value(chunk.clone())
+    .into())
+}
+
+/// An iterator protocol result, `{ value, done }`.
+pub(crate) fn iter_result(value: &JsValue, done: bool) -> JsValue {
+    let result = Object::new();
+    let _ = Reflect::set(&result, &"value".into(), value);
+    let _ = Reflect::set(&result, &"done".into(), &done.into());
+    result.into()
+}
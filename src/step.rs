@@ -15,7 +15,7 @@
 //! - Right identity: `m.and_then(cont) == m`
 //! - Associativity: `m.and_then(f).and_then(g) == m.and_then(|x| f(x).and_then(g))`
 
-use std::fmt;
+use core::fmt;
 
 /// A Step represents a value in a reduction that may signal early termination.
 ///
@@ -0,0 +1,896 @@
+//! # Time Series: Streaming metrics
+//!
+//! Transducers for monitoring pipelines that keep a running view of a metric
+//! without buffering a window of elements:
+//!
+//! - [`Ewma`] emits an exponentially weighted moving average of each value
+//!   and the ones before it.
+//! - [`Decayed`] emits a count or sum in which each element's weight halves
+//!   every `half_life` of event time, so it tracks recent activity even when
+//!   elements arrive irregularly.
+//! - [`Derivative`] emits the rate of change between consecutive elements,
+//!   turning ever-increasing counters into rates.
+//! - [`AnomalyZScore`] flags elements far from the mean of the ones just
+//!   before them.
+//! - [`FillGaps`] inserts elements where samples are missing, so a sparse
+//!   series arrives at a regular interval.
+//!
+//! ```rust
+//! use orlando_transducers::timeseries::Ewma;
+//! use orlando_transducers::to_vec;
+//!
+//! let smoothed = to_vec(&Ewma::new(0.5), vec![10.0, 20.0, 20.0, 0.0]);
+//! assert_eq!(smoothed, vec![10.0, 15.0, 17.5, 8.75]);
+//! ```
+//!
+//! Timestamps are `u64`s in the source's own unit, as in
+//! [`window`](crate::window).
+
+use crate::explain::{Explain, StageInfo};
+use crate::step::Step;
+use crate::transducer::Transducer;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Exponentially weighted moving average.
+///
+/// Each output is `alpha * x + (1 - alpha) * previous`, starting from the
+/// first value, so an `alpha` near 1 follows the input closely and one near
+/// 0 smooths heavily. Weights are per element rather than per unit of time;
+/// for irregularly spaced elements see [`Decayed`].
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::timeseries::Ewma;
+/// use orlando_transducers::{to_vec, Map, Transducer};
+///
+/// // Smoothed latency in milliseconds, from integer samples
+/// let latency = Map::new(|ms: u32| ms).compose(Ewma::new(0.2));
+/// let smoothed = to_vec(&latency, vec![100, 100, 600, 100]);
+/// assert_eq!(smoothed, vec![100.0, 100.0, 200.0, 180.0]);
+/// ```
+pub struct Ewma<T> {
+    alpha: f64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Ewma<T>
+where
+    T: Into<f64>,
+{
+    /// An average with smoothing factor `alpha`, in `(0, 1]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "Ewma alpha must be in (0, 1]");
+        Ewma {
+            alpha,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Transducer<T, f64> for Ewma<T>
+where
+    T: Into<f64> + 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, f64) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let alpha = self.alpha;
+        let average: Cell<Option<f64>> = Cell::new(None);
+
+        Box::new(move |acc, val| {
+            let x = val.into();
+            let next = average
+                .get()
+                .map_or(x, |previous| alpha * x + (1.0 - alpha) * previous);
+            average.set(Some(next));
+            reducer(acc, next)
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("ewma({})", self.alpha)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// Time-decayed count or sum.
+///
+/// After each element, emits the total of the elements so far with each
+/// weighted by `0.5^(age / half_life)`, where `age` is how far its timestamp
+/// is behind the latest seen. Old activity fades out smoothly instead of
+/// dropping off the edge of a window, and only the total and the latest
+/// timestamp are kept.
+///
+/// A steady rate of `r` elements per unit of time settles at a decayed count
+/// of about `r * half_life / ln 2`, so multiplying the count by
+/// `ln 2 / half_life` estimates the recent rate. Elements that arrive out
+/// of order are weighted by their age at arrival.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::timeseries::Decayed;
+/// use orlando_transducers::to_vec;
+///
+/// // Error timestamps in seconds, with a one-minute half-life
+/// let errors = vec![0, 0, 60, 180];
+/// let recent = Decayed::count(|t: &u64| *t, 60);
+/// assert_eq!(to_vec(&recent, errors), vec![1.0, 2.0, 2.0, 1.5]);
+///
+/// // Bytes sent, decayed
+/// let sends = vec![(0, 1000), (10, 500)];
+/// let recent_bytes = Decayed::sum(|s: &(u64, u32)| s.0, 10, |s: &(u64, u32)| s.1 as f64);
+/// assert_eq!(to_vec(&recent_bytes, sends), vec![1000.0, 1000.0]);
+/// ```
+pub struct Decayed<F, G, T> {
+    extract_ts: Rc<F>,
+    value: Rc<G>,
+    half_life: u64,
+    _phantom: PhantomData<T>,
+}
+
+/// The weight of every element in a [`Decayed::count`].
+pub type One<T> = fn(&T) -> f64;
+
+impl<F, T> Decayed<F, One<T>, T>
+where
+    F: Fn(&T) -> u64,
+{
+    /// Decayed number of elements.
+    pub fn count(extract_ts: F, half_life: u64) -> Self {
+        Decayed::sum(extract_ts, half_life, |_| 1.0)
+    }
+}
+
+impl<F, G, T> Decayed<F, G, T>
+where
+    F: Fn(&T) -> u64,
+    G: Fn(&T) -> f64,
+{
+    /// Decayed sum of `value` over the elements.
+    pub fn sum(extract_ts: F, half_life: u64, value: G) -> Self {
+        assert!(half_life > 0, "Decay half-life must be greater than 0");
+        Decayed {
+            extract_ts: Rc::new(extract_ts),
+            value: Rc::new(value),
+            half_life,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, G, T> Transducer<T, f64> for Decayed<F, G, T>
+where
+    F: Fn(&T) -> u64 + 'static,
+    G: Fn(&T) -> f64 + 'static,
+    T: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, f64) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let extract_ts = Rc::clone(&self.extract_ts);
+        let value = Rc::clone(&self.value);
+        let half_life = self.half_life as f64;
+        let decay = move |age: u64| (-(age as f64) / half_life).exp2();
+        // (total as of the latest timestamp, latest timestamp)
+        let state: Cell<Option<(f64, u64)>> = Cell::new(None);
+
+        Box::new(move |acc, val| {
+            let (ts, x) = (extract_ts(&val), value(&val));
+            let total = match state.get() {
+                None => (x, ts),
+                Some((total, latest)) if ts >= latest => (total * decay(ts - latest) + x, ts),
+                Some((total, latest)) => (total + x * decay(latest - ts), latest),
+            };
+            state.set(Some(total));
+            reducer(acc, total.0)
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("decayed({})", self.half_life)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// Rate of change between consecutive elements.
+///
+/// Emits each element after the first paired with
+/// `(value - previous value) / (ts - previous ts)`, in units per unit of
+/// timestamp, or per `unit` with [`per`](Derivative::per). Values are
+/// treated as counters by default: a value below the previous one is a
+/// counter reset (a restarted process, say), and the increase since the
+/// reset is taken to be the value itself, as a counter starting again from
+/// zero. [`gauge`](Derivative::gauge) turns this off so falling values give
+/// negative rates.
+///
+/// Elements at or before the previous timestamp are dropped, since they
+/// have no interval to measure a rate over.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::timeseries::Derivative;
+/// use orlando_transducers::to_vec;
+///
+/// // (timestamp in ms, requests served since start)
+/// let samples = vec![(0, 100.0), (1000, 150.0), (2000, 250.0), (3000, 30.0)];
+/// let rate = Derivative::new(|s: &(u64, f64)| s.0, |s: &(u64, f64)| s.1).per(1000);
+/// let rates: Vec<f64> = to_vec(&rate, samples).into_iter().map(|(_, r)| r).collect();
+/// // The process restarted before the last sample
+/// assert_eq!(rates, vec![50.0, 100.0, 30.0]);
+/// ```
+pub struct Derivative<F, G, T> {
+    extract_ts: Rc<F>,
+    value: Rc<G>,
+    unit: u64,
+    counter: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<F, G, T> Derivative<F, G, T>
+where
+    F: Fn(&T) -> u64,
+    G: Fn(&T) -> f64,
+{
+    /// Rates of `value` over the timestamps from `extract_ts`.
+    pub fn new(extract_ts: F, value: G) -> Self {
+        Derivative {
+            extract_ts: Rc::new(extract_ts),
+            value: Rc::new(value),
+            unit: 1,
+            counter: true,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Express rates per `unit` of timestamp, e.g. `per(1000)` for per-second
+    /// rates from millisecond timestamps.
+    pub fn per(mut self, unit: u64) -> Self {
+        assert!(unit > 0, "Derivative unit must be greater than 0");
+        self.unit = unit;
+        self
+    }
+
+    /// Treat values as a gauge rather than a counter: a decrease gives a
+    /// negative rate instead of being read as a reset.
+    pub fn gauge(mut self) -> Self {
+        self.counter = false;
+        self
+    }
+}
+
+impl<F, G, T> Transducer<T, (T, f64)> for Derivative<F, G, T>
+where
+    F: Fn(&T) -> u64 + 'static,
+    G: Fn(&T) -> f64 + 'static,
+    T: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, (T, f64)) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let extract_ts = Rc::clone(&self.extract_ts);
+        let value = Rc::clone(&self.value);
+        let (unit, counter) = (self.unit as f64, self.counter);
+        let previous: Cell<Option<(u64, f64)>> = Cell::new(None);
+
+        Box::new(move |acc, val| {
+            let (ts, x) = (extract_ts(&val), value(&val));
+            match previous.get() {
+                Some((last_ts, _)) if ts <= last_ts => Step::Continue(acc),
+                None => {
+                    previous.set(Some((ts, x)));
+                    Step::Continue(acc)
+                }
+                Some((last_ts, last)) => {
+                    previous.set(Some((ts, x)));
+                    let change = if counter && x < last { x } else { x - last };
+                    let rate = change * unit / (ts - last_ts) as f64;
+                    reducer(acc, (val, rate))
+                }
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        let name = if self.counter {
+            "derivative"
+        } else {
+            "derivative(gauge)"
+        };
+        Explain::stage(StageInfo::new(name).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
+}
+
+/// Anomaly detection by z-score over a rolling window.
+///
+/// Tags each element with whether its value is more than `threshold`
+/// standard deviations from the mean of the `window` values before it,
+/// emitting `(T, bool)`; [`anomalies`](AnomalyZScore::anomalies) keeps only
+/// the flagged elements instead. The mean and (population) variance are
+/// updated incrementally as values enter and leave the window, so each
+/// element costs O(1) whatever the window size.
+///
+/// Nothing is flagged until the window has filled. Against a window of
+/// identical values, any different value is flagged. NaN values are never
+/// flagged and don't enter the window.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::timeseries::AnomalyZScore;
+/// use orlando_transducers::to_vec;
+///
+/// let latencies = vec![10.0, 12.0, 11.0, 9.0, 10.0, 11.0, 95.0, 10.0];
+/// let spikes = AnomalyZScore::new(4, 3.0).anomalies();
+/// assert_eq!(to_vec(&spikes, latencies), vec![95.0]);
+///
+/// // Tag records by a field instead
+/// let readings = vec![("a", 1.0), ("b", 1.0), ("c", 1.0), ("d", 5.0)];
+/// let tagged = AnomalyZScore::by(3, 2.0, |r: &(&str, f64)| r.1);
+/// let flags: Vec<bool> = to_vec(&tagged, readings).into_iter().map(|(_, a)| a).collect();
+/// assert_eq!(flags, vec![false, false, false, true]);
+/// ```
+pub struct AnomalyZScore<T, F = fn(&T) -> f64> {
+    value: Rc<F>,
+    window: usize,
+    threshold: f64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> AnomalyZScore<T>
+where
+    T: Into<f64> + Clone,
+{
+    /// Flag numeric elements more than `threshold` standard deviations from
+    /// the mean of the previous `window`.
+    pub fn new(window: usize, threshold: f64) -> Self {
+        AnomalyZScore::by(window, threshold, |x: &T| x.clone().into())
+    }
+}
+
+impl<T, F> AnomalyZScore<T, F>
+where
+    F: Fn(&T) -> f64,
+{
+    /// Flag elements by the number `value` reads from each.
+    pub fn by(window: usize, threshold: f64, value: F) -> Self {
+        assert!(window >= 2, "Anomaly window must hold at least 2 values");
+        assert!(threshold > 0.0, "Anomaly threshold must be greater than 0");
+        AnomalyZScore {
+            value: Rc::new(value),
+            window,
+            threshold,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Emit only the anomalous elements, rather than tagging every element.
+    pub fn anomalies(self) -> Anomalies<T, F> {
+        Anomalies { detector: self }
+    }
+
+    /// Apply the detection, with `emit` building the output (if any) for an
+    /// element and whether it is anomalous.
+    fn apply_with<O, Acc, R>(
+        &self,
+        emit: fn(T, bool) -> Option<O>,
+        reducer: R,
+    ) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        F: 'static,
+        T: 'static,
+        O: 'static,
+        R: Fn(Acc, O) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let value = Rc::clone(&self.value);
+        let (window, threshold) = (self.window, self.threshold);
+        let rolling = RefCell::new(Rolling {
+            values: VecDeque::with_capacity(window),
+            mean: 0.0,
+            m2: 0.0,
+        });
+
+        Box::new(move |acc, val| {
+            let x = value(&val);
+            let mut rolling = rolling.borrow_mut();
+            let anomalous = rolling.values.len() == window
+                && rolling.z_score(x).is_some_and(|z| z.abs() > threshold);
+            if !x.is_nan() {
+                rolling.push(x, window);
+            }
+            drop(rolling);
+
+            match emit(val, anomalous) {
+                Some(out) => reducer(acc, out),
+                None => Step::Continue(acc),
+            }
+        })
+    }
+
+    fn stage(&self, name: &str) -> Explain {
+        Explain::stage(
+            StageInfo::new(format!("{}({}, {})", name, self.window, self.threshold)).stateful(),
+        )
+    }
+}
+
+impl<T, F> Transducer<T, (T, bool)> for AnomalyZScore<T, F>
+where
+    F: Fn(&T) -> f64 + 'static,
+    T: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, (T, bool)) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        self.apply_with(|val, anomalous| Some((val, anomalous)), reducer)
+    }
+
+    fn describe(&self) -> Explain {
+        self.stage("anomaly_zscore")
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// [`AnomalyZScore`] that emits only the anomalous elements. See
+/// [`AnomalyZScore::anomalies`].
+pub struct Anomalies<T, F = fn(&T) -> f64> {
+    detector: AnomalyZScore<T, F>,
+}
+
+impl<T, F> Transducer<T, T> for Anomalies<T, F>
+where
+    F: Fn(&T) -> f64 + 'static,
+    T: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, T) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        self.detector
+            .apply_with(|val, anomalous| anomalous.then_some(val), reducer)
+    }
+
+    fn describe(&self) -> Explain {
+        self.detector.stage("anomalies")
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
+}
+
+/// The last `window` values with their running mean and sum of squared
+/// deviations (Welford's method, run forwards and backwards).
+struct Rolling {
+    values: VecDeque<f64>,
+    mean: f64,
+    m2: f64,
+}
+
+impl Rolling {
+    fn push(&mut self, x: f64, window: usize) {
+        if self.values.len() == window {
+            let old = self.values.pop_front().expect("window is full");
+            let n = self.values.len() as f64;
+            let delta = old - self.mean;
+            self.mean -= delta / n;
+            self.m2 -= delta * (old - self.mean);
+        }
+        self.values.push_back(x);
+        let n = self.values.len() as f64;
+        let delta = x - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Standard deviations of `x` from the mean, or `None` for NaN.
+    fn z_score(&self, x: f64) -> Option<f64> {
+        // Rounding can leave a slightly negative sum for a constant window
+        let std_dev = (self.m2.max(0.0) / self.values.len() as f64).sqrt();
+        let delta = x - self.mean;
+        if x.is_nan() {
+            None
+        } else if std_dev == 0.0 {
+            Some(if delta == 0.0 { 0.0 } else { f64::INFINITY })
+        } else {
+            Some(delta / std_dev)
+        }
+    }
+}
+
+type AtFn<T> = Rc<dyn Fn(&T, u64) -> T>;
+type ValueFn<T> = Rc<dyn Fn(&T) -> f64>;
+type InterpolateFn<T> = Rc<dyn Fn(&T, u64, f64) -> T>;
+
+/// How [`FillGaps`] builds the elements it inserts.
+pub struct Fill<T> {
+    strategy: Strategy<T>,
+}
+
+enum Strategy<T> {
+    Previous(AtFn<T>),
+    Linear(ValueFn<T>, InterpolateFn<T>),
+    Default(Rc<dyn Fn(u64) -> T>),
+}
+
+impl<T> Fill<T> {
+    /// Repeat the element before the gap: `at(previous, ts)` builds a copy
+    /// of it at a missing timestamp.
+    pub fn previous(at: impl Fn(&T, u64) -> T + 'static) -> Self {
+        Fill {
+            strategy: Strategy::Previous(Rc::new(at)),
+        }
+    }
+
+    /// Interpolate linearly between the elements either side of the gap:
+    /// `at(previous, ts, value)` builds an element at a missing timestamp
+    /// with the interpolated `value`.
+    pub fn linear(
+        value: impl Fn(&T) -> f64 + 'static,
+        at: impl Fn(&T, u64, f64) -> T + 'static,
+    ) -> Self {
+        Fill {
+            strategy: Strategy::Linear(Rc::new(value), Rc::new(at)),
+        }
+    }
+
+    /// Insert a fixed element built by `at(ts)` for each missing timestamp.
+    pub fn default(at: impl Fn(u64) -> T + 'static) -> Self {
+        Fill {
+            strategy: Strategy::Default(Rc::new(at)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.strategy {
+            Strategy::Previous(_) => "previous",
+            Strategy::Linear(..) => "linear",
+            Strategy::Default(_) => "default",
+        }
+    }
+
+    /// The element at `ts`, between `previous` at `start` and `next` at
+    /// `end`.
+    fn at(&self, previous: &T, next: &T, ts: u64, (start, end): (u64, u64)) -> T {
+        match &self.strategy {
+            Strategy::Previous(at) => at(previous, ts),
+            Strategy::Linear(value, at) => {
+                let (from, to) = (value(previous), value(next));
+                let fraction = (ts - start) as f64 / (end - start) as f64;
+                at(previous, ts, from + (to - from) * fraction)
+            }
+            Strategy::Default(at) => at(ts),
+        }
+    }
+}
+
+/// Gap filling for sparse time series.
+///
+/// Passes elements through, and where the next timestamp is more than
+/// `interval` after the previous one, first inserts elements every
+/// `interval` from the previous timestamp up to the next, built by the
+/// [`Fill`] strategy. Downstream [`WindowByTime`](crate::window::WindowByTime)
+/// aggregations then see regular sampling.
+///
+/// Elements before the previous timestamp pass through without filling,
+/// and one at the same timestamp replaces it as the start of the next gap. The number of elements inserted for a gap is its length over
+/// `interval`, so a very long gap in a fine-grained series inserts many.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::timeseries::{Fill, FillGaps};
+/// use orlando_transducers::to_vec;
+///
+/// // (minute, temperature) with readings missing
+/// let readings = vec![(0, 10.0), (1, 12.0), (4, 18.0)];
+///
+/// let carried = FillGaps::new(|r: &(u64, f64)| r.0, 1, Fill::previous(|r: &(u64, f64), ts| (ts, r.1)));
+/// assert_eq!(
+///     to_vec(&carried, readings.clone()),
+///     vec![(0, 10.0), (1, 12.0), (2, 12.0), (3, 12.0), (4, 18.0)]
+/// );
+///
+/// let interpolated = FillGaps::new(
+///     |r: &(u64, f64)| r.0,
+///     1,
+///     Fill::linear(|r: &(u64, f64)| r.1, |_, ts, value| (ts, value)),
+/// );
+/// assert_eq!(
+///     to_vec(&interpolated, readings),
+///     vec![(0, 10.0), (1, 12.0), (2, 14.0), (3, 16.0), (4, 18.0)]
+/// );
+/// ```
+pub struct FillGaps<F, T> {
+    extract_ts: Rc<F>,
+    interval: u64,
+    fill: Rc<Fill<T>>,
+}
+
+impl<F, T> FillGaps<F, T>
+where
+    F: Fn(&T) -> u64,
+{
+    /// Fill gaps longer than `interval` between timestamps from
+    /// `extract_ts`, with elements built by `fill`.
+    pub fn new(extract_ts: F, interval: u64, fill: Fill<T>) -> Self {
+        assert!(interval > 0, "Fill interval must be greater than 0");
+        FillGaps {
+            extract_ts: Rc::new(extract_ts),
+            interval,
+            fill: Rc::new(fill),
+        }
+    }
+}
+
+impl<F, T> Transducer<T, T> for FillGaps<F, T>
+where
+    F: Fn(&T) -> u64 + 'static,
+    T: Clone + 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, T) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let extract_ts = Rc::clone(&self.extract_ts);
+        let fill = Rc::clone(&self.fill);
+        let interval = self.interval;
+        let previous: RefCell<Option<(u64, T)>> = RefCell::new(None);
+
+        Box::new(move |mut acc, val| {
+            let end = extract_ts(&val);
+            let mut previous = previous.borrow_mut();
+            match previous.as_ref() {
+                Some((start, _)) if end < *start => return reducer(acc, val),
+                Some((start, before)) => {
+                    let mut ts = start.saturating_add(interval);
+                    while ts < end {
+                        match reducer(acc, fill.at(before, &val, ts, (*start, end))) {
+                            Step::Continue(next) => acc = next,
+                            stopped => return stopped,
+                        }
+                        ts = ts.saturating_add(interval);
+                    }
+                }
+                None => {}
+            }
+            *previous = Some((end, val.clone()));
+            drop(previous);
+            reducer(acc, val)
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        let name = format!("fill_gaps({}, {})", self.interval, self.fill.name());
+        Explain::stage(StageInfo::new(name).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (input.0, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::{last, to_vec};
+    use crate::transforms::Take;
+
+    #[test]
+    fn test_ewma_converges_and_reruns() {
+        let ewma = Ewma::new(0.1);
+        let settled = last(&ewma, std::iter::repeat_n(5.0f32, 200)).unwrap();
+        assert!((settled - 5.0).abs() < 1e-9);
+
+        let step = to_vec(&ewma, vec![0.0, 10.0, 10.0]);
+        assert_eq!(step[0], 0.0);
+        assert!((step[1] - 1.0).abs() < 1e-12);
+        assert!((step[2] - 1.9).abs() < 1e-12);
+        // Fresh state on every run
+        assert_eq!(to_vec(&ewma, vec![0.0, 10.0, 10.0]), step);
+
+        assert_eq!(
+            to_vec(&Ewma::new(1.0), vec![3u8, 1, 4]),
+            vec![3.0, 1.0, 4.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Ewma alpha must be in (0, 1]")]
+    fn test_ewma_rejects_zero_alpha() {
+        let _ = Ewma::<f64>::new(0.0);
+    }
+
+    #[test]
+    fn test_decayed_out_of_order_and_steady_rate() {
+        let counts = to_vec(&Decayed::count(|t: &u64| *t, 10), vec![20, 10, 20]);
+        // 10 arrives a half-life behind 20, at half weight
+        assert_eq!(counts, vec![1.0, 1.5, 2.5]);
+
+        // One element per unit of time settles near half_life / ln 2
+        let steady = last(&Decayed::count(|t: &u64| *t, 100), 0..10_000).unwrap();
+        let expected = 1.0 / (1.0 - (-1.0f64 / 100.0).exp2());
+        assert!((steady - expected).abs() < 1e-6);
+        assert!((steady * std::f64::consts::LN_2 / 100.0 - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_decayed_sum_stops_early() {
+        let pipeline =
+            Decayed::sum(|p: &(u64, f64)| p.0, 1, |p: &(u64, f64)| p.1).compose(Take::new(2));
+        assert_eq!(
+            to_vec(&pipeline, vec![(0, 8.0), (2, 2.0), (3, 100.0)]),
+            vec![8.0, 4.0]
+        );
+        assert_eq!(pipeline.describe().stages[0].name, "decayed(1)");
+    }
+
+    #[test]
+    fn test_derivative_counter_resets_and_gauges() {
+        let samples = vec![
+            (10, 5.0),
+            (20, 25.0),
+            (20, 99.0),
+            (15, 0.0),
+            (30, 10.0),
+            (40, 4.0),
+        ];
+        let ts: fn(&(u64, f64)) -> u64 = |s| s.0;
+        let value: fn(&(u64, f64)) -> f64 = |s| s.1;
+        let rates = |pipeline: &Derivative<_, _, _>| -> Vec<(u64, f64)> {
+            to_vec(pipeline, samples.clone())
+                .into_iter()
+                .map(|(s, rate)| (s.0, rate))
+                .collect()
+        };
+
+        // Repeated and out-of-order timestamps are dropped
+        let counter = Derivative::new(ts, value);
+        assert_eq!(rates(&counter), vec![(20, 2.0), (30, 1.0), (40, 0.4)]);
+        assert_eq!(counter.describe().stages[0].name, "derivative");
+
+        let gauge = Derivative::new(ts, value).gauge().per(10);
+        assert_eq!(rates(&gauge), vec![(20, 20.0), (30, -15.0), (40, -6.0)]);
+        assert_eq!(gauge.size_hint((6, Some(6))), (0, Some(6)));
+    }
+
+    #[test]
+    fn test_anomaly_zscore_matches_batch_statistics() {
+        // Incremental statistics agree with recomputing each window
+        let values: Vec<f64> = (0..500).map(|i| ((i * 37) % 101) as f64 * 1e3).collect();
+        let tagged = to_vec(&AnomalyZScore::new(50, 1.5), values.clone());
+        for (i, (x, anomalous)) in tagged.into_iter().enumerate() {
+            let expected = i >= 50 && {
+                let window = &values[i - 50..i];
+                let mean = window.iter().sum::<f64>() / 50.0;
+                let var = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 50.0;
+                ((x - mean) / var.sqrt()).abs() > 1.5
+            };
+            assert_eq!(anomalous, expected, "element {i}");
+        }
+    }
+
+    #[test]
+    fn test_anomalies_constant_windows_and_nan() {
+        let values = vec![2.0, 2.0, 2.0, 2.0, f64::NAN, 2.0, 2.5, 2.0, 9.0];
+        // Any change from a constant window is flagged; NaN is skipped
+        let anomalies = AnomalyZScore::new(3, 10.0).anomalies();
+        assert_eq!(to_vec(&anomalies, values), vec![2.5, 9.0]);
+        assert_eq!(anomalies.describe().stages[0].name, "anomalies(3, 10)");
+        assert_eq!(anomalies.size_hint((9, Some(9))), (0, Some(9)));
+
+        let first = AnomalyZScore::by(2, 1.0, |p: &(u32, f32)| p.1 as f64)
+            .anomalies()
+            .compose(Take::new(1));
+        assert_eq!(
+            to_vec(&first, vec![(1, 1.0), (2, 3.0), (3, 9.0), (4, 0.0)]),
+            vec![(3, 9.0)]
+        );
+    }
+
+    #[test]
+    fn test_fill_gaps_strategies() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Reading {
+            ts: u64,
+            value: f64,
+            synthetic: bool,
+        }
+        let reading = |ts, value| Reading {
+            ts,
+            value,
+            synthetic: false,
+        };
+        let ts = |r: &Reading| r.ts;
+        // Gaps shorter than the interval, repeated and late elements are
+        // left alone
+        let readings = vec![
+            reading(0, 1.0),
+            reading(5, 2.0),
+            reading(5, 3.0),
+            reading(2, 4.0),
+            reading(45, 7.0),
+        ];
+        let filled = |fill| {
+            to_vec(&FillGaps::new(ts, 10, fill), readings.clone())
+                .into_iter()
+                .map(|r| (r.ts, r.value, r.synthetic))
+                .collect::<Vec<_>>()
+        };
+
+        let previous = filled(Fill::previous(|r: &Reading, ts| Reading {
+            ts,
+            synthetic: true,
+            ..r.clone()
+        }));
+        assert_eq!(
+            previous,
+            vec![
+                (0, 1.0, false),
+                (5, 2.0, false),
+                (5, 3.0, false),
+                (2, 4.0, false),
+                (15, 3.0, true),
+                (25, 3.0, true),
+                (35, 3.0, true),
+                (45, 7.0, false)
+            ]
+        );
+
+        let linear = filled(Fill::linear(
+            |r: &Reading| r.value,
+            |_, ts, value| Reading {
+                ts,
+                value,
+                synthetic: true,
+            },
+        ));
+        assert_eq!(
+            &linear[4..7],
+            &[(15, 4.0, true), (25, 5.0, true), (35, 6.0, true)]
+        );
+
+        let default = filled(Fill::default(|ts| Reading {
+            ts,
+            value: 0.0,
+            synthetic: true,
+        }));
+        assert_eq!(default[4], (15, 0.0, true));
+    }
+
+    #[test]
+    fn test_fill_gaps_stops_early_inside_a_gap() {
+        let pipeline = FillGaps::new(|t: &u64| *t, 1, Fill::default(|ts| ts)).compose(Take::new(4));
+        assert_eq!(to_vec(&pipeline, vec![0, 1_000_000_000]), vec![0, 1, 2, 3]);
+        assert_eq!(pipeline.describe().stages[0].name, "fill_gaps(1, default)");
+    }
+}
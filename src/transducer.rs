@@ -23,8 +23,12 @@
 //! - Right identity: `t.compose(id()) == t`
 //! - Associativity: `(t1.compose(t2)).compose(t3) == t1.compose(t2.compose(t3))`
 
+use crate::debug::Tracer;
+use crate::explain::{short_type_name, Explain, StageInfo};
 use crate::step::Step;
-use std::marker::PhantomData;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::marker::PhantomData;
 
 /// A transducer transforms reducing functions.
 ///
@@ -46,6 +50,11 @@ pub trait Transducer<In, Out>: Sized {
     /// Apply this transducer to a reducing function.
     ///
     /// This transforms a reducer that consumes `Out` into one that consumes `In`.
+    ///
+    /// Each call is a separate run: stateful transducers (`Take`, `Unique`,
+    /// `Scan`, ...) create their state here rather than holding it in `self`,
+    /// so the same transducer can be run any number of times and every run
+    /// starts afresh.
     fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, In) -> Step<Acc>>
     where
         R: Fn(Acc, Out) -> Step<Acc> + 'static,
@@ -53,6 +62,70 @@ pub trait Transducer<In, Out>: Sized {
         In: 'static,
         Out: 'static;
 
+    /// Describe the stages of this transducer: their names, whether they keep
+    /// state, and whether they can end a run early.
+    ///
+    /// The built-in transducers describe themselves; others are reported by
+    /// their type name as a single stateless stage unless they override this.
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(short_type_name::<Self>()))
+    }
+
+    /// Render [`describe`](Transducer::describe) as text, like `EXPLAIN` for a
+    /// database query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::{Map, Take, Transducer};
+    ///
+    /// let pipeline = Map::new(|x: i32| x * 2).compose(Take::new(5));
+    /// assert!(pipeline.explain().contains("2. take(5)  [stateful, may stop]"));
+    /// ```
+    fn explain(&self) -> String {
+        self.describe().to_string()
+    }
+
+    /// Bounds on the number of elements this transducer emits for `input`
+    /// elements, in the form of [`Iterator::size_hint`].
+    ///
+    /// Collectors use the lower bound to preallocate their output. The
+    /// default, `(0, None)`, claims nothing; the built-in transducers narrow
+    /// it (a `Map` emits as many elements as it receives, a `Take` at most
+    /// its count). A wrong hint only affects how much is preallocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::{Filter, Map, RepeatEach, Take, Transducer};
+    ///
+    /// let pipeline = Map::new(|x: i32| x + 1).compose(RepeatEach::new(3));
+    /// assert_eq!(pipeline.size_hint((10, Some(10))), (30, Some(30)));
+    ///
+    /// let pipeline = Filter::new(|x: &i32| *x > 0).compose(Take::new(5));
+    /// assert_eq!(pipeline.size_hint((100, Some(100))), (0, Some(5)));
+    /// ```
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let _ = input;
+        (0, None)
+    }
+
+    /// Apply this transducer with each of its stages recording the elements
+    /// that reach it and leave it in `tracer`. Used by
+    /// [`debug_run`](crate::debug::debug_run).
+    ///
+    /// Composed transducers trace each of their stages; others are traced as
+    /// a single stage unless they override this.
+    fn apply_traced<Acc, R>(&self, reducer: R, tracer: &Tracer) -> Box<dyn Fn(Acc, In) -> Step<Acc>>
+    where
+        R: Fn(Acc, Out) -> Step<Acc> + 'static,
+        Acc: 'static,
+        In: 'static,
+        Out: 'static,
+    {
+        tracer.trace(self, reducer)
+    }
+
     /// Compose this transducer with another.
     ///
     /// Creates a new transducer that applies `self` first, then `other`.
@@ -105,6 +178,22 @@ impl<T: 'static> Transducer<T, T> for Identity<T> {
     {
         Box::new(reducer)
     }
+
+    fn describe(&self) -> Explain {
+        Explain::default()
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+
+    fn apply_traced<Acc, R>(&self, reducer: R, _tracer: &Tracer) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, T) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        Box::new(reducer)
+    }
 }
 
 /// Composition of two transducers.
@@ -137,6 +226,23 @@ where
         let r2 = self.second.apply(reducer);
         self.first.apply(r2)
     }
+
+    fn describe(&self) -> Explain {
+        self.first.describe().then(self.second.describe())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        self.second.size_hint(self.first.size_hint(input))
+    }
+
+    fn apply_traced<Acc, R>(&self, reducer: R, tracer: &Tracer) -> Box<dyn Fn(Acc, In) -> Step<Acc>>
+    where
+        R: Fn(Acc, Out) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let r2 = self.second.apply_traced(reducer, tracer);
+        self.first.apply_traced(r2, tracer)
+    }
 }
 
 #[cfg(test)]
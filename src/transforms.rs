@@ -2,13 +2,25 @@
 //!
 //! This module provides common transducers like map, filter, take, etc.
 
+use crate::explain::{Explain, StageInfo};
+#[cfg(feature = "std")]
+use crate::optics::{Lens, Traversal};
 use crate::step::{cont, stop, Step};
 use crate::transducer::Transducer;
-use std::cell::RefCell;
-use std::collections::{HashSet, VecDeque};
-use std::hash::Hash;
-use std::marker::PhantomData;
-use std::rc::Rc;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::cell::RefCell;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
 /// Map transducer - transforms each value with a function.
 ///
@@ -58,6 +70,14 @@ where
         let f = Rc::clone(&self.f);
         Box::new(move |acc, val| reducer(acc, f(val)))
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("map"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 /// Filter transducer - only passes values matching a predicate.
@@ -106,6 +126,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("filter"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
 }
 
 /// Reject transducer - inverse of Filter, only passes values NOT matching a predicate.
@@ -160,6 +188,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("reject"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
 }
 
 /// Chunk transducer - groups consecutive elements into fixed-size chunks.
@@ -183,7 +219,7 @@ where
 /// ```
 pub struct Chunk<T> {
     size: usize,
-    buffer: Rc<RefCell<Vec<T>>>,
+    _phantom: PhantomData<T>,
 }
 
 impl<T> Chunk<T>
@@ -194,7 +230,7 @@ where
         assert!(size > 0, "Chunk size must be greater than 0");
         Chunk {
             size,
-            buffer: Rc::new(RefCell::new(Vec::with_capacity(size))),
+            _phantom: PhantomData,
         }
     }
 }
@@ -210,7 +246,7 @@ where
         Acc: 'static,
     {
         let size = self.size;
-        let buffer = Rc::clone(&self.buffer);
+        let buffer = RefCell::new(Vec::with_capacity(size));
 
         Box::new(move |acc, val| {
             let mut buf = buffer.borrow_mut();
@@ -227,6 +263,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("chunk({})", self.size)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (input.0 / self.size, input.1.map(|n| n / self.size))
+    }
 }
 
 /// Take transducer - takes the first n elements, then stops.
@@ -242,7 +286,6 @@ where
 /// ```
 pub struct Take<T> {
     n: usize,
-    count: Rc<RefCell<usize>>,
     _phantom: PhantomData<T>,
 }
 
@@ -250,7 +293,6 @@ impl<T> Take<T> {
     pub fn new(n: usize) -> Self {
         Take {
             n,
-            count: Rc::new(RefCell::new(0)),
             _phantom: PhantomData,
         }
     }
@@ -264,7 +306,7 @@ impl<T: 'static> Transducer<T, T> for Take<T> {
         Acc: 'static,
     {
         let n = self.n;
-        let count = Rc::clone(&self.count);
+        let count = RefCell::new(0);
 
         Box::new(move |acc, val| {
             let mut c = count.borrow_mut();
@@ -284,6 +326,19 @@ impl<T: 'static> Transducer<T, T> for Take<T> {
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(
+            StageInfo::new(format!("take({})", self.n))
+                .stateful()
+                .may_stop(),
+        )
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let upper = input.1.map_or(self.n, |n| n.min(self.n));
+        (input.0.min(self.n), Some(upper))
+    }
 }
 
 /// TakeWhile transducer - takes elements while predicate is true, then stops.
@@ -332,6 +387,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("take_while").may_stop())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
 }
 
 /// Drop transducer - skips the first n elements.
@@ -345,7 +408,6 @@ where
 /// ```
 pub struct Drop<T> {
     n: usize,
-    count: Rc<RefCell<usize>>,
     _phantom: PhantomData<T>,
 }
 
@@ -353,7 +415,6 @@ impl<T> Drop<T> {
     pub fn new(n: usize) -> Self {
         Drop {
             n,
-            count: Rc::new(RefCell::new(0)),
             _phantom: PhantomData,
         }
     }
@@ -367,7 +428,7 @@ impl<T: 'static> Transducer<T, T> for Drop<T> {
         Acc: 'static,
     {
         let n = self.n;
-        let count = Rc::clone(&self.count);
+        let count = RefCell::new(0);
 
         Box::new(move |acc, val| {
             let mut c = count.borrow_mut();
@@ -379,6 +440,18 @@ impl<T: 'static> Transducer<T, T> for Drop<T> {
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("drop({})", self.n)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let n = self.n;
+        (
+            input.0.saturating_sub(n),
+            input.1.map(|u| u.saturating_sub(n)),
+        )
+    }
 }
 
 /// DropWhile transducer - skips elements while predicate is true.
@@ -392,7 +465,6 @@ impl<T: 'static> Transducer<T, T> for Drop<T> {
 /// ```
 pub struct DropWhile<P, T> {
     predicate: Rc<P>,
-    dropping: Rc<RefCell<bool>>,
     _phantom: PhantomData<T>,
 }
 
@@ -403,7 +475,6 @@ where
     pub fn new(predicate: P) -> Self {
         DropWhile {
             predicate: Rc::new(predicate),
-            dropping: Rc::new(RefCell::new(true)),
             _phantom: PhantomData,
         }
     }
@@ -421,7 +492,7 @@ where
         Acc: 'static,
     {
         let predicate = Rc::clone(&self.predicate);
-        let dropping = Rc::clone(&self.dropping);
+        let dropping = RefCell::new(true);
 
         Box::new(move |acc, val| {
             let mut d = dropping.borrow_mut();
@@ -433,6 +504,75 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("drop_while").stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
+}
+
+/// EveryNth transducer - keeps every nth element, starting with the first.
+///
+/// Useful for downsampling a stream: `EveryNth::new(10)` keeps elements 0,
+/// 10, 20, ...
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::transforms::EveryNth;
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let every_third = EveryNth::new(3);
+/// assert_eq!(to_vec(&every_third, 0..10), vec![0, 3, 6, 9]);
+/// ```
+pub struct EveryNth<T> {
+    n: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> EveryNth<T> {
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "EveryNth step must be greater than 0");
+        EveryNth {
+            n,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> Transducer<T, T> for EveryNth<T> {
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, T) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let n = self.n;
+        let index = RefCell::new(0);
+
+        Box::new(move |acc, val| {
+            let mut i = index.borrow_mut();
+            let keep = *i == 0;
+            *i = (*i + 1) % n;
+            if keep {
+                reducer(acc, val)
+            } else {
+                cont(acc)
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("every_nth({})", self.n)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let n = self.n;
+        (input.0.div_ceil(n), input.1.map(|u| u.div_ceil(n)))
+    }
 }
 
 /// Unique transducer - deduplicates consecutive equal elements.
@@ -445,13 +585,13 @@ where
 /// let unique = Unique::<i32>::new();
 /// ```
 pub struct Unique<T> {
-    last: Rc<RefCell<Option<T>>>,
+    _phantom: PhantomData<T>,
 }
 
 impl<T> Unique<T> {
     pub fn new() -> Self {
         Unique {
-            last: Rc::new(RefCell::new(None)),
+            _phantom: PhantomData,
         }
     }
 }
@@ -469,7 +609,7 @@ impl<T: PartialEq + Clone + 'static> Transducer<T, T> for Unique<T> {
         R: Fn(Acc, T) -> Step<Acc> + 'static,
         Acc: 'static,
     {
-        let last = Rc::clone(&self.last);
+        let last = RefCell::new(None::<T>);
 
         Box::new(move |acc, val| {
             let mut l = last.borrow_mut();
@@ -486,6 +626,14 @@ impl<T: PartialEq + Clone + 'static> Transducer<T, T> for Unique<T> {
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("unique").stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (input.0.min(1), input.1)
+    }
 }
 
 /// UniqueBy transducer - deduplicates by a key function.
@@ -497,12 +645,13 @@ impl<T: PartialEq + Clone + 'static> Transducer<T, T> for Unique<T> {
 ///
 /// let unique_by_abs = UniqueBy::new(|x: &i32| x.abs());
 /// ```
+#[cfg(feature = "std")]
 pub struct UniqueBy<F, T, K> {
     key_fn: Rc<F>,
-    seen: Rc<RefCell<HashSet<K>>>,
-    _phantom: PhantomData<T>,
+    _phantom: PhantomData<(T, K)>,
 }
 
+#[cfg(feature = "std")]
 impl<F, T, K> UniqueBy<F, T, K>
 where
     F: Fn(&T) -> K,
@@ -511,12 +660,12 @@ where
     pub fn new(key_fn: F) -> Self {
         UniqueBy {
             key_fn: Rc::new(key_fn),
-            seen: Rc::new(RefCell::new(HashSet::new())),
             _phantom: PhantomData,
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl<F, T, K> Transducer<T, T> for UniqueBy<F, T, K>
 where
     F: Fn(&T) -> K + 'static,
@@ -530,7 +679,7 @@ where
         Acc: 'static,
     {
         let key_fn = Rc::clone(&self.key_fn);
-        let seen = Rc::clone(&self.seen);
+        let seen = RefCell::new(HashSet::new());
 
         Box::new(move |acc, val| {
             let key = key_fn(&val);
@@ -542,6 +691,14 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("unique_by").stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (input.0.min(1), input.1)
+    }
 }
 
 /// Scan transducer - running accumulation (like reduce, but emits all intermediate values).
@@ -556,9 +713,7 @@ where
 /// ```
 pub struct Scan<F, T, S> {
     f: Rc<F>,
-    #[allow(dead_code)]
     initial: S,
-    state: Rc<RefCell<S>>,
     _phantom: PhantomData<T>,
 }
 
@@ -570,8 +725,7 @@ where
     pub fn new(initial: S, f: F) -> Self {
         Scan {
             f: Rc::new(f),
-            initial: initial.clone(),
-            state: Rc::new(RefCell::new(initial)),
+            initial,
             _phantom: PhantomData,
         }
     }
@@ -590,7 +744,7 @@ where
         Acc: 'static,
     {
         let f = Rc::clone(&self.f);
-        let state = Rc::clone(&self.state);
+        let state = RefCell::new(self.initial.clone());
 
         Box::new(move |acc, val| {
             let mut s = state.borrow_mut();
@@ -599,6 +753,14 @@ where
             reducer(acc, new_state)
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("scan").stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 /// FlatMap transducer - maps each element to a collection and flattens the result.
@@ -670,6 +832,10 @@ where
             cont(acc)
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("flat_map"))
+    }
 }
 
 /// Tap transducer - performs side effects without transforming values.
@@ -715,6 +881,14 @@ where
             reducer(acc, val)
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("tap"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
 }
 
 /// Interpose transducer - inserts a separator between elements.
@@ -744,7 +918,6 @@ where
 /// ```
 pub struct Interpose<T> {
     separator: T,
-    is_first: Rc<RefCell<bool>>,
 }
 
 impl<T> Interpose<T>
@@ -752,10 +925,7 @@ where
     T: Clone,
 {
     pub fn new(separator: T) -> Self {
-        Interpose {
-            separator,
-            is_first: Rc::new(RefCell::new(true)),
-        }
+        Interpose { separator }
     }
 }
 
@@ -770,7 +940,7 @@ where
         Acc: 'static,
     {
         let separator = self.separator.clone();
-        let is_first = Rc::clone(&self.is_first);
+        let is_first = RefCell::new(true);
 
         Box::new(move |acc, val| {
             let mut first = is_first.borrow_mut();
@@ -786,6 +956,22 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("interpose").stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        // A separator before every element but the first
+        let interposed = |n: usize| n.saturating_mul(2).saturating_sub(1);
+        (
+            interposed(input.0),
+            input
+                .1
+                .and_then(|n| n.checked_mul(2))
+                .map(|n| n.saturating_sub(1)),
+        )
+    }
 }
 
 /// RepeatEach transducer - repeats each element n times.
@@ -848,6 +1034,17 @@ where
             cont(acc)
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("repeat_each({})", self.n)))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (
+            input.0.saturating_mul(self.n),
+            input.1.and_then(|u| u.checked_mul(self.n)),
+        )
+    }
 }
 
 /// Aperture (Window) transducer - creates a sliding window of N elements.
@@ -893,7 +1090,7 @@ where
 /// ```
 pub struct Aperture<T> {
     size: usize,
-    buffer: Rc<RefCell<VecDeque<T>>>,
+    _phantom: PhantomData<T>,
 }
 
 impl<T> Aperture<T>
@@ -904,7 +1101,7 @@ where
         assert!(size > 0, "Aperture size must be greater than 0");
         Aperture {
             size,
-            buffer: Rc::new(RefCell::new(VecDeque::with_capacity(size))),
+            _phantom: PhantomData,
         }
     }
 }
@@ -920,7 +1117,7 @@ where
         Acc: 'static,
     {
         let size = self.size;
-        let buffer = Rc::clone(&self.buffer);
+        let buffer = RefCell::new(VecDeque::with_capacity(size));
 
         Box::new(move |acc, val| {
             let mut buf = buffer.borrow_mut();
@@ -937,6 +1134,512 @@ where
             }
         })
     }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("aperture({})", self.size)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let partial = self.size - 1;
+        (
+            input.0.saturating_sub(partial),
+            input.1.map(|n| n.saturating_sub(partial)),
+        )
+    }
+}
+
+/// SplitBytes transducer - splits a stream of byte chunks on a delimiter.
+///
+/// Segments may span chunks, so this suits data read in blocks from a file
+/// or socket. Delimiters are searched for with the vectorized
+/// [`find_byte`](crate::simd::bytes::find_byte), and are not included in the
+/// output.
+///
+/// Like `Chunk`, there is no completion phase: bytes after the last
+/// delimiter stay buffered and are not emitted, so end the input with a
+/// delimiter to get the final segment.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::transforms::SplitBytes;
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let lines = SplitBytes::new(b'\n');
+/// let chunks = vec![b"one\ntw".to_vec(), b"o\n\nthr".to_vec(), b"ee".to_vec()];
+/// let result = to_vec(&lines, chunks);
+/// assert_eq!(result, vec![b"one".to_vec(), b"two".to_vec(), b"".to_vec()]); // "three" is pending
+/// ```
+pub struct SplitBytes {
+    delimiter: u8,
+}
+
+impl SplitBytes {
+    pub fn new(delimiter: u8) -> Self {
+        SplitBytes { delimiter }
+    }
+}
+
+/// Position of the first `delimiter` in `bytes`, using the vectorized search
+/// in `simd` when std is available.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn find_byte(bytes: &[u8], delimiter: u8) -> Option<usize> {
+    crate::simd::bytes::find_byte(bytes, delimiter)
+}
+
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+fn find_byte(bytes: &[u8], delimiter: u8) -> Option<usize> {
+    bytes.iter().position(|&b| b == delimiter)
+}
+
+impl Transducer<Vec<u8>, Vec<u8>> for SplitBytes {
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, Vec<u8>) -> Step<Acc>>
+    where
+        R: Fn(Acc, Vec<u8>) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let delimiter = self.delimiter;
+        let pending = RefCell::new(Vec::new());
+
+        Box::new(move |mut acc, chunk| {
+            let mut pending = pending.borrow_mut();
+            let mut rest = chunk.as_slice();
+
+            while let Some(i) = find_byte(rest, delimiter) {
+                // The first segment completes whatever the last chunk left
+                pending.extend_from_slice(&rest[..i]);
+                rest = &rest[i + 1..];
+                match reducer(acc, core::mem::take(&mut *pending)) {
+                    Step::Continue(new_acc) => acc = new_acc,
+                    Step::Stop(final_acc) => return stop(final_acc),
+                }
+            }
+
+            pending.extend_from_slice(rest);
+            cont(acc)
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("split_bytes").stateful())
+    }
+}
+
+/// An element rejected by [`Validate`]: its position in the validated
+/// stream and the error built for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError<E> {
+    /// Zero-based position of the element among those `Validate` has seen
+    pub index: usize,
+    /// The error built from the failing element
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for ValidationError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "element {}: {}", self.index, self.error)
+    }
+}
+
+/// Validate transducer - tags elements that fail a predicate instead of
+/// dropping them.
+///
+/// Elements passing `predicate` are emitted as `Ok`; failing ones as `Err`
+/// with a [`ValidationError`] holding their position and the error `on_fail`
+/// builds from them. Downstream steps see every element, and
+/// [`partition_valid`](crate::collectors::partition_valid) splits the
+/// result into values and errors.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::transforms::{Validate, ValidationError};
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let non_negative = Validate::new(|x: &i32| *x >= 0, |x| format!("{} is negative", x));
+/// let result = to_vec(&non_negative, vec![3, -1, 4]);
+/// assert_eq!(
+///     result,
+///     vec![
+///         Ok(3),
+///         Err(ValidationError { index: 1, error: "-1 is negative".to_string() }),
+///         Ok(4),
+///     ]
+/// );
+/// ```
+pub struct Validate<P, F, T, E> {
+    predicate: Rc<P>,
+    on_fail: Rc<F>,
+    _phantom: PhantomData<(T, E)>,
+}
+
+impl<P, F, T, E> Validate<P, F, T, E>
+where
+    P: Fn(&T) -> bool,
+    F: Fn(T) -> E,
+{
+    pub fn new(predicate: P, on_fail: F) -> Self {
+        Validate {
+            predicate: Rc::new(predicate),
+            on_fail: Rc::new(on_fail),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, F, T, E> Transducer<T, Result<T, ValidationError<E>>> for Validate<P, F, T, E>
+where
+    P: Fn(&T) -> bool + 'static,
+    F: Fn(T) -> E + 'static,
+    T: 'static,
+    E: 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, Result<T, ValidationError<E>>) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let predicate = Rc::clone(&self.predicate);
+        let on_fail = Rc::clone(&self.on_fail);
+        let index = RefCell::new(0);
+
+        Box::new(move |acc, val| {
+            let i = {
+                let mut next = index.borrow_mut();
+                *next += 1;
+                *next - 1
+            };
+            if predicate(&val) {
+                reducer(acc, Ok(val))
+            } else {
+                let error = on_fail(val);
+                reducer(acc, Err(ValidationError { index: i, error }))
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("validate"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// MapOver transducer - updates the part of each value focused by a lens.
+///
+/// Equivalent to `Map::new(move |s| lens.over(&s, &f))`, without the
+/// hand-written closure.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::Lens;
+/// use orlando_transducers::transforms::MapOver;
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let first = Lens::new(|p: &(String, u32)| p.0.clone(), |p: &(String, u32), s| (s, p.1));
+/// let shout = MapOver::new(first, |s: String| s.to_uppercase());
+/// let result = to_vec(&shout, vec![("ann".to_string(), 1), ("bo".to_string(), 2)]);
+/// assert_eq!(result, vec![("ANN".to_string(), 1), ("BO".to_string(), 2)]);
+/// ```
+#[cfg(feature = "std")]
+pub struct MapOver<F, S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    lens: Rc<Lens<S, A>>,
+    f: Rc<F>,
+}
+
+#[cfg(feature = "std")]
+impl<F, S, A> MapOver<F, S, A>
+where
+    F: Fn(A) -> A,
+    S: Clone,
+    A: Clone,
+{
+    pub fn new(lens: Lens<S, A>, f: F) -> Self {
+        MapOver {
+            lens: Rc::new(lens),
+            f: Rc::new(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F, S, A> Transducer<S, S> for MapOver<F, S, A>
+where
+    F: Fn(A) -> A + 'static,
+    S: Clone + 'static,
+    A: Clone + 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, S) -> Step<Acc>>
+    where
+        R: Fn(Acc, S) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let lens = Rc::clone(&self.lens);
+        let f = Rc::clone(&self.f);
+        Box::new(move |acc, val| reducer(acc, lens.over(&val, |a| f(a))))
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("map_over"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// FilterBy transducer - keeps values whose lens focus satisfies a predicate.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::Lens;
+/// use orlando_transducers::transforms::FilterBy;
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let age = Lens::new(|p: &(&str, u32)| p.1, |p: &(&str, u32), a| (p.0, a));
+/// let adults = FilterBy::new(age, |a: &u32| *a >= 18);
+/// let result = to_vec(&adults, vec![("ann", 34), ("bo", 12)]);
+/// assert_eq!(result, vec![("ann", 34)]);
+/// ```
+#[cfg(feature = "std")]
+pub struct FilterBy<P, S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    lens: Rc<Lens<S, A>>,
+    predicate: Rc<P>,
+}
+
+#[cfg(feature = "std")]
+impl<P, S, A> FilterBy<P, S, A>
+where
+    P: Fn(&A) -> bool,
+    S: Clone,
+    A: Clone,
+{
+    pub fn new(lens: Lens<S, A>, predicate: P) -> Self {
+        FilterBy {
+            lens: Rc::new(lens),
+            predicate: Rc::new(predicate),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P, S, A> Transducer<S, S> for FilterBy<P, S, A>
+where
+    P: Fn(&A) -> bool + 'static,
+    S: Clone + 'static,
+    A: Clone + 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, S) -> Step<Acc>>
+    where
+        R: Fn(Acc, S) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let lens = Rc::clone(&self.lens);
+        let predicate = Rc::clone(&self.predicate);
+        Box::new(move |acc, val| {
+            if predicate(&lens.get(&val)) {
+                reducer(acc, val)
+            } else {
+                cont(acc)
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("filter_by"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
+}
+
+/// Zoom transducer - runs a transducer over the elements a traversal focuses
+/// on within each value, and writes the results back.
+///
+/// Takes a function that builds the inner transducer, which is called and
+/// run once per value, so stateful transducers such as `Take`, `Unique` or
+/// `Scan` treat each record's foci independently.
+///
+/// Foci are fed through the inner transducer one at a time, and each is
+/// replaced by the first value it produces. A focus that produces nothing,
+/// because it was filtered out or the transducer stopped before it, keeps
+/// its value; further values it produces are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::optics::Traversal;
+/// use orlando_transducers::transforms::{Map, Zoom};
+/// use orlando_transducers::collectors::to_vec;
+///
+/// let tags: Traversal<Vec<String>, String> = Traversal::each();
+/// let normalize = Zoom::new(tags, || Map::new(|t: String| t.trim().to_lowercase()));
+/// let result = to_vec(&normalize, vec![vec![" Rust".to_string(), "WASM ".to_string()]]);
+/// assert_eq!(result, vec![vec!["rust", "wasm"]]);
+/// ```
+#[cfg(feature = "std")]
+pub struct Zoom<F, S, A>
+where
+    S: Clone,
+    A: Clone,
+{
+    traversal: Traversal<S, A>,
+    make: Rc<F>,
+}
+
+#[cfg(feature = "std")]
+impl<F, T, S, A> Zoom<F, S, A>
+where
+    F: Fn() -> T,
+    T: Transducer<A, A>,
+    S: Clone,
+    A: Clone,
+{
+    pub fn new(traversal: Traversal<S, A>, make: F) -> Self {
+        Zoom {
+            traversal,
+            make: Rc::new(make),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F, T, S, A> Transducer<S, S> for Zoom<F, S, A>
+where
+    F: Fn() -> T + 'static,
+    T: Transducer<A, A>,
+    S: Clone + 'static,
+    A: Clone + 'static,
+{
+    #[inline(always)]
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, S) -> Step<Acc>>
+    where
+        R: Fn(Acc, S) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let traversal = self.traversal.clone();
+        let make = Rc::clone(&self.make);
+        Box::new(move |acc, val| {
+            let emitted = Rc::new(RefCell::new(Vec::new()));
+            let sink = Rc::clone(&emitted);
+            let step = make().apply(move |(), a: A| {
+                sink.borrow_mut().push(a);
+                cont(())
+            });
+            let stopped = Cell::new(false);
+            let updated = traversal.over_all(&val, |a| {
+                if stopped.get() {
+                    return a;
+                }
+                stopped.set(step((), a.clone()).is_stop());
+                let first = emitted.borrow_mut().drain(..).next();
+                first.unwrap_or(a)
+            });
+            reducer(acc, updated)
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new("zoom"))
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        input
+    }
+}
+
+/// Serde support for the data-only transforms.
+///
+/// Only the configuration is serialized (`{"n": 5}` for `Take`, `Drop` and
+/// `EveryNth`, `{"size": 3}` for `Chunk` and `Aperture`); a deserialized
+/// transform starts with fresh state. Sizes are validated as by the
+/// constructors, so a zero chunk size is a deserialization error rather than
+/// a panic.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{Aperture, Chunk, Drop, EveryNth, Take};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Count {
+        n: usize,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Size {
+        size: usize,
+    }
+
+    fn positive<E: serde::de::Error>(what: &str, value: usize) -> Result<usize, E> {
+        if value == 0 {
+            Err(E::custom(format_args!("{} must be greater than 0", what)))
+        } else {
+            Ok(value)
+        }
+    }
+
+    macro_rules! count_config {
+        ($name:ident $(, $what:literal)?) => {
+            impl<T> Serialize for $name<T> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Count { n: self.n }.serialize(serializer)
+                }
+            }
+
+            impl<'de, T> Deserialize<'de> for $name<T> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let Count { n } = Count::deserialize(deserializer)?;
+                    $(let n = positive::<D::Error>($what, n)?;)?
+                    Ok($name::new(n))
+                }
+            }
+        };
+    }
+
+    count_config!(Take);
+    count_config!(Drop);
+    count_config!(EveryNth, "EveryNth step");
+
+    macro_rules! size_config {
+        ($name:ident, $what:literal) => {
+            impl<T> Serialize for $name<T> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Size { size: self.size }.serialize(serializer)
+                }
+            }
+
+            impl<'de, T: Clone> Deserialize<'de> for $name<T> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let Size { size } = Size::deserialize(deserializer)?;
+                    Ok($name::new(positive::<D::Error>($what, size)?))
+                }
+            }
+        };
+    }
+
+    size_config!(Chunk, "Chunk size");
+    size_config!(Aperture, "Aperture size");
 }
 
 #[cfg(test)]
@@ -1073,6 +1776,68 @@ mod tests {
         assert!(r2.is_stop()); // Should stop after 2 elements
     }
 
+    #[test]
+    fn test_reused_pipeline_starts_afresh() {
+        use crate::collectors::to_vec;
+
+        let pipeline = Scan::new(0, |acc: &i32, x: &i32| acc + x)
+            .compose(Unique::new())
+            .compose(Chunk::new(2))
+            .compose(Take::new(2));
+        let first = to_vec(&pipeline, vec![1, 0, 2, 3, 4, 5]);
+        assert_eq!(first, vec![vec![1, 3], vec![6, 10]]);
+        assert_eq!(to_vec(&pipeline, vec![1, 0, 2, 3, 4, 5]), first);
+
+        let stateful = Drop::new(1)
+            .compose(DropWhile::new(|x: &i32| *x < 3))
+            .compose(EveryNth::new(2))
+            .compose(Interpose::new(0))
+            .compose(Aperture::new(2));
+        let first = to_vec(&stateful, 0..8);
+        assert_eq!(first, vec![vec![3, 0], vec![0, 5], vec![5, 0], vec![0, 7]]);
+        assert_eq!(to_vec(&stateful, 0..8), first);
+    }
+
+    #[test]
+    fn test_size_hints_bound_output() {
+        use crate::collectors::to_vec;
+
+        fn check<U: 'static>(t: &impl Transducer<i32, U>, len: usize) {
+            let (lower, upper) = t.size_hint((len, Some(len)));
+            let actual = to_vec(t, 0..len as i32).len();
+            assert!(lower <= actual && upper.is_none_or(|u| actual <= u));
+        }
+
+        for len in [0, 1, 7, 12] {
+            check(&Map::new(|x: i32| x), len);
+            check(&Filter::new(|x: &i32| x % 3 == 0), len);
+            check(&Chunk::new(5), len);
+            check(&Take::new(4), len);
+            check(&Drop::new(4), len);
+            check(&EveryNth::new(3), len);
+            check(&Interpose::new(-1), len);
+            check(&RepeatEach::new(2), len);
+            check(&Aperture::new(3), len);
+            check(&Unique::new(), len);
+        }
+
+        assert_eq!(Map::new(|x: i32| x).size_hint((7, Some(7))), (7, Some(7)));
+        assert_eq!(Chunk::<i32>::new(5).size_hint((12, Some(12))), (2, Some(2)));
+        assert_eq!(
+            EveryNth::<i32>::new(3).size_hint((7, Some(7))),
+            (3, Some(3))
+        );
+        assert_eq!(Interpose::new(0).size_hint((4, Some(4))), (7, Some(7)));
+        assert_eq!(
+            Take::<i32>::new(4).size_hint((usize::MAX, None)),
+            (4, Some(4))
+        );
+        assert_eq!(
+            FlatMap::new(|x: i32| vec![x]).size_hint((3, Some(3))),
+            (0, None)
+        );
+    }
+
     #[test]
     fn test_flatmap() {
         use crate::collectors::to_vec;
@@ -1252,4 +2017,198 @@ mod tests {
         assert_eq!(result[0], vec![1, 2, 3, 4, 5]);
         assert_eq!(result[95], vec![96, 97, 98, 99, 100]);
     }
+
+    #[test]
+    fn test_split_bytes_spans_chunks_and_stops_early() {
+        use crate::collectors::to_vec;
+
+        let long = "x".repeat(100);
+        let text = format!("a\n{}\nb\nc\n", long);
+        let chunks: Vec<Vec<u8>> = text.as_bytes().chunks(7).map(<[u8]>::to_vec).collect();
+        let result = to_vec(&SplitBytes::new(b'\n'), chunks.clone());
+        assert_eq!(
+            result,
+            vec![
+                b"a".to_vec(),
+                long.into_bytes(),
+                b"b".to_vec(),
+                b"c".to_vec()
+            ]
+        );
+
+        let first_two = SplitBytes::new(b'\n').compose(Take::new(2));
+        assert_eq!(to_vec(&first_two, chunks).len(), 2);
+    }
+
+    #[test]
+    fn test_validate_indexes_failures_after_upstream_filter() {
+        use crate::collectors::to_vec;
+
+        let pipeline = Filter::new(|x: &i32| *x != 0)
+            .compose(Validate::new(|x: &i32| x % 2 == 0, |x| x))
+            .compose(Map::new(|r: Result<i32, ValidationError<i32>>| {
+                r.map(|x| x / 2)
+            }));
+        let result = to_vec(&pipeline, vec![0, 4, 3, 0, 5, 8]);
+
+        assert_eq!(
+            result,
+            vec![
+                Ok(2),
+                Err(ValidationError { index: 1, error: 3 }),
+                Err(ValidationError { index: 2, error: 5 }),
+                Ok(4),
+            ]
+        );
+        assert_eq!(result[1].clone().unwrap_err().to_string(), "element 1: 3");
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Order {
+        customer: String,
+        total: f64,
+    }
+
+    fn total_lens() -> Lens<Order, f64> {
+        Lens::new(
+            |o: &Order| o.total,
+            |o: &Order, total| Order { total, ..o.clone() },
+        )
+    }
+
+    #[test]
+    fn test_map_over_and_filter_by() {
+        use crate::collectors::to_vec;
+
+        let orders = vec![
+            Order {
+                customer: "ann".into(),
+                total: 120.0,
+            },
+            Order {
+                customer: "bo".into(),
+                total: 40.0,
+            },
+        ];
+        let pipeline = FilterBy::new(total_lens(), |t: &f64| *t > 100.0)
+            .compose(MapOver::new(total_lens(), |t| t * 0.9));
+
+        let result = to_vec(&pipeline, orders);
+        assert_eq!(
+            result,
+            vec![Order {
+                customer: "ann".into(),
+                total: 108.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_zoom_runs_pipeline_per_value() {
+        use crate::collectors::to_vec;
+
+        let each_line = Traversal::<Vec<f64>, f64>::each();
+        // Running totals restart for every order
+        let running = Zoom::new(each_line, || Scan::new(0.0, |acc: &f64, x: &f64| acc + x));
+        let orders = vec![vec![1.0, 2.0, 3.0], vec![10.0, 5.0]];
+        assert_eq!(
+            to_vec(&running, orders),
+            vec![vec![1.0, 3.0, 6.0], vec![10.0, 15.0]]
+        );
+
+        // Fewer results than foci leave the rest in place
+        let first_two = Zoom::new(Traversal::each(), || {
+            Map::new(|x: i32| x * 100).compose(Take::new(2))
+        });
+        assert_eq!(
+            to_vec(&first_two, vec![vec![1, 2, 3], vec![4]]),
+            vec![vec![100, 200, 3], vec![400]]
+        );
+    }
+
+    #[test]
+    fn test_zoom_keeps_filtered_foci_in_place() {
+        use crate::collectors::to_vec;
+
+        let positive = Zoom::new(Traversal::each(), || Filter::new(|x: &i32| *x > 0));
+        assert_eq!(
+            to_vec(&positive, vec![vec![-1, 2, 3]]),
+            vec![vec![-1, 2, 3]]
+        );
+
+        let scale_positive = Zoom::new(Traversal::each(), || {
+            Filter::new(|x: &i32| *x > 0).compose(Map::new(|x: i32| x * 10))
+        });
+        assert_eq!(
+            to_vec(&scale_positive, vec![vec![-1, 2, -3, 4]]),
+            vec![vec![-1, 20, -3, 40]]
+        );
+    }
+
+    #[test]
+    fn test_every_nth() {
+        use crate::collectors::to_vec;
+
+        let every_other = EveryNth::new(2).compose(Take::new(3));
+        assert_eq!(to_vec(&every_other, 1..), vec![1, 3, 5]);
+        assert_eq!(to_vec(&EveryNth::new(1), vec!['a', 'b']), vec!['a', 'b']);
+    }
+
+    #[test]
+    #[should_panic(expected = "EveryNth step must be greater than 0")]
+    fn test_every_nth_zero_panics() {
+        EveryNth::<i32>::new(0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_configs() {
+        use crate::collectors::to_vec;
+
+        #[derive(serde::Deserialize)]
+        struct Config {
+            skip: Drop<i32>,
+            limit: Take<i32>,
+            batch: Chunk<i32>,
+        }
+
+        let config: Config = serde_json::from_str(
+            r#"{ "skip": { "n": 1 }, "limit": { "n": 5 }, "batch": { "size": 2 } }"#,
+        )
+        .unwrap();
+        let pipeline = config.skip.compose(config.limit).compose(config.batch);
+        assert_eq!(to_vec(&pipeline, 0..), vec![vec![1, 2], vec![3, 4]]);
+
+        assert_eq!(
+            serde_json::to_string(&Aperture::<u8>::new(3)).unwrap(),
+            r#"{"size":3}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&EveryNth::<u8>::new(4)).unwrap(),
+            r#"{"n":4}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates_sizes() {
+        let err = serde_json::from_str::<Chunk<i32>>(r#"{ "size": 0 }"#)
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("Chunk size must be greater than 0"));
+        assert!(serde_json::from_str::<EveryNth<i32>>(r#"{ "n": 0 }"#).is_err());
+        assert!(serde_json::from_str::<Take<i32>>(r#"{ "count": 3 }"#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_fresh_state() {
+        use crate::collectors::to_vec;
+        let take = Take::<i32>::new(2);
+        assert_eq!(to_vec(&take, 0..10), vec![0, 1]);
+        let copy: Take<i32> = serde_json::from_str(&serde_json::to_string(&take).unwrap()).unwrap();
+        assert_eq!(to_vec(&copy, 0..10), vec![0, 1]);
+    }
 }
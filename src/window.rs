@@ -0,0 +1,793 @@
+//! # Window: Grouping by event time
+//!
+//! [`WindowByTime`] groups elements into windows of event time, read from
+//! each element with a timestamp function, for metrics over "the last
+//! minute" rather than "the last 100 elements":
+//!
+//! - [`WindowByTime::tumbling`] windows are back to back, so each element
+//!   falls in exactly one: `[0, 60)`, `[60, 120)`, ...
+//! - [`WindowByTime::sliding`] windows start every `slide` and last
+//!   `duration`, so they overlap when the slide is shorter and an element
+//!   falls in each window covering its timestamp.
+//!
+//! [`SessionWindow`] instead groups elements into sessions of activity,
+//! ending a session after a gap with no elements.
+//!
+//! Windows start at multiples of the slide, counting from timestamp 0.
+//! Timestamps and durations are plain `u64`s in whatever unit the source
+//! uses (seconds, milliseconds since the epoch, ...).
+//!
+//! ```rust
+//! use orlando_transducers::window::{Window, WindowByTime};
+//! use orlando_transducers::to_vec;
+//!
+//! // (timestamp in seconds, value)
+//! let readings = vec![(1, 'a'), (7, 'b'), (12, 'c'), (25, 'd')];
+//! let windows = WindowByTime::tumbling(|r: &(u64, char)| r.0, 10);
+//!
+//! assert_eq!(
+//!     to_vec(&windows, readings),
+//!     vec![
+//!         (Window::new(0, 10), vec![(1, 'a'), (7, 'b')]),
+//!         (Window::new(10, 20), vec![(12, 'c')]),
+//!     ]
+//! );
+//! ```
+//!
+//! A window is emitted once an element at or past its end arrives (later
+//! with [lateness](#watermarks-and-late-elements) allowed), so event time
+//! only moves forward with the input. As with `Chunk`, there is
+//! no completion phase: the windows still open when the input ends (here
+//! `[20, 30)`) are not emitted. End the input with an element past the last
+//! window to flush them. Windows that no element fell in are skipped.
+//!
+//! With [`WindowByTime::aggregate`], each window is reduced by an
+//! [`Aggregator`] as elements arrive instead of being collected, so a
+//! window holds one accumulator rather than its elements:
+//!
+//! ```rust
+//! use orlando_transducers::aggregate;
+//! use orlando_transducers::window::{Window, WindowByTime};
+//! use orlando_transducers::to_vec;
+//!
+//! let latencies = vec![(0, 120), (20, 80), (40, 300), (70, 90), (95, 0)];
+//! let per_minute = WindowByTime::sliding(|r: &(u64, u32)| r.0, 60, 30)
+//!     .aggregate(aggregate::count());
+//!
+//! assert_eq!(
+//!     to_vec(&per_minute, latencies),
+//!     vec![(Window::new(0, 60), 3), (Window::new(30, 90), 2)]
+//! );
+//! ```
+//!
+//! ## Watermarks and late elements
+//!
+//! Elements may arrive out of order. Event time is tracked as a
+//! *watermark*: the latest timestamp seen, less the
+//! [allowed lateness](WindowByTime::allowed_lateness) (zero by default). A
+//! window is emitted once the watermark reaches its end, so an element up to
+//! the allowed lateness behind the latest still joins its windows. An
+//! element whose windows have all been emitted is *late*, and the
+//! [`LatePolicy`] decides whether it is dropped or merged into the windows
+//! still open; [`WindowByTime::late_output`] emits dropped elements
+//! alongside the windows instead.
+
+use crate::aggregate::{Aggregator, ToVec};
+use crate::either::Either;
+use crate::explain::{Explain, StageInfo};
+use crate::step::{cont, Step};
+use crate::transducer::Transducer;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+
+/// A half-open interval of event time, `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Window {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Window {
+    pub fn new(start: u64, end: u64) -> Self {
+        Window { start, end }
+    }
+
+    /// Whether `timestamp` falls in the window.
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.start <= timestamp && timestamp < self.end
+    }
+}
+
+/// Event-time windowing transducer, emitting `(Window, A::Output)` for each
+/// window as it closes. See the [module documentation](self).
+///
+/// By default each window's output is a `Vec` of its elements.
+pub struct WindowByTime<F, T, A = ToVec> {
+    extract_ts: Rc<F>,
+    spec: Spec,
+    aggregator: Rc<A>,
+    _phantom: PhantomData<T>,
+}
+
+/// What happens to an element that arrives after all of its windows have
+/// closed. See [`WindowByTime::on_late`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LatePolicy {
+    /// Discard it
+    #[default]
+    Drop,
+    /// Add it to the windows still open, as if it had arrived with the
+    /// watermark as its timestamp
+    Merge,
+}
+
+/// The shape of the windows, and how long they wait for late elements.
+#[derive(Clone, Copy)]
+struct Spec {
+    duration: u64,
+    slide: u64,
+    lateness: u64,
+    policy: LatePolicy,
+}
+
+impl<F, T> WindowByTime<F, T>
+where
+    F: Fn(&T) -> u64,
+{
+    /// Back-to-back windows of `duration`.
+    pub fn tumbling(extract_ts: F, duration: u64) -> Self {
+        Self::sliding(extract_ts, duration, duration)
+    }
+
+    /// Windows of `duration` starting every `slide`.
+    ///
+    /// A slide longer than the duration leaves gaps between windows, and
+    /// elements in a gap belong to no window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::window::{Window, WindowByTime};
+    /// use orlando_transducers::to_vec;
+    ///
+    /// let windows = WindowByTime::sliding(|t: &u64| *t, 4, 2);
+    /// assert_eq!(
+    ///     to_vec(&windows, vec![1, 3, 5, 9]),
+    ///     vec![
+    ///         (Window::new(0, 4), vec![1, 3]),
+    ///         (Window::new(2, 6), vec![3, 5]),
+    ///         (Window::new(4, 8), vec![5]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn sliding(extract_ts: F, duration: u64, slide: u64) -> Self {
+        assert!(duration > 0, "Window duration must be greater than 0");
+        assert!(slide > 0, "Window slide must be greater than 0");
+        WindowByTime {
+            extract_ts: Rc::new(extract_ts),
+            spec: Spec {
+                duration,
+                slide,
+                lateness: 0,
+                policy: LatePolicy::Drop,
+            },
+            aggregator: Rc::new(ToVec),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, T, A> WindowByTime<F, T, A> {
+    /// Reduce each window with `aggregator` instead of collecting it.
+    pub fn aggregate<B: Aggregator<T>>(self, aggregator: B) -> WindowByTime<F, T, B> {
+        WindowByTime {
+            extract_ts: self.extract_ts,
+            spec: self.spec,
+            aggregator: Rc::new(aggregator),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Keep windows open until the watermark, `lateness` behind the latest
+    /// timestamp seen, passes their end.
+    ///
+    /// Elements up to `lateness` out of order then still land in their
+    /// windows, at the cost of emitting each window that much later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::window::{Window, WindowByTime};
+    /// use orlando_transducers::to_vec;
+    ///
+    /// let events = vec![1, 12, 8, 15, 23, 31];
+    ///
+    /// // 12 closes [0, 10) before 8 arrives
+    /// let strict = WindowByTime::tumbling(|t: &u64| *t, 10);
+    /// assert_eq!(to_vec(&strict, events.clone())[0], (Window::new(0, 10), vec![1]));
+    ///
+    /// // With 5 of lateness, [0, 10) waits for the watermark to reach 10 (at 15)
+    /// let tolerant = WindowByTime::tumbling(|t: &u64| *t, 10).allowed_lateness(5);
+    /// assert_eq!(to_vec(&tolerant, events)[0], (Window::new(0, 10), vec![1, 8]));
+    /// ```
+    pub fn allowed_lateness(mut self, lateness: u64) -> Self {
+        self.spec.lateness = lateness;
+        self
+    }
+
+    /// What to do with elements whose windows have all closed. They are
+    /// dropped by default; see [`late_output`](Self::late_output) to emit
+    /// them instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::window::{LatePolicy, Window, WindowByTime};
+    /// use orlando_transducers::to_vec;
+    ///
+    /// // 3 is late for [0, 10), and counted in [10, 20) instead
+    /// let windows = WindowByTime::tumbling(|t: &u64| *t, 10).on_late(LatePolicy::Merge);
+    /// assert_eq!(
+    ///     to_vec(&windows, vec![2, 11, 3, 25]),
+    ///     vec![(Window::new(0, 10), vec![2]), (Window::new(10, 20), vec![11, 3])]
+    /// );
+    /// ```
+    pub fn on_late(mut self, policy: LatePolicy) -> Self {
+        self.spec.policy = policy;
+        self
+    }
+
+    /// Emit late elements too, as `Either::Right` next to the windows as
+    /// `Either::Left`, for a pipeline to count, log or reprocess them.
+    ///
+    /// Elements kept by [`LatePolicy::Merge`] go into their windows as
+    /// before, so only the policy's drops are emitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orlando_transducers::window::{Window, WindowByTime};
+    /// use orlando_transducers::partition_map;
+    ///
+    /// let windows = WindowByTime::tumbling(|t: &u64| *t, 10).late_output();
+    /// let (closed, late) = partition_map(&windows, vec![2, 11, 3, 25], |e| e);
+    /// assert_eq!(closed, vec![(Window::new(0, 10), vec![2]), (Window::new(10, 20), vec![11])]);
+    /// assert_eq!(late, vec![3]);
+    /// ```
+    pub fn late_output(self) -> LateOutput<F, T, A> {
+        LateOutput { windows: self }
+    }
+
+    /// Apply the windowing, with `window` building the output for a closed
+    /// window and `late` the output for a dropped late element, if any.
+    fn apply_with<O, Acc, R>(
+        &self,
+        window: fn((Window, A::Output)) -> O,
+        late: Option<fn(T) -> O>,
+        reducer: R,
+    ) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        F: Fn(&T) -> u64 + 'static,
+        T: 'static,
+        A: Aggregator<T> + 'static,
+        A::State: 'static,
+        O: 'static,
+        R: Fn(Acc, O) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let extract_ts = Rc::clone(&self.extract_ts);
+        let aggregator = Rc::clone(&self.aggregator);
+        let spec = self.spec;
+        let windows = RefCell::new(Windows {
+            open: BTreeMap::new(),
+            latest: None,
+        });
+
+        Box::new(move |mut acc, val| {
+            let ts = extract_ts(&val);
+            let (closed, dropped) = windows.borrow_mut().push(spec, &*aggregator, ts, val);
+
+            if let (Some(late), Some(val)) = (late, dropped) {
+                match reducer(acc, late(val)) {
+                    Step::Continue(next) => acc = next,
+                    stopped => return stopped,
+                }
+            }
+            for (start, state) in closed {
+                let closed = Window::new(start, start.saturating_add(spec.duration));
+                match reducer(acc, window((closed, aggregator.finish(state)))) {
+                    Step::Continue(next) => acc = next,
+                    stopped => return stopped,
+                }
+            }
+            cont(acc)
+        })
+    }
+
+    fn stage(&self, name: &str) -> Explain {
+        let Spec {
+            duration,
+            slide,
+            lateness,
+            ..
+        } = self.spec;
+        let name = if lateness > 0 {
+            format!("{}({}, {}, lateness {})", name, duration, slide, lateness)
+        } else {
+            format!("{}({}, {})", name, duration, slide)
+        };
+        Explain::stage(StageInfo::new(name).stateful())
+    }
+
+    fn windows_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        // An element can open one window for each slide its duration spans
+        let per_element = self.spec.duration.div_ceil(self.spec.slide) as usize;
+        (0, input.1.map(|n| n.saturating_mul(per_element)))
+    }
+}
+
+/// The open windows of one run, by start time.
+struct Windows<S> {
+    open: BTreeMap<u64, S>,
+    /// The latest timestamp seen
+    latest: Option<u64>,
+}
+
+impl<S> Windows<S> {
+    /// Add an element at `ts` to its open windows. Returns the windows that
+    /// closed, and the element back if it was late and not merged.
+    fn push<T, A>(
+        &mut self,
+        spec: Spec,
+        aggregator: &A,
+        ts: u64,
+        val: T,
+    ) -> (BTreeMap<u64, S>, Option<T>)
+    where
+        A: Aggregator<T, State = S>,
+    {
+        let latest = self.latest.map_or(ts, |latest| latest.max(ts));
+        self.latest = Some(latest);
+        // Windows ending at or before the watermark are closed
+        let watermark = latest.saturating_sub(spec.lateness);
+
+        let newest = ts - ts % spec.slide;
+        let newest_end = newest.saturating_add(spec.duration);
+        let at = if newest_end > ts && newest_end <= watermark {
+            match spec.policy {
+                LatePolicy::Drop => return (BTreeMap::new(), Some(val)),
+                LatePolicy::Merge => watermark,
+            }
+        } else {
+            ts
+        };
+
+        // Every window covering `at` that hasn't closed, latest first
+        let mut start = at - at % spec.slide;
+        while start.saturating_add(spec.duration) > watermark.max(at) {
+            let state = match self.open.remove(&start) {
+                Some(state) => state,
+                None => aggregator.init(),
+            };
+            self.open.insert(start, aggregator.step(state, &val));
+            match start.checked_sub(spec.slide) {
+                Some(earlier) => start = earlier,
+                None => break,
+            }
+        }
+
+        // Windows with `start + duration <= watermark`
+        let closed = match watermark.checked_sub(spec.duration) {
+            Some(last_closed) => {
+                let open = self.open.split_off(&(last_closed + 1));
+                core::mem::replace(&mut self.open, open)
+            }
+            None => BTreeMap::new(),
+        };
+        (closed, None)
+    }
+}
+
+impl<F, T, A> Transducer<T, (Window, A::Output)> for WindowByTime<F, T, A>
+where
+    F: Fn(&T) -> u64 + 'static,
+    T: 'static,
+    A: Aggregator<T> + 'static,
+    A::State: 'static,
+    A::Output: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, (Window, A::Output)) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        self.apply_with(|closed| closed, None, reducer)
+    }
+
+    fn describe(&self) -> Explain {
+        self.stage("window_by_time")
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        self.windows_hint(input)
+    }
+}
+
+/// [`WindowByTime`] that also emits the late elements it drops. See
+/// [`WindowByTime::late_output`].
+pub struct LateOutput<F, T, A = ToVec> {
+    windows: WindowByTime<F, T, A>,
+}
+
+impl<F, T, A> Transducer<T, Either<(Window, A::Output), T>> for LateOutput<F, T, A>
+where
+    F: Fn(&T) -> u64 + 'static,
+    T: 'static,
+    A: Aggregator<T> + 'static,
+    A::State: 'static,
+    A::Output: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, Either<(Window, A::Output), T>) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        self.windows
+            .apply_with(Either::Left, Some(Either::Right), reducer)
+    }
+
+    fn describe(&self) -> Explain {
+        self.windows.stage("window_by_time_late_output")
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        // A late element takes the place of the windows it would have joined
+        self.windows.windows_hint(input)
+    }
+}
+
+/// Session windowing transducer: groups elements into sessions of activity
+/// separated by gaps of at least `gap` with no elements, emitting
+/// `(Window, A::Output)` for each session as it closes.
+///
+/// A session's window runs from its first timestamp to `gap` past its last,
+/// and it closes when an element arrives at or after that end. As with
+/// [`WindowByTime`], there is no completion phase, so the session still
+/// open when the input ends is not emitted.
+///
+/// Elements may arrive out of order within a session, and one up to `gap`
+/// before the session's start extends it backwards. Older elements belong to
+/// a session already emitted and are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use orlando_transducers::window::{SessionWindow, Window};
+/// use orlando_transducers::{aggregate, to_vec};
+///
+/// // (seconds, page) for one visitor
+/// let clicks = vec![(0, "home"), (20, "search"), (50, "item"), (400, "home"), (410, "cart"), (2000, "home")];
+/// let sessions = SessionWindow::new(|c: &(u64, &str)| c.0, 300).aggregate(aggregate::count());
+///
+/// assert_eq!(
+///     to_vec(&sessions, clicks),
+///     vec![(Window::new(0, 350), 3), (Window::new(400, 710), 2)]
+/// );
+/// ```
+pub struct SessionWindow<F, T, A = ToVec> {
+    extract_ts: Rc<F>,
+    gap: u64,
+    aggregator: Rc<A>,
+    _phantom: PhantomData<T>,
+}
+
+impl<F, T> SessionWindow<F, T>
+where
+    F: Fn(&T) -> u64,
+{
+    /// Sessions separated by at least `gap` of inactivity.
+    pub fn new(extract_ts: F, gap: u64) -> Self {
+        assert!(gap > 0, "Session gap must be greater than 0");
+        SessionWindow {
+            extract_ts: Rc::new(extract_ts),
+            gap,
+            aggregator: Rc::new(ToVec),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, T, A> SessionWindow<F, T, A> {
+    /// Reduce each session with `aggregator` instead of collecting it.
+    pub fn aggregate<B: Aggregator<T>>(self, aggregator: B) -> SessionWindow<F, T, B> {
+        SessionWindow {
+            extract_ts: self.extract_ts,
+            gap: self.gap,
+            aggregator: Rc::new(aggregator),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// The open session of one run.
+struct Session<S> {
+    window: Window,
+    state: S,
+}
+
+impl<F, T, A> Transducer<T, (Window, A::Output)> for SessionWindow<F, T, A>
+where
+    F: Fn(&T) -> u64 + 'static,
+    T: 'static,
+    A: Aggregator<T> + 'static,
+    A::State: 'static,
+    A::Output: 'static,
+{
+    fn apply<Acc, R>(&self, reducer: R) -> Box<dyn Fn(Acc, T) -> Step<Acc>>
+    where
+        R: Fn(Acc, (Window, A::Output)) -> Step<Acc> + 'static,
+        Acc: 'static,
+    {
+        let extract_ts = Rc::clone(&self.extract_ts);
+        let aggregator = Rc::clone(&self.aggregator);
+        let gap = self.gap;
+        let open: RefCell<Option<Session<A::State>>> = RefCell::new(None);
+
+        Box::new(move |acc, val| {
+            let ts = extract_ts(&val);
+            let end = ts.saturating_add(gap);
+            let mut open = open.borrow_mut();
+
+            let closed = match open.take() {
+                // Within the session, or close enough before it to extend it
+                Some(session) if ts < session.window.end && end > session.window.start => {
+                    let window =
+                        Window::new(session.window.start.min(ts), session.window.end.max(end));
+                    let state = aggregator.step(session.state, &val);
+                    *open = Some(Session { window, state });
+                    return cont(acc);
+                }
+                // Before the session: part of one already emitted
+                Some(session) if ts < session.window.start => {
+                    *open = Some(session);
+                    return cont(acc);
+                }
+                closed => closed,
+            };
+
+            let state = aggregator.step(aggregator.init(), &val);
+            *open = Some(Session {
+                window: Window::new(ts, end),
+                state,
+            });
+            drop(open);
+
+            match closed {
+                Some(session) => reducer(acc, (session.window, aggregator.finish(session.state))),
+                None => cont(acc),
+            }
+        })
+    }
+
+    fn describe(&self) -> Explain {
+        Explain::stage(StageInfo::new(format!("session_window({})", self.gap)).stateful())
+    }
+
+    fn size_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        (0, input.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate;
+    use crate::collectors::to_vec;
+    use crate::transforms::Take;
+    use alloc::vec;
+
+    fn ts(x: &u64) -> u64 {
+        *x
+    }
+
+    #[test]
+    fn test_tumbling_skips_empty_windows() {
+        let windows = WindowByTime::tumbling(ts, 10);
+        assert_eq!(
+            to_vec(&windows, vec![3, 10, 19, 45, 51, 60]),
+            vec![
+                (Window::new(0, 10), vec![3]),
+                (Window::new(10, 20), vec![10, 19]),
+                (Window::new(40, 50), vec![45]),
+                (Window::new(50, 60), vec![51]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_out_of_order_elements() {
+        let windows = WindowByTime::tumbling(ts, 10);
+        // 4 is behind 7 but its window is still open; 3 arrives after
+        // [0, 10) was emitted and is dropped
+        assert_eq!(
+            to_vec(&windows, vec![2, 7, 4, 12, 3, 15, 20]),
+            vec![
+                (Window::new(0, 10), vec![2, 7, 4]),
+                (Window::new(10, 20), vec![12, 15]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sliding_with_gaps_and_overlaps() {
+        // Overlapping: windows of 3 starting every 1
+        let overlapping = WindowByTime::sliding(ts, 3, 1).aggregate(aggregate::count());
+        assert_eq!(
+            to_vec(&overlapping, vec![0, 1, 2, 10]),
+            vec![
+                (Window::new(0, 3), 3),
+                (Window::new(1, 4), 2),
+                (Window::new(2, 5), 1),
+            ]
+        );
+
+        // Hopping with gaps: 2-3 and 7-8 are in no window
+        let hopping = WindowByTime::sliding(ts, 2, 5);
+        assert_eq!(
+            to_vec(&hopping, vec![0, 1, 2, 3, 5, 7, 8, 10]),
+            vec![
+                (Window::new(0, 2), vec![0, 1]),
+                (Window::new(5, 7), vec![5])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stops_between_windows() {
+        let pipeline = WindowByTime::sliding(ts, 3, 1)
+            .aggregate(aggregate::count())
+            .compose(Take::new(2));
+        // 10 closes three windows; only two are taken
+        assert_eq!(
+            to_vec(&pipeline, vec![0, 1, 2, 10]),
+            vec![(Window::new(0, 3), 3), (Window::new(1, 4), 2)]
+        );
+    }
+
+    #[test]
+    fn test_fresh_state_per_run() {
+        let windows = WindowByTime::tumbling(ts, 5).aggregate(aggregate::max());
+        let first = to_vec(&windows, vec![1, 4, 7]);
+        assert_eq!(first, vec![(Window::new(0, 5), Some(4))]);
+        assert_eq!(to_vec(&windows, vec![1, 4, 7]), first);
+    }
+
+    #[test]
+    fn test_timestamps_near_the_end_of_time() {
+        let windows = WindowByTime::tumbling(ts, 10);
+        assert!(to_vec(&windows, vec![u64::MAX - 1, u64::MAX]).is_empty());
+        assert_eq!(
+            windows.size_hint((4, Some(4))),
+            (0, Some(4)),
+            "tumbling windows hold each element once"
+        );
+        assert_eq!(
+            WindowByTime::sliding(ts, 10, 3).size_hint((0, Some(4))),
+            (0, Some(16))
+        );
+    }
+
+    #[test]
+    fn test_allowed_lateness_delays_closing() {
+        let windows = WindowByTime::tumbling(ts, 10).allowed_lateness(3);
+        // 12 leaves the watermark at 9, so 7 still joins [0, 10); 13 moves
+        // it to 10 and closes the window, so 6 is late
+        assert_eq!(
+            to_vec(&windows, vec![4, 12, 7, 13, 6, 19, 25]),
+            vec![
+                (Window::new(0, 10), vec![4, 7]),
+                (Window::new(10, 20), vec![12, 13, 19]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_joins_windows_at_the_watermark() {
+        let windows = WindowByTime::sliding(ts, 10, 5)
+            .allowed_lateness(2)
+            .on_late(LatePolicy::Merge)
+            .aggregate(aggregate::to_vec());
+        // 1's windows have closed by the time it arrives (watermark 20), so
+        // it counts as 20
+        assert_eq!(
+            to_vec(&windows, vec![12, 22, 1, 40]),
+            vec![
+                (Window::new(5, 15), vec![12]),
+                (Window::new(10, 20), vec![12]),
+                (Window::new(15, 25), vec![22, 1]),
+                (Window::new(20, 30), vec![22, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_late_output() {
+        let windows = WindowByTime::tumbling(ts, 10)
+            .aggregate(aggregate::count())
+            .late_output();
+        assert_eq!(
+            to_vec(&windows, vec![1, 10, 2, 3, 20]),
+            vec![
+                Either::Left((Window::new(0, 10), 1)),
+                Either::Right(2),
+                Either::Right(3),
+                Either::Left((Window::new(10, 20), 1)),
+            ]
+        );
+
+        // Merged elements aren't emitted as late
+        let merged = WindowByTime::tumbling(ts, 10)
+            .on_late(LatePolicy::Merge)
+            .late_output();
+        assert_eq!(
+            to_vec(&merged, vec![1, 10, 2, 20]),
+            vec![
+                Either::Left((Window::new(0, 10), vec![1])),
+                Either::Left((Window::new(10, 20), vec![10, 2])),
+            ]
+        );
+        assert_eq!(
+            WindowByTime::tumbling(ts, 10)
+                .allowed_lateness(3)
+                .late_output()
+                .describe()
+                .stages[0]
+                .name,
+            "window_by_time_late_output(10, 10, lateness 3)"
+        );
+    }
+
+    #[test]
+    fn test_sessions_split_on_gaps() {
+        let sessions = SessionWindow::new(ts, 5);
+        // A gap of exactly 5 starts a new session
+        assert_eq!(
+            to_vec(&sessions, vec![0, 3, 7, 12, 13, 30, 31]),
+            vec![
+                (Window::new(0, 12), vec![0, 3, 7]),
+                (Window::new(12, 18), vec![12, 13]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sessions_out_of_order() {
+        let sessions = SessionWindow::new(ts, 5).aggregate(aggregate::to_vec());
+        // 8 fills in within the session, 6 extends it backwards, and 1 is
+        // too far behind and dropped
+        assert_eq!(
+            to_vec(&sessions, vec![10, 12, 8, 6, 1, 40, 50]),
+            vec![
+                (Window::new(6, 17), vec![10, 12, 8, 6]),
+                (Window::new(40, 45), vec![40])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sessions_stop_and_rerun() {
+        let sessions = SessionWindow::new(ts, 2)
+            .aggregate(aggregate::count())
+            .compose(Take::new(1));
+        assert_eq!(
+            to_vec(&sessions, vec![0, 1, 5, 9, 20]),
+            vec![(Window::new(0, 3), 2)]
+        );
+        assert_eq!(to_vec(&sessions, vec![4, 10]), vec![(Window::new(4, 6), 1)]);
+        assert_eq!(sessions.describe().stages[0].name, "session_window(2)");
+    }
+}
@@ -0,0 +1,67 @@
+//! Tests for `#[derive(Lenses)]`.
+#![cfg(feature = "derive")]
+
+use orlando_transducers::optics::{Lens, Lenses};
+
+#[derive(Clone, Debug, PartialEq, Lenses)]
+struct User {
+    name: String,
+    age: u32,
+    address: Address,
+    #[lens(skip)]
+    #[allow(dead_code)]
+    session: std::rc::Rc<()>,
+}
+
+#[derive(Clone, Debug, PartialEq, Lenses)]
+struct Address {
+    city: String,
+    r#type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Lenses)]
+struct Tagged<T> {
+    tag: &'static str,
+    value: T,
+}
+
+fn alice() -> User {
+    User {
+        name: "Alice".to_string(),
+        age: 30,
+        address: Address {
+            city: "NYC".to_string(),
+            r#type: "home".to_string(),
+        },
+        session: Default::default(),
+    }
+}
+
+#[test]
+fn test_derived_lenses_get_set_over() {
+    let user = alice();
+    assert_eq!(User::name_lens().get(&user), "Alice");
+    assert_eq!(User::age_lens().over(&user, |a| a + 1).age, 31);
+
+    let renamed = User::name_lens().set(&user, "Bob".to_string());
+    assert_eq!(renamed.name, "Bob");
+    assert_eq!(renamed.age, 30);
+}
+
+#[test]
+fn test_derived_lenses_compose() {
+    let city: Lens<User, String> = User::address_lens().compose(Address::city_lens());
+    let moved = city.set(&alice(), "Boston".to_string());
+    assert_eq!(moved.address.city, "Boston");
+    assert_eq!(Address::type_lens().get(&moved.address), "home");
+}
+
+#[test]
+fn test_derived_lenses_generic_struct() {
+    let tagged = Tagged {
+        tag: "count",
+        value: 2,
+    };
+    assert_eq!(Tagged::value_lens().over(&tagged, |n| n * 10).value, 20);
+    assert_eq!(Tagged::<i32>::tag_lens().get(&tagged), "count");
+}
@@ -133,7 +133,7 @@ fn test_wasm_pipeline_basic() {
     source.push(&2.into());
     source.push(&3.into());
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
 }
 
@@ -151,7 +151,7 @@ fn test_wasm_pipeline_map() {
     source.push(&2.into());
     source.push(&3.into());
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
     assert_eq!(result.get(0).as_f64(), Some(2.0));
     assert_eq!(result.get(1).as_f64(), Some(4.0));
@@ -173,7 +173,7 @@ fn test_wasm_pipeline_filter() {
     source.push(&3.into());
     source.push(&4.into());
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     assert_eq!(result.get(0).as_f64(), Some(2.0));
     assert_eq!(result.get(1).as_f64(), Some(4.0));
@@ -201,7 +201,7 @@ fn test_wasm_pipeline_pluck() {
     Reflect::set(&obj2, &"age".into(), &25.into()).unwrap();
     source.push(&obj2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     assert_eq!(result.get(0).as_string(), Some("Alice".to_string()));
     assert_eq!(result.get(1).as_string(), Some("Bob".to_string()));
@@ -221,7 +221,7 @@ fn test_wasm_pipeline_pluck_missing_property() {
     Reflect::set(&obj, &"name".into(), &"Alice".into()).unwrap();
     source.push(&obj);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1);
     assert!(result.get(0).is_undefined());
 }
@@ -248,13 +248,51 @@ fn test_wasm_pipeline_pluck_nested() {
     Reflect::set(&obj3, &"value".into(), &30.into()).unwrap();
     source.push(&obj3);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
     assert_eq!(result.get(0).as_f64(), Some(10.0));
     assert_eq!(result.get(1).as_f64(), Some(20.0));
     assert_eq!(result.get(2).as_f64(), Some(30.0));
 }
 
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_pluck_dotted_path() {
+    use js_sys::JSON;
+    use orlando_transducers::Pipeline;
+
+    let source = JSON::parse(
+        r#"[
+            {"profile": {"address": {"city": "NYC"}}, "items": [{"id": 7}]},
+            {"profile": {}, "items": []}
+        ]"#,
+    )
+    .unwrap();
+
+    let cities = Pipeline::new()
+        .pluck("profile.address.city")
+        .to_array(&source)
+        .unwrap();
+    assert_eq!(cities.get(0), "NYC");
+    assert!(cities.get(1).is_undefined());
+
+    let ids = Pipeline::new()
+        .pluck("items.0.id")
+        .to_array(&source)
+        .unwrap();
+    assert_eq!(ids.get(0), 7);
+    assert!(ids.get(1).is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_pluck_literal_dotted_key() {
+    use js_sys::JSON;
+    use orlando_transducers::Pipeline;
+
+    let source = JSON::parse(r#"[{"a.b": 1}]"#).unwrap();
+    let result = Pipeline::new().pluck("a.b").to_array(&source).unwrap();
+    assert_eq!(result.get(0), 1);
+}
+
 #[wasm_bindgen_test]
 fn test_wasm_pipeline_pluck_composition() {
     use js_sys::{Array, Function, Object, Reflect};
@@ -282,7 +320,7 @@ fn test_wasm_pipeline_pluck_composition() {
     Reflect::set(&obj3, &"age".into(), &28.into()).unwrap();
     source.push(&obj3);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2); // Only Alice (30) and Charlie (28)
     assert_eq!(result.get(0).as_f64(), Some(30.0));
     assert_eq!(result.get(1).as_f64(), Some(28.0));
@@ -312,222 +350,1338 @@ fn test_wasm_pipeline_take_with_filter() {
     source.push(&9.into());
     source.push(&10.into());
 
-    let result = pipeline.to_array(&source);
-    assert_eq!(result.length(), 1, "take(1) should only return 1 element");
-    assert_eq!(result.get(0).as_f64(), Some(60.0));
+    let result = pipeline.to_array(&source).unwrap();
+    assert_eq!(result.length(), 1, "take(1) should only return 1 element");
+    assert_eq!(result.get(0).as_f64(), Some(60.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_take_multiple_with_filter() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let filter_fn = Function::new_with_args("x", "return x > 0");
+    let pipeline = pipeline.filter(&filter_fn).take(3);
+
+    let source = Array::new();
+    source.push(&(-1).into());
+    source.push(&1.into());
+    source.push(&2.into());
+    source.push(&(-3).into());
+    source.push(&3.into());
+    source.push(&4.into());
+    source.push(&5.into());
+
+    let result = pipeline.to_array(&source).unwrap();
+    assert_eq!(result.length(), 3);
+    assert_eq!(result.get(0).as_f64(), Some(1.0));
+    assert_eq!(result.get(1).as_f64(), Some(2.0));
+    assert_eq!(result.get(2).as_f64(), Some(3.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_drop_with_filter() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let filter_fn = Function::new_with_args("x", "return x > 0");
+    let pipeline = pipeline.filter(&filter_fn).drop(2).take(2);
+
+    let source = Array::new();
+    source.push(&(-1).into());
+    source.push(&1.into());
+    source.push(&(-2).into());
+    source.push(&2.into());
+    source.push(&3.into());
+    source.push(&4.into());
+    source.push(&5.into());
+
+    let result = pipeline.to_array(&source).unwrap();
+    assert_eq!(result.length(), 2);
+    assert_eq!(result.get(0).as_f64(), Some(3.0));
+    assert_eq!(result.get(1).as_f64(), Some(4.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_take_without_filter() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let map_fn = Function::new_with_args("x", "return x * 2");
+    let pipeline = pipeline.map(&map_fn).take(5);
+
+    let source = Array::new();
+    for i in 1..=10 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    assert_eq!(result.length(), 5);
+    assert_eq!(result.get(0).as_f64(), Some(2.0));
+    assert_eq!(result.get(1).as_f64(), Some(4.0));
+    assert_eq!(result.get(2).as_f64(), Some(6.0));
+    assert_eq!(result.get(3).as_f64(), Some(8.0));
+    assert_eq!(result.get(4).as_f64(), Some(10.0));
+}
+
+// Comprehensive integration tests for stateful operations
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_takewhile_with_map() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let map_fn = Function::new_with_args("x", "return x * 2");
+    let pred_fn = Function::new_with_args("x", "return x < 20");
+    let pipeline = pipeline.map(&map_fn).take_while(&pred_fn);
+
+    let source = Array::new();
+    for i in 1..=20 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    // Should take while x*2 < 20, so x < 10, meaning [2, 4, 6, 8, 10, 12, 14, 16, 18]
+    assert_eq!(result.length(), 9);
+    assert_eq!(result.get(0).as_f64(), Some(2.0));
+    assert_eq!(result.get(8).as_f64(), Some(18.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_dropwhile_with_filter() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
+    let pred_fn = Function::new_with_args("x", "return x < 10");
+    let pipeline = pipeline.filter(&filter_fn).drop_while(&pred_fn).take(3);
+
+    let source = Array::new();
+    for i in 1..=20 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    // Even numbers: [2, 4, 6, 8, 10, 12, 14, 16, 18, 20]
+    // Drop while < 10: [10, 12, 14, 16, 18, 20]
+    // Take 3: [10, 12, 14]
+    assert_eq!(result.length(), 3);
+    assert_eq!(result.get(0).as_f64(), Some(10.0));
+    assert_eq!(result.get(1).as_f64(), Some(12.0));
+    assert_eq!(result.get(2).as_f64(), Some(14.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_complex_stateful_combination() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let map_fn = Function::new_with_args("x", "return x + 1");
+    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
+    let pipeline = pipeline
+        .map(&map_fn) // [2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+        .filter(&filter_fn) // [2, 4, 6, 8, 10]
+        .drop(1) // [4, 6, 8, 10]
+        .take(2); // [4, 6]
+
+    let source = Array::new();
+    for i in 1..=10 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    assert_eq!(result.length(), 2);
+    assert_eq!(result.get(0).as_f64(), Some(4.0));
+    assert_eq!(result.get(1).as_f64(), Some(6.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_flatmap_with_take() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new();
+    let flatmap_fn = Function::new_with_args("x", "return [x, x + 1]");
+    let pipeline = pipeline.flat_map(&flatmap_fn).take(5);
+
+    let source = Array::new();
+    for i in 1..=10 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    // flatMap produces: [1, 2, 2, 3, 3, 4, ...]
+    // take(5): [1, 2, 2, 3, 3]
+    assert_eq!(result.length(), 5);
+    assert_eq!(result.get(0).as_f64(), Some(1.0));
+    assert_eq!(result.get(1).as_f64(), Some(2.0));
+    assert_eq!(result.get(2).as_f64(), Some(2.0));
+    assert_eq!(result.get(3).as_f64(), Some(3.0));
+    assert_eq!(result.get(4).as_f64(), Some(3.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_multiple_takes() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    // This tests that take operations compose correctly
+    let pipeline = Pipeline::new();
+    let pipeline = pipeline.take(10).take(5);
+
+    let source = Array::new();
+    for i in 1..=20 {
+        source.push(&i.into());
+    }
+
+    let result = pipeline.to_array(&source).unwrap();
+    // The inner take(10) limits to first 10, then take(5) limits to first 5
+    assert_eq!(result.length(), 5);
+    assert_eq!(result.get(0).as_f64(), Some(1.0));
+    assert_eq!(result.get(4).as_f64(), Some(5.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_reduce_with_stateful_ops() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+
+    let pipeline = Pipeline::new();
+    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
+    let pipeline = pipeline.filter(&filter_fn).take(3);
+
+    let source = Array::new();
+    for i in 1..=20 {
+        source.push(&i.into());
+    }
+
+    let reducer = Function::new_with_args("acc, val", "return acc + val");
+    let result = pipeline
+        .reduce(&source, &reducer, JsValue::from(0))
+        .unwrap();
+
+    // Even numbers [2, 4, 6], sum = 12
+    assert_eq!(result.as_f64(), Some(12.0));
+}
+
+// ============================================================================
+// Pipeline Terminal Collectors
+// ============================================================================
+
+fn numbers(range: std::ops::RangeInclusive<i32>) -> js_sys::Array {
+    let source = js_sys::Array::new();
+    for i in range {
+        source.push(&i.into());
+    }
+    source
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_count_and_sum() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let evens = Pipeline::new().filter(&Function::new_with_args("x", "return x % 2 === 0"));
+    assert_eq!(evens.count(&numbers(1..=10)).unwrap(), 5);
+    assert_eq!(evens.sum(&numbers(1..=10)).unwrap(), 30.0);
+    assert_eq!(evens.take(2).sum(&numbers(1..=10)).unwrap(), 6.0);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_first_and_find() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let calls = js_sys::Array::new();
+    let record = Function::new_with_args("x", "this.push(x)").bind(&calls);
+    let pipeline = Pipeline::new().tap(&record);
+
+    assert_eq!(
+        pipeline.first(&numbers(1..=100)).unwrap().as_f64(),
+        Some(1.0)
+    );
+    assert_eq!(calls.length(), 1);
+
+    let over_3 = Function::new_with_args("x", "return x > 3");
+    assert_eq!(
+        Pipeline::new()
+            .find(&numbers(1..=100), &over_3)
+            .unwrap()
+            .as_f64(),
+        Some(4.0)
+    );
+    assert!(Pipeline::new()
+        .find(&numbers(1..=3), &over_3)
+        .unwrap()
+        .is_undefined());
+    assert!(Pipeline::new()
+        .first(&js_sys::Array::new())
+        .unwrap()
+        .is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_every_and_some() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let positive = Function::new_with_args("x", "return x > 0");
+    let over_5 = Function::new_with_args("x", "return x > 5");
+    let doubled = Pipeline::new().map(&Function::new_with_args("x", "return x * 2"));
+
+    assert!(doubled.every(&numbers(1..=5), &positive).unwrap());
+    assert!(!doubled.every(&numbers(1..=5), &over_5).unwrap());
+    assert!(doubled.some(&numbers(1..=5), &over_5).unwrap());
+    assert!(!doubled.some(&numbers(1..=2), &over_5).unwrap());
+    assert!(doubled.every(&js_sys::Array::new(), &over_5).unwrap());
+    assert!(!doubled.some(&js_sys::Array::new(), &positive).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_group_by() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let parity = Function::new_with_args("x", "return x % 2 === 0 ? 'even' : 'odd'");
+    let groups = Pipeline::new().group_by(&numbers(1..=5), &parity).unwrap();
+
+    assert_eq!(groups.size(), 2);
+    let odd = Array::from(&groups.get(&"odd".into()));
+    let even = Array::from(&groups.get(&"even".into()));
+    assert_eq!(odd.length(), 3);
+    assert_eq!(even.length(), 2);
+    assert_eq!(even.get(1).as_f64(), Some(4.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_frequencies() {
+    use js_sys::Array;
+    use orlando_transducers::Pipeline;
+
+    let source = Array::of4(&"a".into(), &"b".into(), &"a".into(), &"a".into());
+    let freq = Pipeline::new().frequencies(&source).unwrap();
+
+    assert_eq!(freq.size(), 2);
+    assert_eq!(freq.get(&"a".into()).as_f64(), Some(3.0));
+    assert_eq!(freq.get(&"b".into()).as_f64(), Some(1.0));
+}
+
+// ============================================================================
+// Pipeline Stateful Operations
+// ============================================================================
+
+fn as_f64s(array: &js_sys::Array) -> Vec<f64> {
+    (0..array.length())
+        .map(|i| array.get(i).as_f64().unwrap_or(f64::NAN))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_unique_and_unique_by() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let source = Array::new();
+    for x in [1, 1, 2, 2, 3, 1, -1] {
+        source.push(&x.into());
+    }
+
+    let unique = Pipeline::new().unique().to_array(&source).unwrap();
+    assert_eq!(as_f64s(&unique), vec![1.0, 2.0, 3.0, 1.0, -1.0]);
+
+    let by_abs = Pipeline::new()
+        .unique_by(&Function::new_with_args("x", "return Math.abs(x)"))
+        .to_array(&source)
+        .unwrap();
+    assert_eq!(as_f64s(&by_abs), vec![1.0, 2.0, 3.0, 1.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_chunk_flushes_partial() {
+    use js_sys::Array;
+    use orlando_transducers::Pipeline;
+
+    let chunks = Pipeline::new().chunk(3).to_array(&numbers(1..=7)).unwrap();
+    assert_eq!(chunks.length(), 3);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(0))), vec![1.0, 2.0, 3.0]);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(2))), vec![7.0]);
+
+    // Early termination must not flush a partial chunk
+    let limited = Pipeline::new()
+        .chunk(2)
+        .take(2)
+        .to_array(&numbers(1..=7))
+        .unwrap();
+    assert_eq!(limited.length(), 2);
+
+    // ...but a take upstream of the chunk still flushes its remainder
+    let taken = Pipeline::new()
+        .take(5)
+        .chunk(2)
+        .to_array(&numbers(1..=10))
+        .unwrap();
+    assert_eq!(taken.length(), 3);
+    assert_eq!(as_f64s(&Array::from(&taken.get(2))), vec![5.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_aperture() {
+    use js_sys::Array;
+    use orlando_transducers::Pipeline;
+
+    let windows = Pipeline::new()
+        .aperture(3)
+        .to_array(&numbers(1..=5))
+        .unwrap();
+    assert_eq!(windows.length(), 3);
+    assert_eq!(as_f64s(&Array::from(&windows.get(1))), vec![2.0, 3.0, 4.0]);
+    assert_eq!(
+        Pipeline::new().aperture(6).count(&numbers(1..=5)).unwrap(),
+        0
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_scan() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let add = Function::new_with_args("acc, x", "return acc + x");
+    let pipeline = Pipeline::new().scan(&add, 0.into());
+    assert_eq!(
+        as_f64s(&pipeline.to_array(&numbers(1..=4)).unwrap()),
+        vec![1.0, 3.0, 6.0, 10.0]
+    );
+    // Each run starts from the initial accumulator again
+    assert_eq!(
+        as_f64s(&pipeline.to_array(&numbers(1..=2)).unwrap()),
+        vec![1.0, 3.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_interpose() {
+    use orlando_transducers::Pipeline;
+
+    let result = Pipeline::new()
+        .interpose(0.into())
+        .to_array(&numbers(1..=3))
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![1.0, 0.0, 2.0, 0.0, 3.0]);
+
+    let limited = Pipeline::new()
+        .interpose(0.into())
+        .take(2)
+        .to_array(&numbers(1..=3))
+        .unwrap();
+    assert_eq!(as_f64s(&limited), vec![1.0, 0.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_reject() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let result = Pipeline::new()
+        .reject(&odd)
+        .to_array(&numbers(1..=6))
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![2.0, 4.0, 6.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_repeated_stateful_ops_are_independent() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let result = Pipeline::new()
+        .drop(2)
+        .drop(1)
+        .take(4)
+        .take(2)
+        .to_array(&numbers(1..=10))
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![4.0, 5.0]);
+
+    // dropWhile stops dropping after the first failing element
+    let small = Function::new_with_args("x", "return x < 3");
+    let source = numbers(1..=5);
+    source.push(&1.into());
+    let result = Pipeline::new()
+        .drop_while(&small)
+        .to_array(&source)
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![3.0, 4.0, 5.0, 1.0]);
+}
+
+// ============================================================================
+// Pipeline Iterable Sources
+// ============================================================================
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_set_source() {
+    use js_sys::Set;
+    use orlando_transducers::Pipeline;
+
+    let set = Set::new(&numbers(1..=3));
+    set.add(&1.into());
+    let result = Pipeline::new().to_array(&set).unwrap();
+    assert_eq!(as_f64s(&result), vec![1.0, 2.0, 3.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_infinite_generator_with_take() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+
+    let naturals =
+        Function::new_no_args("return (function* () { let n = 0; while (true) { yield n++; } })()")
+            .call0(&JsValue::NULL)
+            .unwrap();
+
+    let evens = Function::new_with_args("x", "return x % 2 === 0");
+    let result = Pipeline::new()
+        .filter(&evens)
+        .take(3)
+        .to_array(&naturals)
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![0.0, 2.0, 4.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_closes_generator_on_early_exit() {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+
+    let probe = js_sys::Object::new();
+    let make = Function::new_with_args(
+        "probe",
+        "return (function* () { try { yield 1; yield 2; yield 3; } finally { probe.closed = true; } })()",
+    );
+    let generator = make.call1(&JsValue::NULL, &probe).unwrap();
+
+    assert_eq!(
+        Pipeline::new().first(&generator).unwrap().as_f64(),
+        Some(1.0)
+    );
+    assert_eq!(
+        Reflect::get(&probe, &"closed".into()).unwrap().as_bool(),
+        Some(true)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_plain_iterator_source() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+
+    // An object with next() but no Symbol.iterator
+    let iterator = Function::new_no_args(
+        "let i = 0; return { next: () => i < 3 ? { value: i++, done: false } : { done: true } }",
+    )
+    .call0(&JsValue::NULL)
+    .unwrap();
+
+    assert_eq!(Pipeline::new().count(&iterator).unwrap(), 3);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_non_iterable_source_is_empty() {
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+
+    assert_eq!(Pipeline::new().count(&JsValue::from(42)).unwrap(), 0);
+    assert_eq!(Pipeline::new().count(&JsValue::UNDEFINED).unwrap(), 0);
+}
+
+// ============================================================================
+// Pipeline Async Sources
+// ============================================================================
+
+fn async_numbers(n: u32) -> wasm_bindgen::JsValue {
+    js_sys::Function::new_with_args(
+        "n",
+        "return (async function* () { for (let i = 1; i <= n; i++) { await null; yield i; } })()",
+    )
+    .call1(&wasm_bindgen::JsValue::NULL, &n.into())
+    .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_to_array_async() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let pipeline = Pipeline::new()
+        .map(&Function::new_with_args("x", "return x * 10"))
+        .chunk(2);
+    let result = JsFuture::from(pipeline.to_array_async(async_numbers(5)))
+        .await
+        .unwrap();
+    let chunks = Array::from(&result);
+
+    assert_eq!(chunks.length(), 3);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(2))), vec![50.0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_broadcast() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::pipeline::broadcast;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+
+    let chunked = Pipeline::new()
+        .map(&Function::new_with_args("x", "return x * 10"))
+        .chunk(4);
+    let pipelines = Array::of3(
+        &JsValue::from(chunked),
+        &JsValue::from(Pipeline::new().take(2)),
+        &JsValue::from(Pipeline::new().filter(&Function::new_with_args("x", "return x % 2"))),
+    );
+    let result = JsFuture::from(broadcast(async_numbers(6), &pipelines).unwrap())
+        .await
+        .unwrap();
+    let outputs = Array::from(&result);
+
+    // Each pipeline keeps its own state, and chunk is flushed at the end
+    let chunks = Array::from(&outputs.get(0));
+    assert_eq!(chunks.length(), 2);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(1))), vec![50.0, 60.0]);
+    assert_eq!(as_f64s(&Array::from(&outputs.get(1))), vec![1.0, 2.0]);
+    assert_eq!(as_f64s(&Array::from(&outputs.get(2))), vec![1.0, 3.0, 5.0]);
+
+    // The pipelines passed in stay usable
+    let taken: Pipeline =
+        wasm_bindgen::convert::TryFromJsValue::try_from_js_value(pipelines.get(1)).unwrap();
+    assert_eq!(taken.to_array(&numbers(7..=9)).unwrap().length(), 2);
+
+    assert!(broadcast(numbers(1..=3).into(), &Array::of1(&1.into())).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_to_array_async_sync_source() {
+    use js_sys::Array;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let result = JsFuture::from(
+        Pipeline::new()
+            .take(2)
+            .to_array_async(numbers(1..=5).into()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(as_f64s(&Array::from(&result)), vec![1.0, 2.0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_for_each_async_awaits_callback() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let seen = js_sys::Array::new();
+    let callback = Function::new_with_args(
+        "x",
+        "return new Promise(resolve => setTimeout(() => { this.push(x); resolve(); }, 0))",
+    )
+    .bind(&seen);
+
+    JsFuture::from(
+        Pipeline::new()
+            .take(3)
+            .for_each_async(async_numbers(10), callback),
+    )
+    .await
+    .unwrap();
+    assert_eq!(as_f64s(&seen), vec![1.0, 2.0, 3.0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_async_rejects_on_callback_error() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let boom = Function::new_with_args("x", "throw new Error('boom')");
+    let outcome = JsFuture::from(Pipeline::new().for_each_async(async_numbers(3), boom)).await;
+    assert!(outcome.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_async_error_closes_source() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+
+    let closed = Array::new();
+    let source = || {
+        Function::new_with_args(
+            "closed",
+            "return (async function* () { \
+               try { for (let i = 1; ; i++) yield i; } finally { closed.push(true); } \
+             })()",
+        )
+        .call1(&JsValue::NULL, &closed)
+        .unwrap()
+    };
+
+    // The callback throws
+    let boom = Function::new_with_args("x", "if (x === 2) throw new Error('boom')");
+    let outcome = JsFuture::from(Pipeline::new().for_each_async(source(), boom)).await;
+    assert!(outcome.is_err());
+    assert_eq!(closed.length(), 1);
+
+    // The callback's promise rejects
+    let reject = Function::new_with_args(
+        "x",
+        "return x === 2 ? Promise.reject(new Error('boom')) : undefined",
+    );
+    let outcome = JsFuture::from(Pipeline::new().for_each_async(source(), reject)).await;
+    assert!(outcome.is_err());
+    assert_eq!(closed.length(), 2);
+}
+
+// ============================================================================
+// Pipeline Typed Array Sources
+// ============================================================================
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_typed_array_fast_path() {
+    use js_sys::Float64Array;
+    use orlando_transducers::Pipeline;
+
+    let source = Float64Array::from(&[1.0, 1.0, 2.0, 3.0, 3.0, 4.0, 5.0][..]);
+    let pipeline = Pipeline::new().unique().drop(1).take(3);
+
+    assert_eq!(
+        as_f64s(&pipeline.to_array(&source).unwrap()),
+        vec![2.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        pipeline.to_float64_array(&source).unwrap().to_vec(),
+        vec![2.0, 3.0, 4.0]
+    );
+    assert_eq!(pipeline.count(&source).unwrap(), 3);
+    assert_eq!(pipeline.sum(&source).unwrap(), 9.0);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_integer_typed_arrays() {
+    use js_sys::{Int32Array, Uint8Array};
+    use orlando_transducers::Pipeline;
+
+    let ints = Int32Array::from(&[-5, 10, 20][..]);
+    assert_eq!(Pipeline::new().sum(&ints).unwrap(), 25.0);
+
+    let bytes = Uint8Array::from(&[255u8, 0, 7][..]);
+    assert_eq!(
+        as_f64s(&Pipeline::new().take(2).to_array(&bytes).unwrap()),
+        vec![255.0, 0.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_typed_array_with_callbacks() {
+    use js_sys::{Float64Array, Function};
+    use orlando_transducers::Pipeline;
+
+    // JS callbacks fall back to the general path, with identical results
+    let source = Float64Array::from(&[1.0, 2.0, 3.0, 4.0][..]);
+    let pipeline = Pipeline::new()
+        .filter(&Function::new_with_args("x", "return x % 2 === 0"))
+        .take(1);
+    assert_eq!(as_f64s(&pipeline.to_array(&source).unwrap()), vec![2.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_to_float64_array_from_array() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new().map(&Function::new_with_args("x", "return x > 2 ? 'big' : x"));
+    let result = pipeline
+        .to_float64_array(&numbers(1..=3).into())
+        .unwrap()
+        .to_vec();
+    assert_eq!(result[..2], [1.0, 2.0]);
+    assert!(result[2].is_nan());
+}
+
+// ============================================================================
+// Pipeline Numeric Operations
+// ============================================================================
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_numeric_ops() {
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new().map_mul(2.0).map_add(1.0).filter_gt(5.0);
+    assert_eq!(
+        as_f64s(&pipeline.to_array(&numbers(1..=5).into()).unwrap()),
+        vec![7.0, 9.0, 11.0]
+    );
+
+    let in_range = Pipeline::new().filter_range(2.0, 4.0);
+    assert_eq!(
+        as_f64s(&in_range.to_array(&numbers(1..=5).into()).unwrap()),
+        vec![2.0, 3.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_fused_numeric_ops() {
+    use js_sys::Float64Array;
+    use orlando_transducers::Pipeline;
+
+    let fahrenheit = Pipeline::new().scale_offset(1.8, 32.0);
+    assert_eq!(
+        as_f64s(
+            &fahrenheit
+                .to_array(&js_sys::Array::of2(&0.into(), &100.into()))
+                .unwrap()
+        ),
+        vec![32.0, 212.0]
+    );
+
+    let standardized = Pipeline::new().normalize(10.0, 4.0);
+    let typed = Float64Array::from([2.0, 10.0, 18.0].as_slice());
+    assert_eq!(
+        standardized.to_float64_array(&typed).unwrap().to_vec(),
+        vec![-2.0, 0.0, 2.0]
+    );
+    assert_eq!(
+        as_f64s(&standardized.to_array(&typed.into()).unwrap()),
+        vec![-2.0, 0.0, 2.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_numeric_ops_non_numbers() {
+    use js_sys::Array;
+    use orlando_transducers::Pipeline;
+
+    let source = Array::of3(&1.into(), &"a".into(), &3.into());
+    assert_eq!(
+        as_f64s(&Pipeline::new().filter_gt(0.0).to_array(&source).unwrap()),
+        vec![1.0, 3.0]
+    );
+
+    let mapped = as_f64s(&Pipeline::new().map_add(1.0).to_array(&source).unwrap());
+    assert!(mapped[1].is_nan());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_numeric_ops_typed_array_matches_array() {
+    use js_sys::Float64Array;
+    use orlando_transducers::Pipeline;
+
+    let data: Vec<f64> = (0..200).map(f64::from).collect();
+    let pipeline = Pipeline::new()
+        .map_mul(0.5)
+        .filter_range(10.0, 50.0)
+        .drop(3)
+        .take(20);
+
+    let typed = pipeline
+        .to_float64_array(&Float64Array::from(data.as_slice()))
+        .unwrap();
+    let boxed = pipeline
+        .to_array(&js_sys::Array::from(&Float64Array::from(data.as_slice())))
+        .unwrap();
+    assert_eq!(typed.to_vec(), as_f64s(&boxed));
+    assert_eq!(
+        pipeline.sum(&Float64Array::from(data.as_slice())).unwrap(),
+        typed.to_vec().iter().sum::<f64>()
+    );
+}
+
+// ============================================================================
+// Pipeline Streams
+// ============================================================================
+
+async fn read_stream(stream: &wasm_bindgen::JsValue) -> js_sys::Array {
+    let read_all = js_sys::Function::new_with_args(
+        "s",
+        "return (async () => { const r = s.getReader(); const out = []; \
+         for (;;) { const { done, value } = await r.read(); if (done) return out; out.push(value); } })()",
+    );
+    let promise: js_sys::Promise = read_all
+        .call1(&wasm_bindgen::JsValue::NULL, stream)
+        .unwrap()
+        .into();
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .unwrap()
+        .into()
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_to_stream() {
+    use js_sys::{Array, Function};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new()
+        .map(&Function::new_with_args("x", "return x * 2"))
+        .chunk(2);
+    let stream = pipeline.to_stream(&numbers(1..=5).into()).unwrap();
+    let chunks = read_stream(&stream.into()).await;
+
+    assert_eq!(chunks.length(), 3);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(0))), vec![2.0, 4.0]);
+    assert_eq!(as_f64s(&Array::from(&chunks.get(2))), vec![10.0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_to_stream_early_stop_closes_source() {
+    use js_sys::{Function, Object, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let probe = Object::new();
+    let naturals = Function::new_with_args(
+        "probe",
+        "return (function* () { try { let n = 0; while (true) yield n++; } finally { probe.closed = true; } })()",
+    )
+    .call1(&wasm_bindgen::JsValue::NULL, &probe)
+    .unwrap();
+
+    let stream = Pipeline::new().take(3).to_stream(&naturals).unwrap();
+    assert_eq!(
+        as_f64s(&read_stream(&stream.into()).await),
+        vec![0.0, 1.0, 2.0]
+    );
+    assert_eq!(Reflect::get(&probe, &"closed".into()).unwrap(), true);
+}
+
+// ============================================================================
+// Pipeline Plans
+// ============================================================================
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_plan_round_trip() {
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new()
+        .map_mul(3.0)
+        .filter_range(5.0, 20.0)
+        .drop(1)
+        .chunk(2)
+        .interpose("|".into());
+    let plan = pipeline.to_plan().unwrap();
+    assert_eq!(plan.length(), 5);
+
+    // Plans survive structured cloning (approximated here with JSON)
+    let json = js_sys::JSON::stringify(&plan).unwrap();
+    let restored = Pipeline::from_plan(
+        &js_sys::JSON::parse(&json.as_string().unwrap()).unwrap(),
+        &wasm_bindgen::JsValue::UNDEFINED,
+    )
+    .unwrap();
+
+    let source: wasm_bindgen::JsValue = numbers(1..=8).into();
+    assert_eq!(
+        js_sys::JSON::stringify(&restored.to_array(&source).unwrap()).unwrap(),
+        js_sys::JSON::stringify(&pipeline.to_array(&source).unwrap()).unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_plan_rejects_callbacks() {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let pipeline = Pipeline::new()
+        .take(2)
+        .filter(&Function::new_with_args("x", "return x > 1"));
+    let error = pipeline.to_plan().unwrap_err();
+    assert_eq!(
+        Reflect::get(&error, &"code".into()).unwrap(),
+        "NOT_SERIALIZABLE"
+    );
+    assert_eq!(
+        Reflect::get(&error, &"operationName".into()).unwrap(),
+        "filter"
+    );
+    assert_eq!(Reflect::get(&error, &"operation".into()).unwrap(), 1);
+
+    let bogus = js_sys::JSON::parse(r#"[{"op": "teleport"}]"#).unwrap();
+    let error = Pipeline::from_plan(&bogus, &wasm_bindgen::JsValue::UNDEFINED)
+        .err()
+        .unwrap();
+    assert_eq!(
+        Reflect::get(&error, &"code".into()).unwrap(),
+        "INVALID_PLAN"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_versioned_plan_with_named_functions() {
+    use js_sys::{Function, Object, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let functions = Object::new();
+    Reflect::set(
+        &functions,
+        &"double".into(),
+        &Function::new_with_args("x", "return x * 2"),
+    )
+    .unwrap();
+    Reflect::set(
+        &functions,
+        &"odd".into(),
+        &Function::new_with_args("x", "return x % 2 === 1"),
+    )
+    .unwrap();
+
+    let plan: wasm_bindgen::JsValue = r#"{"version": 1, "steps": [
+        {"op": "filter", "fn": "odd"},
+        {"op": "map", "fn": "double"},
+        {"op": "take", "n": 2}
+    ]}"#
+    .into();
+    let pipeline = Pipeline::from_plan(&plan, &functions).unwrap();
+    assert_eq!(
+        as_f64s(&pipeline.to_array(&numbers(1..=10).into()).unwrap()),
+        vec![2.0, 6.0]
+    );
+
+    // Missing functions and newer versions are rejected
+    let error = Pipeline::from_plan(&plan, &wasm_bindgen::JsValue::UNDEFINED)
+        .err()
+        .unwrap();
+    assert_eq!(
+        Reflect::get(&error, &"code".into()).unwrap(),
+        "INVALID_PLAN"
+    );
+    let newer: wasm_bindgen::JsValue = r#"{"version": 2, "steps": []}"#.into();
+    assert!(Pipeline::from_plan(&newer, &functions).is_err());
+
+    // toPlanJson writes the same envelope
+    let json = Pipeline::new().map_mul(2.0).take(3).to_plan_json().unwrap();
+    assert_eq!(
+        json,
+        r#"{"version":1,"steps":[{"op":"mapMul","n":2},{"op":"take","n":3}]}"#
+    );
 }
 
+#[cfg(feature = "json")]
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_take_multiple_with_filter() {
-    use js_sys::{Array, Function};
+fn test_wasm_pipeline_plan_matches_native_plan() {
+    use orlando_transducers::plan::{Plan, Registry};
     use orlando_transducers::Pipeline;
+    use serde_json::Value;
+    use wasm_bindgen::JsValue;
 
-    let pipeline = Pipeline::new();
-    let filter_fn = Function::new_with_args("x", "return x > 0");
-    let pipeline = pipeline.filter(&filter_fn).take(3);
+    // Partial chunks, before and after an early stop, come out the same
+    for json in [
+        r#"{"version": 1, "steps": [{"op": "chunk", "n": 3}]}"#,
+        r#"{"version": 1, "steps": [{"op": "drop", "n": 1}, {"op": "take", "n": 5}, {"op": "chunk", "n": 2}]}"#,
+        r#"{"version": 1, "steps": [{"op": "chunk", "n": 3}, {"op": "take", "n": 2}, {"op": "chunk", "n": 4}]}"#,
+        r#"{"version": 1, "steps": [{"op": "zip", "other": ["a", "b", "c"]}, {"op": "unique"}, {"op": "chunk", "n": 2}]}"#,
+    ] {
+        let native = Plan::from_json(json)
+            .unwrap()
+            .compile(&Registry::new())
+            .unwrap()
+            .run((1..=10).map(Value::from));
 
-    let source = Array::new();
-    source.push(&(-1).into());
-    source.push(&1.into());
-    source.push(&2.into());
-    source.push(&(-3).into());
-    source.push(&3.into());
-    source.push(&4.into());
-    source.push(&5.into());
+        let pipeline = Pipeline::from_plan(&json.into(), &JsValue::UNDEFINED).unwrap();
+        let output =
+            js_sys::JSON::stringify(&pipeline.to_array(&numbers(1..=10).into()).unwrap()).unwrap();
+        let wasm: Vec<Value> = serde_json::from_str(&output.as_string().unwrap()).unwrap();
 
-    let result = pipeline.to_array(&source);
-    assert_eq!(result.length(), 3);
-    assert_eq!(result.get(0).as_f64(), Some(1.0));
-    assert_eq!(result.get(1).as_f64(), Some(2.0));
-    assert_eq!(result.get(2).as_f64(), Some(3.0));
+        assert_eq!(wasm, native, "{json}");
+    }
 }
 
+// ============================================================================
+// Pipeline forEach
+// ============================================================================
+
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_drop_with_filter() {
+fn test_wasm_pipeline_for_each() {
     use js_sys::{Array, Function};
     use orlando_transducers::Pipeline;
 
-    let pipeline = Pipeline::new();
-    let filter_fn = Function::new_with_args("x", "return x > 0");
-    let pipeline = pipeline.filter(&filter_fn).drop(2).take(2);
-
-    let source = Array::new();
-    source.push(&(-1).into());
-    source.push(&1.into());
-    source.push(&(-2).into());
-    source.push(&2.into());
-    source.push(&3.into());
-    source.push(&4.into());
-    source.push(&5.into());
+    let seen = Array::new();
+    let push = Function::new_with_args("x", "this.push(x)").bind(&seen);
+    Pipeline::new()
+        .map(&Function::new_with_args("x", "return x * 2"))
+        .for_each(&numbers(1..=3).into(), &push, None)
+        .unwrap();
 
-    let result = pipeline.to_array(&source);
-    assert_eq!(result.length(), 2);
-    assert_eq!(result.get(0).as_f64(), Some(3.0));
-    assert_eq!(result.get(1).as_f64(), Some(4.0));
+    assert_eq!(as_f64s(&seen), vec![2.0, 4.0, 6.0]);
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_take_without_filter() {
-    use js_sys::{Array, Function};
+fn test_wasm_pipeline_for_each_abort_signal() {
+    use js_sys::{Array, Function, Reflect};
     use orlando_transducers::Pipeline;
 
-    let pipeline = Pipeline::new();
-    let map_fn = Function::new_with_args("x", "return x * 2");
-    let pipeline = pipeline.map(&map_fn).take(5);
+    let controller = Function::new_no_args("return new AbortController()")
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap();
+    let signal: web_sys::AbortSignal = Reflect::get(&controller, &"signal".into())
+        .unwrap()
+        .unchecked_into();
+
+    // Abort from inside the callback once 3 is seen
+    let seen = Array::new();
+    let context = Array::of2(&seen, &controller);
+    let callback = Function::new_with_args("x", "this[0].push(x); if (x === 3) this[1].abort();")
+        .bind(&context);
+
+    Pipeline::new()
+        .for_each(&numbers(1..=10).into(), &callback, Some(signal.clone()))
+        .unwrap();
+    assert_eq!(as_f64s(&seen), vec![1.0, 2.0, 3.0]);
+
+    // Aborted by a `tap` while a filter drops everything: the rest of the
+    // source is skipped even though no output reaches the callback
+    let controller = Function::new_no_args("return new AbortController()")
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap();
+    let upstream: web_sys::AbortSignal = Reflect::get(&controller, &"signal".into())
+        .unwrap()
+        .unchecked_into();
+    let tapped = Array::new();
+    let context = Array::of2(&tapped, &controller);
+    let tap = Function::new_with_args("x", "this[0].push(x); if (x === 3) this[1].abort();")
+        .bind(&context);
+    let nothing = Function::new_with_args("x", "return false");
+    Pipeline::new()
+        .tap(&tap)
+        .filter(&nothing)
+        .for_each(&numbers(1..=10).into(), &callback, Some(upstream))
+        .unwrap();
+    assert_eq!(as_f64s(&tapped), vec![1.0, 2.0, 3.0]);
+
+    // Already aborted: nothing runs
+    let untouched = Array::new();
+    let push = Function::new_with_args("x", "this.push(x)").bind(&untouched);
+    Pipeline::new()
+        .for_each(&numbers(1..=3).into(), &push, Some(signal))
+        .unwrap();
+    assert_eq!(untouched.length(), 0);
+}
 
-    let source = Array::new();
-    for i in 1..=10 {
-        source.push(&i.into());
-    }
+// ============================================================================
+// Pipeline Strict Mode
+// ============================================================================
 
-    let result = pipeline.to_array(&source);
-    assert_eq!(result.length(), 5);
-    assert_eq!(result.get(0).as_f64(), Some(2.0));
-    assert_eq!(result.get(1).as_f64(), Some(4.0));
-    assert_eq!(result.get(2).as_f64(), Some(6.0));
-    assert_eq!(result.get(3).as_f64(), Some(8.0));
-    assert_eq!(result.get(4).as_f64(), Some(10.0));
+/// Run `pipeline.toArray(source)` from JavaScript, returning the thrown value.
+fn to_array_error(
+    pipeline: orlando_transducers::Pipeline,
+    source: &wasm_bindgen::JsValue,
+) -> wasm_bindgen::JsValue {
+    js_sys::Function::new_with_args(
+        "p, src",
+        "try { p.toArray(src); return null; } catch (e) { return e; }",
+    )
+    .call2(&wasm_bindgen::JsValue::NULL, &pipeline.into(), source)
+    .unwrap()
 }
 
-// Comprehensive integration tests for stateful operations
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_takewhile_with_map() {
-    use js_sys::{Array, Function};
+fn test_wasm_pipeline_lenient_by_default() {
+    use js_sys::Function;
     use orlando_transducers::Pipeline;
 
-    let pipeline = Pipeline::new();
-    let map_fn = Function::new_with_args("x", "return x * 2");
-    let pred_fn = Function::new_with_args("x", "return x < 20");
-    let pipeline = pipeline.map(&map_fn).take_while(&pred_fn);
-
-    let source = Array::new();
-    for i in 1..=20 {
-        source.push(&i.into());
-    }
+    let boom = Function::new_with_args("x", "if (x === 2) throw new Error('boom'); return x");
+    let result = Pipeline::new()
+        .map(&boom)
+        .to_array(&numbers(1..=3).into())
+        .unwrap();
 
-    let result = pipeline.to_array(&source);
-    // Should take while x*2 < 20, so x < 10, meaning [2, 4, 6, 8, 10, 12, 14, 16, 18]
-    assert_eq!(result.length(), 9);
-    assert_eq!(result.get(0).as_f64(), Some(2.0));
-    assert_eq!(result.get(8).as_f64(), Some(18.0));
+    assert_eq!(result.length(), 3);
+    assert!(result.get(1).is_undefined());
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_dropwhile_with_filter() {
-    use js_sys::{Array, Function};
+fn test_wasm_pipeline_strict_throws_with_index() {
+    use js_sys::{Function, Reflect};
     use orlando_transducers::Pipeline;
 
-    let pipeline = Pipeline::new();
-    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
-    let pred_fn = Function::new_with_args("x", "return x < 10");
-    let pipeline = pipeline.filter(&filter_fn).drop_while(&pred_fn).take(3);
+    let boom = Function::new_with_args("x", "if (x === 3) throw new Error('boom'); return true");
+    let pipeline = Pipeline::new().strict().filter(&boom);
+    let error = to_array_error(pipeline, &numbers(1..=5).into());
 
-    let source = Array::new();
-    for i in 1..=20 {
-        source.push(&i.into());
-    }
+    assert!(error.is_instance_of::<js_sys::Error>());
+    assert_eq!(Reflect::get(&error, &"index".into()).unwrap(), 2);
+    let cause: js_sys::Error = Reflect::get(&error, &"cause".into())
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(cause.message(), "boom");
+}
 
-    let result = pipeline.to_array(&source);
-    // Even numbers: [2, 4, 6, 8, 10, 12, 14, 16, 18, 20]
-    // Drop while < 10: [10, 12, 14, 16, 18, 20]
-    // Take 3: [10, 12, 14]
-    assert_eq!(result.length(), 3);
-    assert_eq!(result.get(0).as_f64(), Some(10.0));
-    assert_eq!(result.get(1).as_f64(), Some(12.0));
-    assert_eq!(result.get(2).as_f64(), Some(14.0));
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_error_describes_operation() {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::{is_orlando_error, Pipeline};
+
+    let double = Function::new_with_args("x", "return x * 2");
+    let boom = Function::new_with_args("x", "if (x === 6) throw new Error('boom'); return x");
+    let pipeline = Pipeline::new()
+        .strict()
+        .take(10)
+        .map(&double)
+        .map(&boom)
+        .freeze();
+    let error = to_array_error(pipeline, &numbers(1..=5).into());
+
+    assert!(is_orlando_error(&error));
+    assert!(!is_orlando_error(&js_sys::Error::new("plain").into()));
+    let get = |key: &str| Reflect::get(&error, &key.into()).unwrap();
+    assert_eq!(get("name"), "OrlandoError");
+    assert_eq!(get("code"), "CALLBACK_THREW");
+    // `freeze` fuses the two maps into the operation after `take`
+    assert_eq!(get("operation"), 1);
+    assert_eq!(get("operationName"), "map");
+    assert_eq!(get("value"), 6);
+    assert_eq!(get("index"), 2);
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_complex_stateful_combination() {
+fn test_wasm_pipeline_strict_error_leaves_pipeline_usable() {
     use js_sys::{Array, Function};
     use orlando_transducers::Pipeline;
+    use wasm_bindgen::JsValue;
 
-    let pipeline = Pipeline::new();
-    let map_fn = Function::new_with_args("x", "return x + 1");
-    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
-    let pipeline = pipeline
-        .map(&map_fn) // [2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
-        .filter(&filter_fn) // [2, 4, 6, 8, 10]
-        .drop(1) // [4, 6, 8, 10]
-        .take(2); // [4, 6]
+    let boom = Function::new_with_args("x", "if (x === 2) throw new Error('boom'); return x");
+    let pipeline = Pipeline::new().strict().map(&boom);
+    // Called from JavaScript, so each call borrows the pipeline
+    let result = Function::new_with_args(
+        "p",
+        "try { p.toArray([1, 2]); } catch (e) {} return p.toArray([1, 3]);",
+    )
+    .call1(&JsValue::NULL, &pipeline.into())
+    .unwrap();
 
-    let source = Array::new();
-    for i in 1..=10 {
-        source.push(&i.into());
-    }
+    assert_eq!(as_f64s(&Array::from(&result)), vec![1.0, 3.0]);
+}
 
-    let result = pipeline.to_array(&source);
-    assert_eq!(result.length(), 2);
-    assert_eq!(result.get(0).as_f64(), Some(4.0));
-    assert_eq!(result.get(1).as_f64(), Some(6.0));
+/// Run `call`, JavaScript with a strict pipeline as `p` and a callback that
+/// throws when given 2 as `boom`, and check that it throws an `OrlandoError`
+/// from the terminal `name` while processing element 1.
+fn assert_strict_terminal_throws(call: &str, name: &str) {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::{is_orlando_error, Pipeline};
+    use wasm_bindgen::JsValue;
+
+    let boom = Function::new_with_args(
+        "...args",
+        "if (args.includes(2)) throw new Error('boom'); return args[args.length - 1];",
+    );
+    let error = Function::new_with_args(
+        "p, boom",
+        &format!("try {{ {call}; return null; }} catch (e) {{ return e; }}"),
+    )
+    .call2(&JsValue::NULL, &Pipeline::new().strict().into(), &boom)
+    .unwrap();
+
+    assert!(is_orlando_error(&error), "{call} did not throw");
+    let get = |key: &str| Reflect::get(&error, &key.into()).unwrap();
+    assert_eq!(get("operationName"), name);
+    assert_eq!(get("index"), 1);
+    let cause: js_sys::Error = get("cause").unchecked_into();
+    assert_eq!(cause.message(), "boom");
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_flatmap_with_take() {
-    use js_sys::{Array, Function};
-    use orlando_transducers::Pipeline;
+fn test_wasm_pipeline_strict_reduce_throws() {
+    assert_strict_terminal_throws("p.reduce([1, 2, 3], boom, 0)", "reduce");
+}
 
-    let pipeline = Pipeline::new();
-    let flatmap_fn = Function::new_with_args("x", "return [x, x + 1]");
-    let pipeline = pipeline.flat_map(&flatmap_fn).take(5);
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_for_each_throws() {
+    assert_strict_terminal_throws("p.forEach([1, 2, 3], boom)", "forEach");
+}
 
-    let source = Array::new();
-    for i in 1..=10 {
-        source.push(&i.into());
-    }
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_find_throws() {
+    assert_strict_terminal_throws("p.find([1, 2, 3], x => boom(x) > 2)", "find");
+}
 
-    let result = pipeline.to_array(&source);
-    // flatMap produces: [1, 2, 2, 3, 3, 4, ...]
-    // take(5): [1, 2, 2, 3, 3]
-    assert_eq!(result.length(), 5);
-    assert_eq!(result.get(0).as_f64(), Some(1.0));
-    assert_eq!(result.get(1).as_f64(), Some(2.0));
-    assert_eq!(result.get(2).as_f64(), Some(2.0));
-    assert_eq!(result.get(3).as_f64(), Some(3.0));
-    assert_eq!(result.get(4).as_f64(), Some(3.0));
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_every_throws() {
+    assert_strict_terminal_throws("p.every([1, 2, 3], boom)", "every");
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_multiple_takes() {
-    use js_sys::{Array, Function};
-    use orlando_transducers::Pipeline;
+fn test_wasm_pipeline_strict_some_throws() {
+    assert_strict_terminal_throws("p.some([1, 2, 3], x => boom(x) > 2)", "some");
+}
 
-    // This tests that take operations compose correctly
-    let pipeline = Pipeline::new();
-    let pipeline = pipeline.take(10).take(5);
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_group_by_throws() {
+    assert_strict_terminal_throws("p.groupBy([1, 2, 3], boom)", "groupBy");
+}
 
-    let source = Array::new();
-    for i in 1..=20 {
-        source.push(&i.into());
-    }
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_index_by_throws() {
+    assert_strict_terminal_throws("p.indexBy([1, 2, 3], boom)", "indexBy");
+}
 
-    let result = pipeline.to_array(&source);
-    // The inner take(10) limits to first 10, then take(5) limits to first 5
-    assert_eq!(result.length(), 5);
-    assert_eq!(result.get(0).as_f64(), Some(1.0));
-    assert_eq!(result.get(4).as_f64(), Some(5.0));
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_to_object_throws() {
+    assert_strict_terminal_throws("p.toObject([1, 2, 3], x => x, boom)", "toObject");
 }
 
 #[wasm_bindgen_test]
-fn test_wasm_pipeline_reduce_with_stateful_ops() {
+fn test_wasm_pipeline_strict_partition_throws() {
+    assert_strict_terminal_throws("p.partition([1, 2, 3], boom)", "partition");
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_span_throws() {
+    assert_strict_terminal_throws("p.span([1, 2, 3], boom)", "span");
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_strict_stops_processing() {
     use js_sys::{Array, Function};
     use orlando_transducers::Pipeline;
-    use wasm_bindgen::JsValue;
 
-    let pipeline = Pipeline::new();
-    let filter_fn = Function::new_with_args("x", "return x % 2 === 0");
-    let pipeline = pipeline.filter(&filter_fn).take(3);
+    let seen = Array::new();
+    let record = Function::new_with_args("x", "this.push(x)").bind(&seen);
+    let boom = Function::new_with_args("x", "if (x === 2) throw 'bad'; return x");
 
-    let source = Array::new();
-    for i in 1..=20 {
-        source.push(&i.into());
-    }
+    let pipeline = Pipeline::new().strict().map(&boom).tap(&record);
+    let error = to_array_error(pipeline, &numbers(1..=4).into());
 
-    let reducer = Function::new_with_args("acc, val", "return acc + val");
-    let result = pipeline.reduce(&source, &reducer, JsValue::from(0));
+    assert!(!error.is_null());
+    assert_eq!(as_f64s(&seen), vec![1.0]);
+}
 
-    // Even numbers [2, 4, 6], sum = 12
-    assert_eq!(result.as_f64(), Some(12.0));
+#[wasm_bindgen_test]
+async fn test_wasm_pipeline_strict_rejects_async() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let boom = Function::new_with_args("x", "throw new Error('nope')");
+    let outcome = JsFuture::from(
+        Pipeline::new()
+            .map(&boom)
+            .strict()
+            .to_array_async(numbers(1..=2).into()),
+    )
+    .await;
+    assert!(outcome.is_err());
 }
 
 // ============================================================================
@@ -808,19 +1962,172 @@ fn test_wasm_lens_law_put_put() {
 
     let name_lens = lens("name");
 
-    // Law 3: set(set(obj, v1), v2) = set(obj, v2)
-    let temp = name_lens.set(obj.as_ref(), "Bob".into());
-    let result1 = name_lens.set(&temp, "Charlie".into());
-    let result2 = name_lens.set(obj.as_ref(), "Charlie".into());
+    // Law 3: set(set(obj, v1), v2) = set(obj, v2)
+    let temp = name_lens.set(obj.as_ref(), "Bob".into());
+    let result1 = name_lens.set(&temp, "Charlie".into());
+    let result2 = name_lens.set(obj.as_ref(), "Charlie".into());
+
+    let result1_obj = result1.dyn_ref::<Object>().unwrap();
+    let result2_obj = result2.dyn_ref::<Object>().unwrap();
+
+    let name1 = js_sys::Reflect::get(result1_obj, &"name".into()).unwrap();
+    let name2 = js_sys::Reflect::get(result2_obj, &"name".into()).unwrap();
+
+    assert_eq!(name1.as_string(), name2.as_string());
+    assert_eq!(name1.as_string(), Some("Charlie".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_lens_from_functions() {
+    use js_sys::Function;
+    use orlando_transducers::lens_from;
+
+    let getter = Function::new_with_args("t", "return t.f / 2");
+    let setter = Function::new_with_args("t, v", "return { ...t, f: v * 2 }");
+    let half = lens_from(&getter, &setter);
+
+    let source = js_sys::JSON::parse(r#"{"f": 10, "x": 1}"#).unwrap();
+    assert_eq!(half.get(&source).as_f64(), Some(5.0));
+    let updated = half.set(&source, 7.into());
+    assert_eq!(
+        js_sys::JSON::stringify(&updated).unwrap(),
+        r#"{"f":14,"x":1}"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_lens_path_dotted_string() {
+    use orlando_transducers::lens_path;
+
+    let user = js_sys::JSON::parse(r#"{"address": {"city": "NYC"}}"#).unwrap();
+    let city = lens_path(&"address.city".into()).unwrap();
+    assert_eq!(city.get(&user).as_string(), Some("NYC".to_string()));
+    let moved = city.set(&user, "LA".into());
+    assert_eq!(
+        js_sys::JSON::stringify(&moved).unwrap(),
+        r#"{"address":{"city":"LA"}}"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_optional_compose() {
+    use orlando_transducers::{lens, optional};
+
+    let city = optional("address").compose(&optional("city"));
+    let with = js_sys::JSON::parse(r#"{"address": {"city": "NYC"}}"#).unwrap();
+    let without = js_sys::JSON::parse(r#"{"name": "Bob"}"#).unwrap();
+
+    assert_eq!(city.get(&with).as_string(), Some("NYC".to_string()));
+    assert!(city.get(&without).is_undefined());
+    assert_eq!(
+        js_sys::JSON::stringify(&city.set(&without, "LA".into())).unwrap(),
+        r#"{"name":"Bob"}"#
+    );
+
+    let nested = js_sys::JSON::parse(r#"{"user": {"address": {"city": "NYC"}}}"#).unwrap();
+    let user_city = lens("user").to_optional().compose(&city);
+    assert_eq!(
+        js_sys::JSON::stringify(&user_city.set(&nested, "LA".into())).unwrap(),
+        r#"{"user":{"address":{"city":"LA"}}}"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_optional_upsert() {
+    use js_sys::Function;
+    use orlando_transducers::{lens, optional};
+
+    let zip = optional("address")
+        .upsert(&Function::new_no_args("return {}"))
+        .compose(&lens("zip"));
+    let without = js_sys::JSON::parse(r#"{"name": "Bob"}"#).unwrap();
+    assert!(zip.get(&without).is_undefined());
+    assert_eq!(
+        js_sys::JSON::stringify(&zip.set(&without, "10001".into())).unwrap(),
+        r#"{"name":"Bob","address":{"zip":"10001"}}"#
+    );
+
+    let with = js_sys::JSON::parse(r#"{"address": {"zip": "1", "city": "LA"}}"#).unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&zip.set(&with, "2".into())).unwrap(),
+        r#"{"address":{"zip":"2","city":"LA"}}"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_traversal_compose_and_fold() {
+    use js_sys::Function;
+    use orlando_transducers::{each, lens};
+
+    let articles =
+        js_sys::JSON::parse(r#"[{"tags": ["Rust", "WASM"]}, {"tags": ["Optics"]}]"#).unwrap();
+    let all_tags = each()
+        .compose(&lens("tags").to_traversal())
+        .compose(&each());
+
+    assert_eq!(
+        js_sys::JSON::stringify(&all_tags.get_all(&articles)).unwrap(),
+        r#"["Rust","WASM","Optics"]"#
+    );
+    let lower = Function::new_with_args("t", "return t.toLowerCase()");
+    assert_eq!(
+        js_sys::JSON::stringify(&all_tags.over_all(&articles, &lower)).unwrap(),
+        r#"[{"tags":["rust","wasm"]},{"tags":["optics"]}]"#
+    );
+
+    let count = Function::new_with_args("n, t", "return n + t.length");
+    assert_eq!(all_tags.fold(&articles, &count, 0.into()), 14);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_iso_compose_and_to_lens() {
+    use js_sys::Function;
+    use orlando_transducers::{iso, lens};
+
+    let to_fahrenheit = iso(
+        &Function::new_with_args("c", "return c * 1.8 + 32"),
+        &Function::new_with_args("f", "return (f - 32) / 1.8"),
+    );
+    let to_kelvin_offset = iso(
+        &Function::new_with_args("f", "return f + 1000"),
+        &Function::new_with_args("k", "return k - 1000"),
+    );
+    let both = to_fahrenheit.compose(&to_kelvin_offset);
+    assert_eq!(both.to(&100.into()), 1212);
+    assert_eq!(both.from(1032.into()), 0);
+
+    let fahrenheit = lens("celsius").compose(&to_fahrenheit.to_lens());
+    let reading = js_sys::JSON::parse(r#"{"celsius": 100, "site": "A"}"#).unwrap();
+    assert_eq!(fahrenheit.get(&reading), 212);
+    assert_eq!(
+        js_sys::JSON::stringify(&fahrenheit.set(&reading, 32.into())).unwrap(),
+        r#"{"celsius":0,"site":"A"}"#
+    );
+}
 
-    let result1_obj = result1.dyn_ref::<Object>().unwrap();
-    let result2_obj = result2.dyn_ref::<Object>().unwrap();
+#[wasm_bindgen_test]
+fn test_wasm_prism_to_optional_compose() {
+    use js_sys::Function;
+    use orlando_transducers::{optional, prism};
 
-    let name1 = js_sys::Reflect::get(result1_obj, &"name".into()).unwrap();
-    let name2 = js_sys::Reflect::get(result2_obj, &"name".into()).unwrap();
+    let ok = prism(
+        &Function::new_with_args("r", "return r.tag === 'ok' ? r.value : undefined"),
+        &Function::new_with_args("value", "return { tag: 'ok', value }"),
+    );
+    let ok_value = optional("result").compose(&ok.to_optional());
+    let success = js_sys::JSON::parse(r#"{"result": {"tag": "ok", "value": 42}}"#).unwrap();
+    let failure = js_sys::JSON::parse(r#"{"result": {"tag": "err", "error": "x"}}"#).unwrap();
 
-    assert_eq!(name1.as_string(), name2.as_string());
-    assert_eq!(name1.as_string(), Some("Charlie".to_string()));
+    assert_eq!(ok_value.get(&success), 42);
+    assert!(ok_value.get(&failure).is_undefined());
+    assert_eq!(
+        js_sys::JSON::stringify(&ok_value.set(&success, 7.into())).unwrap(),
+        r#"{"result":{"tag":"ok","value":7}}"#
+    );
+    assert_eq!(
+        js_sys::JSON::stringify(&ok_value.set(&failure, 7.into())).unwrap(),
+        r#"{"result":{"tag":"err","error":"x"}}"#
+    );
 }
 
 // ============================================================================
@@ -853,7 +2160,7 @@ fn test_wasm_pipeline_project() {
     Reflect::set(&obj2, &"age".into(), &25.into()).unwrap();
     source.push(&obj2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
 
     // Check that projected objects have only id and name
@@ -886,7 +2193,7 @@ fn test_wasm_pipeline_project_missing_keys() {
     // email not present
     source.push(&obj);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1);
 
     let r = result.get(0);
@@ -915,7 +2222,7 @@ fn test_wasm_pipeline_compact() {
     source.push(&JsValue::from_str(""));
     source.push(&42.into());
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
     assert_eq!(result.get(0).as_f64(), Some(1.0));
     assert_eq!(result.get(1).as_string(), Some("hello".to_string()));
@@ -937,7 +2244,7 @@ fn test_wasm_pipeline_compact_preserves_objects() {
     source.push(&JsValue::NULL);
     source.push(&"text".into());
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     // First element should be the object
     assert_eq!(
@@ -968,7 +2275,7 @@ fn test_wasm_pipeline_flatten_depth_1() {
 
     source.push(&5.into()); // non-array passes through
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 5);
     assert_eq!(result.get(0).as_f64(), Some(1.0));
     assert_eq!(result.get(1).as_f64(), Some(2.0));
@@ -994,7 +2301,7 @@ fn test_wasm_pipeline_flatten_depth_2() {
     inner1.push(&nested);
     source.push(&inner1);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
     assert_eq!(result.get(0).as_f64(), Some(1.0));
     assert_eq!(result.get(1).as_f64(), Some(2.0));
@@ -1019,7 +2326,7 @@ fn test_wasm_pipeline_flatten_depth_1_preserves_nesting() {
     inner.push(&3.into());
     source.push(&inner);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     // flatten(1) should expand [[[1,2], 3]] to [[1,2], 3]
     assert_eq!(result.length(), 2);
     // First element should still be an array [1, 2]
@@ -1058,7 +2365,7 @@ fn test_wasm_pipeline_where_matches() {
     Reflect::set(&obj3, &"active".into(), &true.into()).unwrap();
     source.push(&obj3);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     assert_eq!(
         Reflect::get(&result.get(0), &"name".into())
@@ -1106,7 +2413,7 @@ fn test_wasm_pipeline_where_matches_multiple_keys() {
     Reflect::set(&obj3, &"role".into(), &"admin".into()).unwrap();
     source.push(&obj3);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1); // Only Alice matches both criteria
     assert_eq!(
         Reflect::get(&result.get(0), &"name".into())
@@ -1139,7 +2446,7 @@ fn test_wasm_pipeline_where_matches_numeric() {
     Reflect::set(&obj2, &"score".into(), &80.into()).unwrap();
     source.push(&obj2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1);
     assert_eq!(
         Reflect::get(&result.get(0), &"name".into())
@@ -1168,7 +2475,7 @@ fn test_wasm_pipeline_flatten_with_take() {
     inner2.push(&4.into());
     source.push(&inner2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 3);
     assert_eq!(result.get(0).as_f64(), Some(1.0));
     assert_eq!(result.get(1).as_f64(), Some(2.0));
@@ -1207,7 +2514,7 @@ fn test_wasm_pipeline_project_then_where_matches() {
     Reflect::set(&obj2, &"age".into(), &25.into()).unwrap();
     source.push(&obj2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1);
 
     let r = result.get(0);
@@ -1219,6 +2526,830 @@ fn test_wasm_pipeline_project_then_where_matches() {
     assert!(Reflect::get(&r, &"age".into()).unwrap().is_undefined());
 }
 
+fn rows() -> wasm_bindgen::JsValue {
+    js_sys::JSON::parse(
+        r#"[
+            {"name": "Ann", "age": 34, "role": "admin", "address": {"country": "NZ"}},
+            {"name": "Bo", "age": 17, "role": "user", "address": {"country": "NZ"}},
+            {"name": "Cy", "age": 52, "role": "editor", "address": {"country": "AU"}},
+            {"name": "Di", "age": 41, "role": "user"}
+        ]"#,
+    )
+    .unwrap()
+}
+
+fn names(result: &js_sys::Array) -> Vec<String> {
+    result
+        .iter()
+        .map(|row| {
+            js_sys::Reflect::get(&row, &"name".into())
+                .unwrap()
+                .as_string()
+                .unwrap()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_where_operators() {
+    use orlando_transducers::Pipeline;
+
+    let spec = js_sys::JSON::parse(
+        r#"{"age": {"$gte": 18, "$lt": 50}, "role": {"$in": ["admin", "user"]}}"#,
+    )
+    .unwrap();
+    let result = Pipeline::new()
+        .where_(&spec)
+        .unwrap()
+        .to_array(&rows())
+        .unwrap();
+    assert_eq!(names(&result), vec!["Ann", "Di"]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_where_paths_and_nesting() {
+    use orlando_transducers::Pipeline;
+
+    let dotted =
+        js_sys::JSON::parse(r#"{"address.country": "NZ", "role": {"$ne": "user"}}"#).unwrap();
+    let result = Pipeline::new()
+        .where_(&dotted)
+        .unwrap()
+        .to_array(&rows())
+        .unwrap();
+    assert_eq!(names(&result), vec!["Ann"]);
+
+    let nested = js_sys::JSON::parse(r#"{"address": {"country": {"$nin": ["NZ"]}}}"#).unwrap();
+    let result = Pipeline::new()
+        .where_(&nested)
+        .unwrap()
+        .to_array(&rows())
+        .unwrap();
+    assert_eq!(names(&result), vec!["Cy", "Di"]);
+
+    let missing = js_sys::JSON::parse(r#"{"address": {"$exists": false}}"#).unwrap();
+    let result = Pipeline::new()
+        .where_(&missing)
+        .unwrap()
+        .to_array(&rows())
+        .unwrap();
+    assert_eq!(names(&result), vec!["Di"]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_where_string_comparison() {
+    use orlando_transducers::Pipeline;
+
+    let spec = js_sys::JSON::parse(r#"{"name": {"$gt": "B", "$lte": "Cy"}}"#).unwrap();
+    let result = Pipeline::new()
+        .where_(&spec)
+        .unwrap()
+        .to_array(&rows())
+        .unwrap();
+    assert_eq!(names(&result), vec!["Bo", "Cy"]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_where_invalid_spec() {
+    use orlando_transducers::Pipeline;
+
+    for spec in [
+        r#"{"age": {"$near": 3}}"#,
+        r#"{"age": {"$in": 3}}"#,
+        r#"{"age": {"$gt": 3, "x": 1}}"#,
+    ] {
+        let spec = js_sys::JSON::parse(spec).unwrap();
+        let error = Pipeline::new().where_(&spec).err().unwrap();
+        assert_eq!(
+            js_sys::Reflect::get(&error, &"code".into()).unwrap(),
+            "INVALID_ARGUMENT"
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_filter_after_take_keeps_take() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let is_even = Function::new_with_args("x", "return x % 2 === 0");
+    let result = Pipeline::new()
+        .take(4)
+        .filter(&is_even)
+        .to_array(&numbers(1..=10))
+        .unwrap();
+    assert_eq!(as_f64s(&result), vec![2.0, 4.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_freeze_matches_unfrozen() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let double = Function::new_with_args("x", "return x * 2");
+    let small = Function::new_with_args("x", "return x > 12");
+    let pipeline = Pipeline::new()
+        .map(&double)
+        .reject(&small)
+        .compact()
+        .take(4);
+    let frozen = pipeline.freeze();
+
+    for source in [numbers(1..=10), numbers(-3..=3), numbers(5..=6)] {
+        assert_eq!(
+            as_f64s(&frozen.to_array(&source).unwrap()),
+            as_f64s(&pipeline.to_array(&source).unwrap())
+        );
+    }
+    assert_eq!(
+        as_f64s(&frozen.to_array(&numbers(1..=10)).unwrap()),
+        vec![2.0, 4.0, 6.0, 8.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_clone_is_independent() {
+    use orlando_transducers::Pipeline;
+
+    let base = Pipeline::new().take(3);
+    let copy = base.clone_pipeline();
+    let extended = copy.map_mul(10.0);
+
+    assert_eq!(
+        as_f64s(&base.to_array(&numbers(1..=5)).unwrap()),
+        vec![1.0, 2.0, 3.0]
+    );
+    assert_eq!(
+        as_f64s(&copy.to_array(&numbers(1..=5)).unwrap()),
+        vec![1.0, 2.0, 3.0]
+    );
+    assert_eq!(
+        as_f64s(&extended.to_array(&numbers(1..=5)).unwrap()),
+        vec![10.0, 20.0, 30.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_freeze_merges_counters_and_bounds() {
+    use orlando_transducers::Pipeline;
+
+    let frozen = Pipeline::new()
+        .drop(2)
+        .drop(3)
+        .take(6)
+        .take(4)
+        .filter_gt(6.0)
+        .filter_gt(7.0)
+        .freeze();
+    let plan = js_sys::JSON::stringify(&frozen.to_plan().unwrap()).unwrap();
+    assert_eq!(
+        String::from(plan),
+        r#"[{"op":"drop","n":5},{"op":"take","n":4},{"op":"filterGt","n":7}]"#
+    );
+    assert_eq!(
+        as_f64s(&frozen.to_array(&numbers(1..=20)).unwrap()),
+        vec![8.0, 9.0]
+    );
+
+    // A NaN bound keeps rejecting everything after fusion
+    let nan = Pipeline::new().filter_gt(f64::NAN).filter_gt(1.0).freeze();
+    assert_eq!(nan.to_array(&numbers(1..=5)).unwrap().length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_freeze_fuses_maps_and_filters() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let inc = Function::new_with_args("x", "return x + 1");
+    let square = Function::new_with_args("x", "return x * x");
+    let big = Function::new_with_args("x", "return x > 10");
+    let pipeline = Pipeline::new()
+        .filter(&odd)
+        .map(&inc)
+        .map(&square)
+        .filter(&big)
+        .reject(&odd);
+
+    let frozen = pipeline.freeze();
+    assert_eq!(
+        as_f64s(&frozen.to_array(&numbers(1..=9)).unwrap()),
+        as_f64s(&pipeline.to_array(&numbers(1..=9)).unwrap())
+    );
+    assert_eq!(
+        as_f64s(&frozen.to_array(&numbers(1..=9)).unwrap()),
+        vec![16.0, 36.0, 64.0, 100.0]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_freeze_moves_take_before_map() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let calls = js_sys::Array::new();
+    let record = Function::new_with_args("calls", "return x => { calls.push(x); return x * 10; }")
+        .call1(&wasm_bindgen::JsValue::NULL, &calls)
+        .unwrap()
+        .dyn_into::<Function>()
+        .unwrap();
+
+    let frozen = Pipeline::new().map(&record).take(3).freeze();
+    let result = frozen.to_array(&numbers(1..=10)).unwrap();
+    assert_eq!(as_f64s(&result), vec![10.0, 20.0, 30.0]);
+    assert_eq!(as_f64s(&calls), vec![1.0, 2.0, 3.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_explain() {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let inc = Function::new_with_args("x", "return x + 1");
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let pipeline = Pipeline::new().map(&inc).filter(&odd).take(3);
+
+    let description = pipeline.describe_pipeline().unwrap();
+    let pending = Reflect::get(&description, &"pendingFusions".into()).unwrap();
+    assert_eq!(pending.as_f64(), Some(1.0));
+    assert!(pipeline.explain().contains("Pending fusions: 1"));
+
+    assert_eq!(
+        pipeline.freeze().explain(),
+        "Pipeline (2 stages)\n  1. fused  (fused: map + filter)\n  2. take(3)  [stateful, may stop]\n\
+         Stateful: yes (take(3))\nEarly termination: possible (take(3))\n"
+    );
+    let description = pipeline.freeze().describe_pipeline().unwrap();
+    let may_stop = Reflect::get(&description, &"mayStop".into()).unwrap();
+    assert_eq!(may_stop.as_bool(), Some(true));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_debug_run() {
+    use js_sys::{Array, Function, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let get =
+        |target: &wasm_bindgen::JsValue, key: &str| Reflect::get(target, &key.into()).unwrap();
+    let positions = |entries: wasm_bindgen::JsValue| -> Vec<f64> {
+        Array::from(&entries)
+            .iter()
+            .map(|entry| {
+                Reflect::get(&entry, &"position".into())
+                    .unwrap()
+                    .as_f64()
+                    .unwrap()
+            })
+            .collect()
+    };
+
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let pipeline = Pipeline::new().map_mul(10.0).filter(&odd).take(2);
+    let run = pipeline.debug_run(&numbers(1..=5), 3).unwrap();
+
+    assert_eq!(as_f64s(&get(&run, "output").into()), Vec::<f64>::new());
+    let stages = Array::from(&get(&run, "stages"));
+    assert_eq!(stages.length(), 3);
+    assert_eq!(
+        get(&stages.get(0), "name").as_string().unwrap(),
+        "mapMul(10)"
+    );
+    // The filter saw the last three elements and dropped them all
+    assert_eq!(
+        positions(get(&stages.get(1), "inputs")),
+        vec![2.0, 3.0, 4.0]
+    );
+    assert!(positions(get(&stages.get(1), "outputs")).is_empty());
+    assert_eq!(positions(get(&run, "source")), vec![2.0, 3.0, 4.0]);
+
+    let run = Pipeline::new()
+        .filter(&odd)
+        .take(2)
+        .debug_run(&numbers(1..=5), 10)
+        .unwrap();
+    assert_eq!(as_f64s(&get(&run, "output").into()), vec![1.0, 3.0]);
+    let take = Array::from(&get(&run, "stages")).get(1);
+    assert_eq!(positions(get(&take, "outputs")), vec![0.0, 2.0]);
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_run_with_budget() {
+    use js_sys::{Function, Object, Reflect};
+    use orlando_transducers::Pipeline;
+
+    let budget = |key: &str, n: f64| {
+        let budget = Object::new();
+        Reflect::set(&budget, &key.into(), &n.into()).unwrap();
+        budget
+    };
+
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let pipeline = Pipeline::new().filter(&odd).chunk(2);
+    let mut run = pipeline
+        .run_with_budget(&numbers(1..=9), &budget("maxElements", 4.0))
+        .unwrap();
+    assert_eq!(run.output().length(), 1);
+    assert!(!run.done());
+    assert_eq!(run.consumed(), 4);
+
+    // Chunk keeps its buffer between slices, and the final partial chunk is
+    // flushed once the source ends
+    let mut outputs = vec![run.output().length()];
+    while !run.done() {
+        run = run.resume(&budget("maxElements", 4.0)).unwrap();
+        outputs.push(run.output().length());
+    }
+    assert_eq!(outputs, vec![1, 1, 1]);
+    assert_eq!(run.consumed(), 9);
+
+    // Time budgets still read one element per slice
+    let run = Pipeline::new()
+        .take(2)
+        .run_with_budget(&numbers(1..=5), &budget("maxMs", 0.0))
+        .unwrap();
+    assert_eq!(as_f64s(&run.output()), vec![1.0]);
+
+    // ...and so do empty element budgets
+    let mut run = Pipeline::new()
+        .run_with_budget(&numbers(1..=2), &budget("maxElements", 0.0))
+        .unwrap();
+    assert_eq!(as_f64s(&run.output()), vec![1.0]);
+    let run_next = run.resume(&budget("maxElements", 0.0)).unwrap();
+    assert_eq!(as_f64s(&run_next.output()), vec![2.0]);
+
+    let mut run = Pipeline::new()
+        .run_with_budget(&numbers(1..=5), &budget("maxElements", 1.0))
+        .unwrap();
+    run.resume(&budget("maxElements", 1.0)).unwrap();
+    assert!(run.resume(&budget("maxElements", 1.0)).is_err());
+    assert!(Pipeline::new()
+        .run_with_budget(&numbers(1..=5), &Object::new())
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_runner() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    // Chunk and take carry on across batches instead of starting over
+    let odd = Function::new_with_args("x", "return x % 2 === 1");
+    let mut runner = Pipeline::new().filter(&odd).chunk(2).take(2).runner();
+    assert_eq!(runner.feed(&numbers(1..=4)).unwrap().length(), 1);
+    assert_eq!(runner.feed(&numbers(5..=6)).unwrap().length(), 0);
+    assert!(!runner.done());
+    assert_eq!(runner.consumed(), 6);
+
+    // The second chunk stops the pipeline partway through the batch
+    let last = runner.feed(&numbers(7..=20)).unwrap();
+    assert_eq!(as_f64s(&last.get(0).into()), vec![5.0, 7.0]);
+    assert!(runner.done());
+    assert_eq!(runner.consumed(), 7);
+    assert_eq!(runner.feed(&numbers(1..=3)).unwrap().length(), 0);
+
+    let mut runner = Pipeline::new().chunk(2).runner();
+    runner.feed(&numbers(1..=3)).unwrap();
+    let rest = runner.finish().unwrap();
+    assert_eq!(as_f64s(&rest.get(0).into()), vec![3.0]);
+    assert!(runner.done());
+}
+
+fn pair_of_f64s(pair: &js_sys::Array) -> (Vec<f64>, Vec<f64>) {
+    (as_f64s(&pair.get(0).into()), as_f64s(&pair.get(1).into()))
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_partition() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let is_even = Function::new_with_args("x", "return x % 2 === 0");
+    let pair = Pipeline::new()
+        .map_mul(3.0)
+        .partition(&numbers(1..=5), &is_even)
+        .unwrap();
+    assert_eq!(pair_of_f64s(&pair), (vec![6.0, 12.0], vec![3.0, 9.0, 15.0]));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_split_at() {
+    use orlando_transducers::Pipeline;
+
+    let pair = Pipeline::new()
+        .drop(1)
+        .split_at(&numbers(1..=5), 2)
+        .unwrap();
+    assert_eq!(pair_of_f64s(&pair), (vec![2.0, 3.0], vec![4.0, 5.0]));
+
+    let pair = Pipeline::new().split_at(&numbers(1..=2), 5).unwrap();
+    assert_eq!(pair_of_f64s(&pair), (vec![1.0, 2.0], vec![]));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_span() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let small = Function::new_with_args("x", "return x < 3");
+    let pair = Pipeline::new()
+        .span(
+            &js_sys::Array::of4(&1.into(), &2.into(), &5.into(), &1.into()),
+            &small,
+        )
+        .unwrap();
+    assert_eq!(pair_of_f64s(&pair), (vec![1.0, 2.0], vec![5.0, 1.0]));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_zip_stops_at_shorter() {
+    use orlando_transducers::Pipeline;
+
+    let letters = js_sys::Array::of2(&"a".into(), &"b".into());
+    let result = Pipeline::new()
+        .map_mul(10.0)
+        .zip(&letters)
+        .to_array(&numbers(1..=3))
+        .unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&result).unwrap(),
+        r#"[[10,"a"],[20,"b"]]"#
+    );
+
+    let result = Pipeline::new()
+        .zip(&numbers(1..=5))
+        .to_array(&numbers(7..=8))
+        .unwrap();
+    assert_eq!(result.length(), 2);
+
+    // Serializable in plans
+    assert!(Pipeline::new().zip(&letters).to_plan().is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_zip_with() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let times = Function::new_with_args("x, w", "return x * w");
+    let weights = js_sys::Array::of3(&0.5.into(), &2.0.into(), &1.0.into());
+    let pipeline = Pipeline::new().zip_with(&weights, &times);
+    assert_eq!(pipeline.sum(&numbers(1..=3)).unwrap(), 0.5 + 4.0 + 3.0);
+    assert!(pipeline.to_plan().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_stats_terminals() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let is_even = Function::new_with_args("x", "return x % 2 === 0");
+    let evens = Pipeline::new().filter(&is_even);
+    let data = numbers(1..=10); // evens: 2, 4, 6, 8, 10
+
+    assert_eq!(evens.mean(&data).unwrap().as_f64(), Some(6.0));
+    assert_eq!(evens.median(&data).unwrap().as_f64(), Some(6.0));
+    assert_eq!(evens.quantile(&data, 0.25).unwrap().as_f64(), Some(4.0));
+    let sd = evens.std_dev(&data).unwrap().as_f64().unwrap();
+    assert!((sd - 10f64.sqrt()).abs() < 1e-12);
+
+    // Matches the free functions over the materialized output
+    let output = evens.to_array(&data).unwrap();
+    assert_eq!(
+        evens.std_dev(&data).unwrap().as_f64(),
+        orlando_transducers::pipeline::std_dev(&output).as_f64()
+    );
+    assert_eq!(
+        evens.quantile(&data, 0.9).unwrap().as_f64(),
+        orlando_transducers::pipeline::quantile(&output, 0.9).as_f64()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_stats_empty_and_typed() {
+    use orlando_transducers::Pipeline;
+
+    let empty = Pipeline::new().filter_gt(100.0);
+    assert!(empty.mean(&numbers(1..=5)).unwrap().is_undefined());
+    assert!(empty.median(&numbers(1..=5)).unwrap().is_undefined());
+    assert!(Pipeline::new()
+        .std_dev(&numbers(1..=1))
+        .unwrap()
+        .is_undefined());
+    assert!(Pipeline::new()
+        .quantile(&numbers(1..=5), 1.5)
+        .unwrap()
+        .is_undefined());
+
+    let samples = js_sys::Float64Array::from(&[3.0, 1.0, 2.0, 10.0][..]);
+    let pipeline = Pipeline::new().map_mul(2.0);
+    assert_eq!(pipeline.mean(&samples).unwrap().as_f64(), Some(8.0));
+    assert_eq!(pipeline.median(&samples).unwrap().as_f64(), Some(5.0));
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_index_by() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let role = Function::new_with_args("row", "return row.role");
+    let index = Pipeline::new().index_by(&rows(), &role).unwrap();
+    assert_eq!(index.size(), 3);
+    // Last element wins
+    let user = index.get(&"user".into());
+    assert_eq!(js_sys::Reflect::get(&user, &"name".into()).unwrap(), "Di");
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_pipeline_to_object() {
+    use js_sys::Function;
+    use orlando_transducers::Pipeline;
+
+    let name = Function::new_with_args("row", "return row.name");
+    let age = Function::new_with_args("row", "return row.age");
+    let ages = Pipeline::new()
+        .to_object(&rows(), &name, Some(age))
+        .unwrap();
+    assert_eq!(
+        js_sys::JSON::stringify(&ages).unwrap(),
+        r#"{"Ann":34,"Bo":17,"Cy":52,"Di":41}"#
+    );
+
+    let proto = Function::new_with_args("row", "return '__proto__'");
+    let object = Pipeline::new().to_object(&rows(), &proto, None).unwrap();
+    assert!(object.has_own_property(&"__proto__".into()));
+    assert_eq!(
+        js_sys::Object::get_prototype_of(&object),
+        js_sys::Object::get_prototype_of(&js_sys::Object::new())
+    );
+}
+
+// ============================================================================
+// Node.js Stream Adapters
+// ============================================================================
+
+/// A minimal stand-in for a Node `Readable` emitting `chunks` (then `error`,
+/// if given) while flowing.
+#[cfg(feature = "nodejs")]
+fn fake_readable(chunks: &js_sys::Array, error: &wasm_bindgen::JsValue) -> js_sys::Object {
+    js_sys::Function::new_with_args(
+        "chunks, error",
+        "const on = {};
+         const s = {
+           pauses: 0, paused: false, destroyed: false, ended: false,
+           on(e, f) { on[e] = f; if (e === 'data') queueMicrotask(() => s.flow()); return s; },
+           pause() { s.pauses++; s.paused = true; },
+           resume() { s.paused = false; queueMicrotask(() => s.flow()); },
+           destroy() { s.destroyed = true; },
+           flow() {
+             while (!s.paused && !s.destroyed && chunks.length) on.data(chunks.shift());
+             if (s.paused || s.destroyed || s.ended) return;
+             s.ended = true;
+             if (error) on.error(error); else on.end();
+           },
+         };
+         return s;",
+    )
+    .call2(&wasm_bindgen::JsValue::NULL, chunks, error)
+    .unwrap()
+    .unchecked_into()
+}
+
+#[cfg(feature = "nodejs")]
+#[wasm_bindgen_test]
+async fn test_wasm_from_node_readable() {
+    use js_sys::{Function, Reflect};
+    use orlando_transducers::{from_node_readable, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    let stream = fake_readable(&numbers(1..=5), &wasm_bindgen::JsValue::UNDEFINED);
+    let double = Function::new_with_args("x", "return x * 2");
+    let source = from_node_readable(stream.clone().into()).unwrap();
+    let result = JsFuture::from(Pipeline::new().map(&double).to_array_async(source.into()))
+        .await
+        .unwrap();
+
+    assert_eq!(as_f64s(&result.into()), vec![2.0, 4.0, 6.0, 8.0, 10.0]);
+    // Chunks arriving before they were asked for paused the stream
+    assert!(
+        Reflect::get(&stream, &"pauses".into())
+            .unwrap()
+            .as_f64()
+            .unwrap()
+            > 0.0
+    );
+
+    let stream = fake_readable(&numbers(1..=100), &wasm_bindgen::JsValue::UNDEFINED);
+    let source = from_node_readable(stream.clone().into()).unwrap();
+    let result = JsFuture::from(Pipeline::new().take(2).to_array_async(source.into()))
+        .await
+        .unwrap();
+    assert_eq!(as_f64s(&result.into()), vec![1.0, 2.0]);
+    assert_eq!(Reflect::get(&stream, &"destroyed".into()).unwrap(), true);
+}
+
+#[cfg(feature = "nodejs")]
+#[wasm_bindgen_test]
+async fn test_wasm_from_node_readable_rejects_on_error() {
+    use orlando_transducers::{from_node_readable, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    let stream = fake_readable(&numbers(1..=2), &js_sys::Error::new("disk on fire").into());
+    let source = from_node_readable(stream.into()).unwrap();
+    let outcome = JsFuture::from(Pipeline::new().to_array_async(source.into())).await;
+
+    let error: js_sys::Error = outcome.unwrap_err().unchecked_into();
+    assert_eq!(error.message(), "disk on fire");
+}
+
+#[cfg(feature = "nodejs")]
+#[wasm_bindgen_test]
+async fn test_wasm_to_node_writable_waits_for_drain() {
+    use js_sys::{Array, Function, Reflect};
+    use orlando_transducers::{to_node_writable, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    // Buffers two chunks, then asks the writer to wait for 'drain'
+    let stream = Function::new_no_args(
+        "const on = {};
+         const s = {
+           buffer: [], written: [], waits: 0,
+           on(e, f) { on[e] = f; },
+           once(e, f) { s.waits++; on[e] = f; },
+           write(chunk) {
+             s.buffer.push(chunk);
+             if (s.buffer.length < 2) return true;
+             setTimeout(() => { s.written.push(...s.buffer.splice(0)); on.drain(); });
+             return false;
+           },
+         };
+         return s;",
+    )
+    .call0(&wasm_bindgen::JsValue::NULL)
+    .unwrap();
+
+    let sink = to_node_writable(stream.clone()).unwrap();
+    JsFuture::from(Pipeline::new().for_each_async(numbers(1..=6).into(), sink))
+        .await
+        .unwrap();
+
+    let written: Array = Reflect::get(&stream, &"written".into()).unwrap().into();
+    assert_eq!(as_f64s(&written), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(Reflect::get(&stream, &"waits".into()).unwrap(), 3);
+}
+
+// ============================================================================
+// WebGPU
+// ============================================================================
+
+#[cfg(feature = "webgpu")]
+#[wasm_bindgen_test]
+async fn test_wasm_gpu_falls_back_for_small_and_unsupported_input() {
+    use js_sys::Float32Array;
+    use orlando_transducers::Pipeline;
+    use wasm_bindgen_futures::JsFuture;
+
+    let small = Float32Array::from(&[1.0f32, 2.0, 3.0, 4.0][..]);
+    let pipeline = Pipeline::new().map_mul(2.0).filter_gt(3.0);
+    let sum = JsFuture::from(pipeline.sum_gpu(small.clone().into()))
+        .await
+        .unwrap();
+    assert_eq!(sum.as_f64(), Some(18.0));
+
+    // Filters can't be mapped on the GPU, and a plain array can't be uploaded
+    let mapped = JsFuture::from(pipeline.to_float32_array_gpu(small.into()))
+        .await
+        .unwrap();
+    assert_eq!(Float32Array::from(mapped).to_vec(), vec![4.0, 6.0, 8.0]);
+    let mapped = JsFuture::from(pipeline.take(1).to_float32_array_gpu(numbers(1..=4).into()))
+        .await
+        .unwrap();
+    assert_eq!(Float32Array::from(mapped).to_vec(), vec![4.0]);
+}
+
+#[cfg(feature = "webgpu")]
+#[wasm_bindgen_test]
+async fn test_wasm_gpu_matches_simd_path_on_large_input() {
+    use js_sys::Float32Array;
+    use orlando_transducers::{gpu_available, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    // Runs on the GPU where the test browser has one, and on the SIMD path
+    // otherwise; both must agree with `sum` up to f32 rounding
+    let _ = JsFuture::from(gpu_available()).await.unwrap();
+    let values: Vec<f32> = (0..1 << 21).map(|i| (i % 1000) as f32 / 10.0).collect();
+    let input = Float32Array::from(values.as_slice());
+
+    let pipeline = Pipeline::new()
+        .scale_offset(0.5, -5.0)
+        .filter_range(0.0, 30.0);
+    let expected = pipeline.sum(&input).unwrap();
+    let sum = JsFuture::from(pipeline.sum_gpu(input.clone().into()))
+        .await
+        .unwrap()
+        .as_f64()
+        .unwrap();
+    assert!((sum - expected).abs() <= expected * 1e-4);
+
+    let scaled = JsFuture::from(
+        Pipeline::new()
+            .map_add(1.0)
+            .to_float32_array_gpu(input.into()),
+    )
+    .await
+    .unwrap();
+    let scaled = Float32Array::from(scaled);
+    assert_eq!(scaled.length(), 1 << 21);
+    assert_eq!(scaled.get_index(1234), values[1234] + 1.0);
+}
+
+// ============================================================================
+// JSON Lines Source
+// ============================================================================
+
+#[wasm_bindgen_test]
+async fn test_wasm_json_lines_across_chunk_boundaries() {
+    use js_sys::{Array, Uint8Array};
+    use orlando_transducers::{json_lines, lens, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    // "é" is split between chunks, as are the lines themselves
+    let text = "{\"id\":1,\"name\":\"Zoë\"}\r\n\n{\"id\":2,\"name\":\"Renée\"}\n{\"id\":3,\"name\":\"Bo\"}";
+    let bytes = text.as_bytes();
+    let chunks: Array = bytes
+        .chunks(5)
+        .map(|chunk| wasm_bindgen::JsValue::from(Uint8Array::from(chunk)))
+        .collect();
+
+    let source = json_lines(&chunks.into()).unwrap();
+    let names = JsFuture::from(
+        Pipeline::new()
+            .view_lens(&lens("name"))
+            .to_array_async(source.into()),
+    )
+    .await
+    .unwrap();
+
+    let names: Vec<String> = Array::from(&names)
+        .iter()
+        .map(|name| name.as_string().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Zoë", "Renée", "Bo"]);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_json_lines_stops_early() {
+    use js_sys::{Array, Function, Reflect};
+    use orlando_transducers::{json_lines, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    let lines = Function::new_no_args(
+        "const state = { pulled: 0, closed: false };
+         state.chunks = (async function* () {
+           try {
+             for (let i = 0; i < 1000; i++) { state.pulled++; yield `${i}\\n`; }
+           } finally { state.closed = true; }
+         })();
+         return state;",
+    )
+    .call0(&wasm_bindgen::JsValue::NULL)
+    .unwrap();
+
+    let chunks = Reflect::get(&lines, &"chunks".into()).unwrap();
+    let source = json_lines(&chunks).unwrap();
+    let result = JsFuture::from(Pipeline::new().take(3).to_array_async(source.into()))
+        .await
+        .unwrap();
+
+    assert_eq!(as_f64s(&Array::from(&result)), vec![0.0, 1.0, 2.0]);
+    assert!(
+        Reflect::get(&lines, &"pulled".into())
+            .unwrap()
+            .as_f64()
+            .unwrap()
+            < 10.0
+    );
+    assert_eq!(Reflect::get(&lines, &"closed".into()).unwrap(), true);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_json_lines_reports_bad_line() {
+    use js_sys::{Array, Reflect};
+    use orlando_transducers::{json_lines, Pipeline};
+    use wasm_bindgen_futures::JsFuture;
+
+    let chunks = Array::of1(&"{\"ok\":true}\n{oops}\n".into());
+    let source = json_lines(&chunks.into()).unwrap();
+    let error = JsFuture::from(Pipeline::new().to_array_async(source.into()))
+        .await
+        .unwrap_err();
+
+    let get = |key: &str| Reflect::get(&error, &key.into()).unwrap();
+    assert_eq!(get("code"), "PARSE_ERROR");
+    assert_eq!(get("value"), "{oops}");
+    assert!(get("cause").is_instance_of::<js_sys::SyntaxError>());
+}
+
 // ===== Phase 6c: Optics-Pipeline Integration Tests =====
 
 #[wasm_bindgen_test]
@@ -1234,7 +3365,7 @@ fn test_wasm_pipeline_view_lens() {
     source.push(&obj1);
     source.push(&obj2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     assert_eq!(result.get(0).as_string(), Some("Alice".to_string()));
     assert_eq!(result.get(1).as_string(), Some("Bob".to_string()));
@@ -1259,7 +3390,7 @@ fn test_wasm_pipeline_over_lens() {
     .unwrap();
     source.push(&item);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 1);
     let result_obj = result.get(0);
     let price = js_sys::Reflect::get(&result_obj, &"price".into()).unwrap();
@@ -1283,7 +3414,7 @@ fn test_wasm_pipeline_filter_lens() {
         source.push(&obj);
     }
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
 
     let name0 = js_sys::Reflect::get(&result.get(0), &"name".into()).unwrap();
@@ -1307,7 +3438,7 @@ fn test_wasm_pipeline_set_lens() {
     source.push(&item1);
     source.push(&item2);
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     let status0 = js_sys::Reflect::get(&result.get(0), &"status".into()).unwrap();
     let status1 = js_sys::Reflect::get(&result.get(1), &"status".into()).unwrap();
@@ -1331,7 +3462,7 @@ fn test_wasm_pipeline_lens_composition() {
         source.push(&obj);
     }
 
-    let result = pipeline.to_array(&source);
+    let result = pipeline.to_array(&source).unwrap();
     assert_eq!(result.length(), 2);
     assert_eq!(result.get(0).as_string(), Some("Alice".to_string()));
     assert_eq!(result.get(1).as_string(), Some("Charlie".to_string()));